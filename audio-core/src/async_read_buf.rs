@@ -0,0 +1,60 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use crate::{ExactSizeBuf, InterleavedBufMut, WriteBuf};
+
+/// Trait used to govern asynchronous reading of audio buffers from a source,
+/// such as an audio device.
+///
+/// This is the asynchronous counterpart to [ReadBuf][crate::ReadBuf]. Frames
+/// are read into a buffer implementing [WriteBuf], which is advanced as frames
+/// are read. This means that if polling returns [Poll::Pending] after having
+/// read some frames, polling again with the same buffer continues where it
+/// left off.
+pub trait AsyncReadBuf {
+    /// The type of a single sample produced by the source.
+    type Sample;
+
+    /// The error raised by the source.
+    type Error;
+
+    /// Attempt to fill all remaining frames in `buf` from the source.
+    ///
+    /// Returns [Poll::Ready] once the buffer no longer has any remaining
+    /// frames to write to, or an error occured.
+    ///
+    /// If the source has no frames available to read, this returns
+    /// [Poll::Pending] and arranges for the current task to be woken up once
+    /// it does.
+    fn poll_read_buf<B>(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: B,
+    ) -> Poll<Result<(), Self::Error>>
+    where
+        B: WriteBuf
+            + ExactSizeBuf<Sample = Self::Sample>
+            + InterleavedBufMut<Sample = Self::Sample>;
+}
+
+impl<R> AsyncReadBuf for &mut R
+where
+    R: ?Sized + Unpin + AsyncReadBuf,
+{
+    type Sample = R::Sample;
+    type Error = R::Error;
+
+    #[inline]
+    fn poll_read_buf<B>(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: B,
+    ) -> Poll<Result<(), Self::Error>>
+    where
+        B: WriteBuf
+            + ExactSizeBuf<Sample = Self::Sample>
+            + InterleavedBufMut<Sample = Self::Sample>,
+    {
+        Pin::new(&mut **self).poll_read_buf(cx, buf)
+    }
+}
@@ -0,0 +1,56 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use crate::{ExactSizeBuf, InterleavedBuf, ReadBuf};
+
+/// Trait used to govern asynchronous writing of audio buffers into a sink,
+/// such as an audio device.
+///
+/// This is the asynchronous counterpart to [WriteBuf][crate::WriteBuf]. Frames
+/// are consumed from a buffer implementing [ReadBuf], which is advanced as
+/// frames are written. This means that if polling returns [Poll::Pending]
+/// after having written some frames, polling again with the same buffer
+/// continues where it left off.
+pub trait AsyncWriteBuf {
+    /// The type of a single sample accepted by the sink.
+    type Sample;
+
+    /// The error raised by the sink.
+    type Error;
+
+    /// Attempt to write all remaining frames in `buf` to the sink.
+    ///
+    /// Returns [Poll::Ready] once the buffer no longer has any remaining
+    /// frames, or an error occured.
+    ///
+    /// If the sink is not ready to accept more frames, this returns
+    /// [Poll::Pending] and arranges for the current task to be woken up once
+    /// it is.
+    fn poll_write_buf<B>(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: B,
+    ) -> Poll<Result<(), Self::Error>>
+    where
+        B: ReadBuf + ExactSizeBuf<Sample = Self::Sample> + InterleavedBuf<Sample = Self::Sample>;
+}
+
+impl<W> AsyncWriteBuf for &mut W
+where
+    W: ?Sized + Unpin + AsyncWriteBuf,
+{
+    type Sample = W::Sample;
+    type Error = W::Error;
+
+    #[inline]
+    fn poll_write_buf<B>(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: B,
+    ) -> Poll<Result<(), Self::Error>>
+    where
+        B: ReadBuf + ExactSizeBuf<Sample = Self::Sample> + InterleavedBuf<Sample = Self::Sample>,
+    {
+        Pin::new(&mut **self).poll_write_buf(cx, buf)
+    }
+}
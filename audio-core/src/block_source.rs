@@ -0,0 +1,77 @@
+use crate::BufMut;
+
+/// Trait used to govern block-based pull rendering of audio.
+///
+/// A block source is asked to render audio into a buffer provided by the
+/// consumer, such as a device playback loop. This allows generators, mixers,
+/// and decoders to feed devices in the same manner.
+///
+/// # Examples
+///
+/// ```
+/// use audio::{BlockSource, BufMut, ChannelMut};
+///
+/// struct Counter(u32);
+///
+/// impl BlockSource for Counter {
+///     type Sample = u32;
+///
+///     fn render<B>(&mut self, out: &mut B) -> usize
+///     where
+///         B: BufMut<Sample = u32>,
+///     {
+///         let mut frames = 0;
+///
+///         for mut channel in out.iter_channels_mut() {
+///             frames = 0;
+///
+///             for (n, s) in channel.iter_mut().enumerate() {
+///                 *s = self.0 + n as u32;
+///                 frames += 1;
+///             }
+///         }
+///
+///         self.0 += frames as u32;
+///         frames
+///     }
+/// }
+///
+/// let mut source = Counter(0);
+/// let mut buf = audio::sequential![[0u32; 4]; 2];
+///
+/// assert_eq!(source.render(&mut buf), 4);
+/// assert_eq!(buf.as_slice(), &[0, 1, 2, 3, 0, 1, 2, 3]);
+///
+/// assert_eq!(source.render(&mut buf), 4);
+/// assert_eq!(buf.as_slice(), &[4, 5, 6, 7, 4, 5, 6, 7]);
+/// ```
+pub trait BlockSource {
+    /// The type of a single sample rendered by the source.
+    type Sample;
+
+    /// Render audio into `out`, returning the number of frames that were
+    /// rendered.
+    ///
+    /// Rendered frames are written from the start of each channel. Returning
+    /// fewer frames than are available in `out` means that only those frames
+    /// should be consumed, while returning `0` indicates that the source is
+    /// exhausted.
+    fn render<B>(&mut self, out: &mut B) -> usize
+    where
+        B: BufMut<Sample = Self::Sample>;
+}
+
+impl<S> BlockSource for &mut S
+where
+    S: ?Sized + BlockSource,
+{
+    type Sample = S::Sample;
+
+    #[inline]
+    fn render<B>(&mut self, out: &mut B) -> usize
+    where
+        B: BufMut<Sample = Self::Sample>,
+    {
+        (**self).render(out)
+    }
+}
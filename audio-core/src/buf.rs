@@ -1,6 +1,6 @@
 //! Trait for dealing with abstract channel buffers.
 
-use crate::Channel;
+use crate::{Channel, LayoutHint};
 
 #[macro_use]
 mod macros;
@@ -116,6 +116,29 @@ pub trait Buf {
     /// ```
     fn channels(&self) -> usize;
 
+    /// A hint of how the samples of the buffer are laid out in memory.
+    ///
+    /// This defaults to [LayoutHint::Unknown].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio::{Buf, LayoutHint};
+    ///
+    /// fn test(buf: impl Buf<Sample = i16>, expected: LayoutHint) {
+    ///     assert_eq!(buf.layout_hint(), expected);
+    /// }
+    ///
+    /// test(audio::interleaved![[1, 2, 3, 4]; 2], LayoutHint::Interleaved);
+    /// test(audio::wrap::interleaved(&[1, 5, 2, 6, 3, 7, 4, 8], 2), LayoutHint::Interleaved);
+    /// test(audio::wrap::sequential(&[1, 2, 3, 4, 5, 6, 7, 8], 2), LayoutHint::Sequential);
+    /// test(audio::wrap::dynamic(vec![vec![1, 2, 3, 4], vec![5, 6, 7, 8]]), LayoutHint::PerChannel);
+    /// ```
+    #[inline]
+    fn layout_hint(&self) -> LayoutHint {
+        LayoutHint::Unknown
+    }
+
     /// Return a handler to the buffer associated with the channel.
     ///
     /// Note that we don't access the buffer for the underlying channel directly
@@ -310,6 +333,11 @@ where
         (**self).channels()
     }
 
+    #[inline]
+    fn layout_hint(&self) -> LayoutHint {
+        (**self).layout_hint()
+    }
+
     #[inline]
     fn get_channel(&self, channel: usize) -> Option<Self::Channel<'_>> {
         (**self).get_channel(channel)
@@ -347,6 +375,11 @@ where
         (**self).channels()
     }
 
+    #[inline]
+    fn layout_hint(&self) -> LayoutHint {
+        (**self).layout_hint()
+    }
+
     #[inline]
     fn get_channel(&self, channel: usize) -> Option<Self::Channel<'_>> {
         (**self).get_channel(channel)
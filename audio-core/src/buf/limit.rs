@@ -1,4 +1,4 @@
-use crate::{Buf, BufMut, Channel, ChannelMut, ExactSizeBuf, ReadBuf};
+use crate::{Buf, BufMut, Channel, ChannelMut, ExactSizeBuf, LayoutHint, ReadBuf, WriteBuf};
 
 /// A buffer that has been limited.
 ///
@@ -40,6 +40,10 @@ where
         self.buf.channels()
     }
 
+    fn layout_hint(&self) -> LayoutHint {
+        self.buf.layout_hint()
+    }
+
     fn get_channel(&self, channel: usize) -> Option<Self::Channel<'_>> {
         Some(self.buf.get_channel(channel)?.limit(self.limit))
     }
@@ -119,4 +123,31 @@ where
     }
 }
 
+/// [Limit] adjusts the implementation of [WriteBuf].
+///
+/// ```
+/// use audio::{Buf, WriteBuf};
+/// use audio::io;
+///
+/// let mut buf = io::Write::new(audio::interleaved![[0; 4]; 2]);
+///
+/// assert_eq!((&mut buf).limit(2).remaining_mut(), 2);
+/// assert_eq!((&mut buf).limit(5).remaining_mut(), 4);
+///
+/// (&mut buf).limit(2).advance_mut(3);
+/// assert_eq!(buf.remaining_mut(), 2);
+/// ```
+impl<B> WriteBuf for Limit<B>
+where
+    B: WriteBuf,
+{
+    fn remaining_mut(&self) -> usize {
+        usize::min(self.buf.remaining_mut(), self.limit)
+    }
+
+    fn advance_mut(&mut self, n: usize) {
+        self.buf.advance_mut(usize::min(n, self.limit));
+    }
+}
+
 iterators!(limit: usize => self.limit(limit));
@@ -1,4 +1,4 @@
-use crate::{Buf, BufMut, Channel, ChannelMut, ExactSizeBuf, ReadBuf};
+use crate::{Buf, BufMut, Channel, ChannelMut, ExactSizeBuf, LayoutHint, ReadBuf, WriteBuf};
 
 /// A buffer where a number of frames have been skipped over.
 ///
@@ -56,6 +56,10 @@ where
         self.buf.channels()
     }
 
+    fn layout_hint(&self) -> LayoutHint {
+        self.buf.layout_hint()
+    }
+
     fn get_channel(&self, channel: usize) -> Option<Self::Channel<'_>> {
         Some(self.buf.get_channel(channel)?.skip(self.n))
     }
@@ -134,4 +138,31 @@ where
     }
 }
 
+/// [Skip] adjusts the implementation of [WriteBuf].
+///
+/// ```
+/// use audio::{Buf, WriteBuf};
+/// use audio::io;
+///
+/// let mut buf = io::Write::new(audio::interleaved![[0; 4]; 2]);
+///
+/// assert_eq!((&mut buf).skip(1).remaining_mut(), 3);
+/// assert_eq!((&mut buf).skip(5).remaining_mut(), 0);
+///
+/// (&mut buf).skip(1).advance_mut(1);
+/// assert_eq!(buf.remaining_mut(), 2);
+/// ```
+impl<B> WriteBuf for Skip<B>
+where
+    B: WriteBuf,
+{
+    fn remaining_mut(&self) -> usize {
+        self.buf.remaining_mut().saturating_sub(self.n)
+    }
+
+    fn advance_mut(&mut self, n: usize) {
+        self.buf.advance_mut(self.n.saturating_add(n));
+    }
+}
+
 iterators!(n: usize => self.skip(n));
@@ -1,4 +1,4 @@
-use crate::{Buf, BufMut, Channel, ChannelMut, ExactSizeBuf, ReadBuf};
+use crate::{Buf, BufMut, Channel, ChannelMut, ExactSizeBuf, LayoutHint, ReadBuf, WriteBuf};
 
 /// The tail of a buffer.
 ///
@@ -40,6 +40,10 @@ where
         self.buf.channels()
     }
 
+    fn layout_hint(&self) -> LayoutHint {
+        self.buf.layout_hint()
+    }
+
     fn get_channel(&self, channel: usize) -> Option<Self::Channel<'_>> {
         Some(self.buf.get_channel(channel)?.tail(self.n))
     }
@@ -124,4 +128,37 @@ where
     }
 }
 
+/// [Tail] adjusts the implementation of [WriteBuf].
+///
+/// ```
+/// use audio::{Buf, WriteBuf};
+/// use audio::io;
+///
+/// let mut buf = io::Write::new(audio::interleaved![[0; 4]; 2]);
+///
+/// assert_eq!((&mut buf).tail(1).remaining_mut(), 1);
+/// assert_eq!((&mut buf).tail(5).remaining_mut(), 4);
+///
+/// (&mut buf).tail(2).advance_mut(1);
+/// assert_eq!(buf.remaining_mut(), 1);
+/// ```
+impl<B> WriteBuf for Tail<B>
+where
+    B: WriteBuf,
+{
+    fn remaining_mut(&self) -> usize {
+        usize::min(self.buf.remaining_mut(), self.n)
+    }
+
+    fn advance_mut(&mut self, n: usize) {
+        let n = self
+            .buf
+            .remaining_mut()
+            .saturating_sub(self.n)
+            .saturating_add(n);
+
+        self.buf.advance_mut(n);
+    }
+}
+
 iterators!(n: usize => self.tail(n));
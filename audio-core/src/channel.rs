@@ -112,6 +112,8 @@ pub trait Channel {
     /// Try to access the current channel as a linear buffer.
     ///
     /// This is available because it could permit for some optimizations.
+    /// Returns `None` if the channel is not stored contiguously in memory, such
+    /// as the channels of an interleaved buffer with more than one channel.
     ///
     /// # Examples
     ///
@@ -125,6 +127,7 @@ pub trait Channel {
     /// test(&audio::dynamic![[1.0; 16]; 2], Some(&[1.0; 16]));
     /// test(&audio::sequential![[1.0; 16]; 2], Some(&[1.0; 16]));
     /// test(&audio::interleaved![[1.0; 16]; 2], None);
+    /// test(&audio::interleaved![[1.0; 16]; 1], Some(&[1.0; 16]));
     /// ```
     fn try_as_linear(&self) -> Option<&[Self::Sample]>;
 
@@ -0,0 +1,193 @@
+use core::fmt;
+
+use crate::Buf;
+
+/// The speaker position a channel is intended for.
+///
+/// Apart from [ChannelPosition::Mono], the declaration order of the speaker
+/// positions matches the order in which they are conventionally interleaved.
+///
+/// # Examples
+///
+/// ```
+/// use audio::ChannelPosition;
+///
+/// assert_eq!(ChannelPosition::default_layout(2), Some(&[ChannelPosition::FrontLeft, ChannelPosition::FrontRight][..]));
+/// assert_eq!(ChannelPosition::Lfe.to_string(), "LFE");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum ChannelPosition {
+    /// A single channel with no position.
+    Mono,
+    /// Front left.
+    FrontLeft,
+    /// Front right.
+    FrontRight,
+    /// Front center.
+    FrontCenter,
+    /// Low frequency effects.
+    Lfe,
+    /// Rear left.
+    RearLeft,
+    /// Rear right.
+    RearRight,
+    /// Front left of center.
+    FrontLeftOfCenter,
+    /// Front right of center.
+    FrontRightOfCenter,
+    /// Rear center.
+    RearCenter,
+    /// Side left.
+    SideLeft,
+    /// Side right.
+    SideRight,
+    /// Top center.
+    TopCenter,
+    /// Top front left.
+    TopFrontLeft,
+    /// Top front center.
+    TopFrontCenter,
+    /// Top front right.
+    TopFrontRight,
+    /// Top rear left.
+    TopRearLeft,
+    /// Top rear center.
+    TopRearCenter,
+    /// Top rear right.
+    TopRearRight,
+}
+
+impl ChannelPosition {
+    /// Get the conventional channel layout for the given number of channels,
+    /// if there is one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio::ChannelPosition;
+    ///
+    /// assert_eq!(ChannelPosition::default_layout(1), Some(&[ChannelPosition::Mono][..]));
+    /// assert_eq!(ChannelPosition::default_layout(6).map(|l| l[3]), Some(ChannelPosition::Lfe));
+    /// assert_eq!(ChannelPosition::default_layout(7), None);
+    /// ```
+    pub const fn default_layout(channels: usize) -> Option<&'static [ChannelPosition]> {
+        use ChannelPosition::*;
+
+        Some(match channels {
+            1 => &[Mono],
+            2 => &[FrontLeft, FrontRight],
+            3 => &[FrontLeft, FrontRight, Lfe],
+            4 => &[FrontLeft, FrontRight, RearLeft, RearRight],
+            5 => &[FrontLeft, FrontRight, FrontCenter, RearLeft, RearRight],
+            6 => &[FrontLeft, FrontRight, FrontCenter, Lfe, RearLeft, RearRight],
+            8 => &[
+                FrontLeft,
+                FrontRight,
+                FrontCenter,
+                Lfe,
+                RearLeft,
+                RearRight,
+                SideLeft,
+                SideRight,
+            ],
+            _ => return None,
+        })
+    }
+}
+
+impl fmt::Display for ChannelPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Mono => "MONO",
+            Self::FrontLeft => "FL",
+            Self::FrontRight => "FR",
+            Self::FrontCenter => "FC",
+            Self::Lfe => "LFE",
+            Self::RearLeft => "RL",
+            Self::RearRight => "RR",
+            Self::FrontLeftOfCenter => "FLC",
+            Self::FrontRightOfCenter => "FRC",
+            Self::RearCenter => "RC",
+            Self::SideLeft => "SL",
+            Self::SideRight => "SR",
+            Self::TopCenter => "TC",
+            Self::TopFrontLeft => "TFL",
+            Self::TopFrontCenter => "TFC",
+            Self::TopFrontRight => "TFR",
+            Self::TopRearLeft => "TRL",
+            Self::TopRearCenter => "TRC",
+            Self::TopRearRight => "TRR",
+        };
+
+        f.write_str(name)
+    }
+}
+
+/// A buffer which can report the speaker position of each of its channels.
+///
+/// # Examples
+///
+/// ```
+/// use audio::{Buf, ChannelPosition, ChannelPositions};
+///
+/// struct Stereo(audio::buf::Sequential<f32>);
+///
+/// impl Buf for Stereo {
+///     type Sample = f32;
+///     type Channel<'this> = <audio::buf::Sequential<f32> as Buf>::Channel<'this>;
+///     type IterChannels<'this> = <audio::buf::Sequential<f32> as Buf>::IterChannels<'this>;
+///
+///     fn frames_hint(&self) -> Option<usize> {
+///         self.0.frames_hint()
+///     }
+///
+///     fn channels(&self) -> usize {
+///         self.0.channels()
+///     }
+///
+///     fn get_channel(&self, channel: usize) -> Option<Self::Channel<'_>> {
+///         self.0.get_channel(channel)
+///     }
+///
+///     fn iter_channels(&self) -> Self::IterChannels<'_> {
+///         self.0.iter_channels()
+///     }
+/// }
+///
+/// impl ChannelPositions for Stereo {
+///     fn channel_position(&self, channel: usize) -> Option<ChannelPosition> {
+///         ChannelPosition::default_layout(2)?.get(channel).copied()
+///     }
+/// }
+///
+/// let buf = Stereo(audio::sequential![[0.0; 4]; 2]);
+/// assert_eq!(buf.channel_position(1), Some(ChannelPosition::FrontRight));
+/// assert_eq!(buf.channel_position(2), None);
+/// ```
+pub trait ChannelPositions: Buf {
+    /// Get the speaker position of the given channel.
+    ///
+    /// Returns `None` if the channel doesn't exist or has no known position.
+    fn channel_position(&self, channel: usize) -> Option<ChannelPosition>;
+}
+
+impl<B> ChannelPositions for &B
+where
+    B: ?Sized + ChannelPositions,
+{
+    #[inline]
+    fn channel_position(&self, channel: usize) -> Option<ChannelPosition> {
+        (**self).channel_position(channel)
+    }
+}
+
+impl<B> ChannelPositions for &mut B
+where
+    B: ?Sized + ChannelPositions,
+{
+    #[inline]
+    fn channel_position(&self, channel: usize) -> Option<ChannelPosition> {
+        (**self).channel_position(channel)
+    }
+}
@@ -0,0 +1,82 @@
+use crate::{Buf, BufMut, Channel, ChannelMut, InterleavedBuf, InterleavedBufMut, Translate};
+
+/// Copy and translate the content of the buffer `src` into the buffer `dst`.
+///
+/// Samples are translated through the [Translate] trait, which for buffers of
+/// the same sample type is an identity translation. Only the common count of
+/// channels and frames will be copied.
+///
+/// Channels which can be accessed as linear slices through
+/// [Channel::try_as_linear] and [ChannelMut::try_as_linear_mut] are translated
+/// slice-to-slice with [Translate::translate_slice], which copies them directly
+/// if the sample types are the same. Other channels are copied through their
+/// iterators.
+///
+/// # Examples
+///
+/// ```
+/// let src = audio::interleaved![[i16::MIN, 0], [0, i16::MIN]];
+/// let mut dst = audio::sequential![[1.0f32; 2]; 2];
+///
+/// audio::copy(&mut dst, &src);
+///
+/// assert_eq!(dst.as_slice(), &[-1.0, 0.0, 0.0, -1.0]);
+/// ```
+///
+/// Only the common topology is copied:
+///
+/// ```
+/// let src = audio::dynamic![[1i16; 4]; 3];
+/// let mut dst = audio::sequential![[0i16; 2]; 2];
+///
+/// audio::copy(&mut dst, &src);
+///
+/// assert_eq!(dst.as_slice(), &[1, 1, 1, 1]);
+/// ```
+pub fn copy<T, U>(dst: &mut impl BufMut<Sample = T>, src: &impl Buf<Sample = U>)
+where
+    T: Translate<U>,
+    U: Copy,
+{
+    for (mut to, from) in dst.iter_channels_mut().zip(src.iter_channels()) {
+        match (to.try_as_linear_mut(), from.try_as_linear()) {
+            (Some(to), Some(from)) => T::translate_slice(to, from),
+            _ => {
+                for (t, f) in to.iter_mut().zip(from.iter()) {
+                    *t = T::translate(f);
+                }
+            }
+        }
+    }
+}
+
+/// Copy and translate the content of the interleaved buffer `src` into the
+/// interleaved buffer `dst`.
+///
+/// This behaves like [copy], except that if both buffers have the same number
+/// of channels the underlying interleaved slices are copied directly.
+///
+/// # Examples
+///
+/// ```
+/// let src = audio::interleaved![[i16::MIN, 0], [0, i16::MIN]];
+/// let mut dst = audio::interleaved![[1.0f32; 2]; 2];
+///
+/// audio::copy_interleaved(&mut dst, &src);
+///
+/// assert_eq!(dst.as_slice(), &[-1.0, 0.0, 0.0, -1.0]);
+/// ```
+pub fn copy_interleaved<T, U>(
+    dst: &mut (impl BufMut<Sample = T> + InterleavedBufMut<Sample = T>),
+    src: &(impl Buf<Sample = U> + InterleavedBuf<Sample = U>),
+) where
+    T: Translate<U>,
+    U: Copy,
+{
+    if dst.channels() != src.channels() {
+        copy(dst, src);
+        return;
+    }
+
+    T::translate_slice(dst.as_interleaved_mut(), src.as_interleaved());
+}
@@ -1,4 +1,4 @@
-use crate::Buf;
+use crate::{Buf, Topology};
 
 /// Trait used to describe a buffer that knows exactly how many frames it has
 /// regardless of if it's sized or not.
@@ -37,6 +37,28 @@ pub trait ExactSizeBuf: Buf {
     /// test(audio::wrap::sequential([0i16; 16], 4));
     /// ```
     fn frames(&self) -> usize;
+
+    /// The [Topology] of the buffer, which is its number of channels and
+    /// frames.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio::{ExactSizeBuf, Topology};
+    ///
+    /// fn test<T>(buf: T) where T: ExactSizeBuf {
+    ///     assert_eq!(buf.topology(), Topology::new(4, 4));
+    /// }
+    ///
+    /// test(audio::interleaved![[0i16; 4]; 4]);
+    /// test(audio::sequential![[0i16; 4]; 4]);
+    /// test(audio::dynamic![[0i16; 4]; 4]);
+    /// test(audio::wrap::interleaved([0i16; 16], 4));
+    /// test(audio::wrap::sequential([0i16; 16], 4));
+    /// ```
+    fn topology(&self) -> Topology {
+        Topology::new(self.channels(), self.frames())
+    }
 }
 
 impl<B> ExactSizeBuf for &B
@@ -1,6 +1,9 @@
 //! A frame buffer as created through
 //! [UniformBuf::get_frame][crate::UniformBuf::get_frame].
 
+use core::iter;
+use core::slice;
+
 /// The buffer of a single frame.
 pub trait Frame {
     /// The sample of a channel.
@@ -33,3 +36,90 @@ pub trait Frame {
     /// Construct an iterator over the frame.
     fn iter(&self) -> Self::Iter<'_>;
 }
+
+impl<T> Frame for &[T]
+where
+    T: Copy,
+{
+    type Sample = T;
+
+    type Frame<'this>
+        = &'this [T]
+    where
+        Self: 'this;
+
+    type Iter<'this>
+        = iter::Copied<slice::Iter<'this, T>>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn as_frame(&self) -> Self::Frame<'_> {
+        self
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        <[T]>::len(self)
+    }
+
+    #[inline]
+    fn get(&self, channel: usize) -> Option<Self::Sample> {
+        <[T]>::get(self, channel).copied()
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        <[T]>::iter(self).copied()
+    }
+}
+
+/// A fixed-size array can be used as a frame.
+///
+/// # Examples
+///
+/// ```
+/// use audio::Frame;
+///
+/// let frame = [1, 2];
+///
+/// assert_eq!(frame.len(), 2);
+/// assert_eq!(frame.get(1), Some(2));
+/// assert!(Frame::iter(&frame).eq([1, 2]));
+/// ```
+impl<T, const N: usize> Frame for [T; N]
+where
+    T: Copy,
+{
+    type Sample = T;
+
+    type Frame<'this>
+        = &'this [T]
+    where
+        Self: 'this;
+
+    type Iter<'this>
+        = iter::Copied<slice::Iter<'this, T>>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn as_frame(&self) -> Self::Frame<'_> {
+        &self[..]
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        N
+    }
+
+    #[inline]
+    fn get(&self, channel: usize) -> Option<Self::Sample> {
+        <[T]>::get(self, channel).copied()
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        <[T]>::iter(self).copied()
+    }
+}
@@ -89,7 +89,14 @@ pub trait InterleavedBufMut: InterleavedBuf {
     /// # Safety
     ///
     /// The caller must ensure that the topology of the underlying buffer has
-    /// been updated to match the specified parameters.
+    /// been updated to match the specified parameters. That is, at least
+    /// `channels * frames` samples starting at [as_interleaved_mut_ptr] must
+    /// be allocated and initialized.
+    ///
+    /// Implementations are encouraged to verify this with debug assertions
+    /// where possible.
+    ///
+    /// [as_interleaved_mut_ptr]: InterleavedBufMut::as_interleaved_mut_ptr
     ///
     /// # Examples
     ///
@@ -129,6 +136,60 @@ pub trait InterleavedBufMut: InterleavedBuf {
     /// };
     /// ```
     unsafe fn set_interleaved_topology(&mut self, channels: usize, frames: usize);
+
+    /// Specify that `init` samples of the underlying interleaved buffer have
+    /// been initialized with the given number of `channels`, updating the
+    /// topology of the buffer accordingly.
+    ///
+    /// This is a convenience over [set_interleaved_topology] for code which
+    /// fills buffers through [as_interleaved_mut_ptr], such as device
+    /// backends, which typically know the number of samples written rather
+    /// than the number of frames.
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug builds if `init` is not a multiple of `channels`.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `init` samples starting at
+    /// [as_interleaved_mut_ptr] have been allocated and initialized.
+    ///
+    /// [set_interleaved_topology]: InterleavedBufMut::set_interleaved_topology
+    /// [as_interleaved_mut_ptr]: InterleavedBufMut::as_interleaved_mut_ptr
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio::{Buf, Channel, ResizableBuf, InterleavedBufMut};
+    ///
+    /// let mut buf = audio::buf::Interleaved::<i16>::new();
+    /// assert!(buf.try_reserve(8));
+    ///
+    /// unsafe {
+    ///     let ptr = buf.as_interleaved_mut_ptr();
+    ///
+    ///     for n in 0..8 {
+    ///         ptr.as_ptr().add(n).write(n as i16);
+    ///     }
+    ///
+    ///     buf.assume_interleaved_init(8, 2);
+    /// }
+    ///
+    /// assert!(buf.get_channel(0).unwrap().iter().eq([0, 2, 4, 6]));
+    /// assert!(buf.get_channel(1).unwrap().iter().eq([1, 3, 5, 7]));
+    /// ```
+    unsafe fn assume_interleaved_init(&mut self, init: usize, channels: usize) {
+        debug_assert!(
+            channels != 0 && init % channels == 0,
+            "initialized samples {} doesn't match channel configuration {}",
+            init,
+            channels
+        );
+
+        let frames = init.checked_div(channels).unwrap_or_default();
+        self.set_interleaved_topology(channels, frames);
+    }
 }
 
 impl<B> InterleavedBufMut for &mut B
@@ -0,0 +1,33 @@
+/// A hint of how the samples of a buffer are laid out in memory.
+///
+/// This is reported by buffers through [Buf::layout_hint][crate::Buf::layout_hint]
+/// so that generic algorithms can select specialized fast paths.
+///
+/// Note that this is only a hint. Code relying on it for correctness must still
+/// access the memory through safe interfaces such as
+/// [InterleavedBuf][crate::InterleavedBuf] or
+/// [Channel::try_as_linear][crate::Channel::try_as_linear].
+///
+/// # Examples
+///
+/// ```
+/// use audio::{Buf, LayoutHint};
+///
+/// assert_eq!(audio::interleaved![[0; 4]; 2].layout_hint(), LayoutHint::Interleaved);
+/// assert_eq!(audio::sequential![[0; 4]; 2].layout_hint(), LayoutHint::Sequential);
+/// assert_eq!(audio::dynamic![[0; 4]; 2].layout_hint(), LayoutHint::PerChannel);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum LayoutHint {
+    /// The samples of each frame are stored next to each other.
+    Interleaved,
+    /// Each channel is stored contiguously, one after another in a single
+    /// allocation.
+    Sequential,
+    /// Each channel is stored contiguously, but in separate allocations.
+    PerChannel,
+    /// The layout of the buffer is unknown.
+    #[default]
+    Unknown,
+}
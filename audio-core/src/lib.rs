@@ -26,6 +26,9 @@ pub use self::channel::Channel;
 mod channel_mut;
 pub use self::channel_mut::ChannelMut;
 
+mod channel_position;
+pub use self::channel_position::{ChannelPosition, ChannelPositions};
+
 mod frame;
 pub use self::frame::Frame;
 
@@ -38,17 +41,29 @@ pub use self::translate::Translate;
 mod sample;
 pub use self::sample::Sample;
 
+mod sample_format;
+pub use self::sample_format::{Endian, SampleFormat};
+
+mod packed_i24;
+pub use self::packed_i24::PackedI24;
+
 mod read_buf;
 pub use self::read_buf::ReadBuf;
 
 mod write_buf;
 pub use self::write_buf::WriteBuf;
 
+mod async_read_buf;
+pub use self::async_read_buf::AsyncReadBuf;
+
+mod async_write_buf;
+pub use self::async_write_buf::AsyncWriteBuf;
+
 mod exact_size_buf;
 pub use self::exact_size_buf::ExactSizeBuf;
 
 mod resizable_buf;
-pub use self::resizable_buf::ResizableBuf;
+pub use self::resizable_buf::{ResizableBuf, ResizeError};
 
 mod interleaved_buf;
 pub use self::interleaved_buf::InterleavedBuf;
@@ -64,3 +79,15 @@ pub use self::linear_channel_mut::LinearChannelMut;
 
 mod uniform_buf;
 pub use self::uniform_buf::UniformBuf;
+
+mod block_source;
+pub use self::block_source::BlockSource;
+
+mod topology;
+pub use self::topology::Topology;
+
+mod layout_hint;
+pub use self::layout_hint::LayoutHint;
+
+mod copy;
+pub use self::copy::{copy, copy_interleaved};
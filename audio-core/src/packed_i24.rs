@@ -0,0 +1,50 @@
+use crate::Sample;
+
+/// A signed 24-bit sample packed into three little endian bytes, which is a
+/// format commonly used by professional audio interfaces.
+///
+/// This is the in-memory representation of
+/// [SampleFormat::I24Packed][crate::SampleFormat::I24Packed] in little endian
+/// byte order.
+///
+/// # Examples
+///
+/// ```
+/// use audio::PackedI24;
+///
+/// let sample = PackedI24::from_i32(-2);
+/// assert_eq!(sample.to_i32(), -2);
+/// assert_eq!(sample.to_le_bytes(), [0xfe, 0xff, 0xff]);
+/// assert_eq!(PackedI24::from_i32(0x00ff_ffff).to_i32(), -1);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct PackedI24([u8; 3]);
+
+impl PackedI24 {
+    /// Construct a packed sample from the low 24 bits of the given value.
+    pub const fn from_i32(value: i32) -> Self {
+        let [a, b, c, _] = value.to_le_bytes();
+        Self([a, b, c])
+    }
+
+    /// Convert the packed sample into a sign-extended 32-bit value.
+    pub const fn to_i32(self) -> i32 {
+        let [a, b, c] = self.0;
+        i32::from_le_bytes([0, a, b, c]) >> 8
+    }
+
+    /// Construct a packed sample from its little endian bytes.
+    pub const fn from_le_bytes(bytes: [u8; 3]) -> Self {
+        Self(bytes)
+    }
+
+    /// Get the little endian bytes of the packed sample.
+    pub const fn to_le_bytes(self) -> [u8; 3] {
+        self.0
+    }
+}
+
+unsafe impl Sample for PackedI24 {
+    const ZERO: Self = Self([0; 3]);
+}
@@ -1,3 +1,6 @@
+use crate::buf::Limit;
+use crate::Buf;
+
 /// Trait used to govern sequential reading of an audio buffer.
 ///
 /// This is the "in" part of "buffered I/O". It allows for buffers to govern
@@ -83,6 +86,35 @@ pub trait ReadBuf {
     /// assert_eq!(buf.remaining(), 2);
     /// ```
     fn advance(&mut self, n: usize);
+
+    /// Peek at the next `n` frames of the buffer without consuming them.
+    ///
+    /// The returned buffer covers at most the [remaining][ReadBuf::remaining]
+    /// frames, which is useful for lookahead processing where frames need to
+    /// be inspected before they are read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio::{Buf, Channel, ReadBuf};
+    ///
+    /// let mut buf = audio::wrap::interleaved(&[1, 5, 2, 6, 3, 7, 4, 8][..], 2);
+    /// buf.advance(1);
+    ///
+    /// let peek = buf.peek(2);
+    /// assert!(peek.get_channel(0).unwrap().iter().eq([2, 3]));
+    /// assert!(peek.get_channel(1).unwrap().iter().eq([6, 7]));
+    ///
+    /// assert_eq!(buf.peek(10).frames_hint(), Some(3));
+    /// assert_eq!(buf.remaining(), 3);
+    /// ```
+    fn peek(&self, n: usize) -> Limit<&Self>
+    where
+        Self: Buf,
+    {
+        let n = usize::min(n, self.remaining());
+        self.limit(n)
+    }
 }
 
 impl<B> ReadBuf for &mut B
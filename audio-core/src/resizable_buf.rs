@@ -1,3 +1,5 @@
+use core::fmt;
+
 /// Trait implemented for buffers that can be resized.
 pub trait ResizableBuf {
     /// Ensure that the audio buffer has space for at least the given `capacity`
@@ -60,6 +62,66 @@ pub trait ResizableBuf {
     /// assert_eq!(buf.frames(), 4);
     /// ```
     fn resize_topology(&mut self, channels: usize, frames: usize);
+
+    /// Try to resize the number of per-channel frames in the buffer.
+    ///
+    /// This is the fallible counterpart to
+    /// [resize_frames][ResizableBuf::resize_frames], which errors instead of
+    /// aborting or panicking if the memory needed couldn't be allocated. If an
+    /// error is returned the buffer is left unchanged.
+    ///
+    /// The default implementation calls
+    /// [resize_frames][ResizableBuf::resize_frames] and always succeeds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio::{Buf, ExactSizeBuf, ResizableBuf};
+    ///
+    /// let mut buf = audio::interleaved![[0; 0]; 2];
+    ///
+    /// assert!(buf.try_resize(4).is_ok());
+    /// assert_eq!(buf.channels(), 2);
+    /// assert_eq!(buf.frames(), 4);
+    ///
+    /// assert!(buf.try_resize(usize::MAX).is_err());
+    /// assert_eq!(buf.frames(), 4);
+    /// ```
+    fn try_resize(&mut self, frames: usize) -> Result<(), ResizeError> {
+        self.resize_frames(frames);
+        Ok(())
+    }
+
+    /// Try to resize the buffer to match the given topology.
+    ///
+    /// This is the fallible counterpart to
+    /// [resize_topology][ResizableBuf::resize_topology], which errors instead
+    /// of aborting or panicking if the memory needed couldn't be allocated. If
+    /// an error is returned the buffer is left unchanged.
+    ///
+    /// The default implementation calls
+    /// [resize_topology][ResizableBuf::resize_topology] and always succeeds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio::{Buf, ExactSizeBuf, ResizableBuf};
+    ///
+    /// let mut to = [0; 8];
+    /// let mut buf = audio::wrap::interleaved(&mut to[..], 2);
+    ///
+    /// assert!(buf.try_resize_topology(4, 2).is_ok());
+    /// assert_eq!(buf.channels(), 4);
+    /// assert_eq!(buf.frames(), 2);
+    ///
+    /// assert!(buf.try_resize_topology(4, 4).is_err());
+    /// assert_eq!(buf.channels(), 4);
+    /// assert_eq!(buf.frames(), 2);
+    /// ```
+    fn try_resize_topology(&mut self, channels: usize, frames: usize) -> Result<(), ResizeError> {
+        self.resize_topology(channels, frames);
+        Ok(())
+    }
 }
 
 impl<B> ResizableBuf for &mut B
@@ -77,4 +139,36 @@ where
     fn resize_topology(&mut self, channels: usize, frames: usize) {
         (**self).resize_topology(channels, frames);
     }
+
+    fn try_resize(&mut self, frames: usize) -> Result<(), ResizeError> {
+        (**self).try_resize(frames)
+    }
+
+    fn try_resize_topology(&mut self, channels: usize, frames: usize) -> Result<(), ResizeError> {
+        (**self).try_resize_topology(channels, frames)
+    }
+}
+
+/// Error raised when a buffer couldn't be resized.
+///
+/// See [ResizableBuf::try_resize] and [ResizableBuf::try_resize_topology].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ResizeError;
+
+impl ResizeError {
+    /// Construct a new resize error.
+    #[inline]
+    pub const fn new() -> Self {
+        Self
+    }
 }
+
+impl fmt::Display for ResizeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to allocate memory to resize buffer")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ResizeError {}
@@ -0,0 +1,168 @@
+use core::fmt;
+
+/// The byte order of a sample format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Endian {
+    /// Little endian byte order.
+    Little,
+    /// Big endian byte order.
+    Big,
+}
+
+impl Endian {
+    /// The byte order of the current target.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio::Endian;
+    ///
+    /// if cfg!(target_endian = "little") {
+    ///     assert_eq!(Endian::NATIVE, Endian::Little);
+    /// } else {
+    ///     assert_eq!(Endian::NATIVE, Endian::Big);
+    /// }
+    /// ```
+    pub const NATIVE: Self = if cfg!(target_endian = "little") {
+        Self::Little
+    } else {
+        Self::Big
+    };
+}
+
+/// A backend-agnostic description of the format of samples.
+///
+/// This is used to communicate the result of format negotiation with audio
+/// devices without depending on any backend-specific format types.
+///
+/// # Examples
+///
+/// ```
+/// use audio::{Endian, SampleFormat};
+///
+/// let format = SampleFormat::I16(Endian::Little);
+/// assert_eq!(format.bits(), 16);
+/// assert_eq!(format.bytes(), 2);
+/// assert_eq!(format.endian(), Some(Endian::Little));
+/// assert_eq!(format.to_string(), "i16 (little endian)");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SampleFormat {
+    /// Unsigned 8-bit samples.
+    U8,
+    /// Signed 16-bit samples.
+    I16(Endian),
+    /// Signed 24-bit samples, stored in the low bits of a 32-bit word.
+    I24(Endian),
+    /// Signed 24-bit samples, packed into three bytes.
+    I24Packed(Endian),
+    /// Signed 32-bit samples.
+    I32(Endian),
+    /// 32-bit floating point samples.
+    F32(Endian),
+    /// 64-bit floating point samples.
+    F64(Endian),
+}
+
+impl SampleFormat {
+    /// The number of significant bits in a single sample.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio::{Endian, SampleFormat};
+    ///
+    /// assert_eq!(SampleFormat::U8.bits(), 8);
+    /// assert_eq!(SampleFormat::I24(Endian::Little).bits(), 24);
+    /// assert_eq!(SampleFormat::I24Packed(Endian::Little).bits(), 24);
+    /// assert_eq!(SampleFormat::F64(Endian::Big).bits(), 64);
+    /// ```
+    pub const fn bits(&self) -> usize {
+        match self {
+            Self::U8 => 8,
+            Self::I16(..) => 16,
+            Self::I24(..) | Self::I24Packed(..) => 24,
+            Self::I32(..) | Self::F32(..) => 32,
+            Self::F64(..) => 64,
+        }
+    }
+
+    /// The number of bytes used to store a single sample.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio::{Endian, SampleFormat};
+    ///
+    /// assert_eq!(SampleFormat::U8.bytes(), 1);
+    /// assert_eq!(SampleFormat::I24(Endian::Little).bytes(), 4);
+    /// assert_eq!(SampleFormat::I24Packed(Endian::Little).bytes(), 3);
+    /// assert_eq!(SampleFormat::F64(Endian::Big).bytes(), 8);
+    /// ```
+    pub const fn bytes(&self) -> usize {
+        match self {
+            Self::U8 => 1,
+            Self::I16(..) => 2,
+            Self::I24Packed(..) => 3,
+            Self::I24(..) | Self::I32(..) | Self::F32(..) => 4,
+            Self::F64(..) => 8,
+        }
+    }
+
+    /// The byte order of the sample format, if it has one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio::{Endian, SampleFormat};
+    ///
+    /// assert_eq!(SampleFormat::U8.endian(), None);
+    /// assert_eq!(SampleFormat::F32(Endian::Big).endian(), Some(Endian::Big));
+    /// ```
+    pub const fn endian(&self) -> Option<Endian> {
+        match *self {
+            Self::U8 => None,
+            Self::I16(e)
+            | Self::I24(e)
+            | Self::I24Packed(e)
+            | Self::I32(e)
+            | Self::F32(e)
+            | Self::F64(e) => Some(e),
+        }
+    }
+
+    /// Test if this is a floating point sample format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio::{Endian, SampleFormat};
+    ///
+    /// assert!(SampleFormat::F32(Endian::NATIVE).is_float());
+    /// assert!(!SampleFormat::I32(Endian::NATIVE).is_float());
+    /// ```
+    pub const fn is_float(&self) -> bool {
+        matches!(self, Self::F32(..) | Self::F64(..))
+    }
+}
+
+impl fmt::Display for SampleFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::U8 => return f.write_str("u8"),
+            Self::I16(..) => "i16",
+            Self::I24(..) => "i24",
+            Self::I24Packed(..) => "packed i24",
+            Self::I32(..) => "i32",
+            Self::F32(..) => "f32",
+            Self::F64(..) => "f64",
+        };
+
+        match self.endian() {
+            Some(Endian::Little) => write!(f, "{} (little endian)", name),
+            Some(Endian::Big) => write!(f, "{} (big endian)", name),
+            None => f.write_str(name),
+        }
+    }
+}
@@ -0,0 +1,113 @@
+/// The topology of an audio buffer, which is its number of channels and the
+/// number of frames in each channel.
+///
+/// See [ExactSizeBuf::topology][crate::ExactSizeBuf::topology].
+///
+/// # Examples
+///
+/// ```
+/// use audio::{ExactSizeBuf, Topology};
+///
+/// let buf = audio::interleaved![[0i16; 4]; 2];
+///
+/// assert_eq!(buf.topology(), Topology::new(2, 4));
+/// assert_eq!(buf.topology().samples(), 8);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Topology {
+    /// The number of channels.
+    pub channels: usize,
+    /// The number of frames in each channel.
+    pub frames: usize,
+}
+
+impl Topology {
+    /// Construct a new topology with the given number of `channels` and
+    /// `frames`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio::Topology;
+    ///
+    /// let topology = Topology::new(2, 4);
+    /// assert_eq!(topology.channels, 2);
+    /// assert_eq!(topology.frames, 4);
+    /// ```
+    #[inline]
+    pub const fn new(channels: usize, frames: usize) -> Self {
+        Self { channels, frames }
+    }
+
+    /// The total number of samples described by this topology, which is the
+    /// number of channels multiplied by the number of frames.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of samples overflows a `usize`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio::Topology;
+    ///
+    /// assert_eq!(Topology::new(2, 4).samples(), 8);
+    /// assert_eq!(Topology::new(0, 4).samples(), 0);
+    /// ```
+    #[inline]
+    pub const fn samples(&self) -> usize {
+        self.channels * self.frames
+    }
+
+    /// Test if the topology is empty, in that it describes no samples.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio::Topology;
+    ///
+    /// assert!(Topology::new(0, 4).is_empty());
+    /// assert!(Topology::new(2, 0).is_empty());
+    /// assert!(!Topology::new(2, 4).is_empty());
+    /// ```
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.channels == 0 || self.frames == 0
+    }
+
+    /// Test if this topology matches `other`, in that both the number of
+    /// channels and frames are the same.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio::{ExactSizeBuf, Topology};
+    ///
+    /// let a = audio::interleaved![[0i16; 4]; 2];
+    /// let b = audio::sequential![[0i16; 4]; 2];
+    /// let c = audio::sequential![[0i16; 4]; 3];
+    ///
+    /// assert!(a.topology().matches(&b.topology()));
+    /// assert!(!a.topology().matches(&c.topology()));
+    /// ```
+    #[inline]
+    pub const fn matches(&self, other: &Self) -> bool {
+        self.matches_channels(other) && self.frames == other.frames
+    }
+
+    /// Test if this topology has the same number of channels as `other`,
+    /// regardless of the number of frames.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio::Topology;
+    ///
+    /// assert!(Topology::new(2, 4).matches_channels(&Topology::new(2, 16)));
+    /// assert!(!Topology::new(2, 4).matches_channels(&Topology::new(1, 4)));
+    /// ```
+    #[inline]
+    pub const fn matches_channels(&self, other: &Self) -> bool {
+        self.channels == other.channels
+    }
+}
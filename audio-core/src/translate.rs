@@ -19,6 +19,19 @@
 //! * `i8`, `i16`, `i32`, and `i64` for signed PCM 8 to 64 bit audio modulation.
 //! * `f32` and `f64` for 32 and 64 bit PCM floating-point audio modulation.
 //!
+//! Note that this means signed integers are treated as fixed-point fractions,
+//! so `i16` is translated as Q15 and `i32` as Q31. This is the representation
+//! used by formats such as ALSA's `S32`:
+//!
+//! ```
+//! use audio::Translate;
+//!
+//! assert_eq!(f32::translate(0x40000000i32), 0.5);
+//! assert_eq!(f32::translate(-0x40000000i32), -0.5);
+//! assert_eq!(i32::translate(-0.5f32), -0x40000000);
+//! assert_eq!(i32::translate(-1.0f64), i32::MIN);
+//! ```
+//!
 //! The primary traits that govern how something is translated are the
 //! [Translate] and [TryTranslate]. The first deals with non-fallible
 //! translations where conversion loss is expected (as with float-integer
@@ -53,6 +66,34 @@ mod tests;
 pub trait Translate<T>: Sized {
     /// Translate one kind of buffer to another.
     fn translate(value: T) -> Self;
+
+    /// Translate the samples of `src` into `dst`, up to the length of the
+    /// shorter of the two.
+    ///
+    /// Translations between identical sample types copy the slices directly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio::Translate;
+    ///
+    /// let mut dst = [0.0f32; 3];
+    /// f32::translate_slice(&mut dst, &[i16::MIN, 0]);
+    /// assert_eq!(dst, [-1.0, 0.0, 0.0]);
+    ///
+    /// let mut dst = [0i16; 2];
+    /// i16::translate_slice(&mut dst, &[1, 2, 3]);
+    /// assert_eq!(dst, [1, 2]);
+    /// ```
+    #[inline]
+    fn translate_slice(dst: &mut [Self], src: &[T])
+    where
+        T: Copy,
+    {
+        for (d, s) in dst.iter_mut().zip(src) {
+            *d = Self::translate(*s);
+        }
+    }
 }
 
 /// Trait for performing checked translations, where it's checked if a
@@ -103,6 +144,12 @@ macro_rules! identity {
             fn translate(value: $ty) -> Self {
                 value
             }
+
+            #[inline]
+            fn translate_slice(dst: &mut [Self], src: &[$ty]) {
+                let len = usize::min(dst.len(), src.len());
+                dst[..len].copy_from_slice(&src[..len]);
+            }
         }
     };
 }
@@ -225,7 +272,6 @@ macro_rules! conversions {
             }
         }
 
-        #[cfg(feature = "std")]
         impl Translate<$float> for $unsigned {
             #[inline]
             fn translate(value: $float) -> Self {
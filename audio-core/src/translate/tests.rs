@@ -226,3 +226,34 @@ fn test_failing_signed() {
     assert_err!(i64, 0x1300000000000001 => i16);
     assert_err!(i64, 0x1300000000000001 => i32);
 }
+
+#[test]
+fn test_q31() {
+    assert_eq!(f32::translate(0x40000000i32), 0.5);
+    assert_eq!(f64::translate(0x40000000i32), 0.5);
+    assert_eq!(f32::translate(-0x40000000i32), -0.5);
+    assert_eq!(f64::translate(-0x40000000i32), -0.5);
+    assert_eq!(f64::translate(0x20000000i32), 0.25);
+    assert_eq!(f64::translate(-0x60000000i32), -0.75);
+
+    assert_eq!(i32::translate(0.5f32), 0x40000000);
+    assert_eq!(i32::translate(0.5f64), 0x40000000);
+    assert_eq!(i32::translate(-0.75f64), -0x60000000);
+    assert_eq!(i32::translate(-1.0f32), i32::MIN);
+    assert_eq!(i32::translate(1.0f64), i32::MAX);
+}
+
+macro_rules! assert_f64_round_trip {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            assert_eq!(<$ty>::translate(f64::translate(<$ty>::MIN)), <$ty>::MIN);
+            assert_eq!(<$ty>::translate(f64::translate(<$ty>::translate(0.0f64))), <$ty>::translate(0.0f64));
+            assert_eq!(<$ty>::translate(f64::translate(<$ty>::translate(0.5f64))), <$ty>::translate(0.5f64));
+        )*
+    };
+}
+
+#[test]
+fn test_f64_round_trip() {
+    assert_f64_round_trip!(u8, u16, u32, i8, i16, i32, f32, f64);
+}
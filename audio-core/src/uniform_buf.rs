@@ -78,3 +78,53 @@ pub trait UniformBuf: Buf {
     /// ```
     fn iter_frames(&self) -> Self::IterFrames<'_>;
 }
+
+impl<B> UniformBuf for &B
+where
+    B: ?Sized + UniformBuf,
+{
+    type Frame<'this>
+        = B::Frame<'this>
+    where
+        Self: 'this;
+
+    type IterFrames<'this>
+        = B::IterFrames<'this>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn get_frame(&self, frame: usize) -> Option<Self::Frame<'_>> {
+        (**self).get_frame(frame)
+    }
+
+    #[inline]
+    fn iter_frames(&self) -> Self::IterFrames<'_> {
+        (**self).iter_frames()
+    }
+}
+
+impl<B> UniformBuf for &mut B
+where
+    B: ?Sized + UniformBuf,
+{
+    type Frame<'this>
+        = B::Frame<'this>
+    where
+        Self: 'this;
+
+    type IterFrames<'this>
+        = B::IterFrames<'this>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn get_frame(&self, frame: usize) -> Option<Self::Frame<'_>> {
+        (**self).get_frame(frame)
+    }
+
+    #[inline]
+    fn iter_frames(&self) -> Self::IterFrames<'_> {
+        (**self).iter_frames()
+    }
+}
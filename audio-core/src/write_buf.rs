@@ -1,3 +1,5 @@
+use crate::{BufMut, ChannelMut, Frame};
+
 /// Trait used to govern sequential writing to an audio buffer.
 ///
 /// This is the "out" part of "buffered I/O". It allows for buffers to govern
@@ -88,6 +90,63 @@ pub trait WriteBuf {
     /// assert_eq!(buf.remaining_mut(), 2);
     /// ```
     fn advance_mut(&mut self, n: usize);
+
+    /// Write a single frame at the current write position and advance the
+    /// buffer by one frame.
+    ///
+    /// Samples are written to channels in order. Samples in the frame which
+    /// don't have a corresponding channel are ignored, and channels which
+    /// don't have a corresponding sample are left untouched.
+    ///
+    /// Returns `false` if there are no remaining frames to write to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio::WriteBuf;
+    ///
+    /// let mut buf = [0, 0, 0, 0];
+    /// let mut to = audio::wrap::interleaved(&mut buf[..], 2);
+    ///
+    /// assert!(to.write_frame([1, 2]));
+    /// assert!(to.write_frame(&[3, 4][..]));
+    /// assert!(!to.write_frame([5, 6]));
+    ///
+    /// assert_eq!(buf, [1, 2, 3, 4]);
+    /// ```
+    ///
+    /// Writing frames from a synthesizer:
+    ///
+    /// ```
+    /// use audio::{io, WriteBuf};
+    ///
+    /// let mut to = io::Write::new(audio::sequential![[0.0f32; 4]; 2]);
+    /// let mut phase = 0.0f32;
+    ///
+    /// while to.write_frame([phase, -phase]) {
+    ///     phase += 0.25;
+    /// }
+    ///
+    /// assert_eq!(to.as_ref().as_slice(), &[0.0, 0.25, 0.5, 0.75, -0.0, -0.25, -0.5, -0.75]);
+    /// ```
+    fn write_frame<F>(&mut self, frame: F) -> bool
+    where
+        Self: BufMut,
+        F: Frame<Sample = Self::Sample>,
+    {
+        if !self.has_remaining_mut() {
+            return false;
+        }
+
+        for (mut channel, sample) in self.iter_channels_mut().zip(frame.iter()) {
+            if let Some(to) = channel.get_mut(0) {
+                *to = sample;
+            }
+        }
+
+        self.advance_mut(1);
+        true
+    }
 }
 
 impl<B> WriteBuf for &mut B
@@ -1,4 +1,12 @@
 fn main() -> anyhow::Result<()> {
-    pkg_config::Config::new().statik(false).probe("alsa")?;
+    println!("cargo:rerun-if-changed=shim/shim.c");
+
+    let library = pkg_config::Config::new().statik(false).probe("alsa")?;
+
+    cc::Build::new()
+        .includes(&library.include_paths)
+        .file("shim/shim.c")
+        .compile("audio-device-alsa-shim");
+
     Ok(())
 }
@@ -0,0 +1,46 @@
+// A C shim for the parts of alsa-lib which can't be expressed in Rust, like
+// variadic callbacks.
+
+#include <stdarg.h>
+#include <stdio.h>
+#include <alsa/asoundlib.h>
+
+// The size of the buffer used to format error messages. Longer messages are
+// truncated.
+#define MESSAGE_CAPACITY 1024
+
+typedef void (*audio_device_alsa_error_callback_t)(
+    const char *file,
+    int line,
+    const char *function,
+    int err,
+    const char *message
+);
+
+static audio_device_alsa_error_callback_t error_callback;
+
+static void error_handler(const char *file, int line, const char *function, int err, const char *fmt, ...) {
+    audio_device_alsa_error_callback_t callback = __atomic_load_n(&error_callback, __ATOMIC_ACQUIRE);
+
+    if (!callback || !fmt) {
+        return;
+    }
+
+    char message[MESSAGE_CAPACITY];
+    va_list ap;
+
+    va_start(ap, fmt);
+    int n = vsnprintf(message, sizeof(message), fmt, ap);
+    va_end(ap);
+
+    if (n < 0) {
+        return;
+    }
+
+    callback(file, line, function, err, message);
+}
+
+int audio_device_alsa_set_error_handler(audio_device_alsa_error_callback_t callback) {
+    __atomic_store_n(&error_callback, callback, __ATOMIC_RELEASE);
+    return snd_lib_error_set_handler(callback ? error_handler : NULL);
+}
@@ -110,6 +110,9 @@ impl<T> ::std::fmt::Debug for __IncompleteArrayField<T> {
         fmt.write_str("__IncompleteArrayField")
     }
 }
+pub const SND_CHMAP_POSITION_MASK: u32 = 65535;
+pub const SND_CHMAP_PHASE_INVERSE: u32 = 65536;
+pub const SND_CHMAP_DRIVER_SPEC: u32 = 131072;
 pub type va_list = __builtin_va_list;
 pub type __gnuc_va_list = __builtin_va_list;
 extern "C" {
@@ -7848,3 +7851,61 @@ pub struct __va_list_tag {
     pub overflow_arg_area: *mut ::std::os::raw::c_void,
     pub reg_save_area: *mut ::std::os::raw::c_void,
 }
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct snd_use_case_mgr {
+    _unused: [u8; 0],
+}
+pub type snd_use_case_mgr_t = snd_use_case_mgr;
+extern "C" {
+    pub fn snd_use_case_identifier(fmt: *const ::std::os::raw::c_char, ...) -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    pub fn snd_use_case_free_list(
+        list: *mut *const ::std::os::raw::c_char,
+        items: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn snd_use_case_get_list(
+        uc_mgr: *mut snd_use_case_mgr_t,
+        identifier: *const ::std::os::raw::c_char,
+        list: *mut *mut *const ::std::os::raw::c_char,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn snd_use_case_get(
+        uc_mgr: *mut snd_use_case_mgr_t,
+        identifier: *const ::std::os::raw::c_char,
+        value: *mut *const ::std::os::raw::c_char,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn snd_use_case_geti(
+        uc_mgr: *mut snd_use_case_mgr_t,
+        identifier: *const ::std::os::raw::c_char,
+        value: *mut ::std::os::raw::c_long,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn snd_use_case_set(
+        uc_mgr: *mut snd_use_case_mgr_t,
+        identifier: *const ::std::os::raw::c_char,
+        value: *const ::std::os::raw::c_char,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn snd_use_case_mgr_open(
+        uc_mgr: *mut *mut snd_use_case_mgr_t,
+        card_name: *const ::std::os::raw::c_char,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn snd_use_case_mgr_reload(uc_mgr: *mut snd_use_case_mgr_t) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn snd_use_case_mgr_close(uc_mgr: *mut snd_use_case_mgr_t) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn snd_use_case_mgr_reset(uc_mgr: *mut snd_use_case_mgr_t) -> ::std::os::raw::c_int;
+}
@@ -18,6 +18,24 @@ use libc::{pid_t, pollfd, timespec, timeval, FILE};
 
 pub const SND_PCM_NONBLOCK: ::std::os::raw::c_int = 0x1;
 pub const SND_PCM_ASYNC: ::std::os::raw::c_int = 0x2;
+pub const SND_PCM_NO_AUTO_RESAMPLE: ::std::os::raw::c_int = 0x00010000;
+pub const SND_PCM_NO_AUTO_CHANNELS: ::std::os::raw::c_int = 0x00020000;
+pub const SND_PCM_NO_AUTO_FORMAT: ::std::os::raw::c_int = 0x00040000;
+pub const SND_PCM_NO_SOFTVOL: ::std::os::raw::c_int = 0x00080000;
+
+pub const SND_CTL_EVENT_MASK_VALUE: ::std::os::raw::c_uint = 1 << 0;
+pub const SND_CTL_EVENT_MASK_INFO: ::std::os::raw::c_uint = 1 << 1;
+pub const SND_CTL_EVENT_MASK_ADD: ::std::os::raw::c_uint = 1 << 2;
+pub const SND_CTL_EVENT_MASK_TLV: ::std::os::raw::c_uint = 1 << 3;
+pub const SND_CTL_EVENT_MASK_REMOVE: ::std::os::raw::c_uint = !0;
+
+pub const SND_TIMER_OPEN_NONBLOCK: ::std::os::raw::c_int = 1 << 0;
+pub const SND_TIMER_OPEN_TREAD: ::std::os::raw::c_int = 1 << 1;
+
+// Subformats added in alsa-lib 1.2.11.
+pub const SND_PCM_SUBFORMAT_MSBITS_MAX: _snd_pcm_subformat = 1;
+pub const SND_PCM_SUBFORMAT_MSBITS_20: _snd_pcm_subformat = 2;
+pub const SND_PCM_SUBFORMAT_MSBITS_24: _snd_pcm_subformat = 3;
 
 pub const SND_SEQ_OPEN_OUTPUT: i32 = 1;
 pub const SND_SEQ_OPEN_INPUT: i32 = 2;
@@ -42,4 +60,30 @@ pub const SND_SEQ_EVENT_LENGTH_MASK: u8 = 3 << 2;
 pub const SND_SEQ_EVENT_LENGTH_VARIABLE: u8 = 1 << 2;
 pub const SND_SEQ_EVENT_LENGTH_VARUSR: u8 = 2 << 2;
 
+/// Callback receiving formatted alsa-lib error messages.
+///
+/// See [audio_device_alsa_set_error_handler].
+pub type audio_device_alsa_error_callback_t = ::std::option::Option<
+    unsafe extern "C" fn(
+        file: *const ::std::os::raw::c_char,
+        line: ::std::os::raw::c_int,
+        function: *const ::std::os::raw::c_char,
+        err: ::std::os::raw::c_int,
+        message: *const ::std::os::raw::c_char,
+    ),
+>;
+
+extern "C" {
+    /// Install a global alsa-lib error handler through
+    /// `snd_lib_error_set_handler` which formats each message and passes it
+    /// to the given callback, or restore the default handler if the callback
+    /// is `None`.
+    ///
+    /// This is implemented in `shim/shim.c`, since the handler alsa-lib
+    /// expects is variadic.
+    pub fn audio_device_alsa_set_error_handler(
+        callback: audio_device_alsa_error_callback_t,
+    ) -> ::std::os::raw::c_int;
+}
+
 include!("bindings.rs");
@@ -0,0 +1,35 @@
+use std::env;
+use std::path::PathBuf;
+
+fn main() {
+    println!("cargo:rerun-if-env-changed=ASIO_SDK_DIR");
+    println!("cargo:rerun-if-changed=shim/shim.cpp");
+
+    // NB: ASIO drivers are only available on Windows, everywhere else the
+    // bindings can only be type checked.
+    if env::var("CARGO_CFG_TARGET_OS").as_deref() != Ok("windows") {
+        return;
+    }
+
+    let sdk = match env::var_os("ASIO_SDK_DIR") {
+        Some(sdk) => PathBuf::from(sdk),
+        None => panic!(
+            "ASIO_SDK_DIR must point to the ASIO SDK, which can be downloaded from https://www.steinberg.net/developers/"
+        ),
+    };
+
+    cc::Build::new()
+        .cpp(true)
+        .include(sdk.join("common"))
+        .include(sdk.join("host"))
+        .include(sdk.join("host").join("pc"))
+        .file(sdk.join("common").join("asio.cpp"))
+        .file(sdk.join("host").join("asiodrivers.cpp"))
+        .file(sdk.join("host").join("pc").join("asiolist.cpp"))
+        .file("shim/shim.cpp")
+        .compile("asio");
+
+    println!("cargo:rustc-link-lib=advapi32");
+    println!("cargo:rustc-link-lib=ole32");
+    println!("cargo:rustc-link-lib=user32");
+}
@@ -0,0 +1,87 @@
+// A C shim around the C++ interface of the ASIO SDK.
+
+#include "asiosys.h"
+#include "asio.h"
+#include "asiodrivers.h"
+
+// Defined by the SDK in asiodrivers.cpp.
+extern AsioDrivers* asioDrivers;
+bool loadAsioDriver(char *name);
+
+extern "C" {
+
+long asio_get_driver_names(char **names, long max) {
+    if (!asioDrivers) {
+        asioDrivers = new AsioDrivers();
+    }
+
+    return asioDrivers->getDriverNames(names, max);
+}
+
+ASIOBool asio_load_driver(char *name) {
+    return loadAsioDriver(name) ? ASIOTrue : ASIOFalse;
+}
+
+void asio_remove_current_driver(void) {
+    if (asioDrivers) {
+        asioDrivers->removeCurrentDriver();
+    }
+}
+
+ASIOError asio_init(ASIODriverInfo *info) {
+    return ASIOInit(info);
+}
+
+ASIOError asio_exit(void) {
+    return ASIOExit();
+}
+
+ASIOError asio_start(void) {
+    return ASIOStart();
+}
+
+ASIOError asio_stop(void) {
+    return ASIOStop();
+}
+
+ASIOError asio_get_channels(long *inputs, long *outputs) {
+    return ASIOGetChannels(inputs, outputs);
+}
+
+ASIOError asio_get_latencies(long *input, long *output) {
+    return ASIOGetLatencies(input, output);
+}
+
+ASIOError asio_get_buffer_size(long *min, long *max, long *preferred, long *granularity) {
+    return ASIOGetBufferSize(min, max, preferred, granularity);
+}
+
+ASIOError asio_can_sample_rate(ASIOSampleRate rate) {
+    return ASIOCanSampleRate(rate);
+}
+
+ASIOError asio_get_sample_rate(ASIOSampleRate *rate) {
+    return ASIOGetSampleRate(rate);
+}
+
+ASIOError asio_set_sample_rate(ASIOSampleRate rate) {
+    return ASIOSetSampleRate(rate);
+}
+
+ASIOError asio_get_channel_info(ASIOChannelInfo *info) {
+    return ASIOGetChannelInfo(info);
+}
+
+ASIOError asio_create_buffers(ASIOBufferInfo *infos, long channels, long size, ASIOCallbacks *callbacks) {
+    return ASIOCreateBuffers(infos, channels, size, callbacks);
+}
+
+ASIOError asio_dispose_buffers(void) {
+    return ASIODisposeBuffers();
+}
+
+ASIOError asio_output_ready(void) {
+    return ASIOOutputReady();
+}
+
+}
@@ -0,0 +1,162 @@
+/* bindings for shim/shim.cpp */
+
+pub type ASIOError = ::std::os::raw::c_long;
+pub type ASIOBool = ::std::os::raw::c_long;
+pub type ASIOSampleRate = f64;
+pub type ASIOSampleType = ::std::os::raw::c_long;
+pub const ASIOFalse: ASIOBool = 0;
+pub const ASIOTrue: ASIOBool = 1;
+pub const ASE_OK: ASIOError = 0;
+pub const ASE_SUCCESS: ASIOError = 1061701536;
+pub const ASE_NotPresent: ASIOError = -1000;
+pub const ASE_HWMalfunction: ASIOError = -999;
+pub const ASE_InvalidParameter: ASIOError = -998;
+pub const ASE_InvalidMode: ASIOError = -997;
+pub const ASE_SPNotAdvancing: ASIOError = -996;
+pub const ASE_NoClock: ASIOError = -995;
+pub const ASE_NoMemory: ASIOError = -994;
+pub const ASIOSTInt16MSB: ASIOSampleType = 0;
+pub const ASIOSTInt24MSB: ASIOSampleType = 1;
+pub const ASIOSTInt32MSB: ASIOSampleType = 2;
+pub const ASIOSTFloat32MSB: ASIOSampleType = 3;
+pub const ASIOSTFloat64MSB: ASIOSampleType = 4;
+pub const ASIOSTInt16LSB: ASIOSampleType = 16;
+pub const ASIOSTInt24LSB: ASIOSampleType = 17;
+pub const ASIOSTInt32LSB: ASIOSampleType = 18;
+pub const ASIOSTFloat32LSB: ASIOSampleType = 19;
+pub const ASIOSTFloat64LSB: ASIOSampleType = 20;
+pub const ASIOSTInt32LSB16: ASIOSampleType = 24;
+pub const ASIOSTInt32LSB18: ASIOSampleType = 25;
+pub const ASIOSTInt32LSB20: ASIOSampleType = 26;
+pub const ASIOSTInt32LSB24: ASIOSampleType = 27;
+pub const kAsioSelectorSupported: ::std::os::raw::c_long = 1;
+pub const kAsioEngineVersion: ::std::os::raw::c_long = 2;
+pub const kAsioResetRequest: ::std::os::raw::c_long = 3;
+pub const kAsioBufferSizeChange: ::std::os::raw::c_long = 4;
+pub const kAsioResyncRequest: ::std::os::raw::c_long = 5;
+pub const kAsioLatenciesChanged: ::std::os::raw::c_long = 6;
+pub const kAsioSupportsTimeInfo: ::std::os::raw::c_long = 7;
+#[repr(C, packed(4))]
+#[derive(Copy, Clone)]
+pub struct ASIODriverInfo {
+    pub asioVersion: ::std::os::raw::c_long,
+    pub driverVersion: ::std::os::raw::c_long,
+    pub name: [::std::os::raw::c_char; 32usize],
+    pub errorMessage: [::std::os::raw::c_char; 124usize],
+    pub sysRef: *mut ::std::os::raw::c_void,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct ASIOChannelInfo {
+    pub channel: ::std::os::raw::c_long,
+    pub isInput: ASIOBool,
+    pub isActive: ASIOBool,
+    pub channelGroup: ::std::os::raw::c_long,
+    pub type_: ASIOSampleType,
+    pub name: [::std::os::raw::c_char; 32usize],
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct ASIOBufferInfo {
+    pub isInput: ASIOBool,
+    pub channelNum: ::std::os::raw::c_long,
+    pub buffers: [*mut ::std::os::raw::c_void; 2usize],
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct ASIOTime {
+    _unused: [u8; 0],
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct ASIOCallbacks {
+    pub bufferSwitch: ::std::option::Option<
+        unsafe extern "C" fn(doubleBufferIndex: ::std::os::raw::c_long, directProcess: ASIOBool),
+    >,
+    pub sampleRateDidChange: ::std::option::Option<unsafe extern "C" fn(sRate: ASIOSampleRate)>,
+    pub asioMessage: ::std::option::Option<
+        unsafe extern "C" fn(
+            selector: ::std::os::raw::c_long,
+            value: ::std::os::raw::c_long,
+            message: *mut ::std::os::raw::c_void,
+            opt: *mut f64,
+        ) -> ::std::os::raw::c_long,
+    >,
+    pub bufferSwitchTimeInfo: ::std::option::Option<
+        unsafe extern "C" fn(
+            params: *mut ASIOTime,
+            doubleBufferIndex: ::std::os::raw::c_long,
+            directProcess: ASIOBool,
+        ) -> *mut ASIOTime,
+    >,
+}
+extern "C" {
+    pub fn asio_get_driver_names(
+        names: *mut *mut ::std::os::raw::c_char,
+        max: ::std::os::raw::c_long,
+    ) -> ::std::os::raw::c_long;
+}
+extern "C" {
+    pub fn asio_load_driver(name: *mut ::std::os::raw::c_char) -> ASIOBool;
+}
+extern "C" {
+    pub fn asio_remove_current_driver();
+}
+extern "C" {
+    pub fn asio_init(info: *mut ASIODriverInfo) -> ASIOError;
+}
+extern "C" {
+    pub fn asio_exit() -> ASIOError;
+}
+extern "C" {
+    pub fn asio_start() -> ASIOError;
+}
+extern "C" {
+    pub fn asio_stop() -> ASIOError;
+}
+extern "C" {
+    pub fn asio_get_channels(
+        inputs: *mut ::std::os::raw::c_long,
+        outputs: *mut ::std::os::raw::c_long,
+    ) -> ASIOError;
+}
+extern "C" {
+    pub fn asio_get_latencies(
+        input: *mut ::std::os::raw::c_long,
+        output: *mut ::std::os::raw::c_long,
+    ) -> ASIOError;
+}
+extern "C" {
+    pub fn asio_get_buffer_size(
+        min: *mut ::std::os::raw::c_long,
+        max: *mut ::std::os::raw::c_long,
+        preferred: *mut ::std::os::raw::c_long,
+        granularity: *mut ::std::os::raw::c_long,
+    ) -> ASIOError;
+}
+extern "C" {
+    pub fn asio_can_sample_rate(rate: ASIOSampleRate) -> ASIOError;
+}
+extern "C" {
+    pub fn asio_get_sample_rate(rate: *mut ASIOSampleRate) -> ASIOError;
+}
+extern "C" {
+    pub fn asio_set_sample_rate(rate: ASIOSampleRate) -> ASIOError;
+}
+extern "C" {
+    pub fn asio_get_channel_info(info: *mut ASIOChannelInfo) -> ASIOError;
+}
+extern "C" {
+    pub fn asio_create_buffers(
+        infos: *mut ASIOBufferInfo,
+        channels: ::std::os::raw::c_long,
+        size: ::std::os::raw::c_long,
+        callbacks: *mut ASIOCallbacks,
+    ) -> ASIOError;
+}
+extern "C" {
+    pub fn asio_dispose_buffers() -> ASIOError;
+}
+extern "C" {
+    pub fn asio_output_ready() -> ASIOError;
+}
@@ -0,0 +1,11 @@
+use std::env;
+
+fn main() {
+    // NB: the frameworks are only available when targeting macOS, everywhere
+    // else the bindings can only be type checked.
+    if env::var("CARGO_CFG_TARGET_OS").as_deref() == Ok("macos") {
+        println!("cargo:rustc-link-lib=framework=AudioToolbox");
+        println!("cargo:rustc-link-lib=framework=CoreAudio");
+        println!("cargo:rustc-link-lib=framework=CoreFoundation");
+    }
+}
@@ -0,0 +1,316 @@
+/* automatically generated by rust-bindgen 0.59.2 */
+
+pub type Boolean = ::std::os::raw::c_uchar;
+pub type OSStatus = i32;
+pub type Float64 = f64;
+pub type AudioFormatID = u32;
+pub type AudioFormatFlags = u32;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct AudioStreamBasicDescription {
+    pub mSampleRate: Float64,
+    pub mFormatID: AudioFormatID,
+    pub mFormatFlags: AudioFormatFlags,
+    pub mBytesPerPacket: u32,
+    pub mFramesPerPacket: u32,
+    pub mBytesPerFrame: u32,
+    pub mChannelsPerFrame: u32,
+    pub mBitsPerChannel: u32,
+    pub mReserved: u32,
+}
+pub const kAudioFormatLinearPCM: AudioFormatID = 1819304813;
+pub const kAudioFormatFlagIsFloat: AudioFormatFlags = 1;
+pub const kAudioFormatFlagIsBigEndian: AudioFormatFlags = 2;
+pub const kAudioFormatFlagIsSignedInteger: AudioFormatFlags = 4;
+pub const kAudioFormatFlagIsPacked: AudioFormatFlags = 8;
+pub const kAudioFormatFlagIsAlignedHigh: AudioFormatFlags = 16;
+pub const kAudioFormatFlagIsNonInterleaved: AudioFormatFlags = 32;
+pub const kAudioFormatFlagIsNonMixable: AudioFormatFlags = 64;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct AudioStreamPacketDescription {
+    pub mStartOffset: i64,
+    pub mVariableFramesInPacket: u32,
+    pub mDataByteSize: u32,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct SMPTETime {
+    pub mSubframes: i16,
+    pub mSubframeDivisor: i16,
+    pub mCounter: u32,
+    pub mType: u32,
+    pub mFlags: u32,
+    pub mHours: i16,
+    pub mMinutes: i16,
+    pub mSeconds: i16,
+    pub mFrames: i16,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct AudioTimeStamp {
+    pub mSampleTime: Float64,
+    pub mHostTime: u64,
+    pub mRateScalar: Float64,
+    pub mWordClockTime: u64,
+    pub mSMPTETime: SMPTETime,
+    pub mFlags: u32,
+    pub mReserved: u32,
+}
+pub const kAudioTimeStampSampleTimeValid: u32 = 1;
+pub const kAudioTimeStampHostTimeValid: u32 = 2;
+pub const kAudioTimeStampRateScalarValid: u32 = 4;
+pub const kAudioTimeStampWordClockTimeValid: u32 = 8;
+pub const kAudioTimeStampSMPTETimeValid: u32 = 16;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct __CFRunLoop {
+    _unused: [u8; 0],
+}
+pub type CFRunLoopRef = *mut __CFRunLoop;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct __CFString {
+    _unused: [u8; 0],
+}
+pub type CFStringRef = *const __CFString;
+pub type CFIndex = ::std::os::raw::c_long;
+pub type CFTypeRef = *const ::std::os::raw::c_void;
+pub type CFStringEncoding = u32;
+pub const kCFStringEncodingUTF8: CFStringEncoding = 134217984;
+pub type AudioObjectID = u32;
+pub type AudioDeviceID = AudioObjectID;
+pub type AudioObjectPropertySelector = u32;
+pub type AudioObjectPropertyScope = u32;
+pub type AudioObjectPropertyElement = u32;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct AudioObjectPropertyAddress {
+    pub mSelector: AudioObjectPropertySelector,
+    pub mScope: AudioObjectPropertyScope,
+    pub mElement: AudioObjectPropertyElement,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct AudioValueRange {
+    pub mMinimum: Float64,
+    pub mMaximum: Float64,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct AudioBuffer {
+    pub mNumberChannels: u32,
+    pub mDataByteSize: u32,
+    pub mData: *mut ::std::os::raw::c_void,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct AudioBufferList {
+    pub mNumberBuffers: u32,
+    pub mBuffers: [AudioBuffer; 1usize],
+}
+pub const kAudioObjectSystemObject: AudioObjectID = 1;
+pub const kAudioObjectPropertyName: AudioObjectPropertySelector = 1819173229;
+pub const kAudioObjectPropertyScopeGlobal: AudioObjectPropertyScope = 1735159650;
+pub const kAudioObjectPropertyScopeInput: AudioObjectPropertyScope = 1768845428;
+pub const kAudioObjectPropertyScopeOutput: AudioObjectPropertyScope = 1869968496;
+pub const kAudioObjectPropertyElementMain: AudioObjectPropertyElement = 0;
+pub const kAudioHardwarePropertyDevices: AudioObjectPropertySelector = 1684370979;
+pub const kAudioHardwarePropertyDefaultInputDevice: AudioObjectPropertySelector = 1682533920;
+pub const kAudioHardwarePropertyDefaultOutputDevice: AudioObjectPropertySelector = 1682929012;
+pub const kAudioHardwareBadObjectError: OSStatus = 560947818;
+pub const kAudioHardwareBadDeviceError: OSStatus = 560227702;
+pub const kAudioDevicePropertyDeviceUID: AudioObjectPropertySelector = 1969841184;
+pub const kAudioDevicePropertyStreamConfiguration: AudioObjectPropertySelector = 1936482681;
+pub const kAudioDevicePropertyAvailableNominalSampleRates: AudioObjectPropertySelector = 1853059619;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct OpaqueAudioQueue {
+    _unused: [u8; 0],
+}
+pub type AudioQueueRef = *mut OpaqueAudioQueue;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct OpaqueAudioQueueTimeline {
+    _unused: [u8; 0],
+}
+pub type AudioQueueTimelineRef = *mut OpaqueAudioQueueTimeline;
+pub type AudioQueuePropertyID = u32;
+pub type AudioQueueParameterID = u32;
+pub type AudioQueueParameterValue = f32;
+pub const kAudioQueueParam_Volume: AudioQueueParameterID = 1;
+pub const kAudioQueueParam_PlayRate: AudioQueueParameterID = 2;
+pub const kAudioQueueParam_Pitch: AudioQueueParameterID = 3;
+pub const kAudioQueueParam_VolumeRampTime: AudioQueueParameterID = 4;
+pub const kAudioQueueParam_Pan: AudioQueueParameterID = 13;
+pub const kAudioQueueErr_InvalidBuffer: OSStatus = -66687;
+pub const kAudioQueueErr_BufferEmpty: OSStatus = -66686;
+pub const kAudioQueueErr_DisposalPending: OSStatus = -66685;
+pub const kAudioQueueErr_InvalidProperty: OSStatus = -66684;
+pub const kAudioQueueErr_InvalidPropertySize: OSStatus = -66683;
+pub const kAudioQueueErr_InvalidParameter: OSStatus = -66682;
+pub const kAudioQueueErr_CannotStart: OSStatus = -66681;
+pub const kAudioQueueErr_InvalidDevice: OSStatus = -66680;
+pub const kAudioQueueErr_BufferInQueue: OSStatus = -66679;
+pub const kAudioQueueErr_InvalidRunState: OSStatus = -66678;
+pub const kAudioQueueErr_InvalidQueueType: OSStatus = -66677;
+pub const kAudioQueueErr_Permissions: OSStatus = -66676;
+pub const kAudioQueueErr_InvalidPropertyValue: OSStatus = -66675;
+pub const kAudioQueueErr_PrimeTimedOut: OSStatus = -66674;
+pub const kAudioQueueErr_CodecNotFound: OSStatus = -66673;
+pub const kAudioQueueErr_InvalidCodecAccess: OSStatus = -66672;
+pub const kAudioQueueErr_QueueInvalidated: OSStatus = -66671;
+pub const kAudioQueueErr_TooManyTaps: OSStatus = -66670;
+pub const kAudioQueueErr_InvalidTapContext: OSStatus = -66669;
+pub const kAudioQueueErr_RecordUnderrun: OSStatus = -66668;
+pub const kAudioQueueErr_InvalidTapType: OSStatus = -66667;
+pub const kAudioQueueErr_BufferEnqueuedTwice: OSStatus = -66666;
+pub const kAudioQueueErr_CannotStartYet: OSStatus = -66665;
+pub const kAudioQueueErr_EnqueueDuringReset: OSStatus = -66632;
+pub const kAudioQueueErr_InvalidOfflineMode: OSStatus = -66626;
+#[repr(C)]
+#[derive(Debug)]
+pub struct AudioQueueBuffer {
+    pub mAudioDataBytesCapacity: u32,
+    pub mAudioData: *mut ::std::os::raw::c_void,
+    pub mAudioDataByteSize: u32,
+    pub mUserData: *mut ::std::os::raw::c_void,
+    pub mPacketDescriptionCapacity: u32,
+    pub mPacketDescriptions: *mut AudioStreamPacketDescription,
+    pub mPacketDescriptionCount: u32,
+}
+pub type AudioQueueBufferRef = *mut AudioQueueBuffer;
+pub type AudioQueueOutputCallback = ::std::option::Option<
+    unsafe extern "C" fn(
+        inUserData: *mut ::std::os::raw::c_void,
+        inAQ: AudioQueueRef,
+        inBuffer: AudioQueueBufferRef,
+    ),
+>;
+pub type AudioQueueInputCallback = ::std::option::Option<
+    unsafe extern "C" fn(
+        inUserData: *mut ::std::os::raw::c_void,
+        inAQ: AudioQueueRef,
+        inBuffer: AudioQueueBufferRef,
+        inStartTime: *const AudioTimeStamp,
+        inNumberPacketDescriptions: u32,
+        inPacketDescs: *const AudioStreamPacketDescription,
+    ),
+>;
+extern "C" {
+    pub fn AudioQueueNewOutput(
+        inFormat: *const AudioStreamBasicDescription,
+        inCallbackProc: AudioQueueOutputCallback,
+        inUserData: *mut ::std::os::raw::c_void,
+        inCallbackRunLoop: CFRunLoopRef,
+        inCallbackRunLoopMode: CFStringRef,
+        inFlags: u32,
+        outAQ: *mut AudioQueueRef,
+    ) -> OSStatus;
+}
+extern "C" {
+    pub fn AudioQueueNewInput(
+        inFormat: *const AudioStreamBasicDescription,
+        inCallbackProc: AudioQueueInputCallback,
+        inUserData: *mut ::std::os::raw::c_void,
+        inCallbackRunLoop: CFRunLoopRef,
+        inCallbackRunLoopMode: CFStringRef,
+        inFlags: u32,
+        outAQ: *mut AudioQueueRef,
+    ) -> OSStatus;
+}
+extern "C" {
+    pub fn AudioQueueDispose(inAQ: AudioQueueRef, inImmediate: Boolean) -> OSStatus;
+}
+extern "C" {
+    pub fn AudioQueueAllocateBuffer(
+        inAQ: AudioQueueRef,
+        inBufferByteSize: u32,
+        outBuffer: *mut AudioQueueBufferRef,
+    ) -> OSStatus;
+}
+extern "C" {
+    pub fn AudioQueueFreeBuffer(inAQ: AudioQueueRef, inBuffer: AudioQueueBufferRef) -> OSStatus;
+}
+extern "C" {
+    pub fn AudioQueueEnqueueBuffer(
+        inAQ: AudioQueueRef,
+        inBuffer: AudioQueueBufferRef,
+        inNumPacketDescs: u32,
+        inPacketDescs: *const AudioStreamPacketDescription,
+    ) -> OSStatus;
+}
+extern "C" {
+    pub fn AudioQueueStart(inAQ: AudioQueueRef, inStartTime: *const AudioTimeStamp) -> OSStatus;
+}
+extern "C" {
+    pub fn AudioQueuePrime(
+        inAQ: AudioQueueRef,
+        inNumberOfFramesToPrepare: u32,
+        outNumberOfFramesPrepared: *mut u32,
+    ) -> OSStatus;
+}
+extern "C" {
+    pub fn AudioQueueStop(inAQ: AudioQueueRef, inImmediate: Boolean) -> OSStatus;
+}
+extern "C" {
+    pub fn AudioQueuePause(inAQ: AudioQueueRef) -> OSStatus;
+}
+extern "C" {
+    pub fn AudioQueueFlush(inAQ: AudioQueueRef) -> OSStatus;
+}
+extern "C" {
+    pub fn AudioQueueReset(inAQ: AudioQueueRef) -> OSStatus;
+}
+extern "C" {
+    pub fn AudioQueueGetParameter(
+        inAQ: AudioQueueRef,
+        inParamID: AudioQueueParameterID,
+        outValue: *mut AudioQueueParameterValue,
+    ) -> OSStatus;
+}
+extern "C" {
+    pub fn AudioQueueSetParameter(
+        inAQ: AudioQueueRef,
+        inParamID: AudioQueueParameterID,
+        inValue: AudioQueueParameterValue,
+    ) -> OSStatus;
+}
+extern "C" {
+    pub fn AudioQueueGetCurrentTime(
+        inAQ: AudioQueueRef,
+        inTimeline: AudioQueueTimelineRef,
+        outTimeStamp: *mut AudioTimeStamp,
+        outTimelineDiscontinuity: *mut Boolean,
+    ) -> OSStatus;
+}
+extern "C" {
+    pub fn CFStringGetCString(
+        theString: CFStringRef,
+        buffer: *mut ::std::os::raw::c_char,
+        bufferSize: CFIndex,
+        encoding: CFStringEncoding,
+    ) -> Boolean;
+}
+extern "C" {
+    pub fn CFRelease(cf: CFTypeRef);
+}
+extern "C" {
+    pub fn AudioObjectGetPropertyDataSize(
+        inObjectID: AudioObjectID,
+        inAddress: *const AudioObjectPropertyAddress,
+        inQualifierDataSize: u32,
+        inQualifierData: *const ::std::os::raw::c_void,
+        outDataSize: *mut u32,
+    ) -> OSStatus;
+}
+extern "C" {
+    pub fn AudioObjectGetPropertyData(
+        inObjectID: AudioObjectID,
+        inAddress: *const AudioObjectPropertyAddress,
+        inQualifierDataSize: u32,
+        inQualifierData: *const ::std::os::raw::c_void,
+        ioDataSize: *mut u32,
+        outData: *mut ::std::os::raw::c_void,
+    ) -> OSStatus;
+}
@@ -1,4 +1,7 @@
 fn main() -> anyhow::Result<()> {
     pkg_config::Config::new().statik(false).probe("libpulse")?;
+    pkg_config::Config::new()
+        .statik(false)
+        .probe("libpulse-simple")?;
     Ok(())
 }
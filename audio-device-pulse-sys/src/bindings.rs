@@ -2879,3 +2879,58 @@ extern "C" {
 extern "C" {
     pub fn pa_rtclock_now() -> pa_usec_t;
 }
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct pa_simple {
+    _unused: [u8; 0],
+}
+extern "C" {
+    pub fn pa_simple_new(
+        server: *const ::std::os::raw::c_char,
+        name: *const ::std::os::raw::c_char,
+        dir: pa_stream_direction_t,
+        dev: *const ::std::os::raw::c_char,
+        stream_name: *const ::std::os::raw::c_char,
+        ss: *const pa_sample_spec,
+        map: *const pa_channel_map,
+        attr: *const pa_buffer_attr,
+        error: *mut ::std::os::raw::c_int,
+    ) -> *mut pa_simple;
+}
+extern "C" {
+    pub fn pa_simple_free(s: *mut pa_simple);
+}
+extern "C" {
+    pub fn pa_simple_write(
+        s: *mut pa_simple,
+        data: *const ::std::os::raw::c_void,
+        bytes: usize,
+        error: *mut ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn pa_simple_drain(
+        s: *mut pa_simple,
+        error: *mut ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn pa_simple_read(
+        s: *mut pa_simple,
+        data: *mut ::std::os::raw::c_void,
+        bytes: usize,
+        error: *mut ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn pa_simple_get_latency(
+        s: *mut pa_simple,
+        error: *mut ::std::os::raw::c_int,
+    ) -> pa_usec_t;
+}
+extern "C" {
+    pub fn pa_simple_flush(
+        s: *mut pa_simple,
+        error: *mut ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
@@ -0,0 +1,13 @@
+fn main() -> anyhow::Result<()> {
+    // NB: sndio is part of the base system on OpenBSD, where it doesn't come
+    // with a pkg-config file.
+    if pkg_config::Config::new()
+        .statik(false)
+        .probe("sndio")
+        .is_err()
+    {
+        println!("cargo:rustc-link-lib=sndio");
+    }
+
+    Ok(())
+}
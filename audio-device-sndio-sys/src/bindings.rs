@@ -0,0 +1,488 @@
+/* automatically generated by rust-bindgen 0.58.1 */
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sio_hdl {
+    _unused: [u8; 0],
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sio_par {
+    pub bits: ::std::os::raw::c_uint,
+    pub bps: ::std::os::raw::c_uint,
+    pub sig: ::std::os::raw::c_uint,
+    pub le: ::std::os::raw::c_uint,
+    pub msb: ::std::os::raw::c_uint,
+    pub rchan: ::std::os::raw::c_uint,
+    pub pchan: ::std::os::raw::c_uint,
+    pub rate: ::std::os::raw::c_uint,
+    pub bufsz: ::std::os::raw::c_uint,
+    pub xrun: ::std::os::raw::c_uint,
+    pub round: ::std::os::raw::c_uint,
+    pub appbufsz: ::std::os::raw::c_uint,
+    pub __pad: [::std::os::raw::c_int; 3usize],
+    pub __magic: ::std::os::raw::c_uint,
+}
+#[test]
+fn bindgen_test_layout_sio_par() {
+    const UNINIT: ::std::mem::MaybeUninit<sio_par> = ::std::mem::MaybeUninit::uninit();
+    let ptr = UNINIT.as_ptr();
+    assert_eq!(
+        ::std::mem::size_of::<sio_par>(),
+        64usize,
+        concat!("Size of: ", stringify!(sio_par))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<sio_par>(),
+        4usize,
+        concat!("Alignment of ", stringify!(sio_par))
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).bits) as usize - ptr as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(sio_par),
+            "::",
+            stringify!(bits)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).bps) as usize - ptr as usize },
+        4usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(sio_par),
+            "::",
+            stringify!(bps)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).sig) as usize - ptr as usize },
+        8usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(sio_par),
+            "::",
+            stringify!(sig)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).le) as usize - ptr as usize },
+        12usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(sio_par),
+            "::",
+            stringify!(le)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).msb) as usize - ptr as usize },
+        16usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(sio_par),
+            "::",
+            stringify!(msb)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).rchan) as usize - ptr as usize },
+        20usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(sio_par),
+            "::",
+            stringify!(rchan)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).pchan) as usize - ptr as usize },
+        24usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(sio_par),
+            "::",
+            stringify!(pchan)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).rate) as usize - ptr as usize },
+        28usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(sio_par),
+            "::",
+            stringify!(rate)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).bufsz) as usize - ptr as usize },
+        32usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(sio_par),
+            "::",
+            stringify!(bufsz)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).xrun) as usize - ptr as usize },
+        36usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(sio_par),
+            "::",
+            stringify!(xrun)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).round) as usize - ptr as usize },
+        40usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(sio_par),
+            "::",
+            stringify!(round)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).appbufsz) as usize - ptr as usize },
+        44usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(sio_par),
+            "::",
+            stringify!(appbufsz)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).__pad) as usize - ptr as usize },
+        48usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(sio_par),
+            "::",
+            stringify!(__pad)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).__magic) as usize - ptr as usize },
+        60usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(sio_par),
+            "::",
+            stringify!(__magic)
+        )
+    );
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sio_enc {
+    pub bits: ::std::os::raw::c_uint,
+    pub bps: ::std::os::raw::c_uint,
+    pub sig: ::std::os::raw::c_uint,
+    pub le: ::std::os::raw::c_uint,
+    pub msb: ::std::os::raw::c_uint,
+}
+#[test]
+fn bindgen_test_layout_sio_enc() {
+    const UNINIT: ::std::mem::MaybeUninit<sio_enc> = ::std::mem::MaybeUninit::uninit();
+    let ptr = UNINIT.as_ptr();
+    assert_eq!(
+        ::std::mem::size_of::<sio_enc>(),
+        20usize,
+        concat!("Size of: ", stringify!(sio_enc))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<sio_enc>(),
+        4usize,
+        concat!("Alignment of ", stringify!(sio_enc))
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).bits) as usize - ptr as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(sio_enc),
+            "::",
+            stringify!(bits)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).bps) as usize - ptr as usize },
+        4usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(sio_enc),
+            "::",
+            stringify!(bps)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).sig) as usize - ptr as usize },
+        8usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(sio_enc),
+            "::",
+            stringify!(sig)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).le) as usize - ptr as usize },
+        12usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(sio_enc),
+            "::",
+            stringify!(le)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).msb) as usize - ptr as usize },
+        16usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(sio_enc),
+            "::",
+            stringify!(msb)
+        )
+    );
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sio_conf {
+    pub enc: ::std::os::raw::c_uint,
+    pub rchan: ::std::os::raw::c_uint,
+    pub pchan: ::std::os::raw::c_uint,
+    pub rate: ::std::os::raw::c_uint,
+}
+#[test]
+fn bindgen_test_layout_sio_conf() {
+    const UNINIT: ::std::mem::MaybeUninit<sio_conf> = ::std::mem::MaybeUninit::uninit();
+    let ptr = UNINIT.as_ptr();
+    assert_eq!(
+        ::std::mem::size_of::<sio_conf>(),
+        16usize,
+        concat!("Size of: ", stringify!(sio_conf))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<sio_conf>(),
+        4usize,
+        concat!("Alignment of ", stringify!(sio_conf))
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).enc) as usize - ptr as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(sio_conf),
+            "::",
+            stringify!(enc)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).rchan) as usize - ptr as usize },
+        4usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(sio_conf),
+            "::",
+            stringify!(rchan)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).pchan) as usize - ptr as usize },
+        8usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(sio_conf),
+            "::",
+            stringify!(pchan)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).rate) as usize - ptr as usize },
+        12usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(sio_conf),
+            "::",
+            stringify!(rate)
+        )
+    );
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct sio_cap {
+    pub enc: [sio_enc; 8usize],
+    pub rchan: [::std::os::raw::c_uint; 8usize],
+    pub pchan: [::std::os::raw::c_uint; 8usize],
+    pub rate: [::std::os::raw::c_uint; 16usize],
+    pub __pad: [::std::os::raw::c_int; 7usize],
+    pub nconf: ::std::os::raw::c_uint,
+    pub confs: [sio_conf; 4usize],
+}
+#[test]
+fn bindgen_test_layout_sio_cap() {
+    const UNINIT: ::std::mem::MaybeUninit<sio_cap> = ::std::mem::MaybeUninit::uninit();
+    let ptr = UNINIT.as_ptr();
+    assert_eq!(
+        ::std::mem::size_of::<sio_cap>(),
+        384usize,
+        concat!("Size of: ", stringify!(sio_cap))
+    );
+    assert_eq!(
+        ::std::mem::align_of::<sio_cap>(),
+        4usize,
+        concat!("Alignment of ", stringify!(sio_cap))
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).enc) as usize - ptr as usize },
+        0usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(sio_cap),
+            "::",
+            stringify!(enc)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).rchan) as usize - ptr as usize },
+        160usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(sio_cap),
+            "::",
+            stringify!(rchan)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).pchan) as usize - ptr as usize },
+        192usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(sio_cap),
+            "::",
+            stringify!(pchan)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).rate) as usize - ptr as usize },
+        224usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(sio_cap),
+            "::",
+            stringify!(rate)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).__pad) as usize - ptr as usize },
+        288usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(sio_cap),
+            "::",
+            stringify!(__pad)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).nconf) as usize - ptr as usize },
+        316usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(sio_cap),
+            "::",
+            stringify!(nconf)
+        )
+    );
+    assert_eq!(
+        unsafe { ::std::ptr::addr_of!((*ptr).confs) as usize - ptr as usize },
+        320usize,
+        concat!(
+            "Offset of field: ",
+            stringify!(sio_cap),
+            "::",
+            stringify!(confs)
+        )
+    );
+}
+extern "C" {
+    pub fn sio_initpar(arg1: *mut sio_par);
+}
+extern "C" {
+    pub fn sio_open(
+        arg1: *const ::std::os::raw::c_char,
+        arg2: ::std::os::raw::c_uint,
+        arg3: ::std::os::raw::c_int,
+    ) -> *mut sio_hdl;
+}
+extern "C" {
+    pub fn sio_close(arg1: *mut sio_hdl);
+}
+extern "C" {
+    pub fn sio_setpar(arg1: *mut sio_hdl, arg2: *mut sio_par) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn sio_getpar(arg1: *mut sio_hdl, arg2: *mut sio_par) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn sio_getcap(arg1: *mut sio_hdl, arg2: *mut sio_cap) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn sio_onmove(
+        arg1: *mut sio_hdl,
+        arg2: ::std::option::Option<
+            unsafe extern "C" fn(arg1: *mut ::std::os::raw::c_void, arg2: ::std::os::raw::c_int),
+        >,
+        arg3: *mut ::std::os::raw::c_void,
+    );
+}
+extern "C" {
+    pub fn sio_write(
+        arg1: *mut sio_hdl,
+        arg2: *const ::std::os::raw::c_void,
+        arg3: usize,
+    ) -> usize;
+}
+extern "C" {
+    pub fn sio_read(arg1: *mut sio_hdl, arg2: *mut ::std::os::raw::c_void, arg3: usize) -> usize;
+}
+extern "C" {
+    pub fn sio_start(arg1: *mut sio_hdl) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn sio_stop(arg1: *mut sio_hdl) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn sio_nfds(arg1: *mut sio_hdl) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn sio_pollfd(
+        arg1: *mut sio_hdl,
+        arg2: *mut pollfd,
+        arg3: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn sio_revents(arg1: *mut sio_hdl, arg2: *mut pollfd) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn sio_eof(arg1: *mut sio_hdl) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn sio_setvol(arg1: *mut sio_hdl, arg2: ::std::os::raw::c_uint) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn sio_onvol(
+        arg1: *mut sio_hdl,
+        arg2: ::std::option::Option<
+            unsafe extern "C" fn(arg1: *mut ::std::os::raw::c_void, arg2: ::std::os::raw::c_uint),
+        >,
+        arg3: *mut ::std::os::raw::c_void,
+    );
+}
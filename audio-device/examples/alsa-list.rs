@@ -29,6 +29,18 @@ fn main() -> anyhow::Result<()> {
             }
         }
 
+        for device in alsa::devices()? {
+            let name = device.name().to_str()?;
+
+            match device.description() {
+                Some(description) => {
+                    let description = description.to_str()?.replace('\n', ", ");
+                    println!("device: {} ({})", name, description);
+                }
+                None => println!("device: {}", name),
+            }
+        }
+
         Ok::<_, anyhow::Error>(())
     })?;
 
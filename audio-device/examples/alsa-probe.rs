@@ -0,0 +1,52 @@
+use audio_device::alsa;
+use std::ffi::CString;
+
+fn main() -> anyhow::Result<()> {
+    let name = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| String::from("default"));
+    let name = CString::new(name)?;
+
+    let thread = ste::spawn();
+
+    thread.submit(move || {
+        let probe = alsa::probe(&name, alsa::ProbeOptions::default())?;
+
+        println!(
+            "period size: {}-{} frames",
+            probe.period_size.0, probe.period_size.1
+        );
+        println!("periods: {}-{}", probe.periods.0, probe.periods.1);
+        println!(
+            "buffer size: {}-{} frames",
+            probe.buffer_size.0, probe.buffer_size.1
+        );
+
+        for trial in &probe.trials {
+            println!(
+                "period: {}, buffer: {}, latency: {:?}, xruns: {}, jitter: {:?} (max {:?})",
+                trial.period_size,
+                trial.buffer_size,
+                trial.latency(),
+                trial.xruns,
+                trial.jitter.mean,
+                trial.jitter.max,
+            );
+        }
+
+        match probe.minimum_stable() {
+            Some(trial) => println!(
+                "minimum stable: period {} with a buffer of {} ({:?})",
+                trial.period_size,
+                trial.buffer_size,
+                trial.latency()
+            ),
+            None => println!("no stable configuration found"),
+        }
+
+        Ok::<_, anyhow::Error>(())
+    })?;
+
+    thread.join();
+    Ok(())
+}
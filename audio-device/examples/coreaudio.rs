@@ -0,0 +1,36 @@
+use audio_core::Translate as _;
+use audio_device::coreaudio;
+use audio_generator::{self as gen, Generator as _};
+
+fn generate_audio() -> anyhow::Result<()> {
+    let config = coreaudio::QueueConfig::default();
+    let mut queue = coreaudio::OutputQueue::<f32>::open(config)?;
+    dbg!(config);
+
+    let sample_rate = config.sample_rate as f32;
+    let channels = config.channels as usize;
+
+    let mut a = gen::Sine::new(261.63, sample_rate);
+    let mut b = gen::Sine::new(329.63, sample_rate);
+    let mut c = gen::Sine::new(440.00, sample_rate);
+    let mut buf = [0f32; 16 * 1024];
+
+    loop {
+        for o in (0..buf.len()).step_by(channels) {
+            let s = f32::translate((a.sample() + b.sample() + c.sample()) * 0.01);
+
+            for c in 0..channels {
+                buf[o + c] = s;
+            }
+        }
+
+        queue.write_interleaved(audio::wrap::interleaved(&buf[..], channels))?;
+    }
+}
+
+fn main() -> anyhow::Result<()> {
+    let bg = ste::spawn();
+    bg.submit(generate_audio)?;
+    bg.join();
+    Ok(())
+}
@@ -14,7 +14,7 @@ async fn main() -> anyhow::Result<()> {
     });
 
     println!("waiting for event...");
-    event.wait().await;
+    event.wait().await?;
     println!("event woken up");
 
     drop(guard);
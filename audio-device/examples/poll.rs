@@ -8,7 +8,7 @@ use std::os::unix::io::AsRawFd;
 
 /// Read the data from the reader `R` asynchronously using the specified poll
 /// handle.
-async fn read_to_vec<R>(handle: &AsyncPoll, mut read: R) -> io::Result<Vec<u8>>
+async fn read_to_vec<R>(handle: &AsyncPoll, mut read: R) -> anyhow::Result<Vec<u8>>
 where
     R: Read,
 {
@@ -16,7 +16,7 @@ where
     let mut buf = [0u8; 1024];
 
     'outer: loop {
-        let guard = handle.returned_events().await;
+        let guard = handle.returned_events().await?;
         let events = guard.events();
 
         if events & libc::POLLIN != 0 {
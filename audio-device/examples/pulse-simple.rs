@@ -0,0 +1,44 @@
+use audio_core::Translate as _;
+use audio_device::pulse;
+use audio_generator::{self as gen, Generator as _};
+use std::ffi::CString;
+
+fn generate_audio() -> anyhow::Result<()> {
+    let name = CString::new("Hello World")?;
+    let stream_name = CString::new("Sine")?;
+
+    let mut stream = pulse::Simple::<i16>::open(
+        &name,
+        &stream_name,
+        pulse::StreamDirection::Playback,
+        2,
+        44100,
+    )?;
+
+    let sample_rate = stream.rate() as f32;
+    let channels = stream.channels();
+
+    let mut a = gen::Sine::new(261.63, sample_rate);
+    let mut b = gen::Sine::new(329.63, sample_rate);
+    let mut c = gen::Sine::new(440.00, sample_rate);
+    let mut buf = [0i16; 16 * 1024];
+
+    loop {
+        for o in (0..buf.len()).step_by(channels) {
+            let s = i16::translate((a.sample() + b.sample() + c.sample()) * 0.01);
+
+            for c in 0..channels {
+                buf[o + c] = s;
+            }
+        }
+
+        stream.write_interleaved(audio::wrap::interleaved(&buf[..], channels))?;
+    }
+}
+
+fn main() -> anyhow::Result<()> {
+    let bg = ste::spawn();
+    bg.submit(generate_audio)?;
+    bg.join();
+    Ok(())
+}
@@ -44,8 +44,9 @@ async fn generate_audio() -> Result<()> {
     let config = output.default_client_config()?;
 
     match config.sample_format {
-        wasapi::SampleFormat::I16 => run_output::<i16>(output, config).await,
-        wasapi::SampleFormat::F32 => run_output::<f32>(output, config).await,
+        wasapi::SampleFormat::I16(..) => run_output::<i16>(output, config).await,
+        wasapi::SampleFormat::F32(..) => run_output::<f32>(output, config).await,
+        format => Err(anyhow!("unsupported sample format: {}", format)),
     }
 }
 
@@ -0,0 +1,57 @@
+use std::future;
+use std::task::{Context, Poll};
+
+use crate::alsa::{Control, ControlEvent, Error, Result};
+use crate::libc as c;
+use crate::unix::AsyncPoll;
+use crate::unix::Errno;
+
+/// An async receiver of control element events.
+///
+/// See [Control::async_events].
+pub struct AsyncControlEvents<'a> {
+    control: &'a mut Control,
+    poll_handle: AsyncPoll,
+}
+
+impl<'a> AsyncControlEvents<'a> {
+    /// Construct a new async receiver surrounding the given control.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `pollfd` is the poll descriptor of the
+    /// given control.
+    pub(super) unsafe fn new(control: &'a mut Control, pollfd: c::pollfd) -> Result<Self> {
+        Ok(Self {
+            control,
+            poll_handle: AsyncPoll::new(pollfd)?,
+        })
+    }
+
+    /// Wait for the next control element event.
+    pub async fn next(&mut self) -> Result<ControlEvent> {
+        future::poll_fn(|cx| self.poll_next(cx)).await
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Result<ControlEvent>> {
+        loop {
+            match self.control.try_read_event() {
+                Ok(Some(event)) => return Poll::Ready(Ok(event)),
+                Ok(None) => continue,
+                Err(Error::Sys(Errno::EWOULDBLOCK)) => (),
+                Err(e) => return Poll::Ready(Err(e)),
+            }
+
+            let guard = match self.poll_handle.poll_returned_events(cx) {
+                Poll::Ready(guard) => guard?,
+                Poll::Pending => return Poll::Pending,
+            };
+
+            drop(guard);
+        }
+    }
+}
+
+// Safety: [Control] is tagged with the thread its created it and is ensured
+// not to leave it.
+unsafe impl Send for AsyncControlEvents<'_> {}
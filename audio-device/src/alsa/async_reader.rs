@@ -0,0 +1,133 @@
+use core::marker;
+use core::mem;
+use std::future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use crate::alsa::{Error, Pcm, Result};
+use crate::buf_io;
+use crate::libc as c;
+use crate::unix::AsyncPoll;
+use crate::unix::{Errno, PollFlags};
+
+/// An interleaved type-checked async PCM reader.
+///
+/// See [Pcm::async_reader].
+pub struct AsyncReader<'a, T> {
+    pcm: &'a mut Pcm,
+    poll_handle: AsyncPoll,
+    pollfd: c::pollfd,
+    channels: usize,
+    _marker: marker::PhantomData<T>,
+}
+
+impl<'a, T> AsyncReader<'a, T> {
+    /// Construct a new reader surrounding the given PCM.
+    ///
+    /// # Safety
+    ///
+    /// This constructor assumes that the caller has checked that type `T` is
+    /// appropriate for reading from the given PCM.
+    pub(super) unsafe fn new(pcm: &'a mut Pcm, pollfd: c::pollfd, channels: usize) -> Result<Self> {
+        Ok(Self {
+            pcm,
+            poll_handle: AsyncPoll::new(pollfd)?,
+            pollfd,
+            channels,
+            _marker: marker::PhantomData,
+        })
+    }
+
+    /// Read into an interleaved buffer.
+    ///
+    /// Completes once the remaining frames of the buffer have been filled
+    /// with captured audio.
+    pub async fn read_interleaved<B>(&mut self, mut buf: B) -> Result<()>
+    where
+        B: audio_core::Buf<Sample = T>
+            + audio_core::WriteBuf
+            + audio_core::InterleavedBufMut<Sample = T>,
+    {
+        future::poll_fn(|cx| self.poll_read_interleaved(cx, &mut buf)).await
+    }
+
+    fn poll_read_interleaved<B>(&mut self, cx: &mut Context<'_>, mut buf: B) -> Poll<Result<()>>
+    where
+        B: audio_core::Buf<Sample = T>
+            + audio_core::WriteBuf
+            + audio_core::InterleavedBufMut<Sample = T>,
+    {
+        if buf.channels() != self.channels {
+            return Poll::Ready(Err(Error::ChannelsMismatch {
+                actual: buf.channels(),
+                expected: self.channels,
+            }));
+        }
+
+        let Self {
+            pcm,
+            poll_handle,
+            pollfd,
+            channels,
+            ..
+        } = self;
+
+        let frame_size = *channels * mem::size_of::<T>();
+        // NB: reads are frame-oriented, so there's never a partial frame.
+        let mut partial = 0;
+
+        buf_io::poll_read(&mut buf, *channels, &mut partial, |ptr, len| {
+            pcm.tag.ensure_on_thread();
+
+            let result =
+                unsafe { pcm.read_interleaved_unchecked(ptr.cast(), (len / frame_size) as u64) };
+
+            match result {
+                Ok(read) => Poll::Ready(Ok(read as usize * frame_size)),
+                Err(Error::Sys(Errno::EWOULDBLOCK)) => {
+                    loop {
+                        let guard = match poll_handle.poll_returned_events(cx) {
+                            Poll::Ready(guard) => guard?,
+                            Poll::Pending => return Poll::Pending,
+                        };
+
+                        pollfd.revents = guard.events();
+
+                        let mut fds = [*pollfd];
+                        let flags = pcm.poll_descriptors_revents(&mut fds)?;
+
+                        if flags.test(PollFlags::POLLIN) {
+                            break;
+                        }
+
+                        drop(guard);
+                    }
+
+                    Poll::Ready(Ok(0))
+                }
+                Err(e) => Poll::Ready(Err(e)),
+            }
+        })
+    }
+}
+
+impl<T> audio_core::AsyncReadBuf for AsyncReader<'_, T> {
+    type Sample = T;
+    type Error = Error;
+
+    fn poll_read_buf<B>(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: B) -> Poll<Result<()>>
+    where
+        B: audio_core::WriteBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBufMut<Sample = T>,
+    {
+        self.get_mut().poll_read_interleaved(cx, buf)
+    }
+}
+
+// Note: the sample type is only used as a marker.
+impl<T> Unpin for AsyncReader<'_, T> {}
+
+// Safety: [Pcm] is tagged with the thread its created it and is ensured not to
+// leave it.
+unsafe impl<T> Send for AsyncReader<'_, T> {}
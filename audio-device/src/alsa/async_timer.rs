@@ -0,0 +1,76 @@
+use std::future;
+use std::task::{Context, Poll};
+
+use crate::alsa::{Result, Timer};
+use crate::libc as c;
+use crate::unix::AsyncPoll;
+use crate::unix::PollFlags;
+use alsa_sys as alsa;
+
+/// An async wrapper around a [Timer].
+///
+/// See [Timer::async_timer].
+pub struct AsyncTimer<'a> {
+    timer: &'a mut Timer,
+    poll_handle: AsyncPoll,
+    pollfd: c::pollfd,
+}
+
+impl<'a> AsyncTimer<'a> {
+    /// Construct a new async timer surrounding the given timer.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `pollfd` is the poll descriptor of the
+    /// given timer.
+    pub(super) unsafe fn new(timer: &'a mut Timer, pollfd: c::pollfd) -> Result<Self> {
+        Ok(Self {
+            timer,
+            poll_handle: AsyncPoll::new(pollfd)?,
+            pollfd,
+        })
+    }
+
+    /// Wait for the timer to fire, returning the number of ticks which have
+    /// elapsed since the last time it was waited on.
+    pub async fn wait(&mut self) -> Result<c::c_uint> {
+        future::poll_fn(|cx| self.poll_wait(cx)).await
+    }
+
+    fn poll_wait(&mut self, cx: &mut Context<'_>) -> Poll<Result<c::c_uint>> {
+        loop {
+            self.timer.tag.ensure_on_thread();
+
+            let guard = match self.poll_handle.poll_returned_events(cx) {
+                Poll::Ready(guard) => guard?,
+                Poll::Pending => return Poll::Pending,
+            };
+
+            self.pollfd.revents = guard.events();
+
+            let mut revents = 0;
+
+            unsafe {
+                errno!(alsa::snd_timer_poll_descriptors_revents(
+                    self.timer.handle.as_ptr(),
+                    &mut self.pollfd,
+                    1,
+                    &mut revents
+                ))?;
+            }
+
+            let flags = PollFlags::from_bits_truncate(revents as c::c_short);
+
+            if flags.test(PollFlags::POLLIN) {
+                // NB: readiness has been signalled, so this doesn't block.
+                return Poll::Ready(self.timer.read_ticks());
+            }
+
+            drop(guard);
+        }
+    }
+}
+
+// Safety: [Timer] is tagged with the thread its created it and is ensured not
+// to leave it.
+unsafe impl Send for AsyncTimer<'_> {}
@@ -1,6 +1,9 @@
 use core::marker;
+use std::future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
 
-use crate::alsa::{Error, Pcm, Result};
+use crate::alsa::{Error, Pcm, Result, State};
 use crate::libc as c;
 use crate::unix::{Errno, PollFlags};
 use crate::unix::AsyncPoll;
@@ -13,6 +16,8 @@ pub struct AsyncWriter<'a, T> {
     poll_handle: AsyncPoll,
     pollfd: c::pollfd,
     channels: usize,
+    recover: bool,
+    xruns: usize,
     _marker: marker::PhantomData<T>,
 }
 
@@ -29,20 +34,63 @@ impl<'a, T> AsyncWriter<'a, T> {
             poll_handle: AsyncPoll::new(pollfd)?,
             pollfd,
             channels,
+            recover: false,
+            xruns: 0,
             _marker: marker::PhantomData,
         })
     }
 
+    /// Configure whether the writer should automatically recover from
+    /// underruns and suspended streams.
+    ///
+    /// When enabled, writes which fail with `EPIPE` or `ESTRPIPE` recover the
+    /// stream through [Pcm::recover] and are retried instead of raising an
+    /// error. Each recovery is counted in [AsyncWriter::xruns].
+    ///
+    /// This is disabled by default.
+    pub fn set_recover(&mut self, recover: bool) {
+        self.recover = recover;
+    }
+
+    /// The number of underruns or suspensions which the writer has recovered
+    /// from.
+    ///
+    /// See [AsyncWriter::set_recover].
+    pub fn xruns(&self) -> usize {
+        self.xruns
+    }
+
+    /// Get the current playback delay in frames, which is the number of frames
+    /// which have to pass before a frame written now is heard.
+    ///
+    /// See [Pcm::delay].
+    pub fn delay(&mut self) -> Result<c::c_long> {
+        self.pcm.delay()
+    }
+
     /// Write an interleaved buffer.
     pub async fn write_interleaved<B>(&mut self, mut buf: B) -> Result<()>
     where
-        B: audio_core::Buf<Sample = T> + audio_core::ReadBuf + audio_core::ExactSizeBuf + audio_core::InterleavedBuf,
+        B: audio_core::Buf<Sample = T>
+            + audio_core::ReadBuf
+            + audio_core::ExactSizeBuf
+            + audio_core::InterleavedBuf<Sample = T>,
+    {
+        future::poll_fn(|cx| self.poll_write_interleaved(cx, &mut buf)).await
+    }
+
+    fn poll_write_interleaved<B>(&mut self, cx: &mut Context<'_>, mut buf: B) -> Poll<Result<()>>
+    where
+        B: audio_core::Buf<Sample = T>
+            + audio_core::ReadBuf
+            + audio_core::ExactSizeBuf
+            + audio_core::InterleavedBuf<Sample = T>,
     {
         if buf.channels() != self.channels {
-            return Err(Error::ChannelsMismatch {
+            return Poll::Ready(Err(Error::ChannelsMismatch {
                 actual: buf.channels(),
                 expected: self.channels,
-            });
+            }));
         }
 
         while buf.has_remaining() {
@@ -59,7 +107,11 @@ impl<'a, T> AsyncWriter<'a, T> {
                     Ok(written) => written as usize,
                     Err(Error::Sys(Errno::EWOULDBLOCK)) => {
                         loop {
-                            let guard = self.poll_handle.returned_events().await;
+                            let guard = match self.poll_handle.poll_returned_events(cx) {
+                                Poll::Ready(guard) => guard?,
+                                Poll::Pending => return Poll::Pending,
+                            };
+
                             self.pollfd.revents = guard.events();
 
                             let mut fds = [self.pollfd];
@@ -74,17 +126,96 @@ impl<'a, T> AsyncWriter<'a, T> {
 
                         continue;
                     }
-                    Err(e) => return Err(e),
+                    Err(Error::Sys(errno @ (Errno::EPIPE | Errno::ESTRPIPE))) if self.recover => {
+                        self.pcm.recover(errno, true)?;
+                        self.xruns = self.xruns.wrapping_add(1);
+                        continue;
+                    }
+                    Err(e) => return Poll::Ready(Err(e)),
                 };
 
                 buf.advance(written);
             }
         }
 
-        Ok(())
+        Poll::Ready(Ok(()))
+    }
+
+    /// Drain the PCM, resolving once all pending frames have been played.
+    ///
+    /// Unlike [Pcm::drain] this doesn't block the current thread while
+    /// waiting, but instead waits for the PCM to leave the
+    /// [Draining][State::Draining] state.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// async fn play(pcm: &mut alsa::Pcm) -> anyhow::Result<()> {
+    ///     let mut writer = pcm.async_writer::<i16>()?;
+    ///     // write audio through the writer.
+    ///
+    ///     writer.drain().await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn drain(&mut self) -> Result<()> {
+        let mut draining = false;
+        future::poll_fn(|cx| self.poll_drain(cx, &mut draining)).await
+    }
+
+    fn poll_drain(&mut self, cx: &mut Context<'_>, draining: &mut bool) -> Poll<Result<()>> {
+        loop {
+            self.pcm.tag.ensure_on_thread();
+
+            if *draining {
+                if self.pcm.state() != State::Draining {
+                    return Poll::Ready(Ok(()));
+                }
+            } else {
+                match self.pcm.drain() {
+                    Ok(()) => return Poll::Ready(Ok(())),
+                    Err(Error::Sys(Errno::EWOULDBLOCK)) => {
+                        // Drain has been initiated in non-blocking mode, so
+                        // wait for the PCM to leave the draining state.
+                        *draining = true;
+                        continue;
+                    }
+                    Err(e) => return Poll::Ready(Err(e)),
+                }
+            }
+
+            let guard = match self.poll_handle.poll_returned_events(cx) {
+                Poll::Ready(guard) => guard?,
+                Poll::Pending => return Poll::Pending,
+            };
+
+            self.pollfd.revents = guard.events();
+            let mut fds = [self.pollfd];
+            self.pcm.poll_descriptors_revents(&mut fds)?;
+            drop(guard);
+        }
+    }
+}
+
+impl<T> audio_core::AsyncWriteBuf for AsyncWriter<'_, T> {
+    type Sample = T;
+    type Error = Error;
+
+    fn poll_write_buf<B>(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: B) -> Poll<Result<()>>
+    where
+        B: audio_core::ReadBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBuf<Sample = T>,
+    {
+        self.get_mut().poll_write_interleaved(cx, buf)
     }
 }
 
+// Note: the sample type is only used as a marker.
+impl<T> Unpin for AsyncWriter<'_, T> {}
+
 // Safety: [Pcm] is tagged with the thread its created it and is ensured not to
 // leave it.
 unsafe impl<T> Send for AsyncWriter<'_, T> {}
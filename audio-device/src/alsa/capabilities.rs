@@ -0,0 +1,56 @@
+use crate::alsa::{Access, Format, Pcm, Result};
+use crate::libc as c;
+
+/// The capabilities of a [Pcm] device.
+///
+/// See [Pcm::capabilities].
+///
+/// With the `serde` feature enabled this implements `Serialize`, so that
+/// probed capabilities can be stored or reported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[non_exhaustive]
+pub struct Capabilities {
+    /// The sample formats supported by the device.
+    pub formats: Vec<Format>,
+    /// The access types supported by the device.
+    pub access: Vec<Access>,
+    /// The minimum number of channels supported.
+    pub channels_min: c::c_uint,
+    /// The maximum number of channels supported.
+    pub channels_max: c::c_uint,
+    /// The minimum sample rate supported.
+    pub rate_min: c::c_uint,
+    /// The maximum sample rate supported.
+    pub rate_max: c::c_uint,
+}
+
+impl Capabilities {
+    /// Probe the capabilities of the given PCM.
+    pub(super) fn probe(pcm: &mut Pcm) -> Result<Self> {
+        let hw = pcm.hardware_parameters_any()?;
+
+        let mut format_mask = hw.format_mask()?;
+        let formats = Format::ALL
+            .iter()
+            .copied()
+            .filter(|f| format_mask.test(*f))
+            .collect();
+
+        let mut access_mask = hw.get_access_mask()?;
+        let access = Access::ALL
+            .iter()
+            .copied()
+            .filter(|a| access_mask.test(*a))
+            .collect();
+
+        Ok(Self {
+            formats,
+            access,
+            channels_min: hw.channels_min()?,
+            channels_max: hw.channels_max()?,
+            rate_min: hw.rate_min()?,
+            rate_max: hw.rate_max()?,
+        })
+    }
+}
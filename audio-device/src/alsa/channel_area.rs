@@ -1,14 +1,56 @@
-// component is work in progress
-#![allow(unused)]
-
+use crate::alsa::Result;
 use crate::libc as c;
 use alsa_sys as alsa;
 use std::ptr;
 
 /// A memory-mapped channel area.
+///
+/// See [Pcm::mmap_begin][crate::alsa::Pcm::mmap_begin].
 pub struct ChannelArea<'a> {
     pub(super) pcm: &'a mut ptr::NonNull<alsa::snd_pcm_t>,
     pub(super) area: *const alsa::snd_pcm_channel_area_t,
     pub(super) offset: c::c_ulong,
     pub(super) frames: c::c_ulong,
 }
+
+impl ChannelArea<'_> {
+    /// The offset in frames into the mapped areas where access begins.
+    pub fn offset(&self) -> usize {
+        self.offset as usize
+    }
+
+    /// The number of frames which are available for access.
+    pub fn frames(&self) -> usize {
+        self.frames as usize
+    }
+
+    /// Get the raw area description of the given channel.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `channel` is smaller than the number of
+    /// channels the PCM is configured with.
+    pub(super) unsafe fn area(&self, channel: usize) -> &alsa::snd_pcm_channel_area_t {
+        &*self.area.add(channel)
+    }
+
+    /// Commit the given number of frames, making them available to the
+    /// device.
+    ///
+    /// This should only be called once for each call to
+    /// [Pcm::mmap_begin][crate::alsa::Pcm::mmap_begin], and `frames` must not
+    /// be larger than [ChannelArea::frames].
+    pub fn commit(&mut self, frames: usize) -> Result<usize> {
+        debug_assert!(frames <= self.frames as usize);
+
+        unsafe {
+            let committed = errno!(alsa::snd_pcm_mmap_commit(
+                self.pcm.as_mut(),
+                self.offset,
+                frames as c::c_ulong
+            ))?;
+
+            Ok(committed as usize)
+        }
+    }
+}
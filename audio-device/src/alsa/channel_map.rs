@@ -0,0 +1,149 @@
+use crate::libc as c;
+use alsa_sys as alsa;
+use audio_core::ChannelPosition;
+use std::slice;
+
+/// The speaker positions of the channels of a PCM.
+///
+/// See [Pcm::channel_map][super::Pcm::channel_map].
+///
+/// # Examples
+///
+/// ```
+/// use audio::ChannelPosition;
+/// use audio_device::alsa;
+///
+/// let map = alsa::ChannelMap::from_positions(ChannelPosition::default_layout(2).unwrap());
+/// assert_eq!(map.channels(), 2);
+/// assert_eq!(map.position(1), Some(ChannelPosition::FrontRight));
+/// assert_eq!(map.position(2), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ChannelMap {
+    positions: Vec<c::c_uint>,
+}
+
+impl ChannelMap {
+    /// Construct a channel map out of the given speaker positions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio::ChannelPosition;
+    /// use audio_device::alsa;
+    ///
+    /// let map = alsa::ChannelMap::from_positions(&[ChannelPosition::Mono]);
+    /// assert_eq!(map.position(0), Some(ChannelPosition::Mono));
+    /// ```
+    pub fn from_positions(positions: &[ChannelPosition]) -> Self {
+        Self {
+            positions: positions.iter().copied().map(to_alsa).collect(),
+        }
+    }
+
+    /// Construct a channel map from a raw ALSA channel map.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must point to a valid channel map.
+    pub(super) unsafe fn from_raw(map: *const alsa::snd_pcm_chmap_t) -> Self {
+        let channels = (*map).channels as usize;
+        let positions = slice::from_raw_parts((*map).pos.as_ptr(), channels);
+
+        Self {
+            positions: positions.to_vec(),
+        }
+    }
+
+    /// Construct the raw representation of the channel map, which is the
+    /// number of channels followed by the position of each channel.
+    pub(super) fn to_raw(&self) -> Vec<c::c_uint> {
+        let mut raw = Vec::with_capacity(self.positions.len() + 1);
+        raw.push(self.positions.len() as c::c_uint);
+        raw.extend_from_slice(&self.positions);
+        raw
+    }
+
+    /// Get the number of channels in the channel map.
+    pub fn channels(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Get the speaker position of the given channel.
+    ///
+    /// Returns `None` if the channel doesn't exist, or if its position is
+    /// unknown or can't be represented by [ChannelPosition].
+    ///
+    /// Flags on the position, like the phase of the channel being inverted,
+    /// are ignored.
+    pub fn position(&self, channel: usize) -> Option<ChannelPosition> {
+        from_alsa(*self.positions.get(channel)?)
+    }
+
+    /// Iterate over the speaker positions of all channels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio::ChannelPosition;
+    /// use audio_device::alsa;
+    ///
+    /// let map = alsa::ChannelMap::from_positions(ChannelPosition::default_layout(6).unwrap());
+    /// let positions = map.positions().collect::<Vec<_>>();
+    /// assert_eq!(positions[3], Some(ChannelPosition::Lfe));
+    /// ```
+    pub fn positions(&self) -> impl Iterator<Item = Option<ChannelPosition>> + '_ {
+        self.positions.iter().map(|&p| from_alsa(p))
+    }
+}
+
+fn to_alsa(position: ChannelPosition) -> c::c_uint {
+    match position {
+        ChannelPosition::Mono => alsa::SND_CHMAP_MONO,
+        ChannelPosition::FrontLeft => alsa::SND_CHMAP_FL,
+        ChannelPosition::FrontRight => alsa::SND_CHMAP_FR,
+        ChannelPosition::FrontCenter => alsa::SND_CHMAP_FC,
+        ChannelPosition::Lfe => alsa::SND_CHMAP_LFE,
+        ChannelPosition::RearLeft => alsa::SND_CHMAP_RL,
+        ChannelPosition::RearRight => alsa::SND_CHMAP_RR,
+        ChannelPosition::FrontLeftOfCenter => alsa::SND_CHMAP_FLC,
+        ChannelPosition::FrontRightOfCenter => alsa::SND_CHMAP_FRC,
+        ChannelPosition::RearCenter => alsa::SND_CHMAP_RC,
+        ChannelPosition::SideLeft => alsa::SND_CHMAP_SL,
+        ChannelPosition::SideRight => alsa::SND_CHMAP_SR,
+        ChannelPosition::TopCenter => alsa::SND_CHMAP_TC,
+        ChannelPosition::TopFrontLeft => alsa::SND_CHMAP_TFL,
+        ChannelPosition::TopFrontCenter => alsa::SND_CHMAP_TFC,
+        ChannelPosition::TopFrontRight => alsa::SND_CHMAP_TFR,
+        ChannelPosition::TopRearLeft => alsa::SND_CHMAP_TRL,
+        ChannelPosition::TopRearCenter => alsa::SND_CHMAP_TRC,
+        ChannelPosition::TopRearRight => alsa::SND_CHMAP_TRR,
+        _ => alsa::SND_CHMAP_UNKNOWN,
+    }
+}
+
+fn from_alsa(position: c::c_uint) -> Option<ChannelPosition> {
+    // NB: the high bits hold flags, like `SND_CHMAP_PHASE_INVERSE`.
+    Some(match position & alsa::SND_CHMAP_POSITION_MASK {
+        alsa::SND_CHMAP_MONO => ChannelPosition::Mono,
+        alsa::SND_CHMAP_FL => ChannelPosition::FrontLeft,
+        alsa::SND_CHMAP_FR => ChannelPosition::FrontRight,
+        alsa::SND_CHMAP_FC => ChannelPosition::FrontCenter,
+        alsa::SND_CHMAP_LFE => ChannelPosition::Lfe,
+        alsa::SND_CHMAP_RL => ChannelPosition::RearLeft,
+        alsa::SND_CHMAP_RR => ChannelPosition::RearRight,
+        alsa::SND_CHMAP_FLC => ChannelPosition::FrontLeftOfCenter,
+        alsa::SND_CHMAP_FRC => ChannelPosition::FrontRightOfCenter,
+        alsa::SND_CHMAP_RC => ChannelPosition::RearCenter,
+        alsa::SND_CHMAP_SL => ChannelPosition::SideLeft,
+        alsa::SND_CHMAP_SR => ChannelPosition::SideRight,
+        alsa::SND_CHMAP_TC => ChannelPosition::TopCenter,
+        alsa::SND_CHMAP_TFL => ChannelPosition::TopFrontLeft,
+        alsa::SND_CHMAP_TFC => ChannelPosition::TopFrontCenter,
+        alsa::SND_CHMAP_TFR => ChannelPosition::TopFrontRight,
+        alsa::SND_CHMAP_TRL => ChannelPosition::TopRearLeft,
+        alsa::SND_CHMAP_TRC => ChannelPosition::TopRearCenter,
+        alsa::SND_CHMAP_TRR => ChannelPosition::TopRearRight,
+        _ => return None,
+    })
+}
@@ -1,6 +1,7 @@
 use crate::alsa::{Access, Direction, Error, Format, Pcm, Result, Sample};
 use crate::libc as c;
 use std::marker;
+use std::time::Duration;
 
 /// Default access to configure.
 const DEFAULT_ACCESS: Access = Access::ReadWriteInterleaved;
@@ -10,6 +11,11 @@ const DEFAULT_LATENCY: c::c_uint = 500_000;
 const DEFAULT_CHANNELS: c::c_uint = 2;
 /// Default sample rate to use.
 const DEFAULT_RATE: c::c_uint = 44100;
+/// The smallest period size in frames considered by the low latency profile,
+/// since very small periods are commonly advertised but unreliable.
+const LOW_LATENCY_PERIOD_SIZE: c::c_ulong = 64;
+/// The smallest number of periods considered by the low latency profile.
+const LOW_LATENCY_PERIODS: c::c_uint = 2;
 
 /// The stream configuration used after the configurator has been successfully installed.
 ///
@@ -26,6 +32,49 @@ pub struct Config {
     pub period_time: c::c_uint,
     /// The configured period size in frames.
     pub period_size: c::c_ulong,
+    /// The configured buffer size in frames.
+    pub buffer_size: c::c_ulong,
+    /// The configured minimum number of frames available before the stream
+    /// is considered ready.
+    pub available_min: c::c_ulong,
+    /// The configured number of frames which have to be written before the
+    /// stream is automatically started.
+    pub start_threshold: c::c_ulong,
+    /// The configured number of frames available at which the stream is
+    /// automatically stopped.
+    pub stop_threshold: c::c_ulong,
+}
+
+impl Config {
+    /// The achieved latency, which is the configured buffer time.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    /// use std::time::Duration;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    ///
+    /// let config = pcm.configure::<f32>()
+    ///     .latency(Duration::from_millis(20))
+    ///     .period(Duration::from_millis(5))
+    ///     .install()?;
+    ///
+    /// println!("latency: {:?}, period: {:?}", config.latency(), config.period());
+    /// # Ok(()) }
+    /// ```
+    pub fn latency(&self) -> Duration {
+        Duration::from_micros(self.buffer_time as u64)
+    }
+
+    /// The achieved period, which is the configured period time.
+    ///
+    /// See [Config::latency].
+    pub fn period(&self) -> Duration {
+        Duration::from_micros(self.period_time as u64)
+    }
 }
 
 /// A simple [Pcm] stream configuration.
@@ -51,8 +100,15 @@ pub struct Configurator<'a, T> {
     access: Access,
     format: Format,
     latency: c::c_uint,
+    period: Option<c::c_uint>,
+    low_latency: bool,
     channels: c::c_uint,
     rate: c::c_uint,
+    available_min: Option<c::c_ulong>,
+    start_threshold: Option<c::c_ulong>,
+    stop_threshold: Option<c::c_ulong>,
+    silence_threshold: Option<c::c_ulong>,
+    silence_size: Option<c::c_ulong>,
     _marker: marker::PhantomData<T>,
 }
 
@@ -66,8 +122,15 @@ where
             access: DEFAULT_ACCESS,
             format: T::DEFAULT_FORMAT,
             latency: DEFAULT_LATENCY,
+            period: None,
+            low_latency: false,
             channels: DEFAULT_CHANNELS,
             rate: DEFAULT_RATE,
+            available_min: None,
+            start_threshold: None,
+            stop_threshold: None,
+            silence_threshold: None,
+            silence_size: None,
             _marker: marker::PhantomData,
         }
     }
@@ -144,11 +207,78 @@ where
     /// dbg!(config);
     /// # Ok(()) }
     /// ```
-    pub fn latency(self, latency: std::time::Duration) -> Self {
+    pub fn latency(self, latency: Duration) -> Self {
         let latency = u128::min(u32::MAX as u128, latency.as_micros()) as u32;
         Self { latency, ..self }
     }
 
+    /// Configure the stream period to use, which is the interval at which the
+    /// device signals that it's ready for more frames.
+    ///
+    /// The nearest period supported by the device is used, see
+    /// [Config::period] for what was achieved. Defaults to a quarter of the
+    /// [latency][Configurator::latency]. Will never accept a period higher than
+    /// `2**32` microseconds. Anything larger will be floored to it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    /// use std::time::Duration;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    ///
+    /// let config = pcm.configure::<f32>()
+    ///     .latency(Duration::from_millis(20))
+    ///     .period(Duration::from_millis(10))
+    ///     .install()?;
+    ///
+    /// dbg!(config.period_size);
+    /// # Ok(()) }
+    /// ```
+    pub fn period(self, period: Duration) -> Self {
+        let period = u128::min(u32::MAX as u128, period.as_micros()) as u32;
+
+        Self {
+            period: Some(period),
+            ..self
+        }
+    }
+
+    /// Configure the stream for the lowest latency which the device can
+    /// reliably provide.
+    ///
+    /// Instead of targeting a [latency][Configurator::latency] and
+    /// [period][Configurator::period], this searches the configuration space
+    /// of the device for the smallest integral period size and the smallest
+    /// number of periods it supports. Periods smaller than 64 frames and
+    /// buffers of fewer than 2 periods are never picked if the device
+    /// supports larger ones. The chosen values are reported in the returned
+    /// [Config].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    ///
+    /// let config = pcm.configure::<f32>()
+    ///     .low_latency()
+    ///     .install()?;
+    ///
+    /// println!("period: {} frames, buffer: {} frames", config.period_size, config.buffer_size);
+    /// # Ok(()) }
+    /// ```
+    pub fn low_latency(self) -> Self {
+        Self {
+            low_latency: true,
+            ..self
+        }
+    }
+
     /// Configure the number of channels to use.
     ///
     /// # Examples
@@ -191,6 +321,125 @@ where
         Self { rate, ..self }
     }
 
+    /// Configure the minimum number of frames which have to be available
+    /// before the stream is considered ready for reading or writing.
+    ///
+    /// Defaults to the period size.
+    ///
+    /// See [SoftwareParametersMut::set_available_min][crate::alsa::SoftwareParametersMut::set_available_min].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    ///
+    /// let config = pcm.configure::<f32>()
+    ///     .available_min(64)
+    ///     .install()?;
+    ///
+    /// dbg!(config);
+    /// # Ok(()) }
+    /// ```
+    pub fn available_min(self, available_min: c::c_ulong) -> Self {
+        Self {
+            available_min: Some(available_min),
+            ..self
+        }
+    }
+
+    /// Configure the number of frames which have to be written before the
+    /// stream is automatically started.
+    ///
+    /// Defaults to the largest number of whole periods which fit in the
+    /// buffer.
+    ///
+    /// See [SoftwareParametersMut::set_start_threshold][crate::alsa::SoftwareParametersMut::set_start_threshold].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    ///
+    /// let config = pcm.configure::<f32>()
+    ///     .start_threshold(1)
+    ///     .install()?;
+    ///
+    /// dbg!(config);
+    /// # Ok(()) }
+    /// ```
+    pub fn start_threshold(self, start_threshold: c::c_ulong) -> Self {
+        Self {
+            start_threshold: Some(start_threshold),
+            ..self
+        }
+    }
+
+    /// Configure the number of available frames at which the stream is
+    /// automatically stopped.
+    ///
+    /// Defaults to the buffer size, which stops the stream on an underrun.
+    ///
+    /// See [SoftwareParametersMut::set_stop_threshold][crate::alsa::SoftwareParametersMut::set_stop_threshold].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    ///
+    /// let config = pcm.configure::<f32>()
+    ///     .stop_threshold(4096)
+    ///     .install()?;
+    ///
+    /// dbg!(config);
+    /// # Ok(()) }
+    /// ```
+    pub fn stop_threshold(self, stop_threshold: c::c_ulong) -> Self {
+        Self {
+            stop_threshold: Some(stop_threshold),
+            ..self
+        }
+    }
+
+    /// Configure the silence threshold and size in frames.
+    ///
+    /// When the number of frames available for playback falls below
+    /// `threshold`, `size` frames of silence are written to the device.
+    ///
+    /// See [SoftwareParametersMut::set_silence_threshold][crate::alsa::SoftwareParametersMut::set_silence_threshold]
+    /// and [SoftwareParametersMut::set_silence_size][crate::alsa::SoftwareParametersMut::set_silence_size].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    ///
+    /// let config = pcm.configure::<f32>()
+    ///     .silence(256, 256)
+    ///     .install()?;
+    ///
+    /// dbg!(config);
+    /// # Ok(()) }
+    /// ```
+    pub fn silence(self, threshold: c::c_ulong, size: c::c_ulong) -> Self {
+        Self {
+            silence_threshold: Some(threshold),
+            silence_size: Some(size),
+            ..self
+        }
+    }
+
     /// Install the current configuration and return the one which is used by
     /// the underlying PCM.
     ///
@@ -218,17 +467,56 @@ where
         hw.set_channels(self.channels)?;
 
         let (rate, _) = hw.set_rate_near(self.rate, Direction::Nearest)?;
-        let (buffer_time, _) = hw.set_buffer_time_near(self.latency, Direction::Nearest)?;
-        let period_time = self.latency / 4;
-        let (period_time, _) = hw.set_period_time_near(period_time, Direction::Nearest)?;
+
+        let (buffer_time, period_time) = if self.low_latency {
+            hw.set_period_size_integer()?;
+            hw.set_periods_integer()?;
+
+            if hw.period_size_max()?.0 >= LOW_LATENCY_PERIOD_SIZE {
+                hw.set_period_size_min(LOW_LATENCY_PERIOD_SIZE, Direction::Nearest)?;
+            }
+
+            if hw.periods_max()?.0 >= LOW_LATENCY_PERIODS {
+                hw.set_periods_min(LOW_LATENCY_PERIODS, Direction::Nearest)?;
+            }
+
+            hw.set_period_size_first()?;
+            hw.set_periods_first()?;
+            (hw.buffer_time()?.0, hw.period_time()?.0)
+        } else {
+            let (buffer_time, _) = hw.set_buffer_time_near(self.latency, Direction::Nearest)?;
+            let period_time = self.period.unwrap_or(self.latency / 4);
+            let (period_time, _) = hw.set_period_time_near(period_time, Direction::Nearest)?;
+            (buffer_time, period_time)
+        };
+
         let buffer_size = hw.buffer_size()?;
         let (period_size, _) = hw.period_size()?;
 
         hw.install()?;
 
+        let available_min = self.available_min.unwrap_or(period_size);
+        let start_threshold = self
+            .start_threshold
+            .unwrap_or((buffer_size / period_size) * period_size);
+
         let mut sw = self.pcm.software_parameters_mut()?;
-        sw.set_start_threshold((buffer_size / period_size) * period_size)?;
-        sw.set_available_min(period_size)?;
+        sw.set_start_threshold(start_threshold)?;
+        sw.set_available_min(available_min)?;
+
+        if let Some(stop_threshold) = self.stop_threshold {
+            sw.set_stop_threshold(stop_threshold)?;
+        }
+
+        if let Some(silence_threshold) = self.silence_threshold {
+            sw.set_silence_threshold(silence_threshold)?;
+        }
+
+        if let Some(silence_size) = self.silence_size {
+            sw.set_silence_size(silence_size)?;
+        }
+
+        let stop_threshold = sw.stop_threshold()?;
         sw.install()?;
 
         Ok(Config {
@@ -237,6 +525,10 @@ where
             buffer_time,
             period_time,
             period_size,
+            buffer_size,
+            available_min,
+            start_threshold,
+            stop_threshold,
         })
     }
 }
@@ -1,7 +1,10 @@
-use crate::alsa::{ControlElementInterface, Result};
+#[cfg(feature = "poll-driver")]
+use crate::alsa::AsyncControlEvents;
+use crate::alsa::{ControlElementInterface, ControlElementType, Error, Result};
 use crate::libc as c;
+use crate::unix::Errno;
 use alsa_sys as alsa;
-use std::ffi::CStr;
+use std::ffi::{CStr, CString};
 use std::mem;
 use std::ptr;
 
@@ -9,7 +12,7 @@ use std::ptr;
 ///
 /// See [Control::open].
 pub struct Control {
-    tag: ste::Tag,
+    pub(super) tag: ste::Tag,
     pub(super) handle: ptr::NonNull<alsa::snd_ctl_t>,
 }
 
@@ -114,6 +117,220 @@ impl Control {
             Ok(list)
         }
     }
+
+    /// Find the control element with the given interface, name and index and
+    /// read its current value.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    /// use std::ffi::CString;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let name = CString::new("hw:0")?;
+    /// let control = alsa::Control::open(&name)?;
+    ///
+    /// let name = CString::new("Master Playback Switch")?;
+    /// let mut value = control.find(alsa::ControlElementInterface::Mixer, &name, 0)?;
+    ///
+    /// for n in 0..value.count() {
+    ///     value.set_boolean(n, true)?;
+    /// }
+    ///
+    /// value.write()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn find(
+        &self,
+        interface: ControlElementInterface,
+        name: &CStr,
+        index: c::c_uint,
+    ) -> Result<ControlElementValue<'_>> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            let mut info = mem::MaybeUninit::uninit();
+            errno!(alsa::snd_ctl_elem_info_malloc(info.as_mut_ptr()))?;
+            let info = ptr::NonNull::new_unchecked(info.assume_init());
+
+            let mut value = mem::MaybeUninit::uninit();
+
+            if let Err(e) = errno!(alsa::snd_ctl_elem_value_malloc(value.as_mut_ptr())) {
+                alsa::snd_ctl_elem_info_free(info.as_ptr());
+                return Err(e.into());
+            }
+
+            let mut element = ControlElementValue {
+                control: self,
+                info,
+                value: ptr::NonNull::new_unchecked(value.assume_init()),
+            };
+
+            alsa::snd_ctl_elem_info_set_interface(element.info.as_mut(), interface as c::c_uint);
+            alsa::snd_ctl_elem_info_set_name(element.info.as_mut(), name.as_ptr());
+            alsa::snd_ctl_elem_info_set_index(element.info.as_mut(), index);
+            errno!(alsa::snd_ctl_elem_info(
+                self.handle.as_ptr(),
+                element.info.as_mut()
+            ))?;
+
+            let numid = alsa::snd_ctl_elem_info_get_numid(element.info.as_ref());
+            alsa::snd_ctl_elem_value_set_numid(element.value.as_mut(), numid);
+            element.read()?;
+            Ok(element)
+        }
+    }
+
+    /// Set the control to nonblocking or blocking mode.
+    ///
+    /// In nonblocking mode [Control::read_event] returns
+    /// [Error::WouldBlock] instead of blocking if there are no pending events.
+    pub fn set_nonblocking(&mut self, nonblocking: bool) -> Result<()> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            errno!(alsa::snd_ctl_nonblock(
+                self.handle.as_ptr(),
+                nonblocking as c::c_int
+            ))?;
+            Ok(())
+        }
+    }
+
+    /// Subscribe to or unsubscribe from change events of control elements.
+    ///
+    /// Once subscribed, events can be read through [Control::read_event].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    /// use std::ffi::CString;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let name = CString::new("hw:0")?;
+    /// let mut control = alsa::Control::open(&name)?;
+    /// control.subscribe_events(true)?;
+    ///
+    /// loop {
+    ///     let event = control.read_event()?;
+    ///
+    ///     if event.is_value_changed() {
+    ///         println!("changed: {}", event.name().to_str()?);
+    ///     }
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn subscribe_events(&mut self, subscribe: bool) -> Result<()> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            errno!(alsa::snd_ctl_subscribe_events(
+                self.handle.as_ptr(),
+                subscribe as c::c_int
+            ))?;
+            Ok(())
+        }
+    }
+
+    /// Read the next control element event, blocking until one is
+    /// available.
+    ///
+    /// See [Control::subscribe_events].
+    pub fn read_event(&mut self) -> Result<ControlEvent> {
+        loop {
+            match self.try_read_event() {
+                Ok(Some(event)) => return Ok(event),
+                Ok(None) => continue,
+                Err(Error::Sys(Errno::EWOULDBLOCK)) => return Err(Error::WouldBlock),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Try to read a single event, returning `None` if the event read isn't
+    /// an element event.
+    pub(super) fn try_read_event(&mut self) -> Result<Option<ControlEvent>> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            let mut event = mem::MaybeUninit::uninit();
+            errno!(alsa::snd_ctl_event_malloc(event.as_mut_ptr()))?;
+            let event = event.assume_init();
+
+            let result = errno!(alsa::snd_ctl_read(self.handle.as_ptr(), event));
+
+            let out = match result {
+                Ok(n)
+                    if n > 0 && alsa::snd_ctl_event_get_type(event) == alsa::SND_CTL_EVENT_ELEM =>
+                {
+                    Ok(Some(ControlEvent::from_raw(event)))
+                }
+                Ok(..) => Ok(None),
+                Err(e) => Err(e.into()),
+            };
+
+            alsa::snd_ctl_event_free(event);
+            out
+        }
+    }
+
+    cfg_poll_driver! {
+        /// Subscribe to control element events and construct an async
+        /// receiver of them.
+        ///
+        /// This puts the control in nonblocking mode.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the audio runtime is not available.
+        ///
+        /// See [Runtime][crate::runtime::Runtime] for more.
+        ///
+        /// # Examples
+        ///
+        /// ```no_run
+        /// use audio_device::alsa;
+        ///
+        /// async fn watch(control: &mut alsa::Control) -> anyhow::Result<()> {
+        ///     let mut events = control.async_events()?;
+        ///
+        ///     loop {
+        ///         let event = events.next().await?;
+        ///
+        ///         if event.is_value_changed() {
+        ///             println!("changed: {}", event.name().to_str()?);
+        ///         }
+        ///     }
+        /// }
+        /// ```
+        pub fn async_events(&mut self) -> Result<AsyncControlEvents<'_>> {
+            self.subscribe_events(true)?;
+            self.set_nonblocking(true)?;
+
+            let mut fds = [c::pollfd {
+                fd: 0,
+                events: 0,
+                revents: 0,
+            }];
+
+            let count = unsafe {
+                errno!(alsa::snd_ctl_poll_descriptors(
+                    self.handle.as_ptr(),
+                    fds.as_mut_ptr(),
+                    fds.len() as c::c_uint
+                ))?
+            };
+
+            if count != 1 {
+                return Err(Error::MissingPollFds);
+            }
+
+            let fd = fds[0];
+            unsafe { AsyncControlEvents::new(self, fd) }
+        }
+    }
 }
 
 // Safety: [Control] is tagged with the thread its created it and is ensured not to
@@ -126,6 +343,380 @@ impl Drop for Control {
     }
 }
 
+/// A change event of a control element.
+///
+/// See [Control::read_event].
+#[derive(Debug, Clone)]
+pub struct ControlEvent {
+    mask: c::c_uint,
+    numid: c::c_uint,
+    interface: ControlElementInterface,
+    name: CString,
+    index: c::c_uint,
+}
+
+impl ControlEvent {
+    unsafe fn from_raw(event: *const alsa::snd_ctl_event_t) -> Self {
+        let interface = alsa::snd_ctl_event_elem_get_interface(event);
+
+        Self {
+            mask: alsa::snd_ctl_event_elem_get_mask(event),
+            numid: alsa::snd_ctl_event_elem_get_numid(event),
+            interface: ControlElementInterface::from_value(interface)
+                .expect("bad control element interface"),
+            name: CStr::from_ptr(alsa::snd_ctl_event_elem_get_name(event)).to_owned(),
+            index: alsa::snd_ctl_event_elem_get_index(event),
+        }
+    }
+
+    /// The numeric identifier of the element the event is for.
+    pub fn numid(&self) -> c::c_uint {
+        self.numid
+    }
+
+    /// The interface of the element the event is for.
+    pub fn interface(&self) -> ControlElementInterface {
+        self.interface
+    }
+
+    /// The name of the element the event is for.
+    pub fn name(&self) -> &CStr {
+        &self.name
+    }
+
+    /// The index of the element the event is for.
+    pub fn index(&self) -> c::c_uint {
+        self.index
+    }
+
+    /// Test if the element was removed.
+    pub fn is_removed(&self) -> bool {
+        self.mask == alsa::SND_CTL_EVENT_MASK_REMOVE
+    }
+
+    /// Test if the value of the element changed.
+    pub fn is_value_changed(&self) -> bool {
+        self.test(alsa::SND_CTL_EVENT_MASK_VALUE)
+    }
+
+    /// Test if the information of the element changed, like its range.
+    pub fn is_info_changed(&self) -> bool {
+        self.test(alsa::SND_CTL_EVENT_MASK_INFO)
+    }
+
+    /// Test if the element was added.
+    pub fn is_added(&self) -> bool {
+        self.test(alsa::SND_CTL_EVENT_MASK_ADD)
+    }
+
+    /// Test if the TLV data of the element changed.
+    pub fn is_tlv_changed(&self) -> bool {
+        self.test(alsa::SND_CTL_EVENT_MASK_TLV)
+    }
+
+    fn test(&self, bit: c::c_uint) -> bool {
+        !self.is_removed() && self.mask & bit != 0
+    }
+}
+
+/// The value of a single control element, as found through [Control::find].
+///
+/// Values are read from the device when the element is found and can be
+/// refreshed with [ControlElementValue::read]. Modifications are only applied
+/// to the device once [ControlElementValue::write] is called.
+pub struct ControlElementValue<'a> {
+    control: &'a Control,
+    info: ptr::NonNull<alsa::snd_ctl_elem_info_t>,
+    value: ptr::NonNull<alsa::snd_ctl_elem_value_t>,
+}
+
+impl ControlElementValue<'_> {
+    /// Get the type of the control element.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    /// use std::ffi::CString;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let name = CString::new("hw:0")?;
+    /// let control = alsa::Control::open(&name)?;
+    /// let name = CString::new("Master Playback Volume")?;
+    /// let value = control.find(alsa::ControlElementInterface::Mixer, &name, 0)?;
+    /// println!("type: {}", value.ty());
+    /// # Ok(()) }
+    /// ```
+    pub fn ty(&self) -> ControlElementType {
+        unsafe {
+            let ty = alsa::snd_ctl_elem_info_get_type(self.info.as_ref());
+            ControlElementType::from_value(ty).expect("bad control element type")
+        }
+    }
+
+    /// Get the number of values held by the control element, typically one per
+    /// channel.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    /// use std::ffi::CString;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let name = CString::new("hw:0")?;
+    /// let control = alsa::Control::open(&name)?;
+    /// let name = CString::new("Master Playback Volume")?;
+    /// let value = control.find(alsa::ControlElementInterface::Mixer, &name, 0)?;
+    /// println!("count: {}", value.count());
+    /// # Ok(()) }
+    /// ```
+    pub fn count(&self) -> c::c_uint {
+        unsafe { alsa::snd_ctl_elem_info_get_count(self.info.as_ref()) }
+    }
+
+    /// Test if the control element is readable.
+    pub fn is_readable(&self) -> bool {
+        unsafe { alsa::snd_ctl_elem_info_is_readable(self.info.as_ref()) != 0 }
+    }
+
+    /// Test if the control element is writable.
+    pub fn is_writable(&self) -> bool {
+        unsafe { alsa::snd_ctl_elem_info_is_writable(self.info.as_ref()) != 0 }
+    }
+
+    /// Get the minimum and maximum value of an integer control element.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    /// use std::ffi::CString;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let name = CString::new("hw:0")?;
+    /// let control = alsa::Control::open(&name)?;
+    /// let name = CString::new("Master Playback Volume")?;
+    /// let value = control.find(alsa::ControlElementInterface::Mixer, &name, 0)?;
+    /// let (min, max) = value.integer_range()?;
+    /// println!("range: {}-{}", min, max);
+    /// # Ok(()) }
+    /// ```
+    pub fn integer_range(&self) -> Result<(c::c_long, c::c_long)> {
+        self.ensure_type(ControlElementType::Integer)?;
+
+        unsafe {
+            let min = alsa::snd_ctl_elem_info_get_min(self.info.as_ref());
+            let max = alsa::snd_ctl_elem_info_get_max(self.info.as_ref());
+            Ok((min, max))
+        }
+    }
+
+    /// Get the number of items of an enumerated control element.
+    pub fn items(&self) -> Result<c::c_uint> {
+        self.ensure_type(ControlElementType::Enumerated)?;
+        unsafe { Ok(alsa::snd_ctl_elem_info_get_items(self.info.as_ref())) }
+    }
+
+    /// Get the name of the given item of an enumerated control element.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    /// use std::ffi::CString;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let name = CString::new("hw:0")?;
+    /// let control = alsa::Control::open(&name)?;
+    /// let name = CString::new("Capture Source")?;
+    /// let mut value = control.find(alsa::ControlElementInterface::Mixer, &name, 0)?;
+    ///
+    /// for item in 0..value.items()? {
+    ///     println!("{}: {}", item, value.item_name(item)?.to_str()?);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn item_name(&mut self, item: c::c_uint) -> Result<CString> {
+        self.control.tag.ensure_on_thread();
+        self.ensure_type(ControlElementType::Enumerated)?;
+
+        unsafe {
+            alsa::snd_ctl_elem_info_set_item(self.info.as_mut(), item);
+            errno!(alsa::snd_ctl_elem_info(
+                self.control.handle.as_ptr(),
+                self.info.as_mut()
+            ))?;
+            let name = alsa::snd_ctl_elem_info_get_item_name(self.info.as_ref());
+            Ok(CStr::from_ptr(name).to_owned())
+        }
+    }
+
+    /// Get the boolean value at the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not smaller than [ControlElementValue::count].
+    pub fn boolean(&self, index: c::c_uint) -> Result<bool> {
+        self.ensure_access(ControlElementType::Boolean, index)?;
+        unsafe { Ok(alsa::snd_ctl_elem_value_get_boolean(self.value.as_ref(), index) != 0) }
+    }
+
+    /// Set the boolean value at the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not smaller than [ControlElementValue::count].
+    pub fn set_boolean(&mut self, index: c::c_uint, value: bool) -> Result<()> {
+        self.ensure_access(ControlElementType::Boolean, index)?;
+
+        unsafe {
+            alsa::snd_ctl_elem_value_set_boolean(self.value.as_mut(), index, value as c::c_long);
+        }
+
+        Ok(())
+    }
+
+    /// Get the integer value at the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not smaller than [ControlElementValue::count].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    /// use std::ffi::CString;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let name = CString::new("hw:0")?;
+    /// let control = alsa::Control::open(&name)?;
+    /// let name = CString::new("Master Playback Volume")?;
+    /// let mut value = control.find(alsa::ControlElementInterface::Mixer, &name, 0)?;
+    ///
+    /// let (min, max) = value.integer_range()?;
+    /// println!("volume: {}", value.integer(0)?);
+    /// value.set_integer(0, min + (max - min) / 2)?;
+    /// value.write()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn integer(&self, index: c::c_uint) -> Result<c::c_long> {
+        self.ensure_access(ControlElementType::Integer, index)?;
+        unsafe {
+            Ok(alsa::snd_ctl_elem_value_get_integer(
+                self.value.as_ref(),
+                index,
+            ))
+        }
+    }
+
+    /// Set the integer value at the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not smaller than [ControlElementValue::count].
+    pub fn set_integer(&mut self, index: c::c_uint, value: c::c_long) -> Result<()> {
+        self.ensure_access(ControlElementType::Integer, index)?;
+
+        unsafe {
+            alsa::snd_ctl_elem_value_set_integer(self.value.as_mut(), index, value);
+        }
+
+        Ok(())
+    }
+
+    /// Get the selected item of an enumerated value at the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not smaller than [ControlElementValue::count].
+    pub fn enumerated(&self, index: c::c_uint) -> Result<c::c_uint> {
+        self.ensure_access(ControlElementType::Enumerated, index)?;
+        unsafe {
+            Ok(alsa::snd_ctl_elem_value_get_enumerated(
+                self.value.as_ref(),
+                index,
+            ))
+        }
+    }
+
+    /// Set the selected item of an enumerated value at the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not smaller than [ControlElementValue::count].
+    pub fn set_enumerated(&mut self, index: c::c_uint, item: c::c_uint) -> Result<()> {
+        self.ensure_access(ControlElementType::Enumerated, index)?;
+
+        unsafe {
+            alsa::snd_ctl_elem_value_set_enumerated(self.value.as_mut(), index, item);
+        }
+
+        Ok(())
+    }
+
+    /// Read the current value of the control element from the device,
+    /// discarding any modifications which haven't been written.
+    pub fn read(&mut self) -> Result<()> {
+        self.control.tag.ensure_on_thread();
+
+        unsafe {
+            errno!(alsa::snd_ctl_elem_read(
+                self.control.handle.as_ptr(),
+                self.value.as_mut()
+            ))?;
+        }
+
+        Ok(())
+    }
+
+    /// Write the modified value of the control element to the device.
+    pub fn write(&mut self) -> Result<()> {
+        self.control.tag.ensure_on_thread();
+
+        unsafe {
+            errno!(alsa::snd_ctl_elem_write(
+                self.control.handle.as_ptr(),
+                self.value.as_mut()
+            ))?;
+        }
+
+        Ok(())
+    }
+
+    fn ensure_type(&self, expected: ControlElementType) -> Result<()> {
+        let actual = self.ty();
+
+        if actual != expected {
+            return Err(Error::ControlElementTypeMismatch { actual, expected });
+        }
+
+        Ok(())
+    }
+
+    fn ensure_access(&self, expected: ControlElementType, index: c::c_uint) -> Result<()> {
+        self.ensure_type(expected)?;
+        let count = self.count();
+        assert!(
+            index < count,
+            "index {} out of bounds for control element with {} values",
+            index,
+            count
+        );
+        Ok(())
+    }
+}
+
+impl Drop for ControlElementValue<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            alsa::snd_ctl_elem_value_free(self.value.as_ptr());
+            alsa::snd_ctl_elem_info_free(self.info.as_ptr());
+        }
+    }
+}
+
 /// Reference to a control element from a [ControlElementList].
 ///
 /// Fetched with [ControlElementList::get].
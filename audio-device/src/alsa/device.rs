@@ -0,0 +1,222 @@
+use crate::alsa::{CString, Pcm, Result, Stream};
+use crate::device::{DeviceInfo, Direction};
+use crate::libc as c;
+use alsa_sys as alsa;
+use std::ffi::CStr;
+use std::mem;
+
+/// Construct an iterator over available PCM devices.
+///
+/// This includes both hardware devices and the virtual devices defined in the
+/// ALSA configuration, like `default`, which is what `aplay -L` lists.
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::alsa;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// for device in alsa::devices()? {
+///     println!("{}", device.name().to_str()?);
+///
+///     if let Some(description) = device.description() {
+///         println!("  {}", description.to_str()?);
+///     }
+/// }
+/// # Ok(()) }
+/// ```
+pub fn devices() -> Result<Devices> {
+    unsafe {
+        let mut hints = mem::MaybeUninit::uninit();
+        errno!(alsa::snd_device_name_hint(
+            -1,
+            b"pcm\0".as_ptr() as *const c::c_char,
+            hints.as_mut_ptr()
+        ))?;
+        let hints = hints.assume_init();
+
+        Ok(Devices { hints, index: 0 })
+    }
+}
+
+/// An iterator over available PCM devices.
+///
+/// See [devices].
+pub struct Devices {
+    hints: *mut *mut c::c_void,
+    index: usize,
+}
+
+impl Iterator for Devices {
+    type Item = Device;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let hint = unsafe { *self.hints.add(self.index) };
+
+            if hint.is_null() {
+                return None;
+            }
+
+            self.index += 1;
+
+            // NB: devices without a name can't be opened, so skip them.
+            let name = match unsafe { get_hint(hint, b"NAME\0") } {
+                Some(name) => name,
+                None => continue,
+            };
+
+            let description = unsafe { get_hint(hint, b"DESC\0") };
+            let direction = unsafe { get_hint(hint, b"IOID\0") };
+
+            let direction = match direction.as_ref().map(|d| d.to_bytes()) {
+                Some(b"Output") => Some(Stream::Playback),
+                Some(b"Input") => Some(Stream::Capture),
+                _ => None,
+            };
+
+            return Some(Device {
+                name,
+                description,
+                direction,
+            });
+        }
+    }
+}
+
+impl Drop for Devices {
+    fn drop(&mut self) {
+        unsafe {
+            alsa::snd_device_name_free_hint(self.hints);
+        }
+    }
+}
+
+/// A PCM device as returned by [devices].
+pub struct Device {
+    name: CString,
+    description: Option<CString>,
+    direction: Option<Stream>,
+}
+
+impl Device {
+    /// The name of the device, as used with [Pcm::open][crate::alsa::Pcm::open].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// for device in alsa::devices()? {
+    ///     if device.supports(alsa::Stream::Playback) {
+    ///         let pcm = alsa::Pcm::open(device.name(), alsa::Stream::Playback)?;
+    ///     }
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn name(&self) -> &CStr {
+        &self.name
+    }
+
+    /// A human readable description of the device, if available.
+    pub fn description(&self) -> Option<&CStr> {
+        self.description.as_deref()
+    }
+
+    /// Test if the device supports the given stream direction.
+    pub fn supports(&self, stream: Stream) -> bool {
+        match self.direction {
+            Some(direction) => direction == stream,
+            None => true,
+        }
+    }
+}
+
+/// Describe all available PCM devices in a backend-agnostic manner.
+///
+/// See [crate::devices].
+pub(crate) fn device_infos() -> Result<Vec<DeviceInfo>> {
+    let mut infos = Vec::new();
+
+    for device in devices()? {
+        let direction = match (
+            device.supports(Stream::Playback),
+            device.supports(Stream::Capture),
+        ) {
+            (true, true) => Direction::Duplex,
+            (true, false) => Direction::Output,
+            (false, true) => Direction::Input,
+            (false, false) => continue,
+        };
+
+        let id = device.name().to_string_lossy().into_owned();
+
+        // NB: descriptions commonly span multiple lines, like the name of the
+        // card followed by the name of the device.
+        let name = match device.description() {
+            Some(description) => description
+                .to_string_lossy()
+                .lines()
+                .collect::<Vec<_>>()
+                .join(", "),
+            None => id.clone(),
+        };
+
+        let stream = if direction.is_output() {
+            Stream::Playback
+        } else {
+            Stream::Capture
+        };
+
+        // NB: devices might be busy or otherwise fail to open, in which case
+        // they're still listed but without any nominal formats.
+        let capabilities =
+            Pcm::open_nonblocking(device.name(), stream).and_then(|mut pcm| pcm.capabilities());
+
+        let (formats, channels, sample_rates) = match capabilities {
+            Ok(caps) => {
+                let mut formats = Vec::new();
+
+                for format in caps.formats.iter().filter_map(|f| f.sample_format()) {
+                    if !formats.contains(&format) {
+                        formats.push(format);
+                    }
+                }
+
+                let channels = (caps.channels_min as usize, caps.channels_max as usize);
+                let sample_rates = (caps.rate_min, caps.rate_max);
+                (formats, Some(channels), Some(sample_rates))
+            }
+            Err(..) => (Vec::new(), None, None),
+        };
+
+        infos.push(DeviceInfo {
+            is_default: id == "default",
+            id,
+            name,
+            direction,
+            formats,
+            channels,
+            sample_rates,
+        });
+    }
+
+    Ok(infos)
+}
+
+/// Get the given hint, if present.
+///
+/// # Safety
+///
+/// The `hint` must be a valid hint as returned by `snd_device_name_hint` and
+/// `id` must be nul-terminated.
+unsafe fn get_hint(hint: *const c::c_void, id: &[u8]) -> Option<CString> {
+    let ptr = alsa::snd_device_name_get_hint(hint, id.as_ptr() as *const c::c_char);
+
+    if ptr.is_null() {
+        return None;
+    }
+
+    Some(CString::from_raw(ptr))
+}
@@ -0,0 +1,124 @@
+use core::marker;
+
+use crate::alsa::stream::configure;
+use crate::alsa::{Error, Pcm, Result, Sample, Stream};
+use crate::stream::{DriftEstimator, StreamSpec};
+
+/// A portable duplex stream using ALSA.
+///
+/// The capture and playback devices are linked when possible so that they're
+/// started together. Underruns are automatically recovered from.
+///
+/// See [DuplexStream][crate::stream::DuplexStream].
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::alsa;
+/// use audio_device::stream::{DuplexStream, StreamSpec};
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let mut stream = alsa::DuplexStream::<i16>::open_default(StreamSpec::new(2, 48000))?;
+/// let spec = stream.input_spec();
+/// let mut samples = vec![0i16; spec.channels * 256];
+///
+/// loop {
+///     stream.read(audio::wrap::interleaved(&mut samples[..], spec.channels))?;
+///     stream.write(audio::wrap::interleaved(&samples[..], spec.channels))?;
+///     println!("resample ratio: {}", stream.resample_ratio()?);
+/// }
+/// # }
+/// ```
+pub struct DuplexStream<T> {
+    capture: Pcm,
+    playback: Pcm,
+    input_spec: StreamSpec,
+    output_spec: StreamSpec,
+    // Number of frames read from the capture device.
+    read: u64,
+    // Number of frames written to the playback device.
+    written: u64,
+    drift: DriftEstimator,
+    _marker: marker::PhantomData<T>,
+}
+
+impl<T> crate::stream::DuplexStream for DuplexStream<T>
+where
+    T: Sample,
+{
+    type Sample = T;
+    type Error = Error;
+
+    fn open_default(spec: StreamSpec) -> Result<Self> {
+        let mut capture = Pcm::open_default(Stream::Capture)?;
+        let mut playback = Pcm::open_default(Stream::Playback)?;
+
+        let input_spec = configure::<T>(&mut capture, spec)?;
+        let output_spec = configure::<T>(&mut playback, spec)?;
+
+        // NB: linking is only supported by devices which share a clock, like
+        // both directions of the same card.
+        if let Err(error) = capture.link(&playback) {
+            tracing::trace!(%error, "could not link capture and playback");
+        }
+
+        Ok(Self {
+            capture,
+            playback,
+            input_spec,
+            output_spec,
+            read: 0,
+            written: 0,
+            drift: DriftEstimator::new(input_spec.sample_rate, output_spec.sample_rate),
+            _marker: marker::PhantomData,
+        })
+    }
+
+    fn input_spec(&self) -> StreamSpec {
+        self.input_spec
+    }
+
+    fn output_spec(&self) -> StreamSpec {
+        self.output_spec
+    }
+
+    fn read<B>(&mut self, mut buf: B) -> Result<()>
+    where
+        B: audio_core::WriteBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBufMut<Sample = T>,
+    {
+        let remaining = buf.remaining_mut();
+        let mut reader = self.capture.reader::<T>()?;
+        reader.read_interleaved(&mut buf)?;
+        self.read += remaining.saturating_sub(buf.remaining_mut()) as u64;
+        Ok(())
+    }
+
+    fn write<B>(&mut self, mut buf: B) -> Result<()>
+    where
+        B: audio_core::ReadBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBuf<Sample = T>,
+    {
+        let remaining = buf.remaining();
+        let mut writer = self.playback.writer::<T>()?;
+        writer.set_recover(true);
+        writer.write_all_interleaved(&mut buf)?;
+        self.written += remaining.saturating_sub(buf.remaining()) as u64;
+        Ok(())
+    }
+
+    fn resample_ratio(&mut self) -> Result<f64> {
+        // NB: the delay of the capture device is the number of frames which
+        // have been captured but not yet read, while for the playback device
+        // it's the number of frames written but not yet played.
+        let input = self
+            .read
+            .saturating_add(self.capture.delay()?.max(0) as u64);
+        let output = self
+            .written
+            .saturating_sub(self.playback.delay()?.max(0) as u64);
+        Ok(self.drift.update(input, output))
+    }
+}
@@ -1,6 +1,8 @@
-use crate::alsa::Result;
+use crate::alsa::{Error, Result};
 use crate::libc as c;
 use alsa_sys as alsa;
+use audio_core::{Endian, SampleFormat};
+use std::convert::TryFrom;
 use std::fmt;
 
 macro_rules! decl_enum {
@@ -16,6 +18,7 @@ macro_rules! decl_enum {
     ) => {
         $(#[doc = $doc])*
         #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize))]
         #[non_exhaustive]
         #[repr($ty)]
         $vis enum $name {
@@ -27,6 +30,10 @@ macro_rules! decl_enum {
         }
 
         impl $name {
+            /// All known variants.
+            #[allow(dead_code)]
+            $vis const ALL: &'static [Self] = &[$(Self::$a,)*];
+
             /// Parse the given enum from a value.
             $vis fn from_value(value: $ty) -> Option<Self> {
                 Some(match value {
@@ -170,6 +177,79 @@ impl Format {
     pub fn physical_width(self) -> Result<usize> {
         unsafe { Ok(errno!(alsa::snd_pcm_format_physical_width(self as c::c_int))? as usize) }
     }
+
+    /// Get the backend-agnostic [SampleFormat] corresponding to this format,
+    /// if there is one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio_core::{Endian, SampleFormat};
+    /// use audio_device::alsa;
+    ///
+    /// assert_eq!(alsa::Format::S16LE.sample_format(), Some(SampleFormat::I16(Endian::Little)));
+    /// assert_eq!(alsa::Format::FloatBE.sample_format(), Some(SampleFormat::F32(Endian::Big)));
+    /// assert_eq!(alsa::Format::MuLaw.sample_format(), None);
+    /// ```
+    pub fn sample_format(self) -> Option<SampleFormat> {
+        Some(match self {
+            Self::U8 => SampleFormat::U8,
+            Self::S16LE => SampleFormat::I16(Endian::Little),
+            Self::S16BE => SampleFormat::I16(Endian::Big),
+            Self::S24LE => SampleFormat::I24(Endian::Little),
+            Self::S24BE => SampleFormat::I24(Endian::Big),
+            Self::S243LE => SampleFormat::I24Packed(Endian::Little),
+            Self::S243BE => SampleFormat::I24Packed(Endian::Big),
+            Self::S32LE => SampleFormat::I32(Endian::Little),
+            Self::S32BE => SampleFormat::I32(Endian::Big),
+            Self::FloatLE => SampleFormat::F32(Endian::Little),
+            Self::FloatBE => SampleFormat::F32(Endian::Big),
+            Self::Float64LE => SampleFormat::F64(Endian::Little),
+            Self::Float64BE => SampleFormat::F64(Endian::Big),
+            _ => return None,
+        })
+    }
+}
+
+/// Convert a backend-agnostic [SampleFormat] into its ALSA [Format].
+///
+/// Errors with [Error::UnsupportedSampleFormat] if ALSA has no corresponding
+/// format.
+///
+/// # Examples
+///
+/// ```
+/// use std::convert::TryFrom;
+///
+/// use audio_core::{Endian, SampleFormat};
+/// use audio_device::alsa;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// assert_eq!(alsa::Format::try_from(SampleFormat::I24(Endian::Little))?, alsa::Format::S24LE);
+/// assert_eq!(alsa::Format::try_from(SampleFormat::F64(Endian::Big))?, alsa::Format::Float64BE);
+/// # Ok(()) }
+/// ```
+impl TryFrom<SampleFormat> for Format {
+    type Error = Error;
+
+    fn try_from(format: SampleFormat) -> Result<Self> {
+        Ok(match format {
+            SampleFormat::U8 => Self::U8,
+            SampleFormat::I16(Endian::Little) => Self::S16LE,
+            SampleFormat::I16(Endian::Big) => Self::S16BE,
+            SampleFormat::I24(Endian::Little) => Self::S24LE,
+            SampleFormat::I24(Endian::Big) => Self::S24BE,
+            SampleFormat::I24Packed(Endian::Little) => Self::S243LE,
+            SampleFormat::I24Packed(Endian::Big) => Self::S243BE,
+            SampleFormat::I32(Endian::Little) => Self::S32LE,
+            SampleFormat::I32(Endian::Big) => Self::S32BE,
+            SampleFormat::F32(Endian::Little) => Self::FloatLE,
+            SampleFormat::F32(Endian::Big) => Self::FloatBE,
+            SampleFormat::F64(Endian::Little) => Self::Float64LE,
+            SampleFormat::F64(Endian::Big) => Self::Float64BE,
+            _ => return Err(Error::UnsupportedSampleFormat(format)),
+        })
+    }
 }
 
 decl_enum! {
@@ -185,6 +265,21 @@ decl_enum! {
     }
 }
 
+decl_enum! {
+    /// The direction of a raw MIDI port.
+    ///
+    /// See [rawmidi::Port::stream][super::rawmidi::Port::stream].
+    #[repr(u32)]
+    pub enum RawMidiStream {
+        /// An output port, which MIDI data can be written to. Corresponds to
+        /// `SND_RAWMIDI_STREAM_OUTPUT`.
+        Output = SND_RAWMIDI_STREAM_OUTPUT,
+        /// An input port, which MIDI data can be read from. Corresponds to
+        /// `SND_RAWMIDI_STREAM_INPUT`.
+        Input = SND_RAWMIDI_STREAM_INPUT,
+    }
+}
+
 decl_enum! {
     /// Defines how the underlying device is accessed.
     #[repr(u32)]
@@ -202,6 +297,24 @@ decl_enum! {
     }
 }
 
+decl_enum! {
+    /// Defines the subformat of a stream, which describes how samples are
+    /// laid out within the format.
+    ///
+    /// See [HardwareParametersMut::set_subformat][super::HardwareParametersMut::set_subformat].
+    #[repr(u32)]
+    pub enum Subformat {
+        /// Standard.
+        Standard = SND_PCM_SUBFORMAT_STD,
+        /// Maximum number of most significant bits supported by the device.
+        MsbitsMax = SND_PCM_SUBFORMAT_MSBITS_MAX,
+        /// 20 most significant bits.
+        Msbits20 = SND_PCM_SUBFORMAT_MSBITS_20,
+        /// 24 most significant bits.
+        Msbits24 = SND_PCM_SUBFORMAT_MSBITS_24,
+    }
+}
+
 decl_enum! {
     /// Defines if timestamps are enabled or not.
     #[repr(u32)]
@@ -246,3 +359,40 @@ decl_enum! {
         Sequencer = SND_CTL_ELEM_IFACE_SEQUENCER,
     }
 }
+
+decl_enum! {
+    /// The type of the value held by a control element.
+    #[repr(u32)]
+    pub enum ControlElementType {
+        /// Invalid type.
+        None = SND_CTL_ELEM_TYPE_NONE,
+        /// Boolean switch.
+        Boolean = SND_CTL_ELEM_TYPE_BOOLEAN,
+        /// Integer.
+        Integer = SND_CTL_ELEM_TYPE_INTEGER,
+        /// Enumerated items.
+        Enumerated = SND_CTL_ELEM_TYPE_ENUMERATED,
+        /// Raw bytes.
+        Bytes = SND_CTL_ELEM_TYPE_BYTES,
+        /// IEC958 (S/PDIF) settings.
+        Iec958 = SND_CTL_ELEM_TYPE_IEC958,
+        /// 64-bit integer.
+        Integer64 = SND_CTL_ELEM_TYPE_INTEGER64,
+    }
+}
+
+decl_enum! {
+    /// The type of a channel map as returned by
+    /// [Pcm::query_channel_maps][super::Pcm::query_channel_maps].
+    #[repr(u32)]
+    pub enum ChannelMapType {
+        /// Unspecified channel position.
+        None = SND_CHMAP_TYPE_NONE,
+        /// Fixed channel position.
+        Fixed = SND_CHMAP_TYPE_FIXED,
+        /// Freely swappable channel position.
+        Var = SND_CHMAP_TYPE_VAR,
+        /// Pair-wise swappable channel position.
+        Paired = SND_CHMAP_TYPE_PAIRED,
+    }
+}
@@ -0,0 +1,74 @@
+use crate::libc as c;
+use alsa_sys as alsa;
+use std::borrow::Cow;
+use std::ffi::CStr;
+
+/// Route error messages from alsa-lib into [tracing] events instead of
+/// having them written to stderr.
+///
+/// Each message is emitted as a warning with the alsa-lib `file`, `line`,
+/// `function` and `errno` which raised it attached as fields.
+///
+/// The handler is installed globally through `snd_lib_error_set_handler`, so
+/// it applies to ALSA calls made on any thread. alsa-lib invokes it
+/// synchronously on the thread making the failing call, so events are
+/// emitted inside whichever [tracing] span is active on that thread. Opening
+/// a [Pcm][crate::alsa::Pcm] enters a span carrying the name of the device,
+/// and callers can enter their own spans to attach further device context.
+///
+/// A thread which has installed a handler of its own through
+/// `snd_lib_error_set_local` keeps using it instead.
+///
+/// See [reset_error_handler] to restore the default behavior.
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::alsa;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// tracing_subscriber::fmt::init();
+/// alsa::set_tracing_error_handler();
+///
+/// // errors raised by alsa-lib are now logged through tracing.
+/// let pcm = alsa::Pcm::open(&std::ffi::CString::new("missing")?, alsa::Stream::Playback);
+/// assert!(pcm.is_err());
+/// # Ok(()) }
+/// ```
+pub fn set_tracing_error_handler() {
+    unsafe {
+        alsa::audio_device_alsa_set_error_handler(Some(handler));
+    }
+}
+
+/// Restore the default alsa-lib error handler, which writes error messages to
+/// stderr.
+///
+/// Like [set_tracing_error_handler] this applies to all threads.
+pub fn reset_error_handler() {
+    unsafe {
+        alsa::audio_device_alsa_set_error_handler(None);
+    }
+}
+
+unsafe extern "C" fn handler(
+    file: *const c::c_char,
+    line: c::c_int,
+    function: *const c::c_char,
+    err: c::c_int,
+    message: *const c::c_char,
+) {
+    let message = lossy(message);
+    let file = lossy(file);
+    let function = lossy(function);
+
+    tracing::warn!(%file, line, %function, errno = err, "{}", message.trim_end());
+}
+
+unsafe fn lossy<'a>(ptr: *const c::c_char) -> Cow<'a, str> {
+    if ptr.is_null() {
+        return Cow::Borrowed("");
+    }
+
+    CStr::from_ptr(ptr).to_string_lossy()
+}
@@ -1,4 +1,4 @@
-use crate::alsa::{Access, AccessMask, Direction, Error, Format, FormatMask, Result};
+use crate::alsa::{Access, AccessMask, Direction, Error, Format, FormatMask, Result, Subformat};
 use crate::libc as c;
 use alsa_sys as alsa;
 use std::mem;
@@ -327,6 +327,36 @@ impl HardwareParameters {
         }
     }
 
+    /// Extract subformat from a configuration space.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// let hw = pcm.hardware_parameters()?;
+    ///
+    /// println!("{}", hw.subformat()?);
+    /// # Ok(()) }
+    /// ```
+    pub fn subformat(&self) -> Result<Subformat> {
+        unsafe {
+            let mut subformat = mem::MaybeUninit::uninit();
+
+            errno!(alsa::snd_pcm_hw_params_get_subformat(
+                self.handle.as_ptr(),
+                subformat.as_mut_ptr(),
+            ))?;
+
+            let subformat = subformat.assume_init();
+            let subformat =
+                Subformat::from_value(subformat).ok_or(Error::BadSubformat(subformat))?;
+            Ok(subformat)
+        }
+    }
+
     /// Get access mask from a configuration space.
     ///
     /// # Examples
@@ -3387,22 +3417,126 @@ impl<'a> HardwareParametersMut<'a> {
         }
     }
 
-    // Note: subformat related things is not implemented.
+    /// Restrict a configuration space to contain only one subformat.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// let mut hw = pcm.hardware_parameters_any()?;
+    ///
+    /// hw.set_format(alsa::Format::S32LE)?;
+    ///
+    /// if hw.test_subformat(alsa::Subformat::Msbits24)? {
+    ///     hw.set_subformat(alsa::Subformat::Msbits24)?;
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn set_subformat(&mut self, subformat: Subformat) -> Result<()> {
+        unsafe {
+            errno!(alsa::snd_pcm_hw_params_set_subformat(
+                self.pcm.as_mut(),
+                self.base.handle.as_mut(),
+                subformat as c::c_uint
+            ))?;
 
-    // int snd_pcm_hw_params_get_subformat (const snd_pcm_hw_params_t *params, snd_pcm_subformat_t *subformat)
-    // Extract subformat from a configuration space.
+            Ok(())
+        }
+    }
 
-    // int snd_pcm_hw_params_test_subformat (snd_pcm_t *pcm, snd_pcm_hw_params_t *params, snd_pcm_subformat_t subformat)
-    // Verify if a subformat is available inside a configuration space for a PCM.
+    /// Verify if a subformat is available inside a configuration space for a
+    /// PCM.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// let mut hw = pcm.hardware_parameters_any()?;
+    ///
+    /// println!("{}", hw.test_subformat(alsa::Subformat::Standard)?);
+    /// # Ok(()) }
+    /// ```
+    pub fn test_subformat(&mut self, subformat: Subformat) -> Result<bool> {
+        unsafe {
+            let result = alsa::snd_pcm_hw_params_test_subformat(
+                self.pcm.as_mut(),
+                self.base.handle.as_mut(),
+                subformat as c::c_uint,
+            );
+
+            Ok(result == 0)
+        }
+    }
+
+    /// Restrict a configuration space to contain only its first subformat.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// let mut hw = pcm.hardware_parameters_any()?;
+    ///
+    /// println!("{}", hw.set_subformat_first()?);
+    /// # Ok(()) }
+    /// ```
+    pub fn set_subformat_first(&mut self) -> Result<Subformat> {
+        unsafe {
+            let mut subformat = mem::MaybeUninit::uninit();
+
+            errno!(alsa::snd_pcm_hw_params_set_subformat_first(
+                self.pcm.as_mut(),
+                self.base.handle.as_mut(),
+                subformat.as_mut_ptr(),
+            ))?;
 
-    // int snd_pcm_hw_params_set_subformat (snd_pcm_t *pcm, snd_pcm_hw_params_t *params, snd_pcm_subformat_t subformat)
-    // Restrict a configuration space to contain only one subformat.
+            let subformat = subformat.assume_init();
+            let subformat =
+                Subformat::from_value(subformat).ok_or(Error::BadSubformat(subformat))?;
+            Ok(subformat)
+        }
+    }
 
-    // int snd_pcm_hw_params_set_subformat_first (snd_pcm_t *pcm, snd_pcm_hw_params_t *params, snd_pcm_subformat_t *subformat)
-    // Restrict a configuration space to contain only its first subformat.
+    /// Restrict a configuration space to contain only its last subformat.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// let mut hw = pcm.hardware_parameters_any()?;
+    ///
+    /// println!("{}", hw.set_subformat_last()?);
+    /// # Ok(()) }
+    /// ```
+    pub fn set_subformat_last(&mut self) -> Result<Subformat> {
+        unsafe {
+            let mut subformat = mem::MaybeUninit::uninit();
+
+            errno!(alsa::snd_pcm_hw_params_set_subformat_last(
+                self.pcm.as_mut(),
+                self.base.handle.as_mut(),
+                subformat.as_mut_ptr(),
+            ))?;
+
+            let subformat = subformat.assume_init();
+            let subformat =
+                Subformat::from_value(subformat).ok_or(Error::BadSubformat(subformat))?;
+            Ok(subformat)
+        }
+    }
 
-    // int snd_pcm_hw_params_set_subformat_last (snd_pcm_t *pcm, snd_pcm_hw_params_t *params, snd_pcm_subformat_t *subformat)
-    // Restrict a configuration space to contain only its last subformat.
+    // Note: subformat mask related things is not implemented.
 
     // int snd_pcm_hw_params_set_subformat_mask (snd_pcm_t *pcm, snd_pcm_hw_params_t *params, snd_pcm_subformat_mask_t *mask)
     // Restrict a configuration space to contain only a set of subformats.
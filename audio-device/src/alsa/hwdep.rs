@@ -0,0 +1,257 @@
+//! Bindings for the ALSA hardware dependent (hwdep) interface.
+//!
+//! The hwdep interface gives access to device specific functionality which
+//! isn't exposed through PCMs or controls, like firmware loading or metering
+//! on some professional interfaces. The protocol spoken over it is entirely
+//! up to the driver.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use audio_device::alsa::hwdep;
+//! use std::ffi::CString;
+//!
+//! # fn main() -> anyhow::Result<()> {
+//! let name = CString::new("hw:0,0")?;
+//! let mut device = hwdep::Hwdep::open(&name)?;
+//!
+//! let info = device.info()?;
+//! println!("{} (interface {})", info.name().to_str()?, info.interface());
+//! # Ok(()) }
+//! ```
+
+use crate::alsa::{Error, Result};
+use crate::libc as c;
+use crate::unix::Errno;
+use alsa_sys as alsa;
+use std::ffi::{CStr, CString};
+use std::mem;
+use std::ptr;
+
+/// An opened hardware dependent device.
+///
+/// See [Hwdep::open].
+pub struct Hwdep {
+    tag: ste::Tag,
+    handle: ptr::NonNull<alsa::snd_hwdep_t>,
+}
+
+impl Hwdep {
+    /// Open the hardware dependent device identified by name, which is
+    /// typically of the form `hw:<card>,<device>`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa::hwdep;
+    /// use std::ffi::CString;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let name = CString::new("hw:0,0")?;
+    /// let device = hwdep::Hwdep::open(&name)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn open(name: &CStr) -> Result<Self> {
+        unsafe {
+            let mut handle = mem::MaybeUninit::uninit();
+
+            errno!(alsa::snd_hwdep_open(
+                handle.as_mut_ptr(),
+                name.as_ptr(),
+                c::O_RDWR
+            ))?;
+
+            Ok(Self {
+                tag: ste::Tag::current_thread(),
+                handle: ptr::NonNull::new_unchecked(handle.assume_init()),
+            })
+        }
+    }
+
+    /// Set the device to nonblocking or blocking mode.
+    ///
+    /// In nonblocking mode [Hwdep::read] and [Hwdep::write] return
+    /// [Error::WouldBlock] instead of blocking.
+    pub fn set_nonblocking(&mut self, nonblocking: bool) -> Result<()> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            errno!(alsa::snd_hwdep_nonblock(
+                self.handle.as_ptr(),
+                nonblocking as c::c_int
+            ))?;
+            Ok(())
+        }
+    }
+
+    /// Get information about the device.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa::hwdep;
+    /// use std::ffi::CString;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let name = CString::new("hw:0,0")?;
+    /// let mut device = hwdep::Hwdep::open(&name)?;
+    ///
+    /// let info = device.info()?;
+    /// println!("card: {}", info.card());
+    /// println!("device: {}", info.device());
+    /// println!("id: {}", info.id().to_str()?);
+    /// # Ok(()) }
+    /// ```
+    pub fn info(&mut self) -> Result<Info> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            let mut info = mem::MaybeUninit::uninit();
+            errno!(alsa::snd_hwdep_info_malloc(info.as_mut_ptr()))?;
+            let info = info.assume_init();
+
+            if let Err(e) = errno!(alsa::snd_hwdep_info(self.handle.as_ptr(), info)) {
+                alsa::snd_hwdep_info_free(info);
+                return Err(e.into());
+            }
+
+            let out = Info {
+                card: alsa::snd_hwdep_info_get_card(info),
+                device: alsa::snd_hwdep_info_get_device(info),
+                id: CStr::from_ptr(alsa::snd_hwdep_info_get_id(info)).to_owned(),
+                name: CStr::from_ptr(alsa::snd_hwdep_info_get_name(info)).to_owned(),
+                interface: alsa::snd_hwdep_info_get_iface(info),
+            };
+
+            alsa::snd_hwdep_info_free(info);
+            Ok(out)
+        }
+    }
+
+    /// Read data from the device into the given buffer, returning the number
+    /// of bytes read.
+    pub fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        self.tag.ensure_on_thread();
+
+        let result = unsafe {
+            errno!(alsa::snd_hwdep_read(
+                self.handle.as_ptr(),
+                buf.as_mut_ptr() as *mut c::c_void,
+                buf.len()
+            ))
+        };
+
+        match result {
+            Ok(n) => Ok(n as usize),
+            Err(Errno::EWOULDBLOCK) => Err(Error::WouldBlock),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Write data from the given buffer to the device, returning the number of
+    /// bytes written.
+    pub fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.tag.ensure_on_thread();
+
+        let result = unsafe {
+            errno!(alsa::snd_hwdep_write(
+                self.handle.as_ptr(),
+                buf.as_ptr() as *const c::c_void,
+                buf.len()
+            ))
+        };
+
+        match result {
+            Ok(n) => Ok(n as usize),
+            Err(Errno::EWOULDBLOCK) => Err(Error::WouldBlock),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Perform a driver specific ioctl on the device.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `arg` is valid for the given `request`, as
+    /// defined by the driver of the device.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa::hwdep;
+    /// use std::ffi::CString;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// // A made up request which reads a single integer from the device.
+    /// const GET_VERSION: u32 = 0x80044801;
+    ///
+    /// let name = CString::new("hw:0,0")?;
+    /// let mut device = hwdep::Hwdep::open(&name)?;
+    ///
+    /// let mut version = 0u32;
+    ///
+    /// unsafe {
+    ///     device.ioctl(GET_VERSION, &mut version as *mut u32 as *mut _)?;
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub unsafe fn ioctl(&mut self, request: c::c_uint, arg: *mut c::c_void) -> Result<c::c_int> {
+        self.tag.ensure_on_thread();
+        Ok(errno!(alsa::snd_hwdep_ioctl(
+            self.handle.as_ptr(),
+            request,
+            arg
+        ))?)
+    }
+}
+
+// Safety: [Hwdep] is tagged with the thread its created it and is ensured not
+// to leave it.
+unsafe impl Send for Hwdep {}
+
+impl Drop for Hwdep {
+    fn drop(&mut self) {
+        unsafe { alsa::snd_hwdep_close(self.handle.as_ptr()) };
+    }
+}
+
+/// Information about a hardware dependent device.
+///
+/// See [Hwdep::info].
+#[derive(Debug, Clone)]
+pub struct Info {
+    card: c::c_int,
+    device: c::c_uint,
+    id: CString,
+    name: CString,
+    interface: c::c_uint,
+}
+
+impl Info {
+    /// The index of the card the device belongs to.
+    pub fn card(&self) -> c::c_int {
+        self.card
+    }
+
+    /// The index of the device.
+    pub fn device(&self) -> c::c_uint {
+        self.device
+    }
+
+    /// The identifier of the device.
+    pub fn id(&self) -> &CStr {
+        &self.id
+    }
+
+    /// The name of the device.
+    pub fn name(&self) -> &CStr {
+        &self.name
+    }
+
+    /// The interface implemented by the device, which identifies the
+    /// protocol spoken over it. This corresponds to one of the
+    /// `SND_HWDEP_IFACE_*` constants.
+    pub fn interface(&self) -> c::c_uint {
+        self.interface
+    }
+}
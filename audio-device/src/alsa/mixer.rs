@@ -0,0 +1,354 @@
+use crate::alsa::Result;
+use crate::libc as c;
+use alsa_sys as alsa;
+use std::ffi::CStr;
+use std::marker;
+use std::mem;
+use std::ptr;
+
+/// A mixer associated with a device.
+///
+/// This provides access to the simple mixer elements of a device, which is
+/// what tools like `alsamixer` present.
+///
+/// See [Mixer::open].
+pub struct Mixer {
+    tag: ste::Tag,
+    handle: ptr::NonNull<alsa::snd_mixer_t>,
+}
+
+impl Mixer {
+    /// Open the mixer for the given device and load its simple elements.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    /// use std::ffi::CString;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let name = CString::new("hw:0")?;
+    /// let mixer = alsa::Mixer::open(&name)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn open(name: &CStr) -> Result<Self> {
+        unsafe {
+            let mut handle = mem::MaybeUninit::uninit();
+            errno!(alsa::snd_mixer_open(handle.as_mut_ptr(), 0))?;
+
+            // NB: construct early so that the mixer is closed on errors.
+            let mut mixer = Self {
+                tag: ste::Tag::current_thread(),
+                handle: ptr::NonNull::new_unchecked(handle.assume_init()),
+            };
+
+            errno!(alsa::snd_mixer_attach(mixer.handle.as_mut(), name.as_ptr()))?;
+            errno!(alsa::snd_mixer_selem_register(
+                mixer.handle.as_mut(),
+                ptr::null_mut(),
+                ptr::null_mut()
+            ))?;
+            errno!(alsa::snd_mixer_load(mixer.handle.as_mut()))?;
+            Ok(mixer)
+        }
+    }
+
+    /// Open the mixer for the default device.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mixer = alsa::Mixer::open_default()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn open_default() -> Result<Self> {
+        static DEFAULT: &[u8] = b"default\0";
+        unsafe { Self::open(CStr::from_bytes_with_nul_unchecked(DEFAULT)) }
+    }
+
+    /// Handle pending mixer events, updating the state of its elements.
+    ///
+    /// Returns the number of events handled.
+    pub fn handle_events(&mut self) -> Result<usize> {
+        self.tag.ensure_on_thread();
+
+        unsafe { Ok(errno!(alsa::snd_mixer_handle_events(self.handle.as_mut()))? as usize) }
+    }
+
+    /// Construct an iterator over the simple elements of the mixer.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mixer = alsa::Mixer::open_default()?;
+    ///
+    /// for element in mixer.elements() {
+    ///     println!("{} ({})", element.name().to_str()?, element.index());
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn elements(&self) -> MixerElements<'_> {
+        self.tag.ensure_on_thread();
+
+        MixerElements {
+            current: unsafe { alsa::snd_mixer_first_elem(self.handle.as_ptr()) },
+            _marker: marker::PhantomData,
+        }
+    }
+
+    /// Find the simple element with the given name and index.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    /// use std::ffi::CString;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mixer = alsa::Mixer::open_default()?;
+    /// let name = CString::new("Master")?;
+    ///
+    /// if let Some(master) = mixer.find(&name, 0) {
+    ///     let (min, max) = master.playback_volume_range()?;
+    ///     master.set_playback_volume(min + (max - min) / 2)?;
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn find(&self, name: &CStr, index: u32) -> Option<MixerElement<'_>> {
+        self.elements()
+            .find(|e| e.name() == name && e.index() == index)
+    }
+}
+
+// Safety: [Mixer] is tagged with the thread its created it and is ensured not
+// to leave it.
+unsafe impl Send for Mixer {}
+
+impl Drop for Mixer {
+    fn drop(&mut self) {
+        unsafe { alsa::snd_mixer_close(self.handle.as_ptr()) };
+    }
+}
+
+/// An iterator over the simple elements of a [Mixer].
+///
+/// See [Mixer::elements].
+pub struct MixerElements<'a> {
+    current: *mut alsa::snd_mixer_elem_t,
+    _marker: marker::PhantomData<&'a Mixer>,
+}
+
+impl<'a> Iterator for MixerElements<'a> {
+    type Item = MixerElement<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let handle = ptr::NonNull::new(self.current)?;
+        self.current = unsafe { alsa::snd_mixer_elem_next(self.current) };
+
+        Some(MixerElement {
+            handle,
+            _marker: marker::PhantomData,
+        })
+    }
+}
+
+/// A simple mixer element, like `Master` or `Capture`.
+///
+/// Volumes are reported and set in the raw range of the element, see
+/// [MixerElement::playback_volume_range].
+pub struct MixerElement<'a> {
+    handle: ptr::NonNull<alsa::snd_mixer_elem_t>,
+    _marker: marker::PhantomData<&'a Mixer>,
+}
+
+impl MixerElement<'_> {
+    /// The name of the element.
+    pub fn name(&self) -> &CStr {
+        unsafe { CStr::from_ptr(alsa::snd_mixer_selem_get_name(self.handle.as_ptr())) }
+    }
+
+    /// The index of the element, used to distinguish elements with the same
+    /// name.
+    pub fn index(&self) -> u32 {
+        unsafe { alsa::snd_mixer_selem_get_index(self.handle.as_ptr()) }
+    }
+
+    /// Test if the element has a playback volume control.
+    pub fn has_playback_volume(&self) -> bool {
+        unsafe { alsa::snd_mixer_selem_has_playback_volume(self.handle.as_ptr()) != 0 }
+    }
+
+    /// Test if the element has a capture volume control.
+    pub fn has_capture_volume(&self) -> bool {
+        unsafe { alsa::snd_mixer_selem_has_capture_volume(self.handle.as_ptr()) != 0 }
+    }
+
+    /// Test if the element has a playback switch, which is used for muting.
+    pub fn has_playback_switch(&self) -> bool {
+        unsafe { alsa::snd_mixer_selem_has_playback_switch(self.handle.as_ptr()) != 0 }
+    }
+
+    /// Test if the element has a capture switch, which is used for muting.
+    pub fn has_capture_switch(&self) -> bool {
+        unsafe { alsa::snd_mixer_selem_has_capture_switch(self.handle.as_ptr()) != 0 }
+    }
+
+    /// Get the range of playback volumes as `(min, max)`.
+    pub fn playback_volume_range(&self) -> Result<(c::c_long, c::c_long)> {
+        unsafe {
+            let mut min = mem::MaybeUninit::uninit();
+            let mut max = mem::MaybeUninit::uninit();
+            errno!(alsa::snd_mixer_selem_get_playback_volume_range(
+                self.handle.as_ptr(),
+                min.as_mut_ptr(),
+                max.as_mut_ptr()
+            ))?;
+            Ok((min.assume_init(), max.assume_init()))
+        }
+    }
+
+    /// Get the range of capture volumes as `(min, max)`.
+    pub fn capture_volume_range(&self) -> Result<(c::c_long, c::c_long)> {
+        unsafe {
+            let mut min = mem::MaybeUninit::uninit();
+            let mut max = mem::MaybeUninit::uninit();
+            errno!(alsa::snd_mixer_selem_get_capture_volume_range(
+                self.handle.as_ptr(),
+                min.as_mut_ptr(),
+                max.as_mut_ptr()
+            ))?;
+            Ok((min.assume_init(), max.assume_init()))
+        }
+    }
+
+    /// Get the playback volume of the first channel of the element.
+    pub fn playback_volume(&self) -> Result<c::c_long> {
+        unsafe {
+            let mut value = mem::MaybeUninit::uninit();
+            errno!(alsa::snd_mixer_selem_get_playback_volume(
+                self.handle.as_ptr(),
+                alsa::SND_MIXER_SCHN_MONO,
+                value.as_mut_ptr()
+            ))?;
+            Ok(value.assume_init())
+        }
+    }
+
+    /// Get the capture volume of the first channel of the element.
+    pub fn capture_volume(&self) -> Result<c::c_long> {
+        unsafe {
+            let mut value = mem::MaybeUninit::uninit();
+            errno!(alsa::snd_mixer_selem_get_capture_volume(
+                self.handle.as_ptr(),
+                alsa::SND_MIXER_SCHN_MONO,
+                value.as_mut_ptr()
+            ))?;
+            Ok(value.assume_init())
+        }
+    }
+
+    /// Set the playback volume of all channels of the element.
+    pub fn set_playback_volume(&self, volume: c::c_long) -> Result<()> {
+        unsafe {
+            errno!(alsa::snd_mixer_selem_set_playback_volume_all(
+                self.handle.as_ptr(),
+                volume
+            ))?;
+            Ok(())
+        }
+    }
+
+    /// Set the capture volume of all channels of the element.
+    pub fn set_capture_volume(&self, volume: c::c_long) -> Result<()> {
+        unsafe {
+            errno!(alsa::snd_mixer_selem_set_capture_volume_all(
+                self.handle.as_ptr(),
+                volume
+            ))?;
+            Ok(())
+        }
+    }
+
+    /// Test if playback is muted for the first channel of the element.
+    ///
+    /// Elements without a playback switch are never muted.
+    pub fn is_playback_muted(&self) -> Result<bool> {
+        if !self.has_playback_switch() {
+            return Ok(false);
+        }
+
+        unsafe {
+            let mut value = mem::MaybeUninit::uninit();
+            errno!(alsa::snd_mixer_selem_get_playback_switch(
+                self.handle.as_ptr(),
+                alsa::SND_MIXER_SCHN_MONO,
+                value.as_mut_ptr()
+            ))?;
+            // NB: the switch is on when the element is *not* muted.
+            Ok(value.assume_init() == 0)
+        }
+    }
+
+    /// Test if capture is muted for the first channel of the element.
+    ///
+    /// Elements without a capture switch are never muted.
+    pub fn is_capture_muted(&self) -> Result<bool> {
+        if !self.has_capture_switch() {
+            return Ok(false);
+        }
+
+        unsafe {
+            let mut value = mem::MaybeUninit::uninit();
+            errno!(alsa::snd_mixer_selem_get_capture_switch(
+                self.handle.as_ptr(),
+                alsa::SND_MIXER_SCHN_MONO,
+                value.as_mut_ptr()
+            ))?;
+            Ok(value.assume_init() == 0)
+        }
+    }
+
+    /// Mute or unmute playback for all channels of the element.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mixer = alsa::Mixer::open_default()?;
+    ///
+    /// for element in mixer.elements() {
+    ///     if element.has_playback_switch() {
+    ///         element.set_playback_muted(true)?;
+    ///     }
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn set_playback_muted(&self, muted: bool) -> Result<()> {
+        unsafe {
+            errno!(alsa::snd_mixer_selem_set_playback_switch_all(
+                self.handle.as_ptr(),
+                !muted as c::c_int
+            ))?;
+            Ok(())
+        }
+    }
+
+    /// Mute or unmute capture for all channels of the element.
+    pub fn set_capture_muted(&self, muted: bool) -> Result<()> {
+        unsafe {
+            errno!(alsa::snd_mixer_selem_set_capture_switch_all(
+                self.handle.as_ptr(),
+                !muted as c::c_int
+            ))?;
+            Ok(())
+        }
+    }
+}
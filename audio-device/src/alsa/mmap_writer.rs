@@ -0,0 +1,296 @@
+use core::marker;
+use core::ops;
+use core::ptr;
+use core::slice;
+
+use crate::alsa::{ChannelArea, Pcm, Result};
+use ::audio::buf::interleaved::{IterChannels, IterChannelsMut};
+use ::audio::channel::{InterleavedChannel, InterleavedChannelMut};
+use alsa_sys as alsa;
+use audio_core::{Buf, BufMut, ExactSizeBuf, InterleavedBuf, InterleavedBufMut, LayoutHint};
+
+/// A interleaved type-checked PCM writer which writes directly into the
+/// memory-mapped buffer of the device.
+///
+/// See [Pcm::mmap_writer].
+pub struct MmapWriter<'a, T> {
+    pcm: &'a mut Pcm,
+    channels: usize,
+    _marker: marker::PhantomData<T>,
+}
+
+impl<'a, T> MmapWriter<'a, T> {
+    /// Construct a new writer surrounding the given PCM.
+    ///
+    /// # Safety
+    ///
+    /// This constructor assumes that the caller has checked that type `T` is
+    /// appropriate for writing to the given PCM, and that the PCM is
+    /// configured with [Access::MmapInterleaved][crate::alsa::Access::MmapInterleaved].
+    pub(super) unsafe fn new(pcm: &'a mut Pcm, channels: usize) -> Self {
+        Self {
+            pcm,
+            channels,
+            _marker: marker::PhantomData,
+        }
+    }
+
+    /// Begin writing up to `frames` frames directly into the memory-mapped
+    /// buffer of the device.
+    ///
+    /// The returned buffer may contain fewer frames than requested, or none
+    /// at all if the device buffer is full. Written frames are only made
+    /// available to the device once [MmapBufferMut::commit] is called.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio::BufMut;
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// let config = pcm.configure::<f32>().access(alsa::Access::MmapInterleaved).install()?;
+    ///
+    /// let mut writer = pcm.mmap_writer::<f32>()?;
+    ///
+    /// let mut buf = writer.begin(1024)?;
+    /// buf.fill(0.0);
+    /// buf.commit()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn begin(&mut self, frames: usize) -> Result<MmapBufferMut<'_, T>> {
+        // NB: ALSA requires the available frames to be updated before the
+        // mapped area is accessed.
+        let available = self.pcm.available_update()?;
+        let frames = usize::min(frames, available);
+
+        let channels = self.channels;
+        let area = self.pcm.mmap_begin(frames as _)?;
+
+        let data = unsafe {
+            let first = area.area(0);
+
+            debug_assert_eq!(
+                first.first, 0,
+                "interleaved area must start at the first bit"
+            );
+            debug_assert_eq!(
+                first.step as usize,
+                channels * core::mem::size_of::<T>() * 8,
+                "interleaved area must step over all channels"
+            );
+
+            (first.addr as *mut T).add(area.offset() * channels)
+        };
+
+        Ok(MmapBufferMut {
+            len: area.frames() * channels,
+            frames: area.frames(),
+            area,
+            data,
+            channels,
+            _marker: marker::PhantomData,
+        })
+    }
+}
+
+/// A typed mutable view into the memory-mapped buffer of the device.
+///
+/// This dereferences to the interleaved samples of the mapped region, but
+/// also implements [BufMut] and [InterleavedBufMut] so that generic rendering
+/// code can write straight into it.
+///
+/// See [MmapWriter::begin].
+pub struct MmapBufferMut<'a, T> {
+    area: ChannelArea<'a>,
+    data: *mut T,
+    len: usize,
+    frames: usize,
+    channels: usize,
+    _marker: marker::PhantomData<&'a mut [T]>,
+}
+
+impl<T> MmapBufferMut<'_, T> {
+    /// The number of channels in the mapped region.
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// The number of frames in the mapped region.
+    ///
+    /// This is reduced if the buffer is shrunk through
+    /// [InterleavedBufMut::set_interleaved_topology].
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// Commit all frames in the buffer, making them available to the device.
+    ///
+    /// This starts the PCM if it's been prepared but not yet started.
+    /// Dropping the buffer without committing discards anything written to
+    /// it.
+    pub fn commit(self) -> Result<usize> {
+        let frames = self.frames;
+        self.commit_frames(frames)
+    }
+
+    /// Commit the first `frames` frames in the buffer, making them available
+    /// to the device.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `frames` is larger than [MmapBufferMut::frames].
+    pub fn commit_frames(mut self, frames: usize) -> Result<usize> {
+        assert!(frames <= self.frames, "committed frames out of bounds");
+
+        let committed = self.area.commit(frames)?;
+
+        unsafe {
+            let pcm = self.area.pcm.as_mut();
+
+            if alsa::snd_pcm_state(pcm) == alsa::SND_PCM_STATE_PREPARED {
+                errno!(alsa::snd_pcm_start(pcm))?;
+            }
+        }
+
+        Ok(committed)
+    }
+}
+
+impl<T> ops::Deref for MmapBufferMut<'_, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { slice::from_raw_parts(self.data, self.len) }
+    }
+}
+
+impl<T> ops::DerefMut for MmapBufferMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { slice::from_raw_parts_mut(self.data, self.len) }
+    }
+}
+
+impl<T> Buf for MmapBufferMut<'_, T>
+where
+    T: Copy,
+{
+    type Sample = T;
+
+    type Channel<'this>
+        = InterleavedChannel<'this, T>
+    where
+        Self: 'this;
+
+    type IterChannels<'this>
+        = IterChannels<'this, T>
+    where
+        Self: 'this;
+
+    fn frames_hint(&self) -> Option<usize> {
+        Some(self.frames)
+    }
+
+    fn channels(&self) -> usize {
+        self.channels
+    }
+
+    fn layout_hint(&self) -> LayoutHint {
+        LayoutHint::Interleaved
+    }
+
+    fn get_channel(&self, channel: usize) -> Option<Self::Channel<'_>> {
+        InterleavedChannel::from_slice(self, channel, self.channels)
+    }
+
+    fn iter_channels(&self) -> Self::IterChannels<'_> {
+        ::audio::wrap::interleaved(&self[..], self.channels).into_iter()
+    }
+}
+
+impl<T> BufMut for MmapBufferMut<'_, T>
+where
+    T: Copy,
+{
+    type ChannelMut<'this>
+        = InterleavedChannelMut<'this, T>
+    where
+        Self: 'this;
+
+    type IterChannelsMut<'this>
+        = IterChannelsMut<'this, T>
+    where
+        Self: 'this;
+
+    fn get_channel_mut(&mut self, channel: usize) -> Option<Self::ChannelMut<'_>> {
+        let channels = self.channels;
+        InterleavedChannelMut::from_slice(self, channel, channels)
+    }
+
+    fn copy_channel(&mut self, from: usize, to: usize) {
+        let channels = self.channels;
+
+        if from >= channels || to >= channels {
+            return;
+        }
+
+        for frame in self.chunks_exact_mut(channels) {
+            frame[to] = frame[from];
+        }
+    }
+
+    fn iter_channels_mut(&mut self) -> Self::IterChannelsMut<'_> {
+        let channels = self.channels;
+        ::audio::wrap::interleaved(&mut self[..], channels).into_iter()
+    }
+}
+
+impl<T> ExactSizeBuf for MmapBufferMut<'_, T>
+where
+    T: Copy,
+{
+    fn frames(&self) -> usize {
+        self.frames
+    }
+}
+
+impl<T> InterleavedBuf for MmapBufferMut<'_, T> {
+    type Sample = T;
+
+    fn as_interleaved(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T> InterleavedBufMut for MmapBufferMut<'_, T> {
+    fn as_interleaved_mut(&mut self) -> &mut [T] {
+        self
+    }
+
+    fn as_interleaved_mut_ptr(&mut self) -> ptr::NonNull<T> {
+        // Safety: the mapped area is never null.
+        unsafe { ptr::NonNull::new_unchecked(self.data) }
+    }
+
+    /// Shrink the buffer to the given number of frames, which also limits the
+    /// number of frames committed by [MmapBufferMut::commit].
+    ///
+    /// The number of channels in the mapped area is fixed by the device, so
+    /// it can't be changed.
+    unsafe fn set_interleaved_topology(&mut self, channels: usize, frames: usize) {
+        debug_assert_eq!(
+            channels, self.channels,
+            "channels of the mapped area can't be changed"
+        );
+        debug_assert!(
+            frames <= self.area.frames(),
+            "frames {} is larger than the mapped area {}",
+            frames,
+            self.area.frames()
+        );
+
+        self.frames = frames;
+        self.len = self.channels * frames;
+    }
+}
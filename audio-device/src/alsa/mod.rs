@@ -70,6 +70,9 @@ pub enum Error {
         #[from]
         io::Error,
     ),
+    /// An error raised by the audio runtime, like when it has been shut down.
+    #[error("runtime error: {0}")]
+    Runtime(#[from] crate::runtime::Error),
     /// Error raised when there's a format mismatch between an underlying stream
     /// and the type attempting to be used with it.
     #[error("type `{ty}` is not appropriate to use with format `{format}`")]
@@ -91,6 +94,12 @@ pub enum Error {
     /// Underlying function call returned an illegal format identifier.
     #[error("bad format identifier ({0})")]
     BadFormat(c::c_int),
+    /// The sample format has no corresponding ALSA [Format].
+    #[error("sample format `{0}` is not supported by alsa")]
+    UnsupportedSampleFormat(audio_core::SampleFormat),
+    /// Underlying function call returned an illegal subformat identifier.
+    #[error("bad subformat identifier ({0})")]
+    BadSubformat(c::c_uint),
     /// Underlying function call returned an illegal access identifier.
     #[error("bad access identifier ({0})")]
     BadAccess(c::c_uint),
@@ -100,9 +109,66 @@ pub enum Error {
     /// Underlying function call returned an illegal timestamp type identifier.
     #[error("bad timestamp type identifier ({0})")]
     BadTimestampType(c::c_uint),
+    /// The operation would block, because the PCM is in nonblocking mode and
+    /// isn't ready.
+    #[error("operation would block")]
+    WouldBlock,
     /// Underlying PCM was not set up for polling.
     #[error("pcm device is not pollable")]
     MissingPollFds,
+    /// Error raised when an operation is performed on a PCM which is in the
+    /// wrong state.
+    #[error("pcm is in state `{actual}`, expected `{expected}`")]
+    StateMismatch {
+        /// The state the PCM is in.
+        actual: State,
+        /// The state which was expected.
+        expected: State,
+    },
+    /// Error raised when resuming a PCM which is neither paused nor
+    /// suspended.
+    #[error("pcm is in state `{actual}`, expected `Paused` or `Suspended`")]
+    NotResumable {
+        /// The state the PCM is in.
+        actual: State,
+    },
+    /// Error raised when the PCM is configured with an access type which is
+    /// not supported by the operation.
+    #[error("access `{actual}` is not supported, expected `{expected}`")]
+    AccessMismatch {
+        /// The access the PCM is configured with.
+        actual: Access,
+        /// The access which was expected.
+        expected: Access,
+    },
+    /// Error raised when a control element value is accessed as a type which
+    /// doesn't match the type of the element.
+    #[error("control element is of type `{actual}`, expected `{expected}`")]
+    ControlElementTypeMismatch {
+        /// The type of the control element.
+        actual: ControlElementType,
+        /// The type which was expected.
+        expected: ControlElementType,
+    },
+}
+
+impl Error {
+    pub(crate) fn kind(&self) -> crate::ErrorKind {
+        use crate::ErrorKind;
+
+        match self {
+            Self::Sys(errno) => errno.kind(),
+            Self::Io(error) => crate::error::io_error_kind(error),
+            Self::Runtime(error) => error.kind(),
+            Self::FormatMismatch { .. }
+            | Self::BadFormat(..)
+            | Self::BadSubformat(..)
+            | Self::UnsupportedSampleFormat(..) => ErrorKind::UnsupportedFormat,
+            Self::ChannelsMismatch { .. } => ErrorKind::InvalidInput,
+            Self::WouldBlock => ErrorKind::WouldBlock,
+            _ => ErrorKind::Other,
+        }
+    }
 }
 
 /// Helper result wrapper.
@@ -111,6 +177,10 @@ pub type Result<T, E = Error> = ::std::result::Result<T, E>;
 mod card;
 pub use self::card::{cards, Card};
 
+mod device;
+pub(crate) use self::device::device_infos;
+pub use self::device::{devices, Device, Devices};
+
 mod pcm;
 pub use self::pcm::Pcm;
 
@@ -120,6 +190,9 @@ pub use self::hardware_parameters::{HardwareParameters, HardwareParametersMut};
 mod software_parameters;
 pub use self::software_parameters::{SoftwareParameters, SoftwareParametersMut};
 
+mod status;
+pub use self::status::Status;
+
 mod format_mask;
 pub use self::format_mask::FormatMask;
 
@@ -128,9 +201,16 @@ pub use self::access_mask::AccessMask;
 
 mod enums;
 pub use self::enums::{
-    Access, ControlElementInterface, Direction, Format, State, Stream, Timestamp, TimestampType,
+    Access, ChannelMapType, ControlElementInterface, ControlElementType, Direction, Format, State,
+    Stream, Subformat, Timestamp, TimestampType,
 };
 
+mod channel_map;
+pub use self::channel_map::ChannelMap;
+
+mod open_flags;
+pub use self::open_flags::OpenFlags;
+
 mod channel_area;
 #[doc(hidden)]
 pub use self::channel_area::ChannelArea;
@@ -138,9 +218,30 @@ pub use self::channel_area::ChannelArea;
 mod writer;
 pub use self::writer::Writer;
 
+mod reader;
+pub use self::reader::Reader;
+
+mod stream;
+pub use self::stream::{InputStream, OutputStream};
+
+mod duplex;
+pub use self::duplex::DuplexStream;
+
+mod mmap_writer;
+pub use self::mmap_writer::{MmapBufferMut, MmapWriter};
+
 cfg_poll_driver! {
     mod async_writer;
     pub use self::async_writer::AsyncWriter;
+
+    mod async_reader;
+    pub use self::async_reader::AsyncReader;
+
+    mod async_timer;
+    pub use self::async_timer::AsyncTimer;
+
+    mod async_control_events;
+    pub use self::async_control_events::AsyncControlEvents;
 }
 
 mod sample;
@@ -149,5 +250,26 @@ pub use self::sample::Sample;
 mod configurator;
 pub use self::configurator::{Config, Configurator};
 
+mod capabilities;
+pub use self::capabilities::Capabilities;
+
+mod probe;
+pub use self::probe::{probe, Jitter, Probe, ProbeOptions, Trial};
+
 mod control;
-pub use self::control::{Control, ControlElementList};
+pub use self::control::{Control, ControlElementList, ControlElementValue, ControlEvent};
+
+mod mixer;
+pub use self::mixer::{Mixer, MixerElement, MixerElements};
+
+mod timer;
+pub use self::timer::Timer;
+
+mod error_handler;
+pub use self::error_handler::{reset_error_handler, set_tracing_error_handler};
+
+pub mod hwdep;
+
+pub mod rawmidi;
+
+pub mod ucm;
@@ -0,0 +1,66 @@
+use crate::libc as c;
+use alsa_sys as alsa;
+use std::ops;
+
+/// Flags used when opening a PCM through [Pcm::open_with][super::Pcm::open_with].
+///
+/// # Examples
+///
+/// ```
+/// use audio_device::alsa::OpenFlags;
+///
+/// let flags = OpenFlags::NONBLOCK | OpenFlags::NO_AUTO_RESAMPLE;
+/// assert!(flags.contains(OpenFlags::NONBLOCK));
+/// assert!(!flags.contains(OpenFlags::NO_AUTO_FORMAT));
+/// assert!(OpenFlags::empty().is_empty());
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct OpenFlags(c::c_int);
+
+impl OpenFlags {
+    /// Open the PCM in nonblocking mode.
+    pub const NONBLOCK: Self = Self(alsa::SND_PCM_NONBLOCK);
+    /// Disable automatic sample rate conversion.
+    pub const NO_AUTO_RESAMPLE: Self = Self(alsa::SND_PCM_NO_AUTO_RESAMPLE);
+    /// Disable automatic channel conversion.
+    pub const NO_AUTO_CHANNELS: Self = Self(alsa::SND_PCM_NO_AUTO_CHANNELS);
+    /// Disable automatic format conversion.
+    pub const NO_AUTO_FORMAT: Self = Self(alsa::SND_PCM_NO_AUTO_FORMAT);
+    /// Disable software volume control.
+    pub const NO_SOFTVOL: Self = Self(alsa::SND_PCM_NO_SOFTVOL);
+
+    /// Construct an empty set of flags.
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Test if no flags are set.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Test if all flags in `other` are set.
+    pub const fn contains(self, other: Self) -> bool {
+        (self.0 & other.0) == other.0
+    }
+
+    /// Get the raw value of the flags.
+    pub(super) fn bits(self) -> c::c_int {
+        self.0
+    }
+}
+
+impl ops::BitOr for OpenFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl ops::BitOrAssign for OpenFlags {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
@@ -1,15 +1,17 @@
-#[cfg(feature = "poll-driver")]
-use crate::alsa::AsyncWriter;
 use crate::alsa::{
-    ChannelArea, Configurator, Error, HardwareParameters, HardwareParametersMut, Result, Sample,
-    SoftwareParameters, SoftwareParametersMut, State, Stream, Writer,
+    Access, Capabilities, ChannelArea, ChannelMap, ChannelMapType, Configurator, Error,
+    HardwareParameters, HardwareParametersMut, MmapWriter, OpenFlags, Reader, Result, Sample,
+    SoftwareParameters, SoftwareParametersMut, State, Status, Stream, Writer,
 };
+#[cfg(feature = "poll-driver")]
+use crate::alsa::{AsyncReader, AsyncWriter};
 use crate::libc as c;
-use crate::unix::PollFlags;
+use crate::unix::{Errno, PollFlags};
 use alsa_sys as alsa;
-use std::ffi::CStr;
+use std::ffi::{CStr, CString};
 use std::mem;
 use std::ptr;
+use std::time::Duration;
 
 /// An opened PCM device.
 pub struct Pcm {
@@ -36,6 +38,54 @@ impl Pcm {
         Self::open_inner(name, stream, 0)
     }
 
+    /// Open the given pcm device identified by name with the given flags.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    /// use std::ffi::CStr;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let name = CStr::from_bytes_with_nul(b"hw:0\0")?;
+    /// let flags = alsa::OpenFlags::NONBLOCK | alsa::OpenFlags::NO_AUTO_RESAMPLE;
+    ///
+    /// let pcm = alsa::Pcm::open_with(name, alsa::Stream::Playback, flags)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn open_with(name: &CStr, stream: Stream, flags: OpenFlags) -> Result<Self> {
+        Self::open_inner(name, stream, flags.bits())
+    }
+
+    /// Open the hardware pcm device identified by the given card, device and
+    /// subdevice indexes.
+    ///
+    /// This is the same as opening the device named
+    /// `hw:<card>,<device>,<subdevice>`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// for card in alsa::cards() {
+    ///     let card = card?;
+    ///     let pcm = alsa::Pcm::open_card(card.index(), 0, 0, alsa::Stream::Playback)?;
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn open_card(
+        card: c::c_int,
+        device: c::c_int,
+        subdevice: c::c_int,
+        stream: Stream,
+    ) -> Result<Self> {
+        let name = format!("hw:{},{},{}", card, device, subdevice);
+        let name = CString::new(name).expect("device name should not contain nul");
+        Self::open(&name, stream)
+    }
+
     /// Open the default pcm device.
     ///
     /// # Examples
@@ -94,7 +144,38 @@ impl Pcm {
         )
     }
 
+    /// Set the PCM to nonblocking or blocking mode.
+    ///
+    /// In nonblocking mode, reads and writes which can't make progress fail
+    /// immediately instead of blocking. See [Writer::try_write_interleaved].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// pcm.set_nonblocking(true)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn set_nonblocking(&mut self, nonblocking: bool) -> Result<()> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            errno!(alsa::snd_pcm_nonblock(
+                self.handle.as_mut(),
+                nonblocking as c::c_int
+            ))?;
+            Ok(())
+        }
+    }
+
     fn open_inner(name: &CStr, stream: Stream, flags: i32) -> Result<Self> {
+        // NB: gives errors routed through `set_tracing_error_handler` the
+        // context of the device being opened.
+        let _span = tracing::debug_span!("alsa_pcm_open", name = ?name).entered();
+
         unsafe {
             let mut handle = mem::MaybeUninit::uninit();
 
@@ -159,8 +240,39 @@ impl Pcm {
         Configurator::new(self)
     }
 
+    /// Prepare a PCM for use.
+    ///
+    /// This is done automatically when hardware parameters are installed, but
+    /// is needed to restart a stream after it's been stopped through
+    /// [Pcm::drop] or [Pcm::drain].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// let config = pcm.configure::<i16>().install()?;
+    ///
+    /// pcm.drop()?;
+    /// pcm.prepare()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn prepare(&mut self) -> Result<()> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            errno!(alsa::snd_pcm_prepare(self.handle.as_mut()))?;
+            Ok(())
+        }
+    }
+
     /// Start a PCM.
     ///
+    /// The PCM must be in the [State::Prepare] state, or this will error with
+    /// [Error::StateMismatch].
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -168,11 +280,13 @@ impl Pcm {
     ///
     /// # fn main() -> anyhow::Result<()> {
     /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// let config = pcm.configure::<i16>().install()?;
+    ///
     /// pcm.start()?;
     /// # Ok(()) }
     /// ```
     pub fn start(&mut self) -> Result<()> {
-        self.tag.ensure_on_thread();
+        self.ensure_state(State::Prepare)?;
 
         unsafe {
             errno!(alsa::snd_pcm_start(self.handle.as_mut()))?;
@@ -182,6 +296,9 @@ impl Pcm {
 
     /// Pause a PCM.
     ///
+    /// The PCM must be in the [State::Running] state, or this will error with
+    /// [Error::StateMismatch]. Note that not all hardware supports pausing.
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -189,11 +306,14 @@ impl Pcm {
     ///
     /// # fn main() -> anyhow::Result<()> {
     /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// let config = pcm.configure::<i16>().install()?;
+    ///
+    /// pcm.start()?;
     /// pcm.pause()?;
     /// # Ok(()) }
     /// ```
     pub fn pause(&mut self) -> Result<()> {
-        self.tag.ensure_on_thread();
+        self.ensure_state(State::Running)?;
 
         unsafe {
             errno!(alsa::snd_pcm_pause(self.handle.as_mut(), 1))?;
@@ -201,7 +321,10 @@ impl Pcm {
         }
     }
 
-    /// Resume a PCM.
+    /// Resume a PCM which has been paused or suspended.
+    ///
+    /// The PCM must be in the [State::Paused] or [State::Suspended] state, or
+    /// this will error with [Error::NotResumable].
     ///
     /// # Examples
     ///
@@ -210,6 +333,10 @@ impl Pcm {
     ///
     /// # fn main() -> anyhow::Result<()> {
     /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// let config = pcm.configure::<i16>().install()?;
+    ///
+    /// pcm.start()?;
+    /// pcm.pause()?;
     /// pcm.resume()?;
     /// # Ok(()) }
     /// ```
@@ -217,12 +344,27 @@ impl Pcm {
         self.tag.ensure_on_thread();
 
         unsafe {
-            errno!(alsa::snd_pcm_pause(self.handle.as_mut(), 0))?;
+            match self.state() {
+                State::Paused => {
+                    errno!(alsa::snd_pcm_pause(self.handle.as_mut(), 0))?;
+                }
+                State::Suspended => {
+                    errno!(alsa::snd_pcm_resume(self.handle.as_mut()))?;
+                }
+                actual => {
+                    return Err(Error::NotResumable { actual });
+                }
+            }
+
             Ok(())
         }
     }
 
-    /// Open all available hardware parameters for the current handle.
+    /// Stop a PCM after all pending frames have been played.
+    ///
+    /// For playback this blocks until all written frames have been played,
+    /// unless the PCM was opened in nonblocking mode. For capture, any frames
+    /// still buffered remain available for reading.
     ///
     /// # Examples
     ///
@@ -231,18 +373,24 @@ impl Pcm {
     ///
     /// # fn main() -> anyhow::Result<()> {
     /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
-    /// let mut hw = pcm.hardware_parameters_any()?;
-    /// hw.set_rate_last()?;
-    /// hw.install()?;
+    /// let config = pcm.configure::<i16>().install()?;
+    ///
+    /// let mut writer = pcm.writer::<i16>()?;
+    /// // write audio through the writer.
+    ///
+    /// pcm.drain()?;
     /// # Ok(()) }
     /// ```
-    pub fn hardware_parameters_any(&mut self) -> Result<HardwareParametersMut<'_>> {
+    pub fn drain(&mut self) -> Result<()> {
         self.tag.ensure_on_thread();
 
-        unsafe { HardwareParametersMut::any(&mut self.handle) }
+        unsafe {
+            errno!(alsa::snd_pcm_drain(self.handle.as_mut()))?;
+            Ok(())
+        }
     }
 
-    /// Open current hardware parameters for the current handle for mutable access.
+    /// Stop a PCM immediately, discarding any pending frames.
     ///
     /// # Examples
     ///
@@ -251,21 +399,26 @@ impl Pcm {
     ///
     /// # fn main() -> anyhow::Result<()> {
     /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// let config = pcm.configure::<i16>().install()?;
     ///
-    /// let mut hw = pcm.hardware_parameters_mut()?;
-    /// let actual_rate = hw.set_rate(44100, alsa::Direction::Nearest)?;
-    /// hw.install()?;
-    ///
-    /// dbg!(actual_rate);
+    /// pcm.start()?;
+    /// pcm.drop()?;
     /// # Ok(()) }
     /// ```
-    pub fn hardware_parameters_mut(&mut self) -> Result<HardwareParametersMut<'_>> {
+    pub fn drop(&mut self) -> Result<()> {
         self.tag.ensure_on_thread();
 
-        unsafe { HardwareParametersMut::current(&mut self.handle) }
+        unsafe {
+            errno!(alsa::snd_pcm_drop(self.handle.as_mut()))?;
+            Ok(())
+        }
     }
 
-    /// Open current hardware parameters for the current handle.
+    /// Link this PCM with another, so that they are started, stopped and
+    /// prepared together.
+    ///
+    /// This is typically used to start a capture and a playback stream
+    /// sample-synchronously for full-duplex processing.
     ///
     /// # Examples
     ///
@@ -273,18 +426,33 @@ impl Pcm {
     /// use audio_device::alsa;
     ///
     /// # fn main() -> anyhow::Result<()> {
-    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
-    /// let sw = pcm.hardware_parameters()?;
-    /// dbg!(sw.rate()?);
+    /// let mut capture = alsa::Pcm::open_default(alsa::Stream::Capture)?;
+    /// let mut playback = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    ///
+    /// capture.configure::<i16>().install()?;
+    /// playback.configure::<i16>().install()?;
+    ///
+    /// capture.link(&playback)?;
+    /// // starting one starts both.
+    /// capture.start()?;
     /// # Ok(()) }
     /// ```
-    pub fn hardware_parameters(&mut self) -> Result<HardwareParameters> {
+    pub fn link(&mut self, other: &Pcm) -> Result<()> {
         self.tag.ensure_on_thread();
+        other.tag.ensure_on_thread();
 
-        unsafe { HardwareParameters::current(&mut self.handle) }
+        unsafe {
+            errno!(alsa::snd_pcm_link(
+                self.handle.as_mut(),
+                other.handle.as_ptr()
+            ))?;
+            Ok(())
+        }
     }
 
-    /// Open current software parameters for the current handle.
+    /// Remove this PCM from the group of PCMs it's linked with.
+    ///
+    /// See [Pcm::link].
     ///
     /// # Examples
     ///
@@ -292,19 +460,26 @@ impl Pcm {
     /// use audio_device::alsa;
     ///
     /// # fn main() -> anyhow::Result<()> {
-    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
-    /// let sw = pcm.software_parameters()?;
+    /// let mut capture = alsa::Pcm::open_default(alsa::Stream::Capture)?;
+    /// let playback = alsa::Pcm::open_default(alsa::Stream::Playback)?;
     ///
-    /// dbg!(sw.boundary()?);
+    /// capture.link(&playback)?;
+    /// capture.unlink()?;
     /// # Ok(()) }
     /// ```
-    pub fn software_parameters(&mut self) -> Result<SoftwareParameters> {
+    pub fn unlink(&mut self) -> Result<()> {
         self.tag.ensure_on_thread();
 
-        unsafe { SoftwareParameters::new(&mut self.handle) }
+        unsafe {
+            errno!(alsa::snd_pcm_unlink(self.handle.as_mut()))?;
+            Ok(())
+        }
     }
 
-    /// Open current software parameters for the current handle for mutable access.
+    /// Get the name of the timer associated with the PCM, which can be opened
+    /// with [Timer::open][crate::alsa::Timer::open].
+    ///
+    /// The timer ticks once every period of the PCM.
     ///
     /// # Examples
     ///
@@ -313,19 +488,45 @@ impl Pcm {
     ///
     /// # fn main() -> anyhow::Result<()> {
     /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
-    /// let mut sw = pcm.software_parameters_mut()?;
-    ///
-    /// sw.set_timestamp_mode(alsa::Timestamp::Enable)?;
-    /// sw.install()?;
+    /// println!("timer: {}", pcm.timer_name()?.to_str()?);
     /// # Ok(()) }
     /// ```
-    pub fn software_parameters_mut(&mut self) -> Result<SoftwareParametersMut<'_>> {
+    pub fn timer_name(&mut self) -> Result<CString> {
         self.tag.ensure_on_thread();
 
-        unsafe { SoftwareParametersMut::new(&mut self.handle) }
+        unsafe {
+            let mut info = mem::MaybeUninit::uninit();
+            errno!(alsa::snd_pcm_info_malloc(info.as_mut_ptr()))?;
+            let info = info.assume_init();
+
+            if let Err(e) = errno!(alsa::snd_pcm_info(self.handle.as_mut(), info)) {
+                alsa::snd_pcm_info_free(info);
+                return Err(e.into());
+            }
+
+            let card = alsa::snd_pcm_info_get_card(info);
+            let device = alsa::snd_pcm_info_get_device(info);
+            let subdevice = alsa::snd_pcm_info_get_subdevice(info);
+            let stream = alsa::snd_pcm_info_get_stream(info);
+            alsa::snd_pcm_info_free(info);
+
+            let name = format!(
+                "hw:CLASS={},SCLASS={},CARD={},DEV={},SUBDEV={}",
+                alsa::SND_TIMER_CLASS_PCM,
+                alsa::SND_TIMER_SCLASS_NONE,
+                card,
+                device,
+                (subdevice << 1) | stream,
+            );
+
+            Ok(CString::new(name).expect("timer name should not contain nul"))
+        }
     }
 
-    /// Get count of poll descriptors for PCM handle.
+    /// Query the channel maps supported by the PCM, together with the type of
+    /// each map.
+    ///
+    /// Returns an empty collection if the PCM doesn't support channel maps.
     ///
     /// # Examples
     ///
@@ -334,36 +535,41 @@ impl Pcm {
     ///
     /// # fn main() -> anyhow::Result<()> {
     /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
-    /// let count = pcm.poll_descriptors_count();
-    /// dbg!(count);
+    ///
+    /// for (ty, map) in pcm.query_channel_maps() {
+    ///     println!("{}: {:?}", ty, map.positions().collect::<Vec<_>>());
+    /// }
     /// # Ok(()) }
     /// ```
-    pub fn poll_descriptors_count(&mut self) -> usize {
+    pub fn query_channel_maps(&mut self) -> Vec<(ChannelMapType, ChannelMap)> {
         self.tag.ensure_on_thread();
 
-        unsafe { alsa::snd_pcm_poll_descriptors_count(self.handle.as_mut()) as usize }
+        unsafe {
+            let maps = alsa::snd_pcm_query_chmaps(self.handle.as_mut());
+
+            if maps.is_null() {
+                return Vec::new();
+            }
+
+            let mut out = Vec::new();
+            let mut current = maps;
+
+            while !(*current).is_null() {
+                let query = *current;
+                let ty = ChannelMapType::from_value((*query).type_).expect("bad channel map type");
+                out.push((ty, ChannelMap::from_raw(&(*query).map)));
+                current = current.add(1);
+            }
+
+            alsa::snd_pcm_free_chmaps(maps);
+            out
+        }
     }
 
-    /// Get poll descriptors.
-    ///
-    /// This function fills the given poll descriptor structs for the specified
-    /// PCM handle. The poll desctiptor array should have the size returned by
-    /// [poll_descriptors_count()][Pcm::poll_descriptors_count()] function.
-    ///
-    /// The result is intended for direct use with the `poll()` syscall.
-    ///
-    /// For reading the returned events of poll descriptor after `poll()` system
-    /// call, use ::snd_pcm_poll_descriptors_revents() function. The field
-    /// values in pollfd structs may be bogus regarding the stream direction
-    /// from the application perspective (`POLLIN` might not imply read
-    /// direction and `POLLOUT` might not imply write), but the
-    /// [poll_descriptors_revents()][Pcm::poll_descriptors_revents()] function
-    /// does the right "demangling".
+    /// Get the channel map currently used by the PCM.
     ///
-    /// You can use output from this function as arguments for the select()
-    /// syscall, too. Do not forget to translate `POLLIN` and `POLLOUT` events
-    /// to corresponding `FD_SET` arrays and demangle events using
-    /// [poll_descriptors_revents()][Pcm::poll_descriptors_revents()].
+    /// Returns `None` if the PCM doesn't have a channel map, such as when it
+    /// hasn't been configured yet.
     ///
     /// # Examples
     ///
@@ -372,88 +578,534 @@ impl Pcm {
     ///
     /// # fn main() -> anyhow::Result<()> {
     /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// pcm.configure::<i16>().install()?;
     ///
-    /// let mut fds = Vec::with_capacity(pcm.poll_descriptors_count());
-    /// pcm.poll_descriptors_vec(&mut fds)?;
+    /// if let Some(map) = pcm.channel_map() {
+    ///     for (channel, position) in map.positions().enumerate() {
+    ///         println!("{}: {:?}", channel, position);
+    ///     }
+    /// }
     /// # Ok(()) }
     /// ```
-    pub fn poll_descriptors_vec(&mut self, fds: &mut Vec<c::pollfd>) -> Result<()> {
+    pub fn channel_map(&mut self) -> Option<ChannelMap> {
         self.tag.ensure_on_thread();
 
         unsafe {
-            let count = self.poll_descriptors_count();
+            let map = alsa::snd_pcm_get_chmap(self.handle.as_mut());
 
-            if fds.capacity() < count {
-                fds.reserve(count - fds.capacity());
+            if map.is_null() {
+                return None;
             }
 
-            let result = errno!(alsa::snd_pcm_poll_descriptors(
-                self.handle.as_mut(),
-                fds.as_mut_ptr() as *mut c::pollfd,
-                fds.capacity() as c::c_uint
-            ))?;
-
-            let result = result as usize;
-
-            assert!(result <= fds.capacity());
-            fds.set_len(result);
-            Ok(())
+            let out = ChannelMap::from_raw(map);
+            c::free(map as *mut c::c_void);
+            Some(out)
         }
     }
 
-    /// Get returned events from poll descriptors.
+    /// Set the channel map used by the PCM.
     ///
-    /// This function does "demangling" of the revents mask returned from the
-    /// `poll()` syscall to correct semantics ([PollFlags::POLLIN] = read,
-    /// [PollFlags::POLLOUT] = write).
+    /// # Examples
     ///
-    /// Note: The null event also exists. Even if `poll()` or `select()` syscall
-    /// returned that some events are waiting, this function might return empty
-    /// set of events. In this case, application should do next event waiting
-    /// using `poll()` or `select()`.
+    /// ```no_run
+    /// use audio::ChannelPosition;
+    /// use audio_device::alsa;
     ///
-    /// Note: Even if multiple poll descriptors are used (i.e. `fds.len() > 1`),
-    /// this function returns only a single event.
-    pub fn poll_descriptors_revents(&mut self, fds: &mut [c::pollfd]) -> Result<PollFlags> {
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// pcm.configure::<i16>().channels(2).install()?;
+    ///
+    /// let map = alsa::ChannelMap::from_positions(&[
+    ///     ChannelPosition::FrontRight,
+    ///     ChannelPosition::FrontLeft,
+    /// ]);
+    ///
+    /// pcm.set_channel_map(&map)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn set_channel_map(&mut self, map: &ChannelMap) -> Result<()> {
         self.tag.ensure_on_thread();
 
+        let raw = map.to_raw();
+
         unsafe {
-            let mut revents = mem::MaybeUninit::uninit();
-            errno!(alsa::snd_pcm_poll_descriptors_revents(
+            errno!(alsa::snd_pcm_set_chmap(
                 self.handle.as_mut(),
-                // NB: PollFd is `#[repr(transparent)]` around pollfd.
-                fds.as_mut_ptr(),
-                fds.len() as c::c_uint,
-                revents.as_mut_ptr(),
+                raw.as_ptr() as *const alsa::snd_pcm_chmap_t
             ))?;
-            let revents = revents.assume_init();
-            Ok(PollFlags::from_bits_truncate(revents as c::c_short))
+            Ok(())
         }
     }
 
-    /// Write unchecked interleaved frames to a PCM.
-    ///
-    /// Note: that the `len` must be the number of frames in the `buf` which
-    /// *does not* account for the number of channels. So if `len` is 100, and
-    /// the number of configured channels is 2, the `buf` must contain **at
-    /// least** 200 bytes.
-    ///
-    /// See [HardwareParameters::channels].
-    pub unsafe fn write_interleaved_unchecked(
-        &mut self,
-        buf: *const c::c_void,
-        len: c::c_ulong,
-    ) -> Result<c::c_long> {
-        self.tag.ensure_on_thread();
-        Ok(errno!(alsa::snd_pcm_writei(
-            self.handle.as_mut(),
-            buf,
-            len
-        ))?)
+    /// Ensure that the PCM is in the expected state.
+    fn ensure_state(&self, expected: State) -> Result<()> {
+        let actual = self.state();
+
+        if actual != expected {
+            return Err(Error::StateMismatch { actual, expected });
+        }
+
+        Ok(())
     }
 
-    /// Construct a checked safe writer with the given number of channels and
-    /// the specified sample type.
+    /// Probe the capabilities of the device, such as which formats, channels,
+    /// rates and access types it supports.
+    ///
+    /// This doesn't change the configuration of the PCM.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// let caps = pcm.capabilities()?;
+    ///
+    /// println!("formats: {:?}", caps.formats);
+    /// println!("channels: {}-{}", caps.channels_min, caps.channels_max);
+    /// println!("rates: {}-{}", caps.rate_min, caps.rate_max);
+    /// # Ok(()) }
+    /// ```
+    pub fn capabilities(&mut self) -> Result<Capabilities> {
+        self.tag.ensure_on_thread();
+        Capabilities::probe(self)
+    }
+
+    /// Open all available hardware parameters for the current handle.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// let mut hw = pcm.hardware_parameters_any()?;
+    /// hw.set_rate_last()?;
+    /// hw.install()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn hardware_parameters_any(&mut self) -> Result<HardwareParametersMut<'_>> {
+        self.tag.ensure_on_thread();
+
+        unsafe { HardwareParametersMut::any(&mut self.handle) }
+    }
+
+    /// Open current hardware parameters for the current handle for mutable access.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    ///
+    /// let mut hw = pcm.hardware_parameters_mut()?;
+    /// let actual_rate = hw.set_rate(44100, alsa::Direction::Nearest)?;
+    /// hw.install()?;
+    ///
+    /// dbg!(actual_rate);
+    /// # Ok(()) }
+    /// ```
+    pub fn hardware_parameters_mut(&mut self) -> Result<HardwareParametersMut<'_>> {
+        self.tag.ensure_on_thread();
+
+        unsafe { HardwareParametersMut::current(&mut self.handle) }
+    }
+
+    /// Open current hardware parameters for the current handle.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// let sw = pcm.hardware_parameters()?;
+    /// dbg!(sw.rate()?);
+    /// # Ok(()) }
+    /// ```
+    pub fn hardware_parameters(&mut self) -> Result<HardwareParameters> {
+        self.tag.ensure_on_thread();
+
+        unsafe { HardwareParameters::current(&mut self.handle) }
+    }
+
+    /// Open current software parameters for the current handle.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// let sw = pcm.software_parameters()?;
+    ///
+    /// dbg!(sw.boundary()?);
+    /// # Ok(()) }
+    /// ```
+    pub fn software_parameters(&mut self) -> Result<SoftwareParameters> {
+        self.tag.ensure_on_thread();
+
+        unsafe { SoftwareParameters::new(&mut self.handle) }
+    }
+
+    /// Read the current status of the PCM.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// let status = pcm.status()?;
+    ///
+    /// dbg!(status.state(), status.delay(), status.available());
+    /// # Ok(()) }
+    /// ```
+    pub fn status(&mut self) -> Result<Status> {
+        self.tag.ensure_on_thread();
+
+        unsafe { Status::new(&mut self.handle) }
+    }
+
+    /// Open current software parameters for the current handle for mutable access.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// let mut sw = pcm.software_parameters_mut()?;
+    ///
+    /// sw.set_timestamp_mode(alsa::Timestamp::Enable)?;
+    /// sw.install()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn software_parameters_mut(&mut self) -> Result<SoftwareParametersMut<'_>> {
+        self.tag.ensure_on_thread();
+
+        unsafe { SoftwareParametersMut::new(&mut self.handle) }
+    }
+
+    /// Get count of poll descriptors for PCM handle.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// let count = pcm.poll_descriptors_count();
+    /// dbg!(count);
+    /// # Ok(()) }
+    /// ```
+    pub fn poll_descriptors_count(&mut self) -> usize {
+        self.tag.ensure_on_thread();
+
+        unsafe { alsa::snd_pcm_poll_descriptors_count(self.handle.as_mut()) as usize }
+    }
+
+    /// Get poll descriptors.
+    ///
+    /// This function fills the given poll descriptor structs for the specified
+    /// PCM handle. The poll desctiptor array should have the size returned by
+    /// [poll_descriptors_count()][Pcm::poll_descriptors_count()] function.
+    ///
+    /// The result is intended for direct use with the `poll()` syscall.
+    ///
+    /// For reading the returned events of poll descriptor after `poll()` system
+    /// call, use ::snd_pcm_poll_descriptors_revents() function. The field
+    /// values in pollfd structs may be bogus regarding the stream direction
+    /// from the application perspective (`POLLIN` might not imply read
+    /// direction and `POLLOUT` might not imply write), but the
+    /// [poll_descriptors_revents()][Pcm::poll_descriptors_revents()] function
+    /// does the right "demangling".
+    ///
+    /// You can use output from this function as arguments for the select()
+    /// syscall, too. Do not forget to translate `POLLIN` and `POLLOUT` events
+    /// to corresponding `FD_SET` arrays and demangle events using
+    /// [poll_descriptors_revents()][Pcm::poll_descriptors_revents()].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    ///
+    /// let mut fds = Vec::with_capacity(pcm.poll_descriptors_count());
+    /// pcm.poll_descriptors_vec(&mut fds)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn poll_descriptors_vec(&mut self, fds: &mut Vec<c::pollfd>) -> Result<()> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            let count = self.poll_descriptors_count();
+
+            if fds.capacity() < count {
+                fds.reserve(count - fds.capacity());
+            }
+
+            let result = errno!(alsa::snd_pcm_poll_descriptors(
+                self.handle.as_mut(),
+                fds.as_mut_ptr() as *mut c::pollfd,
+                fds.capacity() as c::c_uint
+            ))?;
+
+            let result = result as usize;
+
+            assert!(result <= fds.capacity());
+            fds.set_len(result);
+            Ok(())
+        }
+    }
+
+    /// Get the poll descriptors of the PCM.
+    ///
+    /// This is a convenience over [Pcm::poll_descriptors_vec], and is
+    /// intended to be used to integrate the PCM into an external event loop.
+    /// Once an event has been received, decode it with
+    /// [Pcm::poll_descriptors_revents].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    ///
+    /// for fd in pcm.poll_descriptors()? {
+    ///     println!("fd: {}, events: {}", fd.fd, fd.events);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn poll_descriptors(&mut self) -> Result<Vec<c::pollfd>> {
+        let mut fds = Vec::new();
+        self.poll_descriptors_vec(&mut fds)?;
+        Ok(fds)
+    }
+
+    /// Get returned events from poll descriptors.
+    ///
+    /// This function does "demangling" of the revents mask returned from the
+    /// `poll()` syscall to correct semantics ([PollFlags::POLLIN] = read,
+    /// [PollFlags::POLLOUT] = write).
+    ///
+    /// Note: The null event also exists. Even if `poll()` or `select()` syscall
+    /// returned that some events are waiting, this function might return empty
+    /// set of events. In this case, application should do next event waiting
+    /// using `poll()` or `select()`.
+    ///
+    /// Note: Even if multiple poll descriptors are used (i.e. `fds.len() > 1`),
+    /// this function returns only a single event.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::{alsa, libc, unix::PollFlags};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default_nonblocking(alsa::Stream::Playback)?;
+    /// let config = pcm.configure::<i16>().install()?;
+    ///
+    /// let mut fds = pcm.poll_descriptors()?;
+    ///
+    /// loop {
+    ///     unsafe {
+    ///         libc::poll(fds.as_mut_ptr(), fds.len() as libc::nfds_t, -1);
+    ///     }
+    ///
+    ///     let flags = pcm.poll_descriptors_revents(&mut fds)?;
+    ///
+    ///     if flags.test(PollFlags::POLLOUT) {
+    ///         // the device is ready for writing.
+    ///         break;
+    ///     }
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn poll_descriptors_revents(&mut self, fds: &mut [c::pollfd]) -> Result<PollFlags> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            let mut revents = mem::MaybeUninit::uninit();
+            errno!(alsa::snd_pcm_poll_descriptors_revents(
+                self.handle.as_mut(),
+                // NB: PollFd is `#[repr(transparent)]` around pollfd.
+                fds.as_mut_ptr(),
+                fds.len() as c::c_uint,
+                revents.as_mut_ptr(),
+            ))?;
+            let revents = revents.assume_init();
+            Ok(PollFlags::from_bits_truncate(revents as c::c_short))
+        }
+    }
+
+    /// Recover the stream state from an error raised by a read or a write.
+    ///
+    /// This handles underruns and overruns (`EPIPE`) and suspended streams
+    /// (`ESTRPIPE`) by preparing the stream again, so that reading or writing
+    /// can continue. Other errors are returned as-is. If `silent` is set, ALSA
+    /// won't print a message about the recovery to stderr.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// let config = pcm.configure::<i16>().install()?;
+    ///
+    /// let buf = [0i16; 1024];
+    ///
+    /// let result = unsafe {
+    ///     pcm.write_interleaved_unchecked(buf.as_ptr().cast(), (buf.len() / config.channels as usize) as _)
+    /// };
+    ///
+    /// if let Err(alsa::Error::Sys(errno)) = result {
+    ///     pcm.recover(errno, true)?;
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn recover(&mut self, errno: Errno, silent: bool) -> Result<()> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            errno!(alsa::snd_pcm_recover(
+                self.handle.as_mut(),
+                -errno.value(),
+                silent as c::c_int
+            ))?;
+            Ok(())
+        }
+    }
+
+    /// Write unchecked interleaved frames to a PCM.
+    ///
+    /// Note: that the `len` must be the number of frames in the `buf` which
+    /// *does not* account for the number of channels. So if `len` is 100, and
+    /// the number of configured channels is 2, the `buf` must contain **at
+    /// least** 200 bytes.
+    ///
+    /// See [HardwareParameters::channels].
+    pub unsafe fn write_interleaved_unchecked(
+        &mut self,
+        buf: *const c::c_void,
+        len: c::c_ulong,
+    ) -> Result<c::c_long> {
+        self.tag.ensure_on_thread();
+        Ok(errno!(alsa::snd_pcm_writei(
+            self.handle.as_mut(),
+            buf,
+            len
+        ))?)
+    }
+
+    /// Write unchecked non-interleaved frames to a PCM.
+    ///
+    /// `bufs` must point to one buffer per configured channel, each of which
+    /// contains **at least** `len` samples.
+    ///
+    /// See [HardwareParameters::channels].
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `bufs` points to as many channel buffers as
+    /// there are configured channels, each of which holds at least `len`
+    /// samples of the configured format.
+    pub unsafe fn write_noninterleaved_unchecked(
+        &mut self,
+        bufs: *mut *mut c::c_void,
+        len: c::c_ulong,
+    ) -> Result<c::c_long> {
+        self.tag.ensure_on_thread();
+        Ok(errno!(alsa::snd_pcm_writen(
+            self.handle.as_mut(),
+            bufs,
+            len
+        ))?)
+    }
+
+    /// Read unchecked interleaved frames from a PCM.
+    ///
+    /// Note: that the `len` must be the number of frames in the `buf` which
+    /// *does not* account for the number of channels. So if `len` is 100, and
+    /// the number of configured channels is 2, the `buf` must have room for
+    /// **at least** 200 samples.
+    ///
+    /// See [HardwareParameters::channels].
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `buf` points to a writable buffer of
+    /// samples of the configured format which is large enough to hold `len`
+    /// frames.
+    pub unsafe fn read_interleaved_unchecked(
+        &mut self,
+        buf: *mut c::c_void,
+        len: c::c_ulong,
+    ) -> Result<c::c_long> {
+        self.tag.ensure_on_thread();
+        Ok(errno!(alsa::snd_pcm_readi(self.handle.as_mut(), buf, len))?)
+    }
+
+    /// Construct a checked safe reader with the given number of channels and
+    /// the specified sample type.
+    ///
+    /// This will error if the type `T` is not appropriate for this device, or
+    /// if the number of channels does not match the number of configured
+    /// channels.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Capture)?;
+    /// let config = pcm.configure::<i16>().install()?;
+    ///
+    /// let mut reader = pcm.reader::<i16>()?;
+    /// // use reader with the resulting config.
+    /// # Ok(()) }
+    /// ```
+    pub fn reader<T>(&mut self) -> Result<Reader<'_, T>>
+    where
+        T: Sample,
+    {
+        self.tag.ensure_on_thread();
+
+        let hw = self.hardware_parameters()?;
+        let channels = hw.channels()? as usize;
+
+        // NB: here we check that `T` is appropriate for the current format.
+        let format = hw.format()?;
+
+        if !T::test(format) {
+            return Err(Error::FormatMismatch {
+                ty: T::describe(),
+                format,
+            });
+        }
+
+        unsafe { Ok(Reader::new(self, channels)) }
+    }
+
+    /// Construct a checked safe writer with the given number of channels and
+    /// the specified sample type.
     ///
     /// This will error if the type `T` is not appropriate for this device, or
     /// if the number of channels does not match the number of configured
@@ -491,7 +1143,58 @@ impl Pcm {
             });
         }
 
-        unsafe { Ok(Writer::new(self, channels)) }
+        let access = hw.access()?;
+        unsafe { Ok(Writer::new(self, channels, access)) }
+    }
+
+    /// Construct a checked safe writer which writes directly into the
+    /// memory-mapped buffer of the device.
+    ///
+    /// This will error if the type `T` is not appropriate for this device, or
+    /// if the PCM isn't configured with [Access::MmapInterleaved].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// let config = pcm.configure::<i16>().access(alsa::Access::MmapInterleaved).install()?;
+    ///
+    /// let mut writer = pcm.mmap_writer::<i16>()?;
+    /// // use writer with the resulting config.
+    /// # Ok(()) }
+    /// ```
+    pub fn mmap_writer<T>(&mut self) -> Result<MmapWriter<'_, T>>
+    where
+        T: Sample,
+    {
+        self.tag.ensure_on_thread();
+
+        let hw = self.hardware_parameters()?;
+        let channels = hw.channels()? as usize;
+
+        // NB: here we check that `T` is appropriate for the current format.
+        let format = hw.format()?;
+
+        if !T::test(format) {
+            return Err(Error::FormatMismatch {
+                ty: T::describe(),
+                format,
+            });
+        }
+
+        let access = hw.access()?;
+
+        if access != Access::MmapInterleaved {
+            return Err(Error::AccessMismatch {
+                actual: access,
+                expected: Access::MmapInterleaved,
+            });
+        }
+
+        unsafe { Ok(MmapWriter::new(self, channels)) }
     }
 
     cfg_poll_driver! {
@@ -551,6 +1254,63 @@ impl Pcm {
 
             Ok(unsafe { AsyncWriter::new(self, fd, channels)? })
         }
+
+        /// Construct a checked safe async reader with the given number of
+        /// channels and the specified sample type.
+        ///
+        /// This will error if the type `T` is not appropriate for this device, or
+        /// if the number of channels does not match the number of configured
+        /// channels.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the audio runtime is not available.
+        ///
+        /// See [Runtime][crate::runtime::Runtime] for more.
+        ///
+        /// # Examples
+        ///
+        /// ```no_run
+        /// use audio_device::alsa;
+        ///
+        /// # fn main() -> anyhow::Result<()> {
+        /// let mut pcm = alsa::Pcm::open_default_nonblocking(alsa::Stream::Capture)?;
+        /// let config = pcm.configure::<i16>().install()?;
+        ///
+        /// let mut reader = pcm.async_reader::<i16>()?;
+        /// // use reader with the resulting config.
+        /// # Ok(()) }
+        /// ```
+        pub fn async_reader<T>(&mut self) -> Result<AsyncReader<'_, T>>
+        where
+            T: Sample,
+        {
+            self.tag.ensure_on_thread();
+
+            let hw = self.hardware_parameters()?;
+            let channels = hw.channels()? as usize;
+
+            // NB: here we check that `T` is appropriate for the current format.
+            let format = hw.format()?;
+
+            if !T::test(format) {
+                return Err(Error::FormatMismatch {
+                    ty: T::describe(),
+                    format,
+                });
+            }
+
+            let mut fds = Vec::new();
+            self.poll_descriptors_vec(&mut fds)?;
+
+            if fds.len() != 1 {
+                return Err(Error::MissingPollFds);
+            }
+
+            let fd = fds[0];
+
+            Ok(unsafe { AsyncReader::new(self, fd, channels)? })
+        }
     }
 
     /// Return number of frames ready to be read (capture) / written (playback).
@@ -573,8 +1333,81 @@ impl Pcm {
         unsafe { Ok(errno!(alsa::snd_pcm_avail_update(self.handle.as_mut()))? as usize) }
     }
 
+    /// Wait for the PCM to become ready for reading or writing, or until the
+    /// given timeout has passed.
+    ///
+    /// Passing `None` waits indefinitely. Returns `true` if the PCM is ready,
+    /// or `false` if the timeout expired. Timeouts have millisecond
+    /// resolution.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    /// use std::time::Duration;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// let config = pcm.configure::<i16>().install()?;
+    ///
+    /// if !pcm.wait(Some(Duration::from_millis(500)))? {
+    ///     println!("device stalled");
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn wait(&mut self, timeout: Option<Duration>) -> Result<bool> {
+        self.tag.ensure_on_thread();
+
+        let timeout = match timeout {
+            Some(timeout) => u128::min(c::c_int::MAX as u128, timeout.as_millis()) as c::c_int,
+            None => -1,
+        };
+
+        unsafe { Ok(errno!(alsa::snd_pcm_wait(self.handle.as_mut(), timeout))? == 1) }
+    }
+
+    /// Get the current delay of the PCM in frames.
+    ///
+    /// For playback this is the number of frames which have to pass before a
+    /// frame written now is heard through the DAC. For capture it's how long
+    /// ago a frame which is read now was captured. This is a cheaper
+    /// alternative to [Pcm::status] when only the delay is needed.
+    ///
+    /// The delay is negative if the stream is in an underrun.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// let config = pcm.configure::<i16>().install()?;
+    ///
+    /// let delay = pcm.delay()? as f64 / config.rate as f64;
+    /// println!("delay: {:.3}s", delay);
+    /// # Ok(()) }
+    /// ```
+    pub fn delay(&mut self) -> Result<c::c_long> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            let mut delay = mem::MaybeUninit::uninit();
+            errno!(alsa::snd_pcm_delay(
+                self.handle.as_mut(),
+                delay.as_mut_ptr()
+            ))?;
+            Ok(delay.assume_init())
+        }
+    }
+
     /// Application request to access a portion of direct (mmap) area.
-    #[doc(hidden)] // incomplete feature
+    ///
+    /// [Pcm::available_update] must be called before this. Once the area has
+    /// been accessed, it's made available to the device through
+    /// [ChannelArea::commit].
+    ///
+    /// See [Pcm::mmap_writer] for a safe interleaved wrapper.
     pub fn mmap_begin(&mut self, mut frames: c::c_ulong) -> Result<ChannelArea<'_>> {
         self.tag.ensure_on_thread();
 
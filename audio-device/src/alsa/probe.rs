@@ -0,0 +1,279 @@
+use crate::alsa::{Access, Direction, Error, Pcm, Result, Sample, Stream};
+use crate::libc as c;
+use crate::unix::Errno;
+use std::ffi::CStr;
+use std::time::{Duration, Instant};
+
+/// The smallest period size in frames which is tried, since very small
+/// periods are commonly advertised but unreliable.
+const MIN_PERIOD_SIZE: c::c_ulong = 16;
+
+/// Options used when [probing][probe] a device.
+///
+/// # Examples
+///
+/// ```
+/// use audio_device::alsa::ProbeOptions;
+/// use std::time::Duration;
+///
+/// let options = ProbeOptions {
+///     trial_duration: Duration::from_millis(500),
+///     ..ProbeOptions::default()
+/// };
+///
+/// assert_eq!(options.rate, 48000);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProbeOptions {
+    /// The number of channels to probe with.
+    pub channels: c::c_uint,
+    /// The sample rate to probe with.
+    pub rate: c::c_uint,
+    /// The number of periods in the buffer to probe with.
+    pub periods: c::c_uint,
+    /// The largest period size in frames which is tried.
+    pub max_period_size: c::c_ulong,
+    /// For how long each configuration is played before it's considered
+    /// stable.
+    pub trial_duration: Duration,
+}
+
+impl Default for ProbeOptions {
+    fn default() -> Self {
+        Self {
+            channels: 2,
+            rate: 48000,
+            periods: 2,
+            max_period_size: 4096,
+            trial_duration: Duration::from_secs(2),
+        }
+    }
+}
+
+/// The scheduling jitter measured while playing a configuration, which is how
+/// much the time between the device accepting consecutive periods deviates
+/// from the duration of a period.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Jitter {
+    /// The mean deviation.
+    pub mean: Duration,
+    /// The largest deviation.
+    pub max: Duration,
+}
+
+/// The outcome of playing a single configuration.
+///
+/// See [Probe::trials].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Trial {
+    /// The period size in frames which was achieved.
+    pub period_size: c::c_ulong,
+    /// The buffer size in frames which was achieved.
+    pub buffer_size: c::c_ulong,
+    /// The sample rate which was achieved.
+    pub rate: c::c_uint,
+    /// The number of underruns which occurred while playing.
+    pub xruns: usize,
+    /// The measured scheduling jitter.
+    pub jitter: Jitter,
+}
+
+impl Trial {
+    /// Test if the configuration played without underruns.
+    pub fn is_stable(&self) -> bool {
+        self.xruns == 0
+    }
+
+    /// The latency of the configuration, which is the duration of its buffer.
+    pub fn latency(&self) -> Duration {
+        frames_to_duration(self.buffer_size, self.rate)
+    }
+}
+
+/// The report produced by [probing][probe] a device.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Probe {
+    /// The smallest and largest period size in frames supported by the
+    /// device.
+    pub period_size: (c::c_ulong, c::c_ulong),
+    /// The smallest and largest number of periods supported by the device.
+    pub periods: (c::c_uint, c::c_uint),
+    /// The smallest and largest buffer size in frames supported by the
+    /// device.
+    pub buffer_size: (c::c_ulong, c::c_ulong),
+    /// The configurations which were played, from the smallest to the
+    /// largest period size.
+    pub trials: Vec<Trial>,
+}
+
+impl Probe {
+    /// The smallest configuration which played without underruns, if any.
+    pub fn minimum_stable(&self) -> Option<&Trial> {
+        self.trials.iter().find(|trial| trial.is_stable())
+    }
+}
+
+/// Probe the playback device with the given name, reporting which period and
+/// buffer sizes it supports and which of them play reliably on this machine.
+///
+/// Period sizes are tried as powers of two from the smallest one supported,
+/// and probing stops at the first configuration which plays silence for
+/// [ProbeOptions::trial_duration] without any underruns. So this blocks for
+/// at least that long.
+///
+/// Probing plays 16-bit samples, which is expected to be supported by
+/// virtually all devices.
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::alsa;
+/// use std::ffi::CStr;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let name = CStr::from_bytes_with_nul(b"default\0")?;
+/// let probe = alsa::probe(name, alsa::ProbeOptions::default())?;
+///
+/// if let Some(trial) = probe.minimum_stable() {
+///     println!("period: {}, latency: {:?}", trial.period_size, trial.latency());
+/// }
+/// # Ok(()) }
+/// ```
+pub fn probe(name: &CStr, options: ProbeOptions) -> Result<Probe> {
+    let mut pcm = Pcm::open(name, Stream::Playback)?;
+
+    let (period_size, periods, buffer_size) = {
+        let hw = pcm.hardware_parameters_any()?;
+
+        (
+            (hw.period_size_min()?.0, hw.period_size_max()?.0),
+            (hw.periods_min()?.0, hw.periods_max()?.0),
+            (hw.buffer_size_min()?, hw.buffer_size_max()?),
+        )
+    };
+
+    let max_period_size = c::c_ulong::min(options.max_period_size, period_size.1);
+    let mut candidate = c::c_ulong::max(period_size.0, MIN_PERIOD_SIZE).next_power_of_two();
+    let mut trials = Vec::new();
+
+    while candidate <= max_period_size {
+        let trial = self::trial(&mut pcm, &options, candidate)?;
+
+        // NB: the device might round the period size up, in which case the
+        // next candidate is taken from the achieved size.
+        candidate = c::c_ulong::max(candidate, trial.period_size) * 2;
+        trials.push(trial);
+
+        if trial.is_stable() {
+            break;
+        }
+    }
+
+    Ok(Probe {
+        period_size,
+        periods,
+        buffer_size,
+        trials,
+    })
+}
+
+/// Play silence with the given period size and measure how it fares.
+fn trial(pcm: &mut Pcm, options: &ProbeOptions, period_size: c::c_ulong) -> Result<Trial> {
+    let mut hw = pcm.hardware_parameters_any()?;
+    hw.set_rate_resample(false)?;
+    hw.set_access(Access::ReadWriteInterleaved)?;
+    hw.set_format(<i16 as Sample>::DEFAULT_FORMAT)?;
+    hw.set_channels(options.channels)?;
+    let (rate, _) = hw.set_rate_near(options.rate, Direction::Nearest)?;
+    let (period_size, _) = hw.set_period_size_near(period_size, Direction::Nearest)?;
+    hw.set_periods_near(options.periods, Direction::Nearest)?;
+    let buffer_size = hw.buffer_size()?;
+    hw.install()?;
+
+    let mut sw = pcm.software_parameters_mut()?;
+    sw.set_start_threshold(buffer_size)?;
+    sw.set_available_min(period_size)?;
+    sw.install()?;
+
+    let channels = options.channels as usize;
+    let samples = vec![0i16; period_size as usize * channels];
+    let period = frames_to_duration(period_size, rate);
+
+    // NB: the device only blocks once its buffer has been filled, so the
+    // first periods written aren't representative.
+    let warmup = buffer_size / period_size;
+
+    let started = Instant::now();
+    let mut written = 0;
+    let mut last = None;
+    let mut deviation = Duration::ZERO;
+    let mut max = Duration::ZERO;
+    let mut count = 0u32;
+    let mut xruns = 0;
+
+    while started.elapsed() < options.trial_duration {
+        let mut offset = 0;
+
+        while offset < period_size {
+            let ptr = samples[offset as usize * channels..].as_ptr();
+
+            // Safety: the buffer holds the remaining frames of the period in
+            // the configured format.
+            let result =
+                unsafe { pcm.write_interleaved_unchecked(ptr.cast(), period_size - offset) };
+
+            match result {
+                Ok(n) => offset += n as c::c_ulong,
+                Err(Error::Sys(errno @ (Errno::EPIPE | Errno::ESTRPIPE))) => {
+                    pcm.recover(errno, true)?;
+                    xruns += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        written += 1;
+
+        if written <= warmup {
+            continue;
+        }
+
+        let now = Instant::now();
+
+        if let Some(last) = last.replace(now) {
+            let interval = now - last;
+
+            let d = if interval > period {
+                interval - period
+            } else {
+                period - interval
+            };
+
+            deviation += d;
+            max = Duration::max(max, d);
+            count += 1;
+        }
+    }
+
+    pcm.drop()?;
+
+    Ok(Trial {
+        period_size,
+        buffer_size,
+        rate,
+        xruns,
+        jitter: Jitter {
+            mean: deviation.checked_div(count).unwrap_or_default(),
+            max,
+        },
+    })
+}
+
+fn frames_to_duration(frames: c::c_ulong, rate: c::c_uint) -> Duration {
+    if rate == 0 {
+        return Duration::ZERO;
+    }
+
+    Duration::from_nanos((frames as u128 * 1_000_000_000 / rate as u128) as u64)
+}
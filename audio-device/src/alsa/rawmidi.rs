@@ -0,0 +1,471 @@
+//! Bindings for ALSA raw MIDI ports.
+//!
+//! Raw MIDI gives direct byte-level access to the MIDI ports of hardware
+//! devices, bypassing the sequencer.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use audio_device::alsa::rawmidi;
+//!
+//! # fn main() -> anyhow::Result<()> {
+//! for port in rawmidi::ports()? {
+//!     println!("{}: {}", port.id().to_str()?, port.name().to_str()?);
+//! }
+//!
+//! let port = rawmidi::ports()?
+//!     .into_iter()
+//!     .find(|p| p.stream() == rawmidi::Stream::Output);
+//!
+//! if let Some(port) = port {
+//!     let mut output = rawmidi::Output::open(&port.id())?;
+//!     // Note on, middle C, full velocity.
+//!     output.write(&[0x90, 60, 127])?;
+//!     output.drain()?;
+//! }
+//! # Ok(()) }
+//! ```
+
+use crate::alsa::{cards, Control, Error, Result};
+use crate::libc as c;
+use crate::unix::Errno;
+use alsa_sys as alsa;
+use std::ffi::{CStr, CString};
+use std::mem;
+use std::ptr;
+
+pub use crate::alsa::enums::RawMidiStream as Stream;
+
+/// Open both the input and the output of the raw MIDI port identified by
+/// name.
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::alsa::rawmidi;
+/// use std::ffi::CString;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let name = CString::new("hw:1,0,0")?;
+/// let (mut input, mut output) = rawmidi::open(&name)?;
+///
+/// let mut buf = [0u8; 256];
+/// let n = input.read(&mut buf)?;
+/// // echo the received data.
+/// output.write(&buf[..n])?;
+/// # Ok(()) }
+/// ```
+pub fn open(name: &CStr) -> Result<(Input, Output)> {
+    unsafe {
+        let mut input = mem::MaybeUninit::uninit();
+        let mut output = mem::MaybeUninit::uninit();
+
+        errno!(alsa::snd_rawmidi_open(
+            input.as_mut_ptr(),
+            output.as_mut_ptr(),
+            name.as_ptr(),
+            0
+        ))?;
+
+        let tag = ste::Tag::current_thread();
+
+        let input = Input {
+            raw: RawMidi::new(tag, input.assume_init()),
+        };
+
+        let output = Output {
+            raw: RawMidi::new(tag, output.assume_init()),
+        };
+
+        Ok((input, output))
+    }
+}
+
+/// An opened raw MIDI input port.
+///
+/// See [Input::open].
+pub struct Input {
+    raw: RawMidi,
+}
+
+impl Input {
+    /// Open the raw MIDI input port identified by name.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa::rawmidi;
+    /// use std::ffi::CString;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let name = CString::new("hw:1,0,0")?;
+    /// let input = rawmidi::Input::open(&name)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn open(name: &CStr) -> Result<Self> {
+        unsafe {
+            let mut handle = mem::MaybeUninit::uninit();
+
+            errno!(alsa::snd_rawmidi_open(
+                handle.as_mut_ptr(),
+                ptr::null_mut(),
+                name.as_ptr(),
+                0
+            ))?;
+
+            Ok(Self {
+                raw: RawMidi::new(ste::Tag::current_thread(), handle.assume_init()),
+            })
+        }
+    }
+
+    /// Get the name of the port.
+    pub fn name(&self) -> &CStr {
+        self.raw.name()
+    }
+
+    /// Set the port to nonblocking or blocking mode.
+    ///
+    /// In nonblocking mode [Input::read] returns [Error::WouldBlock] instead
+    /// of blocking if there's no data available.
+    pub fn set_nonblocking(&mut self, nonblocking: bool) -> Result<()> {
+        self.raw.set_nonblocking(nonblocking)
+    }
+
+    /// Read MIDI data into the given buffer, returning the number of bytes
+    /// read.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa::rawmidi;
+    /// use std::ffi::CString;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let name = CString::new("hw:1,0,0")?;
+    /// let mut input = rawmidi::Input::open(&name)?;
+    ///
+    /// let mut buf = [0u8; 256];
+    ///
+    /// loop {
+    ///     let n = input.read(&mut buf)?;
+    ///     println!("{:02x?}", &buf[..n]);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        self.raw.tag.ensure_on_thread();
+
+        let result = unsafe {
+            errno!(alsa::snd_rawmidi_read(
+                self.raw.handle.as_ptr(),
+                buf.as_mut_ptr() as *mut c::c_void,
+                buf.len()
+            ))
+        };
+
+        match result {
+            Ok(n) => Ok(n as usize),
+            Err(Errno::EWOULDBLOCK) => Err(Error::WouldBlock),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Discard all pending input.
+    pub fn drop(&mut self) -> Result<()> {
+        self.raw.drop()
+    }
+}
+
+/// An opened raw MIDI output port.
+///
+/// See [Output::open].
+pub struct Output {
+    raw: RawMidi,
+}
+
+impl Output {
+    /// Open the raw MIDI output port identified by name.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa::rawmidi;
+    /// use std::ffi::CString;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let name = CString::new("hw:1,0,0")?;
+    /// let output = rawmidi::Output::open(&name)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn open(name: &CStr) -> Result<Self> {
+        unsafe {
+            let mut handle = mem::MaybeUninit::uninit();
+
+            errno!(alsa::snd_rawmidi_open(
+                ptr::null_mut(),
+                handle.as_mut_ptr(),
+                name.as_ptr(),
+                0
+            ))?;
+
+            Ok(Self {
+                raw: RawMidi::new(ste::Tag::current_thread(), handle.assume_init()),
+            })
+        }
+    }
+
+    /// Get the name of the port.
+    pub fn name(&self) -> &CStr {
+        self.raw.name()
+    }
+
+    /// Set the port to nonblocking or blocking mode.
+    ///
+    /// In nonblocking mode [Output::write] returns [Error::WouldBlock]
+    /// instead of blocking if there's no room in the output buffer.
+    pub fn set_nonblocking(&mut self, nonblocking: bool) -> Result<()> {
+        self.raw.set_nonblocking(nonblocking)
+    }
+
+    /// Write MIDI data from the given buffer, returning the number of bytes
+    /// written.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa::rawmidi;
+    /// use std::ffi::CString;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let name = CString::new("hw:1,0,0")?;
+    /// let mut output = rawmidi::Output::open(&name)?;
+    ///
+    /// // Note on, middle C, full velocity.
+    /// output.write(&[0x90, 60, 127])?;
+    /// # Ok(()) }
+    /// ```
+    pub fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.raw.tag.ensure_on_thread();
+
+        let result = unsafe {
+            errno!(alsa::snd_rawmidi_write(
+                self.raw.handle.as_ptr(),
+                buf.as_ptr() as *const c::c_void,
+                buf.len()
+            ))
+        };
+
+        match result {
+            Ok(n) => Ok(n as usize),
+            Err(Errno::EWOULDBLOCK) => Err(Error::WouldBlock),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Block until all pending output has been transmitted.
+    pub fn drain(&mut self) -> Result<()> {
+        self.raw.tag.ensure_on_thread();
+
+        unsafe {
+            errno!(alsa::snd_rawmidi_drain(self.raw.handle.as_ptr()))?;
+            Ok(())
+        }
+    }
+
+    /// Discard all pending output.
+    pub fn drop(&mut self) -> Result<()> {
+        self.raw.drop()
+    }
+}
+
+/// Shared handle for both directions of a raw MIDI port.
+struct RawMidi {
+    tag: ste::Tag,
+    handle: ptr::NonNull<alsa::snd_rawmidi_t>,
+}
+
+impl RawMidi {
+    unsafe fn new(tag: ste::Tag, handle: *mut alsa::snd_rawmidi_t) -> Self {
+        Self {
+            tag,
+            handle: ptr::NonNull::new_unchecked(handle),
+        }
+    }
+
+    fn name(&self) -> &CStr {
+        self.tag.ensure_on_thread();
+        unsafe { CStr::from_ptr(alsa::snd_rawmidi_name(self.handle.as_ptr())) }
+    }
+
+    fn set_nonblocking(&mut self, nonblocking: bool) -> Result<()> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            errno!(alsa::snd_rawmidi_nonblock(
+                self.handle.as_ptr(),
+                nonblocking as c::c_int
+            ))?;
+            Ok(())
+        }
+    }
+
+    fn drop(&mut self) -> Result<()> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            errno!(alsa::snd_rawmidi_drop(self.handle.as_ptr()))?;
+            Ok(())
+        }
+    }
+}
+
+// Safety: [RawMidi] is tagged with the thread its created it and is ensured
+// not to leave it.
+unsafe impl Send for RawMidi {}
+
+impl Drop for RawMidi {
+    fn drop(&mut self) {
+        unsafe { alsa::snd_rawmidi_close(self.handle.as_ptr()) };
+    }
+}
+
+/// A raw MIDI port available on the system.
+///
+/// See [ports].
+#[derive(Debug, Clone)]
+pub struct Port {
+    card: c::c_int,
+    device: c::c_uint,
+    subdevice: c::c_uint,
+    stream: Stream,
+    name: CString,
+    subdevice_name: CString,
+}
+
+impl Port {
+    /// The index of the card the port belongs to.
+    pub fn card(&self) -> c::c_int {
+        self.card
+    }
+
+    /// The device of the port.
+    pub fn device(&self) -> c::c_uint {
+        self.device
+    }
+
+    /// The subdevice of the port.
+    pub fn subdevice(&self) -> c::c_uint {
+        self.subdevice
+    }
+
+    /// The direction of the port.
+    pub fn stream(&self) -> Stream {
+        self.stream
+    }
+
+    /// The name of the device the port belongs to.
+    pub fn name(&self) -> &CStr {
+        &self.name
+    }
+
+    /// The name of the subdevice of the port.
+    pub fn subdevice_name(&self) -> &CStr {
+        &self.subdevice_name
+    }
+
+    /// The identifier of the port, which can be used to open it through
+    /// [Input::open] or [Output::open].
+    ///
+    /// This is of the form `hw:<card>,<device>,<subdevice>`.
+    pub fn id(&self) -> CString {
+        let id = format!("hw:{},{},{}", self.card, self.device, self.subdevice);
+        CString::new(id).expect("port identifier should not contain nul")
+    }
+}
+
+/// Enumerate all raw MIDI ports of all cards on the system.
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::alsa::rawmidi;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// for port in rawmidi::ports()? {
+///     println!("{} ({}): {}", port.id().to_str()?, port.stream(), port.subdevice_name().to_str()?);
+/// }
+/// # Ok(()) }
+/// ```
+pub fn ports() -> Result<Vec<Port>> {
+    let mut ports = Vec::new();
+
+    for card in cards() {
+        let card = card?;
+        let name =
+            CString::new(format!("hw:{}", card.index())).expect("card name should not contain nul");
+        let control = Control::open(&name)?;
+
+        unsafe {
+            let mut info = mem::MaybeUninit::uninit();
+            errno!(alsa::snd_rawmidi_info_malloc(info.as_mut_ptr()))?;
+            let info = info.assume_init();
+
+            let result = ports_for_card(&control, info, &mut ports);
+            alsa::snd_rawmidi_info_free(info);
+            result?;
+        }
+    }
+
+    Ok(ports)
+}
+
+unsafe fn ports_for_card(
+    control: &Control,
+    info: *mut alsa::snd_rawmidi_info_t,
+    ports: &mut Vec<Port>,
+) -> Result<()> {
+    let mut device = -1;
+
+    loop {
+        errno!(alsa::snd_ctl_rawmidi_next_device(
+            control.handle.as_ptr(),
+            &mut device
+        ))?;
+
+        if device < 0 {
+            return Ok(());
+        }
+
+        for &stream in &[Stream::Output, Stream::Input] {
+            alsa::snd_rawmidi_info_set_device(info, device as c::c_uint);
+            alsa::snd_rawmidi_info_set_subdevice(info, 0);
+            alsa::snd_rawmidi_info_set_stream(info, stream as c::c_uint);
+
+            // NB: fails if the device doesn't support the given direction.
+            if errno!(alsa::snd_ctl_rawmidi_info(control.handle.as_ptr(), info)).is_err() {
+                continue;
+            }
+
+            let count = alsa::snd_rawmidi_info_get_subdevices_count(info);
+
+            for subdevice in 0..count {
+                alsa::snd_rawmidi_info_set_subdevice(info, subdevice);
+
+                if errno!(alsa::snd_ctl_rawmidi_info(control.handle.as_ptr(), info)).is_err() {
+                    continue;
+                }
+
+                ports.push(Port {
+                    card: alsa::snd_rawmidi_info_get_card(info),
+                    device: device as c::c_uint,
+                    subdevice,
+                    stream,
+                    name: CStr::from_ptr(alsa::snd_rawmidi_info_get_name(info)).to_owned(),
+                    subdevice_name: CStr::from_ptr(alsa::snd_rawmidi_info_get_subdevice_name(info))
+                        .to_owned(),
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use core::marker;
+use core::mem;
+
+use crate::alsa::{Error, Pcm, Result};
+use crate::buf_io;
+
+/// A interleaved type-checked PCM reader.
+///
+/// See [Pcm::reader].
+pub struct Reader<'a, T> {
+    pcm: &'a mut Pcm,
+    channels: usize,
+    _marker: marker::PhantomData<T>,
+}
+
+impl<'a, T> Reader<'a, T> {
+    /// Construct a new reader surrounding the given PCM.
+    ///
+    /// # Safety
+    ///
+    /// This constructor assumes that the caller has checked that type `T` is
+    /// appropriate for reading from the given PCM.
+    pub(super) unsafe fn new(pcm: &'a mut Pcm, channels: usize) -> Self {
+        Self {
+            pcm,
+            channels,
+            _marker: marker::PhantomData,
+        }
+    }
+
+    /// Read into an interleaved buffer.
+    ///
+    /// This blocks until the remaining frames of the buffer have been filled
+    /// with captured audio, advancing its write cursor as frames are read.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio::WriteBuf;
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Capture)?;
+    /// let config = pcm.configure::<i16>().channels(2).install()?;
+    ///
+    /// let mut samples = [0i16; 2048];
+    /// let mut buf = audio::wrap::interleaved(&mut samples[..], 2);
+    ///
+    /// let mut reader = pcm.reader::<i16>()?;
+    /// reader.read_interleaved(&mut buf)?;
+    /// assert!(!buf.has_remaining_mut());
+    /// # Ok(()) }
+    /// ```
+    pub fn read_interleaved<B>(&mut self, mut buf: B) -> Result<()>
+    where
+        B: audio_core::Buf<Sample = T>
+            + audio_core::WriteBuf
+            + audio_core::InterleavedBufMut<Sample = T>,
+    {
+        if buf.channels() != self.channels {
+            return Err(Error::ChannelsMismatch {
+                actual: buf.channels(),
+                expected: self.channels,
+            });
+        }
+
+        let channels = self.channels;
+        let frame_size = channels * mem::size_of::<T>();
+        let pcm = &mut *self.pcm;
+
+        // NB: reads are frame-oriented, so whole frames are always read.
+        buf_io::read(&mut buf, channels, |ptr, len| unsafe {
+            let read = pcm.read_interleaved_unchecked(ptr.cast(), (len / frame_size) as u64)?;
+            Ok(read as usize * frame_size)
+        })
+    }
+}
@@ -1,8 +1,40 @@
 use crate::alsa::Format;
+use audio_core::{Endian, PackedI24, SampleFormat};
 
 /// Trait used to designate types which are sample-appropriate for
 /// [Pcm][super::Pcm].
 ///
+/// Native types like [i16] and [f32] are appropriate for formats in the
+/// native endianness. Packed little endian 24-bit samples (`S24_3LE`) use
+/// [PackedI24], which is the same representation used by other backends.
+/// Formats which are in a non-native endianness can be accessed as raw bytes,
+/// like `[u8; 3]` for `S24_3BE`. So can formats which store 24-bit samples in
+/// the low bits of a 32-bit word (`S24_LE`), like `[u8; 4]`, since they
+/// aren't full scale 32-bit samples.
+///
+/// Sample types which correspond to a backend-agnostic [SampleFormat] report
+/// it through [Sample::SAMPLE_FORMAT].
+///
+/// # Examples
+///
+/// ```
+/// use audio::{Endian, PackedI24, SampleFormat};
+/// use audio_device::alsa::{Format, Sample};
+///
+/// assert!(PackedI24::test(Format::S243LE));
+/// assert!(!PackedI24::test(Format::S243BE));
+/// assert!(<[u8; 3]>::test(Format::S243LE));
+/// assert!(<[u8; 2]>::test(Format::S16BE));
+/// assert!(<[u8; 2]>::test(Format::S16LE));
+/// assert!(!<[u8; 2]>::test(Format::S32LE));
+/// assert!(!i32::test(Format::S24LE));
+/// assert!(<[u8; 4]>::test(Format::S24LE));
+///
+/// assert_eq!(PackedI24::SAMPLE_FORMAT, Some(SampleFormat::I24Packed(Endian::Little)));
+/// assert_eq!(f32::SAMPLE_FORMAT, Some(SampleFormat::F32(Endian::NATIVE)));
+/// assert_eq!(<[u8; 3]>::SAMPLE_FORMAT, None);
+/// ```
+///
 /// # Safety
 ///
 /// This trait is unsafe to implement, because an incorrectly implemented format
@@ -11,6 +43,9 @@ pub unsafe trait Sample {
     /// The default format to use for this sample.
     const DEFAULT_FORMAT: Format;
 
+    /// The backend-agnostic format of this sample type, if it has one.
+    const SAMPLE_FORMAT: Option<SampleFormat> = None;
+
     /// Test if the given format is appropriate for this sample type.
     fn test(format: Format) -> bool;
 
@@ -19,13 +54,18 @@ pub unsafe trait Sample {
 }
 
 macro_rules! implement {
-    ($ty:ty, $le:ident, $be:ident) => {
+    ($ty:ty, $le:ident, $be:ident $(, $sample_format:ident)?) => {
         unsafe impl Sample for $ty {
             #[cfg(target_endian = "little")]
             const DEFAULT_FORMAT: Format = Format::$le;
             #[cfg(target_endian = "big")]
             const DEFAULT_FORMAT: Format = Format::$be;
 
+            $(
+                const SAMPLE_FORMAT: Option<SampleFormat> =
+                    Some(SampleFormat::$sample_format(Endian::NATIVE));
+            )?
+
             fn test(format: Format) -> bool {
                 match format {
                     #[cfg(target_endian = "little")]
@@ -51,6 +91,7 @@ macro_rules! implement {
 
 unsafe impl Sample for u8 {
     const DEFAULT_FORMAT: Format = Format::U8;
+    const SAMPLE_FORMAT: Option<SampleFormat> = Some(SampleFormat::U8);
 
     fn test(format: Format) -> bool {
         matches!(format, Format::U8)
@@ -85,9 +126,75 @@ unsafe impl Sample for i8 {
     }
 }
 
-implement!(i16, S16LE, S16BE);
+unsafe impl Sample for PackedI24 {
+    const DEFAULT_FORMAT: Format = Format::S243LE;
+    const SAMPLE_FORMAT: Option<SampleFormat> = Some(SampleFormat::I24Packed(Endian::Little));
+
+    fn test(format: Format) -> bool {
+        matches!(format, Format::S243LE)
+    }
+
+    fn describe() -> &'static str {
+        "packed i24 (little endian)"
+    }
+}
+
+// Helper macro to implement [Sample] for raw bytes, which are appropriate for
+// any format of the given width regardless of endianness.
+macro_rules! implement_bytes {
+    ($n:literal, $le:ident, $be:ident, $desc:literal, [$($format:ident),* $(,)?]) => {
+        unsafe impl Sample for [u8; $n] {
+            #[cfg(target_endian = "little")]
+            const DEFAULT_FORMAT: Format = Format::$le;
+            #[cfg(target_endian = "big")]
+            const DEFAULT_FORMAT: Format = Format::$be;
+
+            fn test(format: Format) -> bool {
+                matches!(format, $(Format::$format)|*)
+            }
+
+            fn describe() -> &'static str {
+                $desc
+            }
+        }
+    };
+}
+
+implement!(i16, S16LE, S16BE, I16);
 implement!(u16, U16LE, U16BE);
-implement!(i32, S32LE, S32BE);
+implement!(i32, S32LE, S32BE, I32);
 implement!(u32, U32LE, U32BE);
-implement!(f32, FloatLE, FloatBE);
-implement!(f64, Float64LE, Float64BE);
+implement!(f32, FloatLE, FloatBE, F32);
+implement!(f64, Float64LE, Float64BE, F64);
+
+implement_bytes!(
+    2,
+    S16LE,
+    S16BE,
+    "[u8; 2] (raw 16-bit)",
+    [S16LE, S16BE, U16LE, U16BE]
+);
+implement_bytes!(
+    3,
+    S243LE,
+    S243BE,
+    "[u8; 3] (packed 24-bit)",
+    [
+        S243LE, S243BE, U243LE, U243BE, S203LE, S203BE, U203LE, U203BE, S183LE, S183BE, U183LE,
+        U183BE
+    ]
+);
+implement_bytes!(
+    4,
+    S32LE,
+    S32BE,
+    "[u8; 4] (raw 32-bit)",
+    [S24LE, S24BE, U24LE, U24BE, S32LE, S32BE, U32LE, U32BE, FloatLE, FloatBE]
+);
+implement_bytes!(
+    8,
+    Float64LE,
+    Float64BE,
+    "[u8; 8] (raw 64-bit)",
+    [Float64LE, Float64BE]
+);
@@ -0,0 +1,151 @@
+use crate::alsa::{Result, State};
+use crate::libc as c;
+use alsa_sys as alsa;
+use std::convert::TryFrom;
+use std::mem;
+use std::ptr;
+use std::time::Duration;
+
+/// A snapshot of the status of a [Pcm][super::Pcm].
+///
+/// See [Pcm::status][super::Pcm::status].
+pub struct Status {
+    handle: ptr::NonNull<alsa::snd_pcm_status_t>,
+}
+
+impl Status {
+    /// Read the current status of the given PCM.
+    pub(super) unsafe fn new(pcm: &mut ptr::NonNull<alsa::snd_pcm_t>) -> Result<Self> {
+        let mut handle = mem::MaybeUninit::uninit();
+
+        errno!(alsa::snd_pcm_status_malloc(handle.as_mut_ptr()))?;
+
+        let mut handle = ptr::NonNull::new_unchecked(handle.assume_init());
+
+        if let Err(e) = errno!(alsa::snd_pcm_status(pcm.as_ptr(), handle.as_mut())) {
+            alsa::snd_pcm_status_free(handle.as_mut());
+            return Err(e.into());
+        }
+
+        Ok(Status { handle })
+    }
+
+    /// The state of the stream.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// let status = pcm.status()?;
+    ///
+    /// dbg!(status.state());
+    /// # Ok(()) }
+    /// ```
+    pub fn state(&self) -> State {
+        unsafe {
+            let state = alsa::snd_pcm_status_get_state(self.handle.as_ptr());
+            State::from_value(state).unwrap_or(State::Private1)
+        }
+    }
+
+    /// The delay in frames, which is the distance between the current
+    /// application position and the frame which is currently being played or
+    /// captured.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// let config = pcm.configure::<i16>().install()?;
+    /// let status = pcm.status()?;
+    ///
+    /// let delay = status.delay() as f64 / config.rate as f64;
+    /// println!("delay: {:.3}s", delay);
+    /// # Ok(()) }
+    /// ```
+    pub fn delay(&self) -> c::c_long {
+        unsafe { alsa::snd_pcm_status_get_delay(self.handle.as_ptr()) }
+    }
+
+    /// The number of frames available for reading (capture) or writing
+    /// (playback).
+    pub fn available(&self) -> c::c_ulong {
+        unsafe { alsa::snd_pcm_status_get_avail(self.handle.as_ptr()) }
+    }
+
+    /// The maximum number of frames which have been available since the last
+    /// time the status was read.
+    pub fn available_max(&self) -> c::c_ulong {
+        unsafe { alsa::snd_pcm_status_get_avail_max(self.handle.as_ptr()) }
+    }
+
+    /// The system time at which the status was taken.
+    pub fn timestamp(&self) -> Duration {
+        unsafe {
+            let mut ts = mem::MaybeUninit::uninit();
+            alsa::snd_pcm_status_get_htstamp(self.handle.as_ptr(), ts.as_mut_ptr());
+            to_duration(ts.assume_init())
+        }
+    }
+
+    /// The system time at which the stream was last started, stopped or
+    /// paused.
+    pub fn trigger_timestamp(&self) -> Duration {
+        unsafe {
+            let mut ts = mem::MaybeUninit::uninit();
+            alsa::snd_pcm_status_get_trigger_htstamp(self.handle.as_ptr(), ts.as_mut_ptr());
+            to_duration(ts.assume_init())
+        }
+    }
+
+    /// The position of the audio stream as reported by the device at the
+    /// time the status was taken.
+    ///
+    /// Comparing this to [Status::timestamp] over time can be used to measure
+    /// drift between the audio and system clocks.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// let status = pcm.status()?;
+    ///
+    /// println!("system: {:?}, audio: {:?}", status.timestamp(), status.audio_timestamp());
+    /// # Ok(()) }
+    /// ```
+    pub fn audio_timestamp(&self) -> Duration {
+        unsafe {
+            let mut ts = mem::MaybeUninit::uninit();
+            alsa::snd_pcm_status_get_audio_htstamp(self.handle.as_ptr(), ts.as_mut_ptr());
+            to_duration(ts.assume_init())
+        }
+    }
+}
+
+// Safety: the status is a self-contained snapshot which doesn't reference the
+// PCM it was read from.
+unsafe impl Send for Status {}
+
+impl Drop for Status {
+    fn drop(&mut self) {
+        unsafe {
+            alsa::snd_pcm_status_free(self.handle.as_mut());
+        }
+    }
+}
+
+/// Convert a timestamp to a duration, treating negative components as zero.
+fn to_duration(ts: alsa::snd_htimestamp_t) -> Duration {
+    let secs = u64::try_from(ts.tv_sec).unwrap_or_default();
+    let nanos = u32::try_from(ts.tv_nsec).unwrap_or_default();
+    Duration::new(secs, nanos)
+}
@@ -0,0 +1,156 @@
+use core::marker;
+use std::time::Duration;
+
+use crate::alsa::{Error, Pcm, Result, Sample, Stream};
+use crate::libc as c;
+use crate::stream::StreamSpec;
+
+/// A portable output stream using ALSA.
+///
+/// Underruns are automatically recovered from.
+///
+/// See [OutputStream][crate::stream::OutputStream].
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::alsa;
+/// use audio_device::stream::{OutputStream, StreamSpec};
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let mut stream = alsa::OutputStream::<i16>::open_default(StreamSpec::new(2, 44100))?;
+/// let spec = stream.spec();
+///
+/// let samples = vec![0i16; spec.channels * 1024];
+/// stream.write(audio::wrap::interleaved(&samples[..], spec.channels))?;
+/// # Ok(()) }
+/// ```
+pub struct OutputStream<T> {
+    pcm: Pcm,
+    spec: StreamSpec,
+    _marker: marker::PhantomData<T>,
+}
+
+impl<T> crate::stream::OutputStream for OutputStream<T>
+where
+    T: Sample,
+{
+    type Sample = T;
+    type Error = Error;
+
+    fn open_default(spec: StreamSpec) -> Result<Self> {
+        let mut pcm = Pcm::open_default(Stream::Playback)?;
+        let spec = configure::<T>(&mut pcm, spec)?;
+
+        Ok(Self {
+            pcm,
+            spec,
+            _marker: marker::PhantomData,
+        })
+    }
+
+    fn spec(&self) -> StreamSpec {
+        self.spec
+    }
+
+    fn latency(&mut self) -> Result<Duration> {
+        let delay = self.pcm.delay()?;
+        Ok(frames_to_duration(delay, self.spec.sample_rate))
+    }
+
+    fn write<B>(&mut self, buf: B) -> Result<()>
+    where
+        B: audio_core::ReadBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBuf<Sample = T>,
+    {
+        let mut writer = self.pcm.writer::<T>()?;
+        writer.set_recover(true);
+        writer.write_all_interleaved(buf)
+    }
+}
+
+/// A portable input stream using ALSA.
+///
+/// See [InputStream][crate::stream::InputStream].
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::alsa;
+/// use audio_device::stream::{InputStream, StreamSpec};
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let mut stream = alsa::InputStream::<i16>::open_default(StreamSpec::new(2, 44100))?;
+/// let spec = stream.spec();
+///
+/// let mut samples = vec![0i16; spec.channels * 1024];
+/// stream.read(audio::wrap::interleaved(&mut samples[..], spec.channels))?;
+/// # Ok(()) }
+/// ```
+pub struct InputStream<T> {
+    pcm: Pcm,
+    spec: StreamSpec,
+    _marker: marker::PhantomData<T>,
+}
+
+impl<T> crate::stream::InputStream for InputStream<T>
+where
+    T: Sample,
+{
+    type Sample = T;
+    type Error = Error;
+
+    fn open_default(spec: StreamSpec) -> Result<Self> {
+        let mut pcm = Pcm::open_default(Stream::Capture)?;
+        let spec = configure::<T>(&mut pcm, spec)?;
+
+        Ok(Self {
+            pcm,
+            spec,
+            _marker: marker::PhantomData,
+        })
+    }
+
+    fn spec(&self) -> StreamSpec {
+        self.spec
+    }
+
+    fn latency(&mut self) -> Result<Duration> {
+        let delay = self.pcm.delay()?;
+        Ok(frames_to_duration(delay, self.spec.sample_rate))
+    }
+
+    fn read<B>(&mut self, buf: B) -> Result<()>
+    where
+        B: audio_core::WriteBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBufMut<Sample = T>,
+    {
+        let mut reader = self.pcm.reader::<T>()?;
+        reader.read_interleaved(buf)
+    }
+}
+
+/// Configure the PCM with the desired specification, returning the achieved
+/// one.
+pub(super) fn configure<T>(pcm: &mut Pcm, spec: StreamSpec) -> Result<StreamSpec>
+where
+    T: Sample,
+{
+    let config = pcm
+        .configure::<T>()
+        .channels(spec.channels as c::c_uint)
+        .rate(spec.sample_rate)
+        .install()?;
+
+    Ok(StreamSpec::new(config.channels as usize, config.rate))
+}
+
+fn frames_to_duration(frames: c::c_long, rate: u32) -> Duration {
+    if frames <= 0 || rate == 0 {
+        return Duration::ZERO;
+    }
+
+    Duration::from_nanos(frames as u64 * 1_000_000_000 / rate as u64)
+}
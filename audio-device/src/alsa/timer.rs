@@ -0,0 +1,284 @@
+#[cfg(feature = "poll-driver")]
+use crate::alsa::AsyncTimer;
+use crate::alsa::{Error, Result};
+use crate::libc as c;
+use crate::unix::Errno;
+use alsa_sys as alsa;
+use std::ffi::CStr;
+use std::mem;
+use std::ptr;
+
+/// The number of tick events read at a time.
+const READ_CHUNK: usize = 16;
+
+/// An opened timer.
+///
+/// A timer associated with a PCM ticks once every period, which allows for
+/// scheduling rendering without relying on period wakeups of the PCM itself.
+///
+/// See [Pcm::timer_name][super::Pcm::timer_name].
+pub struct Timer {
+    pub(super) tag: ste::Tag,
+    pub(super) handle: ptr::NonNull<alsa::snd_timer_t>,
+}
+
+impl Timer {
+    /// Open the timer identified by name.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// let timer = alsa::Timer::open(&pcm.timer_name()?)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn open(name: &CStr) -> Result<Self> {
+        Self::open_inner(name, 0)
+    }
+
+    /// Open the timer identified by name in a nonblocking mode.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// let timer = alsa::Timer::open_nonblocking(&pcm.timer_name()?)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn open_nonblocking(name: &CStr) -> Result<Self> {
+        Self::open_inner(name, alsa::SND_TIMER_OPEN_NONBLOCK)
+    }
+
+    fn open_inner(name: &CStr, mode: c::c_int) -> Result<Self> {
+        unsafe {
+            let mut handle = mem::MaybeUninit::uninit();
+            errno!(alsa::snd_timer_open(
+                handle.as_mut_ptr(),
+                name.as_ptr(),
+                mode
+            ))?;
+
+            Ok(Self {
+                tag: ste::Tag::current_thread(),
+                handle: ptr::NonNull::new_unchecked(handle.assume_init()),
+            })
+        }
+    }
+
+    /// Get the resolution of the timer in nanoseconds, which is the duration
+    /// of a single tick.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// let timer = alsa::Timer::open(&pcm.timer_name()?)?;
+    /// println!("resolution: {}ns", timer.resolution()?);
+    /// # Ok(()) }
+    /// ```
+    pub fn resolution(&self) -> Result<c::c_long> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            let mut info = mem::MaybeUninit::uninit();
+            errno!(alsa::snd_timer_info_malloc(info.as_mut_ptr()))?;
+            let info = info.assume_init();
+
+            let result = errno!(alsa::snd_timer_info(self.handle.as_ptr(), info));
+            let resolution = alsa::snd_timer_info_get_resolution(info);
+            alsa::snd_timer_info_free(info);
+            result?;
+            Ok(resolution)
+        }
+    }
+
+    /// Configure the timer to fire an event every `ticks` ticks.
+    ///
+    /// The timer is configured to start automatically when the device it's
+    /// associated with starts.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// let mut timer = alsa::Timer::open(&pcm.timer_name()?)?;
+    /// timer.set_ticks(1)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn set_ticks(&mut self, ticks: c::c_long) -> Result<()> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            let mut params = mem::MaybeUninit::uninit();
+            errno!(alsa::snd_timer_params_malloc(params.as_mut_ptr()))?;
+            let params = params.assume_init();
+
+            alsa::snd_timer_params_set_auto_start(params, 1);
+            alsa::snd_timer_params_set_ticks(params, ticks);
+            let result = errno!(alsa::snd_timer_params(self.handle.as_ptr(), params));
+            alsa::snd_timer_params_free(params);
+            result?;
+            Ok(())
+        }
+    }
+
+    /// Start the timer.
+    pub fn start(&mut self) -> Result<()> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            errno!(alsa::snd_timer_start(self.handle.as_ptr()))?;
+            Ok(())
+        }
+    }
+
+    /// Stop the timer.
+    pub fn stop(&mut self) -> Result<()> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            errno!(alsa::snd_timer_stop(self.handle.as_ptr()))?;
+            Ok(())
+        }
+    }
+
+    /// Continue a timer which has been stopped.
+    pub fn resume(&mut self) -> Result<()> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            errno!(alsa::snd_timer_continue(self.handle.as_ptr()))?;
+            Ok(())
+        }
+    }
+
+    /// Wait for the timer to fire, returning the number of ticks which have
+    /// elapsed since the last time it was waited on.
+    ///
+    /// If the timer was opened in nonblocking mode this will return
+    /// [Error::WouldBlock] if the timer hasn't fired yet.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// pcm.configure::<i16>().install()?;
+    ///
+    /// let mut timer = alsa::Timer::open(&pcm.timer_name()?)?;
+    /// timer.set_ticks(1)?;
+    /// pcm.start()?;
+    ///
+    /// loop {
+    ///     let ticks = timer.wait()?;
+    ///     // render `ticks` periods of audio.
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn wait(&mut self) -> Result<c::c_uint> {
+        match self.read_ticks() {
+            Err(Error::Sys(Errno::EWOULDBLOCK)) => Err(Error::WouldBlock),
+            result => result,
+        }
+    }
+
+    /// Read all pending tick events, returning the sum of their ticks.
+    pub(super) fn read_ticks(&mut self) -> Result<c::c_uint> {
+        self.tag.ensure_on_thread();
+
+        let mut buf = [alsa::snd_timer_read_t {
+            resolution: 0,
+            ticks: 0,
+        }; READ_CHUNK];
+
+        let size = mem::size_of_val(&buf);
+
+        unsafe {
+            let n = errno!(alsa::snd_timer_read(
+                self.handle.as_ptr(),
+                buf.as_mut_ptr() as *mut c::c_void,
+                size
+            ))? as usize;
+
+            let count = n / mem::size_of::<alsa::snd_timer_read_t>();
+
+            Ok(buf[..count]
+                .iter()
+                .fold(0, |ticks, read| ticks.wrapping_add(read.ticks)))
+        }
+    }
+
+    cfg_poll_driver! {
+        /// Construct an async wrapper around the timer, which can be used to
+        /// wait for the timer to fire without blocking.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the audio runtime is not available.
+        ///
+        /// See [Runtime][crate::runtime::Runtime] for more.
+        ///
+        /// # Examples
+        ///
+        /// ```no_run
+        /// use audio_device::alsa;
+        ///
+        /// async fn render(timer: &mut alsa::Timer) -> anyhow::Result<()> {
+        ///     let mut timer = timer.async_timer()?;
+        ///
+        ///     loop {
+        ///         let ticks = timer.wait().await?;
+        ///         // render `ticks` periods of audio.
+        ///     }
+        /// }
+        /// ```
+        pub fn async_timer(&mut self) -> Result<AsyncTimer<'_>> {
+            self.tag.ensure_on_thread();
+
+            let mut fds = [c::pollfd {
+                fd: 0,
+                events: 0,
+                revents: 0,
+            }];
+
+            let count = unsafe {
+                errno!(alsa::snd_timer_poll_descriptors(
+                    self.handle.as_ptr(),
+                    fds.as_mut_ptr(),
+                    fds.len() as c::c_uint
+                ))?
+            };
+
+            if count != 1 {
+                return Err(Error::MissingPollFds);
+            }
+
+            let fd = fds[0];
+            unsafe { AsyncTimer::new(self, fd) }
+        }
+    }
+}
+
+// Safety: [Timer] is tagged with the thread its created it and is ensured not
+// to leave it.
+unsafe impl Send for Timer {}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        unsafe { alsa::snd_timer_close(self.handle.as_ptr()) };
+    }
+}
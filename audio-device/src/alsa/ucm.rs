@@ -0,0 +1,301 @@
+//! Bindings for the ALSA Use Case Manager (UCM).
+//!
+//! The use case manager is used on devices like phones and single-board
+//! computers to configure the mixer routing of a card for a particular use
+//! case, like music playback or a voice call, before a PCM can be opened.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use audio_device::alsa::ucm;
+//! use std::ffi::CString;
+//!
+//! # fn main() -> anyhow::Result<()> {
+//! let card = CString::new("hw:0")?;
+//! let mut manager = ucm::Manager::open(&card)?;
+//!
+//! for verb in manager.verbs()? {
+//!     println!("{}", verb.name().to_str()?);
+//!
+//!     for device in manager.devices(verb.name())? {
+//!         println!("  {}", device.name().to_str()?);
+//!     }
+//! }
+//!
+//! manager.set_verb(&CString::new("HiFi")?)?;
+//! manager.enable_device(&CString::new("Speaker")?)?;
+//! # Ok(()) }
+//! ```
+
+use crate::alsa::{CString, Result};
+use crate::libc as c;
+use alsa_sys as alsa;
+use std::ffi::{self, CStr};
+use std::mem;
+use std::ptr;
+use std::slice;
+
+/// A use case manager for a single card.
+///
+/// See [Manager::open].
+pub struct Manager {
+    tag: ste::Tag,
+    handle: ptr::NonNull<alsa::snd_use_case_mgr_t>,
+}
+
+impl Manager {
+    /// Open the use case manager for the given card.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa::ucm;
+    /// use std::ffi::CString;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let card = CString::new("hw:0")?;
+    /// let manager = ucm::Manager::open(&card)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn open(card: &CStr) -> Result<Self> {
+        unsafe {
+            let mut handle = mem::MaybeUninit::uninit();
+            errno!(alsa::snd_use_case_mgr_open(
+                handle.as_mut_ptr(),
+                card.as_ptr()
+            ))?;
+
+            Ok(Self {
+                tag: ste::Tag::current_thread(),
+                handle: ptr::NonNull::new_unchecked(handle.assume_init()),
+            })
+        }
+    }
+
+    /// Reload the use case configuration of the card.
+    pub fn reload(&mut self) -> Result<()> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            errno!(alsa::snd_use_case_mgr_reload(self.handle.as_mut()))?;
+            Ok(())
+        }
+    }
+
+    /// Reset the card to its default state, disabling all use cases.
+    pub fn reset(&mut self) -> Result<()> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            errno!(alsa::snd_use_case_mgr_reset(self.handle.as_mut()))?;
+            Ok(())
+        }
+    }
+
+    /// List the verbs supported by the card, like `HiFi` or `Voice Call`.
+    pub fn verbs(&self) -> Result<Vec<Entry>> {
+        self.list_pairs(ident(b"_verbs\0"))
+    }
+
+    /// List the devices supported by the given verb, like `Speaker` or
+    /// `Headphones`.
+    pub fn devices(&self, verb: &CStr) -> Result<Vec<Entry>> {
+        self.list_pairs(&identifier("_devices/", verb))
+    }
+
+    /// List the modifiers supported by the given verb.
+    pub fn modifiers(&self, verb: &CStr) -> Result<Vec<Entry>> {
+        self.list_pairs(&identifier("_modifiers/", verb))
+    }
+
+    /// Get the current verb, if one is set.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa::ucm;
+    /// use std::ffi::CString;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let card = CString::new("hw:0")?;
+    /// let manager = ucm::Manager::open(&card)?;
+    ///
+    /// if let Some(verb) = manager.verb()? {
+    ///     println!("{}", verb.to_str()?);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn verb(&self) -> Result<Option<CString>> {
+        let verb = self.get(ident(b"_verb\0"))?;
+        Ok(verb.filter(|v| !v.to_bytes().is_empty()))
+    }
+
+    /// Set the current verb.
+    pub fn set_verb(&mut self, verb: &CStr) -> Result<()> {
+        self.set(ident(b"_verb\0"), verb)
+    }
+
+    /// Enable the given device for the current verb.
+    pub fn enable_device(&mut self, device: &CStr) -> Result<()> {
+        self.set(ident(b"_enadev\0"), device)
+    }
+
+    /// Disable the given device for the current verb.
+    pub fn disable_device(&mut self, device: &CStr) -> Result<()> {
+        self.set(ident(b"_disdev\0"), device)
+    }
+
+    /// Enable the given modifier for the current verb.
+    pub fn enable_modifier(&mut self, modifier: &CStr) -> Result<()> {
+        self.set(ident(b"_enamod\0"), modifier)
+    }
+
+    /// Disable the given modifier for the current verb.
+    pub fn disable_modifier(&mut self, modifier: &CStr) -> Result<()> {
+        self.set(ident(b"_dismod\0"), modifier)
+    }
+
+    /// Get the value of the given identifier, like `PlaybackPCM/Speaker`
+    /// which is the name of the PCM to open for a device.
+    ///
+    /// Returns `None` if the value doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa::{self, ucm};
+    /// use std::ffi::CString;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let card = CString::new("hw:0")?;
+    /// let mut manager = ucm::Manager::open(&card)?;
+    /// manager.set_verb(&CString::new("HiFi")?)?;
+    ///
+    /// if let Some(name) = manager.get(&CString::new("PlaybackPCM/Speaker")?)? {
+    ///     let pcm = alsa::Pcm::open(&name, alsa::Stream::Playback)?;
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn get(&self, identifier: &CStr) -> Result<Option<CString>> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            let mut value = ptr::null();
+            let result =
+                alsa::snd_use_case_get(self.handle.as_ptr(), identifier.as_ptr(), &mut value);
+
+            // NB: missing values are reported as ENOENT.
+            if result == -libc::ENOENT {
+                return Ok(None);
+            }
+
+            errno!(result)?;
+
+            if value.is_null() {
+                return Ok(None);
+            }
+
+            Ok(Some(CString::from_raw(value as *mut c::c_char)))
+        }
+    }
+
+    /// Set the value of the given identifier.
+    pub fn set(&mut self, identifier: &CStr, value: &CStr) -> Result<()> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            errno!(alsa::snd_use_case_set(
+                self.handle.as_mut(),
+                identifier.as_ptr(),
+                value.as_ptr()
+            ))?;
+            Ok(())
+        }
+    }
+
+    /// List entries which are returned as name and comment pairs.
+    fn list_pairs(&self, identifier: &CStr) -> Result<Vec<Entry>> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            let mut list = ptr::null_mut();
+
+            let len = errno!(alsa::snd_use_case_get_list(
+                self.handle.as_ptr(),
+                identifier.as_ptr(),
+                &mut list
+            ))? as usize;
+
+            if list.is_null() {
+                return Ok(Vec::new());
+            }
+
+            let items = slice::from_raw_parts(list, len);
+            let mut entries = Vec::with_capacity(len / 2);
+
+            for pair in items.chunks(2) {
+                let name = match pair.first() {
+                    Some(name) if !name.is_null() => CStr::from_ptr(*name).to_owned(),
+                    _ => continue,
+                };
+
+                let comment = match pair.get(1) {
+                    Some(comment) if !comment.is_null() => {
+                        Some(CStr::from_ptr(*comment).to_owned())
+                    }
+                    _ => None,
+                };
+
+                entries.push(Entry { name, comment });
+            }
+
+            alsa::snd_use_case_free_list(list, len as c::c_int);
+            Ok(entries)
+        }
+    }
+}
+
+// Safety: [Manager] is tagged with the thread its created it and is ensured
+// not to leave it.
+unsafe impl Send for Manager {}
+
+impl Drop for Manager {
+    fn drop(&mut self) {
+        unsafe { alsa::snd_use_case_mgr_close(self.handle.as_ptr()) };
+    }
+}
+
+/// A named entry, like a verb or a device, as listed by a [Manager].
+#[derive(Debug, Clone)]
+pub struct Entry {
+    name: ffi::CString,
+    comment: Option<ffi::CString>,
+}
+
+impl Entry {
+    /// The name of the entry.
+    pub fn name(&self) -> &CStr {
+        &self.name
+    }
+
+    /// A human readable comment describing the entry, if available.
+    pub fn comment(&self) -> Option<&CStr> {
+        self.comment.as_deref()
+    }
+}
+
+/// Construct a static identifier.
+fn ident(bytes: &'static [u8]) -> &'static CStr {
+    debug_assert!(bytes.ends_with(&[0]));
+    // Safety: all static identifiers are nul-terminated literals.
+    unsafe { CStr::from_bytes_with_nul_unchecked(bytes) }
+}
+
+/// Construct an identifier out of a prefix and a suffix.
+fn identifier(prefix: &str, suffix: &CStr) -> ffi::CString {
+    let mut identifier = Vec::with_capacity(prefix.len() + suffix.to_bytes().len());
+    identifier.extend_from_slice(prefix.as_bytes());
+    identifier.extend_from_slice(suffix.to_bytes());
+    // Safety: neither the prefix nor the suffix contains interior nul bytes.
+    unsafe { ffi::CString::from_vec_unchecked(identifier) }
+}
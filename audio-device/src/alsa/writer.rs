@@ -1,7 +1,13 @@
 use core::marker;
 
-use crate::alsa::{Error, Pcm, Result};
+use audio_core::{Channel, Frame, LinearChannel};
+
+use crate::alsa::{Access, Error, Pcm, Result};
 use crate::libc as c;
+use crate::unix::Errno;
+
+/// The number of frames which are staged at a time by [Writer::write_frames].
+const STAGING_FRAMES: usize = 256;
 
 /// A interleaved type-checked PCM writer.
 ///
@@ -9,6 +15,13 @@ use crate::libc as c;
 pub struct Writer<'a, T> {
     pcm: &'a mut Pcm,
     channels: usize,
+    access: Access,
+    recover: bool,
+    xruns: usize,
+    /// Interleaved frames staged by [Writer::write_frames] and
+    /// [Writer::write_translated], which is kept so that it's only allocated
+    /// once.
+    staging: Vec<T>,
     _marker: marker::PhantomData<T>,
 }
 
@@ -19,15 +32,149 @@ impl<'a, T> Writer<'a, T> {
     ///
     /// This constructor assumes that the caller has checked that type `T` is
     /// appropriate for writing to the given PCM.
-    pub(super) unsafe fn new(pcm: &'a mut Pcm, channels: usize) -> Self {
+    pub(super) unsafe fn new(pcm: &'a mut Pcm, channels: usize, access: Access) -> Self {
         Self {
             pcm,
             channels,
+            access,
+            recover: false,
+            xruns: 0,
+            staging: Vec::new(),
             _marker: marker::PhantomData,
         }
     }
 
+    /// Configure whether the writer should automatically recover from
+    /// underruns and suspended streams.
+    ///
+    /// When enabled, writes which fail with `EPIPE` or `ESTRPIPE` recover the
+    /// stream through [Pcm::recover] and are retried instead of raising an
+    /// error. Each recovery is counted in [Writer::xruns].
+    ///
+    /// This is disabled by default.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// let config = pcm.configure::<i16>().channels(2).install()?;
+    ///
+    /// let buf = audio::sequential![[0i16; 1024]; 2];
+    ///
+    /// let mut writer = pcm.writer::<i16>()?;
+    /// writer.set_recover(true);
+    /// writer.write_frames(&buf)?;
+    ///
+    /// if writer.xruns() > 0 {
+    ///     println!("recovered from {} underruns", writer.xruns());
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn set_recover(&mut self, recover: bool) {
+        self.recover = recover;
+    }
+
+    /// The number of underruns or suspensions which the writer has recovered
+    /// from.
+    ///
+    /// See [Writer::set_recover].
+    pub fn xruns(&self) -> usize {
+        self.xruns
+    }
+
+    /// Get the current playback delay in frames, which is the number of frames
+    /// which have to pass before a frame written now is heard.
+    ///
+    /// See [Pcm::delay].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// let config = pcm.configure::<i16>().channels(2).install()?;
+    ///
+    /// let buf = audio::sequential![[0i16; 1024]; 2];
+    ///
+    /// let mut writer = pcm.writer::<i16>()?;
+    /// writer.write_frames(&buf)?;
+    ///
+    /// // the last frame written will be heard after this many frames.
+    /// let delay = writer.delay()?;
+    /// println!("delay: {:.3}s", delay as f64 / config.rate as f64);
+    /// # Ok(()) }
+    /// ```
+    pub fn delay(&mut self) -> Result<c::c_long> {
+        self.pcm.delay()
+    }
+
+    /// Write unchecked interleaved frames, recovering from underruns and
+    /// retrying the write if it's enabled.
+    ///
+    /// Returns the number of frames written.
+    ///
+    /// # Safety
+    ///
+    /// Same as [Pcm::write_interleaved_unchecked].
+    unsafe fn write_unchecked(&mut self, ptr: *const c::c_void, frames: usize) -> Result<usize> {
+        loop {
+            let result = self.pcm.write_interleaved_unchecked(ptr, frames as u64);
+
+            if let Some(written) = self.recover_written(result)? {
+                return Ok(written);
+            }
+        }
+    }
+
+    /// Write unchecked non-interleaved frames, recovering from underruns and
+    /// retrying the write if it's enabled.
+    ///
+    /// # Safety
+    ///
+    /// Same as [Pcm::write_noninterleaved_unchecked].
+    unsafe fn write_noninterleaved_unchecked(
+        &mut self,
+        ptrs: &mut [*mut c::c_void],
+        frames: usize,
+    ) -> Result<usize> {
+        loop {
+            let result = self
+                .pcm
+                .write_noninterleaved_unchecked(ptrs.as_mut_ptr(), frames as u64);
+
+            if let Some(written) = self.recover_written(result)? {
+                return Ok(written);
+            }
+        }
+    }
+
+    /// Recover from an underrun raised by a write if it's enabled.
+    ///
+    /// Returns `None` if the stream was recovered, in which case the write
+    /// should be retried.
+    fn recover_written(&mut self, result: Result<c::c_long>) -> Result<Option<usize>> {
+        match result {
+            Ok(written) => Ok(Some(written as usize)),
+            Err(Error::Sys(errno @ (Errno::EPIPE | Errno::ESTRPIPE))) if self.recover => {
+                self.pcm.recover(errno, true)?;
+                self.xruns = self.xruns.wrapping_add(1);
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
     /// Write an interleaved buffer.
+    ///
+    /// The buffer is handed to the device as-is, so its samples have to be of
+    /// the type used by the device. To write a buffer with a different sample
+    /// type, use [Writer::write_translated], which translates it through a
+    /// staging buffer instead.
     pub fn write_interleaved<B>(&mut self, mut buf: B) -> Result<()>
     where
         B: audio_core::Buf<Sample = T> + audio_core::ReadBuf + audio_core::ExactSizeBuf + audio_core::InterleavedBuf,
@@ -43,10 +190,467 @@ impl<'a, T> Writer<'a, T> {
 
         unsafe {
             let ptr = buf.as_interleaved().as_ptr() as *const c::c_void;
-            let written = self.pcm.write_interleaved_unchecked(ptr, frames as u64)?;
-            buf.advance(written as usize);
+            let written = self.write_unchecked(ptr, frames)?;
+            buf.advance(written);
+        }
+
+        Ok(())
+    }
+
+    /// Block until the device is ready to accept more frames, recovering from
+    /// underruns if it's enabled.
+    fn wait(&mut self) -> Result<()> {
+        match self.pcm.wait(None) {
+            Ok(_) => Ok(()),
+            Err(Error::Sys(errno @ (Errno::EPIPE | Errno::ESTRPIPE))) if self.recover => {
+                self.pcm.recover(errno, true)?;
+                self.xruns = self.xruns.wrapping_add(1);
+                Ok(())
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Repeatedly call `write` with the offset of the first frame that hasn't
+    /// been written yet until `len` frames have been accepted by the device.
+    ///
+    /// `write` returns the number of frames written. If the PCM is in
+    /// nonblocking mode and it fails with `EWOULDBLOCK`, this waits for the
+    /// device to become ready before calling it again.
+    fn write_all_with<F>(&mut self, len: usize, mut write: F) -> Result<()>
+    where
+        F: FnMut(&mut Self, usize) -> Result<usize>,
+    {
+        let mut offset = 0;
+
+        while offset < len {
+            match write(self, offset) {
+                Ok(written) => offset += written,
+                Err(Error::Sys(Errno::EWOULDBLOCK)) => self.wait()?,
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write `len` interleaved frames from `samples`, blocking until all of
+    /// them have been accepted by the device.
+    ///
+    /// # Safety
+    ///
+    /// `samples` must point to at least `len` frames with the number of
+    /// channels used by the writer.
+    unsafe fn write_all_unchecked(&mut self, samples: *const T, len: usize) -> Result<()> {
+        self.write_all_with(len, |this, offset| unsafe {
+            let ptr = samples.add(offset * this.channels) as *const c::c_void;
+            this.write_unchecked(ptr, len - offset)
+        })
+    }
+
+    /// Write an entire interleaved buffer, blocking until all of its frames
+    /// have been accepted by the device.
+    ///
+    /// Short writes are retried until the buffer is exhausted. If the PCM is
+    /// in nonblocking mode, this waits for the device to become ready instead
+    /// of raising [Error::WouldBlock]. Underruns are handled according to
+    /// [Writer::set_recover].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// let config = pcm.configure::<i16>().channels(2).install()?;
+    ///
+    /// let samples = [0i16; 2048];
+    ///
+    /// let mut writer = pcm.writer::<i16>()?;
+    /// writer.set_recover(true);
+    /// writer.write_all_interleaved(audio::wrap::interleaved(&samples[..], 2))?;
+    /// # Ok(()) }
+    /// ```
+    pub fn write_all_interleaved<B>(&mut self, mut buf: B) -> Result<()>
+    where
+        B: audio_core::Buf<Sample = T>
+            + audio_core::ReadBuf
+            + audio_core::ExactSizeBuf
+            + audio_core::InterleavedBuf,
+    {
+        if buf.channels() != self.channels {
+            return Err(Error::ChannelsMismatch {
+                actual: buf.channels(),
+                expected: self.channels,
+            });
+        }
+
+        let len = buf.frames();
+
+        self.write_all_with(len, |this, offset| unsafe {
+            let ptr = buf.as_interleaved().as_ptr() as *const c::c_void;
+            let written = this.write_unchecked(ptr, len - offset)?;
+            buf.advance(written);
+            Ok(written)
+        })
+    }
+
+    /// Write multiple consecutive interleaved segments, blocking until all of
+    /// their frames have been accepted by the device.
+    ///
+    /// Each segment is submitted to the device directly, so this can be used
+    /// to write non-contiguous regions like the two halves of a ring buffer
+    /// without first copying them into one contiguous buffer. Short writes,
+    /// nonblocking mode and underruns are handled like in
+    /// [Writer::write_all_interleaved].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// let config = pcm.configure::<i16>().channels(2).install()?;
+    ///
+    /// // a ring buffer whose read position is at sample 1024.
+    /// let ring = [0i16; 4096];
+    /// let (wrapped, unread) = ring.split_at(1024);
+    ///
+    /// let mut writer = pcm.writer::<i16>()?;
+    /// writer.write_vectored([
+    ///     audio::wrap::interleaved(unread, 2),
+    ///     audio::wrap::interleaved(wrapped, 2),
+    /// ])?;
+    /// # Ok(()) }
+    /// ```
+    pub fn write_vectored<I, B>(&mut self, segments: I) -> Result<()>
+    where
+        I: IntoIterator<Item = B>,
+        B: audio_core::Buf<Sample = T>
+            + audio_core::ExactSizeBuf
+            + audio_core::InterleavedBuf<Sample = T>,
+    {
+        for segment in segments {
+            if segment.channels() != self.channels {
+                return Err(Error::ChannelsMismatch {
+                    actual: segment.channels(),
+                    expected: self.channels,
+                });
+            }
+
+            let samples = segment.as_interleaved();
+            let len = usize::min(segment.frames(), samples.len() / self.channels.max(1));
+
+            unsafe {
+                self.write_all_unchecked(samples.as_ptr(), len)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write a buffer of linear channels, such as a sequential or dynamic
+    /// buffer, without interleaving it first.
+    ///
+    /// Each channel is handed to the device as-is, which requires the PCM to
+    /// be configured with [Access::ReadWriteNoninterleaved] or this will error
+    /// with [Error::AccessMismatch]. Short writes, nonblocking mode and
+    /// underruns are handled like in [Writer::write_all_interleaved].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// let config = pcm.configure::<i16>()
+    ///     .channels(2)
+    ///     .access(alsa::Access::ReadWriteNoninterleaved)
+    ///     .install()?;
+    ///
+    /// let buf = audio::sequential![[0i16; 1024]; 2];
+    ///
+    /// let mut writer = pcm.writer::<i16>()?;
+    /// writer.write_noninterleaved(&buf)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn write_noninterleaved<'b, B>(&mut self, buf: &'b B) -> Result<()>
+    where
+        B: ?Sized + audio_core::ExactSizeBuf<Sample = T>,
+        B::Channel<'b>: LinearChannel,
+    {
+        if self.access != Access::ReadWriteNoninterleaved {
+            return Err(Error::AccessMismatch {
+                actual: self.access,
+                expected: Access::ReadWriteNoninterleaved,
+            });
+        }
+
+        if buf.channels() != self.channels {
+            return Err(Error::ChannelsMismatch {
+                actual: buf.channels(),
+                expected: self.channels,
+            });
+        }
+
+        let mut channels = Vec::with_capacity(self.channels);
+        let mut len = buf.frames();
+
+        for channel in buf.iter_channels() {
+            let channel = channel.as_linear_channel();
+            len = usize::min(len, channel.len());
+            channels.push(channel.as_ptr());
+        }
+
+        let mut ptrs = Vec::with_capacity(self.channels);
+
+        self.write_all_with(len, |this, offset| unsafe {
+            ptrs.clear();
+            ptrs.extend(channels.iter().map(|ptr| ptr.add(offset) as *mut c::c_void));
+            this.write_noninterleaved_unchecked(&mut ptrs, len - offset)
+        })
+    }
+
+    /// Try to write an interleaved buffer without blocking.
+    ///
+    /// This requires the PCM to be in nonblocking mode, see
+    /// [Pcm::set_nonblocking]. Returns the number of frames which were
+    /// accepted by the device and advances the buffer by as much. If the
+    /// device can't accept any frames right now, [Error::WouldBlock] is
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio::ReadBuf;
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default_nonblocking(alsa::Stream::Playback)?;
+    /// let config = pcm.configure::<i16>().channels(2).install()?;
+    ///
+    /// let samples = [0i16; 2048];
+    /// let mut buf = audio::wrap::interleaved(&samples[..], 2);
+    ///
+    /// let mut writer = pcm.writer::<i16>()?;
+    ///
+    /// while buf.has_remaining() {
+    ///     match writer.try_write_interleaved(&mut buf) {
+    ///         Ok(written) => println!("wrote {} frames", written),
+    ///         Err(alsa::Error::WouldBlock) => {
+    ///             // wait for the device in your own event loop.
+    ///         }
+    ///         Err(e) => return Err(e.into()),
+    ///     }
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn try_write_interleaved<B>(&mut self, mut buf: B) -> Result<usize>
+    where
+        B: audio_core::Buf<Sample = T>
+            + audio_core::ReadBuf
+            + audio_core::ExactSizeBuf
+            + audio_core::InterleavedBuf,
+    {
+        if buf.channels() != self.channels {
+            return Err(Error::ChannelsMismatch {
+                actual: buf.channels(),
+                expected: self.channels,
+            });
+        }
+
+        let frames = buf.frames();
+
+        unsafe {
+            let ptr = buf.as_interleaved().as_ptr() as *const c::c_void;
+
+            let written = match self.write_unchecked(ptr, frames) {
+                Ok(written) => written,
+                Err(Error::Sys(Errno::EWOULDBLOCK)) => return Err(Error::WouldBlock),
+                Err(e) => return Err(e),
+            };
+
+            buf.advance(written);
+            Ok(written)
+        }
+    }
+
+    /// Write all frames in a uniform buffer.
+    ///
+    /// Unlike [Writer::write_interleaved] this accepts buffers of any layout,
+    /// such as sequential buffers. Frames are staged into an interleaved
+    /// buffer before they are written to the device.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// let config = pcm.configure::<i16>().channels(2).install()?;
+    ///
+    /// let buf = audio::sequential![[0i16; 1024]; 2];
+    ///
+    /// let mut writer = pcm.writer::<i16>()?;
+    /// writer.write_frames(&buf)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn write_frames<B>(&mut self, buf: B) -> Result<()>
+    where
+        T: Copy,
+        B: audio_core::UniformBuf<Sample = T>,
+    {
+        if buf.channels() != self.channels {
+            return Err(Error::ChannelsMismatch {
+                actual: buf.channels(),
+                expected: self.channels,
+            });
+        }
+
+        let mut frames = buf.iter_frames();
+
+        loop {
+            self.staging.clear();
+
+            for frame in (&mut frames).take(STAGING_FRAMES) {
+                self.staging.extend(frame.iter());
+            }
+
+            if self.staging.is_empty() {
+                break;
+            }
+
+            let len = self.staging.len() / self.channels;
+
+            // Safety: writing doesn't touch the staging buffer.
+            unsafe {
+                self.write_all_unchecked(self.staging.as_ptr(), len)?;
+            }
         }
 
         Ok(())
     }
+
+    /// Write all frames in a buffer whose sample type differs from the one
+    /// used by the device, translating them on the fly.
+    ///
+    /// Frames are translated with [Translate][audio_core::Translate] into an
+    /// internal interleaved staging buffer before they are written to the
+    /// device, so callers don't have to maintain a separate conversion
+    /// buffer. This is kept separate from [Writer::write_interleaved] so that
+    /// writing a buffer which already has the sample type of the device never
+    /// pays for the copy. Short writes, nonblocking mode and underruns are handled like
+    /// in [Writer::write_all_interleaved].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// let config = pcm.configure::<i16>().channels(2).install()?;
+    ///
+    /// let buf = audio::sequential![[0.0f32; 1024]; 2];
+    ///
+    /// let mut writer = pcm.writer::<i16>()?;
+    /// writer.write_translated(&buf)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn write_translated<B>(&mut self, buf: B) -> Result<()>
+    where
+        T: audio_core::Sample + audio_core::Translate<B::Sample>,
+        B: audio_core::ExactSizeBuf,
+    {
+        if buf.channels() != self.channels {
+            return Err(Error::ChannelsMismatch {
+                actual: buf.channels(),
+                expected: self.channels,
+            });
+        }
+
+        let frames = buf.frames();
+        self.staging.resize(STAGING_FRAMES * self.channels, T::ZERO);
+        let mut start = 0;
+
+        while start < frames {
+            let len = usize::min(frames - start, STAGING_FRAMES);
+
+            for (c, channel) in buf.iter_channels().enumerate() {
+                let channel = channel.skip(start).limit(len);
+
+                for (f, sample) in channel.iter().enumerate() {
+                    self.staging[f * self.channels + c] = T::translate(sample);
+                }
+            }
+
+            // Safety: writing doesn't touch the staging buffer.
+            unsafe {
+                self.write_all_unchecked(self.staging.as_ptr(), len)?;
+            }
+
+            start += len;
+        }
+
+        Ok(())
+    }
+
+    /// Render audio from the given block source and write it to the device
+    /// until the source is exhausted.
+    ///
+    /// Blocks are rendered into the provided interleaved buffer `buf` before
+    /// they are written, so its size determines the block size.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::alsa;
+    /// use audio_generator::{Generator, Sine};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut pcm = alsa::Pcm::open_default(alsa::Stream::Playback)?;
+    /// let config = pcm.configure::<f32>().install()?;
+    ///
+    /// let source = Sine::new(440.0, config.rate as f32).amplitude(0.1).block();
+    /// let buf = audio::buf::Interleaved::with_topology(config.channels as usize, 1024);
+    ///
+    /// let mut writer = pcm.writer::<f32>()?;
+    /// writer.write_source(source, buf)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn write_source<S, B>(&mut self, mut source: S, mut buf: B) -> Result<()>
+    where
+        S: audio_core::BlockSource<Sample = T>,
+        B: audio_core::BufMut<Sample = T> + audio_core::InterleavedBuf<Sample = T>,
+    {
+        if buf.channels() != self.channels {
+            return Err(Error::ChannelsMismatch {
+                actual: buf.channels(),
+                expected: self.channels,
+            });
+        }
+
+        let frames = buf
+            .as_interleaved()
+            .len()
+            .checked_div(self.channels)
+            .unwrap_or_default();
+
+        loop {
+            // NB: clamp since the source is not trusted to stay in bounds.
+            let len = usize::min(source.render(&mut buf), frames);
+
+            if len == 0 {
+                return Ok(());
+            }
+
+            unsafe {
+                self.write_all_unchecked(buf.as_interleaved().as_ptr(), len)?;
+            }
+        }
+    }
 }
@@ -0,0 +1,321 @@
+use std::ffi::{CStr, CString};
+use std::marker;
+use std::os::raw::{c_char, c_long};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use asio_sys as asio;
+use audio::buf::Dynamic;
+
+use crate::asio::{Error, Result, SampleType, Stream};
+
+/// The maximum number of drivers which are enumerated.
+const MAX_DRIVERS: usize = 32;
+
+/// If a driver is currently loaded, since the SDK only supports one at a time.
+static LOADED: AtomicBool = AtomicBool::new(false);
+
+/// List the names of the installed ASIO drivers.
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::asio;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// for name in asio::drivers()? {
+///     println!("{}", name);
+/// }
+/// # Ok(()) }
+/// ```
+pub fn drivers() -> Result<Vec<String>> {
+    let mut storage = [[0 as c_char; 32]; MAX_DRIVERS];
+    let mut names = storage
+        .iter_mut()
+        .map(|name| name.as_mut_ptr())
+        .collect::<Vec<_>>();
+
+    let count = unsafe { asio::asio_get_driver_names(names.as_mut_ptr(), MAX_DRIVERS as c_long) };
+
+    Ok(storage
+        .iter()
+        .take(count.max(0) as usize)
+        .map(|name| string(name))
+        .collect())
+}
+
+/// The buffer sizes supported by a driver, in frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct BufferSize {
+    /// The smallest supported buffer size.
+    pub min: usize,
+    /// The largest supported buffer size.
+    pub max: usize,
+    /// The preferred buffer size, which is usually what's configured in the
+    /// control panel of the driver.
+    pub preferred: usize,
+    /// The granularity of the supported buffer sizes between `min` and `max`.
+    /// This is `-1` if only powers of two are supported.
+    pub granularity: isize,
+}
+
+/// Information on a channel of a driver.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ChannelInfo {
+    /// The name of the channel.
+    pub name: String,
+    /// The sample type of the channel, unless it's not supported.
+    pub sample_type: Option<SampleType>,
+    /// If the channel is in use.
+    pub is_active: bool,
+    /// The group of the channel, like the channels of a stereo pair.
+    pub group: i32,
+}
+
+/// A loaded ASIO driver.
+///
+/// Only one driver can be loaded at a time, and it's used from the thread
+/// which loaded it.
+///
+/// See [Driver::load].
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::asio;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let name = asio::drivers()?.into_iter().next().expect("no drivers");
+/// let mut driver = asio::Driver::load(&name)?;
+///
+/// let (_, outputs) = driver.channels()?;
+/// let frames = driver.buffer_size()?.preferred;
+/// let mut phase = 0.0f32;
+///
+/// let mut stream = driver.stream(0, outputs, frames, move |_, output| {
+///     for frame in 0..output.frames() {
+///         let sample = (phase * std::f32::consts::TAU).sin() * 0.1;
+///         phase = (phase + 440.0 / 48000.0) % 1.0;
+///
+///         for mut channel in output.iter_channels_mut() {
+///             channel[frame] = sample;
+///         }
+///     }
+/// })?;
+///
+/// stream.start()?;
+/// # Ok(()) }
+/// ```
+pub struct Driver {
+    name: String,
+    // NB: drivers are COM objects, which are tied to the thread which loaded
+    // them.
+    _marker: marker::PhantomData<*const ()>,
+}
+
+impl Driver {
+    /// Load and initialize the driver with the given name.
+    ///
+    /// Errors with [Error::DriverInUse] if another driver is already loaded.
+    pub fn load(name: &str) -> Result<Self> {
+        let c_name = CString::new(name).map_err(|_| Error::LoadDriver(name.to_owned()))?;
+
+        if LOADED.swap(true, Ordering::Acquire) {
+            return Err(Error::DriverInUse);
+        }
+
+        unsafe {
+            if asio::asio_load_driver(c_name.as_ptr() as *mut c_char) == asio::ASIOFalse {
+                LOADED.store(false, Ordering::Release);
+                return Err(Error::LoadDriver(name.to_owned()));
+            }
+
+            let mut info: asio::ASIODriverInfo = std::mem::zeroed();
+            info.asioVersion = 2;
+
+            if asio::asio_init(&mut info) != asio::ASE_OK {
+                asio::asio_remove_current_driver();
+                LOADED.store(false, Ordering::Release);
+                let message = info.errorMessage;
+                return Err(Error::Init(string(&message)));
+            }
+        }
+
+        Ok(Self {
+            name: name.to_owned(),
+            _marker: marker::PhantomData,
+        })
+    }
+
+    /// The name of the driver.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The number of input and output channels of the driver.
+    pub fn channels(&self) -> Result<(usize, usize)> {
+        let mut inputs = 0;
+        let mut outputs = 0;
+
+        unsafe {
+            asio_try!(asio::asio_get_channels(&mut inputs, &mut outputs))?;
+        }
+
+        Ok((inputs as usize, outputs as usize))
+    }
+
+    /// The buffer sizes supported by the driver.
+    pub fn buffer_size(&self) -> Result<BufferSize> {
+        let mut min = 0;
+        let mut max = 0;
+        let mut preferred = 0;
+        let mut granularity = 0;
+
+        unsafe {
+            asio_try!(asio::asio_get_buffer_size(
+                &mut min,
+                &mut max,
+                &mut preferred,
+                &mut granularity
+            ))?;
+        }
+
+        Ok(BufferSize {
+            min: min as usize,
+            max: max as usize,
+            preferred: preferred as usize,
+            granularity: granularity as isize,
+        })
+    }
+
+    /// The input and output latencies of the driver in frames, which includes
+    /// the size of the buffers once a stream has been set up.
+    pub fn latencies(&self) -> Result<(usize, usize)> {
+        let mut input = 0;
+        let mut output = 0;
+
+        unsafe {
+            asio_try!(asio::asio_get_latencies(&mut input, &mut output))?;
+        }
+
+        Ok((input as usize, output as usize))
+    }
+
+    /// The current sample rate of the driver.
+    pub fn sample_rate(&self) -> Result<f64> {
+        let mut rate = 0.0;
+
+        unsafe {
+            asio_try!(asio::asio_get_sample_rate(&mut rate))?;
+        }
+
+        Ok(rate)
+    }
+
+    /// Test if the driver supports the given sample rate.
+    pub fn can_sample_rate(&self, rate: f64) -> bool {
+        unsafe { asio::asio_can_sample_rate(rate) == asio::ASE_OK }
+    }
+
+    /// Set the sample rate of the driver.
+    pub fn set_sample_rate(&mut self, rate: f64) -> Result<()> {
+        unsafe { asio_try!(asio::asio_set_sample_rate(rate)) }
+    }
+
+    /// Information on the given input channel.
+    pub fn input_info(&self, channel: usize) -> Result<ChannelInfo> {
+        self.channel_info(channel, true)
+    }
+
+    /// Information on the given output channel.
+    pub fn output_info(&self, channel: usize) -> Result<ChannelInfo> {
+        self.channel_info(channel, false)
+    }
+
+    /// Test if the driver has requested to be reset, like after its settings
+    /// were changed in its control panel, which clears the request.
+    ///
+    /// A reset is performed by dropping the driver and loading it again.
+    pub fn take_reset_request(&self) -> bool {
+        crate::asio::stream::RESET_REQUESTED.swap(false, Ordering::AcqRel)
+    }
+
+    /// Set up a stream using the first `inputs` input channels and the first
+    /// `outputs` output channels, with buffers of the given number of frames.
+    ///
+    /// The render callback is called from the thread of the driver with the
+    /// captured input and a silent output buffer to fill, each with one
+    /// channel for each channel in use.
+    ///
+    /// The stream isn't running until it's [started][Stream::start].
+    pub fn stream<F>(
+        &mut self,
+        inputs: usize,
+        outputs: usize,
+        frames: usize,
+        render: F,
+    ) -> Result<Stream<'_>>
+    where
+        F: 'static + Send + FnMut(&Dynamic<f32>, &mut Dynamic<f32>),
+    {
+        let mut types = Vec::with_capacity(inputs + outputs);
+
+        for channel in 0..inputs {
+            types.push(self.sample_type(channel, true)?);
+        }
+
+        for channel in 0..outputs {
+            types.push(self.sample_type(channel, false)?);
+        }
+
+        unsafe { Stream::new(self, inputs, types, frames, Box::new(render)) }
+    }
+
+    fn sample_type(&self, channel: usize, is_input: bool) -> Result<SampleType> {
+        let info = self.raw_channel_info(channel, is_input)?;
+        SampleType::from_raw(info.type_).ok_or(Error::UnsupportedSampleType(info.type_ as i32))
+    }
+
+    fn channel_info(&self, channel: usize, is_input: bool) -> Result<ChannelInfo> {
+        let info = self.raw_channel_info(channel, is_input)?;
+
+        Ok(ChannelInfo {
+            name: string(&info.name),
+            sample_type: SampleType::from_raw(info.type_),
+            is_active: info.isActive != asio::ASIOFalse,
+            group: info.channelGroup as i32,
+        })
+    }
+
+    fn raw_channel_info(&self, channel: usize, is_input: bool) -> Result<asio::ASIOChannelInfo> {
+        unsafe {
+            let mut info: asio::ASIOChannelInfo = std::mem::zeroed();
+            info.channel = channel as c_long;
+            info.isInput = is_input as asio::ASIOBool;
+            asio_try!(asio::asio_get_channel_info(&mut info))?;
+            Ok(info)
+        }
+    }
+}
+
+impl Drop for Driver {
+    fn drop(&mut self) {
+        unsafe {
+            asio::asio_exit();
+            asio::asio_remove_current_driver();
+        }
+
+        LOADED.store(false, Ordering::Release);
+    }
+}
+
+/// Convert a nul-terminated fixed size string into an owned string.
+fn string(name: &[c_char]) -> String {
+    let bytes = name.iter().map(|c| *c as u8).collect::<Vec<_>>();
+
+    match CStr::from_bytes_until_nul(&bytes) {
+        Ok(name) => name.to_string_lossy().into_owned(),
+        Err(..) => String::from_utf8_lossy(&bytes).into_owned(),
+    }
+}
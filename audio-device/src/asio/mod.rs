@@ -0,0 +1,82 @@
+//! An idiomatic Rust ASIO interface, for low latency audio on Windows.
+//!
+//! ASIO drivers call a render callback from a thread of their own whenever
+//! the driver switches between the two halves of its double buffer. The
+//! buffers of the driver are converted from and to [audio::buf::Dynamic]
+//! buffers of `f32` samples, regardless of the sample type used by the
+//! driver.
+//!
+//! Building this module for Windows requires the ASIO SDK, see
+//! [audio-device-asio-sys] for how to provide it.
+//!
+//! [audio-device-asio-sys]: https://docs.rs/audio-device-asio-sys
+
+// Documentation: https://www.steinberg.net/developers/
+
+use thiserror::Error;
+
+/// Helper to convert the error code returned by an ASIO function into a
+/// result.
+macro_rules! asio_try {
+    ($expr:expr) => {{
+        let status = $expr;
+
+        if status != asio_sys::ASE_OK && status != asio_sys::ASE_SUCCESS {
+            Err(crate::asio::Error::Sys(status as i32))
+        } else {
+            Ok(())
+        }
+    }};
+}
+
+mod sample;
+pub use self::sample::SampleType;
+
+mod driver;
+pub use self::driver::{drivers, BufferSize, ChannelInfo, Driver};
+
+mod stream;
+pub use self::stream::Stream;
+
+/// Errors that can be raised by the ASIO layer.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// System error, identified by its `ASIOError` code.
+    #[error("system error: {0}")]
+    Sys(i32),
+    /// The driver with the given name could not be loaded.
+    #[error("failed to load driver `{0}`")]
+    LoadDriver(String),
+    /// The driver failed to initialize, with the message it provided.
+    #[error("failed to initialize driver: {0}")]
+    Init(String),
+    /// Only one driver can be loaded at a time.
+    #[error("another driver is already loaded")]
+    DriverInUse,
+    /// The driver uses a sample type which isn't supported.
+    #[error("unsupported sample type: {0}")]
+    UnsupportedSampleType(i32),
+}
+
+impl Error {
+    pub(crate) fn kind(&self) -> crate::ErrorKind {
+        use crate::ErrorKind;
+
+        match *self {
+            Self::Sys(code) => match code as asio_sys::ASIOError {
+                asio_sys::ASE_NotPresent => ErrorKind::Disconnected,
+                asio_sys::ASE_InvalidParameter | asio_sys::ASE_InvalidMode => {
+                    ErrorKind::InvalidInput
+                }
+                _ => ErrorKind::Other,
+            },
+            Self::LoadDriver(..) => ErrorKind::NotFound,
+            Self::DriverInUse => ErrorKind::Busy,
+            Self::UnsupportedSampleType(..) => ErrorKind::UnsupportedFormat,
+            Self::Init(..) => ErrorKind::Other,
+        }
+    }
+}
+
+/// Helper result wrapper.
+pub type Result<T, E = Error> = ::std::result::Result<T, E>;
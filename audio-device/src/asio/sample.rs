@@ -0,0 +1,189 @@
+use asio_sys as asio;
+
+/// The type of the samples of an ASIO channel.
+///
+/// Samples are converted to and from `f32` samples in the range `-1.0` to
+/// `1.0`.
+///
+/// # Examples
+///
+/// ```
+/// use audio_device::asio::SampleType;
+///
+/// let mut bytes = [0u8; 6];
+/// SampleType::Int24Lsb.encode(&[0.5, -1.0], &mut bytes);
+///
+/// let mut samples = [0.0f32; 2];
+/// SampleType::Int24Lsb.decode(&bytes, &mut samples);
+/// assert!((samples[0] - 0.5).abs() < 1e-6);
+/// assert!((samples[1] + 1.0).abs() < 1e-6);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SampleType {
+    /// 16-bit big endian integer samples.
+    Int16Msb,
+    /// 24-bit packed big endian integer samples.
+    Int24Msb,
+    /// 32-bit big endian integer samples.
+    Int32Msb,
+    /// 32-bit big endian float samples.
+    Float32Msb,
+    /// 64-bit big endian float samples.
+    Float64Msb,
+    /// 16-bit little endian integer samples.
+    Int16Lsb,
+    /// 24-bit packed little endian integer samples.
+    Int24Lsb,
+    /// 32-bit little endian integer samples.
+    Int32Lsb,
+    /// 32-bit little endian float samples.
+    Float32Lsb,
+    /// 64-bit little endian float samples.
+    Float64Lsb,
+    /// 16-bit little endian integer samples, in the low bits of 32 bits.
+    Int32Lsb16,
+    /// 18-bit little endian integer samples, in the low bits of 32 bits.
+    Int32Lsb18,
+    /// 20-bit little endian integer samples, in the low bits of 32 bits.
+    Int32Lsb20,
+    /// 24-bit little endian integer samples, in the low bits of 32 bits.
+    Int32Lsb24,
+}
+
+impl SampleType {
+    /// Convert from the raw `ASIOSampleType` of a channel.
+    pub(super) fn from_raw(raw: asio::ASIOSampleType) -> Option<Self> {
+        Some(match raw {
+            asio::ASIOSTInt16MSB => Self::Int16Msb,
+            asio::ASIOSTInt24MSB => Self::Int24Msb,
+            asio::ASIOSTInt32MSB => Self::Int32Msb,
+            asio::ASIOSTFloat32MSB => Self::Float32Msb,
+            asio::ASIOSTFloat64MSB => Self::Float64Msb,
+            asio::ASIOSTInt16LSB => Self::Int16Lsb,
+            asio::ASIOSTInt24LSB => Self::Int24Lsb,
+            asio::ASIOSTInt32LSB => Self::Int32Lsb,
+            asio::ASIOSTFloat32LSB => Self::Float32Lsb,
+            asio::ASIOSTFloat64LSB => Self::Float64Lsb,
+            asio::ASIOSTInt32LSB16 => Self::Int32Lsb16,
+            asio::ASIOSTInt32LSB18 => Self::Int32Lsb18,
+            asio::ASIOSTInt32LSB20 => Self::Int32Lsb20,
+            asio::ASIOSTInt32LSB24 => Self::Int32Lsb24,
+            _ => return None,
+        })
+    }
+
+    /// The size of a single sample in bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio_device::asio::SampleType;
+    ///
+    /// assert_eq!(SampleType::Int24Lsb.size(), 3);
+    /// assert_eq!(SampleType::Int32Lsb24.size(), 4);
+    /// ```
+    pub fn size(self) -> usize {
+        match self {
+            Self::Int16Msb | Self::Int16Lsb => 2,
+            Self::Int24Msb | Self::Int24Lsb => 3,
+            Self::Float64Msb | Self::Float64Lsb => 8,
+            _ => 4,
+        }
+    }
+
+    /// Decode samples of this type from `bytes` into `out`.
+    ///
+    /// Decodes as many samples as fit in both.
+    pub fn decode(self, bytes: &[u8], out: &mut [f32]) {
+        for (out, b) in out.iter_mut().zip(bytes.chunks_exact(self.size())) {
+            *out = self.decode_one(b);
+        }
+    }
+
+    /// Encode samples from `samples` into `bytes` as this type.
+    ///
+    /// Encodes as many samples as fit in both. Samples outside of the range
+    /// `-1.0` to `1.0` are clamped.
+    pub fn encode(self, samples: &[f32], bytes: &mut [u8]) {
+        for (sample, b) in samples.iter().zip(bytes.chunks_exact_mut(self.size())) {
+            self.encode_one(*sample, b);
+        }
+    }
+
+    fn decode_one(self, b: &[u8]) -> f32 {
+        match self {
+            Self::Int16Msb => i16::from_be_bytes([b[0], b[1]]) as f32 / scale(16) as f32,
+            Self::Int16Lsb => i16::from_le_bytes([b[0], b[1]]) as f32 / scale(16) as f32,
+            // NB: shifting right sign extends the 24-bit value.
+            Self::Int24Msb => {
+                (i32::from_be_bytes([b[0], b[1], b[2], 0]) >> 8) as f32 / scale(24) as f32
+            }
+            Self::Int24Lsb => {
+                (i32::from_le_bytes([0, b[0], b[1], b[2]]) >> 8) as f32 / scale(24) as f32
+            }
+            Self::Int32Msb => {
+                (i32::from_be_bytes([b[0], b[1], b[2], b[3]]) as f64 / scale(32)) as f32
+            }
+            Self::Float32Msb => f32::from_be_bytes([b[0], b[1], b[2], b[3]]),
+            Self::Float32Lsb => f32::from_le_bytes([b[0], b[1], b[2], b[3]]),
+            Self::Float64Msb => {
+                f64::from_be_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]) as f32
+            }
+            Self::Float64Lsb => {
+                f64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]) as f32
+            }
+            Self::Int32Lsb
+            | Self::Int32Lsb16
+            | Self::Int32Lsb18
+            | Self::Int32Lsb20
+            | Self::Int32Lsb24 => {
+                (i32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f64 / scale(self.bits())) as f32
+            }
+        }
+    }
+
+    fn encode_one(self, sample: f32, b: &mut [u8]) {
+        let sample = sample.clamp(-1.0, 1.0);
+
+        match self {
+            Self::Int16Msb => b.copy_from_slice(&(to_int(sample, 16) as i16).to_be_bytes()),
+            Self::Int16Lsb => b.copy_from_slice(&(to_int(sample, 16) as i16).to_le_bytes()),
+            Self::Int24Msb => b.copy_from_slice(&to_int(sample, 24).to_be_bytes()[1..]),
+            Self::Int24Lsb => b.copy_from_slice(&to_int(sample, 24).to_le_bytes()[..3]),
+            Self::Int32Msb => b.copy_from_slice(&to_int(sample, 32).to_be_bytes()),
+            Self::Float32Msb => b.copy_from_slice(&sample.to_be_bytes()),
+            Self::Float32Lsb => b.copy_from_slice(&sample.to_le_bytes()),
+            Self::Float64Msb => b.copy_from_slice(&(sample as f64).to_be_bytes()),
+            Self::Float64Lsb => b.copy_from_slice(&(sample as f64).to_le_bytes()),
+            Self::Int32Lsb
+            | Self::Int32Lsb16
+            | Self::Int32Lsb18
+            | Self::Int32Lsb20
+            | Self::Int32Lsb24 => b.copy_from_slice(&to_int(sample, self.bits()).to_le_bytes()),
+        }
+    }
+
+    /// The number of significant bits of 32-bit integer samples.
+    fn bits(self) -> u32 {
+        match self {
+            Self::Int32Lsb16 => 16,
+            Self::Int32Lsb18 => 18,
+            Self::Int32Lsb20 => 20,
+            Self::Int32Lsb24 => 24,
+            _ => 32,
+        }
+    }
+}
+
+/// The magnitude of the most negative integer sample with the given number of
+/// bits.
+fn scale(bits: u32) -> f64 {
+    (1u64 << (bits - 1)) as f64
+}
+
+/// Convert a clamped sample into an integer sample with the given number of
+/// bits.
+fn to_int(sample: f32, bits: u32) -> i32 {
+    (sample as f64 * (scale(bits) - 1.0)).round() as i32
+}
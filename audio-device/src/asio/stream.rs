@@ -0,0 +1,228 @@
+use std::os::raw::c_long;
+use std::ptr;
+use std::slice;
+use std::sync::atomic::{AtomicBool, AtomicPtr, Ordering};
+
+use asio_sys as asio;
+use audio::buf::Dynamic;
+use audio::BufMut;
+
+use crate::asio::{Driver, Result, SampleType};
+
+/// The render callback of a stream.
+type Render = Box<dyn FnMut(&Dynamic<f32>, &mut Dynamic<f32>) + Send>;
+
+/// The state of the current stream, since ASIO callbacks don't have a user
+/// data pointer.
+static STATE: AtomicPtr<State> = AtomicPtr::new(ptr::null_mut());
+
+/// Set when the driver requests to be reset.
+pub(super) static RESET_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// A stream of an ASIO [Driver], which calls its render callback whenever the
+/// driver switches buffers.
+///
+/// The buffers of the stream are disposed of when it's dropped.
+///
+/// See [Driver::stream].
+pub struct Stream<'a> {
+    _driver: &'a mut Driver,
+    state: *mut State,
+    started: bool,
+}
+
+impl<'a> Stream<'a> {
+    /// Create the buffers of the driver and register the render callback.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `types` has one sample type for each of
+    /// the first `inputs` input channels, followed by one for each of the
+    /// output channels.
+    pub(super) unsafe fn new(
+        driver: &'a mut Driver,
+        inputs: usize,
+        types: Vec<SampleType>,
+        frames: usize,
+        render: Render,
+    ) -> Result<Self> {
+        let outputs = types.len() - inputs;
+
+        let infos = (0..types.len())
+            .map(|n| asio::ASIOBufferInfo {
+                isInput: (n < inputs) as asio::ASIOBool,
+                channelNum: (if n < inputs { n } else { n - inputs }) as c_long,
+                buffers: [ptr::null_mut(); 2],
+            })
+            .collect();
+
+        let state = Box::into_raw(Box::new(State {
+            callbacks: asio::ASIOCallbacks {
+                bufferSwitch: Some(buffer_switch),
+                sampleRateDidChange: Some(sample_rate_did_change),
+                asioMessage: Some(asio_message),
+                bufferSwitchTimeInfo: Some(buffer_switch_time_info),
+            },
+            infos,
+            types,
+            inputs,
+            frames,
+            input: Dynamic::with_topology(inputs, frames),
+            output: Dynamic::with_topology(outputs, frames),
+            render,
+            output_ready: false,
+        }));
+
+        // NB: the driver keeps a pointer to the callbacks, so they're stored
+        // alongside the rest of the state.
+        let result = asio_try!(asio::asio_create_buffers(
+            (*state).infos.as_mut_ptr(),
+            (*state).infos.len() as c_long,
+            frames as c_long,
+            &mut (*state).callbacks,
+        ));
+
+        if let Err(e) = result {
+            drop(Box::from_raw(state));
+            return Err(e);
+        }
+
+        // NB: drivers which support it return success here, which means that
+        // they should be told when the output is ready.
+        (*state).output_ready = asio::asio_output_ready() == asio::ASE_OK;
+        STATE.store(state, Ordering::Release);
+
+        Ok(Self {
+            _driver: driver,
+            state,
+            started: false,
+        })
+    }
+
+    /// Start calling the render callback.
+    pub fn start(&mut self) -> Result<()> {
+        if !self.started {
+            unsafe {
+                asio_try!(asio::asio_start())?;
+            }
+
+            self.started = true;
+        }
+
+        Ok(())
+    }
+
+    /// Stop calling the render callback.
+    pub fn stop(&mut self) -> Result<()> {
+        if self.started {
+            unsafe {
+                asio_try!(asio::asio_stop())?;
+            }
+
+            self.started = false;
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for Stream<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            if self.started {
+                asio::asio_stop();
+            }
+
+            asio::asio_dispose_buffers();
+            STATE.store(ptr::null_mut(), Ordering::Release);
+            drop(Box::from_raw(self.state));
+        }
+    }
+}
+
+/// State shared with the callbacks of the driver.
+struct State {
+    callbacks: asio::ASIOCallbacks,
+    infos: Vec<asio::ASIOBufferInfo>,
+    types: Vec<SampleType>,
+    inputs: usize,
+    frames: usize,
+    input: Dynamic<f32>,
+    output: Dynamic<f32>,
+    render: Render,
+    output_ready: bool,
+}
+
+impl State {
+    /// Process the half of the double buffer with the given index.
+    unsafe fn process(&mut self, index: usize) {
+        let (inputs, outputs) = self.infos.split_at(self.inputs);
+        let (input_types, output_types) = self.types.split_at(self.inputs);
+
+        for (n, (info, ty)) in inputs.iter().zip(input_types).enumerate() {
+            let bytes =
+                slice::from_raw_parts(info.buffers[index] as *const u8, self.frames * ty.size());
+            ty.decode(bytes, &mut self.input[n]);
+        }
+
+        self.output.fill(0.0);
+        (self.render)(&self.input, &mut self.output);
+
+        for (n, (info, ty)) in outputs.iter().zip(output_types).enumerate() {
+            let bytes =
+                slice::from_raw_parts_mut(info.buffers[index] as *mut u8, self.frames * ty.size());
+            ty.encode(&self.output[n], bytes);
+        }
+
+        if self.output_ready {
+            asio::asio_output_ready();
+        }
+    }
+}
+
+unsafe extern "C" fn buffer_switch(index: c_long, _: asio::ASIOBool) {
+    let state = STATE.load(Ordering::Acquire);
+
+    if let Some(state) = state.as_mut() {
+        state.process(index as usize);
+    }
+}
+
+unsafe extern "C" fn buffer_switch_time_info(
+    params: *mut asio::ASIOTime,
+    index: c_long,
+    direct: asio::ASIOBool,
+) -> *mut asio::ASIOTime {
+    buffer_switch(index, direct);
+    params
+}
+
+unsafe extern "C" fn sample_rate_did_change(_: asio::ASIOSampleRate) {
+    // NB: the stream has to be set up again for the new sample rate.
+    RESET_REQUESTED.store(true, Ordering::Release);
+}
+
+unsafe extern "C" fn asio_message(
+    selector: c_long,
+    value: c_long,
+    _: *mut std::ffi::c_void,
+    _: *mut f64,
+) -> c_long {
+    match selector {
+        asio::kAsioSelectorSupported => matches!(
+            value,
+            asio::kAsioEngineVersion
+                | asio::kAsioResetRequest
+                | asio::kAsioResyncRequest
+                | asio::kAsioLatenciesChanged
+        ) as c_long,
+        // NB: the version of the host interface in use.
+        asio::kAsioEngineVersion => 2,
+        asio::kAsioResetRequest => {
+            RESET_REQUESTED.store(true, Ordering::Release);
+            1
+        }
+        asio::kAsioResyncRequest | asio::kAsioLatenciesChanged => 1,
+        _ => 0,
+    }
+}
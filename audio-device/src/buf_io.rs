@@ -0,0 +1,185 @@
+//! Helpers shared by backends for transferring the frames of interleaved
+//! buffers to and from devices.
+
+use core::mem;
+use std::task::Poll;
+
+use audio_core::{InterleavedBuf, InterleavedBufMut, ReadBuf, WriteBuf};
+
+/// The number of frames which can be read from the given interleaved buffer.
+///
+/// NB: this is clamped to the size of the underlying buffer so that we never
+/// read past its end.
+pub(crate) fn readable_frames<B>(buf: &B, channels: usize) -> usize
+where
+    B: ReadBuf + InterleavedBuf,
+{
+    let available = buf
+        .as_interleaved()
+        .len()
+        .checked_div(channels)
+        .unwrap_or_default();
+    usize::min(buf.remaining(), available)
+}
+
+/// The number of frames which can be written to the given interleaved
+/// buffer.
+///
+/// NB: this is clamped to the size of the underlying buffer so that we never
+/// write past its end.
+pub(crate) fn writable_frames<B>(buf: &B, channels: usize) -> usize
+where
+    B: WriteBuf + InterleavedBufMut,
+{
+    let available = buf
+        .as_interleaved()
+        .len()
+        .checked_div(channels)
+        .unwrap_or_default();
+    usize::min(buf.remaining_mut(), available)
+}
+
+/// Write the frames of an interleaved buffer through the given byte-oriented
+/// `write` function, advancing the buffer past every frame which has been
+/// written in full.
+///
+/// `write` is called with the bytes which remain to be written and returns
+/// how many of them it wrote, where zero means that it should be called
+/// again. `partial` is the number of bytes of the first frame which have
+/// already been written, which carries over between calls.
+#[cfg_attr(
+    not(any(feature = "oss", feature = "sndio", feature = "pulse")),
+    allow(dead_code)
+)]
+pub(crate) fn poll_write<B, F, E>(
+    buf: &mut B,
+    channels: usize,
+    partial: &mut usize,
+    mut write: F,
+) -> Poll<Result<(), E>>
+where
+    B: ReadBuf + InterleavedBuf,
+    F: FnMut(*const u8, usize) -> Poll<Result<usize, E>>,
+{
+    let frame_size = channels * mem::size_of::<B::Sample>();
+
+    loop {
+        let frames = readable_frames(buf, channels);
+
+        if frames == 0 {
+            return Poll::Ready(Ok(()));
+        }
+
+        let len = frames * frame_size - *partial;
+        // Safety: the offset is within the frames of the buffer.
+        let ptr = unsafe { (buf.as_interleaved().as_ptr() as *const u8).add(*partial) };
+
+        let written = match write(ptr, len) {
+            Poll::Ready(Ok(written)) => written + *partial,
+            Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+            Poll::Pending => return Poll::Pending,
+        };
+
+        *partial = written % frame_size;
+        buf.advance(written / frame_size);
+    }
+}
+
+/// Read into the frames of an interleaved buffer through the given
+/// byte-oriented `read` function, advancing the buffer past every frame which
+/// has been read in full.
+///
+/// See [poll_write] for how `read` and `partial` are used.
+#[cfg_attr(
+    not(any(
+        feature = "alsa",
+        feature = "oss",
+        feature = "sndio",
+        feature = "pulse"
+    )),
+    allow(dead_code)
+)]
+pub(crate) fn poll_read<B, F, E>(
+    buf: &mut B,
+    channels: usize,
+    partial: &mut usize,
+    mut read: F,
+) -> Poll<Result<(), E>>
+where
+    B: WriteBuf + InterleavedBufMut,
+    F: FnMut(*mut u8, usize) -> Poll<Result<usize, E>>,
+{
+    let frame_size = channels * mem::size_of::<B::Sample>();
+
+    loop {
+        let frames = writable_frames(buf, channels);
+
+        if frames == 0 {
+            return Poll::Ready(Ok(()));
+        }
+
+        let len = frames * frame_size - *partial;
+        // Safety: the offset is within the frames of the buffer.
+        let ptr = unsafe { (buf.as_interleaved_mut().as_mut_ptr() as *mut u8).add(*partial) };
+
+        let read = match read(ptr, len) {
+            Poll::Ready(Ok(read)) => read + *partial,
+            Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+            Poll::Pending => return Poll::Pending,
+        };
+
+        *partial = read % frame_size;
+        buf.advance_mut(read / frame_size);
+    }
+}
+
+/// Write all frames of an interleaved buffer through the given blocking
+/// byte-oriented `write` function.
+///
+/// See [poll_write].
+#[cfg_attr(
+    not(any(feature = "oss", feature = "sndio", feature = "pulse")),
+    allow(dead_code)
+)]
+pub(crate) fn write<B, F, E>(buf: &mut B, channels: usize, mut write: F) -> Result<(), E>
+where
+    B: ReadBuf + InterleavedBuf,
+    F: FnMut(*const u8, usize) -> Result<usize, E>,
+{
+    let mut partial = 0;
+
+    match poll_write(buf, channels, &mut partial, |ptr, len| {
+        Poll::Ready(write(ptr, len))
+    }) {
+        Poll::Ready(result) => result,
+        Poll::Pending => unreachable!("blocking writes never wait"),
+    }
+}
+
+/// Read into all frames of an interleaved buffer through the given blocking
+/// byte-oriented `read` function.
+///
+/// See [poll_read].
+#[cfg_attr(
+    not(any(
+        feature = "alsa",
+        feature = "oss",
+        feature = "sndio",
+        feature = "pulse"
+    )),
+    allow(dead_code)
+)]
+pub(crate) fn read<B, F, E>(buf: &mut B, channels: usize, mut read: F) -> Result<(), E>
+where
+    B: WriteBuf + InterleavedBufMut,
+    F: FnMut(*mut u8, usize) -> Result<usize, E>,
+{
+    let mut partial = 0;
+
+    match poll_read(buf, channels, &mut partial, |ptr, len| {
+        Poll::Ready(read(ptr, len))
+    }) {
+        Poll::Ready(result) => result,
+        Poll::Pending => unreachable!("blocking reads never wait"),
+    }
+}
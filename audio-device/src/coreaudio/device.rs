@@ -0,0 +1,223 @@
+use std::ffi::CStr;
+use std::mem;
+use std::ptr;
+
+use audio_core::{Endian, SampleFormat};
+use coreaudio_sys as sys;
+
+use crate::coreaudio::Result;
+use crate::{DeviceInfo, Direction};
+
+/// Enumerate the audio devices of the system.
+pub(crate) fn device_infos() -> Result<Vec<DeviceInfo>> {
+    let devices = unsafe {
+        get_array::<sys::AudioDeviceID>(
+            sys::kAudioObjectSystemObject,
+            sys::kAudioHardwarePropertyDevices,
+            sys::kAudioObjectPropertyScopeGlobal,
+        )?
+    };
+
+    let default_output = unsafe { default_device(sys::kAudioHardwarePropertyDefaultOutputDevice)? };
+    let default_input = unsafe { default_device(sys::kAudioHardwarePropertyDefaultInputDevice)? };
+
+    let mut infos = Vec::new();
+
+    for device in devices {
+        let (output, input) = unsafe {
+            (
+                channels(device, sys::kAudioObjectPropertyScopeOutput)?,
+                channels(device, sys::kAudioObjectPropertyScopeInput)?,
+            )
+        };
+
+        let direction = match (output > 0, input > 0) {
+            (true, true) => Direction::Duplex,
+            (true, false) => Direction::Output,
+            (false, true) => Direction::Input,
+            (false, false) => continue,
+        };
+
+        let id = unsafe { get_string(device, sys::kAudioDevicePropertyDeviceUID)? };
+        let name = unsafe { get_string(device, sys::kAudioObjectPropertyName)? };
+
+        // NB: the channels of a duplex device are reported for output, since
+        // that's the direction it's listed under.
+        let channels = if direction.is_output() { output } else { input };
+
+        let rates = unsafe {
+            get_array::<sys::AudioValueRange>(
+                device,
+                sys::kAudioDevicePropertyAvailableNominalSampleRates,
+                sys::kAudioObjectPropertyScopeGlobal,
+            )?
+        };
+
+        let sample_rates = rates
+            .iter()
+            .map(|r| (r.mMinimum as u32, r.mMaximum as u32))
+            .reduce(|(min, max), (lo, hi)| (min.min(lo), max.max(hi)));
+
+        infos.push(DeviceInfo {
+            id,
+            name,
+            direction,
+            is_default: Some(device) == default_output || Some(device) == default_input,
+            // NB: audio queues convert between the format of the device and
+            // the linear PCM formats supported through [Sample][super::Sample].
+            formats: vec![
+                SampleFormat::I16(Endian::NATIVE),
+                SampleFormat::I32(Endian::NATIVE),
+                SampleFormat::F32(Endian::NATIVE),
+            ],
+            channels: Some((1, channels)),
+            sample_rates,
+        });
+    }
+
+    Ok(infos)
+}
+
+fn address(
+    selector: sys::AudioObjectPropertySelector,
+    scope: sys::AudioObjectPropertyScope,
+) -> sys::AudioObjectPropertyAddress {
+    sys::AudioObjectPropertyAddress {
+        mSelector: selector,
+        mScope: scope,
+        mElement: sys::kAudioObjectPropertyElementMain,
+    }
+}
+
+/// Get the size in bytes of the given property.
+unsafe fn get_size(
+    object: sys::AudioObjectID,
+    address: &sys::AudioObjectPropertyAddress,
+) -> Result<u32> {
+    let mut size = 0;
+    status!(sys::AudioObjectGetPropertyDataSize(
+        object,
+        address,
+        0,
+        ptr::null(),
+        &mut size
+    ))?;
+    Ok(size)
+}
+
+/// Get a property which is an array of `T`.
+unsafe fn get_array<T>(
+    object: sys::AudioObjectID,
+    selector: sys::AudioObjectPropertySelector,
+    scope: sys::AudioObjectPropertyScope,
+) -> Result<Vec<T>>
+where
+    T: Copy,
+{
+    let address = address(selector, scope);
+    let mut size = get_size(object, &address)?;
+
+    let mut values = Vec::<T>::with_capacity(size as usize / mem::size_of::<T>());
+
+    status!(sys::AudioObjectGetPropertyData(
+        object,
+        &address,
+        0,
+        ptr::null(),
+        &mut size,
+        values.as_mut_ptr().cast(),
+    ))?;
+
+    values.set_len(size as usize / mem::size_of::<T>());
+    Ok(values)
+}
+
+/// Get the default device for the given selector, if there is one.
+unsafe fn default_device(
+    selector: sys::AudioObjectPropertySelector,
+) -> Result<Option<sys::AudioDeviceID>> {
+    let devices = get_array::<sys::AudioDeviceID>(
+        sys::kAudioObjectSystemObject,
+        selector,
+        sys::kAudioObjectPropertyScopeGlobal,
+    )?;
+
+    // NB: the unknown object is reported if there is no default device.
+    Ok(devices.first().copied().filter(|&id| id != 0))
+}
+
+/// Count the channels of all streams of a device in the given scope.
+unsafe fn channels(
+    device: sys::AudioDeviceID,
+    scope: sys::AudioObjectPropertyScope,
+) -> Result<usize> {
+    let address = address(sys::kAudioDevicePropertyStreamConfiguration, scope);
+    let mut size = get_size(device, &address)?;
+
+    // NB: the buffer list is variable-length, so it's read into storage which
+    // is aligned for it.
+    let len = (size as usize + mem::size_of::<sys::AudioBufferList>() - 1)
+        / mem::size_of::<sys::AudioBufferList>();
+    let mut storage = Vec::<sys::AudioBufferList>::with_capacity(len.max(1));
+
+    status!(sys::AudioObjectGetPropertyData(
+        device,
+        &address,
+        0,
+        ptr::null(),
+        &mut size,
+        storage.as_mut_ptr().cast(),
+    ))?;
+
+    let list = storage.as_ptr();
+    let buffers = ptr::addr_of!((*list).mBuffers) as *const sys::AudioBuffer;
+    let mut channels = 0;
+
+    for n in 0..(*list).mNumberBuffers as usize {
+        channels += (*buffers.add(n)).mNumberChannels as usize;
+    }
+
+    Ok(channels)
+}
+
+/// Get a property which is a string.
+unsafe fn get_string(
+    object: sys::AudioObjectID,
+    selector: sys::AudioObjectPropertySelector,
+) -> Result<String> {
+    let address = address(selector, sys::kAudioObjectPropertyScopeGlobal);
+    let mut string: sys::CFStringRef = ptr::null();
+    let mut size = mem::size_of::<sys::CFStringRef>() as u32;
+
+    status!(sys::AudioObjectGetPropertyData(
+        object,
+        &address,
+        0,
+        ptr::null(),
+        &mut size,
+        (&mut string as *mut sys::CFStringRef).cast(),
+    ))?;
+
+    if string.is_null() {
+        return Ok(String::new());
+    }
+
+    let mut buf = [0u8; 256];
+
+    let ok = sys::CFStringGetCString(
+        string,
+        buf.as_mut_ptr().cast(),
+        buf.len() as sys::CFIndex,
+        sys::kCFStringEncodingUTF8,
+    );
+
+    sys::CFRelease(string.cast());
+
+    if ok == 0 {
+        return Ok(String::new());
+    }
+
+    Ok(CStr::from_bytes_until_nul(&buf)
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default())
+}
@@ -0,0 +1,226 @@
+use std::future;
+use std::marker;
+use std::pin::Pin;
+use std::ptr;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use crate::buf_io;
+use crate::coreaudio::queue::{Buffer, Queue};
+use crate::coreaudio::{frames_to_duration, Error, QueueConfig, Result, Sample};
+
+/// An audio queue capturing interleaved audio from the default input device.
+///
+/// Capturing starts as soon as the queue is opened. If captured buffers aren't
+/// read fast enough, the device drops audio until a buffer has been read.
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::coreaudio;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let config = coreaudio::QueueConfig::default();
+/// let mut queue = coreaudio::InputQueue::<f32>::open(config)?;
+///
+/// let mut samples = vec![0.0f32; config.channels as usize * 1024];
+///
+/// loop {
+///     queue.read_interleaved(audio::wrap::interleaved(&mut samples[..], config.channels as usize))?;
+/// }
+/// # }
+/// ```
+pub struct InputQueue<T> {
+    queue: Queue,
+    config: QueueConfig,
+    channels: usize,
+    // The buffer which is being read and the number of samples which have
+    // been read from it.
+    current: Option<(Buffer, usize)>,
+    // Number of frames read since the device was started.
+    read: u64,
+    _marker: marker::PhantomData<T>,
+}
+
+impl<T> InputQueue<T>
+where
+    T: Sample,
+{
+    /// Open a queue capturing from the default input device with the given
+    /// configuration.
+    #[tracing::instrument(skip_all)]
+    pub fn open(config: QueueConfig) -> Result<Self> {
+        let queue = Queue::new_input::<T>(&config)?;
+        tracing::trace!(?config, "opened input queue");
+
+        Ok(Self {
+            queue,
+            config,
+            channels: config.channels as usize,
+            current: None,
+            read: 0,
+            _marker: marker::PhantomData,
+        })
+    }
+
+    /// The configuration the queue was opened with.
+    pub fn config(&self) -> QueueConfig {
+        self.config
+    }
+
+    /// Read into an interleaved buffer, blocking until the remaining frames
+    /// of the buffer have been filled with captured audio.
+    pub fn read_interleaved<B>(&mut self, mut buf: B) -> Result<()>
+    where
+        B: audio_core::WriteBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBufMut<Sample = T>,
+    {
+        self.check_channels(&buf)?;
+
+        while buf.has_remaining_mut() {
+            let (buffer, offset) = match self.current.take() {
+                Some(current) => current,
+                None => (self.queue.buffers().pop(), 0),
+            };
+
+            if !self.consume(buffer, offset, &mut buf)? {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Read into an interleaved buffer, completing once the remaining frames
+    /// of the buffer have been filled with captured audio.
+    pub async fn read_interleaved_async<B>(&mut self, mut buf: B) -> Result<()>
+    where
+        B: audio_core::WriteBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBufMut<Sample = T>,
+    {
+        future::poll_fn(|cx| self.poll_read_interleaved(cx, &mut buf)).await
+    }
+
+    fn poll_read_interleaved<B>(&mut self, cx: &mut Context<'_>, mut buf: B) -> Poll<Result<()>>
+    where
+        B: audio_core::WriteBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBufMut<Sample = T>,
+    {
+        self.check_channels(&buf)?;
+
+        while buf.has_remaining_mut() {
+            let (buffer, offset) = match self.current.take() {
+                Some(current) => current,
+                None => match self.queue.buffers().poll_pop(cx) {
+                    Poll::Ready(buffer) => (buffer, 0),
+                    Poll::Pending => return Poll::Pending,
+                },
+            };
+
+            if !self.consume(buffer, offset, &mut buf)? {
+                break;
+            }
+        }
+
+        Poll::Ready(Ok(()))
+    }
+
+    /// Pause capturing.
+    pub fn pause(&mut self) -> Result<()> {
+        self.queue.pause()
+    }
+
+    /// Resume capturing after it's been [paused][InputQueue::pause].
+    pub fn resume(&mut self) -> Result<()> {
+        self.queue.start()
+    }
+
+    /// Get the latency of the frames which have been captured by the device
+    /// but not yet read.
+    pub fn latency(&self) -> Result<Duration> {
+        let pending = self.queue.sample_time()?.saturating_sub(self.read);
+        Ok(frames_to_duration(pending, self.config.sample_rate))
+    }
+
+    /// The number of frames captured by the device since it was started.
+    pub(super) fn position(&self) -> Result<u64> {
+        self.queue.sample_time()
+    }
+
+    fn check_channels<B>(&self, buf: &B) -> Result<()>
+    where
+        B: audio_core::Buf,
+    {
+        if buf.channels() != self.channels {
+            return Err(Error::ChannelsMismatch {
+                actual: buf.channels(),
+                expected: self.channels,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Read frames from a captured buffer into `buf`, handing the buffer back
+    /// to the device once it's been read in full.
+    ///
+    /// Returns `false` if `buf` can't hold any more frames.
+    fn consume<B>(&mut self, buffer: Buffer, offset: usize, buf: &mut B) -> Result<bool>
+    where
+        B: audio_core::WriteBuf + audio_core::InterleavedBufMut<Sample = T>,
+    {
+        let capacity = buf_io::writable_frames(buf, self.channels);
+
+        if capacity == 0 {
+            self.current = Some((buffer, offset));
+            return Ok(false);
+        }
+
+        let available = (buffer.len::<T>() - offset) / self.channels;
+        let frames = usize::min(capacity, available);
+        let len = frames * self.channels;
+
+        unsafe {
+            let to = buf.as_interleaved_mut().as_mut_ptr();
+            ptr::copy_nonoverlapping(buffer.data::<T>().add(offset), to, len);
+        }
+
+        buf.advance_mut(frames);
+        self.read += frames as u64;
+
+        if frames == available {
+            self.queue.enqueue::<T>(buffer)?;
+        } else {
+            self.current = Some((buffer, offset + len));
+        }
+
+        Ok(true)
+    }
+}
+
+impl<T> audio_core::AsyncReadBuf for InputQueue<T>
+where
+    T: Sample,
+{
+    type Sample = T;
+    type Error = Error;
+
+    fn poll_read_buf<B>(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: B,
+    ) -> Poll<Result<(), Error>>
+    where
+        B: audio_core::WriteBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBufMut<Sample = T>,
+    {
+        self.get_mut().poll_read_interleaved(cx, buf)
+    }
+}
+
+// Note: the sample type is only used as a marker.
+impl<T> Unpin for InputQueue<T> {}
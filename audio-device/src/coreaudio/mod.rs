@@ -0,0 +1,147 @@
+//! An idiomatic Rust CoreAudio interface, based on audio queues.
+//!
+//! Devices are enumerated through the CoreAudio hardware abstraction layer,
+//! see [devices][crate::devices].
+//!
+//! Audio queues deliver their buffers through callbacks on a thread managed by
+//! the system. Buffers are handed over to the queue types in this module which
+//! can then be used either by blocking or asynchronously. Since the callback
+//! thread takes care of waking up tasks, the async methods don't need to run
+//! inside of the audio [Runtime][crate::runtime::Runtime].
+
+// Documentation: https://developer.apple.com/documentation/audiotoolbox/audio_queue_services
+
+use std::time::Duration;
+
+use thiserror::Error;
+
+/// Helper to convert the status returned by an audio queue function into a
+/// result.
+macro_rules! status {
+    ($expr:expr) => {{
+        let status = $expr;
+
+        if status != 0 {
+            Err(crate::coreaudio::Error::Sys(status))
+        } else {
+            Ok(())
+        }
+    }};
+}
+
+mod queue;
+
+mod device;
+pub(crate) use self::device::device_infos;
+
+mod sample;
+pub use self::sample::Sample;
+
+mod output_queue;
+pub use self::output_queue::OutputQueue;
+
+mod input_queue;
+pub use self::input_queue::InputQueue;
+
+mod stream;
+pub use self::stream::{DuplexStream, InputStream, OutputStream};
+
+/// Errors that can be raised by the CoreAudio layer.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// System error, identified by its `OSStatus` code.
+    #[error("system error: {0}")]
+    Sys(i32),
+    /// Error raised when there's a channel count mismatch between a buffer and
+    /// the queue it's being used with.
+    #[error("mismatch in number of channels in buffer; actual = {actual}, expected = {expected}")]
+    ChannelsMismatch {
+        /// The actual number of channels.
+        actual: usize,
+        /// The expected number of channels.
+        expected: usize,
+    },
+}
+
+impl Error {
+    pub(crate) fn kind(&self) -> crate::ErrorKind {
+        use crate::ErrorKind;
+        use coreaudio_sys as sys;
+
+        match *self {
+            Self::Sys(status) => match status {
+                sys::kAudioQueueErr_InvalidDevice
+                | sys::kAudioQueueErr_QueueInvalidated
+                | sys::kAudioHardwareBadObjectError
+                | sys::kAudioHardwareBadDeviceError => ErrorKind::Disconnected,
+                sys::kAudioQueueErr_Permissions => ErrorKind::PermissionDenied,
+                sys::kAudioQueueErr_InvalidParameter | sys::kAudioQueueErr_InvalidPropertyValue => {
+                    ErrorKind::InvalidInput
+                }
+                sys::kAudioQueueErr_RecordUnderrun => ErrorKind::Xrun,
+                sys::kAudioQueueErr_CodecNotFound => ErrorKind::UnsupportedFormat,
+                _ => ErrorKind::Other,
+            },
+            Self::ChannelsMismatch { .. } => ErrorKind::InvalidInput,
+        }
+    }
+}
+
+/// Helper result wrapper.
+pub type Result<T, E = Error> = ::std::result::Result<T, E>;
+
+/// The configuration of an audio queue.
+///
+/// # Examples
+///
+/// ```
+/// use audio_device::coreaudio::QueueConfig;
+///
+/// let config = QueueConfig {
+///     channels: 1,
+///     ..QueueConfig::default()
+/// };
+///
+/// assert_eq!(config.channels, 1);
+/// assert_eq!(config.sample_rate, 48000);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueueConfig {
+    /// The number of interleaved channels.
+    pub channels: u32,
+    /// The sample rate in frames per second.
+    pub sample_rate: u32,
+    /// The number of frames in each buffer of the queue.
+    pub buffer_frames: u32,
+    /// The number of buffers allocated for the queue. At least two are needed
+    /// to avoid gaps, and each additional buffer adds a buffer's worth of
+    /// latency.
+    pub buffers: u32,
+}
+
+impl QueueConfig {
+    /// The number of bytes needed to store a single buffer of samples of type
+    /// `T`.
+    fn buffer_bytes<T>(&self) -> u32 {
+        self.buffer_frames * self.channels * std::mem::size_of::<T>() as u32
+    }
+}
+
+impl Default for QueueConfig {
+    fn default() -> Self {
+        Self {
+            channels: 2,
+            sample_rate: 48000,
+            buffer_frames: 1024,
+            buffers: 3,
+        }
+    }
+}
+
+fn frames_to_duration(frames: u64, rate: u32) -> Duration {
+    if rate == 0 {
+        return Duration::ZERO;
+    }
+
+    Duration::from_nanos(frames * 1_000_000_000 / rate as u64)
+}
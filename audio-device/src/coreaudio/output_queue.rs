@@ -0,0 +1,288 @@
+use std::future;
+use std::marker;
+use std::pin::Pin;
+use std::ptr;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use coreaudio_sys as sys;
+
+use crate::coreaudio::queue::{Buffer, Queue};
+use crate::coreaudio::{frames_to_duration, Error, QueueConfig, Result, Sample};
+
+/// An audio queue playing interleaved audio through the default output
+/// device.
+///
+/// Written frames are copied into the buffers of the queue, which are handed
+/// over to the device once they're full or the queue is
+/// [flushed][OutputQueue::flush]. Playback starts once all buffers of the
+/// queue have been filled.
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::coreaudio;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let config = coreaudio::QueueConfig::default();
+/// let mut queue = coreaudio::OutputQueue::<f32>::open(config)?;
+///
+/// let samples = vec![0.0f32; config.channels as usize * 48000];
+/// queue.write_interleaved(audio::wrap::interleaved(&samples[..], config.channels as usize))?;
+/// queue.drain()?;
+/// # Ok(()) }
+/// ```
+pub struct OutputQueue<T> {
+    queue: Queue,
+    config: QueueConfig,
+    channels: usize,
+    // A buffer which has been partially filled but not yet handed over.
+    current: Option<Buffer>,
+    // Number of frames handed over to the device since it was started.
+    written: u64,
+    _marker: marker::PhantomData<T>,
+}
+
+impl<T> OutputQueue<T>
+where
+    T: Sample,
+{
+    /// Open a queue playing through the default output device with the given
+    /// configuration.
+    #[tracing::instrument(skip_all)]
+    pub fn open(config: QueueConfig) -> Result<Self> {
+        let queue = Queue::new_output::<T>(&config)?;
+        tracing::trace!(?config, "opened output queue");
+
+        Ok(Self {
+            queue,
+            config,
+            channels: config.channels as usize,
+            current: None,
+            written: 0,
+            _marker: marker::PhantomData,
+        })
+    }
+
+    /// The configuration the queue was opened with.
+    pub fn config(&self) -> QueueConfig {
+        self.config
+    }
+
+    /// Write an interleaved buffer, blocking until all of its frames have been
+    /// copied into the buffers of the queue.
+    pub fn write_interleaved<B>(&mut self, mut buf: B) -> Result<()>
+    where
+        B: audio_core::ReadBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBuf<Sample = T>,
+    {
+        self.check_channels(&buf)?;
+
+        while buf.has_remaining() {
+            let buffer = match self.next_buffer()? {
+                Some(buffer) => buffer,
+                None => self.queue.buffers().pop(),
+            };
+
+            self.fill(buffer, &mut buf)?;
+        }
+
+        Ok(())
+    }
+
+    /// Write an interleaved buffer, completing once all of its frames have
+    /// been copied into the buffers of the queue.
+    pub async fn write_interleaved_async<B>(&mut self, mut buf: B) -> Result<()>
+    where
+        B: audio_core::ReadBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBuf<Sample = T>,
+    {
+        future::poll_fn(|cx| self.poll_write_interleaved(cx, &mut buf)).await
+    }
+
+    fn poll_write_interleaved<B>(&mut self, cx: &mut Context<'_>, mut buf: B) -> Poll<Result<()>>
+    where
+        B: audio_core::ReadBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBuf<Sample = T>,
+    {
+        self.check_channels(&buf)?;
+
+        while buf.has_remaining() {
+            let buffer = match self.next_buffer()? {
+                Some(buffer) => buffer,
+                None => match self.queue.buffers().poll_pop(cx) {
+                    Poll::Ready(buffer) => buffer,
+                    Poll::Pending => return Poll::Pending,
+                },
+            };
+
+            self.fill(buffer, &mut buf)?;
+        }
+
+        Poll::Ready(Ok(()))
+    }
+
+    /// Hand the partially filled buffer over to the device and make sure
+    /// playback has started.
+    pub fn flush(&mut self) -> Result<()> {
+        if let Some(buffer) = self.current.take() {
+            self.enqueue(buffer)?;
+        }
+
+        self.ensure_started()
+    }
+
+    /// Flush the queue and block until all written frames have been played.
+    ///
+    /// The queue is stopped afterwards, and starts again once it's written
+    /// to.
+    pub fn drain(&mut self) -> Result<()> {
+        self.flush()?;
+        self.queue.stop()?;
+
+        // NB: buffers are handed back as they're played, so once all of them
+        // have been handed back the queue has been drained.
+        let buffers = (0..self.config.buffers)
+            .map(|_| self.queue.buffers().pop())
+            .collect::<Vec<_>>();
+
+        for buffer in buffers {
+            self.queue.buffers().push(buffer);
+        }
+
+        self.written = 0;
+        Ok(())
+    }
+
+    /// Pause playback.
+    pub fn pause(&mut self) -> Result<()> {
+        self.queue.pause()
+    }
+
+    /// Resume playback after it's been [paused][OutputQueue::pause].
+    pub fn resume(&mut self) -> Result<()> {
+        self.queue.start()
+    }
+
+    /// Get the volume of the queue in the range `0.0` to `1.0`.
+    pub fn volume(&self) -> Result<f32> {
+        self.queue.parameter(sys::kAudioQueueParam_Volume)
+    }
+
+    /// Set the volume of the queue in the range `0.0` to `1.0`.
+    pub fn set_volume(&mut self, volume: f32) -> Result<()> {
+        self.queue
+            .set_parameter(sys::kAudioQueueParam_Volume, volume)
+    }
+
+    /// Get the latency of the frames which have been handed over to the
+    /// device but not yet played.
+    pub fn latency(&self) -> Result<Duration> {
+        let pending = self.written.saturating_sub(self.queue.sample_time()?);
+        Ok(frames_to_duration(pending, self.config.sample_rate))
+    }
+
+    /// The number of frames played by the device since it was started.
+    pub(super) fn position(&self) -> Result<u64> {
+        self.queue.sample_time()
+    }
+
+    fn check_channels<B>(&self, buf: &B) -> Result<()>
+    where
+        B: audio_core::Buf,
+    {
+        if buf.channels() != self.channels {
+            return Err(Error::ChannelsMismatch {
+                actual: buf.channels(),
+                expected: self.channels,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Get the next buffer to fill without waiting, or `None` if we need to
+    /// wait for the device to hand one back.
+    fn next_buffer(&mut self) -> Result<Option<Buffer>> {
+        if let Some(buffer) = self.current.take() {
+            return Ok(Some(buffer));
+        }
+
+        if let Some(mut buffer) = self.queue.buffers().try_pop() {
+            buffer.set_len::<T>(0);
+            return Ok(Some(buffer));
+        }
+
+        // NB: the queue is started once all buffers have been filled, so
+        // that playback doesn't start with a gap.
+        self.ensure_started()?;
+        Ok(None)
+    }
+
+    /// Fill a buffer from `buf`, handing it over to the device if it's full.
+    fn fill<B>(&mut self, mut buffer: Buffer, buf: &mut B) -> Result<()>
+    where
+        B: audio_core::ReadBuf + audio_core::ExactSizeBuf + audio_core::InterleavedBuf<Sample = T>,
+    {
+        let len = buffer.len::<T>();
+        let space = (buffer.capacity::<T>() - len) / self.channels;
+        let frames = usize::min(space, buf.frames());
+        let from = &buf.as_interleaved()[..frames * self.channels];
+
+        unsafe {
+            ptr::copy_nonoverlapping(from.as_ptr(), buffer.data::<T>().add(len), from.len());
+        }
+
+        buffer.set_len::<T>(len + from.len());
+        buf.advance(frames);
+
+        if frames == space {
+            self.enqueue(buffer)
+        } else {
+            self.current = Some(buffer);
+            Ok(())
+        }
+    }
+
+    fn enqueue(&mut self, buffer: Buffer) -> Result<()> {
+        let frames = buffer.len::<T>() / self.channels;
+        self.queue.enqueue::<T>(buffer)?;
+        self.written += frames as u64;
+        Ok(())
+    }
+
+    fn ensure_started(&mut self) -> Result<()> {
+        if !self.queue.is_started() {
+            self.queue.start()?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> audio_core::AsyncWriteBuf for OutputQueue<T>
+where
+    T: Sample,
+{
+    type Sample = T;
+    type Error = Error;
+
+    fn poll_write_buf<B>(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: B,
+    ) -> Poll<Result<(), Error>>
+    where
+        B: audio_core::ReadBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBuf<Sample = T>,
+    {
+        self.get_mut().poll_write_interleaved(cx, buf)
+    }
+}
+
+// Note: the sample type is only used as a marker.
+impl<T> Unpin for OutputQueue<T> {}
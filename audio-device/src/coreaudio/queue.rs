@@ -0,0 +1,278 @@
+use std::mem;
+use std::os::raw::c_void;
+use std::ptr;
+
+use coreaudio_sys as sys;
+
+use crate::coreaudio::sample::description;
+use crate::coreaudio::{QueueConfig, Result, Sample};
+use crate::event_queue::EventQueue;
+use crate::loom::sync::Arc;
+
+/// A buffer allocated by an audio queue.
+pub(super) struct Buffer(sys::AudioQueueBufferRef);
+
+impl Buffer {
+    /// Access the data of the buffer as samples of type `T`.
+    pub(super) fn data<T>(&self) -> *mut T {
+        // Safety: buffers are valid for as long as the queue they belong to.
+        unsafe { (*self.0).mAudioData.cast() }
+    }
+
+    /// The number of samples of type `T` the buffer can hold.
+    pub(super) fn capacity<T>(&self) -> usize {
+        unsafe { (*self.0).mAudioDataBytesCapacity as usize / mem::size_of::<T>() }
+    }
+
+    /// The number of samples of type `T` which are in use.
+    pub(super) fn len<T>(&self) -> usize {
+        unsafe { (*self.0).mAudioDataByteSize as usize / mem::size_of::<T>() }
+    }
+
+    /// Set the number of samples of type `T` which are in use.
+    pub(super) fn set_len<T>(&mut self, len: usize) {
+        debug_assert!(len <= self.capacity::<T>());
+
+        unsafe {
+            (*self.0).mAudioDataByteSize = (len * mem::size_of::<T>()) as u32;
+        }
+    }
+}
+
+// Safety: a buffer is either owned by the audio queue or by us, and ownership
+// is handed over through an event queue.
+unsafe impl Send for Buffer {}
+
+/// An audio queue and the buffers which have been handed back to us by it.
+pub(super) struct Queue {
+    handle: sys::AudioQueueRef,
+    // NB: the audio queue holds on to a pointer to this, so it must be kept
+    // alive until the audio queue has been disposed.
+    buffers: Arc<EventQueue<Buffer>>,
+    started: bool,
+}
+
+impl Queue {
+    /// Construct a new output queue, where all buffers are initially handed
+    /// to us so that they can be filled.
+    pub(super) fn new_output<T>(config: &QueueConfig) -> Result<Self>
+    where
+        T: Sample,
+    {
+        let buffers = Arc::new(EventQueue::new());
+        let description = description::<T>(config.channels, config.sample_rate);
+        let mut handle = ptr::null_mut();
+
+        unsafe {
+            status!(sys::AudioQueueNewOutput(
+                &description,
+                Some(output_callback),
+                Arc::as_ptr(&buffers) as *mut c_void,
+                ptr::null_mut(),
+                ptr::null(),
+                0,
+                &mut handle,
+            ))?;
+        }
+
+        let queue = Self {
+            handle,
+            buffers,
+            started: false,
+        };
+
+        for _ in 0..config.buffers {
+            let buffer = queue.allocate(config.buffer_bytes::<T>())?;
+            queue.buffers.push(buffer);
+        }
+
+        Ok(queue)
+    }
+
+    /// Construct a new input queue, where all buffers are initially handed
+    /// to the device so that they can be filled.
+    ///
+    /// The queue is started immediately.
+    pub(super) fn new_input<T>(config: &QueueConfig) -> Result<Self>
+    where
+        T: Sample,
+    {
+        let buffers = Arc::new(EventQueue::new());
+        let description = description::<T>(config.channels, config.sample_rate);
+        let mut handle = ptr::null_mut();
+
+        unsafe {
+            status!(sys::AudioQueueNewInput(
+                &description,
+                Some(input_callback),
+                Arc::as_ptr(&buffers) as *mut c_void,
+                ptr::null_mut(),
+                ptr::null(),
+                0,
+                &mut handle,
+            ))?;
+        }
+
+        let mut queue = Self {
+            handle,
+            buffers,
+            started: false,
+        };
+
+        for _ in 0..config.buffers {
+            let buffer = queue.allocate(config.buffer_bytes::<T>())?;
+            queue.enqueue::<T>(buffer)?;
+        }
+
+        queue.start()?;
+        Ok(queue)
+    }
+
+    /// The buffers which have been handed back to us by the audio queue.
+    pub(super) fn buffers(&self) -> &EventQueue<Buffer> {
+        &self.buffers
+    }
+
+    /// Test if the queue has been started.
+    pub(super) fn is_started(&self) -> bool {
+        self.started
+    }
+
+    fn allocate(&self, bytes: u32) -> Result<Buffer> {
+        let mut buffer = ptr::null_mut();
+
+        unsafe {
+            status!(sys::AudioQueueAllocateBuffer(
+                self.handle,
+                bytes,
+                &mut buffer
+            ))?;
+        }
+
+        Ok(Buffer(buffer))
+    }
+
+    /// Hand a buffer over to the audio queue.
+    ///
+    /// If this fails the buffer is handed back to us empty, so that it isn't
+    /// lost.
+    pub(super) fn enqueue<T>(&self, mut buffer: Buffer) -> Result<()> {
+        unsafe {
+            if let Err(e) = status!(sys::AudioQueueEnqueueBuffer(
+                self.handle,
+                buffer.0,
+                0,
+                ptr::null()
+            )) {
+                buffer.set_len::<T>(0);
+                self.buffers.push(buffer);
+                return Err(e);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Start or resume the queue.
+    pub(super) fn start(&mut self) -> Result<()> {
+        unsafe {
+            status!(sys::AudioQueueStart(self.handle, ptr::null()))?;
+        }
+
+        self.started = true;
+        Ok(())
+    }
+
+    /// Pause the queue without affecting its buffers.
+    pub(super) fn pause(&mut self) -> Result<()> {
+        unsafe { status!(sys::AudioQueuePause(self.handle)) }
+    }
+
+    /// Stop the queue once all of its buffers have been played. Buffers are
+    /// handed back as they're played.
+    pub(super) fn stop(&mut self) -> Result<()> {
+        unsafe {
+            status!(sys::AudioQueueFlush(self.handle))?;
+            status!(sys::AudioQueueStop(self.handle, 0))?;
+        }
+
+        self.started = false;
+        Ok(())
+    }
+
+    /// Set a parameter of the queue.
+    pub(super) fn set_parameter(&self, id: sys::AudioQueueParameterID, value: f32) -> Result<()> {
+        unsafe { status!(sys::AudioQueueSetParameter(self.handle, id, value)) }
+    }
+
+    /// Get a parameter of the queue.
+    pub(super) fn parameter(&self, id: sys::AudioQueueParameterID) -> Result<f32> {
+        let mut value = 0.0;
+
+        unsafe {
+            status!(sys::AudioQueueGetParameter(self.handle, id, &mut value))?;
+        }
+
+        Ok(value)
+    }
+
+    /// The number of frames processed by the device since the queue was
+    /// started.
+    pub(super) fn sample_time(&self) -> Result<u64> {
+        // NB: the timeline of the queue is only valid once it's started.
+        if !self.started {
+            return Ok(0);
+        }
+
+        unsafe {
+            let mut timestamp = mem::zeroed::<sys::AudioTimeStamp>();
+
+            status!(sys::AudioQueueGetCurrentTime(
+                self.handle,
+                ptr::null_mut(),
+                &mut timestamp,
+                ptr::null_mut(),
+            ))?;
+
+            if timestamp.mFlags & sys::kAudioTimeStampSampleTimeValid == 0 {
+                return Ok(0);
+            }
+
+            Ok(timestamp.mSampleTime.max(0.0) as u64)
+        }
+    }
+}
+
+// Safety: audio queues can be used from any thread.
+unsafe impl Send for Queue {}
+
+impl Drop for Queue {
+    fn drop(&mut self) {
+        // NB: disposing immediately is synchronous, so no callbacks are invoked
+        // once this returns. This also frees all of the buffers of the queue.
+        unsafe {
+            let _ = sys::AudioQueueDispose(self.handle, 1);
+        }
+    }
+}
+
+unsafe extern "C" fn output_callback(
+    user_data: *mut c_void,
+    _: sys::AudioQueueRef,
+    buffer: sys::AudioQueueBufferRef,
+) {
+    let buffers = &*(user_data as *const EventQueue<Buffer>);
+    buffers.push(Buffer(buffer));
+}
+
+unsafe extern "C" fn input_callback(
+    user_data: *mut c_void,
+    _: sys::AudioQueueRef,
+    buffer: sys::AudioQueueBufferRef,
+    _: *const sys::AudioTimeStamp,
+    _: u32,
+    _: *const sys::AudioStreamPacketDescription,
+) {
+    let buffers = &*(user_data as *const EventQueue<Buffer>);
+    buffers.push(Buffer(buffer));
+}
@@ -0,0 +1,54 @@
+use coreaudio_sys as sys;
+
+/// Trait implemented for types which can be used as samples of an audio queue.
+///
+/// # Safety
+///
+/// This trait is unsafe to implement, because the format flags need to
+/// accurately describe the memory layout of the type.
+pub unsafe trait Sample: Copy {
+    /// The linear PCM format flags describing the sample type.
+    const FORMAT_FLAGS: u32;
+}
+
+// NB: samples are always in the native endianness.
+const NATIVE_ENDIAN: u32 = if cfg!(target_endian = "big") {
+    sys::kAudioFormatFlagIsBigEndian
+} else {
+    0
+};
+
+unsafe impl Sample for i16 {
+    const FORMAT_FLAGS: u32 =
+        sys::kAudioFormatFlagIsSignedInteger | sys::kAudioFormatFlagIsPacked | NATIVE_ENDIAN;
+}
+
+unsafe impl Sample for i32 {
+    const FORMAT_FLAGS: u32 =
+        sys::kAudioFormatFlagIsSignedInteger | sys::kAudioFormatFlagIsPacked | NATIVE_ENDIAN;
+}
+
+unsafe impl Sample for f32 {
+    const FORMAT_FLAGS: u32 =
+        sys::kAudioFormatFlagIsFloat | sys::kAudioFormatFlagIsPacked | NATIVE_ENDIAN;
+}
+
+/// Construct a description of interleaved linear PCM samples of type `T`.
+pub(super) fn description<T>(channels: u32, sample_rate: u32) -> sys::AudioStreamBasicDescription
+where
+    T: Sample,
+{
+    let bytes_per_frame = channels * std::mem::size_of::<T>() as u32;
+
+    sys::AudioStreamBasicDescription {
+        mSampleRate: sample_rate as f64,
+        mFormatID: sys::kAudioFormatLinearPCM,
+        mFormatFlags: T::FORMAT_FLAGS,
+        mBytesPerPacket: bytes_per_frame,
+        mFramesPerPacket: 1,
+        mBytesPerFrame: bytes_per_frame,
+        mChannelsPerFrame: channels,
+        mBitsPerChannel: std::mem::size_of::<T>() as u32 * 8,
+        mReserved: 0,
+    }
+}
@@ -0,0 +1,210 @@
+use std::time::Duration;
+
+use crate::coreaudio::{Error, InputQueue, OutputQueue, QueueConfig, Result, Sample};
+use crate::stream::{DriftEstimator, StreamSpec};
+
+/// A portable output stream using CoreAudio.
+///
+/// See [OutputStream][crate::stream::OutputStream].
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::coreaudio;
+/// use audio_device::stream::{OutputStream, StreamSpec};
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let mut stream = coreaudio::OutputStream::<f32>::open_default(StreamSpec::new(2, 48000))?;
+/// let spec = stream.spec();
+///
+/// let samples = vec![0.0f32; spec.channels * 1024];
+/// stream.write(audio::wrap::interleaved(&samples[..], spec.channels))?;
+/// # Ok(()) }
+/// ```
+pub struct OutputStream<T> {
+    queue: OutputQueue<T>,
+    spec: StreamSpec,
+}
+
+impl<T> crate::stream::OutputStream for OutputStream<T>
+where
+    T: Sample,
+{
+    type Sample = T;
+    type Error = Error;
+
+    fn open_default(spec: StreamSpec) -> Result<Self> {
+        let queue = OutputQueue::open(configure(spec))?;
+        Ok(Self { queue, spec })
+    }
+
+    fn spec(&self) -> StreamSpec {
+        self.spec
+    }
+
+    fn latency(&mut self) -> Result<Duration> {
+        self.queue.latency()
+    }
+
+    fn write<B>(&mut self, buf: B) -> Result<()>
+    where
+        B: audio_core::ReadBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBuf<Sample = T>,
+    {
+        self.queue.write_interleaved(buf)
+    }
+}
+
+/// A portable input stream using CoreAudio.
+///
+/// See [InputStream][crate::stream::InputStream].
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::coreaudio;
+/// use audio_device::stream::{InputStream, StreamSpec};
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let mut stream = coreaudio::InputStream::<f32>::open_default(StreamSpec::new(2, 48000))?;
+/// let spec = stream.spec();
+///
+/// let mut samples = vec![0.0f32; spec.channels * 1024];
+/// stream.read(audio::wrap::interleaved(&mut samples[..], spec.channels))?;
+/// # Ok(()) }
+/// ```
+pub struct InputStream<T> {
+    queue: InputQueue<T>,
+    spec: StreamSpec,
+}
+
+impl<T> crate::stream::InputStream for InputStream<T>
+where
+    T: Sample,
+{
+    type Sample = T;
+    type Error = Error;
+
+    fn open_default(spec: StreamSpec) -> Result<Self> {
+        let queue = InputQueue::open(configure(spec))?;
+        Ok(Self { queue, spec })
+    }
+
+    fn spec(&self) -> StreamSpec {
+        self.spec
+    }
+
+    fn latency(&mut self) -> Result<Duration> {
+        self.queue.latency()
+    }
+
+    fn read<B>(&mut self, buf: B) -> Result<()>
+    where
+        B: audio_core::WriteBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBufMut<Sample = T>,
+    {
+        self.queue.read_interleaved(buf)
+    }
+}
+
+/// A portable duplex stream using CoreAudio.
+///
+/// See [DuplexStream][crate::stream::DuplexStream].
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::coreaudio;
+/// use audio_device::stream::{DuplexStream, StreamSpec};
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let mut stream = coreaudio::DuplexStream::<f32>::open_default(StreamSpec::new(2, 48000))?;
+/// let spec = stream.input_spec();
+/// let mut samples = vec![0.0f32; spec.channels * 256];
+///
+/// loop {
+///     stream.read(audio::wrap::interleaved(&mut samples[..], spec.channels))?;
+///     stream.write(audio::wrap::interleaved(&samples[..], spec.channels))?;
+///     println!("resample ratio: {}", stream.resample_ratio()?);
+/// }
+/// # }
+/// ```
+pub struct DuplexStream<T> {
+    input: InputQueue<T>,
+    output: OutputQueue<T>,
+    spec: StreamSpec,
+    drift: DriftEstimator,
+}
+
+impl<T> crate::stream::DuplexStream for DuplexStream<T>
+where
+    T: Sample,
+{
+    type Sample = T;
+    type Error = Error;
+
+    fn open_default(spec: StreamSpec) -> Result<Self> {
+        let input = InputQueue::open(configure(spec))?;
+        let output = OutputQueue::open(configure(spec))?;
+
+        Ok(Self {
+            input,
+            output,
+            spec,
+            drift: DriftEstimator::new(spec.sample_rate, spec.sample_rate),
+        })
+    }
+
+    fn input_spec(&self) -> StreamSpec {
+        self.spec
+    }
+
+    fn output_spec(&self) -> StreamSpec {
+        self.spec
+    }
+
+    fn read<B>(&mut self, buf: B) -> Result<()>
+    where
+        B: audio_core::WriteBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBufMut<Sample = T>,
+    {
+        self.input.read_interleaved(buf)
+    }
+
+    fn write<B>(&mut self, buf: B) -> Result<()>
+    where
+        B: audio_core::ReadBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBuf<Sample = T>,
+    {
+        self.output.write_interleaved(buf)
+    }
+
+    fn resample_ratio(&mut self) -> Result<f64> {
+        // NB: the output queue is only started once it's been filled, so
+        // there's nothing to measure until then.
+        let output = self.output.position()?;
+
+        if output == 0 {
+            return Ok(self.drift.ratio());
+        }
+
+        let input = self.input.position()?;
+        Ok(self.drift.update(input, output))
+    }
+}
+
+/// Construct the queue configuration for the given specification.
+///
+/// Audio queues convert to and from the format of the device, so the
+/// specification is always achieved.
+fn configure(spec: StreamSpec) -> QueueConfig {
+    QueueConfig {
+        channels: spec.channels as u32,
+        sample_rate: spec.sample_rate,
+        ..QueueConfig::default()
+    }
+}
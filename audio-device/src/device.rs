@@ -0,0 +1,122 @@
+use audio_core::SampleFormat;
+
+use crate::Result;
+
+/// The direction of a device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Direction {
+    /// A device which audio is played through, like speakers.
+    Output,
+    /// A device which audio is captured from, like a microphone.
+    Input,
+    /// A device which supports both playing and capturing audio.
+    Duplex,
+}
+
+impl Direction {
+    /// Test if the device can be used for output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio_device::Direction;
+    ///
+    /// assert!(Direction::Output.is_output());
+    /// assert!(Direction::Duplex.is_output());
+    /// assert!(!Direction::Input.is_output());
+    /// ```
+    pub fn is_output(self) -> bool {
+        matches!(self, Self::Output | Self::Duplex)
+    }
+
+    /// Test if the device can be used for input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio_device::Direction;
+    ///
+    /// assert!(Direction::Input.is_input());
+    /// assert!(Direction::Duplex.is_input());
+    /// assert!(!Direction::Output.is_input());
+    /// ```
+    pub fn is_input(self) -> bool {
+        matches!(self, Self::Input | Self::Duplex)
+    }
+}
+
+/// A backend-agnostic description of a device.
+///
+/// See [devices].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DeviceInfo {
+    /// The identifier of the device, which is used to open it with its
+    /// backend. This is the PCM name for ALSA, the endpoint identifier for
+    /// WASAPI, the device UID for CoreAudio, the sink or source name for
+    /// PulseAudio, the node name for PipeWire, the device path for OSS and
+    /// the device name for sndio.
+    pub id: String,
+    /// The human readable name of the device.
+    pub name: String,
+    /// The direction of the device.
+    pub direction: Direction,
+    /// If this is the default device in its direction.
+    pub is_default: bool,
+    /// The sample formats nominally supported by the device. This is empty if
+    /// the device couldn't be probed, such as when it's busy.
+    pub formats: Vec<SampleFormat>,
+    /// The minimum and maximum number of channels nominally supported by the
+    /// device, if it could be probed.
+    pub channels: Option<(usize, usize)>,
+    /// The minimum and maximum sample rate nominally supported by the
+    /// device, if it could be probed.
+    pub sample_rates: Option<(u32, u32)>,
+}
+
+/// Enumerate the devices of all enabled backends.
+///
+/// This is intended for building device pickers which work regardless of
+/// platform. For WASAPI only active endpoints are included, and COM must have
+/// been initialized on the current thread through `wasapi::audio_prelude`.
+/// Sound servers like PulseAudio and PipeWire are connected to for the
+/// duration of the call, which fails if they aren't running.
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> anyhow::Result<()> {
+/// for device in audio_device::devices()? {
+///     let marker = if device.is_default { "*" } else { " " };
+///     println!("{} {} ({:?}): {}", marker, device.name, device.direction, device.id);
+/// }
+/// # Ok(()) }
+/// ```
+pub fn devices() -> Result<Vec<DeviceInfo>> {
+    #[allow(unused_mut)]
+    let mut devices = Vec::new();
+
+    #[cfg(feature = "alsa")]
+    devices.extend(crate::alsa::device_infos()?);
+
+    #[cfg(feature = "wasapi")]
+    devices.extend(crate::wasapi::device_infos()?);
+
+    #[cfg(feature = "coreaudio")]
+    devices.extend(crate::coreaudio::device_infos()?);
+
+    #[cfg(feature = "pulse")]
+    devices.extend(crate::pulse::device_infos()?);
+
+    #[cfg(feature = "pipewire")]
+    devices.extend(crate::pipewire::device_infos()?);
+
+    #[cfg(feature = "oss")]
+    devices.extend(crate::oss::device_infos()?);
+
+    #[cfg(feature = "sndio")]
+    devices.extend(crate::sndio::device_infos()?);
+
+    Ok(devices)
+}
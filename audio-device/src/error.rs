@@ -1,3 +1,4 @@
+use std::io;
 use thiserror::Error;
 
 /// Audio runtime errors.
@@ -15,6 +16,142 @@ pub enum Error {
         #[source]
         windows::core::Error,
     ),
+    #[error("runtime error: {0}")]
+    /// An error raised by the audio [runtime][crate::runtime].
+    Runtime(#[from] crate::runtime::Error),
+    #[cfg(feature = "alsa")]
+    #[error("alsa error: {0}")]
+    /// An ALSA error.
+    Alsa(#[from] crate::alsa::Error),
+    #[cfg(feature = "pulse")]
+    #[error("pulseaudio error: {0}")]
+    /// A PulseAudio error.
+    Pulse(#[from] crate::pulse::Error),
+    #[cfg(feature = "pipewire")]
+    #[error("pipewire error: {0}")]
+    /// A PipeWire error.
+    Pipewire(#[from] crate::pipewire::Error),
+    #[cfg(feature = "oss")]
+    #[error("oss error: {0}")]
+    /// An OSS error.
+    Oss(#[from] crate::oss::Error),
+    #[cfg(feature = "sndio")]
+    #[error("sndio error: {0}")]
+    /// A sndio error.
+    Sndio(#[from] crate::sndio::Error),
+    #[cfg(feature = "coreaudio")]
+    #[error("coreaudio error: {0}")]
+    /// A CoreAudio error.
+    CoreAudio(#[from] crate::coreaudio::Error),
+    #[cfg(feature = "wasapi")]
+    #[error("wasapi error: {0}")]
+    /// A WASAPI error.
+    Wasapi(#[from] crate::wasapi::Error),
+    #[cfg(feature = "asio")]
+    #[error("asio error: {0}")]
+    /// An ASIO error.
+    Asio(#[from] crate::asio::Error),
+    #[error("null device error: {0}")]
+    /// An error from the [null][crate::null] backend.
+    Null(#[from] crate::null::Error),
+    #[error("render error: {0}")]
+    /// An error from the [render][crate::render] backend.
+    Render(#[from] crate::render::Error),
+}
+
+impl Error {
+    /// Classify the error regardless of which backend raised it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio_device::ErrorKind;
+    ///
+    /// fn should_retry(error: &audio_device::Error) -> bool {
+    ///     matches!(error.kind(), ErrorKind::WouldBlock | ErrorKind::Xrun | ErrorKind::Busy)
+    /// }
+    /// ```
+    pub fn kind(&self) -> ErrorKind {
+        match *self {
+            #[cfg(feature = "unix")]
+            Self::Unix(errno) => errno.kind(),
+            #[cfg(feature = "windows")]
+            Self::Windows(ref error) => crate::windows::error_kind(error),
+            Self::Runtime(ref error) => error.kind(),
+            #[cfg(feature = "alsa")]
+            Self::Alsa(ref error) => error.kind(),
+            #[cfg(feature = "pulse")]
+            Self::Pulse(ref error) => error.kind(),
+            #[cfg(feature = "pipewire")]
+            Self::Pipewire(ref error) => error.kind(),
+            #[cfg(feature = "oss")]
+            Self::Oss(ref error) => error.kind(),
+            #[cfg(feature = "sndio")]
+            Self::Sndio(ref error) => error.kind(),
+            #[cfg(feature = "coreaudio")]
+            Self::CoreAudio(ref error) => error.kind(),
+            #[cfg(feature = "wasapi")]
+            Self::Wasapi(ref error) => error.kind(),
+            #[cfg(feature = "asio")]
+            Self::Asio(ref error) => error.kind(),
+            Self::Null(ref error) => error.kind(),
+            Self::Render(ref error) => error.kind(),
+        }
+    }
+}
+
+/// Classify an I/O error, using the raw OS error if there is one.
+pub(crate) fn io_error_kind(error: &io::Error) -> ErrorKind {
+    #[cfg(feature = "unix")]
+    if let Some(errno) = error.raw_os_error() {
+        return crate::unix::Errno::new(errno).kind();
+    }
+
+    match error.kind() {
+        io::ErrorKind::WouldBlock => ErrorKind::WouldBlock,
+        io::ErrorKind::PermissionDenied => ErrorKind::PermissionDenied,
+        io::ErrorKind::NotFound => ErrorKind::NotFound,
+        io::ErrorKind::InvalidInput => ErrorKind::InvalidInput,
+        _ => ErrorKind::Other,
+    }
+}
+
+/// A backend-agnostic classification of an [Error].
+///
+/// See [Error::kind].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The operation would block, because the device isn't ready.
+    WouldBlock,
+    /// An underrun or an overrun occurred. For output this means that the
+    /// device ran out of audio to play, and for input that captured audio
+    /// wasn't read fast enough.
+    ///
+    /// These aren't distinguished, since backends like ALSA report both
+    /// through the same error (`EPIPE`) and only the direction of the stream
+    /// tells them apart, which the caller already knows.
+    Xrun,
+    /// The device has been suspended, like when the system goes to sleep.
+    Suspended,
+    /// The device has been disconnected, like when a USB interface is
+    /// unplugged.
+    Disconnected,
+    /// The device doesn't support the requested sample format.
+    UnsupportedFormat,
+    /// The device is in use by someone else.
+    Busy,
+    /// The device can't be accessed due to a lack of permissions.
+    PermissionDenied,
+    /// The device couldn't be found.
+    NotFound,
+    /// An argument was invalid, like a buffer with the wrong number of
+    /// channels.
+    InvalidInput,
+    /// The audio runtime driving the device has been shut down.
+    Shutdown,
+    /// Any other error.
+    Other,
 }
 
 /// The re-exported error type.
@@ -0,0 +1,66 @@
+use std::collections::VecDeque;
+use std::future;
+use std::sync::{Condvar, Mutex};
+use std::task::{Context, Poll};
+
+use crate::runtime::atomic_waker::AtomicWaker;
+
+/// A queue of events delivered by system callbacks, which can be received
+/// either by blocking or asynchronously.
+pub(crate) struct EventQueue<T> {
+    queue: Mutex<VecDeque<T>>,
+    condvar: Condvar,
+    waker: AtomicWaker,
+}
+
+impl<T> EventQueue<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            queue: Mutex::new(VecDeque::new()),
+            condvar: Condvar::new(),
+            waker: AtomicWaker::new(),
+        }
+    }
+
+    /// Push an event and wake up anyone waiting for it.
+    pub(crate) fn push(&self, event: T) {
+        self.queue.lock().unwrap().push_back(event);
+        self.condvar.notify_one();
+        self.waker.wake();
+    }
+
+    /// Try to pop an event without blocking.
+    pub(crate) fn try_pop(&self) -> Option<T> {
+        self.queue.lock().unwrap().pop_front()
+    }
+
+    /// Block until an event is available.
+    pub(crate) fn pop(&self) -> T {
+        let mut queue = self.queue.lock().unwrap();
+
+        loop {
+            if let Some(event) = queue.pop_front() {
+                return event;
+            }
+
+            queue = self.condvar.wait(queue).unwrap();
+        }
+    }
+
+    /// Wait until an event is available.
+    #[cfg_attr(not(feature = "wasapi"), allow(dead_code))]
+    pub(crate) async fn pop_async(&self) -> T {
+        future::poll_fn(|cx| self.poll_pop(cx)).await
+    }
+
+    /// Poll for an event, registering the waker of the current task if none
+    /// is available.
+    pub(crate) fn poll_pop(&self, cx: &mut Context<'_>) -> Poll<T> {
+        self.waker.register_by_ref(cx.waker());
+
+        match self.try_pop() {
+            Some(event) => Poll::Ready(event),
+            None => Poll::Pending,
+        }
+    }
+}
@@ -20,6 +20,7 @@
 //! * [ALSA async playback][alsa-async].
 //! * [WASAPI blocking playback][wasapi-blocking].
 //! * [WASAPI async playback][wasapi-async].
+//! * [CoreAudio blocking playback][coreaudio-blocking].
 //!
 //! <br>
 //!
@@ -27,15 +28,17 @@
 //!
 //! Supported tier 1 platforms and systems are the following:
 //!
-//! | Platform | System | Blocking | Async   |
-//! |----------|--------|----------|---------|
-//! | Windows  | WASAPI | **wip**  | **wip** |
-//! | Linux    | ALSA   | **wip**  | **wip** |
+//! | Platform | System    | Blocking | Async   |
+//! |----------|-----------|----------|---------|
+//! | Windows  | WASAPI    | **wip**  | **wip** |
+//! | Linux    | ALSA      | **wip**  | **wip** |
+//! | macOS    | CoreAudio | **wip**  | **wip** |
 //!
 //! [audio ecosystem]: https://docs.rs/audio
 //! [alsa-blocking]: https://github.com/udoprog/audio/blob/main/audio-device/examples/alsa.rs
 //! [alsa-async]: https://github.com/udoprog/audio/blob/main/audio-device/examples/alsa-async.rs
 //! [audio-core]: https://docs.rs/audio-core
+//! [coreaudio-blocking]: https://github.com/udoprog/audio/blob/main/audio-device/examples/coreaudio.rs
 //! [wasapi-async]: https://github.com/udoprog/audio/blob/main/audio-device/examples/wasapi-async.rs
 //! [wasapi-blocking]: https://github.com/udoprog/audio/blob/main/audio-device/examples/wasapi.rs
 
@@ -57,6 +60,10 @@ cfg_wasapi! {
     pub mod wasapi;
 }
 
+cfg_asio! {
+    pub mod asio;
+}
+
 cfg_windows! {
     pub mod windows;
 }
@@ -77,7 +84,48 @@ cfg_pipewire! {
     pub mod pipewire;
 }
 
+cfg_oss! {
+    pub mod oss;
+}
+
+cfg_sndio! {
+    pub mod sndio;
+}
+
+cfg_coreaudio! {
+    pub mod coreaudio;
+}
+
+cfg_webaudio! {
+    pub mod webaudio;
+}
+
+#[cfg(any(feature = "wasapi", feature = "coreaudio"))]
+mod event_queue;
+
+pub mod null;
+
+pub mod render;
+
 pub mod runtime;
 
+pub mod stream;
+
+pub mod tee;
+
+mod buf_io;
+
+mod device;
+pub use self::device::{devices, DeviceInfo, Direction};
+
+#[cfg(any(feature = "alsa", feature = "pipewire", feature = "wasapi"))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(any(feature = "alsa", feature = "pipewire", feature = "wasapi")))
+)]
+mod monitor;
+#[cfg(any(feature = "alsa", feature = "pipewire", feature = "wasapi"))]
+pub use self::monitor::{DeviceEvent, DeviceMonitor};
+
 mod error;
-pub use self::error::{Error, Result};
+pub use self::error::{Error, ErrorKind, Result};
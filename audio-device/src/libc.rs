@@ -5,10 +5,17 @@
 //!
 //! [libc crate]: https://crates.io/crates/libc
 
-pub use ::libc::eventfd;
 pub use ::libc::free;
 pub use ::libc::nfds_t;
-pub use ::libc::{EFD_NONBLOCK, EWOULDBLOCK};
 pub use ::libc::{c_char, c_int, c_long, c_short, c_uint, c_ulong, c_void};
-pub use ::libc::{poll, pollfd, POLLIN, POLLOUT};
+pub use ::libc::{close, fcntl, ioctl, open};
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub use ::libc::{eventfd, EFD_NONBLOCK};
+#[cfg(target_os = "linux")]
+pub use ::libc::{inotify_add_watch, inotify_event, inotify_init1};
+pub use ::libc::{poll, pollfd, POLLERR, POLLIN, POLLOUT, POLLPRI};
 pub use ::libc::{read, write};
+pub use ::libc::{EWOULDBLOCK, O_RDWR};
+pub use ::libc::{F_GETFL, F_SETFL, O_CLOEXEC, O_NONBLOCK, O_RDONLY, O_WRONLY};
+#[cfg(target_os = "linux")]
+pub use ::libc::{IN_CLOEXEC, IN_CREATE, IN_DELETE, IN_IGNORED, IN_NONBLOCK};
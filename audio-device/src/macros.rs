@@ -109,3 +109,63 @@ macro_rules! cfg_pipewire {
         )*
     }
 }
+
+macro_rules! cfg_oss {
+    ($($item:item)*) => {
+        $(
+            #[cfg(feature = "oss")]
+            #[cfg_attr(docsrs, doc(
+                cfg(feature = "oss")
+            ))]
+            $item
+        )*
+    }
+}
+
+macro_rules! cfg_sndio {
+    ($($item:item)*) => {
+        $(
+            #[cfg(feature = "sndio")]
+            #[cfg_attr(docsrs, doc(
+                cfg(feature = "sndio")
+            ))]
+            $item
+        )*
+    }
+}
+
+macro_rules! cfg_webaudio {
+    ($($item:item)*) => {
+        $(
+            #[cfg(feature = "webaudio")]
+            #[cfg_attr(docsrs, doc(
+                cfg(feature = "webaudio")
+            ))]
+            $item
+        )*
+    }
+}
+
+macro_rules! cfg_asio {
+    ($($item:item)*) => {
+        $(
+            #[cfg(feature = "asio")]
+            #[cfg_attr(docsrs, doc(
+                cfg(feature = "asio")
+            ))]
+            $item
+        )*
+    }
+}
+
+macro_rules! cfg_coreaudio {
+    ($($item:item)*) => {
+        $(
+            #[cfg(feature = "coreaudio")]
+            #[cfg_attr(docsrs, doc(
+                cfg(feature = "coreaudio")
+            ))]
+            $item
+        )*
+    }
+}
@@ -0,0 +1,216 @@
+use std::collections::{HashSet, VecDeque};
+use std::ffi::CStr;
+use std::io;
+use std::mem;
+use std::os::unix::io::{AsRawFd, FromRawFd, OwnedFd};
+use std::ptr;
+use std::task::{Context, Poll};
+
+use crate::alsa::Stream;
+use crate::libc as c;
+use crate::monitor::DeviceEvent;
+use crate::unix::{AsyncPoll, Errno};
+use crate::Result;
+
+const DEV: &[u8] = b"/dev\0";
+const DEV_SND: &[u8] = b"/dev/snd\0";
+
+/// ALSA devices, which are enumerated again whenever a sound card appears or
+/// disappears.
+///
+/// Sound cards are detected through inotify, by watching for their control
+/// devices being created and deleted in `/dev/snd`. Since `/dev/snd` itself
+/// only exists while there's at least one sound card, `/dev` is watched for it
+/// being created.
+pub(super) struct Source {
+    // NB: declared before the inotify descriptor so that the handle is
+    // unregistered before the descriptor is closed.
+    poll_handle: AsyncPoll,
+    fd: OwnedFd,
+    dev_snd: Option<c::c_int>,
+    known: HashSet<String>,
+    events: VecDeque<DeviceEvent>,
+    // NB: set when the devices need to be enumerated again, which initially
+    // reports all present devices.
+    dirty: bool,
+}
+
+impl Source {
+    pub(super) fn new() -> Result<Self> {
+        unsafe {
+            let fd = c::inotify_init1(c::IN_NONBLOCK | c::IN_CLOEXEC);
+
+            if fd < 0 {
+                return Err(last_errno().into());
+            }
+
+            let fd = OwnedFd::from_raw_fd(fd);
+
+            let poll_handle = AsyncPoll::new(c::pollfd {
+                fd: fd.as_raw_fd(),
+                events: c::POLLIN,
+                revents: 0,
+            })?;
+
+            let mut this = Self {
+                poll_handle,
+                fd,
+                dev_snd: None,
+                known: HashSet::new(),
+                events: VecDeque::new(),
+                dirty: true,
+            };
+
+            this.watch(DEV)?;
+            this.watch_dev_snd();
+            Ok(this)
+        }
+    }
+
+    pub(super) fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Result<DeviceEvent>> {
+        loop {
+            if let Some(event) = self.events.pop_front() {
+                return Poll::Ready(Ok(event));
+            }
+
+            if self.dirty {
+                self.dirty = false;
+                self.enumerate()?;
+                continue;
+            }
+
+            match self.read_events() {
+                Ok(()) => continue,
+                Err(e) if e == Errno::EWOULDBLOCK => (),
+                Err(e) => return Poll::Ready(Err(e.into())),
+            }
+
+            let guard = match self.poll_handle.poll_returned_events(cx) {
+                Poll::Ready(guard) => guard?,
+                Poll::Pending => return Poll::Pending,
+            };
+
+            drop(guard);
+        }
+    }
+
+    /// Add a watch for files being created and deleted in the given
+    /// directory.
+    fn watch(&mut self, path: &[u8]) -> Result<c::c_int, Errno> {
+        let wd = unsafe {
+            c::inotify_add_watch(
+                self.fd.as_raw_fd(),
+                path.as_ptr() as *const c::c_char,
+                c::IN_CREATE | c::IN_DELETE,
+            )
+        };
+
+        if wd < 0 {
+            return Err(last_errno());
+        }
+
+        Ok(wd)
+    }
+
+    /// Try to watch `/dev/snd`, which fails if it doesn't exist.
+    fn watch_dev_snd(&mut self) {
+        if self.dev_snd.is_none() {
+            self.dev_snd = self.watch(DEV_SND).ok();
+        }
+    }
+
+    /// Read all pending inotify events, marking the devices as dirty if any of
+    /// them concern sound cards.
+    ///
+    /// Fails with `EWOULDBLOCK` if there are no pending events.
+    fn read_events(&mut self) -> Result<(), Errno> {
+        // NB: aligned so that events can be read in place.
+        let mut buf = [0u64; 512];
+
+        let n = unsafe {
+            c::read(
+                self.fd.as_raw_fd(),
+                buf.as_mut_ptr() as *mut c::c_void,
+                mem::size_of_val(&buf),
+            )
+        };
+
+        if n < 0 {
+            return Err(last_errno());
+        }
+
+        let bytes = buf.as_ptr() as *const u8;
+        let mut offset = 0;
+
+        while offset < n as usize {
+            let event =
+                unsafe { ptr::read_unaligned(bytes.add(offset) as *const c::inotify_event) };
+            let name = bytes.wrapping_add(offset + mem::size_of::<c::inotify_event>());
+            offset += mem::size_of::<c::inotify_event>() + event.len as usize;
+
+            if event.mask & c::IN_IGNORED != 0 {
+                if Some(event.wd) == self.dev_snd {
+                    self.dev_snd = None;
+                }
+
+                continue;
+            }
+
+            if event.len == 0 {
+                continue;
+            }
+
+            // Safety: names are null terminated and padded to the length of
+            // the event.
+            let name = unsafe { CStr::from_ptr(name as *const c::c_char) }.to_bytes();
+
+            if Some(event.wd) == self.dev_snd {
+                if name.starts_with(b"controlC") {
+                    self.dirty = true;
+                }
+            } else if name == b"snd" {
+                if event.mask & c::IN_CREATE != 0 {
+                    self.watch_dev_snd();
+                }
+
+                self.dirty = true;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Enumerate devices, queueing events for the ones which have been added
+    /// or removed since they were last enumerated.
+    fn enumerate(&mut self) -> Result<()> {
+        let mut current = HashSet::new();
+
+        for device in crate::alsa::devices()? {
+            if !device.supports(Stream::Playback) && !device.supports(Stream::Capture) {
+                continue;
+            }
+
+            current.insert(device.name().to_string_lossy().into_owned());
+        }
+
+        for id in self.known.difference(&current) {
+            self.events
+                .push_back(DeviceEvent::Removed { id: id.clone() });
+        }
+
+        for id in current.difference(&self.known) {
+            self.events.push_back(DeviceEvent::Added { id: id.clone() });
+        }
+
+        self.known = current;
+        Ok(())
+    }
+}
+
+fn last_errno() -> Errno {
+    Errno::new(
+        io::Error::last_os_error()
+            .raw_os_error()
+            .unwrap_or_default(),
+    )
+}
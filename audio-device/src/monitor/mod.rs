@@ -0,0 +1,136 @@
+use std::future;
+use std::task::{Context, Poll};
+
+use crate::{Direction, Result};
+
+#[cfg(feature = "alsa")]
+mod alsa;
+
+#[cfg(feature = "pipewire")]
+mod pipewire;
+
+#[cfg(feature = "wasapi")]
+mod wasapi;
+
+/// An event about a change to the audio devices of the system.
+///
+/// See [DeviceMonitor].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DeviceEvent {
+    /// A device has been added, like when a USB interface is plugged in.
+    Added {
+        /// The identifier of the device.
+        id: String,
+    },
+    /// A device has been removed.
+    Removed {
+        /// The identifier of the device.
+        id: String,
+    },
+    /// The default device in the given direction has changed. The identifier
+    /// is `None` if there no longer is a default device.
+    DefaultChanged {
+        /// The direction of the default device which changed.
+        direction: Direction,
+        /// The identifier of the new default device.
+        id: Option<String>,
+    },
+}
+
+/// A monitor of the audio devices of all enabled backends, which reports
+/// devices being added and removed as they happen.
+///
+/// Devices which are present when the monitor is constructed are reported
+/// as [added][DeviceEvent::Added] first. The identifiers of devices are the
+/// same as the ones used by [devices][crate::devices] where the backend
+/// supports it.
+///
+/// The following sources are used:
+/// * ALSA devices are enumerated again each time a sound card appears or
+///   disappears under `/dev/snd`.
+/// * PipeWire audio nodes are reported as they're announced by the
+///   [Registry][crate::pipewire::Registry], identified by their `node.name`.
+/// * WASAPI endpoints are reported through
+///   [DeviceNotifications][crate::wasapi::DeviceNotifications], which is also
+///   the only source of [default changes][DeviceEvent::DefaultChanged].
+///
+/// On unix, the monitor is driven by the audio
+/// [Runtime][crate::runtime::Runtime], so it must be used inside of it. On
+/// Windows, COM must have been initialized on the current thread through
+/// `wasapi::audio_prelude`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::{DeviceEvent, DeviceMonitor};
+///
+/// async fn monitor() -> anyhow::Result<()> {
+///     let mut monitor = DeviceMonitor::new()?;
+///
+///     loop {
+///         match monitor.next().await? {
+///             DeviceEvent::Added { id } => println!("added: {}", id),
+///             DeviceEvent::Removed { id } => println!("removed: {}", id),
+///             event => println!("{:?}", event),
+///         }
+///     }
+/// }
+///
+/// # #[tokio::main] async fn main() -> anyhow::Result<()> {
+/// let runtime = audio_device::runtime::Runtime::new()?;
+/// let bg = ste::spawn();
+/// bg.submit_async(runtime.wrap(monitor())).await?;
+/// bg.join();
+/// # Ok(()) }
+/// ```
+pub struct DeviceMonitor {
+    #[cfg(feature = "alsa")]
+    alsa: self::alsa::Source,
+    #[cfg(feature = "pipewire")]
+    pipewire: self::pipewire::Source,
+    #[cfg(feature = "wasapi")]
+    wasapi: self::wasapi::Source,
+}
+
+impl DeviceMonitor {
+    /// Start monitoring the devices of all enabled backends.
+    ///
+    /// # Panics
+    ///
+    /// On unix, this panics unless called inside of the audio runtime.
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            #[cfg(feature = "alsa")]
+            alsa: self::alsa::Source::new()?,
+            #[cfg(feature = "pipewire")]
+            pipewire: self::pipewire::Source::new()?,
+            #[cfg(feature = "wasapi")]
+            wasapi: self::wasapi::Source::new()?,
+        })
+    }
+
+    /// Wait for the next device event.
+    pub async fn next(&mut self) -> Result<DeviceEvent> {
+        future::poll_fn(|cx| self.poll_next(cx)).await
+    }
+
+    fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Result<DeviceEvent>> {
+        #[cfg(feature = "alsa")]
+        if let Poll::Ready(event) = self.alsa.poll_next(cx) {
+            return Poll::Ready(event);
+        }
+
+        #[cfg(feature = "pipewire")]
+        if let Poll::Ready(event) = self.pipewire.poll_next(cx) {
+            return Poll::Ready(event);
+        }
+
+        #[cfg(feature = "wasapi")]
+        if let Poll::Ready(event) = self.wasapi.poll_next(cx) {
+            return Poll::Ready(event);
+        }
+
+        Poll::Pending
+    }
+}
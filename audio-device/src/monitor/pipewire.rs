@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+use std::ffi::CStr;
+use std::task::{Context, Poll};
+
+use crate::monitor::DeviceEvent;
+use crate::pipewire::{ObjectKind, Registry, RegistryEvent};
+use crate::Result;
+
+const MEDIA_CLASS: &[u8] = b"media.class\0";
+const NODE_NAME: &[u8] = b"node.name\0";
+
+/// Audio devices announced by the PipeWire registry.
+pub(super) struct Source {
+    registry: Registry,
+    // NB: removals are only announced by id, so we keep track of the names of
+    // the nodes we've reported.
+    nodes: HashMap<u32, String>,
+}
+
+impl Source {
+    pub(super) fn new() -> Result<Self> {
+        Ok(Self {
+            registry: Registry::connect()?,
+            nodes: HashMap::new(),
+        })
+    }
+
+    pub(super) fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Result<DeviceEvent>> {
+        loop {
+            let event = match self.registry.poll_next(cx) {
+                Poll::Ready(event) => event?,
+                Poll::Pending => return Poll::Pending,
+            };
+
+            match event {
+                RegistryEvent::Added(object) if object.kind == ObjectKind::Node => {
+                    let properties = &object.properties;
+
+                    // Safety: the keys are null terminated.
+                    let (class, name) = unsafe {
+                        (
+                            properties.get(CStr::from_bytes_with_nul_unchecked(MEDIA_CLASS)),
+                            properties.get(CStr::from_bytes_with_nul_unchecked(NODE_NAME)),
+                        )
+                    };
+
+                    let (class, name) = match (class, name) {
+                        (Some(class), Some(name)) => (class, name),
+                        _ => continue,
+                    };
+
+                    // NB: streams of applications are also nodes, which are
+                    // classified as `Stream/Output/Audio` and the like.
+                    if !matches!(
+                        class.to_bytes(),
+                        b"Audio/Sink" | b"Audio/Source" | b"Audio/Duplex"
+                    ) {
+                        continue;
+                    }
+
+                    let id = name.to_string_lossy().into_owned();
+                    self.nodes.insert(object.id, id.clone());
+                    return Poll::Ready(Ok(DeviceEvent::Added { id }));
+                }
+                RegistryEvent::Removed(id) => {
+                    if let Some(id) = self.nodes.remove(&id) {
+                        return Poll::Ready(Ok(DeviceEvent::Removed { id }));
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+}
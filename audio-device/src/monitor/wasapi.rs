@@ -0,0 +1,84 @@
+use std::collections::{HashSet, VecDeque};
+use std::task::{Context, Poll};
+
+use crate::monitor::DeviceEvent;
+use crate::wasapi::{self, DeviceNotifications, DeviceState, Flow};
+use crate::{Direction, Result};
+
+/// Endpoints reported by WASAPI device notifications.
+pub(super) struct Source {
+    notifications: DeviceNotifications,
+    // NB: endpoints which were active when the monitor was constructed.
+    initial: VecDeque<String>,
+    active: HashSet<String>,
+}
+
+impl Source {
+    pub(super) fn new() -> Result<Self> {
+        // NB: subscribe before enumerating, so that no changes are missed.
+        let notifications = DeviceNotifications::new()?;
+        let initial = wasapi::device_infos()?
+            .into_iter()
+            .map(|info| info.id)
+            .collect::<VecDeque<_>>();
+        let active = initial.iter().cloned().collect();
+
+        Ok(Self {
+            notifications,
+            initial,
+            active,
+        })
+    }
+
+    pub(super) fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Result<DeviceEvent>> {
+        if let Some(id) = self.initial.pop_front() {
+            return Poll::Ready(Ok(DeviceEvent::Added { id }));
+        }
+
+        loop {
+            let event = match self.notifications.poll_next(cx) {
+                Poll::Ready(event) => event,
+                Poll::Pending => return Poll::Pending,
+            };
+
+            // NB: endpoints are rarely added or removed, instead they change
+            // state as they're plugged in and out. Only active endpoints are
+            // considered to be present, like in `devices`.
+            let event = match event {
+                wasapi::DeviceEvent::StateChanged { id, state } => {
+                    if state == Some(DeviceState::Active) {
+                        if !self.active.insert(id.clone()) {
+                            continue;
+                        }
+
+                        DeviceEvent::Added { id }
+                    } else {
+                        if !self.active.remove(&id) {
+                            continue;
+                        }
+
+                        DeviceEvent::Removed { id }
+                    }
+                }
+                wasapi::DeviceEvent::Removed { id } => {
+                    if !self.active.remove(&id) {
+                        continue;
+                    }
+
+                    DeviceEvent::Removed { id }
+                }
+                wasapi::DeviceEvent::DefaultChanged { flow, id } => {
+                    let direction = match flow {
+                        Flow::Render => Direction::Output,
+                        Flow::Capture => Direction::Input,
+                    };
+
+                    DeviceEvent::DefaultChanged { direction, id }
+                }
+                _ => continue,
+            };
+
+            return Poll::Ready(Ok(event));
+        }
+    }
+}
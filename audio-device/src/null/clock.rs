@@ -0,0 +1,170 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// A clock which counts the frames consumed by a device running at a fixed
+/// sample rate.
+///
+/// A clock either follows the time of the system, or is a
+/// [manual][Clock::manual] clock which only moves when it's
+/// [advanced][Clock::advance].
+///
+/// # Examples
+///
+/// ```
+/// use audio_device::null::Clock;
+///
+/// let clock = Clock::new(48000);
+/// clock.wait_until(480);
+/// assert!(clock.position() >= 480);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Clock {
+    rate: u32,
+    time: Time,
+    /// The time at which the clock started counting.
+    start: Duration,
+    offset: u64,
+}
+
+impl Clock {
+    /// Construct a clock with the given sample rate, which starts counting
+    /// from zero now.
+    pub fn new(rate: u32) -> Self {
+        Self::with_time(rate, Time::System(Instant::now()))
+    }
+
+    /// Construct a manual clock with the given sample rate, which starts
+    /// counting from zero.
+    ///
+    /// A manual clock only moves when it's [advanced][Clock::advance], which
+    /// makes devices driven by it deterministic. Clones of the clock, and the
+    /// devices it's used with, share the same time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use audio_device::null::Clock;
+    ///
+    /// let clock = Clock::manual(48000);
+    /// assert_eq!(clock.position(), 0);
+    ///
+    /// clock.advance(Duration::from_millis(10));
+    /// assert_eq!(clock.position(), 480);
+    ///
+    /// // NB: waiting advances the clock instead of blocking.
+    /// clock.wait_until(960);
+    /// assert_eq!(clock.position(), 960);
+    /// ```
+    pub fn manual(rate: u32) -> Self {
+        Self::with_time(rate, Time::Manual(Arc::new(AtomicU64::new(0))))
+    }
+
+    fn with_time(rate: u32, time: Time) -> Self {
+        Self {
+            rate,
+            start: time.now(),
+            time,
+            offset: 0,
+        }
+    }
+
+    /// Construct a clock sharing the time of this one, which starts counting
+    /// from the given position now.
+    pub(super) fn restart(&self, offset: u64) -> Self {
+        Self {
+            rate: self.rate,
+            time: self.time.clone(),
+            start: self.time.now(),
+            offset,
+        }
+    }
+
+    /// The sample rate of the clock.
+    pub fn rate(&self) -> u32 {
+        self.rate
+    }
+
+    /// The number of frames which have passed.
+    pub fn position(&self) -> u64 {
+        let elapsed = self.time.now().saturating_sub(self.start).as_nanos();
+        self.offset + (elapsed * self.rate as u128 / 1_000_000_000) as u64
+    }
+
+    /// The time remaining until the clock reaches the given position.
+    pub fn until(&self, position: u64) -> Duration {
+        self.target(position).saturating_sub(self.time.now())
+    }
+
+    /// Advance a [manual][Clock::manual] clock by the given duration.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this isn't a manual clock.
+    pub fn advance(&self, duration: Duration) {
+        match &self.time {
+            Time::Manual(nanos) => {
+                nanos.fetch_add(duration.as_nanos() as u64, Ordering::AcqRel);
+            }
+            Time::System(..) => panic!("only manual clocks can be advanced"),
+        }
+    }
+
+    /// Block the current thread until the clock has reached the given
+    /// position.
+    ///
+    /// A manual clock is advanced to the position instead, as if the time had
+    /// passed.
+    pub fn wait_until(&self, position: u64) {
+        if self.rate == 0 {
+            return;
+        }
+
+        match &self.time {
+            Time::System(..) => {
+                let remaining = self.until(position);
+
+                if remaining > Duration::ZERO {
+                    thread::sleep(remaining);
+                }
+            }
+            Time::Manual(nanos) => {
+                let target = self.target(position).as_nanos() as u64;
+                nanos.fetch_max(target, Ordering::AcqRel);
+            }
+        }
+    }
+
+    /// The time at which the clock reaches the given position.
+    fn target(&self, position: u64) -> Duration {
+        if self.rate == 0 {
+            return self.start;
+        }
+
+        // NB: round up, so that the position has been reached at the target.
+        let frames = position.saturating_sub(self.offset) as u128;
+        let rate = self.rate as u128;
+        let nanos = (frames * 1_000_000_000 + rate - 1) / rate;
+        self.start + Duration::from_nanos(nanos as u64)
+    }
+}
+
+/// The source of time for a clock.
+#[derive(Debug, Clone)]
+enum Time {
+    /// Time elapsed since the given instant.
+    System(Instant),
+    /// Time which has been advanced manually, in nanoseconds.
+    Manual(Arc<AtomicU64>),
+}
+
+impl Time {
+    fn now(&self) -> Duration {
+        match self {
+            Self::System(epoch) => epoch.elapsed(),
+            Self::Manual(nanos) => Duration::from_nanos(nanos.load(Ordering::Acquire)),
+        }
+    }
+}
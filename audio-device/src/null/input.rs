@@ -0,0 +1,166 @@
+use core::marker;
+
+use audio_core::Sample;
+
+use crate::buf_io;
+use crate::null::{Clock, Config, Error, Result};
+
+/// A null input device, which captures silent frames at its sample rate.
+///
+/// The device starts capturing when it's opened. If frames aren't read fast
+/// enough the oldest ones are dropped, which is counted as an
+/// [overrun][Input::overruns].
+///
+/// # Examples
+///
+/// ```
+/// use audio::WriteBuf;
+/// use audio_device::null;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let config = null::Config::default();
+/// let mut input = null::Input::open(config);
+///
+/// let mut samples = vec![1.0f32; config.channels * 480];
+/// let mut buf = audio::wrap::interleaved(&mut samples[..], config.channels);
+///
+/// let mut reader = input.reader::<f32>();
+/// reader.read_interleaved(&mut buf)?;
+/// assert!(!buf.has_remaining_mut());
+/// assert!(samples.iter().all(|s| *s == 0.0));
+/// # Ok(()) }
+/// ```
+pub struct Input {
+    config: Config,
+    clock: Clock,
+    read: u64,
+    overruns: usize,
+}
+
+impl Input {
+    /// Open a null input device with the given configuration.
+    pub fn open(config: Config) -> Self {
+        Self::with_clock(config, Clock::new(config.sample_rate))
+    }
+
+    /// Open a null input device with the given configuration, which is
+    /// driven by the given clock.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rate of the clock isn't the sample rate of the
+    /// configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use audio_device::null;
+    ///
+    /// let config = null::Config::default();
+    /// let clock = null::Clock::manual(config.sample_rate);
+    /// let input = null::Input::with_clock(config, clock.clone());
+    ///
+    /// clock.advance(Duration::from_millis(5));
+    /// assert_eq!(input.position(), 240);
+    /// assert_eq!(input.delay(), 240);
+    /// ```
+    pub fn with_clock(config: Config, clock: Clock) -> Self {
+        assert_eq!(
+            clock.rate(),
+            config.sample_rate,
+            "clock must run at the sample rate of the device"
+        );
+
+        Self {
+            config,
+            clock: clock.restart(0),
+            read: 0,
+            overruns: 0,
+        }
+    }
+
+    /// The configuration of the device.
+    pub fn config(&self) -> Config {
+        self.config
+    }
+
+    /// The number of frames which have been captured.
+    pub fn position(&self) -> u64 {
+        self.clock.position()
+    }
+
+    /// The number of frames which have been captured but not yet read.
+    pub fn delay(&self) -> u64 {
+        u64::min(
+            self.position() - self.read,
+            self.config.buffer_frames as u64,
+        )
+    }
+
+    /// The number of times captured frames have been dropped since they
+    /// weren't read fast enough.
+    pub fn overruns(&self) -> usize {
+        self.overruns
+    }
+
+    /// Construct a reader for the given sample type.
+    pub fn reader<T>(&mut self) -> Reader<'_, T> {
+        Reader {
+            input: self,
+            _marker: marker::PhantomData,
+        }
+    }
+
+    /// Read the given number of frames, blocking until they have been
+    /// captured.
+    fn read_frames(&mut self, frames: usize) {
+        let position = self.clock.position();
+        let oldest = position.saturating_sub(self.config.buffer_frames as u64);
+
+        if self.read < oldest {
+            self.overruns += 1;
+            self.read = oldest;
+        }
+
+        self.read += frames as u64;
+        self.clock.wait_until(self.read);
+    }
+}
+
+/// An interleaved type-checked reader for an [Input].
+///
+/// See [Input::reader].
+pub struct Reader<'a, T> {
+    input: &'a mut Input,
+    _marker: marker::PhantomData<T>,
+}
+
+impl<T> Reader<'_, T>
+where
+    T: Sample,
+{
+    /// Read into an interleaved buffer, blocking until its remaining frames
+    /// have been filled with silence.
+    pub fn read_interleaved<B>(&mut self, mut buf: B) -> Result<()>
+    where
+        B: audio_core::WriteBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBufMut<Sample = T>,
+    {
+        if buf.channels() != self.input.config.channels {
+            return Err(Error::ChannelsMismatch {
+                actual: buf.channels(),
+                expected: self.input.config.channels,
+            });
+        }
+
+        let channels = self.input.config.channels;
+        let frames = buf_io::writable_frames(&buf, channels);
+
+        self.input.read_frames(frames);
+        buf.as_interleaved_mut()[..frames * channels].fill(T::ZERO);
+        buf.advance_mut(frames);
+        Ok(())
+    }
+}
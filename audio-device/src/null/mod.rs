@@ -0,0 +1,96 @@
+//! A null backend, which plays and captures audio without any sound
+//! hardware.
+//!
+//! Frames are consumed and produced at the configured sample rate as
+//! measured by a [Clock], so applications behave as if a real device was in
+//! use. Played audio is discarded and captured audio is silent.
+//!
+//! This is useful for running applications and tests on machines which don't
+//! have any sound hardware, like CI machines.
+//!
+//! Devices can also be driven by a [manual][Clock::manual] clock which only
+//! moves when it's advanced, so that tests using them are deterministic.
+
+use std::time::Duration;
+
+use thiserror::Error;
+
+mod clock;
+pub use self::clock::Clock;
+
+mod output;
+pub use self::output::{Output, Writer};
+
+mod input;
+pub use self::input::{Input, Reader};
+
+mod stream;
+pub use self::stream::{DuplexStream, InputStream, OutputStream};
+
+/// Errors that can be raised by the null backend.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// Error raised when there's a channel count mismatch between a buffer and
+    /// the device it's being used with.
+    #[error("mismatch in number of channels in buffer; actual = {actual}, expected = {expected}")]
+    ChannelsMismatch {
+        /// The actual number of channels.
+        actual: usize,
+        /// The expected number of channels.
+        expected: usize,
+    },
+}
+
+impl Error {
+    pub(crate) fn kind(&self) -> crate::ErrorKind {
+        match self {
+            Self::ChannelsMismatch { .. } => crate::ErrorKind::InvalidInput,
+        }
+    }
+}
+
+/// Helper result wrapper.
+pub type Result<T, E = Error> = ::std::result::Result<T, E>;
+
+/// The configuration of a null device.
+///
+/// # Examples
+///
+/// ```
+/// use audio_device::null::Config;
+///
+/// let config = Config {
+///     channels: 1,
+///     ..Config::default()
+/// };
+///
+/// assert_eq!(config.channels, 1);
+/// assert_eq!(config.sample_rate, 48000);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Config {
+    /// The number of interleaved channels.
+    pub channels: usize,
+    /// The sample rate in frames per second.
+    pub sample_rate: u32,
+    /// The number of frames which fit in the buffer of the device.
+    pub buffer_frames: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            channels: 2,
+            sample_rate: 48000,
+            buffer_frames: 1024,
+        }
+    }
+}
+
+fn frames_to_duration(frames: u64, rate: u32) -> Duration {
+    if rate == 0 {
+        return Duration::ZERO;
+    }
+
+    Duration::from_nanos((frames as u128 * 1_000_000_000 / rate as u128) as u64)
+}
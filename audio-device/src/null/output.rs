@@ -0,0 +1,188 @@
+use core::marker;
+
+use crate::null::{Clock, Config, Error, Result};
+
+/// A null output device, which consumes written frames at its sample rate.
+///
+/// The device starts playing once the first frames are written to it. If
+/// it runs out of frames to play it stops, which is counted as an
+/// [underrun][Output::underruns], and starts again once more frames are
+/// written.
+///
+/// # Examples
+///
+/// ```
+/// use audio_device::null;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let config = null::Config {
+///     buffer_frames: 256,
+///     ..null::Config::default()
+/// };
+///
+/// let mut output = null::Output::open(config);
+/// let samples = vec![0i16; config.channels * 512];
+///
+/// let mut writer = output.writer::<i16>();
+/// writer.write_interleaved(audio::wrap::interleaved(&samples[..], config.channels))?;
+///
+/// // NB: half of the frames have been played to make room for the rest.
+/// assert!(output.position() >= 256);
+/// assert!(output.delay() <= 256);
+/// # Ok(()) }
+/// ```
+pub struct Output {
+    config: Config,
+    /// The clock which the device is driven by.
+    time: Clock,
+    /// The clock counting the frames played since the device was last
+    /// started.
+    clock: Option<Clock>,
+    written: u64,
+    underruns: usize,
+}
+
+impl Output {
+    /// Open a null output device with the given configuration.
+    pub fn open(config: Config) -> Self {
+        Self::with_clock(config, Clock::new(config.sample_rate))
+    }
+
+    /// Open a null output device with the given configuration, which is
+    /// driven by the given clock.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rate of the clock isn't the sample rate of the
+    /// configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use audio_device::null;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let config = null::Config::default();
+    /// let clock = null::Clock::manual(config.sample_rate);
+    /// let mut output = null::Output::with_clock(config, clock.clone());
+    ///
+    /// let samples = vec![0i16; config.channels * 480];
+    /// output
+    ///     .writer::<i16>()
+    ///     .write_interleaved(audio::wrap::interleaved(&samples[..], config.channels))?;
+    ///
+    /// clock.advance(Duration::from_millis(5));
+    /// assert_eq!(output.position(), 240);
+    /// assert_eq!(output.delay(), 240);
+    /// # Ok(()) }
+    /// ```
+    pub fn with_clock(config: Config, clock: Clock) -> Self {
+        assert_eq!(
+            clock.rate(),
+            config.sample_rate,
+            "clock must run at the sample rate of the device"
+        );
+
+        Self {
+            config,
+            time: clock,
+            clock: None,
+            written: 0,
+            underruns: 0,
+        }
+    }
+
+    /// The configuration of the device.
+    pub fn config(&self) -> Config {
+        self.config
+    }
+
+    /// The number of frames which have been played.
+    pub fn position(&self) -> u64 {
+        match &self.clock {
+            Some(clock) => u64::min(clock.position(), self.written),
+            None => 0,
+        }
+    }
+
+    /// The number of frames which have been written but not yet played.
+    pub fn delay(&self) -> u64 {
+        self.written - self.position()
+    }
+
+    /// The number of times the device has run out of frames to play.
+    pub fn underruns(&self) -> usize {
+        self.underruns
+    }
+
+    /// Construct a writer for the given sample type.
+    pub fn writer<T>(&mut self) -> Writer<'_, T> {
+        Writer {
+            output: self,
+            _marker: marker::PhantomData,
+        }
+    }
+
+    /// Write the given number of frames, blocking until they fit in the
+    /// buffer.
+    fn write_frames(&mut self, mut frames: usize) {
+        while frames > 0 {
+            let clock = match &self.clock {
+                Some(clock) if clock.position() <= self.written => clock.clone(),
+                Some(..) => {
+                    self.underruns += 1;
+                    self.restart()
+                }
+                None => self.restart(),
+            };
+
+            let buffered = (self.written - clock.position()) as usize;
+
+            if buffered >= self.config.buffer_frames {
+                clock.wait_until(self.written + 1 - self.config.buffer_frames as u64);
+                continue;
+            }
+
+            let n = usize::min(frames, self.config.buffer_frames - buffered);
+            self.written += n as u64;
+            frames -= n;
+        }
+    }
+
+    /// Restart the clock from the frames written so far.
+    fn restart(&mut self) -> Clock {
+        self.clock.insert(self.time.restart(self.written)).clone()
+    }
+}
+
+/// An interleaved type-checked writer for an [Output].
+///
+/// See [Output::writer].
+pub struct Writer<'a, T> {
+    output: &'a mut Output,
+    _marker: marker::PhantomData<T>,
+}
+
+impl<T> Writer<'_, T> {
+    /// Write an interleaved buffer, blocking until all of its frames have been
+    /// accepted by the device.
+    pub fn write_interleaved<B>(&mut self, mut buf: B) -> Result<()>
+    where
+        B: audio_core::ReadBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBuf<Sample = T>,
+    {
+        if buf.channels() != self.output.config.channels {
+            return Err(Error::ChannelsMismatch {
+                actual: buf.channels(),
+                expected: self.output.config.channels,
+            });
+        }
+
+        let frames = buf.remaining();
+        self.output.write_frames(frames);
+        buf.advance(frames);
+        Ok(())
+    }
+}
@@ -0,0 +1,268 @@
+use std::time::Duration;
+
+use audio_core::Sample;
+
+use crate::null::{frames_to_duration, Clock, Config, Error, Input, Output, Result};
+use crate::stream::{DriftEstimator, StreamSpec};
+
+/// A portable output stream using the null backend.
+///
+/// See [OutputStream][crate::stream::OutputStream].
+///
+/// # Examples
+///
+/// ```
+/// use audio_device::null;
+/// use audio_device::stream::{OutputStream, StreamSpec};
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let mut stream = null::OutputStream::<f32>::open_default(StreamSpec::new(2, 48000))?;
+/// let spec = stream.spec();
+///
+/// let samples = vec![0.0f32; spec.channels * 256];
+/// stream.write(audio::wrap::interleaved(&samples[..], spec.channels))?;
+/// # Ok(()) }
+/// ```
+pub struct OutputStream<T> {
+    output: Output,
+    spec: StreamSpec,
+    _marker: core::marker::PhantomData<T>,
+}
+
+impl<T> OutputStream<T> {
+    /// Open an output stream with the given specification, which is driven
+    /// by the given clock.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rate of the clock isn't the sample rate of the
+    /// specification.
+    pub fn with_clock(spec: StreamSpec, clock: Clock) -> Self {
+        Self {
+            output: Output::with_clock(configure(spec), clock),
+            spec,
+            _marker: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<T> crate::stream::OutputStream for OutputStream<T>
+where
+    T: Sample,
+{
+    type Sample = T;
+    type Error = Error;
+
+    fn open_default(spec: StreamSpec) -> Result<Self> {
+        Ok(Self::with_clock(spec, Clock::new(spec.sample_rate)))
+    }
+
+    fn spec(&self) -> StreamSpec {
+        self.spec
+    }
+
+    fn latency(&mut self) -> Result<Duration> {
+        Ok(frames_to_duration(
+            self.output.delay(),
+            self.spec.sample_rate,
+        ))
+    }
+
+    fn write<B>(&mut self, buf: B) -> Result<()>
+    where
+        B: audio_core::ReadBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBuf<Sample = T>,
+    {
+        self.output.writer::<T>().write_interleaved(buf)
+    }
+}
+
+/// A portable input stream using the null backend.
+///
+/// See [InputStream][crate::stream::InputStream].
+///
+/// # Examples
+///
+/// ```
+/// use audio_device::null;
+/// use audio_device::stream::{InputStream, StreamSpec};
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let mut stream = null::InputStream::<f32>::open_default(StreamSpec::new(2, 48000))?;
+/// let spec = stream.spec();
+///
+/// let mut samples = vec![0.0f32; spec.channels * 256];
+/// stream.read(audio::wrap::interleaved(&mut samples[..], spec.channels))?;
+/// # Ok(()) }
+/// ```
+pub struct InputStream<T> {
+    input: Input,
+    spec: StreamSpec,
+    _marker: core::marker::PhantomData<T>,
+}
+
+impl<T> InputStream<T> {
+    /// Open an input stream with the given specification, which is driven by
+    /// the given clock.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rate of the clock isn't the sample rate of the
+    /// specification.
+    pub fn with_clock(spec: StreamSpec, clock: Clock) -> Self {
+        Self {
+            input: Input::with_clock(configure(spec), clock),
+            spec,
+            _marker: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<T> crate::stream::InputStream for InputStream<T>
+where
+    T: Sample,
+{
+    type Sample = T;
+    type Error = Error;
+
+    fn open_default(spec: StreamSpec) -> Result<Self> {
+        Ok(Self::with_clock(spec, Clock::new(spec.sample_rate)))
+    }
+
+    fn spec(&self) -> StreamSpec {
+        self.spec
+    }
+
+    fn latency(&mut self) -> Result<Duration> {
+        Ok(frames_to_duration(
+            self.input.delay(),
+            self.spec.sample_rate,
+        ))
+    }
+
+    fn read<B>(&mut self, buf: B) -> Result<()>
+    where
+        B: audio_core::WriteBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBufMut<Sample = T>,
+    {
+        self.input.reader::<T>().read_interleaved(buf)
+    }
+}
+
+/// A portable duplex stream using the null backend.
+///
+/// Since the input and the output are driven by clocks running at the same
+/// rate, the resample ratio stays close to one.
+///
+/// See [DuplexStream][crate::stream::DuplexStream].
+///
+/// # Examples
+///
+/// ```
+/// use audio_device::null;
+/// use audio_device::stream::{DuplexStream, StreamSpec};
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let mut stream = null::DuplexStream::<f32>::open_default(StreamSpec::new(2, 48000))?;
+/// let spec = stream.input_spec();
+/// let mut samples = vec![0.0f32; spec.channels * 256];
+///
+/// for _ in 0..4 {
+///     stream.read(audio::wrap::interleaved(&mut samples[..], spec.channels))?;
+///     stream.write(audio::wrap::interleaved(&samples[..], spec.channels))?;
+/// }
+///
+/// let ratio = stream.resample_ratio()?;
+/// assert!(ratio > 0.5 && ratio < 2.0);
+/// # Ok(()) }
+/// ```
+pub struct DuplexStream<T> {
+    input: Input,
+    output: Output,
+    spec: StreamSpec,
+    drift: DriftEstimator,
+    _marker: core::marker::PhantomData<T>,
+}
+
+impl<T> DuplexStream<T> {
+    /// Open a duplex stream with the given specification, where both the
+    /// input and the output are driven by the given clock.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rate of the clock isn't the sample rate of the
+    /// specification.
+    pub fn with_clock(spec: StreamSpec, clock: Clock) -> Self {
+        Self {
+            input: Input::with_clock(configure(spec), clock.clone()),
+            output: Output::with_clock(configure(spec), clock),
+            spec,
+            drift: DriftEstimator::new(spec.sample_rate, spec.sample_rate),
+            _marker: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<T> crate::stream::DuplexStream for DuplexStream<T>
+where
+    T: Sample,
+{
+    type Sample = T;
+    type Error = Error;
+
+    fn open_default(spec: StreamSpec) -> Result<Self> {
+        Ok(Self::with_clock(spec, Clock::new(spec.sample_rate)))
+    }
+
+    fn input_spec(&self) -> StreamSpec {
+        self.spec
+    }
+
+    fn output_spec(&self) -> StreamSpec {
+        self.spec
+    }
+
+    fn read<B>(&mut self, buf: B) -> Result<()>
+    where
+        B: audio_core::WriteBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBufMut<Sample = T>,
+    {
+        self.input.reader::<T>().read_interleaved(buf)
+    }
+
+    fn write<B>(&mut self, buf: B) -> Result<()>
+    where
+        B: audio_core::ReadBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBuf<Sample = T>,
+    {
+        self.output.writer::<T>().write_interleaved(buf)
+    }
+
+    fn resample_ratio(&mut self) -> Result<f64> {
+        // NB: the output is only started once it's been written to, so
+        // there's nothing to measure until then.
+        let output = self.output.position();
+
+        if output == 0 {
+            return Ok(self.drift.ratio());
+        }
+
+        let input = self.input.position();
+        Ok(self.drift.update(input, output))
+    }
+}
+
+/// Construct the device configuration for the given specification.
+///
+/// Null devices support any specification, so it's always achieved.
+fn configure(spec: StreamSpec) -> Config {
+    Config {
+        channels: spec.channels,
+        sample_rate: spec.sample_rate,
+        ..Config::default()
+    }
+}
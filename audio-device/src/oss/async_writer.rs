@@ -0,0 +1,128 @@
+use core::marker;
+use std::future;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use crate::buf_io;
+use crate::libc as c;
+use crate::oss::{Dsp, Error, Result};
+use crate::unix::{AsyncPoll, Errno};
+
+/// An interleaved type-checked async writer for a [Dsp].
+///
+/// See [Dsp::async_writer].
+pub struct AsyncWriter<'a, T> {
+    dsp: &'a mut Dsp,
+    poll_handle: AsyncPoll,
+    channels: usize,
+    // NB: the number of bytes of the first frame of the buffer which have
+    // already been written, since writes are byte-oriented.
+    partial: usize,
+    _marker: marker::PhantomData<T>,
+}
+
+impl<'a, T> AsyncWriter<'a, T> {
+    /// Construct a new writer surrounding the given device.
+    ///
+    /// # Safety
+    ///
+    /// This constructor assumes that the caller has checked that type `T` is
+    /// appropriate for writing to the given device, and that the device is in
+    /// nonblocking mode.
+    pub(super) unsafe fn new(dsp: &'a mut Dsp, channels: usize) -> Result<Self> {
+        let pollfd = c::pollfd {
+            fd: dsp.fd,
+            events: c::POLLOUT,
+            revents: 0,
+        };
+
+        Ok(Self {
+            dsp,
+            poll_handle: AsyncPoll::new(pollfd)?,
+            channels,
+            partial: 0,
+            _marker: marker::PhantomData,
+        })
+    }
+
+    /// Get the current playback delay in frames.
+    ///
+    /// See [Dsp::delay].
+    pub fn delay(&mut self) -> Result<usize> {
+        self.dsp.delay()
+    }
+
+    /// Write an interleaved buffer.
+    pub async fn write_interleaved<B>(&mut self, mut buf: B) -> Result<()>
+    where
+        B: audio_core::Buf<Sample = T>
+            + audio_core::ReadBuf
+            + audio_core::ExactSizeBuf
+            + audio_core::InterleavedBuf<Sample = T>,
+    {
+        future::poll_fn(|cx| self.poll_write_interleaved(cx, &mut buf)).await
+    }
+
+    fn poll_write_interleaved<B>(&mut self, cx: &mut Context<'_>, mut buf: B) -> Poll<Result<()>>
+    where
+        B: audio_core::Buf<Sample = T>
+            + audio_core::ReadBuf
+            + audio_core::ExactSizeBuf
+            + audio_core::InterleavedBuf<Sample = T>,
+    {
+        if buf.channels() != self.channels {
+            return Poll::Ready(Err(Error::ChannelsMismatch {
+                actual: buf.channels(),
+                expected: self.channels,
+            }));
+        }
+
+        let Self {
+            dsp,
+            poll_handle,
+            channels,
+            partial,
+            ..
+        } = self;
+
+        buf_io::poll_write(&mut buf, *channels, partial, |ptr, len| {
+            match unsafe { dsp.write_raw(ptr as *const c::c_void, len) } {
+                Ok(0) => Poll::Ready(Err(Error::Io(io::ErrorKind::WriteZero.into()))),
+                Ok(n) => Poll::Ready(Ok(n)),
+                Err(Error::Sys(Errno::EWOULDBLOCK)) => {
+                    // NB: the guard is dropped right away, which re-arms the
+                    // descriptor before the write is retried.
+                    match poll_handle.poll_returned_events(cx) {
+                        Poll::Ready(guard) => drop(guard?),
+                        Poll::Pending => return Poll::Pending,
+                    }
+
+                    Poll::Ready(Ok(0))
+                }
+                Err(e) => Poll::Ready(Err(e)),
+            }
+        })
+    }
+}
+
+impl<T> audio_core::AsyncWriteBuf for AsyncWriter<'_, T> {
+    type Sample = T;
+    type Error = Error;
+
+    fn poll_write_buf<B>(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: B) -> Poll<Result<()>>
+    where
+        B: audio_core::ReadBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBuf<Sample = T>,
+    {
+        self.get_mut().poll_write_interleaved(cx, buf)
+    }
+}
+
+// Note: the sample type is only used as a marker.
+impl<T> Unpin for AsyncWriter<'_, T> {}
+
+// Safety: the device is only accessed through a mutable reference to the
+// writer.
+unsafe impl<T> Send for AsyncWriter<'_, T> {}
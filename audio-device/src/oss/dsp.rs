@@ -0,0 +1,494 @@
+use crate::libc as c;
+use crate::oss::ioctl;
+use crate::oss::{Error, Format, Reader, Result, Sample, Writer};
+use crate::unix::Errno;
+use crate::{DeviceInfo, Direction};
+use std::ffi::CString;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+
+/// The path of the default DSP device.
+const DEFAULT_PATH: &str = "/dev/dsp";
+
+/// The direction a [Dsp] device is opened in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stream {
+    /// The device is opened for playback.
+    Playback,
+    /// The device is opened for capture.
+    Capture,
+}
+
+impl fmt::Display for Stream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Playback => f.write_str("playback"),
+            Self::Capture => f.write_str("capture"),
+        }
+    }
+}
+
+/// List the paths of the DSP devices of the system, like `/dev/dsp` and
+/// `/dev/dsp0`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::oss;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// for path in oss::devices()? {
+///     println!("{}", path.display());
+/// }
+/// # Ok(()) }
+/// ```
+pub fn devices() -> Result<Vec<PathBuf>> {
+    let mut devices = Vec::new();
+
+    for entry in fs::read_dir("/dev")? {
+        let entry = entry?;
+
+        if entry.file_name().as_bytes().starts_with(b"dsp") {
+            devices.push(entry.path());
+        }
+    }
+
+    devices.sort();
+    Ok(devices)
+}
+
+/// Enumerate the DSP devices of the system, probing their formats by opening
+/// them in each direction.
+pub(crate) fn device_infos() -> Result<Vec<DeviceInfo>> {
+    let mut infos = Vec::new();
+
+    for path in devices()? {
+        let mut formats = Vec::new();
+        let mut playback = false;
+        let mut capture = false;
+
+        for stream in [Stream::Playback, Stream::Capture] {
+            // NB: devices might be busy or only support one direction, in
+            // which case they fail to open.
+            let supported = match Dsp::open(&path, stream).and_then(|mut dsp| dsp.formats()) {
+                Ok(supported) => supported,
+                Err(..) => continue,
+            };
+
+            match stream {
+                Stream::Playback => playback = true,
+                Stream::Capture => capture = true,
+            }
+
+            for format in supported.into_iter().filter_map(Format::sample_format) {
+                if !formats.contains(&format) {
+                    formats.push(format);
+                }
+            }
+        }
+
+        // NB: a device which couldn't be opened at all is still listed, and
+        // since DSP devices nearly always support playback it's listed as
+        // such.
+        let direction = match (playback, capture) {
+            (true, true) => Direction::Duplex,
+            (false, true) => Direction::Input,
+            _ => Direction::Output,
+        };
+
+        let id = path.to_string_lossy().into_owned();
+
+        infos.push(DeviceInfo {
+            is_default: path == Path::new(DEFAULT_PATH),
+            name: id.clone(),
+            id,
+            direction,
+            formats,
+            channels: None,
+            sample_rates: None,
+        });
+    }
+
+    Ok(infos)
+}
+
+/// An opened OSS DSP device, like `/dev/dsp`.
+///
+/// The device is configured by setting its format, channels and rate, in that
+/// order. Each setting returns what the device actually picked, which might
+/// differ from what was requested.
+///
+/// See [Dsp::open].
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::oss;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let mut dsp = oss::Dsp::open_default(oss::Stream::Playback)?;
+/// dsp.set_format(oss::Format::S16LE)?;
+/// let channels = dsp.set_channels(2)?;
+/// let rate = dsp.set_rate(48000)?;
+///
+/// let samples = vec![0i16; 1024 * channels as usize];
+///
+/// let mut writer = dsp.writer::<i16>()?;
+/// writer.write_interleaved(audio::wrap::interleaved(&samples[..], channels as usize))?;
+/// # Ok(()) }
+/// ```
+pub struct Dsp {
+    pub(super) fd: c::c_int,
+    stream: Stream,
+    format: Option<Format>,
+    channels: u32,
+    rate: u32,
+}
+
+impl Dsp {
+    /// Open the DSP device at the given path.
+    pub fn open<P>(path: P, stream: Stream) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let path = CString::new(path.as_ref().as_os_str().as_bytes())
+            .map_err(|e| Error::Io(io::Error::new(io::ErrorKind::InvalidInput, e)))?;
+
+        let flags = match stream {
+            Stream::Playback => c::O_WRONLY,
+            Stream::Capture => c::O_RDONLY,
+        };
+
+        let fd = unsafe { c::open(path.as_ptr(), flags | c::O_CLOEXEC) };
+
+        if fd < 0 {
+            return Err(Error::Io(io::Error::last_os_error()));
+        }
+
+        let mut dsp = Self {
+            fd,
+            stream,
+            format: None,
+            channels: 1,
+            rate: 8000,
+        };
+
+        // NB: the device starts out with an implementation defined format,
+        // which we query by passing in a format of zero.
+        let format = dsp.ioctl_int(ioctl::SETFMT, 0)?;
+        dsp.format = Format::from_value(format);
+        Ok(dsp)
+    }
+
+    /// Open the default DSP device, which is `/dev/dsp`.
+    pub fn open_default(stream: Stream) -> Result<Self> {
+        Self::open(DEFAULT_PATH, stream)
+    }
+
+    /// The direction the device is opened in.
+    pub fn stream(&self) -> Stream {
+        self.stream
+    }
+
+    /// The formats supported by the device.
+    pub fn formats(&mut self) -> Result<Vec<Format>> {
+        let mask = self.ioctl_int(ioctl::GETFMTS, 0)?;
+        Ok(Format::from_mask(mask))
+    }
+
+    /// Set the sample format, returning the format picked by the device.
+    pub fn set_format(&mut self, format: Format) -> Result<Format> {
+        let value = self.ioctl_int(ioctl::SETFMT, format as c::c_int)?;
+        let format = Format::from_value(value).ok_or(Error::BadFormat(value))?;
+        self.format = Some(format);
+        Ok(format)
+    }
+
+    /// Set the number of channels, returning the number of channels picked by
+    /// the device.
+    pub fn set_channels(&mut self, channels: u32) -> Result<u32> {
+        self.channels = self.ioctl_int(ioctl::CHANNELS, channels as c::c_int)? as u32;
+        Ok(self.channels)
+    }
+
+    /// Set the sample rate, returning the rate picked by the device.
+    pub fn set_rate(&mut self, rate: u32) -> Result<u32> {
+        self.rate = self.ioctl_int(ioctl::SPEED, rate as c::c_int)? as u32;
+        Ok(self.rate)
+    }
+
+    /// The configured sample format, unless the device uses a format which
+    /// isn't supported by this crate.
+    pub fn format(&self) -> Option<Format> {
+        self.format
+    }
+
+    /// The configured number of channels.
+    pub fn channels(&self) -> u32 {
+        self.channels
+    }
+
+    /// The configured sample rate.
+    pub fn rate(&self) -> u32 {
+        self.rate
+    }
+
+    /// Set the device in nonblocking mode, in which reads and writes which
+    /// would block fail with `EWOULDBLOCK` instead.
+    pub fn set_nonblocking(&mut self, nonblocking: bool) -> Result<()> {
+        unsafe {
+            let flags = c::fcntl(self.fd, c::F_GETFL);
+
+            if flags < 0 {
+                return Err(Error::Io(io::Error::last_os_error()));
+            }
+
+            let flags = if nonblocking {
+                flags | c::O_NONBLOCK
+            } else {
+                flags & !c::O_NONBLOCK
+            };
+
+            if c::fcntl(self.fd, c::F_SETFL, flags) < 0 {
+                return Err(Error::Io(io::Error::last_os_error()));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The number of frames which can be written or read without blocking.
+    pub fn available(&mut self) -> Result<usize> {
+        let request = match self.stream {
+            Stream::Playback => ioctl::GETOSPACE,
+            Stream::Capture => ioctl::GETISPACE,
+        };
+
+        let mut info = ioctl::AudioBufInfo::default();
+
+        unsafe {
+            self.ioctl(request, &mut info as *mut _ as *mut c::c_void)?;
+        }
+
+        Ok(self.bytes_to_frames(info.bytes))
+    }
+
+    /// Get the current playback delay in frames, which is the number of frames
+    /// which have to pass before a frame written now is heard.
+    pub fn delay(&mut self) -> Result<usize> {
+        self.ensure_stream(Stream::Playback)?;
+        let bytes = self.ioctl_int(ioctl::GETODELAY, 0)?;
+        Ok(self.bytes_to_frames(bytes))
+    }
+
+    /// Block until all written frames have been played.
+    pub fn sync(&mut self) -> Result<()> {
+        unsafe { self.ioctl(ioctl::SYNC, std::ptr::null_mut()) }
+    }
+
+    /// Immediately stop the device, discarding any frames which haven't been
+    /// played or read yet.
+    pub fn reset(&mut self) -> Result<()> {
+        unsafe { self.ioctl(ioctl::RESET, std::ptr::null_mut()) }
+    }
+
+    /// Construct a checked blocking writer for the given sample type.
+    ///
+    /// Errors with [Error::FormatMismatch] if the sample is not appropriate for
+    /// the configured format.
+    pub fn writer<T>(&mut self) -> Result<Writer<'_, T>>
+    where
+        T: Sample,
+    {
+        self.ensure_stream(Stream::Playback)?;
+        self.ensure_sample::<T>()?;
+        let channels = self.channels as usize;
+        Ok(unsafe { Writer::new(self, channels) })
+    }
+
+    /// Construct a checked blocking reader for the given sample type.
+    ///
+    /// Errors with [Error::FormatMismatch] if the sample is not appropriate for
+    /// the configured format.
+    pub fn reader<T>(&mut self) -> Result<Reader<'_, T>>
+    where
+        T: Sample,
+    {
+        self.ensure_stream(Stream::Capture)?;
+        self.ensure_sample::<T>()?;
+        let channels = self.channels as usize;
+        Ok(unsafe { Reader::new(self, channels) })
+    }
+
+    cfg_poll_driver! {
+        /// Construct a checked async writer for the given sample type, which
+        /// puts the device in nonblocking mode.
+        ///
+        /// # Panics
+        ///
+        /// Panics unless called inside of the audio runtime.
+        ///
+        /// # Examples
+        ///
+        /// ```no_run
+        /// use audio_device::oss;
+        ///
+        /// async fn play() -> anyhow::Result<()> {
+        ///     let mut dsp = oss::Dsp::open_default(oss::Stream::Playback)?;
+        ///     dsp.set_format(oss::Format::S16LE)?;
+        ///     dsp.set_channels(2)?;
+        ///     dsp.set_rate(48000)?;
+        ///
+        ///     let samples = [0i16; 2048];
+        ///
+        ///     let mut writer = dsp.async_writer::<i16>()?;
+        ///     writer.write_interleaved(audio::wrap::interleaved(&samples[..], 2)).await?;
+        ///     Ok(())
+        /// }
+        ///
+        /// # #[tokio::main] async fn main() -> anyhow::Result<()> {
+        /// let runtime = audio_device::runtime::Runtime::new()?;
+        /// let bg = ste::spawn();
+        /// bg.submit_async(runtime.wrap(play())).await?;
+        /// bg.join();
+        /// # Ok(()) }
+        /// ```
+        pub fn async_writer<T>(&mut self) -> Result<crate::oss::AsyncWriter<'_, T>>
+        where
+            T: Sample,
+        {
+            self.ensure_stream(Stream::Playback)?;
+            self.ensure_sample::<T>()?;
+            self.set_nonblocking(true)?;
+            let channels = self.channels as usize;
+            unsafe { crate::oss::AsyncWriter::new(self, channels) }
+        }
+
+        /// Construct a checked async reader for the given sample type, which
+        /// puts the device in nonblocking mode.
+        ///
+        /// # Panics
+        ///
+        /// Panics unless called inside of the audio runtime.
+        pub fn async_reader<T>(&mut self) -> Result<crate::oss::AsyncReader<'_, T>>
+        where
+            T: Sample,
+        {
+            self.ensure_stream(Stream::Capture)?;
+            self.ensure_sample::<T>()?;
+            self.set_nonblocking(true)?;
+            let channels = self.channels as usize;
+            unsafe { crate::oss::AsyncReader::new(self, channels) }
+        }
+    }
+
+    /// Write raw bytes to the device, returning the number of bytes written.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must be valid for reads of `len` bytes.
+    pub(super) unsafe fn write_raw(&mut self, ptr: *const c::c_void, len: usize) -> Result<usize> {
+        let result = c::write(self.fd, ptr, len);
+
+        if result < 0 {
+            return Err(last_errno());
+        }
+
+        Ok(result as usize)
+    }
+
+    /// Read raw bytes from the device, returning the number of bytes read.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must be valid for writes of `len` bytes.
+    pub(super) unsafe fn read_raw(&mut self, ptr: *mut c::c_void, len: usize) -> Result<usize> {
+        let result = c::read(self.fd, ptr, len);
+
+        if result < 0 {
+            return Err(last_errno());
+        }
+
+        Ok(result as usize)
+    }
+
+    fn ensure_stream(&self, expected: Stream) -> Result<()> {
+        if self.stream != expected {
+            return Err(Error::StreamMismatch {
+                actual: self.stream,
+                expected,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn ensure_sample<T>(&self) -> Result<()>
+    where
+        T: Sample,
+    {
+        // NB: here we check that `T` is appropriate for the current format.
+        match self.format {
+            Some(format) if T::test(format) => Ok(()),
+            Some(format) => Err(Error::FormatMismatch {
+                ty: T::describe(),
+                format,
+            }),
+            None => Err(Error::BadFormat(self.ioctl_query_format()?)),
+        }
+    }
+
+    fn ioctl_query_format(&self) -> Result<c::c_int> {
+        let mut value = 0;
+
+        unsafe {
+            self.ioctl(ioctl::SETFMT, &mut value as *mut c::c_int as *mut c::c_void)?;
+        }
+
+        Ok(value)
+    }
+
+    fn bytes_to_frames(&self, bytes: c::c_int) -> usize {
+        let size =
+            self.format.map(Format::sample_size).unwrap_or(1) * self.channels.max(1) as usize;
+        bytes.max(0) as usize / size
+    }
+
+    /// Perform an ioctl which takes and returns an integer.
+    fn ioctl_int(&mut self, request: c::c_ulong, value: c::c_int) -> Result<c::c_int> {
+        let mut value = value;
+
+        unsafe {
+            self.ioctl(request, &mut value as *mut c::c_int as *mut c::c_void)?;
+        }
+
+        Ok(value)
+    }
+
+    unsafe fn ioctl(&self, request: c::c_ulong, arg: *mut c::c_void) -> Result<()> {
+        // NB: the type of the request differs between libc implementations.
+        if c::ioctl(self.fd, request as _, arg) < 0 {
+            return Err(last_errno());
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for Dsp {
+    fn drop(&mut self) {
+        unsafe {
+            c::close(self.fd);
+        }
+    }
+}
+
+fn last_errno() -> Error {
+    let errno = io::Error::last_os_error()
+        .raw_os_error()
+        .unwrap_or_default();
+    Error::Sys(Errno::new(errno))
+}
@@ -0,0 +1,95 @@
+use crate::libc as c;
+use audio_core::{Endian, SampleFormat};
+use std::fmt;
+
+/// A sample format supported by an OSS device, as identified by its `AFMT_*`
+/// value.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+#[repr(i32)]
+pub enum Format {
+    /// Unsigned 8-bit samples.
+    U8 = 0x00000008,
+    /// Signed 16-bit little endian samples.
+    S16LE = 0x00000010,
+    /// Signed 16-bit big endian samples.
+    S16BE = 0x00000020,
+    /// Signed 8-bit samples.
+    S8 = 0x00000040,
+    /// Signed 32-bit little endian samples.
+    S32LE = 0x00001000,
+    /// Signed 32-bit big endian samples.
+    S32BE = 0x00002000,
+}
+
+impl Format {
+    const ALL: [Self; 6] = [
+        Self::U8,
+        Self::S16LE,
+        Self::S16BE,
+        Self::S8,
+        Self::S32LE,
+        Self::S32BE,
+    ];
+
+    /// Parse the given format from its `AFMT_*` value.
+    pub fn from_value(value: c::c_int) -> Option<Self> {
+        Self::ALL.iter().copied().find(|f| *f as c::c_int == value)
+    }
+
+    /// Get all formats which are set in the given `AFMT_*` mask.
+    pub(super) fn from_mask(mask: c::c_int) -> Vec<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .filter(|f| mask & *f as c::c_int != 0)
+            .collect()
+    }
+
+    /// Get the backend-agnostic [SampleFormat] corresponding to this format,
+    /// if there is one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio_core::{Endian, SampleFormat};
+    /// use audio_device::oss;
+    ///
+    /// assert_eq!(oss::Format::S16LE.sample_format(), Some(SampleFormat::I16(Endian::Little)));
+    /// assert_eq!(oss::Format::S8.sample_format(), None);
+    /// ```
+    pub fn sample_format(self) -> Option<SampleFormat> {
+        Some(match self {
+            Self::U8 => SampleFormat::U8,
+            Self::S16LE => SampleFormat::I16(Endian::Little),
+            Self::S16BE => SampleFormat::I16(Endian::Big),
+            Self::S32LE => SampleFormat::I32(Endian::Little),
+            Self::S32BE => SampleFormat::I32(Endian::Big),
+            Self::S8 => return None,
+        })
+    }
+
+    /// The size of a single sample in bytes.
+    pub fn sample_size(self) -> usize {
+        match self {
+            Self::U8 | Self::S8 => 1,
+            Self::S16LE | Self::S16BE => 2,
+            Self::S32LE | Self::S32BE => 4,
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let id = match self {
+            Self::U8 => "U8",
+            Self::S16LE => "S16LE",
+            Self::S16BE => "S16BE",
+            Self::S8 => "S8",
+            Self::S32LE => "S32LE",
+            Self::S32BE => "S32BE",
+        };
+
+        f.write_str(id)
+    }
+}
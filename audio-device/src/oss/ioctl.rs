@@ -0,0 +1,60 @@
+//! Request numbers for the `SNDCTL_*` ioctls, which are encoded differently
+//! on Linux and the BSDs.
+
+use crate::libc as c;
+use std::mem;
+
+#[cfg(target_os = "linux")]
+const fn encode(inout: c::c_ulong, group: u8, num: u8, len: usize) -> c::c_ulong {
+    // NB: on Linux the direction is 0 for none, 1 for write and 2 for read.
+    let dir = match inout {
+        IN => 1,
+        OUT => 2,
+        INOUT => 3,
+        _ => 0,
+    };
+
+    (dir << 30) | ((len as c::c_ulong) << 16) | ((group as c::c_ulong) << 8) | num as c::c_ulong
+}
+
+#[cfg(not(target_os = "linux"))]
+const fn encode(inout: c::c_ulong, group: u8, num: u8, len: usize) -> c::c_ulong {
+    let inout = match inout {
+        IN => 0x80000000,
+        OUT => 0x40000000,
+        INOUT => 0xc0000000,
+        _ => 0x20000000,
+    };
+
+    inout
+        | (((len as c::c_ulong) & 0x1fff) << 16)
+        | ((group as c::c_ulong) << 8)
+        | num as c::c_ulong
+}
+
+const VOID: c::c_ulong = 0;
+const IN: c::c_ulong = 1;
+const OUT: c::c_ulong = 2;
+const INOUT: c::c_ulong = 3;
+
+const INT: usize = mem::size_of::<c::c_int>();
+
+/// The `audio_buf_info` structure returned by [GETOSPACE] and [GETISPACE].
+#[repr(C)]
+#[derive(Default)]
+pub(super) struct AudioBufInfo {
+    pub(super) fragments: c::c_int,
+    pub(super) fragstotal: c::c_int,
+    pub(super) fragsize: c::c_int,
+    pub(super) bytes: c::c_int,
+}
+
+pub(super) const RESET: c::c_ulong = encode(VOID, b'P', 0, 0);
+pub(super) const SYNC: c::c_ulong = encode(VOID, b'P', 1, 0);
+pub(super) const SPEED: c::c_ulong = encode(INOUT, b'P', 2, INT);
+pub(super) const SETFMT: c::c_ulong = encode(INOUT, b'P', 5, INT);
+pub(super) const CHANNELS: c::c_ulong = encode(INOUT, b'P', 6, INT);
+pub(super) const GETFMTS: c::c_ulong = encode(OUT, b'P', 11, INT);
+pub(super) const GETOSPACE: c::c_ulong = encode(OUT, b'P', 12, mem::size_of::<AudioBufInfo>());
+pub(super) const GETISPACE: c::c_ulong = encode(OUT, b'P', 13, mem::size_of::<AudioBufInfo>());
+pub(super) const GETODELAY: c::c_ulong = encode(OUT, b'P', 23, INT);
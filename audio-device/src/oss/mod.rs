@@ -0,0 +1,97 @@
+//! An idiomatic Rust OSS interface, as used by FreeBSD and other systems which
+//! provide the Open Sound System.
+// Documentation: http://manuals.opensound.com/developer/
+
+use crate::unix::Errno;
+use std::io;
+use thiserror::Error;
+
+mod ioctl;
+
+mod dsp;
+pub(crate) use self::dsp::device_infos;
+pub use self::dsp::{devices, Dsp, Stream};
+
+mod format;
+pub use self::format::Format;
+
+mod sample;
+pub use self::sample::Sample;
+
+mod writer;
+pub use self::writer::Writer;
+
+mod reader;
+pub use self::reader::Reader;
+
+cfg_poll_driver! {
+    mod async_writer;
+    pub use self::async_writer::AsyncWriter;
+
+    mod async_reader;
+    pub use self::async_reader::AsyncReader;
+}
+
+/// Errors that can be raised by the OSS layer.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// System error.
+    #[error("system error: {0}")]
+    Sys(#[from] Errno),
+    /// I/O error.
+    #[error("i/o error: {0}")]
+    Io(
+        #[source]
+        #[from]
+        io::Error,
+    ),
+    /// An error raised by the audio runtime, like when it has been shut down.
+    #[error("runtime error: {0}")]
+    Runtime(#[from] crate::runtime::Error),
+    /// Error raised when there's a format mismatch between the device and the
+    /// type attempting to be used with it.
+    #[error("type `{ty}` is not appropriate to use with format `{format}`")]
+    FormatMismatch {
+        /// A description of the type expected.
+        ty: &'static str,
+        /// The format that mismatched.
+        format: Format,
+    },
+    /// Error raised when there's a channel count mismatch between the device
+    /// and the buffer attempting to be used with it.
+    #[error("mismatch in number of channels in buffer; actual = {actual}, expected = {expected}")]
+    ChannelsMismatch {
+        /// The actual number of channels.
+        actual: usize,
+        /// The expected number of channels.
+        expected: usize,
+    },
+    /// Error raised when a device is used in a direction it wasn't opened for.
+    #[error("device is opened for `{actual}`, expected `{expected}`")]
+    StreamMismatch {
+        /// The stream the device was opened for.
+        actual: Stream,
+        /// The stream which was expected.
+        expected: Stream,
+    },
+    /// The device configured a format which isn't supported by this crate.
+    #[error("bad format identifier ({0})")]
+    BadFormat(crate::libc::c_int),
+}
+
+impl Error {
+    pub(crate) fn kind(&self) -> crate::ErrorKind {
+        use crate::ErrorKind;
+
+        match self {
+            Self::Sys(errno) => errno.kind(),
+            Self::Io(error) => crate::error::io_error_kind(error),
+            Self::Runtime(error) => error.kind(),
+            Self::FormatMismatch { .. } | Self::BadFormat(..) => ErrorKind::UnsupportedFormat,
+            Self::ChannelsMismatch { .. } | Self::StreamMismatch { .. } => ErrorKind::InvalidInput,
+        }
+    }
+}
+
+/// Helper result wrapper.
+pub type Result<T, E = Error> = ::std::result::Result<T, E>;
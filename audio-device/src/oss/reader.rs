@@ -0,0 +1,79 @@
+use core::marker;
+use std::io;
+
+use crate::buf_io;
+use crate::libc as c;
+use crate::oss::{Dsp, Error, Result};
+
+/// An interleaved type-checked blocking reader for a [Dsp].
+///
+/// See [Dsp::reader].
+pub struct Reader<'a, T> {
+    dsp: &'a mut Dsp,
+    channels: usize,
+    _marker: marker::PhantomData<T>,
+}
+
+impl<'a, T> Reader<'a, T> {
+    /// Construct a new reader surrounding the given device.
+    ///
+    /// # Safety
+    ///
+    /// This constructor assumes that the caller has checked that type `T` is
+    /// appropriate for reading from the given device.
+    pub(super) unsafe fn new(dsp: &'a mut Dsp, channels: usize) -> Self {
+        Self {
+            dsp,
+            channels,
+            _marker: marker::PhantomData,
+        }
+    }
+
+    /// Read into an interleaved buffer, blocking until its remaining frames
+    /// have been filled with captured audio.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio::WriteBuf;
+    /// use audio_device::oss;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut dsp = oss::Dsp::open_default(oss::Stream::Capture)?;
+    /// dsp.set_format(oss::Format::S16LE)?;
+    /// dsp.set_channels(2)?;
+    ///
+    /// let mut samples = [0i16; 2048];
+    /// let mut buf = audio::wrap::interleaved(&mut samples[..], 2);
+    ///
+    /// let mut reader = dsp.reader::<i16>()?;
+    /// reader.read_interleaved(&mut buf)?;
+    /// assert!(!buf.has_remaining_mut());
+    /// # Ok(()) }
+    /// ```
+    pub fn read_interleaved<B>(&mut self, mut buf: B) -> Result<()>
+    where
+        B: audio_core::Buf<Sample = T>
+            + audio_core::WriteBuf
+            + audio_core::InterleavedBufMut<Sample = T>,
+    {
+        if buf.channels() != self.channels {
+            return Err(Error::ChannelsMismatch {
+                actual: buf.channels(),
+                expected: self.channels,
+            });
+        }
+
+        let dsp = &mut *self.dsp;
+
+        buf_io::read(&mut buf, self.channels, |ptr, len| {
+            let n = unsafe { dsp.read_raw(ptr as *mut c::c_void, len)? };
+
+            if n == 0 {
+                return Err(Error::Io(io::ErrorKind::UnexpectedEof.into()));
+            }
+
+            Ok(n)
+        })
+    }
+}
@@ -0,0 +1,71 @@
+use crate::oss::Format;
+
+/// Trait used to designate types which are sample-appropriate for a
+/// [Dsp][super::Dsp].
+///
+/// Native types are appropriate for formats in the native endianness.
+///
+/// # Examples
+///
+/// ```
+/// use audio_device::oss::{Format, Sample};
+///
+/// assert!(u8::test(Format::U8));
+/// assert!(!i8::test(Format::U8));
+///
+/// #[cfg(target_endian = "little")]
+/// assert!(i16::test(Format::S16LE));
+/// ```
+///
+/// # Safety
+///
+/// This trait is unsafe to implement, because an incorrectly implemented format
+/// test might have safety implications.
+pub unsafe trait Sample {
+    /// The default format to use for this sample.
+    const DEFAULT_FORMAT: Format;
+
+    /// Test if the given format is appropriate for this sample type.
+    fn test(format: Format) -> bool {
+        format == Self::DEFAULT_FORMAT
+    }
+
+    /// A static description of the sample type.
+    fn describe() -> &'static str;
+}
+
+macro_rules! implement {
+    ($ty:ty, $format:ident) => {
+        unsafe impl Sample for $ty {
+            const DEFAULT_FORMAT: Format = Format::$format;
+
+            fn describe() -> &'static str {
+                stringify!($ty)
+            }
+        }
+    };
+
+    ($ty:ty, $le:ident, $be:ident) => {
+        unsafe impl Sample for $ty {
+            #[cfg(target_endian = "little")]
+            const DEFAULT_FORMAT: Format = Format::$le;
+            #[cfg(target_endian = "big")]
+            const DEFAULT_FORMAT: Format = Format::$be;
+
+            #[cfg(target_endian = "little")]
+            fn describe() -> &'static str {
+                concat!(stringify!($ty), " (little endian)")
+            }
+
+            #[cfg(target_endian = "big")]
+            fn describe() -> &'static str {
+                concat!(stringify!($ty), " (big endian)")
+            }
+        }
+    };
+}
+
+implement!(u8, U8);
+implement!(i8, S8);
+implement!(i16, S16LE, S16BE);
+implement!(i32, S32LE, S32BE);
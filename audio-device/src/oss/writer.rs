@@ -0,0 +1,67 @@
+use core::marker;
+use std::io;
+
+use crate::buf_io;
+use crate::libc as c;
+use crate::oss::{Dsp, Error, Result};
+
+/// An interleaved type-checked blocking writer for a [Dsp].
+///
+/// See [Dsp::writer].
+pub struct Writer<'a, T> {
+    dsp: &'a mut Dsp,
+    channels: usize,
+    _marker: marker::PhantomData<T>,
+}
+
+impl<'a, T> Writer<'a, T> {
+    /// Construct a new writer surrounding the given device.
+    ///
+    /// # Safety
+    ///
+    /// This constructor assumes that the caller has checked that type `T` is
+    /// appropriate for writing to the given device.
+    pub(super) unsafe fn new(dsp: &'a mut Dsp, channels: usize) -> Self {
+        Self {
+            dsp,
+            channels,
+            _marker: marker::PhantomData,
+        }
+    }
+
+    /// Get the current playback delay in frames.
+    ///
+    /// See [Dsp::delay].
+    pub fn delay(&mut self) -> Result<usize> {
+        self.dsp.delay()
+    }
+
+    /// Write an interleaved buffer, blocking until all of its frames have been
+    /// accepted by the device.
+    pub fn write_interleaved<B>(&mut self, mut buf: B) -> Result<()>
+    where
+        B: audio_core::Buf<Sample = T>
+            + audio_core::ReadBuf
+            + audio_core::ExactSizeBuf
+            + audio_core::InterleavedBuf,
+    {
+        if buf.channels() != self.channels {
+            return Err(Error::ChannelsMismatch {
+                actual: buf.channels(),
+                expected: self.channels,
+            });
+        }
+
+        let dsp = &mut *self.dsp;
+
+        buf_io::write(&mut buf, self.channels, |ptr, len| {
+            let n = unsafe { dsp.write_raw(ptr as *const c::c_void, len)? };
+
+            if n == 0 {
+                return Err(Error::Io(io::ErrorKind::WriteZero.into()));
+            }
+
+            Ok(n)
+        })
+    }
+}
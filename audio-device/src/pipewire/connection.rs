@@ -0,0 +1,191 @@
+use std::io;
+use std::ptr;
+use std::sync::Once;
+use std::task::{Context, Poll};
+
+use pipewire_sys as pw;
+
+use crate::libc as c;
+use crate::pipewire::{Error, Result};
+use crate::unix::{AsyncPoll, Errno};
+
+/// A connection to the default PipeWire server, with a loop which is either
+/// driven by the audio runtime or by [blocking][Connection::wait].
+pub(super) struct Connection {
+    pub(super) core: ptr::NonNull<pw::pw_core>,
+    context: ptr::NonNull<pw::pw_context>,
+    // NB: registered the first time the connection is polled, so that
+    // connections which are only waited on don't need a runtime.
+    poll: Option<AsyncPoll>,
+    main: Loop,
+}
+
+impl Connection {
+    /// Connect to the default PipeWire server.
+    pub(super) fn connect() -> Result<Self> {
+        static INIT: Once = Once::new();
+
+        INIT.call_once(|| unsafe {
+            pw::pw_init(ptr::null_mut(), ptr::null_mut());
+        });
+
+        unsafe {
+            let main = Loop::new()?;
+
+            let context = pw::pw_context_new(main.handle.as_ptr(), ptr::null_mut(), 0);
+            let context = ptr::NonNull::new(context).ok_or_else(last_error)?;
+
+            let core = pw::pw_context_connect(context.as_ptr(), ptr::null_mut(), 0);
+
+            let core = match ptr::NonNull::new(core) {
+                Some(core) => core,
+                None => {
+                    let error = last_error();
+                    pw::pw_context_destroy(context.as_ptr());
+                    return Err(error);
+                }
+            };
+
+            Ok(Self {
+                core,
+                context,
+                poll: None,
+                main,
+            })
+        }
+    }
+
+    /// Dispatch all events which are ready without blocking.
+    pub(super) fn dispatch(&mut self) -> Result<()> {
+        unsafe { self.main.iterate(0) }
+    }
+
+    /// Block until there are events to dispatch, and dispatch them.
+    pub(super) fn wait(&mut self) -> Result<()> {
+        unsafe { self.main.iterate(-1) }
+    }
+
+    /// Poll for the loop to have events to dispatch.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless called inside of the audio runtime.
+    pub(super) fn poll_events(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let poll = match &mut self.poll {
+            Some(poll) => poll,
+            poll @ None => {
+                let pollfd = c::pollfd {
+                    fd: unsafe { self.main.fd()? },
+                    events: c::POLLIN,
+                    revents: 0,
+                };
+
+                // Safety: the descriptor belongs to the loop, which outlives
+                // the registration.
+                poll.insert(unsafe { AsyncPoll::new(pollfd)? })
+            }
+        };
+
+        // NB: the guard is dropped right away, which re-arms the descriptor
+        // before the loop is iterated again.
+        match poll.poll_returned_events(cx) {
+            Poll::Ready(guard) => {
+                guard?;
+                Poll::Ready(Ok(()))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        unsafe {
+            pw::pw_core_disconnect(self.core.as_ptr());
+            pw::pw_context_destroy(self.context.as_ptr());
+        }
+    }
+}
+
+// Safety: the connection exclusively owns its loop, which is only ever
+// iterated through a mutable reference to it.
+unsafe impl Send for Connection {}
+
+/// A PipeWire loop which is iterated without blocking.
+struct Loop {
+    handle: ptr::NonNull<pw::pw_loop>,
+}
+
+impl Loop {
+    fn new() -> Result<Self> {
+        let handle = unsafe { pw::pw_loop_new(ptr::null()) };
+
+        Ok(Self {
+            handle: ptr::NonNull::new(handle).ok_or_else(last_error)?,
+        })
+    }
+
+    /// Get the file descriptor which becomes readable when the loop has
+    /// events to dispatch.
+    unsafe fn fd(&self) -> Result<c::c_int> {
+        let (methods, data) = self.control();
+
+        match (*methods).get_fd {
+            Some(get_fd) => check(get_fd(data)),
+            None => Err(Error::Sys(Errno::new(libc::ENOTSUP))),
+        }
+    }
+
+    /// Dispatch all events which are ready, waiting for up to `timeout`
+    /// milliseconds for there to be any. A negative timeout waits forever.
+    unsafe fn iterate(&mut self, timeout: c::c_int) -> Result<()> {
+        let (methods, data) = self.control();
+
+        if let (Some(enter), Some(iterate), Some(leave)) =
+            ((*methods).enter, (*methods).iterate, (*methods).leave)
+        {
+            enter(data);
+            let result = iterate(data, timeout);
+            leave(data);
+
+            if result < 0 && result != -libc::EINTR {
+                return Err(Error::Sys(Errno::new(-result)));
+            }
+        }
+
+        Ok(())
+    }
+
+    unsafe fn control(&self) -> (*const pw::spa_loop_control_methods, *mut c::c_void) {
+        let iface = &(*(*self.handle.as_ptr()).control).iface;
+        (
+            iface.cb.funcs as *const pw::spa_loop_control_methods,
+            iface.cb.data,
+        )
+    }
+}
+
+impl Drop for Loop {
+    fn drop(&mut self) {
+        unsafe {
+            pw::pw_loop_destroy(self.handle.as_ptr());
+        }
+    }
+}
+
+/// Convert a negative errno result into an error.
+pub(super) fn check(result: c::c_int) -> Result<c::c_int> {
+    if result < 0 {
+        return Err(Error::Sys(Errno::new(-result)));
+    }
+
+    Ok(result)
+}
+
+/// Get the error of the last failed PipeWire call.
+pub(super) fn last_error() -> Error {
+    let errno = io::Error::last_os_error()
+        .raw_os_error()
+        .unwrap_or_default();
+    Error::Sys(Errno::new(errno))
+}
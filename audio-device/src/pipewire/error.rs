@@ -0,0 +1,30 @@
+use crate::unix::Errno;
+use crate::ErrorKind;
+use thiserror::Error;
+
+/// Errors that can be raised by the PipeWire layer.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// System error.
+    #[error("system error: {0}")]
+    Sys(#[from] Errno),
+    /// An error raised by the audio runtime, like when it has been shut down.
+    #[error("runtime error: {0}")]
+    Runtime(#[from] crate::runtime::Error),
+    /// The filter failed with the given message.
+    #[error("filter error: {0}")]
+    Filter(String),
+}
+
+impl Error {
+    pub(crate) fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Sys(errno) => errno.kind(),
+            Self::Runtime(error) => error.kind(),
+            Self::Filter(..) => ErrorKind::Other,
+        }
+    }
+}
+
+/// Helper result wrapper.
+pub type Result<T, E = Error> = ::std::result::Result<T, E>;
@@ -0,0 +1,387 @@
+use std::ffi::{CStr, CString};
+use std::future;
+use std::mem;
+use std::ptr;
+use std::task::{Context, Poll};
+
+use pipewire_sys as pw;
+
+use crate::libc as c;
+use crate::pipewire::connection::{check, last_error, Connection};
+use crate::pipewire::{Error, Pod, PortBuffer, PortBufferMut, Result};
+
+/// The id used by PipeWire to indicate that an object is missing.
+const INVALID_ID: u32 = u32::MAX;
+/// The version of the filter events in use.
+const VERSION_FILTER_EVENTS: u32 = 0;
+
+const KEY_MEDIA_TYPE: &[u8] = b"media.type\0";
+const KEY_MEDIA_CATEGORY: &[u8] = b"media.category\0";
+const KEY_MEDIA_ROLE: &[u8] = b"media.role\0";
+const KEY_FORMAT_DSP: &[u8] = b"format.dsp\0";
+const KEY_PORT_NAME: &[u8] = b"port.name\0";
+
+const MEDIA_TYPE_AUDIO: &[u8] = b"Audio\0";
+const MEDIA_CATEGORY_FILTER: &[u8] = b"Filter\0";
+const MEDIA_ROLE_DSP: &[u8] = b"DSP\0";
+const FORMAT_DSP_F32: &[u8] = b"32 bit float mono audio\0";
+
+static FILTER_EVENTS: pw::pw_filter_events = pw::pw_filter_events {
+    version: VERSION_FILTER_EVENTS,
+    destroy: None,
+    state_changed: Some(state_changed_callback),
+    io_changed: None,
+    param_changed: None,
+    add_buffer: None,
+    remove_buffer: None,
+    process: Some(process_callback),
+    drained: None,
+};
+
+/// The state of a [Filter].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FilterState {
+    /// The filter is in an error state.
+    Error,
+    /// The filter is not connected.
+    Unconnected,
+    /// The filter is being connected.
+    Connecting,
+    /// The filter is connected but not processing.
+    Paused,
+    /// The filter is processing audio.
+    Streaming,
+}
+
+impl FilterState {
+    fn from_value(value: pw::pw_filter_state) -> Self {
+        match value {
+            pw::PW_FILTER_STATE_UNCONNECTED => Self::Unconnected,
+            pw::PW_FILTER_STATE_CONNECTING => Self::Connecting,
+            pw::PW_FILTER_STATE_PAUSED => Self::Paused,
+            pw::PW_FILTER_STATE_STREAMING => Self::Streaming,
+            _ => Self::Error,
+        }
+    }
+}
+
+/// A processing node inserted into the PipeWire graph, like an effect.
+///
+/// The filter has a number of input and output ports, each of which carries a
+/// single channel of 32-bit float audio. Once per graph cycle the process
+/// callback is called with the audio of the input ports as a [PortBuffer],
+/// and is expected to fill the output ports through a [PortBufferMut].
+///
+/// The filter is driven by the audio [Runtime][crate::runtime::Runtime]
+/// through [Filter::run], which is also where the process callback is called.
+///
+/// See [Filter::connect].
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio::{Buf, BufMut, Channel, ChannelMut};
+/// use audio_device::pipewire;
+/// use std::ffi::CString;
+///
+/// async fn gain() -> anyhow::Result<()> {
+///     let name = CString::new("gain")?;
+///
+///     let mut filter = pipewire::Filter::connect(&name, 2, 2, |input, mut output| {
+///         for (from, mut to) in input.iter_channels().zip(output.iter_channels_mut()) {
+///             for (from, to) in from.iter().zip(to.iter_mut()) {
+///                 *to = from * 0.5;
+///             }
+///         }
+///     })?;
+///
+///     filter.run().await?;
+///     Ok(())
+/// }
+///
+/// # #[tokio::main] async fn main() -> anyhow::Result<()> {
+/// let runtime = audio_device::runtime::Runtime::new()?;
+/// let bg = ste::spawn();
+/// bg.submit_async(runtime.wrap(gain())).await?;
+/// bg.join();
+/// # Ok(()) }
+/// ```
+pub struct Filter {
+    filter: ptr::NonNull<pw::pw_filter>,
+    connection: Connection,
+    // NB: registered with the filter, so it must have a stable address and
+    // outlive it.
+    listener: Box<Listener>,
+}
+
+impl Filter {
+    /// Connect a new filter with the given node name and number of input and
+    /// output ports to the default PipeWire server.
+    ///
+    /// The `process` callback is called once per graph cycle with the audio
+    /// of the input ports, and the buffer of the output ports to fill.
+    pub fn connect<F>(name: &CStr, inputs: usize, outputs: usize, process: F) -> Result<Self>
+    where
+        F: 'static + Send + FnMut(PortBuffer<'_>, PortBufferMut<'_>),
+    {
+        let connection = Connection::connect()?;
+
+        unsafe {
+            let props = pw::pw_properties_new(
+                KEY_MEDIA_TYPE.as_ptr() as *const c::c_char,
+                MEDIA_TYPE_AUDIO.as_ptr() as *const c::c_char,
+                KEY_MEDIA_CATEGORY.as_ptr() as *const c::c_char,
+                MEDIA_CATEGORY_FILTER.as_ptr() as *const c::c_char,
+                KEY_MEDIA_ROLE.as_ptr() as *const c::c_char,
+                MEDIA_ROLE_DSP.as_ptr() as *const c::c_char,
+                ptr::null::<c::c_char>(),
+            );
+
+            // NB: the filter takes ownership of the properties.
+            let filter = pw::pw_filter_new(connection.core.as_ptr(), name.as_ptr(), props);
+            let filter = ptr::NonNull::new(filter).ok_or_else(last_error)?;
+
+            let mut this = Self {
+                filter,
+                connection,
+                listener: Box::new(Listener {
+                    hook: mem::zeroed(),
+                    inputs: Vec::with_capacity(inputs),
+                    outputs: Vec::with_capacity(outputs),
+                    input_buffers: vec![ptr::null(); inputs],
+                    output_buffers: vec![ptr::null_mut(); outputs],
+                    silence: Vec::new(),
+                    scratch: vec![Vec::new(); outputs],
+                    process: Box::new(process),
+                    error: None,
+                    disconnected: false,
+                }),
+            };
+
+            pw::pw_filter_add_listener(
+                this.filter.as_ptr(),
+                &mut this.listener.hook,
+                &FILTER_EVENTS,
+                &mut *this.listener as *mut Listener as *mut c::c_void,
+            );
+
+            for n in 0..inputs {
+                let port = this.add_port(pw::SPA_DIRECTION_INPUT, &format!("input_{}", n))?;
+                this.listener.inputs.push(port);
+            }
+
+            for n in 0..outputs {
+                let port = this.add_port(pw::SPA_DIRECTION_OUTPUT, &format!("output_{}", n))?;
+                this.listener.outputs.push(port);
+            }
+
+            check(pw::pw_filter_connect(
+                this.filter.as_ptr(),
+                pw::PW_FILTER_FLAG_NONE,
+                ptr::null_mut(),
+                0,
+            ))?;
+
+            Ok(this)
+        }
+    }
+
+    /// The id of the node of the filter in the graph, which is available once
+    /// the filter is connected.
+    pub fn node_id(&self) -> Option<u32> {
+        let id = unsafe { pw::pw_filter_get_node_id(self.filter.as_ptr()) };
+
+        if id == INVALID_ID {
+            None
+        } else {
+            Some(id)
+        }
+    }
+
+    /// The current state of the filter.
+    pub fn state(&self) -> FilterState {
+        let state = unsafe { pw::pw_filter_get_state(self.filter.as_ptr(), ptr::null_mut()) };
+        FilterState::from_value(state)
+    }
+
+    /// Update the parameters of the filter node, which are built through a
+    /// [PodBuilder][super::PodBuilder] or one of the parameter helpers like
+    /// [BufferParams][super::BufferParams].
+    pub fn update_params(&mut self, params: &[Pod]) -> Result<()> {
+        let mut params = params.iter().map(Pod::as_ptr).collect::<Vec<_>>();
+
+        unsafe {
+            check(pw::pw_filter_update_params(
+                self.filter.as_ptr(),
+                ptr::null_mut(),
+                params.as_mut_ptr(),
+                params.len() as u32,
+            ))?;
+        }
+
+        Ok(())
+    }
+
+    /// Drive the filter, calling its process callback for each graph cycle.
+    ///
+    /// This completes once the filter is disconnected from the server, or
+    /// with an error if the filter fails.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless called inside of the audio runtime.
+    pub async fn run(&mut self) -> Result<()> {
+        future::poll_fn(|cx| self.poll_run(cx)).await
+    }
+
+    fn poll_run(&mut self, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        loop {
+            self.connection.dispatch()?;
+
+            if let Some(error) = self.listener.error.take() {
+                return Poll::Ready(Err(Error::Filter(error)));
+            }
+
+            if self.listener.disconnected {
+                return Poll::Ready(Ok(()));
+            }
+
+            match self.connection.poll_events(cx) {
+                Poll::Ready(result) => result?,
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+
+    /// Add a port carrying a single channel of 32-bit float audio.
+    unsafe fn add_port(&mut self, direction: pw::spa_direction, name: &str) -> Result<Port> {
+        // NB: port names are generated, so they never contain a nul byte.
+        let name = CString::new(name).expect("port name contains nul byte");
+
+        let props = pw::pw_properties_new(
+            KEY_FORMAT_DSP.as_ptr() as *const c::c_char,
+            FORMAT_DSP_F32.as_ptr() as *const c::c_char,
+            KEY_PORT_NAME.as_ptr() as *const c::c_char,
+            name.as_ptr(),
+            ptr::null::<c::c_char>(),
+        );
+
+        let port = pw::pw_filter_add_port(
+            self.filter.as_ptr(),
+            direction,
+            pw::PW_FILTER_PORT_FLAG_MAP_BUFFERS,
+            mem::size_of::<usize>(),
+            props,
+            ptr::null_mut(),
+            0,
+        );
+
+        let port = ptr::NonNull::new(port).ok_or_else(last_error)?;
+        Ok(Port(port))
+    }
+}
+
+impl Drop for Filter {
+    fn drop(&mut self) {
+        unsafe {
+            pw::pw_filter_destroy(self.filter.as_ptr());
+        }
+    }
+}
+
+// Safety: the filter exclusively owns its connection, which is only ever
+// driven through a mutable reference to the filter, and the process callback
+// is required to be `Send`.
+unsafe impl Send for Filter {}
+
+/// The data of a port of the filter, which identifies it.
+struct Port(ptr::NonNull<c::c_void>);
+
+/// State shared with the filter listener.
+struct Listener {
+    hook: pw::spa_hook,
+    inputs: Vec<Port>,
+    outputs: Vec<Port>,
+    input_buffers: Vec<*const f32>,
+    output_buffers: Vec<*mut f32>,
+    /// Used for input ports which have no buffer available.
+    silence: Vec<f32>,
+    /// Used for output ports which have no buffer available.
+    scratch: Vec<Vec<f32>>,
+    process: Box<dyn FnMut(PortBuffer<'_>, PortBufferMut<'_>) + Send>,
+    error: Option<String>,
+    disconnected: bool,
+}
+
+unsafe extern "C" fn state_changed_callback(
+    data: *mut c::c_void,
+    _: pw::pw_filter_state,
+    state: pw::pw_filter_state,
+    error: *const c::c_char,
+) {
+    let listener = &mut *(data as *mut Listener);
+
+    match state {
+        pw::PW_FILTER_STATE_ERROR => {
+            let error = if error.is_null() {
+                String::from("unknown error")
+            } else {
+                CStr::from_ptr(error).to_string_lossy().into_owned()
+            };
+
+            listener.error = Some(error);
+        }
+        pw::PW_FILTER_STATE_UNCONNECTED => {
+            listener.disconnected = true;
+        }
+        _ => (),
+    }
+}
+
+unsafe extern "C" fn process_callback(data: *mut c::c_void, position: *mut pw::spa_io_position) {
+    let listener = &mut *(data as *mut Listener);
+
+    if position.is_null() {
+        return;
+    }
+
+    let frames = (*position).clock.duration as usize;
+
+    for (port, buffer) in listener.inputs.iter().zip(&mut listener.input_buffers) {
+        let data = pw::pw_filter_get_dsp_buffer(port.0.as_ptr(), frames as u32);
+
+        *buffer = if data.is_null() {
+            if listener.silence.len() < frames {
+                listener.silence.resize(frames, 0.0);
+            }
+
+            listener.silence.as_ptr()
+        } else {
+            data as *const f32
+        };
+    }
+
+    for ((port, buffer), scratch) in listener
+        .outputs
+        .iter()
+        .zip(&mut listener.output_buffers)
+        .zip(&mut listener.scratch)
+    {
+        let data = pw::pw_filter_get_dsp_buffer(port.0.as_ptr(), frames as u32);
+
+        *buffer = if data.is_null() {
+            if scratch.len() < frames {
+                scratch.resize(frames, 0.0);
+            }
+
+            scratch.as_mut_ptr()
+        } else {
+            data as *mut f32
+        };
+    }
+
+    let input = PortBuffer::new(&listener.input_buffers, frames);
+    let output = PortBufferMut::new(&listener.output_buffers, frames);
+    (listener.process)(input, output);
+}
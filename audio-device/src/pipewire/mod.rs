@@ -1,8 +1,29 @@
 //! An idiomatic Rust PipeWire interface.
 // Documentation: https://docs.pipewire.org/
 
+mod connection;
+
+mod error;
+pub use self::error::{Error, Result};
+
+mod filter;
+pub use self::filter::{Filter, FilterState};
+
 mod main_loop;
 pub use self::main_loop::MainLoop;
 
+mod param;
+pub use self::param::{AudioChannel, AudioFormat, AudioInfo, BufferParams};
+
+mod pod;
+pub use self::pod::{ChoiceKind, ObjectBuilder, ObjectType, ParamType, Pod, PodBuilder};
+
+mod port_buffer;
+pub use self::port_buffer::{IterChannels, IterChannelsMut, PortBuffer, PortBufferMut};
+
 mod property_list;
 pub use self::property_list::PropertyList;
+
+mod registry;
+pub(crate) use self::registry::device_infos;
+pub use self::registry::{GlobalObject, ObjectKind, Registry, RegistryEvent};
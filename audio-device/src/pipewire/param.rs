@@ -0,0 +1,265 @@
+use crate::pipewire::{ChoiceKind, ObjectType, ParamType, Pod, PodBuilder};
+use pipewire_sys as pw;
+
+const MEDIA_TYPE_AUDIO: u32 = 1;
+const MEDIA_SUBTYPE_RAW: u32 = 1;
+
+const FORMAT_MEDIA_TYPE: u32 = 1;
+const FORMAT_MEDIA_SUBTYPE: u32 = 2;
+const FORMAT_AUDIO_FORMAT: u32 = 0x10001;
+const FORMAT_AUDIO_RATE: u32 = 0x10003;
+const FORMAT_AUDIO_CHANNELS: u32 = 0x10004;
+const FORMAT_AUDIO_POSITION: u32 = 0x10005;
+
+const PARAM_BUFFERS_BUFFERS: u32 = pw::SPA_PARAM_BUFFERS_buffers;
+const PARAM_BUFFERS_BLOCKS: u32 = pw::SPA_PARAM_BUFFERS_blocks;
+const PARAM_BUFFERS_SIZE: u32 = pw::SPA_PARAM_BUFFERS_size;
+const PARAM_BUFFERS_STRIDE: u32 = pw::SPA_PARAM_BUFFERS_stride;
+
+/// Pick the little or big endian value of a sample format, depending on the
+/// endianness of the target.
+macro_rules! native {
+    ($le:literal, $be:literal) => {
+        if cfg!(target_endian = "little") {
+            $le
+        } else {
+            $be
+        }
+    };
+}
+
+/// A raw audio sample format, in native endianness.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+#[repr(u32)]
+pub enum AudioFormat {
+    /// Signed 8-bit samples.
+    S8 = 0x101,
+    /// Unsigned 8-bit samples.
+    U8 = 0x102,
+    /// Signed 16-bit samples.
+    S16 = native!(0x103, 0x104),
+    /// Signed 24-bit samples, stored in the low bits of 32 bits.
+    S24_32 = native!(0x107, 0x108),
+    /// Signed 32-bit samples.
+    S32 = native!(0x10b, 0x10c),
+    /// Signed 24-bit packed samples.
+    S24 = native!(0x10f, 0x110),
+    /// 32-bit float samples.
+    F32 = native!(0x11b, 0x11c),
+    /// 64-bit float samples.
+    F64 = native!(0x11d, 0x11e),
+    /// Planar 32-bit float samples, where each channel has its own buffer.
+    F32P = 0x206,
+}
+
+/// The position of a channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+#[repr(u32)]
+pub enum AudioChannel {
+    /// A mono channel.
+    Mono = 2,
+    /// Front left.
+    FrontLeft = 3,
+    /// Front right.
+    FrontRight = 4,
+    /// Front center.
+    FrontCenter = 5,
+    /// Low frequency effects.
+    LowFrequency = 6,
+    /// Side left.
+    SideLeft = 7,
+    /// Side right.
+    SideRight = 8,
+    /// Front left of center.
+    FrontLeftCenter = 9,
+    /// Front right of center.
+    FrontRightCenter = 10,
+    /// Rear center.
+    RearCenter = 11,
+    /// Rear left.
+    RearLeft = 12,
+    /// Rear right.
+    RearRight = 13,
+}
+
+/// A description of a raw audio format, which can be turned into a
+/// [ParamType::EnumFormat] or a [ParamType::Format] parameter.
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::pipewire::{AudioChannel, AudioFormat, AudioInfo, ParamType};
+///
+/// let mut info = AudioInfo::new(AudioFormat::F32, 48000, 2);
+/// info.set_positions(&[AudioChannel::FrontLeft, AudioChannel::FrontRight]);
+/// let pod = info.to_pod(ParamType::EnumFormat);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AudioInfo {
+    format: AudioFormat,
+    rate: u32,
+    channels: u32,
+    positions: Vec<AudioChannel>,
+}
+
+impl AudioInfo {
+    /// Construct a description of a raw audio format.
+    pub fn new(format: AudioFormat, rate: u32, channels: u32) -> Self {
+        Self {
+            format,
+            rate,
+            channels,
+            positions: Vec::new(),
+        }
+    }
+
+    /// Set the positions of the channels.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless there's one position for each channel.
+    pub fn set_positions(&mut self, positions: &[AudioChannel]) {
+        assert_eq!(
+            positions.len(),
+            self.channels as usize,
+            "expected one position for each channel"
+        );
+
+        self.positions = positions.to_vec();
+    }
+
+    /// The sample format.
+    pub fn format(&self) -> AudioFormat {
+        self.format
+    }
+
+    /// The sample rate.
+    pub fn rate(&self) -> u32 {
+        self.rate
+    }
+
+    /// The number of channels.
+    pub fn channels(&self) -> u32 {
+        self.channels
+    }
+
+    /// Build a format object describing this format, as the given kind of
+    /// parameter.
+    pub fn to_pod(&self, param: ParamType) -> Pod {
+        let mut builder = PodBuilder::new();
+
+        builder.object(ObjectType::Format, param, |object| {
+            object
+                .property(FORMAT_MEDIA_TYPE, |value| {
+                    value.id(MEDIA_TYPE_AUDIO);
+                })
+                .property(FORMAT_MEDIA_SUBTYPE, |value| {
+                    value.id(MEDIA_SUBTYPE_RAW);
+                })
+                .property(FORMAT_AUDIO_FORMAT, |value| {
+                    value.id(self.format as u32);
+                })
+                .property(FORMAT_AUDIO_RATE, |value| {
+                    value.int(self.rate as i32);
+                })
+                .property(FORMAT_AUDIO_CHANNELS, |value| {
+                    value.int(self.channels as i32);
+                });
+
+            if !self.positions.is_empty() {
+                let positions = self.positions.iter().map(|p| *p as u32).collect::<Vec<_>>();
+
+                object.property(FORMAT_AUDIO_POSITION, |value| {
+                    value.id_array(&positions);
+                });
+            }
+        });
+
+        builder.finish()
+    }
+}
+
+/// A buffer configuration, which is used as a [ParamType::Buffers]
+/// parameter.
+///
+/// Only the configured values are included in the parameter.
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::pipewire::BufferParams;
+///
+/// let mut params = BufferParams::new();
+/// params.buffers(2, 1, 16).size(4096);
+/// let pod = params.to_pod();
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BufferParams {
+    buffers: Option<[i32; 3]>,
+    blocks: Option<i32>,
+    size: Option<i32>,
+    stride: Option<i32>,
+}
+
+impl BufferParams {
+    /// Construct an empty buffer configuration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the preferred number of buffers, and the range of buffers which
+    /// is acceptable.
+    pub fn buffers(&mut self, default: i32, min: i32, max: i32) -> &mut Self {
+        self.buffers = Some([default, min, max]);
+        self
+    }
+
+    /// Set the number of data blocks in each buffer.
+    pub fn blocks(&mut self, blocks: i32) -> &mut Self {
+        self.blocks = Some(blocks);
+        self
+    }
+
+    /// Set the size of each data block in bytes.
+    pub fn size(&mut self, size: i32) -> &mut Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// Set the stride of the data in bytes, which is the size of a frame.
+    pub fn stride(&mut self, stride: i32) -> &mut Self {
+        self.stride = Some(stride);
+        self
+    }
+
+    /// Build a buffers object describing this configuration.
+    pub fn to_pod(&self) -> Pod {
+        let mut builder = PodBuilder::new();
+
+        builder.object(ObjectType::ParamBuffers, ParamType::Buffers, |object| {
+            if let Some(buffers) = &self.buffers {
+                object.property(PARAM_BUFFERS_BUFFERS, |value| {
+                    value.choice_int(ChoiceKind::Range, buffers);
+                });
+            }
+
+            let ints = [
+                (PARAM_BUFFERS_BLOCKS, self.blocks),
+                (PARAM_BUFFERS_SIZE, self.size),
+                (PARAM_BUFFERS_STRIDE, self.stride),
+            ];
+
+            for (key, int) in ints {
+                if let Some(int) = int {
+                    object.property(key, |value| {
+                        value.int(int);
+                    });
+                }
+            }
+        });
+
+        builder.finish()
+    }
+}
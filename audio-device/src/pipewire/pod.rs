@@ -0,0 +1,393 @@
+use pipewire_sys as pw;
+use std::ffi::CStr;
+use std::fmt;
+use std::mem;
+
+#[cfg(test)]
+mod tests;
+
+/// The alignment of every pod, which is also what each pod is padded to.
+const ALIGN: usize = 8;
+
+const TYPE_BOOL: u32 = 2;
+const TYPE_ID: u32 = 3;
+const TYPE_INT: u32 = 4;
+const TYPE_LONG: u32 = 5;
+const TYPE_FLOAT: u32 = 6;
+const TYPE_DOUBLE: u32 = 7;
+const TYPE_STRING: u32 = 8;
+const TYPE_BYTES: u32 = 9;
+const TYPE_RECTANGLE: u32 = 10;
+const TYPE_FRACTION: u32 = 11;
+const TYPE_ARRAY: u32 = 13;
+const TYPE_STRUCT: u32 = 14;
+const TYPE_OBJECT: u32 = 15;
+const TYPE_CHOICE: u32 = 19;
+
+/// The type of an object pod, which determines which keys its properties
+/// use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+#[repr(u32)]
+pub enum ObjectType {
+    /// Properties of a node, keyed by `SPA_PROP_*`.
+    Props = 0x40002,
+    /// A media format, keyed by `SPA_FORMAT_*`.
+    Format = 0x40003,
+    /// A buffer configuration, keyed by `SPA_PARAM_BUFFERS_*`.
+    ParamBuffers = 0x40004,
+    /// A metadata configuration, keyed by `SPA_PARAM_META_*`.
+    ParamMeta = 0x40005,
+    /// An io area configuration, keyed by `SPA_PARAM_IO_*`.
+    ParamIo = 0x40006,
+}
+
+/// The kind of parameter an object pod describes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+#[repr(u32)]
+pub enum ParamType {
+    /// Properties of a node.
+    Props = pw::SPA_PARAM_Props,
+    /// The formats which are supported.
+    EnumFormat = pw::SPA_PARAM_EnumFormat,
+    /// The format which is in use.
+    Format = pw::SPA_PARAM_Format,
+    /// The buffer configuration.
+    Buffers = pw::SPA_PARAM_Buffers,
+    /// The metadata to add to buffers.
+    Meta = pw::SPA_PARAM_Meta,
+    /// The io areas in use.
+    Io = pw::SPA_PARAM_IO,
+}
+
+/// The kind of a choice, which determines how its values are interpreted.
+///
+/// See [PodBuilder::choice_int].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+#[repr(u32)]
+pub enum ChoiceKind {
+    /// A single value.
+    None = pw::SPA_CHOICE_None,
+    /// A default value, followed by a minimum and a maximum.
+    Range = pw::SPA_CHOICE_Range,
+    /// A default value, followed by a minimum, a maximum and a step.
+    Step = pw::SPA_CHOICE_Step,
+    /// A default value, followed by the alternatives to pick from.
+    Enum = pw::SPA_CHOICE_Enum,
+    /// A default value, followed by the flags which can be set.
+    Flags = pw::SPA_CHOICE_Flags,
+}
+
+/// A serialized SPA pod, which is how PipeWire describes formats and other
+/// parameters.
+///
+/// Pods are constructed through a [PodBuilder].
+#[derive(Clone)]
+pub struct Pod {
+    // NB: stored as words to guarantee the alignment of the pod.
+    data: Vec<u64>,
+    len: usize,
+}
+
+impl Pod {
+    /// The serialized bytes of the pod, including its header.
+    pub fn as_bytes(&self) -> &[u8] {
+        // Safety: the words are fully initialized and span at least `len`
+        // bytes.
+        unsafe { std::slice::from_raw_parts(self.data.as_ptr() as *const u8, self.len) }
+    }
+
+    /// Get a pointer to the pod, which is valid for as long as the pod is
+    /// alive.
+    pub(super) fn as_ptr(&self) -> *const pw::spa_pod {
+        self.data.as_ptr() as *const pw::spa_pod
+    }
+}
+
+impl fmt::Debug for Pod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pod").field("len", &self.len).finish()
+    }
+}
+
+/// A builder for a single [Pod].
+///
+/// Containers like structs, objects and their properties are built through
+/// closures, which guarantees that they are always correctly terminated.
+///
+/// # Examples
+///
+/// ```
+/// use audio_device::pipewire::{ObjectType, ParamType, PodBuilder};
+///
+/// // SPA_PARAM_BUFFERS_buffers
+/// const BUFFERS: u32 = 1;
+///
+/// let mut builder = PodBuilder::new();
+///
+/// builder.object(ObjectType::ParamBuffers, ParamType::Buffers, |object| {
+///     object.property(BUFFERS, |value| {
+///         value.int(8);
+///     });
+/// });
+///
+/// let pod = builder.finish();
+/// assert_eq!(pod.as_bytes().len() % 8, 0);
+/// ```
+#[derive(Default)]
+pub struct PodBuilder {
+    data: Vec<u8>,
+    depth: usize,
+    count: usize,
+}
+
+impl PodBuilder {
+    /// Construct a new empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Push a boolean.
+    pub fn bool(&mut self, value: bool) -> &mut Self {
+        self.primitive(TYPE_BOOL, &(value as i32).to_ne_bytes())
+    }
+
+    /// Push an id, which is an enumerated value like a format.
+    pub fn id(&mut self, value: u32) -> &mut Self {
+        self.primitive(TYPE_ID, &value.to_ne_bytes())
+    }
+
+    /// Push a 32-bit integer.
+    pub fn int(&mut self, value: i32) -> &mut Self {
+        self.primitive(TYPE_INT, &value.to_ne_bytes())
+    }
+
+    /// Push a 64-bit integer.
+    pub fn long(&mut self, value: i64) -> &mut Self {
+        self.primitive(TYPE_LONG, &value.to_ne_bytes())
+    }
+
+    /// Push a 32-bit float.
+    pub fn float(&mut self, value: f32) -> &mut Self {
+        self.primitive(TYPE_FLOAT, &value.to_ne_bytes())
+    }
+
+    /// Push a 64-bit float.
+    pub fn double(&mut self, value: f64) -> &mut Self {
+        self.primitive(TYPE_DOUBLE, &value.to_ne_bytes())
+    }
+
+    /// Push a string.
+    pub fn string(&mut self, value: &CStr) -> &mut Self {
+        self.primitive(TYPE_STRING, value.to_bytes_with_nul())
+    }
+
+    /// Push a sequence of bytes.
+    pub fn bytes(&mut self, value: &[u8]) -> &mut Self {
+        self.primitive(TYPE_BYTES, value)
+    }
+
+    /// Push a rectangle with the given width and height.
+    pub fn rectangle(&mut self, width: u32, height: u32) -> &mut Self {
+        self.container(TYPE_RECTANGLE, |this| {
+            this.extend(&width.to_ne_bytes());
+            this.extend(&height.to_ne_bytes());
+        })
+    }
+
+    /// Push a fraction, like a frame rate.
+    pub fn fraction(&mut self, num: u32, denom: u32) -> &mut Self {
+        self.container(TYPE_FRACTION, |this| {
+            this.extend(&num.to_ne_bytes());
+            this.extend(&denom.to_ne_bytes());
+        })
+    }
+
+    /// Push an array of ids, like a list of channel positions.
+    pub fn id_array(&mut self, values: &[u32]) -> &mut Self {
+        self.container(TYPE_ARRAY, |this| {
+            this.values(TYPE_ID, values.iter().map(|v| v.to_ne_bytes()));
+        })
+    }
+
+    /// Push an array of 32-bit integers.
+    pub fn int_array(&mut self, values: &[i32]) -> &mut Self {
+        self.container(TYPE_ARRAY, |this| {
+            this.values(TYPE_INT, values.iter().map(|v| v.to_ne_bytes()));
+        })
+    }
+
+    /// Push a choice of ids, where the meaning of the values depends on the
+    /// kind of choice.
+    pub fn choice_id(&mut self, kind: ChoiceKind, values: &[u32]) -> &mut Self {
+        self.container(TYPE_CHOICE, |this| {
+            this.extend(&(kind as u32).to_ne_bytes());
+            this.extend(&0u32.to_ne_bytes());
+            this.values(TYPE_ID, values.iter().map(|v| v.to_ne_bytes()));
+        })
+    }
+
+    /// Push a choice of 32-bit integers, where the meaning of the values
+    /// depends on the kind of choice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio_device::pipewire::{ChoiceKind, PodBuilder};
+    ///
+    /// let mut builder = PodBuilder::new();
+    /// // A default of 48000, anywhere between 8000 and 192000.
+    /// builder.choice_int(ChoiceKind::Range, &[48000, 8000, 192000]);
+    /// let pod = builder.finish();
+    /// ```
+    pub fn choice_int(&mut self, kind: ChoiceKind, values: &[i32]) -> &mut Self {
+        self.container(TYPE_CHOICE, |this| {
+            this.extend(&(kind as u32).to_ne_bytes());
+            this.extend(&0u32.to_ne_bytes());
+            this.values(TYPE_INT, values.iter().map(|v| v.to_ne_bytes()));
+        })
+    }
+
+    /// Push a struct, whose fields are the pods pushed by the given closure.
+    pub fn push_struct<F>(&mut self, f: F) -> &mut Self
+    where
+        F: FnOnce(&mut PodBuilder),
+    {
+        self.container(TYPE_STRUCT, |this| {
+            this.depth += 1;
+            f(this);
+            this.depth -= 1;
+        })
+    }
+
+    /// Push an object of the given type, whose properties are added by the
+    /// given closure.
+    pub fn object<F>(&mut self, object_type: ObjectType, id: ParamType, f: F) -> &mut Self
+    where
+        F: FnOnce(&mut ObjectBuilder<'_>),
+    {
+        self.container(TYPE_OBJECT, |this| {
+            this.extend(&(object_type as u32).to_ne_bytes());
+            this.extend(&(id as u32).to_ne_bytes());
+            this.depth += 1;
+            f(&mut ObjectBuilder { builder: this });
+            this.depth -= 1;
+        })
+    }
+
+    /// Finish building the pod.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless exactly one pod has been pushed to the builder.
+    pub fn finish(self) -> Pod {
+        assert!(
+            self.count == 1,
+            "expected exactly one pod, but {} were pushed",
+            self.count
+        );
+
+        let len = self.data.len();
+        let mut data = vec![0u64; len / mem::size_of::<u64>()];
+
+        // Safety: the data is padded to a multiple of the word size, so it
+        // fits exactly.
+        unsafe {
+            std::ptr::copy_nonoverlapping(self.data.as_ptr(), data.as_mut_ptr() as *mut u8, len);
+        }
+
+        Pod { data, len }
+    }
+
+    /// Push a pod whose body is the given bytes.
+    fn primitive(&mut self, ty: u32, body: &[u8]) -> &mut Self {
+        self.container(ty, |this| this.extend(body))
+    }
+
+    /// Push a pod whose body is written by the given closure, patching in its
+    /// size and padding it once the closure returns.
+    fn container<F>(&mut self, ty: u32, f: F) -> &mut Self
+    where
+        F: FnOnce(&mut Self),
+    {
+        if self.depth == 0 {
+            self.count += 1;
+        }
+
+        let start = self.data.len();
+        self.extend(&0u32.to_ne_bytes());
+        self.extend(&ty.to_ne_bytes());
+
+        f(self);
+
+        let size = (self.data.len() - start - mem::size_of::<pw::spa_pod>()) as u32;
+        self.data[start..start + 4].copy_from_slice(&size.to_ne_bytes());
+        self.pad();
+        self
+    }
+
+    /// Write the child header and packed values of an array or a choice.
+    fn values<I, const N: usize>(&mut self, ty: u32, values: I)
+    where
+        I: IntoIterator<Item = [u8; N]>,
+    {
+        self.extend(&(N as u32).to_ne_bytes());
+        self.extend(&ty.to_ne_bytes());
+
+        for value in values {
+            self.extend(&value);
+        }
+    }
+
+    fn extend(&mut self, bytes: &[u8]) {
+        self.data.extend_from_slice(bytes);
+    }
+
+    fn pad(&mut self) {
+        let len = (self.data.len() + ALIGN - 1) / ALIGN * ALIGN;
+        self.data.resize(len, 0);
+    }
+}
+
+/// A builder for the properties of an object.
+///
+/// See [PodBuilder::object].
+pub struct ObjectBuilder<'a> {
+    builder: &'a mut PodBuilder,
+}
+
+impl ObjectBuilder<'_> {
+    /// Add a property with the given key, whose value is the single pod
+    /// pushed by the given closure.
+    ///
+    /// The meaning of the key depends on the type of the object.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless the closure pushes exactly one pod.
+    pub fn property<F>(&mut self, key: u32, f: F) -> &mut Self
+    where
+        F: FnOnce(&mut PodBuilder),
+    {
+        self.builder.extend(&key.to_ne_bytes());
+        self.builder.extend(&0u32.to_ne_bytes());
+
+        // NB: count the pods pushed by the closure as if they were top-level,
+        // so that we can check that exactly one value was pushed.
+        let depth = mem::take(&mut self.builder.depth);
+        let count = mem::take(&mut self.builder.count);
+        f(self.builder);
+        let pushed = mem::replace(&mut self.builder.count, count);
+        self.builder.depth = depth;
+
+        assert!(
+            pushed == 1,
+            "expected exactly one property value, but {} were pushed",
+            pushed
+        );
+
+        self
+    }
+}
@@ -0,0 +1,132 @@
+use std::ffi::CStr;
+
+use crate::pipewire::{ChoiceKind, ObjectType, ParamType, PodBuilder};
+
+const TYPE_ID: u32 = 3;
+const TYPE_INT: u32 = 4;
+const TYPE_LONG: u32 = 5;
+const TYPE_STRING: u32 = 8;
+const TYPE_STRUCT: u32 = 14;
+const TYPE_OBJECT: u32 = 15;
+const TYPE_CHOICE: u32 = 19;
+
+const FORMAT_MEDIA_TYPE: u32 = 1;
+const FORMAT_MEDIA_SUBTYPE: u32 = 2;
+const FORMAT_AUDIO_FORMAT: u32 = 0x10001;
+const FORMAT_AUDIO_RATE: u32 = 0x10003;
+const FORMAT_AUDIO_CHANNELS: u32 = 0x10004;
+
+const MEDIA_TYPE_AUDIO: u32 = 1;
+const MEDIA_SUBTYPE_RAW: u32 = 1;
+const AUDIO_FORMAT_F32_LE: u32 = 0x11b;
+
+/// Serialize the given words in native endianness, which is how pods are
+/// laid out.
+fn words(words: &[u32]) -> Vec<u8> {
+    words.iter().flat_map(|w| w.to_ne_bytes()).collect()
+}
+
+#[test]
+fn test_audio_format_object() {
+    let mut builder = PodBuilder::new();
+
+    builder.object(ObjectType::Format, ParamType::EnumFormat, |object| {
+        object.property(FORMAT_MEDIA_TYPE, |value| {
+            value.id(MEDIA_TYPE_AUDIO);
+        });
+        object.property(FORMAT_MEDIA_SUBTYPE, |value| {
+            value.id(MEDIA_SUBTYPE_RAW);
+        });
+        object.property(FORMAT_AUDIO_FORMAT, |value| {
+            value.id(AUDIO_FORMAT_F32_LE);
+        });
+        object.property(FORMAT_AUDIO_RATE, |value| {
+            value.choice_int(ChoiceKind::Range, &[48000, 8000, 192000]);
+        });
+        object.property(FORMAT_AUDIO_CHANNELS, |value| {
+            value.int(2);
+        });
+    });
+
+    let pod = builder.finish();
+
+    #[rustfmt::skip]
+    let expected = words(&[
+        152, TYPE_OBJECT,
+        0x40003, 3,
+        // key, flags, then an id pod padded to 8 bytes.
+        FORMAT_MEDIA_TYPE, 0, 4, TYPE_ID, MEDIA_TYPE_AUDIO, 0,
+        FORMAT_MEDIA_SUBTYPE, 0, 4, TYPE_ID, MEDIA_SUBTYPE_RAW, 0,
+        FORMAT_AUDIO_FORMAT, 0, 4, TYPE_ID, AUDIO_FORMAT_F32_LE, 0,
+        // a range choice: kind, flags, the child pod header and its values.
+        FORMAT_AUDIO_RATE, 0, 28, TYPE_CHOICE, 1, 0, 4, TYPE_INT, 48000, 8000, 192000, 0,
+        FORMAT_AUDIO_CHANNELS, 0, 4, TYPE_INT, 2, 0,
+    ]);
+
+    assert_eq!(pod.as_bytes(), &expected[..]);
+}
+
+#[test]
+fn test_choice() {
+    let mut builder = PodBuilder::new();
+    builder.choice_int(ChoiceKind::Enum, &[2, 1, 2]);
+    let pod = builder.finish();
+
+    #[rustfmt::skip]
+    let expected = words(&[
+        28, TYPE_CHOICE,
+        3, 0,
+        4, TYPE_INT,
+        2, 1, 2,
+        // padding
+        0,
+    ]);
+
+    assert_eq!(pod.as_bytes(), &expected[..]);
+}
+
+#[test]
+fn test_struct() {
+    let mut builder = PodBuilder::new();
+
+    builder.push_struct(|fields| {
+        fields.int(-1);
+        fields.string(CStr::from_bytes_with_nul(b"ab\0").unwrap());
+        fields.long(0x1_0000_0002);
+    });
+
+    let pod = builder.finish();
+
+    let string = u32::from_ne_bytes([b'a', b'b', 0, 0]);
+    let long = 0x1_0000_0002u64.to_ne_bytes();
+    let long_0 = u32::from_ne_bytes([long[0], long[1], long[2], long[3]]);
+    let long_1 = u32::from_ne_bytes([long[4], long[5], long[6], long[7]]);
+
+    #[rustfmt::skip]
+    let expected = words(&[
+        48, TYPE_STRUCT,
+        4, TYPE_INT, -1i32 as u32, 0,
+        3, TYPE_STRING, string, 0,
+        8, TYPE_LONG, long_0, long_1,
+    ]);
+
+    assert_eq!(pod.as_bytes(), &expected[..]);
+}
+
+#[test]
+#[should_panic(expected = "expected exactly one pod, but 2 were pushed")]
+fn test_finish_multiple() {
+    let mut builder = PodBuilder::new();
+    builder.int(1).int(2);
+    builder.finish();
+}
+
+#[test]
+#[should_panic(expected = "expected exactly one property value, but 0 were pushed")]
+fn test_empty_property() {
+    let mut builder = PodBuilder::new();
+
+    builder.object(ObjectType::Props, ParamType::Props, |object| {
+        object.property(1, |_| {});
+    });
+}
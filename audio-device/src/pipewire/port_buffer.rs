@@ -0,0 +1,218 @@
+use ::audio::channel::{LinearChannel, LinearChannelMut};
+use audio_core::{Buf, BufMut, ExactSizeBuf};
+use std::marker;
+use std::slice;
+
+/// The audio received on the input ports of a [Filter][super::Filter] during
+/// a single processing cycle.
+///
+/// Each port is a channel of 32-bit float samples, and every channel has the
+/// same number of frames.
+pub struct PortBuffer<'a> {
+    ports: &'a [*const f32],
+    frames: usize,
+}
+
+impl<'a> PortBuffer<'a> {
+    /// Construct a new port buffer.
+    ///
+    /// # Safety
+    ///
+    /// Every port must point to `frames` initialized samples which are valid
+    /// for reads during `'a`.
+    pub(super) unsafe fn new(ports: &'a [*const f32], frames: usize) -> Self {
+        Self { ports, frames }
+    }
+}
+
+impl Buf for PortBuffer<'_> {
+    type Sample = f32;
+
+    type Channel<'this>
+        = LinearChannel<'this, f32>
+    where
+        Self: 'this;
+
+    type IterChannels<'this>
+        = IterChannels<'this>
+    where
+        Self: 'this;
+
+    fn frames_hint(&self) -> Option<usize> {
+        Some(self.frames)
+    }
+
+    fn channels(&self) -> usize {
+        self.ports.len()
+    }
+
+    fn get_channel(&self, channel: usize) -> Option<Self::Channel<'_>> {
+        let port = *self.ports.get(channel)?;
+        Some(LinearChannel::new(unsafe {
+            slice::from_raw_parts(port, self.frames)
+        }))
+    }
+
+    fn iter_channels(&self) -> Self::IterChannels<'_> {
+        IterChannels {
+            iter: self.ports.iter(),
+            frames: self.frames,
+        }
+    }
+}
+
+impl ExactSizeBuf for PortBuffer<'_> {
+    fn frames(&self) -> usize {
+        self.frames
+    }
+}
+
+/// The audio to send through the output ports of a [Filter][super::Filter]
+/// during a single processing cycle.
+///
+/// Each port is a channel of 32-bit float samples, and every channel has the
+/// same number of frames. Output ports start out with unspecified contents,
+/// so every channel should be written to.
+pub struct PortBufferMut<'a> {
+    ports: &'a [*mut f32],
+    frames: usize,
+}
+
+impl<'a> PortBufferMut<'a> {
+    /// Construct a new mutable port buffer.
+    ///
+    /// # Safety
+    ///
+    /// Every port must point to `frames` initialized samples which are valid
+    /// for reads and writes during `'a`, and which don't overlap with each
+    /// other.
+    pub(super) unsafe fn new(ports: &'a [*mut f32], frames: usize) -> Self {
+        Self { ports, frames }
+    }
+}
+
+impl Buf for PortBufferMut<'_> {
+    type Sample = f32;
+
+    type Channel<'this>
+        = LinearChannel<'this, f32>
+    where
+        Self: 'this;
+
+    type IterChannels<'this>
+        = IterChannels<'this>
+    where
+        Self: 'this;
+
+    fn frames_hint(&self) -> Option<usize> {
+        Some(self.frames)
+    }
+
+    fn channels(&self) -> usize {
+        self.ports.len()
+    }
+
+    fn get_channel(&self, channel: usize) -> Option<Self::Channel<'_>> {
+        let port = *self.ports.get(channel)?;
+        Some(LinearChannel::new(unsafe {
+            slice::from_raw_parts(port, self.frames)
+        }))
+    }
+
+    fn iter_channels(&self) -> Self::IterChannels<'_> {
+        IterChannels {
+            // NB: `*mut f32` and `*const f32` have the same layout.
+            iter: unsafe {
+                slice::from_raw_parts(self.ports.as_ptr() as *const *const f32, self.ports.len())
+            }
+            .iter(),
+            frames: self.frames,
+        }
+    }
+}
+
+impl BufMut for PortBufferMut<'_> {
+    type ChannelMut<'this>
+        = LinearChannelMut<'this, f32>
+    where
+        Self: 'this;
+
+    type IterChannelsMut<'this>
+        = IterChannelsMut<'this>
+    where
+        Self: 'this;
+
+    fn get_channel_mut(&mut self, channel: usize) -> Option<Self::ChannelMut<'_>> {
+        let port = *self.ports.get(channel)?;
+        Some(LinearChannelMut::new(unsafe {
+            slice::from_raw_parts_mut(port, self.frames)
+        }))
+    }
+
+    fn copy_channel(&mut self, from: usize, to: usize) {
+        if let (Some(&from), Some(&to)) = (self.ports.get(from), self.ports.get(to)) {
+            // Safety: both ports are valid for `frames` samples, and
+            // `ptr::copy` permits them to be the same.
+            unsafe {
+                std::ptr::copy(from, to, self.frames);
+            }
+        }
+    }
+
+    fn iter_channels_mut(&mut self) -> Self::IterChannelsMut<'_> {
+        IterChannelsMut {
+            iter: self.ports.iter(),
+            frames: self.frames,
+            _marker: marker::PhantomData,
+        }
+    }
+}
+
+impl ExactSizeBuf for PortBufferMut<'_> {
+    fn frames(&self) -> usize {
+        self.frames
+    }
+}
+
+/// An iterator over the channels of a port buffer.
+pub struct IterChannels<'a> {
+    iter: slice::Iter<'a, *const f32>,
+    frames: usize,
+}
+
+impl<'a> Iterator for IterChannels<'a> {
+    type Item = LinearChannel<'a, f32>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let port = *self.iter.next()?;
+        Some(LinearChannel::new(unsafe {
+            slice::from_raw_parts(port, self.frames)
+        }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// A mutable iterator over the channels of a port buffer.
+pub struct IterChannelsMut<'a> {
+    iter: slice::Iter<'a, *mut f32>,
+    frames: usize,
+    _marker: marker::PhantomData<&'a mut f32>,
+}
+
+impl<'a> Iterator for IterChannelsMut<'a> {
+    type Item = LinearChannelMut<'a, f32>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let port = *self.iter.next()?;
+        Some(LinearChannelMut::new(unsafe {
+            slice::from_raw_parts_mut(port, self.frames)
+        }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
@@ -1,4 +1,5 @@
 use pipewire_sys as pw;
+use std::ffi::CStr;
 use std::ptr;
 
 /// A property list object.
@@ -27,6 +28,30 @@ impl PropertyList {
             }
         }
     }
+
+    /// Construct a property list by copying the given dictionary.
+    ///
+    /// # Safety
+    ///
+    /// The given dictionary must be valid.
+    pub(super) unsafe fn from_dict(dict: *const pw::spa_dict) -> Self {
+        Self {
+            handle: ptr::NonNull::new_unchecked(pw::pw_properties_new_dict(dict)),
+        }
+    }
+
+    /// Get the value of the given key, if it's set.
+    pub fn get(&self, key: &CStr) -> Option<&CStr> {
+        unsafe {
+            let value = pw::pw_properties_get(self.handle.as_ptr(), key.as_ptr());
+
+            if value.is_null() {
+                None
+            } else {
+                Some(CStr::from_ptr(value))
+            }
+        }
+    }
 }
 
 impl Drop for PropertyList {
@@ -36,3 +61,6 @@ impl Drop for PropertyList {
         }
     }
 }
+
+// Safety: the property list is exclusively owned.
+unsafe impl Send for PropertyList {}
@@ -0,0 +1,423 @@
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::ffi::{CStr, CString};
+use std::future;
+use std::mem;
+use std::ptr;
+use std::task::{Context, Poll};
+
+use pipewire_sys as pw;
+
+use audio_core::{Endian, SampleFormat};
+
+use crate::libc as c;
+use crate::pipewire::connection::{last_error, Connection};
+use crate::pipewire::{Error, PropertyList, Result};
+use crate::unix::Errno;
+use crate::{DeviceInfo, Direction};
+
+/// The version of the registry interface in use.
+const VERSION_REGISTRY: u32 = 3;
+/// The version of the registry events in use.
+const VERSION_REGISTRY_EVENTS: u32 = 0;
+
+const TYPE_NODE: &[u8] = b"PipeWire:Interface:Node";
+const TYPE_PORT: &[u8] = b"PipeWire:Interface:Port";
+const TYPE_DEVICE: &[u8] = b"PipeWire:Interface:Device";
+
+/// The version of the core events in use.
+const VERSION_CORE_EVENTS: u32 = 0;
+/// The id of the core object.
+const ID_CORE: u32 = 0;
+
+const KEY_MEDIA_CLASS: &[u8] = b"media.class\0";
+const KEY_NODE_NAME: &[u8] = b"node.name\0";
+const KEY_NODE_DESCRIPTION: &[u8] = b"node.description\0";
+const KEY_AUDIO_CHANNELS: &[u8] = b"audio.channels\0";
+const KEY_AUDIO_RATE: &[u8] = b"audio.rate\0";
+
+static REGISTRY_EVENTS: pw::pw_registry_events = pw::pw_registry_events {
+    version: VERSION_REGISTRY_EVENTS,
+    global: Some(global_callback),
+    global_remove: Some(global_remove_callback),
+};
+
+static CORE_EVENTS: pw::pw_core_events = pw::pw_core_events {
+    version: VERSION_CORE_EVENTS,
+    info: None,
+    done: Some(core_done_callback),
+    ping: None,
+    error: Some(core_error_callback),
+    remove_id: None,
+    bound_id: None,
+    add_mem: None,
+    remove_mem: None,
+};
+
+/// The kind of a global object.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ObjectKind {
+    /// A node, which produces or consumes audio, like a device or a stream.
+    Node,
+    /// A port, which is an input or an output of a node.
+    Port,
+    /// A device, which is a physical or virtual device that nodes are created
+    /// for.
+    Device,
+    /// Any other kind of object, with the name of its interface.
+    Other(CString),
+}
+
+/// A global object announced by the registry.
+#[non_exhaustive]
+pub struct GlobalObject {
+    /// The id of the object.
+    pub id: u32,
+    /// The kind of the object.
+    pub kind: ObjectKind,
+    /// The version of the interface of the object.
+    pub version: u32,
+    /// The properties of the object.
+    pub properties: PropertyList,
+}
+
+/// An event emitted by the [Registry].
+#[non_exhaustive]
+pub enum RegistryEvent {
+    /// A global object was added.
+    ///
+    /// All objects which exist when the registry is connected are announced
+    /// through this event.
+    Added(GlobalObject),
+    /// The global object with the given id was removed.
+    Removed(u32),
+}
+
+/// The registry of a PipeWire server, which announces the global objects of
+/// the server, like its nodes, ports and devices.
+///
+/// The registry is driven by the audio [Runtime][crate::runtime::Runtime], so
+/// it must be used inside of it.
+///
+/// See [Registry::connect].
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::pipewire;
+///
+/// async fn list() -> anyhow::Result<()> {
+///     let mut registry = pipewire::Registry::connect()?;
+///
+///     loop {
+///         match registry.next().await? {
+///             pipewire::RegistryEvent::Added(object) => {
+///                 println!("added {} ({:?})", object.id, object.kind);
+///             }
+///             pipewire::RegistryEvent::Removed(id) => {
+///                 println!("removed {}", id);
+///             }
+///             _ => (),
+///         }
+///     }
+/// }
+///
+/// # #[tokio::main] async fn main() -> anyhow::Result<()> {
+/// let runtime = audio_device::runtime::Runtime::new()?;
+/// let bg = ste::spawn();
+/// bg.submit_async(runtime.wrap(list())).await?;
+/// bg.join();
+/// # Ok(()) }
+/// ```
+pub struct Registry {
+    registry: ptr::NonNull<pw::pw_registry>,
+    connection: Connection,
+    // NB: registered with the registry, so it must have a stable address and
+    // outlive it.
+    listener: Box<Listener>,
+}
+
+impl Registry {
+    /// Connect to the default PipeWire server and start listening to its
+    /// registry.
+    pub fn connect() -> Result<Self> {
+        let connection = Connection::connect()?;
+
+        unsafe {
+            let registry =
+                ptr::NonNull::new(get_registry(connection.core.as_ptr())).ok_or_else(last_error)?;
+
+            let mut this = Self {
+                registry,
+                connection,
+                listener: Box::new(Listener {
+                    hook: mem::zeroed(),
+                    events: VecDeque::new(),
+                }),
+            };
+
+            add_listener(this.registry.as_ptr(), &mut this.listener);
+            Ok(this)
+        }
+    }
+
+    /// Wait for the next event of the registry.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless called inside of the audio runtime.
+    pub async fn next(&mut self) -> Result<RegistryEvent> {
+        future::poll_fn(|cx| self.poll_next(cx)).await
+    }
+
+    pub(crate) fn poll_next(&mut self, cx: &mut Context<'_>) -> Poll<Result<RegistryEvent>> {
+        loop {
+            if let Some(event) = self.listener.events.pop_front() {
+                return Poll::Ready(Ok(event));
+            }
+
+            self.connection.dispatch()?;
+
+            if !self.listener.events.is_empty() {
+                continue;
+            }
+
+            match self.connection.poll_events(cx) {
+                Poll::Ready(result) => result?,
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl Drop for Registry {
+    fn drop(&mut self) {
+        unsafe {
+            pw::pw_proxy_destroy(self.registry.as_ptr() as *mut pw::pw_proxy);
+        }
+    }
+}
+
+// Safety: the registry exclusively owns its connection, which is only ever
+// driven through a mutable reference to the registry.
+unsafe impl Send for Registry {}
+
+/// Enumerate the audio nodes of the default server, blocking until the
+/// server has announced all of its objects.
+///
+/// The server doesn't announce which nodes are the defaults through the
+/// registry, so none of the devices are marked as such.
+pub(crate) fn device_infos() -> Result<Vec<DeviceInfo>> {
+    // NB: registered with the core, so it must outlive the connection.
+    let mut core_listener = Box::new(CoreListener {
+        hook: unsafe { mem::zeroed() },
+        seq: None,
+        done: Cell::new(false),
+        error: Cell::new(None),
+    });
+
+    let mut registry = Registry::connect()?;
+
+    unsafe {
+        let core = registry.connection.core.as_ptr();
+        add_core_listener(core, &mut core_listener);
+        // NB: the server answers a sync once it has handled everything before
+        // it, which includes announcing all the current global objects.
+        core_listener.seq = Some(sync(core)?);
+    }
+
+    while !core_listener.done.get() {
+        if let Some(res) = core_listener.error.get() {
+            return Err(Error::Sys(Errno::new(-res)));
+        }
+
+        registry.connection.wait()?;
+    }
+
+    let mut nodes = Vec::new();
+
+    for event in registry.listener.events.drain(..) {
+        match event {
+            RegistryEvent::Added(object) if object.kind == ObjectKind::Node => {
+                nodes.push(object);
+            }
+            RegistryEvent::Removed(id) => {
+                nodes.retain(|object| object.id != id);
+            }
+            _ => (),
+        }
+    }
+
+    Ok(nodes.iter().filter_map(device_info).collect())
+}
+
+fn device_info(object: &GlobalObject) -> Option<DeviceInfo> {
+    let get = |key: &[u8]| {
+        let key = CStr::from_bytes_with_nul(key).ok()?;
+        object
+            .properties
+            .get(key)
+            .map(|value| value.to_string_lossy())
+    };
+
+    let direction = match get(KEY_MEDIA_CLASS)?.as_ref() {
+        "Audio/Sink" => Direction::Output,
+        "Audio/Source" => Direction::Input,
+        "Audio/Duplex" => Direction::Duplex,
+        _ => return None,
+    };
+
+    let id = get(KEY_NODE_NAME)?.into_owned();
+
+    let name = match get(KEY_NODE_DESCRIPTION) {
+        Some(description) => description.into_owned(),
+        None => id.clone(),
+    };
+
+    let channels = get(KEY_AUDIO_CHANNELS).and_then(|c| c.parse::<usize>().ok());
+    let rate = get(KEY_AUDIO_RATE).and_then(|r| r.parse::<u32>().ok());
+
+    Some(DeviceInfo {
+        id,
+        name,
+        direction,
+        is_default: false,
+        // NB: filters exchange 32-bit float samples, which are converted by
+        // the server into the format of the node.
+        formats: vec![SampleFormat::F32(Endian::NATIVE)],
+        channels: channels.map(|c| (c, c)),
+        sample_rates: rate.map(|r| (r, r)),
+    })
+}
+
+/// State shared with the core listener used by [device_infos].
+struct CoreListener {
+    hook: pw::spa_hook,
+    /// The sequence number of the sync being waited for.
+    seq: Option<c::c_int>,
+    // NB: cells, since they're written by the callbacks while the loop
+    // waiting for them holds a reference to the listener.
+    done: Cell<bool>,
+    error: Cell<Option<c::c_int>>,
+}
+
+/// Add the given listener to the core.
+unsafe fn add_core_listener(core: *mut pw::pw_core, listener: &mut CoreListener) {
+    let iface = &*(core as *const pw::spa_interface);
+    let methods = iface.cb.funcs as *const pw::pw_core_methods;
+
+    if let Some(add_listener) = (*methods).add_listener {
+        add_listener(
+            iface.cb.data,
+            &mut listener.hook,
+            &CORE_EVENTS,
+            listener as *mut CoreListener as *mut c::c_void,
+        );
+    }
+}
+
+/// Ask the server for a round trip, returning the sequence number that the
+/// done event is emitted with.
+unsafe fn sync(core: *mut pw::pw_core) -> Result<c::c_int> {
+    let iface = &*(core as *const pw::spa_interface);
+    let methods = iface.cb.funcs as *const pw::pw_core_methods;
+
+    match (*methods).sync {
+        Some(sync) => super::connection::check(sync(iface.cb.data, ID_CORE, 0)),
+        None => Err(Error::Sys(Errno::new(libc::ENOTSUP))),
+    }
+}
+
+unsafe extern "C" fn core_done_callback(data: *mut c::c_void, id: u32, seq: c::c_int) {
+    let listener = &*(data as *const CoreListener);
+
+    if id == ID_CORE && listener.seq == Some(seq) {
+        listener.done.set(true);
+    }
+}
+
+unsafe extern "C" fn core_error_callback(
+    data: *mut c::c_void,
+    id: u32,
+    _: c::c_int,
+    res: c::c_int,
+    _: *const c::c_char,
+) {
+    let listener = &*(data as *const CoreListener);
+
+    // NB: errors on the core are fatal to the connection.
+    if id == ID_CORE {
+        listener.error.set(Some(res));
+    }
+}
+
+/// State shared with the registry listener.
+struct Listener {
+    hook: pw::spa_hook,
+    events: VecDeque<RegistryEvent>,
+}
+
+/// Get the registry of the given core.
+unsafe fn get_registry(core: *mut pw::pw_core) -> *mut pw::pw_registry {
+    let iface = &*(core as *const pw::spa_interface);
+    let methods = iface.cb.funcs as *const pw::pw_core_methods;
+
+    match (*methods).get_registry {
+        Some(get_registry) => get_registry(iface.cb.data, VERSION_REGISTRY, 0),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Add the given listener to the registry.
+unsafe fn add_listener(registry: *mut pw::pw_registry, listener: &mut Listener) {
+    let iface = &*(registry as *const pw::spa_interface);
+    let methods = iface.cb.funcs as *const pw::pw_registry_methods;
+
+    if let Some(add_listener) = (*methods).add_listener {
+        add_listener(
+            iface.cb.data,
+            &mut listener.hook,
+            &REGISTRY_EVENTS,
+            listener as *mut Listener as *mut c::c_void,
+        );
+    }
+}
+
+unsafe extern "C" fn global_callback(
+    data: *mut c::c_void,
+    id: u32,
+    _: u32,
+    type_: *const c::c_char,
+    version: u32,
+    props: *const pw::spa_dict,
+) {
+    let listener = &mut *(data as *mut Listener);
+
+    let kind = match CStr::from_ptr(type_) {
+        name if name.to_bytes() == TYPE_NODE => ObjectKind::Node,
+        name if name.to_bytes() == TYPE_PORT => ObjectKind::Port,
+        name if name.to_bytes() == TYPE_DEVICE => ObjectKind::Device,
+        name => ObjectKind::Other(name.to_owned()),
+    };
+
+    let properties = if props.is_null() {
+        PropertyList::new()
+    } else {
+        PropertyList::from_dict(props)
+    };
+
+    listener
+        .events
+        .push_back(RegistryEvent::Added(GlobalObject {
+            id,
+            kind,
+            version,
+            properties,
+        }));
+}
+
+unsafe extern "C" fn global_remove_callback(data: *mut c::c_void, id: u32) {
+    let listener = &mut *(data as *mut Listener);
+    listener.events.push_back(RegistryEvent::Removed(id));
+}
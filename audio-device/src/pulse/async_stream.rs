@@ -0,0 +1,637 @@
+use std::cell::Cell;
+use std::ffi::CStr;
+use std::future;
+use std::marker;
+use std::mem;
+use std::pin::Pin;
+use std::ptr;
+use std::task::{Context as TaskContext, Poll};
+use std::time::Duration;
+
+use pulse_sys as pulse;
+
+use crate::buf_io;
+use crate::libc as c;
+use crate::pulse::connection::Connection;
+use crate::pulse::introspect;
+use crate::pulse::{
+    ChannelVolume, Error, Result, Sample, SinkInputInfo, StreamDirection, StreamState, TimingInfo,
+};
+
+/// Flags which make the server keep the timing information of the stream up to
+/// date.
+const TIMING_FLAGS: pulse::pa_stream_flags_t =
+    pulse::PA_STREAM_INTERPOLATE_TIMING | pulse::PA_STREAM_AUTO_TIMING_UPDATE;
+
+/// An asynchronous stream, connected to the default device of the default
+/// server.
+///
+/// The stream is driven by the audio [Runtime][crate::runtime::Runtime], so it
+/// must be used inside of it.
+///
+/// See [AsyncStream::connect].
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::pulse;
+/// use std::ffi::CString;
+///
+/// async fn play() -> anyhow::Result<()> {
+///     let name = CString::new("My Application")?;
+///     let stream_name = CString::new("Playback")?;
+///
+///     let mut stream = pulse::AsyncStream::<f32>::connect(
+///         &name,
+///         &stream_name,
+///         pulse::StreamDirection::Playback,
+///         2,
+///         48000,
+///     )
+///     .await?;
+///
+///     let samples = vec![0.0f32; 2 * 48000];
+///     stream.write_interleaved(audio::wrap::interleaved(&samples[..], 2)).await?;
+///     stream.drain().await?;
+///     Ok(())
+/// }
+///
+/// # #[tokio::main] async fn main() -> anyhow::Result<()> {
+/// let runtime = audio_device::runtime::Runtime::new()?;
+/// let bg = ste::spawn();
+/// bg.submit_async(runtime.wrap(play())).await?;
+/// bg.join();
+/// # Ok(()) }
+/// ```
+pub struct AsyncStream<T> {
+    handle: ptr::NonNull<pulse::pa_stream>,
+    connection: Connection,
+    channels: usize,
+    rate: u32,
+    // Number of bytes which have already been read from the current fragment
+    // of a record stream.
+    offset: usize,
+    _marker: marker::PhantomData<T>,
+}
+
+impl<T> AsyncStream<T>
+where
+    T: Sample,
+{
+    /// Connect a stream with the given direction to the default device of the
+    /// default server, completing once the stream is ready.
+    ///
+    /// The `name` identifies the application to the server, while
+    /// `stream_name` describes what the stream is used for.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless called inside of the audio runtime.
+    pub async fn connect(
+        name: &CStr,
+        stream_name: &CStr,
+        direction: StreamDirection,
+        channels: u8,
+        rate: u32,
+    ) -> Result<Self> {
+        let mut connection = Connection::new(name)?;
+        future::poll_fn(|cx| connection.poll_ready(cx)).await?;
+
+        let spec = pulse::pa_sample_spec {
+            format: T::FORMAT as pulse::pa_sample_format_t,
+            rate,
+            channels,
+        };
+
+        let mut stream = unsafe {
+            let handle = pulse::pa_stream_new(
+                connection.context.handle.as_ptr(),
+                stream_name.as_ptr(),
+                &spec,
+                ptr::null(),
+            );
+
+            let handle = match ptr::NonNull::new(handle) {
+                Some(handle) => handle,
+                None => return Err(connection.context.last_error()),
+            };
+
+            Self {
+                handle,
+                connection,
+                channels: channels as usize,
+                rate,
+                offset: 0,
+                _marker: marker::PhantomData,
+            }
+        };
+
+        unsafe {
+            let result = match direction {
+                StreamDirection::Playback => pulse::pa_stream_connect_playback(
+                    stream.handle.as_ptr(),
+                    ptr::null(),
+                    ptr::null(),
+                    TIMING_FLAGS,
+                    ptr::null(),
+                    ptr::null_mut(),
+                ),
+                StreamDirection::Record => pulse::pa_stream_connect_record(
+                    stream.handle.as_ptr(),
+                    ptr::null(),
+                    ptr::null(),
+                    TIMING_FLAGS,
+                ),
+            };
+
+            if result < 0 {
+                return Err(stream.connection.context.last_error());
+            }
+        }
+
+        future::poll_fn(|cx| stream.poll_stream_ready(cx)).await?;
+        Ok(stream)
+    }
+
+    /// The number of channels of the stream.
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// The sample rate of the stream.
+    pub fn rate(&self) -> u32 {
+        self.rate
+    }
+
+    /// Get the current state of the stream.
+    pub fn state(&self) -> Result<StreamState> {
+        unsafe {
+            let state = pulse::pa_stream_get_state(self.handle.as_ptr());
+            StreamState::from_value(state).ok_or(Error::BadStreamState(state))
+        }
+    }
+
+    /// Write an interleaved buffer to a playback stream.
+    ///
+    /// Frames are written as the server requests them, and this completes
+    /// once all of them have been written.
+    pub async fn write_interleaved<B>(&mut self, mut buf: B) -> Result<()>
+    where
+        B: audio_core::ReadBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBuf<Sample = T>,
+    {
+        future::poll_fn(|cx| self.poll_write_interleaved(cx, &mut buf)).await
+    }
+
+    fn poll_write_interleaved<B>(
+        &mut self,
+        cx: &mut TaskContext<'_>,
+        mut buf: B,
+    ) -> Poll<Result<()>>
+    where
+        B: audio_core::ReadBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBuf<Sample = T>,
+    {
+        self.check_channels(&buf)?;
+        let frame_size = self.channels * mem::size_of::<T>();
+
+        loop {
+            self.connection.dispatch()?;
+            self.check_state()?;
+
+            while buf.has_remaining() {
+                let writable = unsafe { pulse::pa_stream_writable_size(self.handle.as_ptr()) };
+                let frames = usize::min(
+                    writable / frame_size,
+                    buf_io::readable_frames(&buf, self.channels),
+                );
+
+                if frames == 0 {
+                    break;
+                }
+
+                let data = &buf.as_interleaved()[..frames * self.channels];
+
+                unsafe {
+                    let result = pulse::pa_stream_write(
+                        self.handle.as_ptr(),
+                        data.as_ptr().cast(),
+                        mem::size_of_val(data),
+                        None,
+                        0,
+                        pulse::PA_SEEK_RELATIVE,
+                    );
+
+                    if result < 0 {
+                        return Poll::Ready(Err(self.connection.context.last_error()));
+                    }
+                }
+
+                buf.advance(frames);
+            }
+
+            if !buf.has_remaining() {
+                // NB: written data is sent to the server when the main loop
+                // is dispatched.
+                self.connection.dispatch()?;
+                return Poll::Ready(Ok(()));
+            }
+
+            if !self.connection.poll_descriptors(cx)? {
+                return Poll::Pending;
+            }
+        }
+    }
+
+    /// Read into an interleaved buffer from a record stream.
+    ///
+    /// Completes once the remaining frames of the buffer have been filled
+    /// with captured audio.
+    pub async fn read_interleaved<B>(&mut self, mut buf: B) -> Result<()>
+    where
+        B: audio_core::WriteBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBufMut<Sample = T>,
+    {
+        future::poll_fn(|cx| self.poll_read_interleaved(cx, &mut buf)).await
+    }
+
+    fn poll_read_interleaved<B>(&mut self, cx: &mut TaskContext<'_>, mut buf: B) -> Poll<Result<()>>
+    where
+        B: audio_core::WriteBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBufMut<Sample = T>,
+    {
+        self.check_channels(&buf)?;
+        let frame_size = self.channels * mem::size_of::<T>();
+
+        loop {
+            self.connection.dispatch()?;
+            self.check_state()?;
+
+            while buf.has_remaining_mut() {
+                let capacity = buf_io::writable_frames(&buf, self.channels);
+
+                if capacity == 0 {
+                    return Poll::Ready(Ok(()));
+                }
+
+                let mut data = ptr::null();
+                let mut len = 0;
+
+                unsafe {
+                    if pulse::pa_stream_peek(self.handle.as_ptr(), &mut data, &mut len) < 0 {
+                        return Poll::Ready(Err(self.connection.context.last_error()));
+                    }
+                }
+
+                if len == 0 {
+                    break;
+                }
+
+                // NB: a fragment without data is a hole in the stream, which
+                // is skipped.
+                let frames = if data.is_null() {
+                    0
+                } else {
+                    usize::min((len - self.offset) / frame_size, capacity)
+                };
+
+                unsafe {
+                    let from = data.cast::<u8>().add(self.offset).cast::<T>();
+                    let to = buf.as_interleaved_mut().as_mut_ptr();
+                    ptr::copy_nonoverlapping(from, to, frames * self.channels);
+                }
+
+                self.offset += frames * frame_size;
+
+                // NB: fragments can only be dropped in full, so if the buffer
+                // couldn't fit the whole fragment we keep track of how far
+                // into it we've read.
+                if data.is_null() || len - self.offset < frame_size {
+                    unsafe {
+                        if pulse::pa_stream_drop(self.handle.as_ptr()) < 0 {
+                            return Poll::Ready(Err(self.connection.context.last_error()));
+                        }
+                    }
+
+                    self.offset = 0;
+                }
+
+                buf.advance_mut(frames);
+            }
+
+            if !buf.has_remaining_mut() {
+                return Poll::Ready(Ok(()));
+            }
+
+            if !self.connection.poll_descriptors(cx)? {
+                return Poll::Pending;
+            }
+        }
+    }
+
+    /// Complete once all data written to a playback stream has been played.
+    pub async fn drain(&mut self) -> Result<()> {
+        let operation = unsafe {
+            let operation = pulse::pa_stream_drain(self.handle.as_ptr(), None, ptr::null_mut());
+            self.connection.operation(operation)?
+        };
+
+        self.connection.wait(operation).await
+    }
+
+    /// Get the index of the stream on the server.
+    ///
+    /// For playback streams this is the index of its sink input, as listed by
+    /// [Introspector::sink_inputs][super::Introspector::sink_inputs].
+    pub fn index(&self) -> u32 {
+        unsafe { pulse::pa_stream_get_index(self.handle.as_ptr()) }
+    }
+
+    /// Move a playback stream to the sink with the given index.
+    ///
+    /// Available sinks are listed by
+    /// [Introspector::sinks][super::Introspector::sinks].
+    pub async fn move_to_sink(&mut self, sink: u32) -> Result<()> {
+        let index = self.index();
+
+        self.connection
+            .complete(|context, callback, userdata| unsafe {
+                pulse::pa_context_move_sink_input_by_index(context, index, sink, callback, userdata)
+            })
+            .await
+    }
+
+    /// Pause the stream, which stops playback or capture until it's
+    /// [uncorked][AsyncStream::uncork].
+    pub async fn cork(&mut self) -> Result<()> {
+        self.set_corked(true).await
+    }
+
+    /// Resume a stream which was [corked][AsyncStream::cork].
+    pub async fn uncork(&mut self) -> Result<()> {
+        self.set_corked(false).await
+    }
+
+    /// Test if the stream is corked.
+    pub fn is_corked(&self) -> Result<bool> {
+        let result = unsafe { pulse::pa_stream_is_corked(self.handle.as_ptr()) };
+
+        if result < 0 {
+            return Err(Error::Pulse(-result));
+        }
+
+        Ok(result != 0)
+    }
+
+    async fn set_corked(&mut self, corked: bool) -> Result<()> {
+        self.complete(|stream, callback, userdata| unsafe {
+            pulse::pa_stream_cork(stream, corked as c::c_int, callback, userdata)
+        })
+        .await
+    }
+
+    /// Get the volume of a playback stream, unless it doesn't support one.
+    pub async fn volume(&mut self) -> Result<Option<ChannelVolume>> {
+        let index = self.index();
+
+        let inputs = introspect::list::<SinkInputInfo, _>(
+            &mut self.connection,
+            |context, userdata| unsafe {
+                pulse::pa_context_get_sink_input_info(
+                    context,
+                    index,
+                    Some(introspect::list_callback::<SinkInputInfo>),
+                    userdata,
+                )
+            },
+        )
+        .await?;
+
+        Ok(inputs.into_iter().next().and_then(|input| input.volume))
+    }
+
+    /// Set the volume of a playback stream.
+    pub async fn set_volume(&mut self, volume: &ChannelVolume) -> Result<()> {
+        let index = self.index();
+
+        self.connection
+            .complete(|context, callback, userdata| unsafe {
+                pulse::pa_context_set_sink_input_volume(
+                    context,
+                    index,
+                    &volume.raw,
+                    callback,
+                    userdata,
+                )
+            })
+            .await
+    }
+
+    /// Get the latency of the stream.
+    ///
+    /// For playback streams this is the time it takes for audio which is
+    /// written now to be played, and for record streams the time since the
+    /// oldest audio which has not been read was captured. A negative latency
+    /// of a record stream is reported as zero.
+    ///
+    /// This is interpolated from the last [timing info][AsyncStream::timing_info]
+    /// received, and errors if none has been received yet.
+    pub fn latency(&self) -> Result<Duration> {
+        let mut usec = 0;
+        let mut negative = 0;
+
+        let result =
+            unsafe { pulse::pa_stream_get_latency(self.handle.as_ptr(), &mut usec, &mut negative) };
+
+        if result < 0 {
+            return Err(Error::Pulse(-result));
+        }
+
+        if negative != 0 {
+            return Ok(Duration::ZERO);
+        }
+
+        Ok(Duration::from_micros(usec))
+    }
+
+    /// Get the playback time of the stream, which is the position of the
+    /// audio currently being played or captured.
+    ///
+    /// This is interpolated from the last [timing info][AsyncStream::timing_info]
+    /// received, and errors if none has been received yet.
+    pub fn time(&self) -> Result<Duration> {
+        let mut usec = 0;
+
+        let result = unsafe { pulse::pa_stream_get_time(self.handle.as_ptr(), &mut usec) };
+
+        if result < 0 {
+            return Err(Error::Pulse(-result));
+        }
+
+        Ok(Duration::from_micros(usec))
+    }
+
+    /// Get the timing information last received from the server, if any.
+    ///
+    /// Timing information is updated automatically, but an update can be
+    /// requested through [AsyncStream::update_timing_info].
+    pub fn timing_info(&self) -> Option<TimingInfo> {
+        unsafe {
+            let info = pulse::pa_stream_get_timing_info(self.handle.as_ptr());
+
+            if info.is_null() {
+                None
+            } else {
+                Some(TimingInfo::from_raw(&*info))
+            }
+        }
+    }
+
+    /// Request updated timing information from the server, completing once
+    /// it's been received.
+    pub async fn update_timing_info(&mut self) -> Result<()> {
+        self.complete(|stream, callback, userdata| unsafe {
+            pulse::pa_stream_update_timing_info(stream, callback, userdata)
+        })
+        .await
+    }
+
+    /// Start an operation on the stream which reports whether it succeeded,
+    /// and wait for it to complete.
+    async fn complete<F>(&mut self, start: F) -> Result<()>
+    where
+        F: FnOnce(
+            *mut pulse::pa_stream,
+            pulse::pa_stream_success_cb_t,
+            *mut c::c_void,
+        ) -> *mut pulse::pa_operation,
+    {
+        let success = Box::new(Cell::new(false));
+
+        let operation = start(
+            self.handle.as_ptr(),
+            Some(success_callback),
+            &*success as *const Cell<bool> as *mut c::c_void,
+        );
+
+        let operation = self.connection.operation(operation)?;
+        self.connection.wait(operation).await?;
+
+        if !success.get() {
+            return Err(self.connection.context.last_error());
+        }
+
+        Ok(())
+    }
+
+    fn poll_stream_ready(&mut self, cx: &mut TaskContext<'_>) -> Poll<Result<()>> {
+        loop {
+            self.connection.dispatch()?;
+
+            if let StreamState::Ready = self.check_state()? {
+                return Poll::Ready(Ok(()));
+            }
+
+            if !self.connection.poll_descriptors(cx)? {
+                return Poll::Pending;
+            }
+        }
+    }
+
+    /// Get the state of the stream, erroring if it has failed.
+    fn check_state(&self) -> Result<StreamState> {
+        match self.state()? {
+            StreamState::Failed | StreamState::Terminated => {
+                Err(self.connection.context.last_error())
+            }
+            state => Ok(state),
+        }
+    }
+
+    fn check_channels<B>(&self, buf: &B) -> Result<()>
+    where
+        B: audio_core::Buf,
+    {
+        if buf.channels() != self.channels {
+            return Err(Error::ChannelsMismatch {
+                actual: buf.channels(),
+                expected: self.channels,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> audio_core::AsyncWriteBuf for AsyncStream<T>
+where
+    T: Sample,
+{
+    type Sample = T;
+    type Error = Error;
+
+    fn poll_write_buf<B>(
+        self: Pin<&mut Self>,
+        cx: &mut TaskContext<'_>,
+        buf: B,
+    ) -> Poll<Result<(), Error>>
+    where
+        B: audio_core::ReadBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBuf<Sample = T>,
+    {
+        self.get_mut().poll_write_interleaved(cx, buf)
+    }
+}
+
+impl<T> audio_core::AsyncReadBuf for AsyncStream<T>
+where
+    T: Sample,
+{
+    type Sample = T;
+    type Error = Error;
+
+    fn poll_read_buf<B>(
+        self: Pin<&mut Self>,
+        cx: &mut TaskContext<'_>,
+        buf: B,
+    ) -> Poll<Result<(), Error>>
+    where
+        B: audio_core::WriteBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBufMut<Sample = T>,
+    {
+        self.get_mut().poll_read_interleaved(cx, buf)
+    }
+}
+
+impl<T> Drop for AsyncStream<T> {
+    fn drop(&mut self) {
+        unsafe {
+            pulse::pa_stream_disconnect(self.handle.as_ptr());
+            pulse::pa_stream_unref(self.handle.as_ptr());
+        }
+    }
+}
+
+/// Callback for stream operations which report whether they succeeded,
+/// storing the outcome in a `Cell<bool>`.
+unsafe extern "C" fn success_callback(
+    _: *mut pulse::pa_stream,
+    success: c::c_int,
+    userdata: *mut c::c_void,
+) {
+    let outcome = &*(userdata as *const Cell<bool>);
+    outcome.set(success != 0);
+}
+
+// Note: the sample type is only used as a marker.
+impl<T> Unpin for AsyncStream<T> {}
+
+// Safety: the stream exclusively owns its main loop, which is only ever
+// iterated through a mutable reference to the stream.
+unsafe impl<T> Send for AsyncStream<T> {}
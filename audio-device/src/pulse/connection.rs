@@ -0,0 +1,263 @@
+use std::cell::Cell;
+use std::ffi::CStr;
+use std::future;
+use std::ptr;
+use std::slice;
+use std::task::{Context as TaskContext, Poll};
+
+use pulse_sys as pulse;
+
+use crate::libc as c;
+use crate::pulse::{Context, ContextState, MainLoop, Result};
+use crate::unix::AsyncPoll;
+
+/// A connection to the default server whose main loop is driven by the audio
+/// [Runtime][crate::runtime::Runtime].
+///
+/// The main loop is only ever iterated without blocking. Its descriptors are
+/// captured through a custom poll function, and are then waited on through
+/// the poll driver of the runtime.
+///
+/// Note that timer events of the main loop are only dispatched once the
+/// connection is woken up by one of its descriptors.
+pub(super) struct Connection {
+    // Registrations for the descriptors last polled by the main loop.
+    polls: Vec<(c::pollfd, AsyncPoll)>,
+    pub(super) context: Context,
+    main: MainLoop,
+    // NB: this is written to by the poll function of the main loop, so it
+    // must outlive it and have a stable address.
+    #[allow(clippy::box_collection)]
+    descriptors: Box<Vec<c::pollfd>>,
+}
+
+impl Connection {
+    /// Start connecting a new context to the default server.
+    pub(super) fn new(name: &CStr) -> Result<Self> {
+        let mut main = MainLoop::new();
+        let mut context = main.context(name);
+        let mut descriptors = Box::new(Vec::new());
+
+        unsafe {
+            pulse::pa_mainloop_set_poll_func(
+                main.handle.as_ptr(),
+                Some(poll_func),
+                &mut *descriptors as *mut Vec<c::pollfd> as *mut c::c_void,
+            );
+        }
+
+        context.connect()?;
+
+        Ok(Self {
+            polls: Vec::new(),
+            context,
+            main,
+            descriptors,
+        })
+    }
+
+    /// Dispatch all events of the main loop which are ready without blocking.
+    pub(super) fn dispatch(&mut self) -> Result<()> {
+        loop {
+            let dispatched = unsafe {
+                pulse::pa_mainloop_iterate(self.main.handle.as_ptr(), 0, ptr::null_mut())
+            };
+
+            if dispatched < 0 {
+                return Err(self.context.last_error());
+            }
+
+            if dispatched == 0 {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Register interest in the descriptors last polled by the main loop,
+    /// returning `true` if any of them are ready so that the main loop should
+    /// be dispatched again.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless an audio runtime is available.
+    pub(super) fn poll_descriptors(&mut self, cx: &mut TaskContext<'_>) -> Result<bool> {
+        let descriptors = &*self.descriptors;
+
+        self.polls.retain(|(fd, _)| {
+            descriptors
+                .iter()
+                .any(|d| d.fd == fd.fd && d.events == fd.events)
+        });
+
+        for d in descriptors {
+            if !self
+                .polls
+                .iter()
+                .any(|(fd, _)| d.fd == fd.fd && d.events == fd.events)
+            {
+                // Safety: descriptors are unregistered as soon as the main
+                // loop stops polling them.
+                let poll = unsafe { AsyncPoll::new(*d)? };
+                self.polls.push((*d, poll));
+            }
+        }
+
+        let mut ready = false;
+
+        for (_, poll) in &self.polls {
+            if let Poll::Ready(guard) = poll.poll_returned_events(cx) {
+                guard?;
+                ready = true;
+            }
+        }
+
+        Ok(ready)
+    }
+
+    /// Wrap an operation which was just started, erroring if it couldn't be
+    /// started.
+    pub(super) fn operation(&self, operation: *mut pulse::pa_operation) -> Result<Operation> {
+        Operation::new(&self.context, operation)
+    }
+
+    /// Wait for the given operation to complete.
+    pub(super) async fn wait(&mut self, mut operation: Operation) -> Result<()> {
+        future::poll_fn(|cx| self.poll_operation(cx, &mut operation)).await
+    }
+
+    /// Start an operation which reports whether it succeeded through a
+    /// success callback, and wait for it to complete.
+    pub(super) async fn complete<F>(&mut self, start: F) -> Result<()>
+    where
+        F: FnOnce(
+            *mut pulse::pa_context,
+            pulse::pa_context_success_cb_t,
+            *mut c::c_void,
+        ) -> *mut pulse::pa_operation,
+    {
+        let success = Box::new(Cell::new(false));
+
+        let operation = start(
+            self.context.handle.as_ptr(),
+            Some(success_callback),
+            &*success as *const Cell<bool> as *mut c::c_void,
+        );
+
+        let operation = self.operation(operation)?;
+        self.wait(operation).await?;
+
+        if !success.get() {
+            return Err(self.context.last_error());
+        }
+
+        Ok(())
+    }
+
+    fn poll_operation(
+        &mut self,
+        cx: &mut TaskContext<'_>,
+        operation: &mut Operation,
+    ) -> Poll<Result<()>> {
+        loop {
+            self.dispatch()?;
+
+            match operation.state() {
+                pulse::PA_OPERATION_DONE => return Poll::Ready(Ok(())),
+                pulse::PA_OPERATION_CANCELLED => {
+                    return Poll::Ready(Err(self.context.last_error()));
+                }
+                _ => (),
+            }
+
+            if !self.poll_descriptors(cx)? {
+                return Poll::Pending;
+            }
+        }
+    }
+
+    /// Poll until the context is ready.
+    pub(super) fn poll_ready(&mut self, cx: &mut TaskContext<'_>) -> Poll<Result<()>> {
+        loop {
+            self.dispatch()?;
+
+            match self.context.state()? {
+                ContextState::Ready => return Poll::Ready(Ok(())),
+                ContextState::Failed | ContextState::Terminated => {
+                    return Poll::Ready(Err(self.context.last_error()));
+                }
+                _ => (),
+            }
+
+            if !self.poll_descriptors(cx)? {
+                return Poll::Pending;
+            }
+        }
+    }
+}
+
+/// Poll function for the main loop which captures the descriptors that are
+/// polled, without ever blocking.
+unsafe extern "C" fn poll_func(
+    ufds: *mut c::pollfd,
+    nfds: c::c_ulong,
+    _: c::c_int,
+    userdata: *mut c::c_void,
+) -> c::c_int {
+    let descriptors = &mut *(userdata as *mut Vec<c::pollfd>);
+    descriptors.clear();
+    descriptors.extend_from_slice(slice::from_raw_parts(ufds, nfds as usize));
+    c::poll(ufds, nfds as c::nfds_t, 0)
+}
+
+/// A pending operation.
+///
+/// If the operation is still running when dropped it's cancelled, which
+/// ensures that its callback is never called after any data it refers to has
+/// been freed.
+pub(super) struct Operation(ptr::NonNull<pulse::pa_operation>);
+
+impl Operation {
+    /// Wrap an operation which was just started on the given context,
+    /// erroring if it couldn't be started.
+    pub(super) fn new(context: &Context, operation: *mut pulse::pa_operation) -> Result<Self> {
+        match ptr::NonNull::new(operation) {
+            Some(operation) => Ok(Self(operation)),
+            None => Err(context.last_error()),
+        }
+    }
+
+    pub(super) fn state(&self) -> pulse::pa_operation_state_t {
+        unsafe { pulse::pa_operation_get_state(self.0.as_ptr()) }
+    }
+}
+
+impl Drop for Operation {
+    fn drop(&mut self) {
+        unsafe {
+            if self.state() == pulse::PA_OPERATION_RUNNING {
+                pulse::pa_operation_cancel(self.0.as_ptr());
+            }
+
+            pulse::pa_operation_unref(self.0.as_ptr());
+        }
+    }
+}
+
+/// Callback for operations which report whether they succeeded, storing the
+/// outcome in a `Cell<bool>`.
+unsafe extern "C" fn success_callback(
+    _: *mut pulse::pa_context,
+    success: c::c_int,
+    userdata: *mut c::c_void,
+) {
+    let outcome = &*(userdata as *const Cell<bool>);
+    outcome.set(success != 0);
+}
+
+// Safety: the operation is only ever used alongside the connection it belongs
+// to.
+unsafe impl Send for Operation {}
+
+// Safety: the main loop and its context are only ever used through a mutable
+// reference to the connection which owns them.
+unsafe impl Send for Connection {}
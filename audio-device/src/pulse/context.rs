@@ -99,6 +99,11 @@ impl Context {
             ContextState::from_value(state).ok_or_else(|| Error::BadContextState(state))
         }
     }
+
+    /// Get the last error raised by the context.
+    pub(super) fn last_error(&self) -> Error {
+        unsafe { Error::Pulse(pulse::pa_context_errno(self.handle.as_ptr())) }
+    }
 }
 
 impl Drop for Context {
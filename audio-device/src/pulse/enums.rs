@@ -1,3 +1,4 @@
+use audio_core::Endian;
 use pulse_sys as pulse;
 use std::fmt;
 
@@ -66,3 +67,98 @@ decl_enum! {
         Terminated = PA_CONTEXT_TERMINATED,
     }
 }
+
+decl_enum! {
+    /// The state of a stream.
+    #[repr(u32)]
+    pub enum StreamState {
+        /// The stream isn't connected yet.
+        Unconnected = PA_STREAM_UNCONNECTED,
+        /// The stream is being created.
+        Creating = PA_STREAM_CREATING,
+        /// The stream is established and can be used.
+        Ready = PA_STREAM_READY,
+        /// The stream failed or was disconnected.
+        Failed = PA_STREAM_FAILED,
+        /// The stream was terminated cleanly.
+        Terminated = PA_STREAM_TERMINATED,
+    }
+}
+
+decl_enum! {
+    /// The direction of a stream.
+    #[repr(u32)]
+    pub enum StreamDirection {
+        /// The stream plays audio through a sink.
+        Playback = PA_STREAM_PLAYBACK,
+        /// The stream records audio from a source.
+        Record = PA_STREAM_RECORD,
+    }
+}
+
+decl_enum! {
+    /// The format of the samples in a stream.
+    #[repr(i32)]
+    pub enum SampleFormat {
+        /// Unsigned 8-bit PCM.
+        U8 = PA_SAMPLE_U8,
+        /// 8-bit a-Law.
+        ALaw = PA_SAMPLE_ALAW,
+        /// 8-bit mu-Law.
+        ULaw = PA_SAMPLE_ULAW,
+        /// Signed 16-bit PCM, little endian.
+        S16LE = PA_SAMPLE_S16LE,
+        /// Signed 16-bit PCM, big endian.
+        S16BE = PA_SAMPLE_S16BE,
+        /// 32-bit IEEE floating point, little endian, range -1.0 to 1.0.
+        Float32LE = PA_SAMPLE_FLOAT32LE,
+        /// 32-bit IEEE floating point, big endian, range -1.0 to 1.0.
+        Float32BE = PA_SAMPLE_FLOAT32BE,
+        /// Signed 32-bit PCM, little endian.
+        S32LE = PA_SAMPLE_S32LE,
+        /// Signed 32-bit PCM, big endian.
+        S32BE = PA_SAMPLE_S32BE,
+        /// Signed 24-bit PCM packed, little endian.
+        S24LE = PA_SAMPLE_S24LE,
+        /// Signed 24-bit PCM packed, big endian.
+        S24BE = PA_SAMPLE_S24BE,
+        /// Signed 24-bit PCM in the low bits of a 32-bit container, little endian.
+        S2432LE = PA_SAMPLE_S24_32LE,
+        /// Signed 24-bit PCM in the low bits of a 32-bit container, big endian.
+        S2432BE = PA_SAMPLE_S24_32BE,
+    }
+}
+
+impl SampleFormat {
+    /// Get the backend-agnostic [SampleFormat][audio_core::SampleFormat]
+    /// corresponding to this format, if there is one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio_core::{Endian, SampleFormat};
+    /// use audio_device::pulse;
+    ///
+    /// assert_eq!(pulse::SampleFormat::S16LE.sample_format(), Some(SampleFormat::I16(Endian::Little)));
+    /// assert_eq!(pulse::SampleFormat::S24LE.sample_format(), Some(SampleFormat::I24Packed(Endian::Little)));
+    /// assert_eq!(pulse::SampleFormat::ALaw.sample_format(), None);
+    /// ```
+    pub fn sample_format(self) -> Option<audio_core::SampleFormat> {
+        use audio_core::SampleFormat as F;
+
+        Some(match self {
+            Self::U8 => F::U8,
+            Self::S16LE => F::I16(Endian::Little),
+            Self::S16BE => F::I16(Endian::Big),
+            Self::Float32LE => F::F32(Endian::Little),
+            Self::Float32BE => F::F32(Endian::Big),
+            Self::S32LE => F::I32(Endian::Little),
+            Self::S32BE => F::I32(Endian::Big),
+            Self::S24LE => F::I24Packed(Endian::Little),
+            Self::S24BE => F::I24Packed(Endian::Big),
+            Self::S2432LE => F::I24(Endian::Little),
+            Self::S2432BE => F::I24(Endian::Big),
+            Self::ALaw | Self::ULaw => return None,
+        })
+    }
+}
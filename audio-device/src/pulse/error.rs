@@ -1,6 +1,10 @@
 use crate::libc as c;
 use crate::unix::Errno;
+use crate::ErrorKind;
+use pulse_sys as pulse;
+use std::borrow::Cow;
 use std::cell::Cell;
+use std::ffi::CStr;
 use std::ptr;
 use thiserror::Error;
 
@@ -9,11 +13,8 @@ macro_rules! error {
         let result = $expr;
 
         if result < 0 {
-            let errno = { pulse::pa_context_errno($s.handle.as_ptr()) };
-
-            Err(crate::pulse::Error::Sys(
-                crate::unix::Errno::new(errno),
-            ))
+            let code = { pulse::pa_context_errno($s.handle.as_ptr()) };
+            Err(crate::pulse::Error::Pulse(code))
         } else {
             ffi_error!(result)
         }
@@ -80,13 +81,68 @@ pub enum Error {
     /// System error.
     #[error("system error: {0}")]
     Sys(#[from] Errno),
+    /// An error raised by the audio runtime, like when it has been shut down.
+    #[error("runtime error: {0}")]
+    Runtime(#[from] crate::runtime::Error),
+    /// An error code reported by PulseAudio, like `PA_ERR_ACCESS`.
+    #[error("{} ({0})", strerror(*.0))]
+    Pulse(c::c_int),
     /// Tried to decode bad context state.
     #[error("bad context state identifier `{0}`")]
     BadContextState(c::c_uint),
+    /// Tried to decode bad stream state.
+    #[error("bad stream state identifier `{0}`")]
+    BadStreamState(c::c_uint),
+    /// The background thread of a threaded main loop could not be started.
+    #[error("failed to start main loop thread")]
+    MainLoopStart,
+    /// Error raised when there's a channel count mismatch between a buffer and
+    /// the stream it's being used with.
+    #[error("mismatch in number of channels in buffer; actual = {actual}, expected = {expected}")]
+    ChannelsMismatch {
+        /// The actual number of channels.
+        actual: usize,
+        /// The expected number of channels.
+        expected: usize,
+    },
     /// A custom user error.
     #[error("error: {0}")]
     User(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
 }
 
+impl Error {
+    pub(crate) fn kind(&self) -> ErrorKind {
+        match *self {
+            Self::Sys(errno) => errno.kind(),
+            Self::Runtime(ref error) => error.kind(),
+            Self::Pulse(code) => match code as pulse::pa_error_code {
+                pulse::PA_ERR_ACCESS | pulse::PA_ERR_AUTHKEY => ErrorKind::PermissionDenied,
+                pulse::PA_ERR_INVALID => ErrorKind::InvalidInput,
+                pulse::PA_ERR_NOENTITY | pulse::PA_ERR_INVALIDSERVER => ErrorKind::NotFound,
+                pulse::PA_ERR_CONNECTIONTERMINATED | pulse::PA_ERR_KILLED => {
+                    ErrorKind::Disconnected
+                }
+                pulse::PA_ERR_BUSY => ErrorKind::Busy,
+                _ => ErrorKind::Other,
+            },
+            Self::ChannelsMismatch { .. } => ErrorKind::InvalidInput,
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
+/// Get the message PulseAudio associates with the given error code.
+fn strerror(code: c::c_int) -> Cow<'static, str> {
+    unsafe {
+        let message = pulse::pa_strerror(code);
+
+        if message.is_null() {
+            return Cow::Borrowed("unknown error");
+        }
+
+        CStr::from_ptr(message).to_string_lossy()
+    }
+}
+
 /// Helper result wrapper.
 pub type Result<T, E = Error> = ::std::result::Result<T, E>;
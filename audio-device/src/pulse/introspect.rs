@@ -0,0 +1,570 @@
+use std::ffi::{CStr, CString};
+use std::ptr;
+
+use pulse_sys as pulse;
+
+use crate::libc as c;
+use crate::pulse::connection::{Connection, Operation};
+use crate::pulse::{
+    ChannelVolume, Context, ContextState, MainLoop, PropertyList, Result, SampleFormat,
+};
+use crate::{DeviceInfo, Direction};
+
+/// The index used by PulseAudio to indicate that an object is missing.
+const INVALID_INDEX: u32 = u32::MAX;
+
+/// Information on a sink, which is a device that audio is played through.
+///
+/// See [Introspector::sinks].
+#[non_exhaustive]
+pub struct SinkInfo {
+    /// The index of the sink.
+    pub index: u32,
+    /// The name of the sink.
+    pub name: CString,
+    /// A human readable description of the sink.
+    pub description: CString,
+    /// The sample format of the sink.
+    pub format: Option<SampleFormat>,
+    /// The number of channels of the sink.
+    pub channels: u8,
+    /// The sample rate of the sink.
+    pub rate: u32,
+    /// The volume of the sink.
+    pub volume: ChannelVolume,
+    /// If the sink is muted.
+    pub mute: bool,
+    /// The index of the source which monitors the sink.
+    pub monitor_source: Option<u32>,
+    /// The properties of the sink.
+    pub properties: PropertyList,
+}
+
+/// Information on a source, which is a device that audio is captured from.
+///
+/// See [Introspector::sources].
+#[non_exhaustive]
+pub struct SourceInfo {
+    /// The index of the source.
+    pub index: u32,
+    /// The name of the source.
+    pub name: CString,
+    /// A human readable description of the source.
+    pub description: CString,
+    /// The sample format of the source.
+    pub format: Option<SampleFormat>,
+    /// The number of channels of the source.
+    pub channels: u8,
+    /// The sample rate of the source.
+    pub rate: u32,
+    /// The volume of the source.
+    pub volume: ChannelVolume,
+    /// If the source is muted.
+    pub mute: bool,
+    /// The index of the sink that the source monitors, if it's a monitor.
+    pub monitor_of_sink: Option<u32>,
+    /// The properties of the source.
+    pub properties: PropertyList,
+}
+
+/// Information on a sink input, which is a stream played through a sink.
+///
+/// See [Introspector::sink_inputs].
+#[non_exhaustive]
+pub struct SinkInputInfo {
+    /// The index of the sink input.
+    pub index: u32,
+    /// The name of the sink input.
+    pub name: CString,
+    /// The index of the sink that the sink input is played through.
+    pub sink: u32,
+    /// The sample format of the sink input.
+    pub format: Option<SampleFormat>,
+    /// The number of channels of the sink input.
+    pub channels: u8,
+    /// The sample rate of the sink input.
+    pub rate: u32,
+    /// The volume of the sink input, unless it doesn't support one.
+    pub volume: Option<ChannelVolume>,
+    /// If the sink input is muted.
+    pub mute: bool,
+    /// The properties of the sink input.
+    pub properties: PropertyList,
+}
+
+/// A connection to the default server used to enumerate and configure its
+/// devices and streams.
+///
+/// The connection is driven by the audio [Runtime][crate::runtime::Runtime],
+/// so it must be used inside of it.
+///
+/// See [Introspector::connect].
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::pulse;
+/// use std::ffi::CString;
+///
+/// async fn list() -> anyhow::Result<()> {
+///     let name = CString::new("My Application")?;
+///     let mut introspector = pulse::Introspector::connect(&name).await?;
+///
+///     for sink in introspector.sinks().await? {
+///         println!("{}: {:?} ({:?})", sink.index, sink.description, sink.volume);
+///     }
+///
+///     for source in introspector.sources().await? {
+///         println!("{}: {:?}", source.index, source.description);
+///     }
+///
+///     Ok(())
+/// }
+///
+/// # #[tokio::main] async fn main() -> anyhow::Result<()> {
+/// let runtime = audio_device::runtime::Runtime::new()?;
+/// let bg = ste::spawn();
+/// bg.submit_async(runtime.wrap(list())).await?;
+/// bg.join();
+/// # Ok(()) }
+/// ```
+pub struct Introspector {
+    connection: Connection,
+}
+
+impl Introspector {
+    /// Connect to the default server, completing once the connection is
+    /// ready.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless called inside of the audio runtime.
+    pub async fn connect(name: &CStr) -> Result<Self> {
+        let mut connection = Connection::new(name)?;
+        std::future::poll_fn(|cx| connection.poll_ready(cx)).await?;
+        Ok(Self { connection })
+    }
+
+    /// List the sinks of the server.
+    pub async fn sinks(&mut self) -> Result<Vec<SinkInfo>> {
+        list(&mut self.connection, |context, userdata| unsafe {
+            pulse::pa_context_get_sink_info_list(context, Some(list_callback::<SinkInfo>), userdata)
+        })
+        .await
+    }
+
+    /// List the sources of the server, including the monitors of its sinks.
+    pub async fn sources(&mut self) -> Result<Vec<SourceInfo>> {
+        list(&mut self.connection, |context, userdata| unsafe {
+            pulse::pa_context_get_source_info_list(
+                context,
+                Some(list_callback::<SourceInfo>),
+                userdata,
+            )
+        })
+        .await
+    }
+
+    /// List the sink inputs of the server, which are the streams currently
+    /// being played.
+    pub async fn sink_inputs(&mut self) -> Result<Vec<SinkInputInfo>> {
+        list(&mut self.connection, |context, userdata| unsafe {
+            pulse::pa_context_get_sink_input_info_list(
+                context,
+                Some(list_callback::<SinkInputInfo>),
+                userdata,
+            )
+        })
+        .await
+    }
+
+    /// Set the volume of the sink with the given index.
+    pub async fn set_sink_volume(&mut self, sink: u32, volume: &ChannelVolume) -> Result<()> {
+        self.connection
+            .complete(|context, callback, userdata| unsafe {
+                pulse::pa_context_set_sink_volume_by_index(
+                    context,
+                    sink,
+                    &volume.raw,
+                    callback,
+                    userdata,
+                )
+            })
+            .await
+    }
+
+    /// Set the volume of the source with the given index.
+    pub async fn set_source_volume(&mut self, source: u32, volume: &ChannelVolume) -> Result<()> {
+        self.connection
+            .complete(|context, callback, userdata| unsafe {
+                pulse::pa_context_set_source_volume_by_index(
+                    context,
+                    source,
+                    &volume.raw,
+                    callback,
+                    userdata,
+                )
+            })
+            .await
+    }
+
+    /// Set the volume of the sink input with the given index.
+    pub async fn set_sink_input_volume(
+        &mut self,
+        sink_input: u32,
+        volume: &ChannelVolume,
+    ) -> Result<()> {
+        self.connection
+            .complete(|context, callback, userdata| unsafe {
+                pulse::pa_context_set_sink_input_volume(
+                    context,
+                    sink_input,
+                    &volume.raw,
+                    callback,
+                    userdata,
+                )
+            })
+            .await
+    }
+
+    /// Move the sink input with the given index to another sink.
+    ///
+    /// The index of a stream opened by this crate is available through
+    /// [AsyncStream::index][super::AsyncStream::index].
+    pub async fn move_sink_input(&mut self, sink_input: u32, sink: u32) -> Result<()> {
+        self.connection
+            .complete(|context, callback, userdata| unsafe {
+                pulse::pa_context_move_sink_input_by_index(
+                    context, sink_input, sink, callback, userdata,
+                )
+            })
+            .await
+    }
+}
+
+/// Start an operation which lists objects through [list_callback], and
+/// collect them once it completes.
+pub(super) async fn list<T, F>(connection: &mut Connection, start: F) -> Result<Vec<T>>
+where
+    T: FromInfo,
+    F: FnOnce(*mut pulse::pa_context, *mut c::c_void) -> *mut pulse::pa_operation,
+{
+    let mut list = Box::new(List {
+        items: Vec::new(),
+        failed: false,
+    });
+
+    let operation = start(
+        connection.context.handle.as_ptr(),
+        &mut *list as *mut List<T> as *mut c::c_void,
+    );
+
+    let operation = connection.operation(operation)?;
+    connection.wait(operation).await?;
+
+    if list.failed {
+        return Err(connection.context.last_error());
+    }
+
+    Ok(list.items)
+}
+
+/// Enumerate the sinks and sources of the default server, blocking until they
+/// have been listed.
+///
+/// Monitor sources aren't included, since they capture what is played
+/// through a sink rather than being devices of their own.
+pub(crate) fn device_infos() -> Result<Vec<DeviceInfo>> {
+    let name = CStr::from_bytes_with_nul(b"audio-device\0").expect("valid name");
+
+    let mut main = MainLoop::new();
+    let mut context = main.context(name);
+    context.connect()?;
+
+    loop {
+        match context.state()? {
+            ContextState::Ready => break,
+            ContextState::Failed | ContextState::Terminated => {
+                return Err(context.last_error());
+            }
+            _ => iterate(&mut main, &context)?,
+        }
+    }
+
+    let mut defaults = Box::new(Defaults::default());
+
+    let operation = unsafe {
+        pulse::pa_context_get_server_info(
+            context.handle.as_ptr(),
+            Some(server_info_callback),
+            &mut *defaults as *mut Defaults as *mut c::c_void,
+        )
+    };
+
+    wait_blocking(&mut main, &context, Operation::new(&context, operation)?)?;
+
+    let sinks = list_blocking::<SinkInfo, _>(&mut main, &context, |context, userdata| unsafe {
+        pulse::pa_context_get_sink_info_list(context, Some(list_callback::<SinkInfo>), userdata)
+    })?;
+
+    let sources =
+        list_blocking::<SourceInfo, _>(&mut main, &context, |context, userdata| unsafe {
+            pulse::pa_context_get_source_info_list(
+                context,
+                Some(list_callback::<SourceInfo>),
+                userdata,
+            )
+        })?;
+
+    let mut infos = Vec::new();
+
+    for sink in sinks {
+        infos.push(device_info(
+            &sink.name,
+            &sink.description,
+            Direction::Output,
+            defaults.sink.as_deref() == Some(&sink.name),
+            sink.format,
+            sink.channels,
+            sink.rate,
+        ));
+    }
+
+    for source in sources.iter().filter(|s| s.monitor_of_sink.is_none()) {
+        infos.push(device_info(
+            &source.name,
+            &source.description,
+            Direction::Input,
+            defaults.source.as_deref() == Some(&source.name),
+            source.format,
+            source.channels,
+            source.rate,
+        ));
+    }
+
+    Ok(infos)
+}
+
+fn device_info(
+    name: &CStr,
+    description: &CStr,
+    direction: Direction,
+    is_default: bool,
+    format: Option<SampleFormat>,
+    channels: u8,
+    rate: u32,
+) -> DeviceInfo {
+    let id = name.to_string_lossy().into_owned();
+
+    let name = match description.to_bytes() {
+        [] => id.clone(),
+        _ => description.to_string_lossy().into_owned(),
+    };
+
+    // NB: the server converts streams into the sample specification of the
+    // device, so what's reported is only what the device is configured with.
+    DeviceInfo {
+        id,
+        name,
+        direction,
+        is_default,
+        formats: format
+            .and_then(SampleFormat::sample_format)
+            .into_iter()
+            .collect(),
+        channels: Some((channels as usize, channels as usize)),
+        sample_rates: Some((rate, rate)),
+    }
+}
+
+/// Iterate the main loop, blocking until there are events to dispatch.
+fn iterate(main: &mut MainLoop, context: &Context) -> Result<()> {
+    let result = unsafe { pulse::pa_mainloop_iterate(main.handle.as_ptr(), 1, ptr::null_mut()) };
+
+    if result < 0 {
+        return Err(context.last_error());
+    }
+
+    Ok(())
+}
+
+/// Block until the given operation completes.
+fn wait_blocking(main: &mut MainLoop, context: &Context, operation: Operation) -> Result<()> {
+    loop {
+        match operation.state() {
+            pulse::PA_OPERATION_DONE => return Ok(()),
+            pulse::PA_OPERATION_CANCELLED => return Err(context.last_error()),
+            _ => iterate(main, context)?,
+        }
+    }
+}
+
+/// Start an operation which lists objects through [list_callback], and block
+/// until they've been collected.
+fn list_blocking<T, F>(main: &mut MainLoop, context: &Context, start: F) -> Result<Vec<T>>
+where
+    T: FromInfo,
+    F: FnOnce(*mut pulse::pa_context, *mut c::c_void) -> *mut pulse::pa_operation,
+{
+    let mut list = Box::new(List {
+        items: Vec::new(),
+        failed: false,
+    });
+
+    let operation = start(
+        context.handle.as_ptr(),
+        &mut *list as *mut List<T> as *mut c::c_void,
+    );
+
+    wait_blocking(main, context, Operation::new(context, operation)?)?;
+
+    if list.failed {
+        return Err(context.last_error());
+    }
+
+    Ok(list.items)
+}
+
+/// The default sink and source of a server.
+#[derive(Default)]
+struct Defaults {
+    sink: Option<CString>,
+    source: Option<CString>,
+}
+
+/// Callback for getting the server information, storing its defaults in a
+/// [Defaults].
+unsafe extern "C" fn server_info_callback(
+    _: *mut pulse::pa_context,
+    info: *const pulse::pa_server_info,
+    userdata: *mut c::c_void,
+) {
+    let defaults = &mut *(userdata as *mut Defaults);
+
+    if let Some(info) = info.as_ref() {
+        if !info.default_sink_name.is_null() {
+            defaults.sink = Some(to_owned(info.default_sink_name));
+        }
+
+        if !info.default_source_name.is_null() {
+            defaults.source = Some(to_owned(info.default_source_name));
+        }
+    }
+}
+
+/// Objects being collected by [list_callback].
+struct List<T> {
+    items: Vec<T>,
+    failed: bool,
+}
+
+/// Conversion from the information passed to a list callback, which is only
+/// valid for the duration of the callback.
+pub(super) trait FromInfo {
+    type Info;
+
+    /// # Safety
+    ///
+    /// The pointers in the given information must be valid.
+    unsafe fn from_info(info: &Self::Info) -> Self;
+}
+
+impl FromInfo for SinkInfo {
+    type Info = pulse::pa_sink_info;
+
+    unsafe fn from_info(info: &Self::Info) -> Self {
+        Self {
+            index: info.index,
+            name: to_owned(info.name),
+            description: to_owned(info.description),
+            format: SampleFormat::from_value(info.sample_spec.format),
+            channels: info.sample_spec.channels,
+            rate: info.sample_spec.rate,
+            volume: ChannelVolume::from_raw(&info.volume),
+            mute: info.mute != 0,
+            monitor_source: to_index(info.monitor_source),
+            properties: PropertyList::copy_from(info.proplist),
+        }
+    }
+}
+
+impl FromInfo for SourceInfo {
+    type Info = pulse::pa_source_info;
+
+    unsafe fn from_info(info: &Self::Info) -> Self {
+        Self {
+            index: info.index,
+            name: to_owned(info.name),
+            description: to_owned(info.description),
+            format: SampleFormat::from_value(info.sample_spec.format),
+            channels: info.sample_spec.channels,
+            rate: info.sample_spec.rate,
+            volume: ChannelVolume::from_raw(&info.volume),
+            mute: info.mute != 0,
+            monitor_of_sink: to_index(info.monitor_of_sink),
+            properties: PropertyList::copy_from(info.proplist),
+        }
+    }
+}
+
+impl FromInfo for SinkInputInfo {
+    type Info = pulse::pa_sink_input_info;
+
+    unsafe fn from_info(info: &Self::Info) -> Self {
+        Self {
+            index: info.index,
+            name: to_owned(info.name),
+            sink: info.sink,
+            format: SampleFormat::from_value(info.sample_spec.format),
+            channels: info.sample_spec.channels,
+            rate: info.sample_spec.rate,
+            volume: if info.has_volume != 0 {
+                Some(ChannelVolume::from_raw(&info.volume))
+            } else {
+                None
+            },
+            mute: info.mute != 0,
+            properties: PropertyList::copy_from(info.proplist),
+        }
+    }
+}
+
+/// Callback for operations which list objects, collecting them into a
+/// `List<T>`.
+pub(super) unsafe extern "C" fn list_callback<T>(
+    _: *mut pulse::pa_context,
+    info: *const T::Info,
+    eol: c::c_int,
+    userdata: *mut c::c_void,
+) where
+    T: FromInfo,
+{
+    let list = &mut *(userdata as *mut List<T>);
+
+    // NB: a negative end of list marker indicates that listing failed, in
+    // which case the error is available through the context.
+    if eol < 0 {
+        list.failed = true;
+        return;
+    }
+
+    if eol == 0 && !info.is_null() {
+        list.items.push(T::from_info(&*info));
+    }
+}
+
+unsafe fn to_owned(string: *const c::c_char) -> CString {
+    if string.is_null() {
+        CString::default()
+    } else {
+        CStr::from_ptr(string).to_owned()
+    }
+}
+
+fn to_index(index: u32) -> Option<u32> {
+    if index == INVALID_INDEX {
+        None
+    } else {
+        Some(index)
+    }
+}
@@ -7,7 +7,7 @@ use std::ptr;
 ///
 /// See [MainLoop::new].
 pub struct MainLoop {
-    handle: ptr::NonNull<pulse::pa_mainloop>,
+    pub(super) handle: ptr::NonNull<pulse::pa_mainloop>,
     api: ptr::NonNull<pulse::pa_mainloop_api>,
 }
 
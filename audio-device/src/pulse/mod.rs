@@ -7,13 +7,37 @@ mod error;
 pub use self::error::{Error, Result};
 
 mod enums;
-pub use self::enums::ContextState;
+pub use self::enums::{ContextState, SampleFormat, StreamDirection, StreamState};
 
 mod main_loop;
 pub use self::main_loop::MainLoop;
 
+mod threaded_main_loop;
+pub use self::threaded_main_loop::{Lock, ThreadedMainLoop};
+
 mod property_list;
 pub use self::property_list::PropertyList;
 
 mod context;
 pub use self::context::Context;
+
+mod sample;
+pub use self::sample::Sample;
+
+mod simple;
+pub use self::simple::Simple;
+
+mod connection;
+
+mod async_stream;
+pub use self::async_stream::AsyncStream;
+
+mod timing_info;
+pub use self::timing_info::TimingInfo;
+
+mod volume;
+pub use self::volume::ChannelVolume;
+
+mod introspect;
+pub(crate) use self::introspect::device_infos;
+pub use self::introspect::{Introspector, SinkInfo, SinkInputInfo, SourceInfo};
@@ -1,5 +1,6 @@
 use crate::libc as c;
 use pulse_sys as pulse;
+use std::ffi::CStr;
 use std::ptr;
 
 /// A property list object.
@@ -31,6 +32,30 @@ impl PropertyList {
         }
     }
 
+    /// Construct a property list by copying the given one.
+    ///
+    /// # Safety
+    ///
+    /// The given handle must point to a valid property list.
+    pub(super) unsafe fn copy_from(handle: *const pulse::pa_proplist) -> Self {
+        Self {
+            handle: ptr::NonNull::new_unchecked(pulse::pa_proplist_copy(handle)),
+        }
+    }
+
+    /// Get the value of the given key, if it's set and is a string.
+    pub fn get(&self, key: &CStr) -> Option<&CStr> {
+        unsafe {
+            let value = pulse::pa_proplist_gets(self.handle.as_ptr(), key.as_ptr());
+
+            if value.is_null() {
+                None
+            } else {
+                Some(CStr::from_ptr(value))
+            }
+        }
+    }
+
     /// Return the number of entries in the property list.
     ///
     /// # Examples
@@ -71,3 +96,6 @@ impl Drop for PropertyList {
         }
     }
 }
+
+// Safety: the property list is exclusively owned.
+unsafe impl Send for PropertyList {}
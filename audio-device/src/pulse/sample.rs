@@ -0,0 +1,49 @@
+use crate::pulse::SampleFormat;
+
+/// Trait used to designate types which are sample-appropriate for
+/// [Simple][super::Simple] streams.
+///
+/// # Examples
+///
+/// ```
+/// use audio_device::pulse::{Sample, SampleFormat};
+///
+/// #[cfg(target_endian = "little")]
+/// assert_eq!(f32::FORMAT, SampleFormat::Float32LE);
+/// assert_eq!(u8::FORMAT, SampleFormat::U8);
+/// ```
+///
+/// # Safety
+///
+/// This trait is unsafe to implement, because the format needs to accurately
+/// describe the memory layout of the type.
+pub unsafe trait Sample: Copy {
+    /// The format of the sample in the native endianness.
+    const FORMAT: SampleFormat;
+}
+
+macro_rules! native {
+    ($le:ident, $be:ident) => {
+        if cfg!(target_endian = "little") {
+            SampleFormat::$le
+        } else {
+            SampleFormat::$be
+        }
+    };
+}
+
+unsafe impl Sample for u8 {
+    const FORMAT: SampleFormat = SampleFormat::U8;
+}
+
+unsafe impl Sample for i16 {
+    const FORMAT: SampleFormat = native!(S16LE, S16BE);
+}
+
+unsafe impl Sample for i32 {
+    const FORMAT: SampleFormat = native!(S32LE, S32BE);
+}
+
+unsafe impl Sample for f32 {
+    const FORMAT: SampleFormat = native!(Float32LE, Float32BE);
+}
@@ -0,0 +1,220 @@
+use std::ffi::CStr;
+use std::marker;
+use std::ptr;
+use std::time::Duration;
+
+use pulse_sys as pulse;
+
+use crate::buf_io;
+use crate::libc as c;
+use crate::pulse::{Error, Result, Sample, StreamDirection};
+
+/// A simple blocking stream, connected to the default device of the default
+/// server.
+///
+/// This is the easiest way to play or record audio through PulseAudio, since
+/// it doesn't require driving a [MainLoop][super::MainLoop].
+///
+/// See [Simple::open].
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::pulse;
+/// use std::ffi::CString;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let name = CString::new("My Application")?;
+/// let stream_name = CString::new("Playback")?;
+///
+/// let mut stream = pulse::Simple::<f32>::open(
+///     &name,
+///     &stream_name,
+///     pulse::StreamDirection::Playback,
+///     2,
+///     48000,
+/// )?;
+///
+/// let samples = vec![0.0f32; 2 * 48000];
+/// stream.write_interleaved(audio::wrap::interleaved(&samples[..], 2))?;
+/// stream.drain()?;
+/// # Ok(()) }
+/// ```
+pub struct Simple<T> {
+    handle: ptr::NonNull<pulse::pa_simple>,
+    channels: usize,
+    rate: u32,
+    _marker: marker::PhantomData<T>,
+}
+
+impl<T> Simple<T>
+where
+    T: Sample,
+{
+    /// Connect a stream with the given direction to the default device of the
+    /// default server.
+    ///
+    /// The `name` identifies the application to the server, while
+    /// `stream_name` describes what the stream is used for.
+    pub fn open(
+        name: &CStr,
+        stream_name: &CStr,
+        direction: StreamDirection,
+        channels: u8,
+        rate: u32,
+    ) -> Result<Self> {
+        let spec = pulse::pa_sample_spec {
+            format: T::FORMAT as pulse::pa_sample_format_t,
+            rate,
+            channels,
+        };
+
+        unsafe {
+            let mut error = 0;
+
+            let handle = pulse::pa_simple_new(
+                ptr::null(),
+                name.as_ptr(),
+                direction as pulse::pa_stream_direction_t,
+                ptr::null(),
+                stream_name.as_ptr(),
+                &spec,
+                ptr::null(),
+                ptr::null(),
+                &mut error,
+            );
+
+            let handle = match ptr::NonNull::new(handle) {
+                Some(handle) => handle,
+                None => return Err(Error::Pulse(error)),
+            };
+
+            Ok(Self {
+                handle,
+                channels: channels as usize,
+                rate,
+                _marker: marker::PhantomData,
+            })
+        }
+    }
+
+    /// The number of channels of the stream.
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// The sample rate of the stream.
+    pub fn rate(&self) -> u32 {
+        self.rate
+    }
+
+    /// Write an interleaved buffer to a playback stream, blocking until all of
+    /// its frames have been accepted by the server.
+    pub fn write_interleaved<B>(&mut self, mut buf: B) -> Result<()>
+    where
+        B: audio_core::ReadBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBuf<Sample = T>,
+    {
+        self.check_channels(&buf)?;
+
+        let handle = self.handle;
+
+        buf_io::write(&mut buf, self.channels, |ptr, len| unsafe {
+            let mut error = 0;
+            let result = pulse::pa_simple_write(handle.as_ptr(), ptr.cast(), len, &mut error);
+            check(result, error)?;
+            Ok(len)
+        })
+    }
+
+    /// Read into an interleaved buffer from a record stream, blocking until
+    /// the remaining frames of the buffer have been filled.
+    pub fn read_interleaved<B>(&mut self, mut buf: B) -> Result<()>
+    where
+        B: audio_core::WriteBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBufMut<Sample = T>,
+    {
+        self.check_channels(&buf)?;
+
+        let handle = self.handle;
+
+        buf_io::read(&mut buf, self.channels, |ptr, len| unsafe {
+            let mut error = 0;
+            let result = pulse::pa_simple_read(handle.as_ptr(), ptr.cast(), len, &mut error);
+            check(result, error)?;
+            Ok(len)
+        })
+    }
+
+    /// Block until all data written to a playback stream has been played.
+    pub fn drain(&mut self) -> Result<()> {
+        unsafe {
+            let mut error = 0;
+            let result = pulse::pa_simple_drain(self.handle.as_ptr(), &mut error);
+            check(result, error)
+        }
+    }
+
+    /// Discard all data written to a playback stream which hasn't been
+    /// played yet.
+    pub fn flush(&mut self) -> Result<()> {
+        unsafe {
+            let mut error = 0;
+            let result = pulse::pa_simple_flush(self.handle.as_ptr(), &mut error);
+            check(result, error)
+        }
+    }
+
+    /// Get the latency of the stream, which for playback streams is the time
+    /// until a frame written now is played, and for record streams the time
+    /// since the oldest frame which hasn't been read yet was captured.
+    pub fn latency(&mut self) -> Result<Duration> {
+        unsafe {
+            let mut error = 0;
+            let latency = pulse::pa_simple_get_latency(self.handle.as_ptr(), &mut error);
+
+            if latency == pulse::pa_usec_t::MAX {
+                return Err(Error::Pulse(error));
+            }
+
+            Ok(Duration::from_micros(latency))
+        }
+    }
+
+    fn check_channels<B>(&self, buf: &B) -> Result<()>
+    where
+        B: audio_core::Buf,
+    {
+        if buf.channels() != self.channels {
+            return Err(Error::ChannelsMismatch {
+                actual: buf.channels(),
+                expected: self.channels,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> Drop for Simple<T> {
+    fn drop(&mut self) {
+        unsafe {
+            pulse::pa_simple_free(self.handle.as_ptr());
+        }
+    }
+}
+
+// Safety: the simple API is blocking and doesn't rely on any thread-local
+// state, so the stream can be moved across threads.
+unsafe impl<T> Send for Simple<T> {}
+
+/// Convert the result of a call to the simple API into a result.
+fn check(result: c::c_int, error: c::c_int) -> Result<()> {
+    if result < 0 {
+        return Err(Error::Pulse(error));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,308 @@
+use crate::libc as c;
+use crate::pulse::{Context, ContextState, Error, Result};
+use pulse_sys as pulse;
+use std::cell::UnsafeCell;
+use std::ffi::CStr;
+use std::marker;
+use std::mem::ManuallyDrop;
+use std::ptr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// A PulseAudio main loop which runs in a background thread, together with
+/// the context it drives.
+///
+/// The context can only be used while the main loop is
+/// [locked][ThreadedMainLoop::lock], which prevents the background thread from
+/// using it at the same time. The main loop is signalled whenever the state of
+/// the context changes, so blocking programs can [wait][Lock::wait] for it.
+///
+/// Callbacks are called in the background thread, so the ones installed
+/// through a [Lock] must be [Send].
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::pulse;
+/// use std::ffi::CString;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let mut main = pulse::ThreadedMainLoop::new(&CString::new("My Application")?);
+/// main.start()?;
+///
+/// let mut lock = main.lock();
+/// lock.connect()?;
+/// println!("state: {}", lock.state()?);
+/// # Ok(()) }
+/// ```
+pub struct ThreadedMainLoop {
+    handle: ptr::NonNull<pulse::pa_threaded_mainloop>,
+    context: ManuallyDrop<UnsafeCell<Context>>,
+    locked: AtomicBool,
+    /// The last error returned by a callback, which is stored here since
+    /// callbacks are called in the background thread.
+    callback_error: Arc<Mutex<Option<Error>>>,
+}
+
+impl ThreadedMainLoop {
+    /// Construct a new threaded main loop, and a context with the given
+    /// application name which is driven by it.
+    ///
+    /// The background thread isn't running until the main loop is
+    /// [started][ThreadedMainLoop::start].
+    pub fn new(name: &CStr) -> Self {
+        unsafe {
+            let handle = pulse::pa_threaded_mainloop_new();
+            assert!(!handle.is_null(), "pa_threaded_mainloop_new: returned NULL");
+
+            let api = pulse::pa_threaded_mainloop_get_api(handle);
+            let context = pulse::pa_context_new(api, name.as_ptr());
+            assert!(!context.is_null(), "pa_context_new: returned NULL");
+
+            // NB: wake up anyone waiting on the main loop when the state of
+            // the context changes.
+            pulse::pa_context_set_state_callback(
+                context,
+                Some(signal_callback),
+                handle as *mut c::c_void,
+            );
+
+            Self {
+                handle: ptr::NonNull::new_unchecked(handle),
+                context: ManuallyDrop::new(UnsafeCell::new(Context {
+                    handle: ptr::NonNull::new_unchecked(context),
+                    callbacks: Vec::new(),
+                })),
+                locked: AtomicBool::new(false),
+                callback_error: Arc::new(Mutex::new(None)),
+            }
+        }
+    }
+
+    /// Start the background thread of the main loop.
+    pub fn start(&mut self) -> Result<()> {
+        unsafe {
+            if pulse::pa_threaded_mainloop_start(self.handle.as_ptr()) < 0 {
+                return Err(Error::MainLoopStart);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Stop the background thread of the main loop.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from the background thread of the main loop.
+    pub fn stop(&mut self) {
+        assert!(
+            !self.in_thread(),
+            "cannot stop main loop from its own thread"
+        );
+
+        unsafe {
+            pulse::pa_threaded_mainloop_stop(self.handle.as_ptr());
+        }
+    }
+
+    /// Lock the main loop, giving access to its context until the returned
+    /// guard is dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the main loop is already locked by the current thread, or if
+    /// called from the background thread of the main loop. Callbacks which run
+    /// in the background thread are already called with the lock held.
+    pub fn lock(&self) -> Lock<'_> {
+        assert!(
+            !self.in_thread(),
+            "cannot lock main loop from its own thread"
+        );
+
+        unsafe {
+            pulse::pa_threaded_mainloop_lock(self.handle.as_ptr());
+        }
+
+        // NB: the lock is recursive, so if it's already marked as locked it's
+        // held by the current thread.
+        if self.locked.swap(true, Ordering::Acquire) {
+            unsafe {
+                pulse::pa_threaded_mainloop_unlock(self.handle.as_ptr());
+            }
+
+            panic!("main loop is already locked by the current thread");
+        }
+
+        Lock {
+            main: self,
+            _marker: marker::PhantomData,
+        }
+    }
+
+    /// Test if the caller is running in the background thread of the main
+    /// loop.
+    pub fn in_thread(&self) -> bool {
+        unsafe { pulse::pa_threaded_mainloop_in_thread(self.handle.as_ptr()) != 0 }
+    }
+}
+
+impl Drop for ThreadedMainLoop {
+    fn drop(&mut self) {
+        unsafe {
+            // NB: the background thread must be stopped before the context is
+            // freed, since it might still be using it.
+            pulse::pa_threaded_mainloop_stop(self.handle.as_ptr());
+            ManuallyDrop::drop(&mut self.context);
+            pulse::pa_threaded_mainloop_free(self.handle.as_ptr());
+        }
+    }
+}
+
+// Safety: the context is only accessible while the main loop is locked, and
+// only callbacks which are `Send` can be installed through the lock.
+unsafe impl Send for ThreadedMainLoop {}
+unsafe impl Sync for ThreadedMainLoop {}
+
+/// A lock on a [ThreadedMainLoop], giving access to its context.
+///
+/// The main loop is unlocked when the guard is dropped.
+///
+/// See [ThreadedMainLoop::lock].
+pub struct Lock<'a> {
+    main: &'a ThreadedMainLoop,
+    // NB: the lock must be released by the thread which acquired it.
+    _marker: marker::PhantomData<*const ()>,
+}
+
+impl Lock<'_> {
+    /// Connect the context to the default server, waiting until the
+    /// connection is ready.
+    pub fn connect(&mut self) -> Result<()> {
+        self.context_mut().connect()?;
+
+        loop {
+            match self.state()? {
+                ContextState::Ready => return Ok(()),
+                ContextState::Failed | ContextState::Terminated => {
+                    return Err(self.context().last_error());
+                }
+                _ => self.wait(),
+            }
+        }
+    }
+
+    /// Get the current state of the context.
+    pub fn state(&self) -> Result<ContextState> {
+        self.context().state()
+    }
+
+    /// Set a callback function that is called in the background thread
+    /// whenever the context status changes.
+    ///
+    /// The main loop is still signalled after the callback has been called,
+    /// so it can be [waited][Lock::wait] on. If the callback errors, the
+    /// error can be taken with [take_callback_error][Lock::take_callback_error].
+    pub fn set_callback<C>(&mut self, mut cb: C) -> Result<()>
+    where
+        C: 'static + Send + FnMut(&mut Context) -> Result<()>,
+    {
+        let main = Signal(self.main.handle);
+        let callback_error = self.main.callback_error.clone();
+
+        self.context_mut().set_callback(move |cx| {
+            if let Err(e) = cb(cx) {
+                *callback_error.lock().unwrap() = Some(e);
+            }
+
+            main.signal();
+            Ok(())
+        })
+    }
+
+    /// Take the last error returned by a callback installed through
+    /// [set_callback][Lock::set_callback].
+    pub fn take_callback_error(&mut self) -> Option<Error> {
+        self.main.callback_error.lock().unwrap().take()
+    }
+
+    /// Release the lock and wait until the main loop is
+    /// [signalled][Lock::signal], re-acquiring the lock before returning.
+    ///
+    /// Spurious wakeups can happen, so the condition being waited for should
+    /// be checked again once this returns.
+    pub fn wait(&mut self) {
+        self.main.locked.store(false, Ordering::Release);
+
+        unsafe {
+            pulse::pa_threaded_mainloop_wait(self.main.handle.as_ptr());
+        }
+
+        self.main.locked.store(true, Ordering::Relaxed);
+    }
+
+    /// Signal all threads waiting on the main loop.
+    ///
+    /// If `wait_for_accept` is set, this blocks until the signal has been
+    /// [accepted][Lock::accept] by the waiting thread.
+    pub fn signal(&self, wait_for_accept: bool) {
+        unsafe {
+            pulse::pa_threaded_mainloop_signal(
+                self.main.handle.as_ptr(),
+                wait_for_accept as c::c_int,
+            );
+        }
+    }
+
+    /// Accept a signal which was sent while waiting for it to be accepted.
+    pub fn accept(&self) {
+        unsafe {
+            pulse::pa_threaded_mainloop_accept(self.main.handle.as_ptr());
+        }
+    }
+}
+
+impl Lock<'_> {
+    fn context(&self) -> &Context {
+        // Safety: the context is only accessed while the main loop is locked.
+        unsafe { &*self.main.context.get() }
+    }
+
+    // NB: the context isn't handed out mutably, since it would allow for
+    // installing callbacks which aren't `Send`.
+    fn context_mut(&mut self) -> &mut Context {
+        // Safety: the context is only accessed while the main loop is locked,
+        // and only through a single lock at a time.
+        unsafe { &mut *self.main.context.get() }
+    }
+}
+
+impl Drop for Lock<'_> {
+    fn drop(&mut self) {
+        self.main.locked.store(false, Ordering::Release);
+
+        unsafe {
+            pulse::pa_threaded_mainloop_unlock(self.main.handle.as_ptr());
+        }
+    }
+}
+
+/// A handle used to signal the main loop from its background thread.
+struct Signal(ptr::NonNull<pulse::pa_threaded_mainloop>);
+
+impl Signal {
+    fn signal(&self) {
+        unsafe {
+            pulse::pa_threaded_mainloop_signal(self.0.as_ptr(), 0);
+        }
+    }
+}
+
+// Safety: signalling the main loop is thread safe, and the callback holding
+// this is dropped before the main loop is freed.
+unsafe impl Send for Signal {}
+
+/// State callback which signals the main loop passed as userdata.
+unsafe extern "C" fn signal_callback(_: *mut pulse::pa_context, userdata: *mut c::c_void) {
+    pulse::pa_threaded_mainloop_signal(userdata as *mut pulse::pa_threaded_mainloop, 0);
+}
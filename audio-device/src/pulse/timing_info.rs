@@ -0,0 +1,46 @@
+use pulse_sys as pulse;
+use std::time::Duration;
+
+/// Timing information of a stream, as last reported by the server.
+///
+/// See [AsyncStream::timing_info][super::AsyncStream::timing_info].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TimingInfo {
+    /// If the stream is currently playing.
+    pub playing: bool,
+    /// The latency of the sink, which is the time it takes for audio in its
+    /// buffer to be played.
+    pub sink_latency: Duration,
+    /// The latency of the source, which is the time it takes for captured
+    /// audio to become available in its buffer.
+    pub source_latency: Duration,
+    /// The time it takes to transport audio between the client and the
+    /// server.
+    pub transport_latency: Duration,
+    /// The write index of the stream buffer in bytes, unless it's out of date.
+    pub write_index: Option<i64>,
+    /// The read index of the stream buffer in bytes, unless it's out of date.
+    pub read_index: Option<i64>,
+}
+
+impl TimingInfo {
+    pub(super) fn from_raw(raw: &pulse::pa_timing_info) -> Self {
+        Self {
+            playing: raw.playing != 0,
+            sink_latency: Duration::from_micros(raw.sink_usec),
+            source_latency: Duration::from_micros(raw.source_usec),
+            transport_latency: Duration::from_micros(raw.transport_usec),
+            write_index: if raw.write_index_corrupt == 0 {
+                Some(raw.write_index)
+            } else {
+                None
+            },
+            read_index: if raw.read_index_corrupt == 0 {
+                Some(raw.read_index)
+            } else {
+                None
+            },
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use pulse_sys as pulse;
+use std::fmt;
+
+/// The maximum number of channels supported by PulseAudio.
+const CHANNELS_MAX: u8 = 32;
+
+/// The volume of each channel of a device or a stream.
+///
+/// Volumes are expressed as linear factors, where `0.0` is silent and `1.0`
+/// is the nominal volume. Values above `1.0` amplify the signal.
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::pulse;
+///
+/// let mut volume = pulse::ChannelVolume::new(2, 1.0);
+/// volume.set(1, 0.5);
+/// assert_eq!(volume.channels(), 2);
+/// ```
+pub struct ChannelVolume {
+    pub(super) raw: pulse::pa_cvolume,
+}
+
+impl ChannelVolume {
+    /// Construct a volume where every channel has the given linear volume.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `channels` is zero or larger than 32.
+    pub fn new(channels: u8, volume: f64) -> Self {
+        assert!(
+            channels > 0 && channels <= CHANNELS_MAX,
+            "unsupported number of channels `{}`",
+            channels
+        );
+
+        let mut raw = pulse::pa_cvolume {
+            channels: 0,
+            values: [0; CHANNELS_MAX as usize],
+        };
+
+        unsafe {
+            pulse::pa_cvolume_set(
+                &mut raw,
+                channels as u32,
+                pulse::pa_sw_volume_from_linear(volume),
+            );
+        }
+
+        Self { raw }
+    }
+
+    pub(super) fn from_raw(raw: &pulse::pa_cvolume) -> Self {
+        Self {
+            raw: pulse::pa_cvolume {
+                channels: raw.channels,
+                values: raw.values,
+            },
+        }
+    }
+
+    /// The number of channels.
+    pub fn channels(&self) -> usize {
+        self.raw.channels as usize
+    }
+
+    /// Get the linear volume of the given channel.
+    pub fn get(&self, channel: usize) -> Option<f64> {
+        let value = *self.values().get(channel)?;
+        Some(unsafe { pulse::pa_sw_volume_to_linear(value) })
+    }
+
+    /// Set the linear volume of the given channel.
+    ///
+    /// Channels which are out of bounds are ignored.
+    pub fn set(&mut self, channel: usize, volume: f64) {
+        if channel < self.channels() {
+            self.raw.values[channel] = unsafe { pulse::pa_sw_volume_from_linear(volume) };
+        }
+    }
+
+    /// The average linear volume of all channels.
+    pub fn average(&self) -> f64 {
+        unsafe { pulse::pa_sw_volume_to_linear(pulse::pa_cvolume_avg(&self.raw)) }
+    }
+
+    fn values(&self) -> &[pulse::pa_volume_t] {
+        &self.raw.values[..self.channels()]
+    }
+}
+
+impl Clone for ChannelVolume {
+    fn clone(&self) -> Self {
+        Self::from_raw(&self.raw)
+    }
+}
+
+impl fmt::Debug for ChannelVolume {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries((0..self.channels()).filter_map(|c| self.get(c)))
+            .finish()
+    }
+}
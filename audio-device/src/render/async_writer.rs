@@ -0,0 +1,75 @@
+use std::io::{Seek, Write};
+
+use crate::buf_io;
+use crate::render::delay::Delay;
+use crate::render::{Output, Result, Sample};
+
+/// An interleaved type-checked async writer for an [Output].
+///
+/// See [Output::async_writer].
+pub struct AsyncWriter<'a, T, W>
+where
+    W: Write + Seek,
+{
+    output: &'a mut Output<T, W>,
+}
+
+impl<'a, T, W> AsyncWriter<'a, T, W>
+where
+    T: Sample,
+    W: Write + Seek,
+{
+    pub(super) fn new(output: &'a mut Output<T, W>) -> Self {
+        Self { output }
+    }
+
+    /// Write an interleaved buffer, completing once it's been rendered at the
+    /// configured pace.
+    ///
+    /// Writing to the sink itself is blocking, which is only expected to take
+    /// a short amount of time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io;
+    /// use audio_device::render;
+    ///
+    /// # #[tokio::main] async fn main() -> anyhow::Result<()> {
+    /// let config = render::Config {
+    ///     channels: 1,
+    ///     pace: render::Pace::Speed(10.0),
+    ///     ..render::Config::default()
+    /// };
+    ///
+    /// let mut output = render::Output::<f32, _>::new(io::Cursor::new(Vec::new()), config)?;
+    /// let samples = vec![0.0f32; 480];
+    ///
+    /// let mut writer = output.async_writer();
+    /// writer.write_interleaved(audio::wrap::interleaved(&samples[..], 1)).await?;
+    /// writer.write_interleaved(audio::wrap::interleaved(&samples[..], 1)).await?;
+    /// assert_eq!(output.position(), 960);
+    /// # Ok(()) }
+    /// ```
+    pub async fn write_interleaved<B>(&mut self, mut buf: B) -> Result<()>
+    where
+        B: audio_core::ReadBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBuf<Sample = T>,
+    {
+        self.output.check_channels(&buf)?;
+
+        let channels = self.output.config().channels;
+        let frames = buf_io::readable_frames(&buf, channels);
+        let deadline = self
+            .output
+            .write_samples(&buf.as_interleaved()[..frames * channels])?;
+        buf.advance(frames);
+
+        if let Some(deadline) = deadline {
+            Delay::new(deadline).await;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,164 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Condvar, Mutex, OnceLock, Weak};
+use std::task::{Context, Poll};
+use std::thread;
+use std::time::Instant;
+
+use crate::runtime::atomic_waker::AtomicWaker;
+
+/// A future which completes at the given deadline.
+///
+/// NB: deadlines are queued on a single timer thread shared by all delays,
+/// which wakes each task once its deadline has been reached.
+pub(super) struct Delay {
+    deadline: Instant,
+    waker: Option<Arc<AtomicWaker>>,
+}
+
+impl Delay {
+    pub(super) fn new(deadline: Instant) -> Self {
+        Self {
+            deadline,
+            waker: None,
+        }
+    }
+}
+
+impl Future for Delay {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if Instant::now() >= self.deadline {
+            return Poll::Ready(());
+        }
+
+        if let Some(waker) = &self.waker {
+            waker.register_by_ref(cx.waker());
+            return Poll::Pending;
+        }
+
+        let waker = Arc::new(AtomicWaker::new());
+        waker.register_by_ref(cx.waker());
+        timer().schedule(self.deadline, Arc::downgrade(&waker));
+        self.waker = Some(waker);
+        Poll::Pending
+    }
+}
+
+/// Get the shared timer, starting its thread if it isn't running.
+fn timer() -> &'static Timer {
+    static TIMER: OnceLock<Arc<Timer>> = OnceLock::new();
+
+    TIMER.get_or_init(|| {
+        let timer = Arc::new(Timer {
+            queue: Mutex::new(BinaryHeap::new()),
+            condvar: Condvar::new(),
+        });
+
+        thread::Builder::new()
+            .name(String::from("audio-render-timer"))
+            .spawn({
+                let timer = timer.clone();
+                move || timer.run()
+            })
+            .expect("failed to spawn render timer thread");
+
+        timer
+    })
+}
+
+struct Timer {
+    queue: Mutex<BinaryHeap<Reverse<Entry>>>,
+    condvar: Condvar,
+}
+
+impl Timer {
+    /// Schedule the given waker to be woken at the deadline.
+    fn schedule(&self, deadline: Instant, waker: Weak<AtomicWaker>) {
+        let mut queue = self.queue.lock().unwrap();
+
+        // NB: the timer thread only needs to be notified if it's waiting for
+        // a later deadline.
+        let earliest = match queue.peek() {
+            Some(Reverse(entry)) => deadline < entry.deadline,
+            None => true,
+        };
+
+        queue.push(Reverse(Entry { deadline, waker }));
+
+        if earliest {
+            self.condvar.notify_one();
+        }
+    }
+
+    fn run(&self) {
+        let mut expired = Vec::new();
+        let mut queue = self.queue.lock().unwrap();
+
+        loop {
+            let now = Instant::now();
+
+            while let Some(Reverse(entry)) = queue.peek() {
+                if entry.deadline > now {
+                    break;
+                }
+
+                if let Some(Reverse(entry)) = queue.pop() {
+                    expired.push(entry.waker);
+                }
+            }
+
+            if !expired.is_empty() {
+                // NB: tasks are woken without holding the lock, in case waking
+                // them schedules another delay.
+                drop(queue);
+
+                for waker in expired.drain(..) {
+                    if let Some(waker) = waker.upgrade() {
+                        waker.wake();
+                    }
+                }
+
+                queue = self.queue.lock().unwrap();
+                continue;
+            }
+
+            queue = match queue.peek() {
+                Some(Reverse(entry)) => {
+                    let timeout = entry.deadline - now;
+                    self.condvar.wait_timeout(queue, timeout).unwrap().0
+                }
+                None => self.condvar.wait(queue).unwrap(),
+            };
+        }
+    }
+}
+
+/// A queued deadline, whose waker is dropped if the delay is.
+struct Entry {
+    deadline: Instant,
+    waker: Weak<AtomicWaker>,
+}
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.deadline == other.deadline
+    }
+}
+
+impl Eq for Entry {}
+
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Entry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.deadline.cmp(&other.deadline)
+    }
+}
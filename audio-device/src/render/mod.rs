@@ -0,0 +1,115 @@
+//! A backend which renders audio into a WAV file instead of playing it on a
+//! device.
+//!
+//! Rendering can either happen as fast as possible, or be paced by a
+//! [Clock][crate::null::Clock] at a configurable speed to simulate a real
+//! device. This is useful for regression testing audio pipelines and for
+//! bouncing them offline.
+
+use std::io;
+
+use thiserror::Error;
+
+mod sample;
+pub use self::sample::Sample;
+
+mod output;
+pub use self::output::Output;
+
+mod writer;
+pub use self::writer::Writer;
+
+mod async_writer;
+pub use self::async_writer::AsyncWriter;
+
+mod delay;
+
+/// Errors that can be raised by the render backend.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// An I/O error raised by the underlying sink.
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+    /// Error raised when there's a channel count mismatch between a buffer and
+    /// the output it's being used with.
+    #[error("mismatch in number of channels in buffer; actual = {actual}, expected = {expected}")]
+    ChannelsMismatch {
+        /// The actual number of channels.
+        actual: usize,
+        /// The expected number of channels.
+        expected: usize,
+    },
+    /// Error raised when the configuration can't be represented in a WAV
+    /// file.
+    #[error("unsupported configuration: {0}")]
+    Unsupported(&'static str),
+    /// Error raised when too much audio has been written to fit in a WAV
+    /// file.
+    #[error("too much audio written to fit in a WAV file")]
+    TooLarge,
+    /// Error raised when writing to an output which has already been
+    /// finished.
+    #[error("output has already been finished")]
+    Finished,
+}
+
+impl Error {
+    pub(crate) fn kind(&self) -> crate::ErrorKind {
+        use crate::ErrorKind;
+
+        match self {
+            Self::Io(error) => crate::error::io_error_kind(error),
+            Self::ChannelsMismatch { .. } | Self::Finished => ErrorKind::InvalidInput,
+            Self::Unsupported(..) => ErrorKind::UnsupportedFormat,
+            Self::TooLarge => ErrorKind::Other,
+        }
+    }
+}
+
+/// Helper result wrapper.
+pub type Result<T, E = Error> = ::std::result::Result<T, E>;
+
+/// The pace at which audio is rendered.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Pace {
+    /// Render audio as fast as possible.
+    Unlimited,
+    /// Render audio at the given multiple of the sample rate, where `1.0`
+    /// corresponds to realtime.
+    Speed(f64),
+}
+
+/// The configuration of a rendered output.
+///
+/// # Examples
+///
+/// ```
+/// use audio_device::render::{Config, Pace};
+///
+/// let config = Config {
+///     pace: Pace::Speed(1.0),
+///     ..Config::default()
+/// };
+///
+/// assert_eq!(config.channels, 2);
+/// assert_eq!(config.sample_rate, 48000);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Config {
+    /// The number of interleaved channels.
+    pub channels: usize,
+    /// The sample rate in frames per second.
+    pub sample_rate: u32,
+    /// The pace at which audio is rendered.
+    pub pace: Pace,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            channels: 2,
+            sample_rate: 48000,
+            pace: Pace::Unlimited,
+        }
+    }
+}
@@ -0,0 +1,314 @@
+use core::marker;
+use std::convert::TryFrom;
+use std::fs;
+use std::io::{self, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::time::Instant;
+
+use crate::null::Clock;
+use crate::render::{AsyncWriter, Config, Error, Pace, Result, Sample, Writer};
+
+/// The format tag of floating point samples.
+const WAVE_FORMAT_IEEE_FLOAT: u16 = 3;
+
+/// An output which renders audio into a WAV file.
+///
+/// The header of the file is written when the output is constructed, and the
+/// sizes recorded in it are updated once the output is
+/// [finished][Output::finish]. If the output is dropped without being
+/// finished, the header is updated on a best-effort basis.
+///
+/// # Examples
+///
+/// ```
+/// use std::io;
+/// use audio_device::render;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let config = render::Config::default();
+/// let mut output = render::Output::<i16, _>::new(io::Cursor::new(Vec::new()), config)?;
+///
+/// let samples = vec![0i16; config.channels * 1024];
+/// output.writer().write_interleaved(audio::wrap::interleaved(&samples[..], config.channels))?;
+/// assert_eq!(output.position(), 1024);
+///
+/// let wav = output.finish()?.into_inner();
+/// assert_eq!(&wav[..4], b"RIFF");
+/// assert_eq!(wav.len(), 44 + samples.len() * 2);
+/// assert_eq!(&wav[40..44], &(samples.len() as u32 * 2).to_le_bytes());
+/// # Ok(()) }
+/// ```
+pub struct Output<T, W = io::BufWriter<fs::File>>
+where
+    W: Write + Seek,
+{
+    sink: Option<W>,
+    config: Config,
+    header: Header,
+    clock: Option<Clock>,
+    written: u64,
+    data: u32,
+    scratch: Vec<u8>,
+    _marker: marker::PhantomData<T>,
+}
+
+impl<T> Output<T>
+where
+    T: Sample,
+{
+    /// Create a WAV file at the given path and render into it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::render;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut output = render::Output::<f32>::create("bounce.wav", render::Config::default())?;
+    /// # Ok(()) }
+    /// ```
+    pub fn create<P>(path: P, config: Config) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let file = fs::File::create(path)?;
+        Self::new(io::BufWriter::new(file), config)
+    }
+}
+
+impl<T, W> Output<T, W>
+where
+    T: Sample,
+    W: Write + Seek,
+{
+    /// Render into the given sink, which must be positioned at the start of
+    /// where the WAV file should be written.
+    ///
+    /// Floating point samples are described with an extended format chunk
+    /// and a `fact` chunk recording the number of frames, which strict readers
+    /// require.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io;
+    /// use audio_device::render;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let config = render::Config::default();
+    /// let mut output = render::Output::<f32, _>::new(io::Cursor::new(Vec::new()), config)?;
+    ///
+    /// let samples = vec![0.0f32; config.channels * 256];
+    /// output.writer().write_interleaved(audio::wrap::interleaved(&samples[..], config.channels))?;
+    ///
+    /// let wav = output.finish()?.into_inner();
+    /// assert_eq!(wav.len(), 58 + samples.len() * 4);
+    /// assert_eq!(&wav[20..22], &3u16.to_le_bytes());
+    /// assert_eq!(&wav[38..42], b"fact");
+    /// assert_eq!(&wav[46..50], &256u32.to_le_bytes());
+    /// assert_eq!(&wav[50..54], b"data");
+    /// assert_eq!(&wav[54..58], &(samples.len() as u32 * 4).to_le_bytes());
+    /// # Ok(()) }
+    /// ```
+    pub fn new(mut sink: W, config: Config) -> Result<Self> {
+        if let Pace::Speed(speed) = config.pace {
+            if speed.is_nan() || speed <= 0.0 {
+                return Err(Error::Unsupported("speed must be positive"));
+            }
+        }
+
+        let channels = u16::try_from(config.channels)
+            .ok()
+            .filter(|c| *c > 0)
+            .ok_or(Error::Unsupported("number of channels"))?;
+
+        let block_align = u32::from(channels) * u32::from(T::BITS / 8);
+        let byte_rate = block_align
+            .checked_mul(config.sample_rate)
+            .ok_or(Error::Unsupported("sample rate"))?;
+
+        let header = Header {
+            format: T::FORMAT,
+            channels,
+            sample_rate: config.sample_rate,
+            byte_rate,
+            block_align: block_align as u16,
+            bits: T::BITS,
+        };
+
+        sink.write_all(&header.encode(0))?;
+
+        Ok(Self {
+            sink: Some(sink),
+            config,
+            header,
+            clock: None,
+            written: 0,
+            data: 0,
+            scratch: Vec::new(),
+            _marker: marker::PhantomData,
+        })
+    }
+
+    /// The configuration of the output.
+    pub fn config(&self) -> Config {
+        self.config
+    }
+
+    /// The number of frames which have been rendered.
+    pub fn position(&self) -> u64 {
+        self.written
+    }
+
+    /// Construct a writer for the output.
+    pub fn writer(&mut self) -> Writer<'_, T, W> {
+        Writer::new(self)
+    }
+
+    /// Construct an async writer for the output.
+    pub fn async_writer(&mut self) -> AsyncWriter<'_, T, W> {
+        AsyncWriter::new(self)
+    }
+
+    /// Finish rendering, updating the header of the WAV file and returning
+    /// the underlying sink.
+    pub fn finish(mut self) -> Result<W> {
+        let mut sink = self.sink.take().ok_or(Error::Finished)?;
+        finish(&mut sink, &self.header, self.data)?;
+        Ok(sink)
+    }
+
+    /// Write the given interleaved samples to the sink, returning the instant
+    /// at which rendering should continue if it's paced.
+    pub(super) fn write_samples(&mut self, samples: &[T]) -> Result<Option<Instant>> {
+        let sink = self.sink.as_mut().ok_or(Error::Finished)?;
+        let frames = (samples.len() / self.config.channels) as u64;
+
+        // NB: the clock starts with the first write.
+        if let Pace::Speed(speed) = self.config.pace {
+            let rate = (self.config.sample_rate as f64 * speed).max(1.0) as u32;
+            self.clock.get_or_insert_with(|| Clock::new(rate));
+        }
+
+        let current = self.data;
+        let header_size = self.header.size();
+        self.scratch.clear();
+        T::encode(samples, &mut self.scratch);
+
+        let data = u32::try_from(self.scratch.len())
+            .ok()
+            .and_then(|len| current.checked_add(len))
+            .filter(|data| data.checked_add(header_size).is_some())
+            .ok_or(Error::TooLarge)?;
+
+        sink.write_all(&self.scratch)?;
+        self.written += frames;
+        self.data = data;
+
+        Ok(self
+            .clock
+            .as_ref()
+            .map(|clock| Instant::now() + clock.until(self.written)))
+    }
+
+    pub(super) fn check_channels<B>(&self, buf: &B) -> Result<()>
+    where
+        B: audio_core::Buf,
+    {
+        if buf.channels() != self.config.channels {
+            return Err(Error::ChannelsMismatch {
+                actual: buf.channels(),
+                expected: self.config.channels,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl<T, W> Drop for Output<T, W>
+where
+    W: Write + Seek,
+{
+    fn drop(&mut self) {
+        if let Some(mut sink) = self.sink.take() {
+            let _ = finish(&mut sink, &self.header, self.data);
+        }
+    }
+}
+
+/// Update the sizes recorded in the header and flush the sink.
+fn finish<W>(sink: &mut W, header: &Header, data: u32) -> Result<()>
+where
+    W: Write + Seek,
+{
+    let end = sink.stream_position()?;
+    let start = end - u64::from(header.size() + data);
+
+    sink.seek(SeekFrom::Start(start))?;
+    sink.write_all(&header.encode(data))?;
+    sink.seek(SeekFrom::Start(end))?;
+    sink.flush()?;
+    Ok(())
+}
+
+/// The header of a WAV file.
+struct Header {
+    format: u16,
+    channels: u16,
+    sample_rate: u32,
+    byte_rate: u32,
+    block_align: u16,
+    bits: u16,
+}
+
+impl Header {
+    /// Test if the header is for floating point samples, which are described
+    /// with an extended format chunk and a `fact` chunk.
+    fn is_float(&self) -> bool {
+        self.format == WAVE_FORMAT_IEEE_FLOAT
+    }
+
+    /// The size of the header in bytes.
+    fn size(&self) -> u32 {
+        if self.is_float() {
+            58
+        } else {
+            44
+        }
+    }
+
+    /// Encode the header for the given number of bytes of sample data.
+    fn encode(&self, data: u32) -> Vec<u8> {
+        let size = self.size();
+        let mut header = Vec::with_capacity(size as usize);
+        header.extend_from_slice(b"RIFF");
+        header.extend_from_slice(&(size - 8 + data).to_le_bytes());
+        header.extend_from_slice(b"WAVE");
+
+        header.extend_from_slice(b"fmt ");
+        let fmt_size: u32 = if self.is_float() { 18 } else { 16 };
+        header.extend_from_slice(&fmt_size.to_le_bytes());
+        header.extend_from_slice(&self.format.to_le_bytes());
+        header.extend_from_slice(&self.channels.to_le_bytes());
+        header.extend_from_slice(&self.sample_rate.to_le_bytes());
+        header.extend_from_slice(&self.byte_rate.to_le_bytes());
+        header.extend_from_slice(&self.block_align.to_le_bytes());
+        header.extend_from_slice(&self.bits.to_le_bytes());
+
+        if self.is_float() {
+            // NB: formats other than integer PCM record the size of the
+            // format extension, which is empty, and the number of frames in a
+            // `fact` chunk.
+            header.extend_from_slice(&0u16.to_le_bytes());
+            header.extend_from_slice(b"fact");
+            header.extend_from_slice(&4u32.to_le_bytes());
+            let frames = data / u32::from(self.block_align);
+            header.extend_from_slice(&frames.to_le_bytes());
+        }
+
+        header.extend_from_slice(b"data");
+        header.extend_from_slice(&data.to_le_bytes());
+        header
+    }
+}
@@ -0,0 +1,46 @@
+/// Trait used to designate types which can be stored in a WAV file.
+///
+/// # Examples
+///
+/// ```
+/// use audio_device::render::Sample;
+///
+/// assert_eq!(<i16 as Sample>::BITS, 16);
+/// assert_eq!(<i16 as Sample>::FORMAT, 1);
+/// assert_eq!(<f32 as Sample>::BITS, 32);
+/// assert_eq!(<f32 as Sample>::FORMAT, 3);
+/// ```
+pub trait Sample: Copy {
+    /// The WAV format tag of the sample, which is `1` for integer PCM and `3`
+    /// for floating point samples.
+    const FORMAT: u16;
+
+    /// The number of bits in the sample.
+    const BITS: u16;
+
+    /// Append the little endian encoding of the given samples to `out`.
+    fn encode(samples: &[Self], out: &mut Vec<u8>);
+}
+
+macro_rules! implement {
+    ($ty:ty, $format:expr) => {
+        impl Sample for $ty {
+            const FORMAT: u16 = $format;
+            const BITS: u16 = (::core::mem::size_of::<$ty>() * 8) as u16;
+
+            fn encode(samples: &[Self], out: &mut Vec<u8>) {
+                for sample in samples {
+                    out.extend_from_slice(&sample.to_le_bytes());
+                }
+            }
+        }
+    };
+}
+
+// NB: 8-bit samples in WAV files are unsigned, while wider samples are
+// signed.
+implement!(u8, 1);
+implement!(i16, 1);
+implement!(i32, 1);
+implement!(f32, 3);
+implement!(f64, 3);
@@ -0,0 +1,54 @@
+use std::io::{Seek, Write};
+use std::thread;
+use std::time::Instant;
+
+use crate::buf_io;
+use crate::render::{Output, Result, Sample};
+
+/// An interleaved type-checked writer for an [Output].
+///
+/// See [Output::writer].
+pub struct Writer<'a, T, W>
+where
+    W: Write + Seek,
+{
+    output: &'a mut Output<T, W>,
+}
+
+impl<'a, T, W> Writer<'a, T, W>
+where
+    T: Sample,
+    W: Write + Seek,
+{
+    pub(super) fn new(output: &'a mut Output<T, W>) -> Self {
+        Self { output }
+    }
+
+    /// Write an interleaved buffer, blocking until it's been rendered at the
+    /// configured pace.
+    pub fn write_interleaved<B>(&mut self, mut buf: B) -> Result<()>
+    where
+        B: audio_core::ReadBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBuf<Sample = T>,
+    {
+        self.output.check_channels(&buf)?;
+
+        let channels = self.output.config().channels;
+        let frames = buf_io::readable_frames(&buf, channels);
+        let deadline = self
+            .output
+            .write_samples(&buf.as_interleaved()[..frames * channels])?;
+        buf.advance(frames);
+
+        if let Some(deadline) = deadline {
+            let now = Instant::now();
+
+            if deadline > now {
+                thread::sleep(deadline - now);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,255 @@
+use crate::loom::sync::Arc;
+use crate::runtime::executor::Executor;
+use crate::runtime::{Drivers, Runtime};
+use crate::Result;
+
+/// The scheduling priority of the threads driving the [Runtime].
+///
+/// Priorities which aren't supported on the current platform are ignored with
+/// a warning, as are failures to apply them. Real-time scheduling on Unix
+/// typically requires the `CAP_SYS_NICE` capability or a suitable
+/// `RLIMIT_RTPRIO`.
+///
+/// See [Builder::priority].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ThreadPriority {
+    /// Run the threads at the default priority of the system.
+    Normal,
+    /// Use the `SCHED_FIFO` real-time policy with the given priority. Only
+    /// supported on Unix.
+    Fifo(u8),
+    /// Use the `SCHED_RR` real-time policy with the given priority. Only
+    /// supported on Unix.
+    RoundRobin(u8),
+    /// Register the threads with the given task of the Multimedia Class
+    /// Scheduler Service (MMCSS), like `"Pro Audio"`. Only supported on
+    /// Windows.
+    Mmcss(String),
+}
+
+/// A builder for a [Runtime], configuring the threads driving it.
+///
+/// See [Runtime::builder].
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::runtime::{Runtime, ThreadPriority};
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let runtime = Runtime::builder()
+///     .thread_name("audio-driver")
+///     .priority(ThreadPriority::Fifo(10))
+///     .affinity([2])
+///     .build()?;
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(
+    not(any(feature = "events-driver", feature = "poll-driver")),
+    allow(dead_code)
+)]
+pub struct Builder {
+    pub(super) name: Option<String>,
+    pub(super) priority: Option<ThreadPriority>,
+    pub(super) affinity: Option<Vec<usize>>,
+}
+
+impl Builder {
+    /// Construct a new builder with the default configuration.
+    ///
+    /// By default threads run at the default priority of the system, except
+    /// on Windows where the events driver is registered with the `"Pro Audio"`
+    /// MMCSS task.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the name of the threads driving the runtime.
+    pub fn thread_name<N>(self, name: N) -> Self
+    where
+        N: Into<String>,
+    {
+        Self {
+            name: Some(name.into()),
+            ..self
+        }
+    }
+
+    /// Set the scheduling priority of the threads driving the runtime.
+    ///
+    /// Since these threads wake up the tasks reading and writing audio, running
+    /// them at default priority adds jitter when the system is under load.
+    pub fn priority(self, priority: ThreadPriority) -> Self {
+        Self {
+            priority: Some(priority),
+            ..self
+        }
+    }
+
+    /// Restrict the threads driving the runtime to the CPUs with the given
+    /// indexes. Only supported on Linux and Windows.
+    pub fn affinity<I>(self, cpus: I) -> Self
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        Self {
+            affinity: Some(cpus.into_iter().collect()),
+            ..self
+        }
+    }
+
+    /// Construct the runtime.
+    pub fn build(self) -> Result<Runtime> {
+        let drivers = Drivers {
+            #[cfg(feature = "events-driver")]
+            events: super::events::EventsDriver::with_builder(&self)?,
+            #[cfg(feature = "poll-driver")]
+            poll: super::poll::PollDriver::with_builder(&self)?,
+        };
+
+        Ok(Runtime {
+            executor: Executor::new(),
+            drivers: Arc::new(drivers),
+        })
+    }
+
+    /// Spawn a thread driving the runtime, which is configured before `f` is
+    /// called.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the thread can't be spawned, like [std::thread::spawn].
+    #[cfg(any(feature = "events-driver", feature = "poll-driver"))]
+    pub(super) fn spawn<F>(&self, f: F) -> super::join::JoinHandle
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let mut builder = crate::loom::thread::Builder::new();
+
+        if let Some(name) = &self.name {
+            builder = builder.name(name.clone());
+        }
+
+        let priority = self.priority.clone();
+        let affinity = self.affinity.clone();
+
+        let result = super::join::spawn(builder, move || {
+            let _priority = priority.and_then(|priority| match sys::set_priority(&priority) {
+                Ok(guard) => Some(guard),
+                Err(error) => {
+                    tracing::warn!(?priority, %error, "failed to set driver thread priority");
+                    None
+                }
+            });
+
+            if let Some(cpus) = affinity {
+                if let Err(error) = sys::set_affinity(&cpus) {
+                    tracing::warn!(?cpus, %error, "failed to set driver thread affinity");
+                }
+            }
+
+            f()
+        });
+
+        result.expect("failed to spawn driver thread")
+    }
+}
+
+#[cfg(all(unix, feature = "poll-driver"))]
+mod sys {
+    use super::ThreadPriority;
+    use std::io;
+    use std::mem;
+
+    pub(super) fn set_priority(priority: &ThreadPriority) -> io::Result<()> {
+        let (policy, value) = match *priority {
+            ThreadPriority::Normal => return Ok(()),
+            ThreadPriority::Fifo(value) => (libc::SCHED_FIFO, value),
+            ThreadPriority::RoundRobin(value) => (libc::SCHED_RR, value),
+            ThreadPriority::Mmcss(..) => return Err(unsupported()),
+        };
+
+        // NB: some systems have additional fields.
+        let mut param: libc::sched_param = unsafe { mem::zeroed() };
+        param.sched_priority = value as libc::c_int;
+
+        let result = unsafe { libc::pthread_setschedparam(libc::pthread_self(), policy, &param) };
+
+        if result != 0 {
+            return Err(io::Error::from_raw_os_error(result));
+        }
+
+        Ok(())
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub(super) fn set_affinity(cpus: &[usize]) -> io::Result<()> {
+        let mut set: libc::cpu_set_t = unsafe { mem::zeroed() };
+
+        for &cpu in cpus {
+            if cpu >= libc::CPU_SETSIZE as usize {
+                return Err(io::Error::from_raw_os_error(libc::EINVAL));
+            }
+
+            unsafe {
+                libc::CPU_SET(cpu, &mut set);
+            }
+        }
+
+        let result = unsafe { libc::sched_setaffinity(0, mem::size_of::<libc::cpu_set_t>(), &set) };
+
+        if result < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(())
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    pub(super) fn set_affinity(_: &[usize]) -> io::Result<()> {
+        Err(unsupported())
+    }
+
+    fn unsupported() -> io::Error {
+        io::Error::new(io::ErrorKind::Other, "not supported on this platform")
+    }
+}
+
+#[cfg(all(windows, feature = "events-driver"))]
+mod sys {
+    use super::ThreadPriority;
+    use crate::windows::MmcssRegistration;
+    use windows::core::{Error, Result};
+    use windows::Win32::Foundation as f;
+    use windows::Win32::System::Threading as th;
+
+    pub(super) fn set_priority(priority: &ThreadPriority) -> Result<Option<MmcssRegistration>> {
+        match priority {
+            ThreadPriority::Normal => Ok(None),
+            ThreadPriority::Mmcss(task) => Ok(Some(crate::windows::register_mmcss_thread(task)?)),
+            ThreadPriority::Fifo(..) | ThreadPriority::RoundRobin(..) => {
+                Err(Error::from(f::E_NOTIMPL))
+            }
+        }
+    }
+
+    pub(super) fn set_affinity(cpus: &[usize]) -> Result<()> {
+        let mut mask = 0usize;
+
+        for &cpu in cpus {
+            if cpu >= usize::BITS as usize {
+                return Err(Error::from(f::E_INVALIDARG));
+            }
+
+            mask |= 1 << cpu;
+        }
+
+        if unsafe { th::SetThreadAffinityMask(th::GetCurrentThread(), mask) } == 0 {
+            return Err(Error::from_win32());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,70 @@
+//! Batching of the tasks woken up by a driver.
+//!
+//! Drivers collect the tasks to wake up while they go through the events of a
+//! wakeup, and only wake them up once all events have been handled. A task
+//! waiting on several handles which became ready at the same time, like the
+//! descriptors of a single stream, is only woken up once.
+
+use crate::runtime::atomic_waker::AtomicWaker;
+use crate::runtime::metrics::Metrics;
+use std::task::Waker;
+use std::time::{Duration, Instant};
+
+/// The tasks to wake up in response to a single wakeup of a driver, which are
+/// woken up when this is dropped.
+pub(crate) struct Dispatch<'a> {
+    metrics: &'a Metrics,
+    /// When the driver observed that handles were ready.
+    ready: Instant,
+    /// Buffer of wakers, reused across wakeups by the driver.
+    wakers: &'a mut Vec<Waker>,
+}
+
+impl<'a> Dispatch<'a> {
+    /// Start dispatching the events of a wakeup, which have been observed to
+    /// be ready now.
+    pub(crate) fn new(metrics: &'a Metrics, wakers: &'a mut Vec<Waker>) -> Self {
+        metrics.wakeup();
+
+        Self {
+            metrics,
+            ready: Instant::now(),
+            wakers,
+        }
+    }
+
+    /// Queue the task registered with the given waker to be woken up, unless
+    /// it already is.
+    pub(crate) fn wake(&mut self, waker: &AtomicWaker) {
+        let waker = match waker.take_waker() {
+            Some(waker) => waker,
+            None => return,
+        };
+
+        if !self.wakers.iter().any(|w| w.will_wake(&waker)) {
+            self.wakers.push(waker);
+        }
+    }
+}
+
+impl Drop for Dispatch<'_> {
+    fn drop(&mut self) {
+        if self.wakers.is_empty() {
+            return;
+        }
+
+        let woken = self.wakers.len() as u64;
+        let mut latency = Duration::default();
+        let mut max_latency = Duration::default();
+
+        for waker in self.wakers.drain(..) {
+            let elapsed = self.ready.elapsed();
+            latency += elapsed;
+            max_latency = max_latency.max(elapsed);
+            waker.wake();
+        }
+
+        self.metrics.dispatched(woken, latency, max_latency);
+        tracing::trace!(woken, ?max_latency, "dispatched");
+    }
+}
@@ -0,0 +1,312 @@
+//! A backend for the [PollDriver][super::PollDriver] built on epoll, which is
+//! the default on Linux.
+//!
+//! Descriptors are registered once with `EPOLLONESHOT`, which causes the
+//! kernel to disarm them after they've reported readiness. Releasing the
+//! returned events of a handle arms it again. Registrations are stored in a
+//! [Slab] whose keys are handed to the kernel, so readiness is dispatched
+//! without searching for the handle it belongs to.
+//!
+//! A descriptor can only be registered once with an epoll instance, so
+//! additional handles for the same descriptor register a duplicate of it.
+
+use crate::loom::sync::Arc;
+use crate::runtime::dispatch::Dispatch;
+use crate::runtime::poll::{Events, Shared, Token, Waker};
+use crate::runtime::slab::{Slab, PARKER};
+use crate::unix::Errno;
+use crate::Result;
+use std::collections::HashMap;
+use std::io;
+use std::sync::atomic::Ordering;
+use std::task;
+
+/// The maximum number of events received per wakeup.
+const EVENTS: usize = 64;
+
+struct Registration {
+    waker: Arc<Waker>,
+    /// The descriptor registered with epoll, which is owned by the
+    /// registration if it's a duplicate.
+    fd: libc::c_int,
+    dup: bool,
+    /// The descriptor can't be used with epoll, like regular files. Like with
+    /// `poll(2)` it's treated as always being ready.
+    always_ready: bool,
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        if self.dup {
+            unsafe {
+                let _ = libc::close(self.fd);
+            }
+        }
+    }
+}
+
+pub(super) struct Driver {
+    epfd: libc::c_int,
+    /// The slab key of a given token.
+    keys: HashMap<Token, u64>,
+    events: Vec<libc::epoll_event>,
+    /// Buffer of tasks to wake up.
+    woken: Vec<task::Waker>,
+}
+
+impl Driver {
+    /// Construct a new epoll instance.
+    pub(super) fn new() -> Result<Self, Errno> {
+        let epfd = unsafe { libc::epoll_create1(libc::EPOLL_CLOEXEC) };
+
+        if epfd < 0 {
+            return Err(last_errno());
+        }
+
+        Ok(Self {
+            epfd,
+            keys: HashMap::new(),
+            events: Vec::with_capacity(EVENTS),
+            woken: Vec::new(),
+        })
+    }
+
+    fn run(mut self, guard: &mut PanicGuard) -> Result<()> {
+        self.ctl(
+            libc::EPOLL_CTL_ADD,
+            guard.shared.parker.fd,
+            libc::EPOLLIN as u32,
+            PARKER,
+        )?;
+
+        while guard.shared.running.load(Ordering::Acquire) {
+            let n = unsafe {
+                libc::epoll_wait(
+                    self.epfd,
+                    self.events.as_mut_ptr(),
+                    self.events.capacity() as libc::c_int,
+                    -1,
+                )
+            };
+
+            if n < 0 {
+                let errno = last_errno();
+
+                if errno.value() == libc::EINTR {
+                    continue;
+                }
+
+                return Err(errno.into());
+            }
+
+            // Safety: the kernel initialized this many events.
+            unsafe {
+                self.events.set_len(n as usize);
+            }
+
+            let mut dispatch = Dispatch::new(&guard.shared.metrics, &mut self.woken);
+            let mut notified = false;
+
+            for e in self.events.drain(..) {
+                let key = e.u64;
+
+                if key == PARKER {
+                    notified = true;
+                    continue;
+                }
+
+                // NB: readiness of removed handles is ignored.
+                if let Some(registration) = guard.registrations.get(key) {
+                    // NB: poll and epoll events share the same values.
+                    let waker = &registration.waker;
+                    waker
+                        .returned_events
+                        .store(e.events as u16 as usize, Ordering::Release);
+                    dispatch.wake(&waker.waker);
+                }
+            }
+
+            drop(dispatch);
+
+            if notified {
+                let _ = guard.shared.parker.recv()?;
+                let mut holders = guard.shared.holders.lock();
+                guard.shared.metrics.queue(holders.len());
+                self.process(&mut holders, &mut guard.registrations);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Process all queued registrations.
+    fn process(&mut self, events: &mut Events, registrations: &mut Slab<Registration>) {
+        // NB: removals are processed first, since the descriptor of a removed
+        // handle might've been closed and its number reused by an added one.
+        for token in events.removed.drain(..) {
+            if let Some(key) = self.keys.remove(&token) {
+                if let Some(r) = registrations.remove(key) {
+                    // NB: a duplicate is unregistered once it's closed when
+                    // the registration is dropped.
+                    if !r.dup && !r.always_ready {
+                        // NB: this fails if the descriptor has already been
+                        // closed, which also unregisters it.
+                        let _ = self.ctl(libc::EPOLL_CTL_DEL, r.fd, 0, key);
+                    }
+                }
+            }
+        }
+
+        for waker in events.added.drain(..) {
+            let d = waker.descriptor;
+            let token = waker.token();
+
+            let key = registrations.insert(Registration {
+                waker: waker.clone(),
+                fd: d.fd,
+                dup: false,
+                always_ready: false,
+            });
+
+            self.keys.insert(token, key);
+
+            let mut result = self.ctl(libc::EPOLL_CTL_ADD, d.fd, interest(&d), key);
+
+            if let Err(errno) = result {
+                if errno.value() == libc::EEXIST {
+                    result = self.add_dup(d, key, registrations);
+                }
+            }
+
+            match result {
+                Ok(()) => (),
+                Err(errno) if errno.value() == libc::EPERM => {
+                    if let Some(r) = registrations.get_mut(key) {
+                        r.always_ready = true;
+                    }
+
+                    ready(&waker, d.events);
+                }
+                Err(errno) => {
+                    tracing::error!(%errno, fd = d.fd, "failed to register descriptor");
+                    ready(&waker, libc::POLLERR);
+                }
+            }
+        }
+
+        for token in events.released.drain(..) {
+            let key = match self.keys.get(&token) {
+                Some(&key) => key,
+                None => continue,
+            };
+
+            let r = match registrations.get(key) {
+                Some(r) => r,
+                None => continue,
+            };
+
+            let d = r.waker.descriptor;
+
+            if r.always_ready {
+                ready(&r.waker, d.events);
+                continue;
+            }
+
+            if let Err(errno) = self.ctl(libc::EPOLL_CTL_MOD, r.fd, interest(&d), key) {
+                tracing::error!(%errno, fd = d.fd, "failed to re-arm descriptor");
+                ready(&r.waker, libc::POLLERR);
+            }
+        }
+    }
+
+    /// Register a duplicate of a descriptor which is already registered by
+    /// another handle.
+    fn add_dup(
+        &self,
+        d: libc::pollfd,
+        key: u64,
+        registrations: &mut Slab<Registration>,
+    ) -> Result<(), Errno> {
+        let fd = unsafe { libc::fcntl(d.fd, libc::F_DUPFD_CLOEXEC, 0) };
+
+        if fd < 0 {
+            return Err(last_errno());
+        }
+
+        if let Some(r) = registrations.get_mut(key) {
+            r.fd = fd;
+            r.dup = true;
+        }
+
+        self.ctl(libc::EPOLL_CTL_ADD, fd, interest(&d), key)
+    }
+
+    fn ctl(&self, op: libc::c_int, fd: libc::c_int, events: u32, key: u64) -> Result<(), Errno> {
+        let mut event = libc::epoll_event { events, u64: key };
+
+        if unsafe { libc::epoll_ctl(self.epfd, op, fd, &mut event) } < 0 {
+            return Err(last_errno());
+        }
+
+        Ok(())
+    }
+
+    pub(super) fn start(self, shared: Arc<Shared>) {
+        let mut guard = PanicGuard {
+            shared,
+            registrations: Slab::new(),
+        };
+
+        if let Err(e) = self.run(&mut guard) {
+            panic!("epoll thread errored: {}", e)
+        }
+    }
+}
+
+impl Drop for Driver {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = libc::close(self.epfd);
+        }
+    }
+}
+
+/// The epoll interest corresponding to the given descriptor.
+fn interest(d: &libc::pollfd) -> u32 {
+    d.events as u16 as u32 | libc::EPOLLONESHOT as u32
+}
+
+/// Store the returned events of a handle and wake it up.
+fn ready(waker: &Waker, events: libc::c_short) {
+    waker
+        .returned_events
+        .store(events as u16 as usize, Ordering::Release);
+    waker.waker.wake();
+}
+
+/// Wrap a guard around the resources allocated by the driver, which marks it
+/// as stopped when dropped, whether it was shut down or panicked.
+struct PanicGuard {
+    shared: Arc<Shared>,
+    registrations: Slab<Registration>,
+}
+
+impl Drop for PanicGuard {
+    fn drop(&mut self) {
+        self.shared.running.store(false, Ordering::Release);
+
+        // Wake up every waker so that they can observe that the driver
+        // stopped.
+        for r in self.registrations.iter() {
+            r.waker.waker.wake();
+        }
+    }
+}
+
+fn last_errno() -> Errno {
+    Errno::new(
+        io::Error::last_os_error()
+            .raw_os_error()
+            .unwrap_or_default(),
+    )
+}
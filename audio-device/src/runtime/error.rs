@@ -0,0 +1,38 @@
+use crate::ErrorKind;
+use thiserror::Error;
+
+/// Errors raised by handles which are driven by the audio runtime.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// The runtime has been shut down, so handles can no longer be registered
+    /// with it or waited on.
+    ///
+    /// See [Runtime::shutdown][crate::runtime::Runtime::shutdown].
+    #[error("audio runtime has been shut down")]
+    Shutdown,
+    #[cfg(feature = "unix")]
+    #[error("system error: {0}")]
+    /// A unix system error.
+    Unix(#[from] crate::unix::Errno),
+    #[cfg(feature = "windows")]
+    #[error("system error: {0}")]
+    /// A windows system error.
+    Windows(
+        #[from]
+        #[source]
+        windows::core::Error,
+    ),
+}
+
+impl Error {
+    pub(crate) fn kind(&self) -> ErrorKind {
+        match *self {
+            Self::Shutdown => ErrorKind::Shutdown,
+            #[cfg(feature = "unix")]
+            Self::Unix(errno) => errno.kind(),
+            #[cfg(feature = "windows")]
+            Self::Windows(ref error) => crate::windows::error_kind(error),
+        }
+    }
+}
@@ -1,11 +1,16 @@
 use crate::loom::sync::atomic::{AtomicBool, Ordering};
 use crate::loom::sync::{Arc, Mutex};
-use crate::loom::thread;
 use crate::runtime::atomic_waker::AtomicWaker;
+use crate::runtime::dispatch::Dispatch;
+use crate::runtime::join::JoinHandle;
+use crate::runtime::metrics::{Metrics, RuntimeMetrics};
+use crate::runtime::{Builder, Error, ThreadPriority};
 use crate::windows::{Event, RawEvent};
 use crate::Result;
 use std::io;
 use std::mem;
+use std::task::{Context, Poll};
+use std::time::Instant;
 use windows::Win32::Foundation as f;
 use windows::Win32::System::Threading as th;
 use windows::Win32::System::WindowsProgramming as wp;
@@ -21,6 +26,7 @@ struct Shared {
     running: AtomicBool,
     holders: Mutex<Holders>,
     parker: Event,
+    metrics: Metrics,
 }
 
 #[derive(Default)]
@@ -29,6 +35,14 @@ struct Holders {
     removed: Vec<Event>,
 }
 
+impl Holders {
+    /// Test if the driver needs to be notified of the next queued change,
+    /// since it processes everything which is queued at once.
+    fn needs_notify(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
 /// An executor to drive things which are woken up by [windows event
 /// objects].
 ///
@@ -37,7 +51,7 @@ struct Holders {
 /// [windows event objects]:
 /// https://docs.microsoft.com/en-us/windows/win32/sync/event-objects
 pub struct EventsDriver {
-    thread: Option<thread::JoinHandle<()>>,
+    thread: Mutex<Option<JoinHandle>>,
     shared: Arc<Shared>,
 }
 
@@ -45,42 +59,90 @@ impl EventsDriver {
     /// Construct a new events windows event object driver and return its
     /// handle.
     pub fn new() -> Result<Self> {
+        Self::with_builder(&Builder::new())
+    }
+
+    /// Construct a new driver whose thread is configured by the given
+    /// builder.
+    pub(crate) fn with_builder(builder: &Builder) -> Result<Self> {
+        // NB: the driver wakes up audio tasks, so unless told otherwise it's
+        // scheduled as such. Failing to register only affects latency.
+        let mut builder = builder.clone();
+        builder
+            .priority
+            .get_or_insert_with(|| ThreadPriority::Mmcss(String::from("Pro Audio")));
+
         let shared = Arc::new(Shared {
             running: AtomicBool::new(true),
             holders: Mutex::new(Holders::default()),
             parker: Event::new(false, false)?,
+            metrics: Metrics::new(),
         });
 
-        let thread = thread::spawn({
+        let thread = builder.spawn({
             let shared = shared.clone();
             || Driver::start(shared)
         });
 
         let handle = Self {
-            thread: Some(thread),
+            thread: Mutex::new(Some(thread)),
             shared,
         };
 
         Ok(handle)
     }
 
+    /// Take a snapshot of the counters of the driver.
+    pub fn metrics(&self) -> RuntimeMetrics {
+        self.shared.metrics.snapshot()
+    }
+
     /// Join the current handle.
     ///
     /// # Panics
     ///
     /// This panics if the background thread panicked. But this should only ever
     /// happen if there's a bug.
-    pub fn join(mut self) {
-        self.inner_join();
+    pub fn join(self) {
+        self.shutdown(None);
     }
 
-    fn inner_join(&mut self) {
-        if let Some(thread) = self.thread.take() {
+    /// Shut down the driver, waiting for its thread to finish until the given
+    /// deadline. Returns `false` if it didn't, in which case the thread is
+    /// detached.
+    ///
+    /// New events can't be registered once this is called, and every
+    /// registered event is woken up with an error.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the background thread panicked.
+    pub(crate) fn shutdown(&self, deadline: Option<Instant>) -> bool {
+        let thread = match self.thread.lock().take() {
+            Some(thread) => thread,
+            None => return true,
+        };
+
+        let added = {
+            let mut holders = self.shared.holders.lock();
             self.shared.running.store(false, Ordering::Release);
-            self.shared.parker.set();
+            mem::take(&mut holders.added)
+        };
+
+        // NB: the driver wakes up the events it has registered as it stops,
+        // but never gets to the ones which haven't been processed yet.
+        for waker in added {
+            waker.waker.wake();
+        }
+
+        self.shared.parker.set();
 
-            if thread.join().is_err() {
-                panic!("event handler thread panicked");
+        match thread.join(deadline) {
+            Some(Ok(())) => true,
+            Some(Err(..)) => panic!("event handler thread panicked"),
+            None => {
+                tracing::warn!("events driver thread didn't shut down in time");
+                false
             }
         }
     }
@@ -88,7 +150,7 @@ impl EventsDriver {
 
 impl Drop for EventsDriver {
     fn drop(&mut self) {
-        let _ = self.inner_join();
+        self.shutdown(None);
     }
 }
 
@@ -96,6 +158,8 @@ struct Driver {
     events: Vec<f::HANDLE>,
     wakers: Vec<Arc<Waker>>,
     shared: Arc<Shared>,
+    /// Buffer of tasks to wake up.
+    woken: Vec<std::task::Waker>,
 }
 
 impl Driver {
@@ -114,39 +178,53 @@ impl Driver {
                 )
             };
 
-            match result {
-                f::WAIT_ABANDONED_0 => panic!("wait abandoned"),
-                f::WAIT_TIMEOUT => panic!("timed out"),
-                f::WAIT_FAILED => {
-                    panic!("wait failed: {}", io::Error::last_os_error())
-                }
-                f::WIN32_ERROR(other) => {
-                    let base = f::WAIT_OBJECT_0.0;
+            let mut index = match signaled(result, self.events.len()) {
+                Some(index) => index,
+                None => panic!("timed out"),
+            };
 
-                    if other < base {
-                        panic!("other out of bounds; other = {}", other);
-                    }
+            let mut dispatch = Dispatch::new(&self.shared.metrics, &mut self.woken);
+            let mut notified = false;
+
+            loop {
+                // NB: index 0 is the wakeup to notify once things are
+                // added, any other is a legit registered event.
+                if index == 0 {
+                    notified = true;
+                } else if let Some(waker) = guard.wakers.get(index - 1) {
+                    waker.ready.store(true, Ordering::Release);
+                    dispatch.wake(&waker.waker);
+                }
 
-                    let index = (other - base) as usize;
+                // NB: only the first signaled event is reported, so the ones
+                // after it are checked without waiting. This dispatches all of
+                // them in one wakeup, and prevents events early in the list
+                // from starving the ones after them.
+                let rest = &self.events[index + 1..];
 
-                    if !(index < self.events.len()) {
-                        panic!("wakeup out of bounds; index = {}", index);
-                    }
+                if rest.is_empty() {
+                    break;
+                }
 
-                    // NB: index 0 is the wakeup to notify once things are
-                    // added, any other is a legit registered event.
-                    if index > 0 {
-                        if let Some(waker) = guard.wakers.get(index - 1) {
-                            waker.ready.store(true, Ordering::Release);
-                            waker.waker.wake();
-                        }
+                let result = unsafe { th::WaitForMultipleObjects(rest, false, 0) };
 
-                        continue;
-                    }
+                match signaled(result, rest.len()) {
+                    Some(n) => index += n + 1,
+                    None => break,
                 }
             }
 
+            drop(dispatch);
+
+            if !notified {
+                continue;
+            }
+
             let mut holders = self.shared.holders.lock();
+            self.shared
+                .metrics
+                .queue(holders.added.len() + holders.removed.len());
+
             let mut added = mem::replace(&mut holders.added, Vec::new());
 
             for waker in added.drain(..) {
@@ -170,10 +248,10 @@ impl Driver {
             holders.removed = removed;
         }
 
-        mem::forget(guard);
+        drop(guard);
 
-        /// Wrap a panic guard around self which will release any resources it
-        /// has allocated when dropped and mark itself as panicked.
+        /// Wrap a guard around the wakers of the driver, which marks it as
+        /// stopped when dropped, whether it was shut down or panicked.
         struct PanicGuard<'a> {
             shared: &'a Shared,
             wakers: &'a mut Vec<Arc<Waker>>,
@@ -183,7 +261,8 @@ impl Driver {
             fn drop(&mut self) {
                 self.shared.running.store(false, Ordering::Release);
 
-                // Wake up every waker so that they can observe the panic.
+                // Wake up every waker so that they can observe that the
+                // driver stopped.
                 for waker in self.wakers.iter() {
                     waker.waker.wake();
                 }
@@ -192,16 +271,46 @@ impl Driver {
     }
 
     fn start(shared: Arc<Shared>) {
+        let _span = tracing::debug_span!("events_driver").entered();
+
         let state = Driver {
             events: vec![unsafe { shared.parker.raw_event() }],
             wakers: vec![],
             shared,
+            woken: Vec::new(),
         };
 
         state.run()
     }
 }
 
+/// Decode the result of waiting for multiple objects into the index of the
+/// object which was signaled, or `None` if the wait timed out.
+fn signaled(result: f::WIN32_ERROR, len: usize) -> Option<usize> {
+    match result {
+        f::WAIT_ABANDONED_0 => panic!("wait abandoned"),
+        f::WAIT_TIMEOUT => None,
+        f::WAIT_FAILED => {
+            panic!("wait failed: {}", io::Error::last_os_error())
+        }
+        f::WIN32_ERROR(other) => {
+            let base = f::WAIT_OBJECT_0.0;
+
+            if other < base {
+                panic!("other out of bounds; other = {}", other);
+            }
+
+            let index = (other - base) as usize;
+
+            if !(index < len) {
+                panic!("wakeup out of bounds; index = {}", index);
+            }
+
+            Some(index)
+        }
+    }
+}
+
 /// An asynchronous variant of [Event].
 ///
 /// See [AsyncEvent::new].
@@ -215,14 +324,22 @@ impl AsyncEvent {
     /// Construct an asynchronous event associated with the current handle. The
     /// constructed event has the initial state specified by `initial_state`.
     ///
+    /// # Errors
+    ///
+    /// Errors with [Error::Shutdown] if the runtime has been shut down.
+    ///
     /// # Panics
     ///
     /// Panics unless an audio runtime is available.
     ///
     /// See [Runtime][crate::runtime::Runtime].
-    pub fn new(initial_state: bool) -> windows::core::Result<AsyncEvent> {
+    pub fn new(initial_state: bool) -> Result<AsyncEvent, Error> {
+        Self::from_event(Event::new(false, initial_state)?)
+    }
+
+    /// Register the given event with the driver of the current runtime.
+    pub(crate) fn from_event(event: Event) -> Result<AsyncEvent, Error> {
         crate::runtime::with_events(|events| {
-            let event = Event::new(false, initial_state)?;
             let handle = unsafe { event.raw_event() };
 
             let waker = Arc::new(Waker {
@@ -231,8 +348,19 @@ impl AsyncEvent {
                 handle,
             });
 
-            events.shared.holders.lock().added.push(waker.clone());
-            events.shared.parker.set();
+            let mut holders = events.shared.holders.lock();
+
+            if !events.shared.running.load(Ordering::Acquire) {
+                return Err(Error::Shutdown);
+            }
+
+            let notify = holders.needs_notify();
+            holders.added.push(waker.clone());
+            drop(holders);
+
+            if notify {
+                events.shared.parker.set();
+            }
 
             Ok(AsyncEvent {
                 shared: events.shared.clone(),
@@ -243,38 +371,50 @@ impl AsyncEvent {
     }
 
     /// Wait for the specified event handle to become set.
-    pub async fn wait(&self) {
+    ///
+    /// # Errors
+    ///
+    /// Errors with [Error::Shutdown] once the runtime has been shut down.
+    pub async fn wait(&self) -> Result<(), Error> {
         use std::future::Future;
         use std::pin::Pin;
-        use std::task::{Context, Poll};
 
-        return WaitFor {
-            shared: &*self.shared,
-            waker: &*self.waker,
-        }
-        .await;
+        return WaitFor(self).await;
 
-        struct WaitFor<'a> {
-            shared: &'a Shared,
-            waker: &'a Waker,
-        }
+        struct WaitFor<'a>(&'a AsyncEvent);
 
         impl Future for WaitFor<'_> {
-            type Output = ();
+            type Output = Result<(), Error>;
 
             fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-                if !self.shared.running.load(Ordering::Acquire) {
-                    panic!("background thread panicked");
-                }
+                self.0.poll_wait(cx)
+            }
+        }
+    }
 
-                if self.waker.ready.load(Ordering::Acquire) {
-                    return Poll::Ready(());
-                }
+    /// Poll for the specified event handle to become set.
+    ///
+    /// If the event is not set, the current task is registered to be woken up
+    /// once it is. Like the event itself, this resets once it has been
+    /// observed to be set.
+    ///
+    /// See [AsyncEvent::wait].
+    pub fn poll_wait(&self, cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        // NB: the event is reset as the driver waits on it, so the readiness
+        // it observed is consumed here.
+        if self.waker.ready.swap(false, Ordering::AcqRel) {
+            return Poll::Ready(Ok(()));
+        }
 
-                self.waker.waker.register_by_ref(cx.waker());
-                Poll::Pending
-            }
+        self.waker.waker.register_by_ref(cx.waker());
+
+        // NB: every event is woken up once the driver stops, whether it was
+        // shut down or panicked.
+        if !self.shared.running.load(Ordering::Acquire) {
+            return Poll::Ready(Err(Error::Shutdown));
         }
+
+        Poll::Pending
     }
 
     /// Set the current event handle.
@@ -292,8 +432,15 @@ impl RawEvent for AsyncEvent {
 impl Drop for AsyncEvent {
     fn drop(&mut self) {
         let event = self.event.take().unwrap();
-        self.shared.holders.lock().removed.push(event);
-        self.shared.parker.set();
+
+        let mut holders = self.shared.holders.lock();
+        let notify = holders.needs_notify();
+        holders.removed.push(event);
+        drop(holders);
+
+        if notify {
+            self.shared.parker.set();
+        }
     }
 }
 
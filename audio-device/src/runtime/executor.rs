@@ -0,0 +1,221 @@
+//! A minimal executor driving tasks spawned with
+//! [Runtime::spawn][super::Runtime::spawn].
+//!
+//! Tasks are polled one at a time on a single thread, which has the runtime
+//! entered for as long as it runs.
+
+use crate::loom::sync::atomic::{AtomicBool, Ordering};
+use crate::loom::sync::Arc;
+use crate::loom::thread;
+use crate::runtime::join::{self, JoinHandle};
+use crate::runtime::Drivers;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+// NB: the condition variable requires the mutex from std.
+use std::sync::{Condvar, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+use std::time::Instant;
+
+type BoxFuture = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+/// The executor of a runtime, whose thread is started the first time a task
+/// is spawned.
+pub(super) struct Executor {
+    thread: Mutex<Option<Thread>>,
+}
+
+impl Executor {
+    pub(super) fn new() -> Self {
+        Self {
+            thread: Mutex::new(None),
+        }
+    }
+
+    /// Spawn the given future, starting the executor thread if needed.
+    pub(super) fn spawn(&self, drivers: &Arc<Drivers>, future: BoxFuture) {
+        let mut thread = self.thread.lock().unwrap();
+        let thread = thread.get_or_insert_with(|| Thread::start(drivers.clone()));
+
+        let task = Arc::new(Task {
+            id: thread.next_id,
+            future: Mutex::new(Some(future)),
+            queue: thread.queue.clone(),
+            scheduled: AtomicBool::new(true),
+        });
+
+        thread.next_id += 1;
+        thread.queue.push(task);
+    }
+
+    /// Shut down the executor, waiting for its thread to finish until the
+    /// given deadline. Returns `false` if it didn't, in which case the thread
+    /// is detached.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the executor thread panicked.
+    pub(super) fn shutdown(&self, deadline: Option<Instant>) -> bool {
+        let thread = match self.thread.lock().unwrap().take() {
+            Some(thread) => thread,
+            None => return true,
+        };
+
+        thread.queue.state.lock().unwrap().running = false;
+        thread.queue.condvar.notify_one();
+
+        match thread.handle.join(deadline) {
+            Some(Ok(())) => true,
+            Some(Err(..)) => panic!("executor thread panicked"),
+            None => {
+                tracing::warn!("executor thread didn't shut down in time");
+                false
+            }
+        }
+    }
+}
+
+impl Drop for Executor {
+    fn drop(&mut self) {
+        self.shutdown(None);
+    }
+}
+
+struct Thread {
+    queue: Arc<Queue>,
+    handle: JoinHandle,
+    next_id: usize,
+}
+
+impl Thread {
+    fn start(drivers: Arc<Drivers>) -> Self {
+        let queue = Arc::new(Queue {
+            state: Mutex::new(State {
+                ready: VecDeque::new(),
+                running: true,
+            }),
+            condvar: Condvar::new(),
+        });
+
+        let handle = join::spawn(thread::Builder::new(), {
+            let queue = queue.clone();
+            move || run(&drivers, &queue)
+        })
+        .expect("failed to spawn executor thread");
+
+        Self {
+            queue,
+            handle,
+            next_id: 0,
+        }
+    }
+}
+
+fn run(drivers: &Drivers, queue: &Queue) {
+    let _guard = drivers.enter();
+
+    // NB: every task which hasn't completed, so that they can be dropped on
+    // shutdown. Otherwise a task which is waiting for a handle would keep
+    // itself alive through the waker registered with the handle.
+    let mut tasks = HashMap::new();
+
+    while let Some(task) = queue.pop() {
+        task.scheduled.store(false, Ordering::Release);
+        let mut future = task.future.lock().unwrap();
+
+        // NB: the task has already completed.
+        let f = match future.as_mut() {
+            Some(f) => f,
+            None => continue,
+        };
+
+        tasks.entry(task.id).or_insert_with(|| task.clone());
+
+        let waker = Waker::from(task.clone());
+        let mut cx = Context::from_waker(&waker);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| f.as_mut().poll(&mut cx)));
+
+        match result {
+            Ok(Poll::Pending) => continue,
+            Ok(Poll::Ready(())) => (),
+            Err(..) => {
+                tracing::error!(id = task.id, "spawned task panicked");
+            }
+        }
+
+        *future = None;
+        tasks.remove(&task.id);
+    }
+
+    for task in tasks.values() {
+        let future = task.future.lock().unwrap().take();
+        drop(future);
+    }
+
+    // NB: tasks queued before shutdown refer back to the queue.
+    queue.state.lock().unwrap().ready.clear();
+}
+
+struct Queue {
+    state: Mutex<State>,
+    condvar: Condvar,
+}
+
+struct State {
+    ready: VecDeque<Arc<Task>>,
+    running: bool,
+}
+
+impl Queue {
+    fn push(&self, task: Arc<Task>) {
+        let mut state = self.state.lock().unwrap();
+
+        // NB: tasks woken up after shutdown are never polled again.
+        if !state.running {
+            return;
+        }
+
+        state.ready.push_back(task);
+        drop(state);
+        self.condvar.notify_one();
+    }
+
+    /// Wait for the next task to poll, or `None` if the executor is shutting
+    /// down.
+    fn pop(&self) -> Option<Arc<Task>> {
+        let mut state = self.state.lock().unwrap();
+
+        loop {
+            if !state.running {
+                return None;
+            }
+
+            if let Some(task) = state.ready.pop_front() {
+                return Some(task);
+            }
+
+            state = self.condvar.wait(state).unwrap();
+        }
+    }
+}
+
+struct Task {
+    id: usize,
+    future: Mutex<Option<BoxFuture>>,
+    queue: Arc<Queue>,
+    /// If the task is queued to be polled, so that it's only queued once.
+    scheduled: AtomicBool,
+}
+
+impl Wake for Task {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        if !self.scheduled.swap(true, Ordering::AcqRel) {
+            self.queue.push(self.clone());
+        }
+    }
+}
@@ -0,0 +1,82 @@
+//! Threads which can be joined with a deadline, so that shutting down a
+//! runtime doesn't hang on a thread which is stuck.
+
+use crate::loom::sync::Arc;
+use crate::loom::thread;
+use std::io;
+// NB: the condition variable requires the mutex from std.
+use std::sync::{Condvar, Mutex};
+use std::time::Instant;
+
+/// A handle to a thread spawned with [spawn].
+pub(crate) struct JoinHandle {
+    handle: thread::JoinHandle<()>,
+    finished: Arc<Finished>,
+}
+
+impl JoinHandle {
+    /// Wait for the thread to finish, returning the result of joining it. If
+    /// the deadline is reached first, the thread is detached and `None` is
+    /// returned.
+    pub(crate) fn join(self, deadline: Option<Instant>) -> Option<thread::Result<()>> {
+        if let Some(deadline) = deadline {
+            let mut done = self.finished.done.lock().unwrap();
+
+            while !*done {
+                let now = Instant::now();
+
+                if now >= deadline {
+                    return None;
+                }
+
+                done = self
+                    .finished
+                    .condvar
+                    .wait_timeout(done, deadline - now)
+                    .unwrap()
+                    .0;
+            }
+        }
+
+        Some(self.handle.join())
+    }
+}
+
+struct Finished {
+    done: Mutex<bool>,
+    condvar: Condvar,
+}
+
+/// Marks the thread as finished when dropped, which also happens if it
+/// panics.
+struct FinishGuard(Arc<Finished>);
+
+impl Drop for FinishGuard {
+    fn drop(&mut self) {
+        *self.0.done.lock().unwrap() = true;
+        self.0.condvar.notify_all();
+    }
+}
+
+/// Spawn a thread with the given builder which can be joined with a
+/// deadline.
+pub(crate) fn spawn<F>(builder: thread::Builder, f: F) -> io::Result<JoinHandle>
+where
+    F: FnOnce() + Send + 'static,
+{
+    let finished = Arc::new(Finished {
+        done: Mutex::new(false),
+        condvar: Condvar::new(),
+    });
+
+    let handle = builder.spawn({
+        let finished = finished.clone();
+
+        move || {
+            let _guard = FinishGuard(finished);
+            f()
+        }
+    })?;
+
+    Ok(JoinHandle { handle, finished })
+}
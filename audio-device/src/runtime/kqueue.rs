@@ -0,0 +1,312 @@
+//! A backend for the [PollDriver][super::PollDriver] built on kqueue, which is
+//! used on macOS and the BSDs.
+//!
+//! Descriptors are registered once with `EV_DISPATCH`, which causes the kernel
+//! to disable them after they've delivered an event. Releasing the returned
+//! events of a handle enables it again, which matches how the `poll(2)` loop
+//! stops polling a descriptor until its events have been processed.
+//!
+//! Filters are identified by their descriptor, so additional handles for the
+//! same descriptor register a duplicate of it.
+
+use crate::loom::sync::Arc;
+use crate::runtime::dispatch::Dispatch;
+use crate::runtime::poll::{Events, Shared, Token, Waker};
+use crate::unix::Errno;
+use crate::Result;
+use std::collections::HashMap;
+use std::io;
+use std::mem;
+use std::ptr;
+use std::sync::atomic::Ordering;
+use std::task;
+
+/// The maximum number of events received per wakeup.
+const EVENTS: usize = 64;
+
+struct Registration {
+    waker: Arc<Waker>,
+    /// The descriptor registered with kqueue, which is owned by the
+    /// registration if it's a duplicate.
+    fd: libc::c_int,
+    dup: bool,
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        if self.dup {
+            unsafe {
+                let _ = libc::close(self.fd);
+            }
+        }
+    }
+}
+
+pub(super) struct Driver {
+    kq: libc::c_int,
+    /// The token of the handle a registered descriptor belongs to.
+    tokens: HashMap<libc::c_int, Token>,
+    /// Changes to apply the next time the driver waits for events.
+    changes: Vec<libc::kevent>,
+    events: Vec<libc::kevent>,
+    /// Buffer of tasks to wake up.
+    woken: Vec<task::Waker>,
+}
+
+impl Driver {
+    /// Construct a new kqueue.
+    pub(super) fn new() -> Result<Self, Errno> {
+        unsafe {
+            let kq = libc::kqueue();
+
+            if kq < 0 {
+                return Err(last_errno());
+            }
+
+            let this = Self {
+                kq,
+                tokens: HashMap::new(),
+                changes: Vec::new(),
+                events: Vec::with_capacity(EVENTS),
+                woken: Vec::new(),
+            };
+
+            if libc::fcntl(kq, libc::F_SETFD, libc::FD_CLOEXEC) < 0 {
+                return Err(last_errno());
+            }
+
+            Ok(this)
+        }
+    }
+
+    fn run(mut self, guard: &mut PanicGuard) -> Result<()> {
+        let parker = guard.shared.parker.fd;
+        self.change(parker, libc::POLLIN, libc::EV_ADD as u32);
+
+        while guard.shared.running.load(Ordering::Acquire) {
+            let n = unsafe {
+                libc::kevent(
+                    self.kq,
+                    self.changes.as_ptr(),
+                    self.changes.len() as _,
+                    self.events.as_mut_ptr(),
+                    self.events.capacity() as _,
+                    ptr::null(),
+                )
+            };
+
+            self.changes.clear();
+
+            if n < 0 {
+                let errno = last_errno();
+
+                if errno.value() == libc::EINTR {
+                    continue;
+                }
+
+                return Err(errno.into());
+            }
+
+            // Safety: the kernel initialized this many events.
+            unsafe {
+                self.events.set_len(n as usize);
+            }
+
+            let mut dispatch = Dispatch::new(&guard.shared.metrics, &mut self.woken);
+            let mut notified = false;
+
+            for e in self.events.drain(..) {
+                let fd = e.ident as libc::c_int;
+
+                if fd == parker {
+                    notified = true;
+                    continue;
+                }
+
+                // NB: events for removed handles are ignored.
+                let waker = match self
+                    .tokens
+                    .get(&fd)
+                    .and_then(|token| guard.registrations.get(token))
+                {
+                    Some(r) => &r.waker,
+                    None => continue,
+                };
+
+                let mut events = 0;
+
+                if e.flags & libc::EV_ERROR != 0 {
+                    events |= libc::POLLERR;
+                } else if e.filter == libc::EVFILT_READ {
+                    events |= libc::POLLIN;
+                } else if e.filter == libc::EVFILT_WRITE {
+                    events |= libc::POLLOUT;
+                }
+
+                if e.flags & libc::EV_EOF != 0 {
+                    events |= libc::POLLHUP;
+                }
+
+                // NB: a descriptor registered for both reading and writing
+                // might deliver both filters before it's released.
+                waker
+                    .returned_events
+                    .fetch_or(events as u16 as usize, Ordering::Release);
+                dispatch.wake(&waker.waker);
+            }
+
+            drop(dispatch);
+
+            if notified {
+                let _ = guard.shared.parker.recv()?;
+                let mut holders = guard.shared.holders.lock();
+                guard.shared.metrics.queue(holders.len());
+                self.process(&mut holders, &mut guard.registrations);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Process all queued registrations.
+    fn process(&mut self, events: &mut Events, registrations: &mut HashMap<Token, Registration>) {
+        // NB: removals are processed first, since the descriptor of a removed
+        // handle might've been closed and its number reused by an added one.
+        for token in events.removed.drain(..) {
+            if let Some(r) = registrations.remove(&token) {
+                self.tokens.remove(&r.fd);
+
+                // NB: a duplicate is unregistered once it's closed when the
+                // registration is dropped.
+                if !r.dup {
+                    self.delete(r.fd, r.waker.descriptor.events);
+                }
+            }
+        }
+
+        for waker in events.added.drain(..) {
+            let d = waker.descriptor;
+            let token = waker.token();
+
+            let (fd, dup) = if self.tokens.contains_key(&d.fd) {
+                let fd = unsafe { libc::fcntl(d.fd, libc::F_DUPFD_CLOEXEC, 0) };
+
+                if fd < 0 {
+                    let errno = last_errno();
+                    tracing::error!(%errno, fd = d.fd, "failed to duplicate descriptor");
+                    waker
+                        .returned_events
+                        .store(libc::POLLERR as u16 as usize, Ordering::Release);
+                    waker.waker.wake();
+                    continue;
+                }
+
+                (fd, true)
+            } else {
+                (d.fd, false)
+            };
+
+            self.change(fd, d.events, (libc::EV_ADD | libc::EV_DISPATCH) as u32);
+            self.tokens.insert(fd, token);
+            registrations.insert(token, Registration { waker, fd, dup });
+        }
+
+        for token in events.released.drain(..) {
+            if let Some(r) = registrations.get(&token) {
+                self.change(r.fd, r.waker.descriptor.events, libc::EV_ENABLE as u32);
+            }
+        }
+    }
+
+    /// Delete the filters of a descriptor immediately, ignoring errors since
+    /// the descriptor might already have been closed.
+    ///
+    /// If these were queued with other changes, the errors would be reported
+    /// as events for the descriptor, which might since have been reused.
+    fn delete(&self, fd: libc::c_int, events: libc::c_short) {
+        for change in kevents(fd, events, libc::EV_DELETE as u32) {
+            unsafe {
+                let _ = libc::kevent(self.kq, &change, 1, ptr::null_mut(), 0, ptr::null());
+            }
+        }
+    }
+
+    /// Queue a change to the filters corresponding to the given poll events.
+    fn change(&mut self, fd: libc::c_int, events: libc::c_short, flags: u32) {
+        self.changes.extend(kevents(fd, events, flags));
+    }
+
+    pub(super) fn start(self, shared: Arc<Shared>) {
+        let mut guard = PanicGuard {
+            shared,
+            registrations: HashMap::new(),
+        };
+
+        if let Err(e) = self.run(&mut guard) {
+            panic!("kqueue thread errored: {}", e)
+        }
+    }
+}
+
+impl Drop for Driver {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = libc::close(self.kq);
+        }
+    }
+}
+
+/// The changes to the filters corresponding to the given poll events.
+fn kevents(
+    fd: libc::c_int,
+    events: libc::c_short,
+    flags: u32,
+) -> impl Iterator<Item = libc::kevent> {
+    let filters = [
+        (libc::POLLIN, libc::EVFILT_READ),
+        (libc::POLLOUT, libc::EVFILT_WRITE),
+    ];
+
+    IntoIterator::into_iter(filters)
+        .filter(move |&(mask, _)| events & mask != 0)
+        .map(move |(_, filter)| {
+            // NB: the layout of kevent differs across systems, so only the
+            // fields which are used are assigned.
+            let mut e: libc::kevent = unsafe { mem::zeroed() };
+            e.ident = fd as _;
+            e.filter = filter;
+            e.flags = flags as _;
+            e
+        })
+}
+
+// Safety: the changes and events are plain data, which only refer to
+// descriptors through their numbers.
+unsafe impl Send for Driver {}
+
+/// Wrap a guard around the resources allocated by the driver, which marks it
+/// as stopped when dropped, whether it was shut down or panicked.
+struct PanicGuard {
+    shared: Arc<Shared>,
+    registrations: HashMap<Token, Registration>,
+}
+
+impl Drop for PanicGuard {
+    fn drop(&mut self) {
+        self.shared.running.store(false, Ordering::Release);
+
+        // Wake up every waker so that they can observe that the driver
+        // stopped.
+        for r in self.registrations.values() {
+            r.waker.waker.wake();
+        }
+    }
+}
+
+fn last_errno() -> Errno {
+    Errno::new(
+        io::Error::last_os_error()
+            .raw_os_error()
+            .unwrap_or_default(),
+    )
+}
@@ -0,0 +1,196 @@
+//! Counters maintained by the threads driving the runtime.
+//!
+//! These are updated with relaxed atomics by the driver threads, so reading
+//! them never blocks a driver.
+
+#![cfg_attr(
+    not(any(feature = "events-driver", feature = "poll-driver")),
+    allow(dead_code)
+)]
+
+use crate::loom::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::convert::TryFrom;
+use std::time::{Duration, Instant};
+
+/// Counters of a single driver.
+pub(crate) struct Metrics {
+    started: Instant,
+    wakeups: AtomicU64,
+    dispatched: AtomicU64,
+    /// Accumulated dispatch latency, in nanoseconds.
+    latency: AtomicU64,
+    /// Maximum dispatch latency, in nanoseconds.
+    max_latency: AtomicU64,
+    queue_depth: AtomicUsize,
+    max_queue_depth: AtomicUsize,
+}
+
+impl Metrics {
+    pub(crate) fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            wakeups: AtomicU64::new(0),
+            dispatched: AtomicU64::new(0),
+            latency: AtomicU64::new(0),
+            max_latency: AtomicU64::new(0),
+            queue_depth: AtomicUsize::new(0),
+            max_queue_depth: AtomicUsize::new(0),
+        }
+    }
+
+    /// Record that the driver woke up to handle events.
+    pub(crate) fn wakeup(&self) {
+        self.wakeups.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record that the driver woke up the given number of tasks, with the
+    /// given accumulated and maximum dispatch latency.
+    pub(crate) fn dispatched(&self, woken: u64, latency: Duration, max_latency: Duration) {
+        self.dispatched.fetch_add(woken, Ordering::Relaxed);
+        self.latency.fetch_add(nanos(latency), Ordering::Relaxed);
+        self.max_latency
+            .fetch_max(nanos(max_latency), Ordering::Relaxed);
+    }
+
+    /// Record the number of registration changes the driver is about to
+    /// process.
+    pub(crate) fn queue(&self, depth: usize) {
+        self.queue_depth.store(depth, Ordering::Relaxed);
+        self.max_queue_depth.fetch_max(depth, Ordering::Relaxed);
+    }
+
+    /// Take a snapshot of the counters.
+    pub(crate) fn snapshot(&self) -> RuntimeMetrics {
+        RuntimeMetrics {
+            elapsed: self.started.elapsed(),
+            wakeups: self.wakeups.load(Ordering::Relaxed),
+            dispatched: self.dispatched.load(Ordering::Relaxed),
+            latency: Duration::from_nanos(self.latency.load(Ordering::Relaxed)),
+            max_latency: Duration::from_nanos(self.max_latency.load(Ordering::Relaxed)),
+            queue_depth: self.queue_depth.load(Ordering::Relaxed),
+            max_queue_depth: self.max_queue_depth.load(Ordering::Relaxed),
+        }
+    }
+}
+
+fn nanos(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
+
+/// A snapshot of the counters of a [Runtime][super::Runtime].
+///
+/// The dispatch latency is measured from the moment a driver observes that a
+/// handle is ready, to when the driver has woken up the tasks waiting for it.
+/// The queue depth is the number of handles registered, released, or dropped
+/// which were waiting to be processed by a driver the last time it woke up.
+///
+/// See [Runtime::metrics][super::Runtime::metrics].
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let runtime = audio_device::runtime::Runtime::new()?;
+///
+/// let before = runtime.metrics();
+/// std::thread::sleep(Duration::from_secs(1));
+/// let metrics = runtime.metrics().since(&before);
+///
+/// println!("wakeups: {:.1}/s", metrics.wakeups_per_second());
+/// println!("mean latency: {:?}", metrics.mean_dispatch_latency());
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RuntimeMetrics {
+    elapsed: Duration,
+    wakeups: u64,
+    dispatched: u64,
+    latency: Duration,
+    max_latency: Duration,
+    queue_depth: usize,
+    max_queue_depth: usize,
+}
+
+impl RuntimeMetrics {
+    /// The time the counters have been collected for.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// The number of times the drivers have woken up to handle events.
+    pub fn wakeups(&self) -> u64 {
+        self.wakeups
+    }
+
+    /// The average number of wakeups per second over [elapsed][Self::elapsed].
+    pub fn wakeups_per_second(&self) -> f64 {
+        let secs = self.elapsed.as_secs_f64();
+
+        if secs == 0.0 {
+            return 0.0;
+        }
+
+        self.wakeups as f64 / secs
+    }
+
+    /// The number of times a task has been woken up by the drivers.
+    pub fn dispatched(&self) -> u64 {
+        self.dispatched
+    }
+
+    /// The mean dispatch latency of each woken up task.
+    pub fn mean_dispatch_latency(&self) -> Duration {
+        match self.dispatched {
+            0 => Duration::default(),
+            n => Duration::from_nanos(nanos(self.latency) / n),
+        }
+    }
+
+    /// The maximum dispatch latency observed.
+    ///
+    /// This isn't affected by [since][Self::since], so it's the maximum since
+    /// the runtime was constructed.
+    pub fn max_dispatch_latency(&self) -> Duration {
+        self.max_latency
+    }
+
+    /// The queue depth the last time the drivers woke up to process it.
+    pub fn queue_depth(&self) -> usize {
+        self.queue_depth
+    }
+
+    /// The maximum queue depth observed.
+    ///
+    /// This isn't affected by [since][Self::since], so it's the maximum since
+    /// the runtime was constructed.
+    pub fn max_queue_depth(&self) -> usize {
+        self.max_queue_depth
+    }
+
+    /// The counters accumulated since the given earlier snapshot was taken, so
+    /// that rates can be calculated over a recent interval.
+    pub fn since(&self, earlier: &RuntimeMetrics) -> RuntimeMetrics {
+        RuntimeMetrics {
+            elapsed: self.elapsed.saturating_sub(earlier.elapsed),
+            wakeups: self.wakeups.saturating_sub(earlier.wakeups),
+            dispatched: self.dispatched.saturating_sub(earlier.dispatched),
+            latency: self.latency.saturating_sub(earlier.latency),
+            ..*self
+        }
+    }
+
+    /// Combine the counters of multiple drivers.
+    pub(crate) fn merge(self, other: RuntimeMetrics) -> RuntimeMetrics {
+        RuntimeMetrics {
+            elapsed: self.elapsed.max(other.elapsed),
+            wakeups: self.wakeups + other.wakeups,
+            dispatched: self.dispatched + other.dispatched,
+            latency: self.latency + other.latency,
+            max_latency: self.max_latency.max(other.max_latency),
+            queue_depth: self.queue_depth + other.queue_depth,
+            max_queue_depth: self.max_queue_depth.max(other.max_queue_depth),
+        }
+    }
+}
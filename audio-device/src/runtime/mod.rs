@@ -1,14 +1,42 @@
 //! If any available, this provides handles for various forms of asynchronous
 //! drivers that can be used in combination with audio interfaces.
 
-mod atomic_waker;
+pub(crate) mod atomic_waker;
+#[cfg(any(feature = "events-driver", feature = "poll-driver"))]
+pub(crate) mod dispatch;
+mod error;
+pub use self::error::Error;
+mod builder;
+mod executor;
+mod join;
+pub use self::builder::{Builder, ThreadPriority};
+mod metrics;
+pub use self::metrics::RuntimeMetrics;
+#[cfg(any(
+    feature = "events-driver",
+    all(
+        feature = "poll-driver",
+        any(target_os = "linux", target_os = "android")
+    )
+))]
+mod timer;
+#[cfg(any(
+    feature = "events-driver",
+    all(
+        feature = "poll-driver",
+        any(target_os = "linux", target_os = "android")
+    )
+))]
+pub use self::timer::{timeout, Timer};
+use crate::loom::sync::Arc;
 use crate::Result;
 use std::cell::Cell;
 use std::future::Future;
 use std::ptr;
+use std::time::{Duration, Instant};
 
 thread_local! {
-    static RUNTIME: Cell<*const Runtime> = Cell::new(ptr::null());
+    static RUNTIME: Cell<*const Drivers> = Cell::new(ptr::null());
 }
 
 cfg_events_driver! {
@@ -28,6 +56,23 @@ cfg_events_driver! {
 
 cfg_poll_driver! {
     pub(crate) mod poll;
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub(crate) mod slab;
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub(crate) mod epoll;
+    #[cfg(all(feature = "io-uring-driver", target_os = "linux"))]
+    pub(crate) mod uring;
+    #[cfg(feature = "tokio-driver")]
+    pub(crate) mod tokio_poll;
+    #[cfg(any(
+        target_os = "macos",
+        target_os = "ios",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "dragonfly"
+    ))]
+    pub(crate) mod kqueue;
     #[doc(hidden)]
     pub use self::poll::{PollDriver, AsyncPoll};
 
@@ -48,6 +93,17 @@ cfg_poll_driver! {
 /// To run an asynchronous task inside of the audio runtime, we use the
 /// [wrap][Runtime::wrap] function.
 ///
+/// Descriptors are polled through epoll on Linux and kqueue on macOS and the
+/// BSDs, so that the cost of a wakeup doesn't grow with the number of
+/// registered streams. With the `io-uring-driver` feature enabled, io_uring is
+/// used on Linux instead, which further reduces the number of syscalls. If none
+/// are available the runtime silently falls back to `poll(2)`.
+///
+/// With the `tokio-driver` feature enabled, descriptors registered in the
+/// context of a tokio runtime are driven by its reactor instead, so no audio
+/// runtime is needed for them. This doesn't affect Windows event objects,
+/// which tokio has no support for waiting on.
+///
 /// # Examples
 ///
 /// ```no_run
@@ -58,32 +114,81 @@ cfg_poll_driver! {
 /// # Ok(()) }
 /// ```
 pub struct Runtime {
-    #[cfg(feature = "events-driver")]
-    events: self::events::EventsDriver,
-    #[cfg(feature = "poll-driver")]
-    poll: self::poll::PollDriver,
+    // NB: declared first so that the executor is shut down before the
+    // drivers it uses.
+    executor: self::executor::Executor,
+    drivers: Arc<Drivers>,
 }
 
 impl Runtime {
     /// Construct a new audio runtime.
     pub fn new() -> Result<Self> {
-        Ok(Self {
-            #[cfg(feature = "events-driver")]
-            events: self::events::EventsDriver::new()?,
-            #[cfg(feature = "poll-driver")]
-            poll: self::poll::PollDriver::new()?,
-        })
+        Builder::new().build()
+    }
+
+    /// Construct a [Builder] to configure the threads driving the runtime,
+    /// like their priority.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::runtime::Runtime;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let runtime = Runtime::builder().thread_name("audio-driver").build()?;
+    /// # Ok(()) }
+    /// ```
+    pub fn builder() -> Builder {
+        Builder::new()
+    }
+
+    /// Take a snapshot of the counters of the threads driving the runtime,
+    /// which can be used to diagnose latency problems.
+    ///
+    /// See [RuntimeMetrics].
+    pub fn metrics(&self) -> RuntimeMetrics {
+        let metrics = RuntimeMetrics::default();
+        #[cfg(feature = "events-driver")]
+        let metrics = metrics.merge(self.drivers.events.metrics());
+        #[cfg(feature = "poll-driver")]
+        let metrics = metrics.merge(self.drivers.poll.metrics());
+        metrics
     }
 
     /// Construct a runtime guard that when in scope will provide thread-local
     /// access to runtime drivers.
     pub fn enter(&self) -> RuntimeGuard<'_> {
-        let old = RUNTIME.with(|rt| rt.replace(self as *const _));
+        self.drivers.enter()
+    }
 
-        RuntimeGuard {
-            _runtime: self,
-            old,
-        }
+    /// Spawn a task onto a thread owned by the runtime, which polls it with
+    /// the runtime entered.
+    ///
+    /// This is intended for small supervisory tasks, like ones watching for
+    /// devices being plugged in, so that an executor isn't needed just to call
+    /// [wrap][Runtime::wrap]. Since all tasks are polled on the same thread,
+    /// they shouldn't block.
+    ///
+    /// The thread is started the first time a task is spawned. Tasks which
+    /// haven't completed when the runtime is shut down are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async fn watch() {}
+    /// # fn main() -> anyhow::Result<()> {
+    /// let runtime = audio_device::runtime::Runtime::new()?;
+    ///
+    /// runtime.spawn(async {
+    ///     watch().await;
+    /// });
+    /// # Ok(()) }
+    /// ```
+    pub fn spawn<F>(&self, future: F)
+    where
+        F: 'static + Send + Future<Output = ()>,
+    {
+        self.executor.spawn(&self.drivers, Box::pin(future));
     }
 
     /// Run the given asynchronous task inside of the runtime.
@@ -122,13 +227,90 @@ impl Runtime {
         }
     }
 
+    /// Shut down the runtime, giving up on its threads if they haven't
+    /// finished within the given timeout.
+    ///
+    /// New handles can't be registered once this is called, and tasks waiting
+    /// for handles are woken up with [Error::Shutdown]. Unlike
+    /// [join][Runtime::join], this doesn't hang if a thread is stuck, like when
+    /// a spawned task is blocked on a wedged device. Such threads are left
+    /// running detached.
+    ///
+    /// Returns `true` if all threads finished in time.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let runtime = audio_device::runtime::Runtime::new()?;
+    ///
+    /// if !runtime.shutdown(Duration::from_secs(1)) {
+    ///     println!("runtime didn't shut down in time");
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn shutdown(self, timeout: Duration) -> bool {
+        let deadline = Some(Instant::now() + timeout);
+
+        // NB: the drivers are stopped first, so that spawned tasks waiting for
+        // them are woken up with an error and get a chance to complete.
+        let drivers = self.drivers.shutdown(deadline);
+        let executor = self.executor.shutdown(deadline);
+        drivers && executor
+    }
+
     /// Shutdown and join the runtime.
     pub fn join(self) {
+        let Runtime { executor, drivers } = self;
+
+        // NB: the executor holds on to the drivers until it's shut down.
+        drop(executor);
+
+        if let Ok(drivers) = Arc::try_unwrap(drivers) {
+            drivers.join();
+        }
+    }
+}
+
+/// The drivers of a [Runtime], which are shared with its executor.
+pub(crate) struct Drivers {
+    #[cfg(feature = "events-driver")]
+    events: self::events::EventsDriver,
+    #[cfg(feature = "poll-driver")]
+    poll: self::poll::PollDriver,
+}
+
+impl Drivers {
+    fn enter(&self) -> RuntimeGuard<'_> {
+        let old = RUNTIME.with(|rt| rt.replace(self as *const _));
+
+        RuntimeGuard {
+            _drivers: self,
+            old,
+        }
+    }
+
+    fn join(self) {
         #[cfg(feature = "events-driver")]
         let _ = self.events.join();
         #[cfg(feature = "poll-driver")]
         let _ = self.poll.join();
     }
+
+    #[cfg_attr(
+        not(any(feature = "events-driver", feature = "poll-driver")),
+        allow(unused_variables)
+    )]
+    fn shutdown(&self, deadline: Option<Instant>) -> bool {
+        let finished = true;
+        #[cfg(feature = "events-driver")]
+        let finished = self.events.shutdown(deadline) && finished;
+        #[cfg(feature = "poll-driver")]
+        let finished = self.poll.shutdown(deadline) && finished;
+        finished
+    }
 }
 
 /// The runtime guard constructed with [Runtime::enter].
@@ -136,8 +318,8 @@ impl Runtime {
 /// Runtime plumbing is available as long as this guard is in scope.
 pub struct RuntimeGuard<'a> {
     // NB: prevent the guard from outliving the runtime it was constructed from.
-    _runtime: &'a Runtime,
-    old: *const Runtime,
+    _drivers: &'a Drivers,
+    old: *const Drivers,
 }
 
 impl Drop for RuntimeGuard<'_> {
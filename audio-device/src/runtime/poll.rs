@@ -1,12 +1,17 @@
 use crate::libc as c;
 use crate::loom::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use crate::loom::sync::{Arc, Mutex};
-use crate::loom::thread;
 use crate::runtime::atomic_waker::AtomicWaker;
+use crate::runtime::dispatch::Dispatch;
+use crate::runtime::join::JoinHandle;
+use crate::runtime::metrics::{Metrics, RuntimeMetrics};
+use crate::runtime::{Builder, Error};
 use crate::unix::Errno;
 use crate::Result;
 use std::collections::HashMap;
 use std::mem;
+use std::task::{Context, Poll};
+use std::time::Instant;
 
 macro_rules! errno {
     ($expr:expr) => {{
@@ -21,9 +26,12 @@ macro_rules! errno {
 }
 
 /// The token associated with the current waiter.
+///
+/// Tokens are unique to each handle, since descriptors might be shared across
+/// handles or be reused once they've been closed.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(transparent)]
-pub struct Token(c::c_int);
+pub struct Token(pub(super) usize);
 
 /// A guard for the returned events of the poll handler.
 ///
@@ -33,8 +41,9 @@ pub struct Token(c::c_int);
 /// Constructed by waiting on [AsyncPoll::returned_events].
 pub struct PollEventsGuard<'a> {
     events: c::c_short,
-    shared: &'a Shared,
-    token: Token,
+    /// The driver to release the handle to, which is `None` if the handle
+    /// isn't driven by it.
+    release: Option<(&'a Shared, Token)>,
 }
 
 impl PollEventsGuard<'_> {
@@ -46,9 +55,16 @@ impl PollEventsGuard<'_> {
 
 impl Drop for PollEventsGuard<'_> {
     fn drop(&mut self) {
-        self.shared.holders.lock().released.push(self.token);
+        let (shared, token) = match self.release {
+            Some(release) => release,
+            None => return,
+        };
+
+        if !shared.holders.lock().push_released(token) {
+            return;
+        }
 
-        if let Err(e) = self.shared.parker.send(1) {
+        if let Err(e) = shared.parker.send(1) {
             tracing::error!("failed to unpark background thread: {}", e);
         }
     }
@@ -58,8 +74,16 @@ impl Drop for PollEventsGuard<'_> {
 ///
 /// See [AsyncPoll::new].
 pub struct AsyncPoll {
-    shared: Arc<Shared>,
-    waker: Arc<Waker>,
+    kind: Kind,
+}
+
+enum Kind {
+    Driver {
+        shared: Arc<Shared>,
+        waker: Arc<Waker>,
+    },
+    #[cfg(feature = "tokio-driver")]
+    Tokio(super::tokio_poll::TokioPoll),
 }
 
 impl AsyncPoll {
@@ -68,6 +92,19 @@ impl AsyncPoll {
     ///
     /// Dropping the returned handle will unregister interest.
     ///
+    /// Handles can be registered and dropped at any time while the runtime is
+    /// running, and multiple handles can be registered for the same
+    /// descriptor.
+    ///
+    /// With the `tokio-driver` feature enabled, handles constructed in the
+    /// context of a tokio runtime are driven by its reactor instead, in which
+    /// case an audio runtime isn't needed. The tokio runtime must have IO
+    /// enabled.
+    ///
+    /// # Errors
+    ///
+    /// Errors with [Error::Shutdown] if the runtime has been shut down.
+    ///
     /// # Panics
     ///
     /// Panics unless an audio runtime is available.
@@ -78,20 +115,41 @@ impl AsyncPoll {
     ///
     /// This is unsafe, because the caller must ensure that the provided
     /// `pollfd` is not closed before this handle is dropped.
-    pub unsafe fn new(descriptor: c::pollfd) -> Result<AsyncPoll, Errno> {
+    pub unsafe fn new(descriptor: c::pollfd) -> Result<AsyncPoll, Error> {
+        #[cfg(feature = "tokio-driver")]
+        if super::tokio_poll::TokioPoll::is_available() {
+            return Ok(AsyncPoll {
+                kind: Kind::Tokio(super::tokio_poll::TokioPoll::new(descriptor)?),
+            });
+        }
+
         crate::runtime::with_poll(|poll| {
+            let token = Token(poll.shared.next_token.fetch_add(1, Ordering::Relaxed));
+
             let waker = Arc::new(Waker {
                 waker: AtomicWaker::new(),
+                token,
                 descriptor,
                 returned_events: AtomicUsize::new(0),
             });
 
-            poll.shared.holders.lock().added.push(waker.clone());
-            poll.shared.parker.send(1)?;
+            let mut holders = poll.shared.holders.lock();
+
+            if !poll.shared.running.load(Ordering::Acquire) {
+                return Err(Error::Shutdown);
+            }
+
+            if holders.push_added(waker.clone()) {
+                poll.shared.parker.send(1)?;
+            }
+
+            drop(holders);
 
             Ok(AsyncPoll {
-                shared: poll.shared.clone(),
-                waker,
+                kind: Kind::Driver {
+                    shared: poll.shared.clone(),
+                    waker,
+                },
             })
         })
     }
@@ -101,41 +159,94 @@ impl AsyncPoll {
     ///
     /// Once this guard is dropped the driver will be released to register more
     /// interest.
-    pub async fn returned_events(&self) -> PollEventsGuard<'_> {
+    ///
+    /// # Errors
+    ///
+    /// Errors with [Error::Shutdown] once the runtime has been shut down.
+    pub async fn returned_events(&self) -> Result<PollEventsGuard<'_>, Error> {
         use std::future::Future;
         use std::pin::Pin;
-        use std::task::{Context, Poll};
 
         return ReturnedEvents(self).await;
 
         struct ReturnedEvents<'a>(&'a AsyncPoll);
 
         impl<'a> Future for ReturnedEvents<'a> {
-            type Output = PollEventsGuard<'a>;
+            type Output = Result<PollEventsGuard<'a>, Error>;
 
             fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-                self.0.waker.waker.register_by_ref(cx.waker());
-                let returned_events = self.0.waker.returned_events.swap(0, Ordering::Acquire);
-
-                if returned_events != 0 {
-                    Poll::Ready(PollEventsGuard {
-                        events: returned_events as c::c_short,
-                        shared: &*self.0.shared,
-                        token: self.0.waker.token(),
+                self.0.poll_returned_events(cx)
+            }
+        }
+    }
+
+    /// Poll for events to be triggered on the background driver, returning a
+    /// guard to the events once they are available.
+    ///
+    /// If no events are available, the current task is registered to be woken
+    /// up once they are.
+    ///
+    /// See [AsyncPoll::returned_events].
+    pub fn poll_returned_events(
+        &self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<PollEventsGuard<'_>, Error>> {
+        let (shared, waker) = match &self.kind {
+            Kind::Driver { shared, waker } => (shared, waker),
+            #[cfg(feature = "tokio-driver")]
+            Kind::Tokio(poll) => {
+                return poll.poll_returned_events(cx).map(|events| {
+                    Ok(PollEventsGuard {
+                        events,
+                        release: None,
                     })
-                } else {
-                    Poll::Pending
-                }
+                });
             }
+        };
+
+        waker.waker.register_by_ref(cx.waker());
+        let returned_events = waker.returned_events.swap(0, Ordering::Acquire);
+
+        if returned_events != 0 {
+            Poll::Ready(Ok(PollEventsGuard {
+                events: returned_events as c::c_short,
+                release: Some((&**shared, waker.token())),
+            }))
+        } else if !shared.running.load(Ordering::Acquire) {
+            // NB: every handle is woken up once the driver stops, whether it
+            // was shut down or panicked.
+            Poll::Ready(Err(Error::Shutdown))
+        } else {
+            Poll::Pending
         }
     }
 }
 
 impl Drop for AsyncPoll {
     fn drop(&mut self) {
-        self.shared.holders.lock().removed.push(self.waker.token());
+        let (shared, waker) = match &self.kind {
+            Kind::Driver { shared, waker } => (shared, waker),
+            #[cfg(feature = "tokio-driver")]
+            Kind::Tokio(..) => return,
+        };
 
-        if let Err(e) = self.shared.parker.send(1) {
+        let mut holders = shared.holders.lock();
+
+        // NB: the driver hasn't seen the handle yet, so it can be forgotten
+        // without involving it.
+        if let Some(index) = holders.added.iter().position(|w| Arc::ptr_eq(w, waker)) {
+            holders.added.swap_remove(index);
+            return;
+        }
+
+        let notify = holders.push_removed(waker.token());
+        drop(holders);
+
+        if !notify {
+            return;
+        }
+
+        if let Err(e) = shared.parker.send(1) {
             tracing::error!("failed to unpark background thread: {}", e);
         }
     }
@@ -145,35 +256,71 @@ impl Drop for AsyncPoll {
 pub(crate) struct Waker {
     /// The waker to call when waking up the task waiting for events.
     pub(crate) waker: AtomicWaker,
+    /// The unique token of the handle.
+    token: Token,
     /// The descriptors associated with this waker.
-    descriptor: c::pollfd,
+    pub(super) descriptor: c::pollfd,
     /// The last revents decoded. `None` if no events are ready.
-    returned_events: AtomicUsize,
+    pub(super) returned_events: AtomicUsize,
 }
 
 impl Waker {
     /// Get the token associated with this waker.
-    ///
-    /// Note: always the first file descriptor.
-    fn token(&self) -> Token {
-        Token(self.descriptor.fd)
+    pub(super) fn token(&self) -> Token {
+        self.token
     }
 }
 
 pub(crate) struct Shared {
     pub(crate) running: AtomicBool,
     pub(crate) holders: Mutex<Events>,
-    pub(crate) parker: EventFd,
+    pub(crate) parker: Parker,
+    pub(crate) metrics: Metrics,
+    /// The token of the next handle to be registered.
+    pub(crate) next_token: AtomicUsize,
 }
 
 #[derive(Default)]
 pub(crate) struct Events {
-    added: Vec<Arc<Waker>>,
-    released: Vec<Token>,
-    removed: Vec<Token>,
+    pub(super) added: Vec<Arc<Waker>>,
+    pub(super) released: Vec<Token>,
+    pub(super) removed: Vec<Token>,
 }
 
 impl Events {
+    /// The number of queued elements.
+    pub(super) fn len(&self) -> usize {
+        self.added.len() + self.released.len() + self.removed.len()
+    }
+
+    // NB: the driver only needs to be notified when the queue stops being
+    // empty, since it processes everything which is queued at once. This
+    // coalesces the notifications of many handles into a single wakeup.
+
+    /// Queue an added handle, returning `true` if the driver needs to be
+    /// notified.
+    fn push_added(&mut self, waker: Arc<Waker>) -> bool {
+        let notify = self.len() == 0;
+        self.added.push(waker);
+        notify
+    }
+
+    /// Queue a released handle, returning `true` if the driver needs to be
+    /// notified.
+    fn push_released(&mut self, token: Token) -> bool {
+        let notify = self.len() == 0;
+        self.released.push(token);
+        notify
+    }
+
+    /// Queue a removed handle, returning `true` if the driver needs to be
+    /// notified.
+    fn push_removed(&mut self, token: Token) -> bool {
+        let notify = self.len() == 0;
+        self.removed.push(token);
+        notify
+    }
+
     // Process all queued elements in the driver.
     fn process(&mut self, driver: &mut Driver, wakers: &mut Vec<Arc<Waker>>) -> Result<()> {
         let mut added = mem::replace(&mut self.added, Vec::new());
@@ -222,7 +369,7 @@ impl Events {
 
 /// An executor to drive things which are woken up by polling.
 pub struct PollDriver {
-    thread: Option<thread::JoinHandle<()>>,
+    thread: Mutex<Option<JoinHandle>>,
     shared: Arc<Shared>,
 }
 
@@ -230,45 +377,91 @@ impl PollDriver {
     /// Construct a new events windows event object driver and return its
     /// handle.
     pub fn new() -> Result<Self> {
+        Self::with_builder(&Builder::new())
+    }
+
+    /// Construct a new driver whose thread is configured by the given
+    /// builder.
+    pub(crate) fn with_builder(builder: &Builder) -> Result<Self> {
         let shared = Arc::new(Shared {
             running: AtomicBool::new(true),
             holders: Mutex::new(Events::default()),
-            parker: EventFd::new()?,
+            parker: Parker::new()?,
+            metrics: Metrics::new(),
+            next_token: AtomicUsize::new(0),
         });
 
-        let thread = thread::spawn({
+        // NB: the backend is set up here so that errors can be reported, and
+        // so that the `poll(2)` loop can be used as a fallback.
+        let backend = Backend::new();
+
+        let thread = builder.spawn({
             let shared = shared.clone();
-            || Driver::start(shared)
+            move || backend.start(shared)
         });
 
         let handle = Self {
-            thread: Some(thread),
+            thread: Mutex::new(Some(thread)),
             shared,
         };
 
         Ok(handle)
     }
 
+    /// Take a snapshot of the counters of the driver.
+    pub fn metrics(&self) -> RuntimeMetrics {
+        self.shared.metrics.snapshot()
+    }
+
     /// Join the current handle.
     ///
     /// # Panics
     ///
     /// This panics if the background thread panicked. But this should only ever
     /// happen if there's a bug.
-    pub fn join(mut self) {
-        self.inner_join();
+    pub fn join(self) {
+        self.shutdown(None);
     }
 
-    fn inner_join(&mut self) {
-        if let Some(thread) = self.thread.take() {
+    /// Shut down the driver, waiting for its thread to finish until the given
+    /// deadline. Returns `false` if it didn't, in which case the thread is
+    /// detached.
+    ///
+    /// New handles can't be registered once this is called, and every
+    /// registered handle is woken up with an error.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the background thread panicked.
+    pub(crate) fn shutdown(&self, deadline: Option<Instant>) -> bool {
+        let thread = match self.thread.lock().take() {
+            Some(thread) => thread,
+            None => return true,
+        };
+
+        let added = {
+            let mut holders = self.shared.holders.lock();
             self.shared.running.store(false, Ordering::Release);
+            mem::take(&mut holders.added)
+        };
 
-            if let Err(errno) = self.shared.parker.send(0) {
-                panic!("failed to set event: {}", errno);
-            }
+        // NB: the driver wakes up the handles it has registered as it stops,
+        // but never gets to the ones which haven't been processed yet.
+        for waker in added {
+            waker.waker.wake();
+        }
+
+        // NB: adding zero to an eventfd doesn't make it readable.
+        if let Err(errno) = self.shared.parker.send(1) {
+            panic!("failed to set event: {}", errno);
+        }
 
-            if thread.join().is_err() {
-                panic!("event handler thread panicked");
+        match thread.join(deadline) {
+            Some(Ok(())) => true,
+            Some(Err(..)) => panic!("event handler thread panicked"),
+            None => {
+                tracing::warn!("poll driver thread didn't shut down in time");
+                false
             }
         }
     }
@@ -276,7 +469,99 @@ impl PollDriver {
 
 impl Drop for PollDriver {
     fn drop(&mut self) {
-        let _ = self.inner_join();
+        self.shutdown(None);
+    }
+}
+
+/// The mechanism used by the background thread to wait for events.
+enum Backend {
+    #[cfg(all(feature = "io-uring-driver", target_os = "linux"))]
+    Uring(super::uring::Ring),
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    Epoll(super::epoll::Driver),
+    #[cfg(any(
+        target_os = "macos",
+        target_os = "ios",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "dragonfly"
+    ))]
+    Kqueue(super::kqueue::Driver),
+    Poll,
+}
+
+impl Backend {
+    /// Set up the most efficient backend available, falling back to
+    /// `poll(2)` if none are. The others might be unavailable on older
+    /// kernels, or be disabled through seccomp.
+    fn new() -> Self {
+        #[cfg(all(feature = "io-uring-driver", target_os = "linux"))]
+        match super::uring::Ring::new(super::uring::ENTRIES) {
+            Ok(ring) => return Backend::Uring(ring),
+            Err(error) => tracing::debug!(%error, "io_uring unavailable"),
+        }
+
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        match super::epoll::Driver::new() {
+            Ok(driver) => return Backend::Epoll(driver),
+            Err(error) => tracing::debug!(%error, "epoll unavailable"),
+        }
+
+        #[cfg(any(
+            target_os = "macos",
+            target_os = "ios",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd",
+            target_os = "dragonfly"
+        ))]
+        match super::kqueue::Driver::new() {
+            Ok(driver) => return Backend::Kqueue(driver),
+            Err(error) => tracing::debug!(%error, "kqueue unavailable"),
+        }
+
+        Backend::Poll
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            #[cfg(all(feature = "io-uring-driver", target_os = "linux"))]
+            Backend::Uring(..) => "io_uring",
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            Backend::Epoll(..) => "epoll",
+            #[cfg(any(
+                target_os = "macos",
+                target_os = "ios",
+                target_os = "freebsd",
+                target_os = "netbsd",
+                target_os = "openbsd",
+                target_os = "dragonfly"
+            ))]
+            Backend::Kqueue(..) => "kqueue",
+            Backend::Poll => "poll",
+        }
+    }
+
+    fn start(self, shared: Arc<Shared>) {
+        let _span = tracing::debug_span!("poll_driver", backend = self.name()).entered();
+
+        match self {
+            #[cfg(all(feature = "io-uring-driver", target_os = "linux"))]
+            Backend::Uring(ring) => super::uring::Driver::start(ring, shared),
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            Backend::Epoll(driver) => driver.start(shared),
+            #[cfg(any(
+                target_os = "macos",
+                target_os = "ios",
+                target_os = "freebsd",
+                target_os = "netbsd",
+                target_os = "openbsd",
+                target_os = "dragonfly"
+            ))]
+            Backend::Kqueue(driver) => driver.start(shared),
+            Backend::Poll => Driver::start(shared),
+        }
     }
 }
 
@@ -291,6 +576,8 @@ struct Driver {
     locations: HashMap<Token, Loc>,
     /// The descriptors being driven.
     descriptors: Vec<libc::pollfd>,
+    /// Buffer of tasks to wake up.
+    woken: Vec<std::task::Waker>,
 }
 
 impl Driver {
@@ -299,11 +586,12 @@ impl Driver {
             let mut result = unsafe {
                 errno!(libc::poll(
                     self.descriptors.as_mut_ptr(),
-                    self.descriptors.len() as libc::c_ulong,
+                    self.descriptors.len() as c::nfds_t,
                     -1,
                 ))?
             };
 
+            let mut dispatch = Dispatch::new(&guard.shared.metrics, &mut self.woken);
             let mut notified = false;
 
             for (n, e) in self.descriptors.iter_mut().enumerate() {
@@ -330,11 +618,14 @@ impl Driver {
                 waker
                     .returned_events
                     .store(std::mem::take(&mut e.revents) as usize, Ordering::Release);
-                waker.waker.wake();
+                dispatch.wake(&waker.waker);
             }
 
+            drop(dispatch);
+
             if notified {
                 let mut holders = guard.shared.holders.lock();
+                guard.shared.metrics.queue(holders.len());
                 holders.process(&mut self, &mut guard.wakers)?;
             }
         }
@@ -350,6 +641,7 @@ impl Driver {
                 events: libc::POLLIN,
                 revents: 0,
             }],
+            woken: Vec::new(),
         };
 
         let mut guard = PanicGuard {
@@ -360,13 +652,11 @@ impl Driver {
         if let Err(e) = state.run(&mut guard) {
             panic!("poll thread errored: {}", e)
         }
-
-        mem::forget(guard);
     }
 }
 
-/// Wrap a panic guard around self which will release any resources it
-/// has allocated when dropped and mark itself as panicked.
+/// Wrap a guard around the resources allocated by the driver, which marks it
+/// as stopped when dropped, whether it was shut down or panicked.
 struct PanicGuard {
     shared: Arc<Shared>,
     wakers: Vec<Arc<Waker>>,
@@ -376,18 +666,27 @@ impl Drop for PanicGuard {
     fn drop(&mut self) {
         self.shared.running.store(false, Ordering::Release);
 
-        // Wake up every waker so that they can observe the panic.
+        // Wake up every waker so that they can observe that the driver
+        // stopped.
         for waker in self.wakers.iter() {
             waker.waker.wake();
         }
     }
 }
 
+/// The handle used to wake up the background thread.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(crate) type Parker = EventFd;
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+pub(crate) type Parker = Pipe;
+
 /// Helper wrapper around an eventfd.
+#[cfg(any(target_os = "linux", target_os = "android"))]
 pub(crate) struct EventFd {
-    fd: c::c_int,
+    pub(super) fd: c::c_int,
 }
 
+#[cfg(any(target_os = "linux", target_os = "android"))]
 impl EventFd {
     fn new() -> Result<Self> {
         unsafe {
@@ -407,7 +706,7 @@ impl EventFd {
     }
 
     /// Read the next value from the eventfd.
-    fn recv(&self) -> Result<u64> {
+    pub(super) fn recv(&self) -> Result<u64> {
         unsafe {
             let mut bytes = [0u8; 8];
             let read = errno!(c::read(self.fd, bytes.as_mut_ptr() as *mut c::c_void, 8))?;
@@ -418,6 +717,7 @@ impl EventFd {
     }
 }
 
+#[cfg(any(target_os = "linux", target_os = "android"))]
 impl Drop for EventFd {
     fn drop(&mut self) {
         unsafe {
@@ -425,3 +725,108 @@ impl Drop for EventFd {
         }
     }
 }
+
+/// Helper wrapper around a self-pipe, for systems which don't have eventfd.
+///
+/// The background thread polls the read end, which is readable for as long as
+/// there are unreceived notifications.
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+pub(crate) struct Pipe {
+    pub(super) fd: c::c_int,
+    write: c::c_int,
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+impl Pipe {
+    fn new() -> Result<Self> {
+        let mut fds = [0; 2];
+
+        unsafe {
+            if libc::pipe(fds.as_mut_ptr()) < 0 {
+                return Err(last_errno().into());
+            }
+
+            let this = Self {
+                fd: fds[0],
+                write: fds[1],
+            };
+
+            for &fd in &fds {
+                let flags = libc::fcntl(fd, libc::F_GETFL);
+
+                if flags < 0
+                    || libc::fcntl(fd, libc::F_SETFL, flags | libc::O_NONBLOCK) < 0
+                    || libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC) < 0
+                {
+                    return Err(last_errno().into());
+                }
+            }
+
+            Ok(this)
+        }
+    }
+
+    /// Notify the read end of the pipe.
+    ///
+    /// Since it doesn't matter how many notifications are pending, `v` is
+    /// ignored.
+    fn send(&self, _: u64) -> Result<(), Errno> {
+        let n = unsafe { libc::write(self.write, [1u8].as_ptr() as *const c::c_void, 1) };
+
+        if n < 0 {
+            let errno = last_errno();
+
+            // NB: a full pipe already has pending notifications.
+            if errno != Errno::EWOULDBLOCK {
+                return Err(errno);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Receive all pending notifications, returning how many there were.
+    pub(super) fn recv(&self) -> Result<u64> {
+        let mut count = 0;
+        let mut buf = [0u8; 64];
+
+        loop {
+            let n = unsafe { libc::read(self.fd, buf.as_mut_ptr() as *mut c::c_void, buf.len()) };
+
+            if n < 0 {
+                let errno = last_errno();
+
+                if errno == Errno::EWOULDBLOCK {
+                    return Ok(count);
+                }
+
+                return Err(errno.into());
+            }
+
+            count += n as u64;
+
+            if (n as usize) < buf.len() {
+                return Ok(count);
+            }
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+impl Drop for Pipe {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = libc::close(self.fd);
+            let _ = libc::close(self.write);
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+fn last_errno() -> Errno {
+    Errno::new(
+        std::io::Error::last_os_error()
+            .raw_os_error()
+            .unwrap_or_default(),
+    )
+}
@@ -0,0 +1,115 @@
+//! A slab of registrations, addressed by keys which can be handed to the
+//! kernel as user data.
+//!
+//! Keys combine the index of an entry with a generation which is incremented
+//! every time the entry is vacated. So readiness reported by the kernel for a
+//! registration which has since been removed, or whose entry has been reused,
+//! is never mistaken for that of the current occupant.
+
+#[cfg(test)]
+mod tests;
+
+/// Key which is never handed out by a slab, so that it can be used as the
+/// user data of the parker.
+pub(crate) const PARKER: u64 = u64::MAX;
+
+/// The maximum number of entries in a slab.
+///
+/// NB: the remaining indexes are reserved, so that [PARKER] and the keys just
+/// below it can be used for other user data without colliding with a key.
+const MAX_ENTRIES: usize = u32::MAX as usize - 1;
+
+/// A slab of values.
+pub(crate) struct Slab<T> {
+    entries: Vec<Entry<T>>,
+    /// Indexes of vacant entries.
+    free: Vec<usize>,
+}
+
+struct Entry<T> {
+    value: Option<T>,
+    generation: u32,
+}
+
+impl<T> Slab<T> {
+    /// Construct a new empty slab.
+    pub(crate) fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            free: Vec::new(),
+        }
+    }
+
+    /// Insert a value, returning the key it can be accessed through.
+    pub(crate) fn insert(&mut self, value: T) -> u64 {
+        let index = match self.free.pop() {
+            Some(index) => index,
+            None => {
+                assert!(self.entries.len() < MAX_ENTRIES, "slab is full");
+
+                self.entries.push(Entry {
+                    value: None,
+                    generation: 0,
+                });
+
+                self.entries.len() - 1
+            }
+        };
+
+        let entry = &mut self.entries[index];
+        entry.value = Some(value);
+        key(index, entry.generation)
+    }
+
+    /// Get the value associated with the given key.
+    pub(crate) fn get(&self, key: u64) -> Option<&T> {
+        let (index, generation) = split(key);
+        let entry = self.entries.get(index)?;
+
+        if entry.generation != generation {
+            return None;
+        }
+
+        entry.value.as_ref()
+    }
+
+    /// Get the value associated with the given key mutably.
+    pub(crate) fn get_mut(&mut self, key: u64) -> Option<&mut T> {
+        let (index, generation) = split(key);
+        let entry = self.entries.get_mut(index)?;
+
+        if entry.generation != generation {
+            return None;
+        }
+
+        entry.value.as_mut()
+    }
+
+    /// Remove the value associated with the given key.
+    pub(crate) fn remove(&mut self, key: u64) -> Option<T> {
+        let (index, generation) = split(key);
+        let entry = self.entries.get_mut(index)?;
+
+        if entry.generation != generation {
+            return None;
+        }
+
+        let value = entry.value.take()?;
+        entry.generation = entry.generation.wrapping_add(1);
+        self.free.push(index);
+        Some(value)
+    }
+
+    /// Iterate over all values in the slab.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        self.entries.iter().flat_map(|entry| &entry.value)
+    }
+}
+
+fn key(index: usize, generation: u32) -> u64 {
+    (generation as u64) << 32 | index as u64
+}
+
+fn split(key: u64) -> (usize, u32) {
+    (key as u32 as usize, (key >> 32) as u32)
+}
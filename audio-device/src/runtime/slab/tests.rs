@@ -0,0 +1,73 @@
+use super::{key, split, Slab, MAX_ENTRIES, PARKER};
+
+#[test]
+fn test_key_encoding() {
+    assert_eq!(key(0, 0), 0);
+    assert_eq!(key(3, 0), 3);
+    assert_eq!(key(3, 2), 2 << 32 | 3);
+    assert_eq!(split(key(3, 2)), (3, 2));
+    assert_eq!(split(key(7, u32::MAX)), (7, u32::MAX));
+}
+
+#[test]
+fn test_insert_remove() {
+    let mut slab = Slab::new();
+    let a = slab.insert("a");
+    let b = slab.insert("b");
+    assert_eq!(split(a), (0, 0));
+    assert_eq!(split(b), (1, 0));
+
+    assert_eq!(slab.get(a), Some(&"a"));
+    *slab.get_mut(b).unwrap() = "c";
+    assert_eq!(slab.iter().copied().collect::<Vec<_>>(), ["a", "c"]);
+
+    assert_eq!(slab.remove(a), Some("a"));
+    assert_eq!(slab.get(a), None);
+    assert_eq!(slab.remove(a), None);
+    assert_eq!(slab.iter().copied().collect::<Vec<_>>(), ["c"]);
+}
+
+#[test]
+fn test_stale_key() {
+    let mut slab = Slab::new();
+    let stale = slab.insert(1);
+    assert_eq!(slab.remove(stale), Some(1));
+
+    // NB: the vacated entry is reused with the next generation.
+    let current = slab.insert(2);
+    assert_eq!(split(current), (0, 1));
+
+    assert_eq!(slab.get(stale), None);
+    assert_eq!(slab.get_mut(stale), None);
+    assert_eq!(slab.remove(stale), None);
+    assert_eq!(slab.get(current), Some(&2));
+}
+
+#[test]
+fn test_generation_wraparound() {
+    let mut slab = Slab::new();
+    let first = slab.insert(1);
+    slab.entries[0].generation = u32::MAX;
+    let old = key(0, u32::MAX);
+    assert_eq!(slab.get(old), Some(&1));
+
+    assert_eq!(slab.remove(old), Some(1));
+    let new = slab.insert(2);
+    assert_eq!(split(new), (0, 0));
+    assert_eq!(new, first);
+
+    assert_eq!(slab.get(old), None);
+    assert_eq!(slab.get(new), Some(&2));
+}
+
+#[test]
+fn test_parker_is_reserved() {
+    let mut slab = Slab::new();
+    slab.insert(1);
+
+    let (index, _) = split(PARKER);
+    assert!(index >= MAX_ENTRIES);
+    assert_eq!(slab.get(PARKER), None);
+    assert_eq!(slab.remove(PARKER), None);
+    assert_eq!(slab.iter().count(), 1);
+}
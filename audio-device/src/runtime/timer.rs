@@ -0,0 +1,316 @@
+//! Timers driven by the runtime, which are backed by a timerfd on Linux and a
+//! waitable timer on Windows.
+
+use crate::Result;
+use std::future::{self, Future};
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+/// A timer driven by the audio runtime.
+///
+/// Periodic timers are aligned to their period, so ticks don't drift with the
+/// time it takes to handle them. If ticks are missed because the timer wasn't
+/// waited on in time, the next tick reports how many periods have elapsed
+/// instead of firing for each of them.
+///
+/// On Windows the timer has millisecond resolution.
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::runtime::{Runtime, Timer};
+/// use std::time::Duration;
+///
+/// # #[tokio::main] async fn main() -> anyhow::Result<()> {
+/// let runtime = Runtime::new()?;
+///
+/// runtime.wrap(async {
+///     let mut timer = Timer::interval(Duration::from_millis(10))?;
+///
+///     loop {
+///         let ticks = timer.tick().await?;
+///
+///         if ticks > 1 {
+///             println!("missed {} ticks", ticks - 1);
+///         }
+///     }
+/// }).await
+/// # }
+/// ```
+pub struct Timer {
+    inner: sys::Timer,
+}
+
+impl Timer {
+    /// Construct a timer which fires once after the given duration.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless an audio runtime is available.
+    ///
+    /// See [Runtime][crate::runtime::Runtime].
+    pub fn after(duration: Duration) -> Result<Self> {
+        Ok(Self {
+            inner: sys::Timer::new(duration, None)?,
+        })
+    }
+
+    /// Construct a timer which ticks once every period, starting one period
+    /// from now.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `period` is zero, or unless an audio runtime is available.
+    ///
+    /// See [Runtime][crate::runtime::Runtime].
+    pub fn interval(period: Duration) -> Result<Self> {
+        assert!(period > Duration::from_secs(0), "period must be non-zero");
+
+        Ok(Self {
+            inner: sys::Timer::new(period, Some(period))?,
+        })
+    }
+
+    /// Wait for the timer to fire, returning the number of times it has
+    /// fired since it was last waited on.
+    ///
+    /// A timer constructed with [after][Timer::after] never fires again once
+    /// this has completed.
+    pub async fn tick(&mut self) -> Result<u64> {
+        future::poll_fn(|cx| self.poll_tick(cx)).await
+    }
+
+    /// Poll for the timer to fire.
+    ///
+    /// If it hasn't, the current task is registered to be woken up once it
+    /// does.
+    ///
+    /// See [Timer::tick].
+    pub fn poll_tick(&mut self, cx: &mut Context<'_>) -> Poll<Result<u64>> {
+        self.inner.poll_tick(cx)
+    }
+}
+
+/// Run the given future until it completes or the given duration elapses,
+/// returning `None` if it timed out.
+///
+/// This can be used to stop waiting for a device which has stopped
+/// responding.
+///
+/// # Panics
+///
+/// Panics unless an audio runtime is available.
+///
+/// See [Runtime][crate::runtime::Runtime].
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::runtime::{timeout, Runtime};
+/// use std::time::Duration;
+///
+/// # async fn wait_for_device() {}
+/// # #[tokio::main] async fn main() -> anyhow::Result<()> {
+/// let runtime = Runtime::new()?;
+///
+/// runtime.wrap(async {
+///     if timeout(Duration::from_secs(1), wait_for_device()).await?.is_none() {
+///         println!("device didn't respond in time");
+///     }
+///
+///     Ok(())
+/// }).await
+/// # }
+/// ```
+pub async fn timeout<F>(duration: Duration, future: F) -> Result<Option<F::Output>>
+where
+    F: Future,
+{
+    let mut timer = Timer::after(duration)?;
+    let mut future = Box::pin(future);
+
+    future::poll_fn(|cx| {
+        if let Poll::Ready(output) = future.as_mut().poll(cx) {
+            return Poll::Ready(Ok(Some(output)));
+        }
+
+        match timer.poll_tick(cx) {
+            Poll::Ready(result) => Poll::Ready(result.map(|_| None)),
+            Poll::Pending => Poll::Pending,
+        }
+    })
+    .await
+}
+
+#[cfg(all(
+    feature = "poll-driver",
+    any(target_os = "linux", target_os = "android")
+))]
+mod sys {
+    use crate::libc as c;
+    use crate::unix::{AsyncPoll, Errno};
+    use crate::Result;
+    use std::io;
+    use std::os::unix::io::{AsRawFd, FromRawFd, OwnedFd};
+    use std::task::{Context, Poll};
+    use std::time::Duration;
+
+    pub(super) struct Timer {
+        // NB: declared first so that the handle is unregistered before the
+        // timerfd is closed.
+        poll: AsyncPoll,
+        fd: OwnedFd,
+    }
+
+    impl Timer {
+        pub(super) fn new(value: Duration, interval: Option<Duration>) -> Result<Self> {
+            let fd = unsafe {
+                let fd = libc::timerfd_create(
+                    libc::CLOCK_MONOTONIC,
+                    libc::TFD_NONBLOCK | libc::TFD_CLOEXEC,
+                );
+
+                if fd < 0 {
+                    return Err(last_errno().into());
+                }
+
+                OwnedFd::from_raw_fd(fd)
+            };
+
+            let spec = libc::itimerspec {
+                it_interval: timespec(interval.unwrap_or_default()),
+                // NB: a zero value disarms the timer, so it fires as soon as
+                // possible instead.
+                it_value: timespec(value.max(Duration::from_nanos(1))),
+            };
+
+            unsafe {
+                if libc::timerfd_settime(fd.as_raw_fd(), 0, &spec, std::ptr::null_mut()) < 0 {
+                    return Err(last_errno().into());
+                }
+            }
+
+            // Safety: the timerfd outlives the handle, since it's dropped
+            // after it.
+            let poll = unsafe {
+                AsyncPoll::new(c::pollfd {
+                    fd: fd.as_raw_fd(),
+                    events: c::POLLIN,
+                    revents: 0,
+                })?
+            };
+
+            Ok(Self { poll, fd })
+        }
+
+        pub(super) fn poll_tick(&mut self, cx: &mut Context<'_>) -> Poll<Result<u64>> {
+            loop {
+                let guard = match self.poll.poll_returned_events(cx) {
+                    Poll::Ready(guard) => guard?,
+                    Poll::Pending => return Poll::Pending,
+                };
+
+                let mut expirations = [0u8; 8];
+
+                let n = unsafe {
+                    libc::read(
+                        self.fd.as_raw_fd(),
+                        expirations.as_mut_ptr() as *mut c::c_void,
+                        expirations.len(),
+                    )
+                };
+
+                if n < 0 {
+                    let errno = last_errno();
+
+                    // NB: the readiness was stale, so the guard is dropped to
+                    // wait for the timer again.
+                    if errno == Errno::EWOULDBLOCK {
+                        drop(guard);
+                        continue;
+                    }
+
+                    return Poll::Ready(Err(errno.into()));
+                }
+
+                return Poll::Ready(Ok(u64::from_ne_bytes(expirations)));
+            }
+        }
+    }
+
+    fn timespec(duration: Duration) -> libc::timespec {
+        libc::timespec {
+            tv_sec: duration.as_secs() as libc::time_t,
+            tv_nsec: duration.subsec_nanos() as _,
+        }
+    }
+
+    fn last_errno() -> Errno {
+        Errno::new(
+            io::Error::last_os_error()
+                .raw_os_error()
+                .unwrap_or_default(),
+        )
+    }
+}
+
+#[cfg(all(windows, feature = "events-driver"))]
+mod sys {
+    use crate::windows::{AsyncEvent, Event};
+    use crate::Result;
+    use std::task::{Context, Poll};
+    use std::time::{Duration, Instant};
+    use windows::core::PCWSTR;
+    use windows::Win32::System::Threading as th;
+
+    pub(super) struct Timer {
+        event: AsyncEvent,
+        /// When the timer is next due to fire.
+        next: Instant,
+        /// The period of the timer, as rounded when it was armed.
+        period: Option<Duration>,
+    }
+
+    impl Timer {
+        pub(super) fn new(value: Duration, interval: Option<Duration>) -> Result<Self> {
+            // NB: the period of a waitable timer is in milliseconds, while the
+            // due time is in 100 nanosecond intervals where negative values
+            // are relative to now.
+            let period = interval.map(|p| Duration::from_millis((p.as_millis() as u64).max(1)));
+            let period_ms = period.map(|p| p.as_millis() as i32).unwrap_or_default();
+            let due = -((value.as_nanos() / 100) as i64).max(1);
+
+            let event = unsafe {
+                let handle = th::CreateWaitableTimerW(None, false, PCWSTR::null())?;
+                let event = Event::from_raw(handle);
+                th::SetWaitableTimer(handle, &due, period_ms, None, None, false).ok()?;
+                event
+            };
+
+            Ok(Self {
+                event: AsyncEvent::from_event(event)?,
+                next: Instant::now() + value,
+                period,
+            })
+        }
+
+        pub(super) fn poll_tick(&mut self, cx: &mut Context<'_>) -> Poll<Result<u64>> {
+            match self.event.poll_wait(cx) {
+                Poll::Ready(result) => result?,
+                Poll::Pending => return Poll::Pending,
+            }
+
+            let period = match self.period {
+                Some(period) => period,
+                None => return Poll::Ready(Ok(1)),
+            };
+
+            // NB: waitable timers don't count their expirations, so the ticks
+            // which were missed are derived from when the timer was due.
+            let late = Instant::now().saturating_duration_since(self.next);
+            let ticks = 1 + (late.as_nanos() / period.as_nanos()) as u64;
+            self.next += period * ticks as u32;
+            Poll::Ready(Ok(ticks))
+        }
+    }
+}
@@ -0,0 +1,150 @@
+//! Handles driven by the reactor of the current tokio runtime, instead of the
+//! [PollDriver][super::PollDriver].
+//!
+//! Tokio only reports which directions a descriptor is ready in, so once it
+//! says that a descriptor is ready the returned events are read with a
+//! non-blocking `poll(2)`. This also filters out stale readiness, since tokio
+//! only forgets about it once it's been cleared.
+
+use crate::libc as c;
+use crate::unix::Errno;
+use std::io;
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::task::{Context, Poll};
+use tokio::io::unix::{AsyncFd, AsyncFdReadyGuard};
+use tokio::io::Interest;
+
+/// A descriptor registered with tokio.
+struct Descriptor {
+    fd: RawFd,
+    /// The descriptor is a duplicate owned by the handle.
+    dup: bool,
+}
+
+impl AsRawFd for Descriptor {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd
+    }
+}
+
+impl Drop for Descriptor {
+    fn drop(&mut self) {
+        if self.dup {
+            unsafe {
+                let _ = libc::close(self.fd);
+            }
+        }
+    }
+}
+
+pub(crate) struct TokioPoll {
+    fd: AsyncFd<Descriptor>,
+    descriptor: c::pollfd,
+}
+
+impl TokioPoll {
+    /// Test if the current thread is in the context of a tokio runtime.
+    pub(crate) fn is_available() -> bool {
+        tokio::runtime::Handle::try_current().is_ok()
+    }
+
+    /// Register the given descriptor with the reactor of the current tokio
+    /// runtime.
+    pub(crate) fn new(descriptor: c::pollfd) -> Result<Self, Errno> {
+        let readable = descriptor.events & (c::POLLIN | c::POLLPRI) != 0;
+        let writable = descriptor.events & c::POLLOUT != 0;
+
+        let interest = match (readable, writable) {
+            (true, true) => Interest::READABLE.add(Interest::WRITABLE),
+            (true, false) => Interest::READABLE,
+            (false, true) => Interest::WRITABLE,
+            (false, false) => return Err(Errno::new(libc::EINVAL)),
+        };
+
+        let original = Descriptor {
+            fd: descriptor.fd,
+            dup: false,
+        };
+
+        let fd = match AsyncFd::with_interest(original, interest) {
+            Ok(fd) => fd,
+            // NB: the descriptor is already registered by another handle.
+            Err(e) if e.raw_os_error() == Some(libc::EEXIST) => {
+                let fd = unsafe { libc::fcntl(descriptor.fd, libc::F_DUPFD_CLOEXEC, 0) };
+
+                if fd < 0 {
+                    return Err(errno(io::Error::last_os_error()));
+                }
+
+                let dup = Descriptor { fd, dup: true };
+                AsyncFd::with_interest(dup, interest).map_err(errno)?
+            }
+            Err(e) => return Err(errno(e)),
+        };
+
+        Ok(Self { fd, descriptor })
+    }
+
+    /// Poll for the returned events of the descriptor.
+    pub(crate) fn poll_returned_events(&self, cx: &mut Context<'_>) -> Poll<c::c_short> {
+        loop {
+            let mut guard = match self.poll_ready(cx) {
+                Poll::Ready(Ok(guard)) => guard,
+                Poll::Ready(Err(error)) => {
+                    // NB: the reactor has shut down.
+                    tracing::error!(%error, "failed to poll readiness");
+                    return Poll::Ready(c::POLLERR);
+                }
+                Poll::Pending => return Poll::Pending,
+            };
+
+            let mut d = c::pollfd {
+                fd: self.fd.get_ref().fd,
+                events: self.descriptor.events,
+                revents: 0,
+            };
+
+            if unsafe { libc::poll(&mut d, 1, 0) } < 0 {
+                let error = io::Error::last_os_error();
+
+                if error.kind() == io::ErrorKind::Interrupted {
+                    continue;
+                }
+
+                tracing::error!(%error, "failed to poll descriptor");
+                return Poll::Ready(c::POLLERR);
+            }
+
+            if d.revents != 0 {
+                return Poll::Ready(d.revents);
+            }
+
+            guard.clear_ready();
+        }
+    }
+
+    /// Poll for readiness in any of the directions of interest, registering
+    /// the task to be woken up for all of them.
+    fn poll_ready(
+        &self,
+        cx: &mut Context<'_>,
+    ) -> Poll<io::Result<AsyncFdReadyGuard<'_, Descriptor>>> {
+        if self.descriptor.events & (c::POLLIN | c::POLLPRI) != 0 {
+            if let Poll::Ready(result) = self.fd.poll_read_ready(cx) {
+                return Poll::Ready(result);
+            }
+        }
+
+        if self.descriptor.events & c::POLLOUT != 0 {
+            if let Poll::Ready(result) = self.fd.poll_write_ready(cx) {
+                return Poll::Ready(result);
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
+fn errno(error: io::Error) -> Errno {
+    Errno::new(error.raw_os_error().unwrap_or(libc::EIO))
+}
@@ -0,0 +1,486 @@
+//! A backend for the [PollDriver][super::PollDriver] built on io_uring.
+//!
+//! Instead of handing every registered descriptor to `poll(2)` each time the
+//! driver wakes up, interest is submitted as one-shot poll operations which
+//! the kernel completes as descriptors become ready. Registering or re-arming
+//! a handle is a single submission, and the driver thread submits and reaps
+//! in the same `io_uring_enter` call. This keeps the cost of a wakeup
+//! independent of how many streams and timers are registered.
+
+use crate::loom::sync::Arc;
+use crate::runtime::dispatch::Dispatch;
+use crate::runtime::poll::{Events, Shared, Token, Waker};
+use crate::runtime::slab::{Slab, PARKER};
+use crate::unix::Errno;
+use crate::Result;
+use std::collections::{HashMap, VecDeque};
+use std::io;
+use std::mem;
+use std::ptr;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::task;
+
+/// The number of submission queue entries the ring is set up with.
+pub(super) const ENTRIES: u32 = 256;
+
+const IORING_OFF_SQ_RING: libc::off_t = 0;
+const IORING_OFF_CQ_RING: libc::off_t = 0x8000000;
+const IORING_OFF_SQES: libc::off_t = 0x10000000;
+const IORING_OP_POLL_ADD: u8 = 6;
+const IORING_OP_POLL_REMOVE: u8 = 7;
+const IORING_ENTER_GETEVENTS: libc::c_uint = 1;
+
+/// User data of operations whose completions are ignored.
+const IGNORED: u64 = u64::MAX - 1;
+
+#[repr(C)]
+#[derive(Default)]
+struct SqringOffsets {
+    head: u32,
+    tail: u32,
+    ring_mask: u32,
+    ring_entries: u32,
+    flags: u32,
+    dropped: u32,
+    array: u32,
+    resv1: u32,
+    resv2: u64,
+}
+
+#[repr(C)]
+#[derive(Default)]
+struct CqringOffsets {
+    head: u32,
+    tail: u32,
+    ring_mask: u32,
+    ring_entries: u32,
+    overflow: u32,
+    cqes: u32,
+    flags: u32,
+    resv1: u32,
+    resv2: u64,
+}
+
+#[repr(C)]
+#[derive(Default)]
+struct Params {
+    sq_entries: u32,
+    cq_entries: u32,
+    flags: u32,
+    sq_thread_cpu: u32,
+    sq_thread_idle: u32,
+    features: u32,
+    wq_fd: u32,
+    resv: [u32; 3],
+    sq_off: SqringOffsets,
+    cq_off: CqringOffsets,
+}
+
+/// A submission queue entry.
+#[repr(C)]
+#[derive(Default)]
+struct Sqe {
+    opcode: u8,
+    flags: u8,
+    ioprio: u16,
+    fd: i32,
+    off: u64,
+    addr: u64,
+    len: u32,
+    op_flags: u32,
+    user_data: u64,
+    buf_index: u16,
+    personality: u16,
+    splice_fd_in: i32,
+    pad: [u64; 2],
+}
+
+impl Sqe {
+    /// Poll the given descriptor once for the given events.
+    fn poll_add(fd: libc::c_int, events: libc::c_short, user_data: u64) -> Self {
+        let events = events as u16 as u32;
+
+        // NB: the poll mask is stored as two little-endian halves.
+        #[cfg(target_endian = "big")]
+        let events = events.rotate_left(16);
+
+        Self {
+            opcode: IORING_OP_POLL_ADD,
+            fd,
+            op_flags: events,
+            user_data,
+            ..Self::default()
+        }
+    }
+
+    /// Cancel the poll operation with the given user data.
+    fn poll_remove(target: u64) -> Self {
+        Self {
+            opcode: IORING_OP_POLL_REMOVE,
+            fd: -1,
+            addr: target,
+            user_data: IGNORED,
+            ..Self::default()
+        }
+    }
+}
+
+/// A completion queue entry.
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct Cqe {
+    user_data: u64,
+    res: i32,
+    flags: u32,
+}
+
+/// A memory mapped region of the ring.
+struct Mmap {
+    ptr: *mut libc::c_void,
+    len: usize,
+}
+
+impl Mmap {
+    fn new(fd: &Fd, len: usize, offset: libc::off_t) -> Result<Self, Errno> {
+        let ptr = unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED | libc::MAP_POPULATE,
+                fd.0,
+                offset,
+            )
+        };
+
+        if ptr == libc::MAP_FAILED {
+            return Err(last_errno());
+        }
+
+        Ok(Self { ptr, len })
+    }
+
+    /// Get a pointer at the given byte offset into the region.
+    fn at<T>(&self, offset: u32) -> *mut T {
+        self.ptr.wrapping_add(offset as usize).cast()
+    }
+}
+
+impl Drop for Mmap {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = libc::munmap(self.ptr, self.len);
+        }
+    }
+}
+
+struct Fd(libc::c_int);
+
+impl Drop for Fd {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = libc::close(self.0);
+        }
+    }
+}
+
+/// An io_uring instance.
+pub(super) struct Ring {
+    sq_head: *const AtomicU32,
+    sq_tail: *const AtomicU32,
+    sq_mask: u32,
+    sq_entries: u32,
+    sq_array: *mut u32,
+    sqes: *mut Sqe,
+    cq_head: *const AtomicU32,
+    cq_tail: *const AtomicU32,
+    cq_mask: u32,
+    cqes: *const Cqe,
+    /// The tail of the submission queue, which is only written to by us.
+    tail: u32,
+    // NB: regions must be unmapped before the descriptor is closed.
+    _sq: Mmap,
+    _cq: Mmap,
+    _sqes: Mmap,
+    fd: Fd,
+}
+
+impl Ring {
+    /// Set up a new ring with the given number of submission queue entries.
+    pub(super) fn new(entries: u32) -> Result<Self, Errno> {
+        let mut p = Params::default();
+
+        let fd = unsafe { libc::syscall(libc::SYS_io_uring_setup, entries, &mut p as *mut Params) };
+
+        if fd < 0 {
+            return Err(last_errno());
+        }
+
+        let fd = Fd(fd as libc::c_int);
+
+        let sq_len = p.sq_off.array as usize + p.sq_entries as usize * mem::size_of::<u32>();
+        let cq_len = p.cq_off.cqes as usize + p.cq_entries as usize * mem::size_of::<Cqe>();
+        let sqes_len = p.sq_entries as usize * mem::size_of::<Sqe>();
+
+        let sq = Mmap::new(&fd, sq_len, IORING_OFF_SQ_RING)?;
+        let cq = Mmap::new(&fd, cq_len, IORING_OFF_CQ_RING)?;
+        let sqes = Mmap::new(&fd, sqes_len, IORING_OFF_SQES)?;
+
+        // Safety: the masks are written by the kernel during setup and never
+        // change.
+        let (sq_mask, cq_mask) = unsafe {
+            (
+                *sq.at::<u32>(p.sq_off.ring_mask),
+                *cq.at::<u32>(p.cq_off.ring_mask),
+            )
+        };
+
+        let sq_tail = sq.at::<AtomicU32>(p.sq_off.tail);
+
+        Ok(Self {
+            sq_head: sq.at(p.sq_off.head),
+            sq_tail,
+            sq_mask,
+            sq_entries: p.sq_entries,
+            sq_array: sq.at(p.sq_off.array),
+            sqes: sqes.at(0),
+            cq_head: cq.at(p.cq_off.head),
+            cq_tail: cq.at(p.cq_off.tail),
+            cq_mask,
+            cqes: cq.at(p.cq_off.cqes),
+            tail: unsafe { (*sq_tail).load(Ordering::Relaxed) },
+            _sq: sq,
+            _cq: cq,
+            _sqes: sqes,
+            fd,
+        })
+    }
+
+    /// Push an entry onto the submission queue. Returns `false` if the queue
+    /// is full.
+    fn push(&mut self, sqe: &Sqe) -> bool {
+        unsafe {
+            let head = (*self.sq_head).load(Ordering::Acquire);
+
+            if self.tail.wrapping_sub(head) >= self.sq_entries {
+                return false;
+            }
+
+            let index = self.tail & self.sq_mask;
+            ptr::copy_nonoverlapping(sqe, self.sqes.add(index as usize), 1);
+            *self.sq_array.add(index as usize) = index;
+            self.tail = self.tail.wrapping_add(1);
+            (*self.sq_tail).store(self.tail, Ordering::Release);
+        }
+
+        true
+    }
+
+    /// Submit all queued entries and wait for at least one completion.
+    fn submit_and_wait(&mut self) -> Result<(), Errno> {
+        let head = unsafe { (*self.sq_head).load(Ordering::Acquire) };
+        let to_submit = self.tail.wrapping_sub(head);
+
+        let result = unsafe {
+            libc::syscall(
+                libc::SYS_io_uring_enter,
+                self.fd.0,
+                to_submit,
+                1 as libc::c_uint,
+                IORING_ENTER_GETEVENTS,
+                ptr::null::<libc::c_void>(),
+                0 as libc::size_t,
+            )
+        };
+
+        if result < 0 {
+            match last_errno() {
+                // NB: interrupted, or the completion queue needs to be reaped
+                // before anything more can be submitted.
+                errno if errno.value() == libc::EINTR || errno.value() == libc::EBUSY => (),
+                errno => return Err(errno),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reap all available completions into `out`.
+    fn reap(&mut self, out: &mut Vec<Cqe>) {
+        unsafe {
+            let tail = (*self.cq_tail).load(Ordering::Acquire);
+            let mut head = (*self.cq_head).load(Ordering::Relaxed);
+
+            while head != tail {
+                out.push(*self.cqes.add((head & self.cq_mask) as usize));
+                head = head.wrapping_add(1);
+            }
+
+            (*self.cq_head).store(head, Ordering::Release);
+        }
+    }
+}
+
+// Safety: the ring is only ever accessed by the thread which owns it.
+unsafe impl Send for Ring {}
+
+pub(super) struct Driver {
+    ring: Ring,
+    /// Entries which didn't fit in the submission queue.
+    backlog: VecDeque<Sqe>,
+    /// The slab key of a given token.
+    keys: HashMap<Token, u64>,
+    completed: Vec<Cqe>,
+    /// Buffer of tasks to wake up.
+    woken: Vec<task::Waker>,
+}
+
+impl Driver {
+    fn run(mut self, guard: &mut PanicGuard) -> Result<()> {
+        self.queue(Sqe::poll_add(guard.shared.parker.fd, libc::POLLIN, PARKER));
+
+        while guard.shared.running.load(Ordering::Acquire) {
+            while let Some(sqe) = self.backlog.front() {
+                if !self.ring.push(sqe) {
+                    break;
+                }
+
+                self.backlog.pop_front();
+            }
+
+            self.ring.submit_and_wait()?;
+            self.ring.reap(&mut self.completed);
+
+            let mut dispatch = Dispatch::new(&guard.shared.metrics, &mut self.woken);
+            let mut notified = false;
+
+            for cqe in self.completed.drain(..) {
+                let key = match cqe.user_data {
+                    PARKER => {
+                        notified = true;
+                        continue;
+                    }
+                    IGNORED => continue,
+                    key => key,
+                };
+
+                // NB: completions of operations which belong to removed
+                // handles are ignored.
+                let waker = match guard.wakers.get(key) {
+                    Some(waker) => waker,
+                    None => continue,
+                };
+
+                let events = if cqe.res < 0 {
+                    // NB: the operation was cancelled through removal.
+                    if cqe.res == -libc::ECANCELED {
+                        continue;
+                    }
+
+                    libc::POLLERR
+                } else {
+                    cqe.res as libc::c_short
+                };
+
+                // The operation is one-shot, so the handle stays disarmed
+                // until the task releases the returned events.
+                waker
+                    .returned_events
+                    .store(events as u16 as usize, Ordering::Release);
+                dispatch.wake(&waker.waker);
+            }
+
+            drop(dispatch);
+
+            if notified {
+                let _ = guard.shared.parker.recv()?;
+                self.queue(Sqe::poll_add(guard.shared.parker.fd, libc::POLLIN, PARKER));
+
+                let mut holders = guard.shared.holders.lock();
+                guard.shared.metrics.queue(holders.len());
+                self.process(&mut holders, &mut guard.wakers);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Process all queued registrations.
+    fn process(&mut self, events: &mut Events, wakers: &mut Slab<Arc<Waker>>) {
+        for waker in events.added.drain(..) {
+            let d = waker.descriptor;
+            let token = waker.token();
+            let key = wakers.insert(waker);
+            self.keys.insert(token, key);
+            self.queue(Sqe::poll_add(d.fd, d.events, key));
+        }
+
+        for token in events.removed.drain(..) {
+            if let Some(key) = self.keys.remove(&token) {
+                wakers.remove(key);
+                self.queue(Sqe::poll_remove(key));
+            }
+        }
+
+        for token in events.released.drain(..) {
+            if let Some(&key) = self.keys.get(&token) {
+                if let Some(waker) = wakers.get(key) {
+                    let d = waker.descriptor;
+                    self.queue(Sqe::poll_add(d.fd, d.events, key));
+                }
+            }
+        }
+    }
+
+    fn queue(&mut self, sqe: Sqe) {
+        if !self.backlog.is_empty() || !self.ring.push(&sqe) {
+            self.backlog.push_back(sqe);
+        }
+    }
+
+    pub(super) fn start(ring: Ring, shared: Arc<Shared>) {
+        let state = Driver {
+            ring,
+            backlog: VecDeque::new(),
+            keys: HashMap::new(),
+            completed: Vec::new(),
+            woken: Vec::new(),
+        };
+
+        let mut guard = PanicGuard {
+            shared,
+            wakers: Slab::new(),
+        };
+
+        if let Err(e) = state.run(&mut guard) {
+            panic!("io_uring thread errored: {}", e)
+        }
+    }
+}
+
+/// Wrap a guard around the resources allocated by the driver, which marks it
+/// as stopped when dropped, whether it was shut down or panicked.
+struct PanicGuard {
+    shared: Arc<Shared>,
+    wakers: Slab<Arc<Waker>>,
+}
+
+impl Drop for PanicGuard {
+    fn drop(&mut self) {
+        self.shared.running.store(false, Ordering::Release);
+
+        // Wake up every waker so that they can observe that the driver
+        // stopped.
+        for waker in self.wakers.iter() {
+            waker.waker.wake();
+        }
+    }
+}
+
+fn last_errno() -> Errno {
+    Errno::new(
+        io::Error::last_os_error()
+            .raw_os_error()
+            .unwrap_or_default(),
+    )
+}
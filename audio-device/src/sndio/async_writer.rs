@@ -0,0 +1,136 @@
+use core::marker;
+use std::future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use crate::buf_io;
+use crate::libc as c;
+use crate::sndio::{Error, Handle, Result};
+use crate::unix::{AsyncPoll, PollFlags};
+
+/// An interleaved type-checked async writer for a [Handle].
+///
+/// See [Handle::async_writer].
+pub struct AsyncWriter<'a, T> {
+    handle: &'a mut Handle,
+    poll_handle: AsyncPoll,
+    pollfd: c::pollfd,
+    channels: usize,
+    // NB: the number of bytes of the first frame of the buffer which have
+    // already been written, since writes are byte-oriented.
+    partial: usize,
+    _marker: marker::PhantomData<T>,
+}
+
+impl<'a, T> AsyncWriter<'a, T> {
+    /// Construct a new writer surrounding the given device.
+    ///
+    /// # Safety
+    ///
+    /// This constructor assumes that the caller has checked that type `T` is
+    /// appropriate for writing to the given device, and that `pollfd` is the
+    /// poll descriptor of the device.
+    pub(super) unsafe fn new(
+        handle: &'a mut Handle,
+        pollfd: c::pollfd,
+        channels: usize,
+    ) -> Result<Self> {
+        Ok(Self {
+            handle,
+            poll_handle: AsyncPoll::new(pollfd)?,
+            pollfd,
+            channels,
+            partial: 0,
+            _marker: marker::PhantomData,
+        })
+    }
+
+    /// Get the current playback delay in frames.
+    ///
+    /// See [Handle::delay].
+    pub fn delay(&self) -> i64 {
+        self.handle.delay()
+    }
+
+    /// Write an interleaved buffer.
+    pub async fn write_interleaved<B>(&mut self, mut buf: B) -> Result<()>
+    where
+        B: audio_core::Buf<Sample = T>
+            + audio_core::ReadBuf
+            + audio_core::ExactSizeBuf
+            + audio_core::InterleavedBuf<Sample = T>,
+    {
+        future::poll_fn(|cx| self.poll_write_interleaved(cx, &mut buf)).await
+    }
+
+    fn poll_write_interleaved<B>(&mut self, cx: &mut Context<'_>, mut buf: B) -> Poll<Result<()>>
+    where
+        B: audio_core::Buf<Sample = T>
+            + audio_core::ReadBuf
+            + audio_core::ExactSizeBuf
+            + audio_core::InterleavedBuf<Sample = T>,
+    {
+        if buf.channels() != self.channels {
+            return Poll::Ready(Err(Error::ChannelsMismatch {
+                actual: buf.channels(),
+                expected: self.channels,
+            }));
+        }
+
+        let Self {
+            handle,
+            poll_handle,
+            pollfd,
+            channels,
+            partial,
+            ..
+        } = self;
+
+        buf_io::poll_write(&mut buf, *channels, partial, |ptr, len| {
+            match unsafe { handle.write_raw(ptr as *const c::c_void, len) } {
+                Ok(0) => {
+                    loop {
+                        let guard = match poll_handle.poll_returned_events(cx) {
+                            Poll::Ready(guard) => guard?,
+                            Poll::Pending => return Poll::Pending,
+                        };
+
+                        pollfd.revents = guard.events();
+
+                        // NB: this also dispatches messages from the server,
+                        // which drives the onmove callback.
+                        let flags = PollFlags::from_bits_truncate(handle.revents(pollfd));
+
+                        if flags.test(PollFlags::POLLOUT) {
+                            return Poll::Ready(Ok(0));
+                        }
+
+                        drop(guard);
+                    }
+                }
+                result => Poll::Ready(result),
+            }
+        })
+    }
+}
+
+impl<T> audio_core::AsyncWriteBuf for AsyncWriter<'_, T> {
+    type Sample = T;
+    type Error = Error;
+
+    fn poll_write_buf<B>(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: B) -> Poll<Result<()>>
+    where
+        B: audio_core::ReadBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBuf<Sample = T>,
+    {
+        self.get_mut().poll_write_interleaved(cx, buf)
+    }
+}
+
+// Note: the sample type is only used as a marker.
+impl<T> Unpin for AsyncWriter<'_, T> {}
+
+// Safety: the device is only accessed through a mutable reference to the
+// writer.
+unsafe impl<T> Send for AsyncWriter<'_, T> {}
@@ -0,0 +1,527 @@
+use crate::libc as c;
+use crate::sndio::{Error, Parameters, Reader, Result, Sample, Writer};
+use crate::{DeviceInfo, Direction};
+use sndio_sys as sndio;
+use std::cell::Cell;
+use std::ffi::CStr;
+use std::fmt;
+use std::ptr;
+
+/// Open the device for playback.
+const SIO_PLAY: u32 = 1;
+/// Open the device for recording.
+const SIO_REC: u32 = 2;
+/// The maximum volume of a device.
+const SIO_MAXVOL: u32 = 127;
+
+/// The name of the default device, which is picked through the `AUDIODEVICE`
+/// environment variable.
+static DEFAULT: &[u8] = b"default\0";
+
+/// The direction a [Handle] is opened in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stream {
+    /// The device is opened for playback.
+    Playback,
+    /// The device is opened for recording.
+    Capture,
+}
+
+impl fmt::Display for Stream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Playback => f.write_str("playback"),
+            Self::Capture => f.write_str("capture"),
+        }
+    }
+}
+
+/// Describe the default device, which is the only one sndio can enumerate.
+///
+/// The device is probed by opening it in each direction, and is omitted if
+/// it can't be opened at all.
+pub(crate) fn device_infos() -> Result<Vec<DeviceInfo>> {
+    // NB: the device is opened in nonblocking mode, so that probing it
+    // doesn't wait for a device which is in use.
+    let playback = Handle::open_default_nonblocking(Stream::Playback).ok();
+    let capture = Handle::open_default_nonblocking(Stream::Capture).ok();
+
+    let (direction, parameters, channels) = match (&playback, &capture) {
+        (Some(p), Some(_)) => (
+            Direction::Duplex,
+            p.parameters(),
+            p.parameters().play_channels(),
+        ),
+        (Some(p), None) => (
+            Direction::Output,
+            p.parameters(),
+            p.parameters().play_channels(),
+        ),
+        (None, Some(c)) => (
+            Direction::Input,
+            c.parameters(),
+            c.parameters().record_channels(),
+        ),
+        (None, None) => return Ok(Vec::new()),
+    };
+
+    let id = Handle::default_name().to_string_lossy().into_owned();
+    let rate = parameters.rate();
+
+    // NB: sndio converts between encodings, so what's reported is only what
+    // the device is configured with.
+    Ok(vec![DeviceInfo {
+        name: id.clone(),
+        id,
+        direction,
+        is_default: true,
+        formats: parameters.sample_format().into_iter().collect(),
+        channels: Some((channels as usize, channels as usize)),
+        sample_rates: Some((rate, rate)),
+    }])
+}
+
+/// An opened sndio device.
+///
+/// The device is configured through [Handle::set_parameters], after which it
+/// can be read from or written to. Readers and writers start the device if it
+/// hasn't been started already.
+///
+/// See [Handle::open].
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::sndio;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let mut handle = sndio::Handle::open_default(sndio::Stream::Playback)?;
+///
+/// let mut parameters = sndio::Parameters::new();
+/// parameters.set_sample::<i16>();
+/// parameters.set_play_channels(2);
+/// parameters.set_rate(48000);
+/// let parameters = handle.set_parameters(&parameters)?;
+///
+/// let channels = parameters.play_channels() as usize;
+/// let samples = vec![0i16; 1024 * channels];
+///
+/// let mut writer = handle.writer::<i16>()?;
+/// writer.write_interleaved(audio::wrap::interleaved(&samples[..], channels))?;
+/// # Ok(()) }
+/// ```
+pub struct Handle {
+    pub(super) handle: ptr::NonNull<sndio::sio_hdl>,
+    stream: Stream,
+    parameters: Parameters,
+    // NB: registered with sio_onmove, so it must have a stable address and
+    // outlive the handle.
+    moved: Box<Cell<i64>>,
+    /// The number of bytes transferred by readers and writers.
+    transferred: i64,
+    started: bool,
+}
+
+impl Handle {
+    /// Open the device identified by the given name, like `snd/0` or
+    /// `rsnd/0`.
+    pub fn open(name: &CStr, stream: Stream) -> Result<Self> {
+        Self::open_inner(name, stream, false)
+    }
+
+    /// Open the default device.
+    pub fn open_default(stream: Stream) -> Result<Self> {
+        Self::open(Self::default_name(), stream)
+    }
+
+    /// Open the device identified by the given name in nonblocking mode, as
+    /// is required to use it with an async reader or writer.
+    pub fn open_nonblocking(name: &CStr, stream: Stream) -> Result<Self> {
+        Self::open_inner(name, stream, true)
+    }
+
+    /// Open the default device in nonblocking mode.
+    pub fn open_default_nonblocking(stream: Stream) -> Result<Self> {
+        Self::open_nonblocking(Self::default_name(), stream)
+    }
+
+    fn default_name() -> &'static CStr {
+        unsafe { CStr::from_bytes_with_nul_unchecked(DEFAULT) }
+    }
+
+    fn open_inner(name: &CStr, stream: Stream, nonblocking: bool) -> Result<Self> {
+        let mode = match stream {
+            Stream::Playback => SIO_PLAY,
+            Stream::Capture => SIO_REC,
+        };
+
+        unsafe {
+            let handle = sndio::sio_open(name.as_ptr(), mode, nonblocking as c::c_int);
+            let handle = ptr::NonNull::new(handle).ok_or(Error::Failed("sio_open"))?;
+
+            let mut this = Self {
+                handle,
+                stream,
+                parameters: Parameters::new(),
+                moved: Box::new(Cell::new(0)),
+                transferred: 0,
+                started: false,
+            };
+
+            sndio::sio_onmove(
+                this.handle.as_ptr(),
+                Some(onmove_callback),
+                &*this.moved as *const Cell<i64> as *mut c::c_void,
+            );
+
+            this.parameters = this.get_parameters()?;
+            Ok(this)
+        }
+    }
+
+    /// The direction the device is opened in.
+    pub fn stream(&self) -> Stream {
+        self.stream
+    }
+
+    /// The current parameters of the device.
+    pub fn parameters(&self) -> &Parameters {
+        &self.parameters
+    }
+
+    /// Configure the device with the given parameters, returning the
+    /// parameters which were picked by the device.
+    ///
+    /// The picked parameters might differ from the requested ones, so they
+    /// should be checked before the device is used.
+    ///
+    /// This stops the device if it's started.
+    pub fn set_parameters(&mut self, parameters: &Parameters) -> Result<Parameters> {
+        self.stop()?;
+
+        let mut raw = parameters.raw;
+
+        unsafe {
+            if sndio::sio_setpar(self.handle.as_ptr(), &mut raw) == 0 {
+                return Err(Error::Failed("sio_setpar"));
+            }
+        }
+
+        self.parameters = self.get_parameters()?;
+        Ok(self.parameters.clone())
+    }
+
+    /// Start the device.
+    ///
+    /// Playback starts once the play buffer of the device has been filled, so
+    /// this doesn't have to be called before writing.
+    pub fn start(&mut self) -> Result<()> {
+        if !self.started {
+            unsafe {
+                if sndio::sio_start(self.handle.as_ptr()) == 0 {
+                    return Err(self.failed("sio_start"));
+                }
+            }
+
+            self.moved.set(0);
+            self.transferred = 0;
+            self.started = true;
+        }
+
+        Ok(())
+    }
+
+    /// Stop the device, blocking until all pending frames have been played.
+    pub fn stop(&mut self) -> Result<()> {
+        if self.started {
+            self.started = false;
+
+            unsafe {
+                if sndio::sio_stop(self.handle.as_ptr()) == 0 {
+                    return Err(self.failed("sio_stop"));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Test if the device is started.
+    pub fn is_started(&self) -> bool {
+        self.started
+    }
+
+    /// Set the volume of the device, from `0.0` to `1.0`.
+    pub fn set_volume(&mut self, volume: f32) -> Result<()> {
+        let volume = (volume.clamp(0.0, 1.0) * SIO_MAXVOL as f32).round() as u32;
+
+        unsafe {
+            if sndio::sio_setvol(self.handle.as_ptr(), volume) == 0 {
+                return Err(self.failed("sio_setvol"));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The number of frames which the hardware has played or recorded since
+    /// the device was started.
+    ///
+    /// This is updated as the device is read from or written to.
+    pub fn position(&self) -> i64 {
+        self.moved.get()
+    }
+
+    /// Get the current delay in frames.
+    ///
+    /// For playback, this is the number of frames which have to pass before a
+    /// frame written now is heard. For capture, it's the number of recorded
+    /// frames which haven't been read yet.
+    pub fn delay(&self) -> i64 {
+        let channels = match self.stream {
+            Stream::Playback => self.parameters.play_channels(),
+            Stream::Capture => self.parameters.record_channels(),
+        };
+
+        let frame_size = (self.parameters.bytes_per_sample() * channels) as i64;
+        let transferred = self.transferred.checked_div(frame_size).unwrap_or_default();
+
+        match self.stream {
+            Stream::Playback => transferred - self.moved.get(),
+            Stream::Capture => self.moved.get() - transferred,
+        }
+    }
+
+    /// Test if the device is in an unrecoverable error state, like when it has
+    /// been disconnected.
+    pub fn is_eof(&self) -> bool {
+        unsafe { sndio::sio_eof(self.handle.as_ptr()) != 0 }
+    }
+
+    /// Construct a checked blocking writer for the given sample type.
+    ///
+    /// Errors with [Error::FormatMismatch] if the sample is not appropriate for
+    /// the configured parameters.
+    pub fn writer<T>(&mut self) -> Result<Writer<'_, T>>
+    where
+        T: Sample,
+    {
+        let channels = self.prepare::<T>(Stream::Playback)?;
+        Ok(unsafe { Writer::new(self, channels) })
+    }
+
+    /// Construct a checked blocking reader for the given sample type.
+    ///
+    /// Errors with [Error::FormatMismatch] if the sample is not appropriate for
+    /// the configured parameters.
+    pub fn reader<T>(&mut self) -> Result<Reader<'_, T>>
+    where
+        T: Sample,
+    {
+        let channels = self.prepare::<T>(Stream::Capture)?;
+        Ok(unsafe { Reader::new(self, channels) })
+    }
+
+    cfg_poll_driver! {
+        /// Construct a checked async writer for the given sample type.
+        ///
+        /// The device should be opened in nonblocking mode, like through
+        /// [Handle::open_nonblocking], or writes will block the runtime.
+        ///
+        /// # Panics
+        ///
+        /// Panics unless called inside of the audio runtime.
+        ///
+        /// # Examples
+        ///
+        /// ```no_run
+        /// use audio_device::sndio;
+        ///
+        /// async fn play() -> anyhow::Result<()> {
+        ///     let mut handle = sndio::Handle::open_default_nonblocking(sndio::Stream::Playback)?;
+        ///
+        ///     let mut parameters = sndio::Parameters::new();
+        ///     parameters.set_sample::<i16>();
+        ///     parameters.set_play_channels(2);
+        ///     handle.set_parameters(&parameters)?;
+        ///
+        ///     let samples = [0i16; 2048];
+        ///
+        ///     let mut writer = handle.async_writer::<i16>()?;
+        ///     writer.write_interleaved(audio::wrap::interleaved(&samples[..], 2)).await?;
+        ///     Ok(())
+        /// }
+        ///
+        /// # #[tokio::main] async fn main() -> anyhow::Result<()> {
+        /// let runtime = audio_device::runtime::Runtime::new()?;
+        /// let bg = ste::spawn();
+        /// bg.submit_async(runtime.wrap(play())).await?;
+        /// bg.join();
+        /// # Ok(()) }
+        /// ```
+        pub fn async_writer<T>(&mut self) -> Result<crate::sndio::AsyncWriter<'_, T>>
+        where
+            T: Sample,
+        {
+            let channels = self.prepare::<T>(Stream::Playback)?;
+            let pollfd = self.pollfd(c::POLLOUT)?;
+            unsafe { crate::sndio::AsyncWriter::new(self, pollfd, channels) }
+        }
+
+        /// Construct a checked async reader for the given sample type.
+        ///
+        /// The device should be opened in nonblocking mode, like through
+        /// [Handle::open_nonblocking], or reads will block the runtime.
+        ///
+        /// # Panics
+        ///
+        /// Panics unless called inside of the audio runtime.
+        pub fn async_reader<T>(&mut self) -> Result<crate::sndio::AsyncReader<'_, T>>
+        where
+            T: Sample,
+        {
+            let channels = self.prepare::<T>(Stream::Capture)?;
+            let pollfd = self.pollfd(c::POLLIN)?;
+            unsafe { crate::sndio::AsyncReader::new(self, pollfd, channels) }
+        }
+
+        /// Fill in the poll descriptor of the device for the given events.
+        ///
+        /// Errors with [Error::MissingPollFds] unless the device uses exactly
+        /// one descriptor.
+        pub(super) fn pollfd(&mut self, events: c::c_short) -> Result<c::pollfd> {
+            unsafe {
+                if sndio::sio_nfds(self.handle.as_ptr()) != 1 {
+                    return Err(Error::MissingPollFds);
+                }
+
+                let mut pollfd = c::pollfd {
+                    fd: -1,
+                    events: 0,
+                    revents: 0,
+                };
+
+                if sndio::sio_pollfd(self.handle.as_ptr(), &mut pollfd, events as c::c_int) != 1 {
+                    return Err(self.failed("sio_pollfd"));
+                }
+
+                Ok(pollfd)
+            }
+        }
+
+        /// Translate the events returned for the given poll descriptor into
+        /// the events of the device.
+        pub(super) fn revents(&mut self, pollfd: &mut c::pollfd) -> c::c_short {
+            unsafe { sndio::sio_revents(self.handle.as_ptr(), pollfd) as c::c_short }
+        }
+    }
+
+    /// Write raw bytes to the device, returning the number of bytes written.
+    ///
+    /// A return value of zero means that the write would block.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must be valid for reads of `len` bytes.
+    pub(super) unsafe fn write_raw(&mut self, ptr: *const c::c_void, len: usize) -> Result<usize> {
+        let n = sndio::sio_write(self.handle.as_ptr(), ptr, len);
+
+        if n == 0 && self.is_eof() {
+            return Err(Error::Eof);
+        }
+
+        self.transferred += n as i64;
+        Ok(n)
+    }
+
+    /// Read raw bytes from the device, returning the number of bytes read.
+    ///
+    /// A return value of zero means that the read would block.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must be valid for writes of `len` bytes.
+    pub(super) unsafe fn read_raw(&mut self, ptr: *mut c::c_void, len: usize) -> Result<usize> {
+        let n = sndio::sio_read(self.handle.as_ptr(), ptr, len);
+
+        if n == 0 && self.is_eof() {
+            return Err(Error::Eof);
+        }
+
+        self.transferred += n as i64;
+        Ok(n)
+    }
+
+    /// Check that the device can be used in the given direction with the given
+    /// sample type, and start it, returning the number of channels to use.
+    fn prepare<T>(&mut self, expected: Stream) -> Result<usize>
+    where
+        T: Sample,
+    {
+        if self.stream != expected {
+            return Err(Error::StreamMismatch {
+                actual: self.stream,
+                expected,
+            });
+        }
+
+        // NB: here we check that `T` is appropriate for the current
+        // parameters.
+        if !T::test(&self.parameters) {
+            return Err(Error::FormatMismatch {
+                ty: T::describe(),
+                bits: self.parameters.bits(),
+                signed: self.parameters.is_signed(),
+            });
+        }
+
+        let channels = match self.stream {
+            Stream::Playback => self.parameters.play_channels(),
+            Stream::Capture => self.parameters.record_channels(),
+        };
+
+        self.start()?;
+        Ok(channels as usize)
+    }
+
+    fn get_parameters(&mut self) -> Result<Parameters> {
+        let mut parameters = Parameters::new();
+
+        unsafe {
+            if sndio::sio_getpar(self.handle.as_ptr(), &mut parameters.raw) == 0 {
+                return Err(Error::Failed("sio_getpar"));
+            }
+        }
+
+        Ok(parameters)
+    }
+
+    /// Construct the error for a failed call, which is [Error::Eof] if the
+    /// failure left the device in an unrecoverable state.
+    fn failed(&self, call: &'static str) -> Error {
+        if self.is_eof() {
+            Error::Eof
+        } else {
+            Error::Failed(call)
+        }
+    }
+}
+
+impl Drop for Handle {
+    fn drop(&mut self) {
+        unsafe {
+            sndio::sio_close(self.handle.as_ptr());
+        }
+    }
+}
+
+// Safety: the handle is only accessed through a mutable reference, and sndio
+// handles aren't tied to the thread which opened them.
+unsafe impl Send for Handle {}
+
+/// Callback invoked by sndio whenever the hardware has played or recorded
+/// frames.
+unsafe extern "C" fn onmove_callback(arg: *mut c::c_void, delta: c::c_int) {
+    let moved = &*(arg as *const Cell<i64>);
+    moved.set(moved.get() + delta as i64);
+}
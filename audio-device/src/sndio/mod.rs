@@ -0,0 +1,100 @@
+//! An idiomatic Rust sndio interface, as used by OpenBSD.
+// Documentation: https://sndio.org/
+
+use crate::unix::Errno;
+use thiserror::Error;
+
+mod handle;
+pub(crate) use self::handle::device_infos;
+pub use self::handle::{Handle, Stream};
+
+mod parameters;
+pub use self::parameters::Parameters;
+
+mod sample;
+pub use self::sample::Sample;
+
+mod writer;
+pub use self::writer::Writer;
+
+mod reader;
+pub use self::reader::Reader;
+
+cfg_poll_driver! {
+    mod async_writer;
+    pub use self::async_writer::AsyncWriter;
+
+    mod async_reader;
+    pub use self::async_reader::AsyncReader;
+}
+
+/// Errors that can be raised by the sndio layer.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// System error.
+    #[error("system error: {0}")]
+    Sys(#[from] Errno),
+    /// An error raised by the audio runtime, like when it has been shut down.
+    #[error("runtime error: {0}")]
+    Runtime(#[from] crate::runtime::Error),
+    /// A call to the sndio library failed.
+    ///
+    /// sndio doesn't report why calls fail, so only the name of the failed
+    /// call is available.
+    #[error("call to `{0}` failed")]
+    Failed(&'static str),
+    /// The device is in an unrecoverable error state, like when it has been
+    /// disconnected.
+    #[error("device is in an unrecoverable error state")]
+    Eof,
+    /// Error raised when there's a format mismatch between the device and the
+    /// type attempting to be used with it.
+    #[error("type `{ty}` is not appropriate to use with a {bits}-bit format (signed = {signed})")]
+    FormatMismatch {
+        /// A description of the type expected.
+        ty: &'static str,
+        /// The number of bits per sample of the device.
+        bits: u32,
+        /// If the samples of the device are signed.
+        signed: bool,
+    },
+    /// Error raised when there's a channel count mismatch between the device
+    /// and the buffer attempting to be used with it.
+    #[error("mismatch in number of channels in buffer; actual = {actual}, expected = {expected}")]
+    ChannelsMismatch {
+        /// The actual number of channels.
+        actual: usize,
+        /// The expected number of channels.
+        expected: usize,
+    },
+    /// Error raised when a device is used in a direction it wasn't opened for.
+    #[error("device is opened for `{actual}`, expected `{expected}`")]
+    StreamMismatch {
+        /// The stream the device was opened for.
+        actual: Stream,
+        /// The stream which was expected.
+        expected: Stream,
+    },
+    /// The device doesn't use exactly one poll descriptor, which is required
+    /// to drive it asynchronously.
+    #[error("device is not pollable")]
+    MissingPollFds,
+}
+
+impl Error {
+    pub(crate) fn kind(&self) -> crate::ErrorKind {
+        use crate::ErrorKind;
+
+        match self {
+            Self::Sys(errno) => errno.kind(),
+            Self::Runtime(error) => error.kind(),
+            Self::Eof => ErrorKind::Disconnected,
+            Self::FormatMismatch { .. } => ErrorKind::UnsupportedFormat,
+            Self::ChannelsMismatch { .. } | Self::StreamMismatch { .. } => ErrorKind::InvalidInput,
+            Self::Failed(..) | Self::MissingPollFds => ErrorKind::Other,
+        }
+    }
+}
+
+/// Helper result wrapper.
+pub type Result<T, E = Error> = ::std::result::Result<T, E>;
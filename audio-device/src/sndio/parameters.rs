@@ -0,0 +1,174 @@
+use crate::sndio::Sample;
+use audio_core::{Endian, SampleFormat};
+use sndio_sys as sndio;
+use std::fmt;
+use std::mem;
+
+/// The parameters of a sndio device, like its encoding, channels and rate.
+///
+/// Parameters start out with every field unset, in which case the device
+/// picks a value. The parameters picked by the device are returned by
+/// [Handle::set_parameters][super::Handle::set_parameters].
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::sndio;
+///
+/// let mut parameters = sndio::Parameters::new();
+/// parameters.set_sample::<i16>();
+/// parameters.set_play_channels(2);
+/// parameters.set_rate(48000);
+/// ```
+pub struct Parameters {
+    pub(super) raw: sndio::sio_par,
+}
+
+impl Parameters {
+    /// Construct parameters where every field is unset.
+    pub fn new() -> Self {
+        unsafe {
+            let mut raw = mem::MaybeUninit::<sndio::sio_par>::uninit();
+            sndio::sio_initpar(raw.as_mut_ptr());
+
+            Self {
+                raw: raw.assume_init(),
+            }
+        }
+    }
+
+    /// Set the encoding to match the given sample type.
+    pub fn set_sample<T>(&mut self)
+    where
+        T: Sample,
+    {
+        self.raw.bits = T::BITS;
+        self.raw.bps = mem::size_of::<T>() as u32;
+        self.raw.sig = T::SIGNED as u32;
+        self.raw.le = cfg!(target_endian = "little") as u32;
+    }
+
+    /// The number of significant bits per sample.
+    pub fn bits(&self) -> u32 {
+        self.raw.bits
+    }
+
+    /// The number of bytes per sample.
+    pub fn bytes_per_sample(&self) -> u32 {
+        self.raw.bps
+    }
+
+    /// Test if samples are signed.
+    pub fn is_signed(&self) -> bool {
+        self.raw.sig != 0
+    }
+
+    /// Test if samples are little endian.
+    pub fn is_little_endian(&self) -> bool {
+        self.raw.le != 0
+    }
+
+    /// Get the backend-agnostic [SampleFormat] corresponding to the encoding,
+    /// if there is one.
+    pub fn sample_format(&self) -> Option<SampleFormat> {
+        let endian = if self.is_little_endian() {
+            Endian::Little
+        } else {
+            Endian::Big
+        };
+
+        let msb = self.raw.msb != 0;
+
+        let format = match (self.is_signed(), self.bits(), self.bytes_per_sample()) {
+            (false, 8, 1) => SampleFormat::U8,
+            (true, 16, 2) => SampleFormat::I16(endian),
+            (true, 24, 3) => SampleFormat::I24Packed(endian),
+            // NB: samples which are aligned to the most significant bits are
+            // laid out like full scale 32-bit samples.
+            (true, 24, 4) if !msb => SampleFormat::I24(endian),
+            (true, 24, 4) | (true, 32, 4) => SampleFormat::I32(endian),
+            _ => return None,
+        };
+
+        Some(format)
+    }
+
+    /// The number of channels which are recorded.
+    pub fn record_channels(&self) -> u32 {
+        self.raw.rchan
+    }
+
+    /// Set the number of channels which are recorded.
+    pub fn set_record_channels(&mut self, channels: u32) {
+        self.raw.rchan = channels;
+    }
+
+    /// The number of channels which are played.
+    pub fn play_channels(&self) -> u32 {
+        self.raw.pchan
+    }
+
+    /// Set the number of channels which are played.
+    pub fn set_play_channels(&mut self, channels: u32) {
+        self.raw.pchan = channels;
+    }
+
+    /// The sample rate in frames per second.
+    pub fn rate(&self) -> u32 {
+        self.raw.rate
+    }
+
+    /// Set the sample rate in frames per second.
+    pub fn set_rate(&mut self, rate: u32) {
+        self.raw.rate = rate;
+    }
+
+    /// The end-to-end buffer size in frames, which determines the latency.
+    pub fn buffer_size(&self) -> u32 {
+        self.raw.bufsz
+    }
+
+    /// Set the end-to-end buffer size in frames.
+    pub fn set_buffer_size(&mut self, frames: u32) {
+        self.raw.bufsz = frames;
+    }
+
+    /// The block size in frames, which the buffer size is a multiple of.
+    /// Reading and writing in multiples of the block size is optimal.
+    pub fn block_size(&self) -> u32 {
+        self.raw.round
+    }
+
+    /// Set the preferred block size in frames.
+    pub fn set_block_size(&mut self, frames: u32) {
+        self.raw.round = frames;
+    }
+}
+
+impl Default for Parameters {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clone for Parameters {
+    fn clone(&self) -> Self {
+        Self { raw: self.raw }
+    }
+}
+
+impl fmt::Debug for Parameters {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Parameters")
+            .field("bits", &self.bits())
+            .field("bytes_per_sample", &self.bytes_per_sample())
+            .field("signed", &self.is_signed())
+            .field("little_endian", &self.is_little_endian())
+            .field("record_channels", &self.record_channels())
+            .field("play_channels", &self.play_channels())
+            .field("rate", &self.rate())
+            .field("buffer_size", &self.buffer_size())
+            .field("block_size", &self.block_size())
+            .finish()
+    }
+}
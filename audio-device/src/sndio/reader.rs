@@ -0,0 +1,77 @@
+use core::marker;
+
+use crate::buf_io;
+use crate::libc as c;
+use crate::sndio::{Error, Handle, Result};
+
+/// An interleaved type-checked blocking reader for a [Handle].
+///
+/// See [Handle::reader].
+pub struct Reader<'a, T> {
+    handle: &'a mut Handle,
+    channels: usize,
+    _marker: marker::PhantomData<T>,
+}
+
+impl<'a, T> Reader<'a, T> {
+    /// Construct a new reader surrounding the given device.
+    ///
+    /// # Safety
+    ///
+    /// This constructor assumes that the caller has checked that type `T` is
+    /// appropriate for reading from the given device.
+    pub(super) unsafe fn new(handle: &'a mut Handle, channels: usize) -> Self {
+        Self {
+            handle,
+            channels,
+            _marker: marker::PhantomData,
+        }
+    }
+
+    /// Read into an interleaved buffer, blocking until its remaining frames
+    /// have been filled with recorded audio.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio::WriteBuf;
+    /// use audio_device::sndio;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut handle = sndio::Handle::open_default(sndio::Stream::Capture)?;
+    ///
+    /// let mut parameters = sndio::Parameters::new();
+    /// parameters.set_sample::<i16>();
+    /// parameters.set_record_channels(2);
+    /// handle.set_parameters(&parameters)?;
+    ///
+    /// let mut samples = [0i16; 2048];
+    /// let mut buf = audio::wrap::interleaved(&mut samples[..], 2);
+    ///
+    /// let mut reader = handle.reader::<i16>()?;
+    /// reader.read_interleaved(&mut buf)?;
+    /// assert!(!buf.has_remaining_mut());
+    /// # Ok(()) }
+    /// ```
+    pub fn read_interleaved<B>(&mut self, mut buf: B) -> Result<()>
+    where
+        B: audio_core::Buf<Sample = T>
+            + audio_core::WriteBuf
+            + audio_core::InterleavedBufMut<Sample = T>,
+    {
+        if buf.channels() != self.channels {
+            return Err(Error::ChannelsMismatch {
+                actual: buf.channels(),
+                expected: self.channels,
+            });
+        }
+
+        let handle = &mut *self.handle;
+
+        // NB: a blocking read only comes up short if the device fails, which
+        // is reported as an error by `read_raw`.
+        buf_io::read(&mut buf, self.channels, |ptr, len| unsafe {
+            handle.read_raw(ptr as *mut c::c_void, len)
+        })
+    }
+}
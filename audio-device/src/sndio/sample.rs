@@ -0,0 +1,53 @@
+use crate::sndio::Parameters;
+use std::mem;
+
+/// Trait used to designate types which are sample-appropriate for a
+/// [Handle][super::Handle].
+///
+/// Native types are appropriate for encodings in the native endianness where
+/// every bit of the sample is significant.
+///
+/// # Safety
+///
+/// This trait is unsafe to implement, because an incorrectly implemented format
+/// test might have safety implications.
+pub unsafe trait Sample: Sized {
+    /// The number of significant bits of the sample.
+    const BITS: u32;
+
+    /// If the sample is signed.
+    const SIGNED: bool;
+
+    /// Test if the given parameters are appropriate for this sample type.
+    fn test(parameters: &Parameters) -> bool {
+        let size = mem::size_of::<Self>() as u32;
+
+        parameters.bits() == Self::BITS
+            && parameters.bytes_per_sample() == size
+            && parameters.is_signed() == Self::SIGNED
+            && (size == 1 || parameters.is_little_endian() == cfg!(target_endian = "little"))
+    }
+
+    /// A static description of the sample type.
+    fn describe() -> &'static str;
+}
+
+macro_rules! implement {
+    ($ty:ty, $signed:expr) => {
+        unsafe impl Sample for $ty {
+            const BITS: u32 = (mem::size_of::<$ty>() * 8) as u32;
+            const SIGNED: bool = $signed;
+
+            fn describe() -> &'static str {
+                stringify!($ty)
+            }
+        }
+    };
+}
+
+implement!(u8, false);
+implement!(i8, true);
+implement!(u16, false);
+implement!(i16, true);
+implement!(u32, false);
+implement!(i32, true);
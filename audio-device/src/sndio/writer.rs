@@ -0,0 +1,62 @@
+use core::marker;
+
+use crate::buf_io;
+use crate::libc as c;
+use crate::sndio::{Error, Handle, Result};
+
+/// An interleaved type-checked blocking writer for a [Handle].
+///
+/// See [Handle::writer].
+pub struct Writer<'a, T> {
+    handle: &'a mut Handle,
+    channels: usize,
+    _marker: marker::PhantomData<T>,
+}
+
+impl<'a, T> Writer<'a, T> {
+    /// Construct a new writer surrounding the given device.
+    ///
+    /// # Safety
+    ///
+    /// This constructor assumes that the caller has checked that type `T` is
+    /// appropriate for writing to the given device.
+    pub(super) unsafe fn new(handle: &'a mut Handle, channels: usize) -> Self {
+        Self {
+            handle,
+            channels,
+            _marker: marker::PhantomData,
+        }
+    }
+
+    /// Get the current playback delay in frames.
+    ///
+    /// See [Handle::delay].
+    pub fn delay(&self) -> i64 {
+        self.handle.delay()
+    }
+
+    /// Write an interleaved buffer, blocking until all of its frames have been
+    /// accepted by the device.
+    pub fn write_interleaved<B>(&mut self, mut buf: B) -> Result<()>
+    where
+        B: audio_core::Buf<Sample = T>
+            + audio_core::ReadBuf
+            + audio_core::ExactSizeBuf
+            + audio_core::InterleavedBuf,
+    {
+        if buf.channels() != self.channels {
+            return Err(Error::ChannelsMismatch {
+                actual: buf.channels(),
+                expected: self.channels,
+            });
+        }
+
+        let handle = &mut *self.handle;
+
+        // NB: a blocking write only comes up short if the device fails, which
+        // is reported as an error by `write_raw`.
+        buf_io::write(&mut buf, self.channels, |ptr, len| unsafe {
+            handle.write_raw(ptr as *const c::c_void, len)
+        })
+    }
+}
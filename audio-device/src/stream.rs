@@ -0,0 +1,304 @@
+//! Backend-agnostic audio streams.
+//!
+//! The [OutputStream], [InputStream] and [DuplexStream] traits are
+//! implemented by each backend, which allows for writing portable applications
+//! which are generic over the stream in use. `DefaultOutputStream`,
+//! `DefaultInputStream` and `DefaultDuplexStream` are aliases for the streams
+//! of the backend which is enabled.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use audio_device::stream::{OutputStream, StreamSpec};
+//!
+//! fn play<S>(spec: StreamSpec) -> Result<(), S::Error>
+//! where
+//!     S: OutputStream<Sample = f32>,
+//! {
+//!     let mut stream = S::open_default(spec)?;
+//!     let spec = stream.spec();
+//!
+//!     let samples = vec![0.0f32; spec.channels * 1024];
+//!     stream.write(audio::wrap::interleaved(&samples[..], spec.channels))?;
+//!     Ok(())
+//! }
+//! ```
+
+use std::time::Duration;
+
+use audio_core::{ExactSizeBuf, InterleavedBuf, InterleavedBufMut, ReadBuf, WriteBuf};
+
+/// The specification of a stream.
+///
+/// When opening a stream this is the desired specification, which the
+/// backend might not be able to achieve. The achieved specification is
+/// available through [OutputStream::spec] and [InputStream::spec].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct StreamSpec {
+    /// The number of interleaved channels.
+    pub channels: usize,
+    /// The sample rate in frames per second.
+    pub sample_rate: u32,
+}
+
+impl StreamSpec {
+    /// Construct a new stream specification.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio_device::stream::StreamSpec;
+    ///
+    /// let spec = StreamSpec::new(2, 48000);
+    /// assert_eq!(spec.channels, 2);
+    /// assert_eq!(spec.sample_rate, 48000);
+    /// ```
+    pub const fn new(channels: usize, sample_rate: u32) -> Self {
+        Self {
+            channels,
+            sample_rate,
+        }
+    }
+}
+
+/// A stream which plays audio through an output device.
+pub trait OutputStream: Sized {
+    /// The type of a single sample written to the stream.
+    type Sample;
+
+    /// The error raised by the stream.
+    type Error;
+
+    /// Open a stream on the default output device with the desired
+    /// specification.
+    fn open_default(spec: StreamSpec) -> Result<Self, Self::Error>;
+
+    /// The specification which was achieved when the stream was opened.
+    fn spec(&self) -> StreamSpec;
+
+    /// Get the latency of the frames which have been written but not yet
+    /// played by the device.
+    fn latency(&mut self) -> Result<Duration, Self::Error>;
+
+    /// Write the remaining frames of an interleaved buffer, blocking until
+    /// all of them have been accepted by the device.
+    ///
+    /// This errors if the number of channels in the buffer doesn't match the
+    /// number of channels of the stream.
+    fn write<B>(&mut self, buf: B) -> Result<(), Self::Error>
+    where
+        B: ReadBuf + ExactSizeBuf<Sample = Self::Sample> + InterleavedBuf<Sample = Self::Sample>;
+}
+
+/// A stream which captures audio from an input device.
+pub trait InputStream: Sized {
+    /// The type of a single sample read from the stream.
+    type Sample;
+
+    /// The error raised by the stream.
+    type Error;
+
+    /// Open a stream on the default input device with the desired
+    /// specification.
+    fn open_default(spec: StreamSpec) -> Result<Self, Self::Error>;
+
+    /// The specification which was achieved when the stream was opened.
+    fn spec(&self) -> StreamSpec;
+
+    /// Get the latency of the frames which have been captured by the device
+    /// but not yet read.
+    fn latency(&mut self) -> Result<Duration, Self::Error>;
+
+    /// Fill the remaining frames of an interleaved buffer, blocking until
+    /// enough audio has been captured.
+    ///
+    /// This errors if the number of channels in the buffer doesn't match the
+    /// number of channels of the stream.
+    fn read<B>(&mut self, buf: B) -> Result<(), Self::Error>
+    where
+        B: WriteBuf
+            + ExactSizeBuf<Sample = Self::Sample>
+            + InterleavedBufMut<Sample = Self::Sample>;
+}
+
+/// A stream which both captures audio from an input device and plays audio
+/// through an output device, like for real-time effects or voice chat.
+///
+/// The clocks of the two devices typically drift apart over time, so the
+/// stream provides a [resample ratio][DuplexStream::resample_ratio] hint which
+/// can be used to compensate for it.
+pub trait DuplexStream: Sized {
+    /// The type of a single sample of the stream.
+    type Sample;
+
+    /// The error raised by the stream.
+    type Error;
+
+    /// Open a stream on the default input and output devices with the desired
+    /// specification.
+    fn open_default(spec: StreamSpec) -> Result<Self, Self::Error>;
+
+    /// The specification which was achieved for the input device.
+    fn input_spec(&self) -> StreamSpec;
+
+    /// The specification which was achieved for the output device.
+    fn output_spec(&self) -> StreamSpec;
+
+    /// Fill the remaining frames of an interleaved buffer with captured
+    /// audio, see [InputStream::read].
+    fn read<B>(&mut self, buf: B) -> Result<(), Self::Error>
+    where
+        B: WriteBuf
+            + ExactSizeBuf<Sample = Self::Sample>
+            + InterleavedBufMut<Sample = Self::Sample>;
+
+    /// Write the remaining frames of an interleaved buffer to the output
+    /// device, see [OutputStream::write].
+    fn write<B>(&mut self, buf: B) -> Result<(), Self::Error>
+    where
+        B: ReadBuf + ExactSizeBuf<Sample = Self::Sample> + InterleavedBuf<Sample = Self::Sample>;
+
+    /// Measure the positions of the streams and get the number of input
+    /// frames which are captured for every output frame which is played.
+    ///
+    /// Captured audio should be resampled by this ratio before it's played to
+    /// keep the amount of buffered audio constant. See [DriftEstimator].
+    fn resample_ratio(&mut self) -> Result<f64, Self::Error>;
+}
+
+/// Estimates the drift between the clocks of an input and an output device.
+///
+/// The estimator is fed the positions of both devices in frames, sampled at
+/// the same time, and estimates the number of input frames which are
+/// captured for every output frame which is played.
+///
+/// # Examples
+///
+/// ```
+/// use audio_device::stream::DriftEstimator;
+///
+/// let mut drift = DriftEstimator::new(48000, 48000);
+/// assert_eq!(drift.ratio(), 1.0);
+///
+/// drift.update(1000, 2000);
+/// // The input device runs slightly faster than the output device.
+/// let ratio = drift.update(1000 + 48010, 2000 + 48000);
+/// assert!(ratio > 1.0 && ratio < 1.001);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DriftEstimator {
+    origin: Option<(u64, u64)>,
+    nominal: f64,
+    ratio: f64,
+}
+
+impl DriftEstimator {
+    /// Construct a new estimator for devices with the given nominal sample
+    /// rates, which is the ratio used until drift has been measured.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio_device::stream::DriftEstimator;
+    ///
+    /// let drift = DriftEstimator::new(44100, 48000);
+    /// assert_eq!(drift.ratio(), 44100.0 / 48000.0);
+    /// ```
+    pub fn new(input_rate: u32, output_rate: u32) -> Self {
+        let nominal = if input_rate == 0 || output_rate == 0 {
+            1.0
+        } else {
+            input_rate as f64 / output_rate as f64
+        };
+
+        Self {
+            origin: None,
+            nominal,
+            ratio: nominal,
+        }
+    }
+
+    /// Update the estimate with the current position of the input and the
+    /// output device in frames, returning the updated ratio.
+    ///
+    /// The first update establishes the origin which later positions are
+    /// measured against.
+    pub fn update(&mut self, input: u64, output: u64) -> f64 {
+        let (input_origin, output_origin) = *self.origin.get_or_insert((input, output));
+
+        let input = input.saturating_sub(input_origin);
+        let output = output.saturating_sub(output_origin);
+
+        if input != 0 && output != 0 {
+            self.ratio = input as f64 / output as f64;
+        }
+
+        self.ratio
+    }
+
+    /// The current estimate of the number of input frames which are captured
+    /// for every output frame which is played.
+    pub fn ratio(&self) -> f64 {
+        self.ratio
+    }
+
+    /// Reset the estimator, such as after the streams have been restarted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio_device::stream::DriftEstimator;
+    ///
+    /// let mut drift = DriftEstimator::new(48000, 48000);
+    /// drift.update(0, 0);
+    /// drift.update(1100, 1000);
+    /// assert_eq!(drift.ratio(), 1.1);
+    ///
+    /// drift.reset();
+    /// assert_eq!(drift.ratio(), 1.0);
+    /// ```
+    pub fn reset(&mut self) {
+        self.origin = None;
+        self.ratio = self.nominal;
+    }
+}
+
+cfg_wasapi! {
+    /// The output stream of the enabled backend.
+    pub type DefaultOutputStream<T> = crate::wasapi::OutputStream<T>;
+    /// The input stream of the enabled backend.
+    pub type DefaultInputStream<T> = crate::wasapi::InputStream<T>;
+    /// The duplex stream of the enabled backend.
+    pub type DefaultDuplexStream<T> = crate::wasapi::DuplexStream<T>;
+}
+
+/// The output stream of the enabled backend.
+#[cfg(all(feature = "alsa", not(feature = "wasapi")))]
+#[cfg_attr(docsrs, doc(cfg(feature = "alsa")))]
+pub type DefaultOutputStream<T> = crate::alsa::OutputStream<T>;
+
+/// The input stream of the enabled backend.
+#[cfg(all(feature = "alsa", not(feature = "wasapi")))]
+#[cfg_attr(docsrs, doc(cfg(feature = "alsa")))]
+pub type DefaultInputStream<T> = crate::alsa::InputStream<T>;
+
+/// The duplex stream of the enabled backend.
+#[cfg(all(feature = "alsa", not(feature = "wasapi")))]
+#[cfg_attr(docsrs, doc(cfg(feature = "alsa")))]
+pub type DefaultDuplexStream<T> = crate::alsa::DuplexStream<T>;
+
+/// The output stream of the enabled backend.
+#[cfg(all(feature = "coreaudio", not(any(feature = "wasapi", feature = "alsa"))))]
+#[cfg_attr(docsrs, doc(cfg(feature = "coreaudio")))]
+pub type DefaultOutputStream<T> = crate::coreaudio::OutputStream<T>;
+
+/// The input stream of the enabled backend.
+#[cfg(all(feature = "coreaudio", not(any(feature = "wasapi", feature = "alsa"))))]
+#[cfg_attr(docsrs, doc(cfg(feature = "coreaudio")))]
+pub type DefaultInputStream<T> = crate::coreaudio::InputStream<T>;
+
+/// The duplex stream of the enabled backend.
+#[cfg(all(feature = "coreaudio", not(any(feature = "wasapi", feature = "alsa"))))]
+#[cfg_attr(docsrs, doc(cfg(feature = "coreaudio")))]
+pub type DefaultDuplexStream<T> = crate::coreaudio::DuplexStream<T>;
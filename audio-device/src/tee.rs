@@ -0,0 +1,216 @@
+//! Inspecting what's written to an output stream.
+//!
+//! A [Tee] wraps any [OutputStream] and duplicates every frame written to it
+//! into a [Sink], like a file, a ring buffer or a level meter. It also records
+//! the timing of each write, which helps when debugging underruns.
+//!
+//! # Examples
+//!
+//! ```
+//! use audio_device::null;
+//! use audio_device::stream::{OutputStream, StreamSpec};
+//! use audio_device::tee::Tee;
+//!
+//! # fn main() -> anyhow::Result<()> {
+//! let stream = null::OutputStream::<f32>::open_default(StreamSpec::new(2, 48000))?;
+//! let mut tee = Tee::new(stream, Vec::new());
+//!
+//! let samples = vec![0.5f32; 2 * 256];
+//! tee.write(audio::wrap::interleaved(&samples[..], 2))?;
+//! tee.write(audio::wrap::interleaved(&samples[..], 2))?;
+//!
+//! assert_eq!(tee.sink().len(), 2 * 512);
+//! assert_eq!(tee.timings().count(), 2);
+//! assert_eq!(tee.frames(), 512);
+//! # Ok(()) }
+//! ```
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::stream::{OutputStream, StreamSpec};
+
+/// The default number of write timings which are retained by a [Tee].
+const DEFAULT_HISTORY: usize = 1024;
+
+/// A destination for the frames written through a [Tee].
+///
+/// This is implemented for vectors, which collect all written samples, and
+/// for closures which are called with the number of channels and the
+/// interleaved samples of each write.
+///
+/// Sinks are called on the thread performing the write, right before the
+/// frames are handed to the wrapped stream. So they should be fast to avoid
+/// causing the underruns they might be used to debug.
+///
+/// # Examples
+///
+/// ```
+/// use audio_device::tee::Sink;
+///
+/// let mut peak = 0.0f32;
+///
+/// let mut meter = |_: usize, samples: &[f32]| {
+///     for s in samples {
+///         peak = peak.max(s.abs());
+///     }
+/// };
+///
+/// meter.write(2, &[0.1, -0.5, 0.2, 0.3]);
+/// assert_eq!(peak, 0.5);
+/// ```
+pub trait Sink<T> {
+    /// Receive the interleaved samples of a single write.
+    fn write(&mut self, channels: usize, samples: &[T]);
+}
+
+impl<T> Sink<T> for Vec<T>
+where
+    T: Copy,
+{
+    fn write(&mut self, _: usize, samples: &[T]) {
+        self.extend_from_slice(samples);
+    }
+}
+
+impl<T, F> Sink<T> for F
+where
+    F: FnMut(usize, &[T]),
+{
+    fn write(&mut self, channels: usize, samples: &[T]) {
+        self(channels, samples)
+    }
+}
+
+/// The timing of a single write through a [Tee].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WriteTiming {
+    /// When the write started.
+    pub start: Instant,
+    /// How long the wrapped stream took to accept the write.
+    pub duration: Duration,
+    /// The time since the previous write started, if any.
+    pub interval: Option<Duration>,
+    /// The number of frames written.
+    pub frames: usize,
+}
+
+/// A wrapper around an [OutputStream] which duplicates written frames into a
+/// [Sink] and records the timing of each write.
+///
+/// See the [module level documentation][self].
+pub struct Tee<S, K> {
+    stream: S,
+    sink: K,
+    timings: VecDeque<WriteTiming>,
+    history: usize,
+    frames: u64,
+}
+
+impl<S, K> Tee<S, K>
+where
+    S: OutputStream,
+    K: Sink<S::Sample>,
+{
+    /// Wrap the given stream, duplicating written frames into `sink`.
+    pub fn new(stream: S, sink: K) -> Self {
+        Self::with_history(stream, sink, DEFAULT_HISTORY)
+    }
+
+    /// Wrap the given stream, retaining the timings of the last `history`
+    /// writes.
+    pub fn with_history(stream: S, sink: K, history: usize) -> Self {
+        Self {
+            stream,
+            sink,
+            timings: VecDeque::with_capacity(history),
+            history,
+            frames: 0,
+        }
+    }
+
+    /// The specification of the wrapped stream.
+    pub fn spec(&self) -> StreamSpec {
+        self.stream.spec()
+    }
+
+    /// Get the latency of the wrapped stream.
+    pub fn latency(&mut self) -> Result<Duration, S::Error> {
+        self.stream.latency()
+    }
+
+    /// Write an interleaved buffer to the sink and to the wrapped stream.
+    pub fn write<B>(&mut self, buf: B) -> Result<(), S::Error>
+    where
+        B: audio_core::ReadBuf
+            + audio_core::ExactSizeBuf<Sample = S::Sample>
+            + audio_core::InterleavedBuf<Sample = S::Sample>,
+    {
+        let channels = buf.channels();
+        let samples = buf.as_interleaved();
+        let len = usize::min(buf.remaining() * channels, samples.len());
+        let frames = len.checked_div(channels).unwrap_or_default();
+        self.sink.write(channels, &samples[..len]);
+
+        let start = Instant::now();
+        let result = self.stream.write(buf);
+        let duration = start.elapsed();
+
+        if result.is_ok() {
+            self.frames += frames as u64;
+        }
+
+        let interval = self.timings.back().map(|last| start - last.start);
+
+        if self.history > 0 {
+            if self.timings.len() == self.history {
+                self.timings.pop_front();
+            }
+
+            self.timings.push_back(WriteTiming {
+                start,
+                duration,
+                interval,
+                frames,
+            });
+        }
+
+        result
+    }
+
+    /// The timings of the most recent writes, oldest first.
+    pub fn timings(&self) -> impl Iterator<Item = &WriteTiming> + '_ {
+        self.timings.iter()
+    }
+
+    /// The total number of frames which have been successfully written to the
+    /// wrapped stream.
+    pub fn frames(&self) -> u64 {
+        self.frames
+    }
+
+    /// Access the sink.
+    pub fn sink(&self) -> &K {
+        &self.sink
+    }
+
+    /// Access the sink mutably.
+    pub fn sink_mut(&mut self) -> &mut K {
+        &mut self.sink
+    }
+
+    /// Access the wrapped stream.
+    pub fn stream(&self) -> &S {
+        &self.stream
+    }
+
+    /// Access the wrapped stream mutably.
+    pub fn stream_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+
+    /// Unwrap the stream and the sink.
+    pub fn into_inner(self) -> (S, K) {
+        (self.stream, self.sink)
+    }
+}
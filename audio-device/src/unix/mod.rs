@@ -2,6 +2,8 @@
 
 use std::{fmt, error};
 
+use crate::ErrorKind;
+
 /// A unix error number.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(transparent)]
@@ -9,10 +11,33 @@ pub struct Errno(i32);
 
 impl Errno {
     pub(crate) const EWOULDBLOCK: Self = Self(libc::EWOULDBLOCK);
+    pub(crate) const EPIPE: Self = Self(libc::EPIPE);
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub(crate) const ESTRPIPE: Self = Self(libc::ESTRPIPE);
 
     pub(crate) fn new(value: i32) -> Self {
         Self(value)
     }
+
+    pub(crate) fn value(self) -> i32 {
+        self.0
+    }
+
+    /// Classify the error number.
+    pub(crate) fn kind(self) -> ErrorKind {
+        match self.0 {
+            libc::EAGAIN => ErrorKind::WouldBlock,
+            libc::EPIPE => ErrorKind::Xrun,
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            libc::ESTRPIPE => ErrorKind::Suspended,
+            libc::ENODEV | libc::ENXIO | libc::ESHUTDOWN => ErrorKind::Disconnected,
+            libc::EBUSY => ErrorKind::Busy,
+            libc::EACCES | libc::EPERM => ErrorKind::PermissionDenied,
+            libc::ENOENT => ErrorKind::NotFound,
+            libc::EINVAL => ErrorKind::InvalidInput,
+            _ => ErrorKind::Other,
+        }
+    }
 }
 
 impl fmt::Display for Errno {
@@ -21,8 +46,15 @@ impl fmt::Display for Errno {
             Self::EWOULDBLOCK => {
                 write!(f, "EWOULDBLOCK")
             }
+            Self::EPIPE => {
+                write!(f, "EPIPE")
+            }
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            Self::ESTRPIPE => {
+                write!(f, "ESTRPIPE")
+            }
             errno => {
-                write!(f, "({})", errno)
+                write!(f, "({})", errno.0)
             }
         }
     }
@@ -32,19 +64,42 @@ impl error::Error for Errno {
 }
 
 cfg_poll_driver! {
-    /// Poll flags.
+    /// Poll flags, as used in the `events` and `revents` fields of a
+    /// [pollfd][crate::libc::pollfd].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio_device::unix::PollFlags;
+    ///
+    /// let flags = PollFlags::from_bits_truncate(audio_device::libc::POLLOUT);
+    /// assert!(flags.test(PollFlags::POLLOUT));
+    /// assert!(!flags.test(PollFlags::POLLIN));
+    /// ```
     #[derive(Debug, Clone, Copy)]
     #[repr(transparent)]
     pub struct PollFlags(libc::c_short);
     
     impl PollFlags {
-        pub(crate) const POLLOUT: Self = Self(crate::libc::POLLOUT);
+        /// There is data to read.
+        pub const POLLIN: Self = Self(crate::libc::POLLIN);
+        /// Writing is now possible.
+        pub const POLLOUT: Self = Self(crate::libc::POLLOUT);
+        /// An error condition.
+        pub const POLLERR: Self = Self(crate::libc::POLLERR);
     
-        pub(crate) fn from_bits_truncate(bits: libc::c_short) -> Self {
+        /// Construct poll flags from raw bits.
+        pub fn from_bits_truncate(bits: libc::c_short) -> Self {
             Self(bits)
         }
-    
-        pub(crate) fn test(self, bits: PollFlags) -> bool {
+
+        /// Get the raw bits of the poll flags.
+        pub fn bits(self) -> libc::c_short {
+            self.0
+        }
+
+        /// Test if any of the given flags are set.
+        pub fn test(self, bits: PollFlags) -> bool {
             (self.0 & bits.0) != 0
         }
     }
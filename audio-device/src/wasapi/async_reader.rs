@@ -0,0 +1,161 @@
+use std::future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use crate::loom::sync::Arc;
+use crate::wasapi::capture::Capture;
+use crate::wasapi::{Error, Recovered, Sample};
+use crate::windows::{AsyncEvent, RawEvent};
+
+/// An interleaved type-checked async reader of captured audio.
+///
+/// The reader is woken up through the audio event of the stream, so it must
+/// be used inside of the audio [Runtime][crate::runtime::Runtime].
+///
+/// See [InitializedClient::async_reader][crate::wasapi::InitializedClient::async_reader].
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::wasapi;
+///
+/// async fn capture() -> anyhow::Result<()> {
+///     let client = match wasapi::default_input_client()? {
+///         Some(client) => client,
+///         None => return Ok(()),
+///     };
+///
+///     let config = client.default_client_config()?;
+///     let initialized = client.initialize_async::<f32>(config)?;
+///     let mut reader = initialized.async_reader()?;
+///     client.start()?;
+///
+///     let mut samples = vec![0.0f32; config.channels as usize * 1024];
+///
+///     loop {
+///         let mut buf = audio::wrap::interleaved(&mut samples[..], config.channels as usize);
+///         reader.read_interleaved(&mut buf).await?;
+///     }
+/// }
+///
+/// # #[tokio::main] async fn main() -> anyhow::Result<()> {
+/// let runtime = audio_device::runtime::Runtime::new()?;
+/// let bg = ste::Builder::new().prelude(wasapi::audio_prelude).build()?;
+/// bg.submit_async(runtime.wrap(capture())).await?;
+/// # Ok(()) }
+/// ```
+pub struct AsyncReader<T> {
+    capture: Capture<T>,
+    event: Arc<AsyncEvent>,
+}
+
+impl<T> AsyncReader<T>
+where
+    T: Sample,
+{
+    pub(super) fn new(capture: Capture<T>, event: Arc<AsyncEvent>) -> Self {
+        Self { capture, event }
+    }
+
+    /// Configure if the reader should recover when the device it's capturing
+    /// from is invalidated, such as when a USB device is unplugged.
+    ///
+    /// When enabled, the stream is moved to the new default input device
+    /// with the same configuration and started, after which reading
+    /// continues. Recovery fails with the original error if there's no
+    /// default device or if it doesn't support the same number of channels
+    /// and sample rate. Each recovery is reported through
+    /// [AsyncReader::take_recovered].
+    ///
+    /// Defaults to `false`.
+    pub fn set_recover(&mut self, recover: bool) {
+        self.capture.recover = recover;
+    }
+
+    /// The number of times the reader has recovered from the device being
+    /// invalidated.
+    ///
+    /// See [AsyncReader::set_recover].
+    pub fn recoveries(&self) -> usize {
+        self.capture.recoveries
+    }
+
+    /// Take the most recent recovery from the device being invalidated which
+    /// hasn't been taken yet.
+    ///
+    /// See [AsyncReader::set_recover].
+    pub fn take_recovered(&mut self) -> Option<Recovered> {
+        self.capture.recovered.take()
+    }
+
+    /// Read into an interleaved buffer.
+    ///
+    /// Completes once the remaining frames of the buffer have been filled
+    /// with captured audio. Frames which the device reports as silent are
+    /// filled with [Sample::MID].
+    pub async fn read_interleaved<B>(&mut self, mut buf: B) -> Result<(), Error>
+    where
+        B: audio_core::Buf<Sample = T>
+            + audio_core::WriteBuf
+            + audio_core::InterleavedBufMut<Sample = T>,
+    {
+        future::poll_fn(|cx| self.poll_read_interleaved(cx, &mut buf)).await
+    }
+
+    fn poll_read_interleaved<B>(
+        &mut self,
+        cx: &mut Context<'_>,
+        mut buf: B,
+    ) -> Poll<Result<(), Error>>
+    where
+        B: audio_core::Buf<Sample = T>
+            + audio_core::WriteBuf
+            + audio_core::InterleavedBufMut<Sample = T>,
+    {
+        self.capture.check_channels(&buf)?;
+
+        while buf.has_remaining_mut() {
+            // NB: only wait for the device when there's no packet available,
+            // otherwise we read as much as is available.
+            let event = unsafe { self.event.raw_event() };
+
+            match self.capture.read_packet_or_recover(&mut buf, event)? {
+                Some(0) => break,
+                Some(_) => (),
+                None => match self.event.poll_wait(cx) {
+                    Poll::Ready(result) => result?,
+                    Poll::Pending => return Poll::Pending,
+                },
+            }
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<T> audio_core::AsyncReadBuf for AsyncReader<T>
+where
+    T: Sample,
+{
+    type Sample = T;
+    type Error = Error;
+
+    fn poll_read_buf<B>(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: B,
+    ) -> Poll<Result<(), Error>>
+    where
+        B: audio_core::WriteBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBufMut<Sample = T>,
+    {
+        self.get_mut().poll_read_interleaved(cx, buf)
+    }
+}
+
+// Note: the sample type is only used as a marker.
+impl<T> Unpin for AsyncReader<T> {}
+
+// Safety: thread safety is ensured through tagging with ste::Tag.
+unsafe impl<T> Send for AsyncReader<T> {}
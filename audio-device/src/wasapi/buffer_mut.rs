@@ -1,15 +1,59 @@
 use crate::wasapi::Error;
+use ::audio::buf::interleaved::{IterChannels, IterChannelsMut};
+use ::audio::channel::{InterleavedChannel, InterleavedChannelMut};
+use audio_core::{Buf, BufMut, ExactSizeBuf, InterleavedBuf, InterleavedBufMut, LayoutHint};
 use std::marker;
 use std::ops;
+use std::ptr;
 use std::slice;
 use windows::Win32::Media::Audio as audio;
 
 /// A typed mutable data buffer.
+///
+/// The buffer derefs to the raw interleaved samples, but also implements
+/// [BufMut] and [InterleavedBufMut] so that generic rendering code can write
+/// straight into it.
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio::{BufMut, ChannelMut};
+/// use audio_device::wasapi;
+///
+/// fn render(mut buf: impl BufMut<Sample = f32>) {
+///     for mut channel in buf.iter_channels_mut() {
+///         for sample in channel.iter_mut() {
+///             *sample = 0.0;
+///         }
+///     }
+/// }
+///
+/// # fn main() -> anyhow::Result<()> {
+/// wasapi::audio_prelude();
+///
+/// let client = match wasapi::default_output_client()? {
+///     Some(client) => client,
+///     None => return Ok(()),
+/// };
+///
+/// let config = client.default_client_config()?;
+/// let initialized = client.initialize::<f32>(config)?;
+/// let mut render_client = initialized.render_client()?;
+/// client.start()?;
+///
+/// loop {
+///     let mut buffer = render_client.buffer_mut()?;
+///     render(&mut buffer);
+///     buffer.release()?;
+/// }
+/// # }
+/// ```
 pub struct BufferMut<'a, T> {
     pub(super) tag: ste::Tag,
     pub(super) render_client: &'a mut audio::IAudioRenderClient,
     pub(super) data: *mut T,
     pub(super) frames: u32,
+    pub(super) channels: usize,
     pub(super) len: usize,
     pub(super) in_use: bool,
     pub(super) _marker: marker::PhantomData<&'a mut [T]>,
@@ -63,5 +107,123 @@ impl<'a, T> ops::DerefMut for BufferMut<'a, T> {
     }
 }
 
+impl<'a, T> Buf for BufferMut<'a, T>
+where
+    T: Copy,
+{
+    type Sample = T;
+
+    type Channel<'this>
+        = InterleavedChannel<'this, T>
+    where
+        Self: 'this;
+
+    type IterChannels<'this>
+        = IterChannels<'this, T>
+    where
+        Self: 'this;
+
+    fn frames_hint(&self) -> Option<usize> {
+        Some(self.frames as usize)
+    }
+
+    fn channels(&self) -> usize {
+        self.channels
+    }
+
+    fn layout_hint(&self) -> LayoutHint {
+        LayoutHint::Interleaved
+    }
+
+    fn get_channel(&self, channel: usize) -> Option<Self::Channel<'_>> {
+        InterleavedChannel::from_slice(self, channel, self.channels)
+    }
+
+    fn iter_channels(&self) -> Self::IterChannels<'_> {
+        ::audio::wrap::interleaved(&self[..], self.channels).into_iter()
+    }
+}
+
+impl<'a, T> BufMut for BufferMut<'a, T>
+where
+    T: Copy,
+{
+    type ChannelMut<'this>
+        = InterleavedChannelMut<'this, T>
+    where
+        Self: 'this;
+
+    type IterChannelsMut<'this>
+        = IterChannelsMut<'this, T>
+    where
+        Self: 'this;
+
+    fn get_channel_mut(&mut self, channel: usize) -> Option<Self::ChannelMut<'_>> {
+        let channels = self.channels;
+        InterleavedChannelMut::from_slice(self, channel, channels)
+    }
+
+    fn copy_channel(&mut self, from: usize, to: usize) {
+        let channels = self.channels;
+
+        if from >= channels || to >= channels {
+            return;
+        }
+
+        for frame in self.chunks_exact_mut(channels) {
+            frame[to] = frame[from];
+        }
+    }
+
+    fn iter_channels_mut(&mut self) -> Self::IterChannelsMut<'_> {
+        let channels = self.channels;
+        ::audio::wrap::interleaved(&mut self[..], channels).into_iter()
+    }
+}
+
+impl<'a, T> ExactSizeBuf for BufferMut<'a, T>
+where
+    T: Copy,
+{
+    fn frames(&self) -> usize {
+        self.frames as usize
+    }
+}
+
+impl<'a, T> InterleavedBuf for BufferMut<'a, T> {
+    type Sample = T;
+
+    fn as_interleaved(&self) -> &[T] {
+        self
+    }
+}
+
+impl<'a, T> InterleavedBufMut for BufferMut<'a, T> {
+    fn as_interleaved_mut(&mut self) -> &mut [T] {
+        self
+    }
+
+    fn as_interleaved_mut_ptr(&mut self) -> ptr::NonNull<T> {
+        // Safety: the buffer provided by the device is never null.
+        unsafe { ptr::NonNull::new_unchecked(self.data) }
+    }
+
+    /// Shrink the buffer to the given topology, which also limits the number
+    /// of frames which are submitted to the device when it's released.
+    unsafe fn set_interleaved_topology(&mut self, channels: usize, frames: usize) {
+        debug_assert!(
+            channels.saturating_mul(frames) <= self.len,
+            "topology {}x{} is larger than the device buffer {}",
+            channels,
+            frames,
+            self.len
+        );
+
+        self.channels = channels;
+        self.frames = frames as u32;
+        self.len = channels * frames;
+    }
+}
+
 // Safety: thread safety is ensured through tagging with ste::Tag.
 unsafe impl<T> Send for BufferMut<'_, T> {}
@@ -0,0 +1,172 @@
+use std::marker;
+use std::ptr;
+
+use windows::Win32::Foundation as f;
+use windows::Win32::Media::Audio as audio;
+
+use crate::buf_io;
+use crate::wasapi::recovery;
+use crate::wasapi::{ClientConfig, Error, Flow, Recovered, Sample};
+
+/// Packet handling and recovery shared by the capture readers.
+pub(super) struct Capture<T> {
+    pub(super) tag: ste::Tag,
+    // NB: keeps the client of the stream alive, since it's replaced when
+    // recovering.
+    _audio_client: audio::IAudioClient,
+    capture_client: audio::IAudioCaptureClient,
+    config: ClientConfig,
+    channels: usize,
+    // Number of frames which have already been read from the current packet.
+    offset: usize,
+    pub(super) recover: bool,
+    pub(super) recoveries: usize,
+    pub(super) recovered: Option<Recovered>,
+    _marker: marker::PhantomData<T>,
+}
+
+impl<T> Capture<T>
+where
+    T: Sample,
+{
+    pub(super) fn new(
+        tag: ste::Tag,
+        audio_client: audio::IAudioClient,
+        capture_client: audio::IAudioCaptureClient,
+        config: ClientConfig,
+    ) -> Self {
+        Self {
+            tag,
+            _audio_client: audio_client,
+            capture_client,
+            config,
+            channels: config.channels as usize,
+            offset: 0,
+            recover: false,
+            recoveries: 0,
+            recovered: None,
+            _marker: marker::PhantomData,
+        }
+    }
+
+    /// Read frames from the next available packet like [Capture::read_packet],
+    /// recovering from the device being invalidated if it's enabled.
+    ///
+    /// The reopened stream signals the given event.
+    pub(super) fn read_packet_or_recover<B>(
+        &mut self,
+        buf: &mut B,
+        event: f::HANDLE,
+    ) -> Result<Option<usize>, Error>
+    where
+        B: audio_core::WriteBuf + audio_core::InterleavedBufMut<Sample = T>,
+    {
+        loop {
+            match self.read_packet(buf) {
+                Err(e) => self.recover_or_fail(e, event)?,
+                result => return result,
+            }
+        }
+    }
+
+    /// Recover from the device being invalidated if the error was raised
+    /// because of it and recovery is enabled, otherwise return the error.
+    fn recover_or_fail(&mut self, error: Error, event: f::HANDLE) -> Result<(), Error> {
+        if !self.recover || !recovery::is_invalidated(&error) {
+            return Err(error);
+        }
+
+        let (audio_client, config) =
+            recovery::reopen::<T>(Flow::Capture, self.config, event, error)?;
+
+        unsafe {
+            let capture_client: audio::IAudioCaptureClient = audio_client.GetService()?;
+            audio_client.Start()?;
+
+            self._audio_client = audio_client;
+            self.capture_client = capture_client;
+        }
+
+        // NB: the packet which was partially read belonged to the old device.
+        self.offset = 0;
+        self.config = config;
+        self.recoveries += 1;
+        self.recovered = Some(Recovered { config });
+        tracing::trace!(recoveries = self.recoveries, "recovered stream");
+        Ok(())
+    }
+
+    /// Check that the given buffer has the expected number of channels.
+    pub(super) fn check_channels<B>(&self, buf: &B) -> Result<(), Error>
+    where
+        B: audio_core::Buf,
+    {
+        if buf.channels() != self.channels {
+            return Err(Error::ChannelsMismatch {
+                actual: buf.channels(),
+                expected: self.channels,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Read frames from the next available packet into `buf`.
+    ///
+    /// Returns the number of frames read, or `None` if there's no packet
+    /// available and the caller should wait for the audio event. Frames which
+    /// the device reports as silent are filled with [Sample::MID].
+    pub(super) fn read_packet<B>(&mut self, buf: &mut B) -> Result<Option<usize>, Error>
+    where
+        B: audio_core::WriteBuf + audio_core::InterleavedBufMut<Sample = T>,
+    {
+        self.tag.ensure_on_thread();
+
+        let capacity = buf_io::writable_frames(buf, self.channels);
+
+        if capacity == 0 {
+            return Ok(Some(0));
+        }
+
+        unsafe {
+            if self.capture_client.GetNextPacketSize()? == 0 {
+                return Ok(None);
+            }
+
+            let mut data = ptr::null_mut();
+            let mut frames = 0;
+            let mut flags = 0;
+
+            self.capture_client
+                .GetBuffer(&mut data, &mut frames, &mut flags, None, None)?;
+
+            let frames = frames as usize;
+            let read = usize::min(frames.saturating_sub(self.offset), capacity);
+            let to = buf.as_interleaved_mut().as_mut_ptr();
+            let len = read * self.channels;
+
+            if flags & audio::AUDCLNT_BUFFERFLAGS_SILENT.0 as u32 != 0 {
+                for n in 0..len {
+                    to.add(n).write(T::MID);
+                }
+            } else {
+                let from = (data as *const T).add(self.offset * self.channels);
+                ptr::copy_nonoverlapping(from, to, len);
+            }
+
+            // NB: packets can only be released in full, so if the buffer
+            // couldn't fit the whole packet we release nothing and keep track
+            // of how far into it we've read.
+            if self.offset + read < frames {
+                self.capture_client.ReleaseBuffer(0)?;
+                self.offset += read;
+            } else {
+                self.capture_client.ReleaseBuffer(frames as u32)?;
+                self.offset = 0;
+            }
+
+            buf.advance_mut(read);
+            Ok(Some(read))
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use std::mem;
+
+use audio_core::ChannelPosition;
+use windows::Win32::Media::Audio as audio;
+use windows::Win32::Media::KernelStreaming as ks;
+
+#[cfg(test)]
+mod tests;
+
+/// Speaker positions in the order of the `SPEAKER_*` bits of a channel mask,
+/// starting with `SPEAKER_FRONT_LEFT` in bit 0.
+const POSITIONS: [ChannelPosition; 18] = [
+    ChannelPosition::FrontLeft,
+    ChannelPosition::FrontRight,
+    ChannelPosition::FrontCenter,
+    ChannelPosition::Lfe,
+    ChannelPosition::RearLeft,
+    ChannelPosition::RearRight,
+    ChannelPosition::FrontLeftOfCenter,
+    ChannelPosition::FrontRightOfCenter,
+    ChannelPosition::RearCenter,
+    ChannelPosition::SideLeft,
+    ChannelPosition::SideRight,
+    ChannelPosition::TopCenter,
+    ChannelPosition::TopFrontLeft,
+    ChannelPosition::TopFrontCenter,
+    ChannelPosition::TopFrontRight,
+    ChannelPosition::TopRearLeft,
+    ChannelPosition::TopRearCenter,
+    ChannelPosition::TopRearRight,
+];
+
+/// Convert a channel mask into the speaker positions of each channel in the
+/// order they are interleaved.
+pub(super) fn to_positions(mask: u32) -> Vec<ChannelPosition> {
+    if mask == ks::SPEAKER_FRONT_CENTER {
+        return vec![ChannelPosition::Mono];
+    }
+
+    POSITIONS
+        .iter()
+        .enumerate()
+        .filter(|&(bit, _)| mask & (1 << bit) != 0)
+        .map(|(_, &position)| position)
+        .collect()
+}
+
+/// Convert speaker positions into a channel mask.
+///
+/// Positions which can't be represented in a channel mask are ignored.
+pub(super) fn from_positions(positions: &[ChannelPosition]) -> u32 {
+    let mut mask = 0;
+
+    for &position in positions {
+        // NB: a single channel is conventionally played through the front
+        // center speaker.
+        let position = match position {
+            ChannelPosition::Mono => ChannelPosition::FrontCenter,
+            position => position,
+        };
+
+        if let Some(bit) = POSITIONS.iter().position(|&p| p == position) {
+            mask |= 1 << bit;
+        }
+    }
+
+    mask
+}
+
+/// Get the conventional channel mask for the given number of channels, or `0`
+/// if there is none.
+pub(super) fn default_mask(channels: u16) -> u32 {
+    match ChannelPosition::default_layout(channels as usize) {
+        Some(layout) => from_positions(layout),
+        None => 0,
+    }
+}
+
+/// Read the channel mask out of a wave format, or `0` if it doesn't specify
+/// one.
+///
+/// # Safety
+///
+/// The caller must ensure that `format` points to a valid wave format.
+pub(super) unsafe fn of_format(format: *const audio::WAVEFORMATEX) -> u32 {
+    if (*format).wFormatTag as u32 != ks::WAVE_FORMAT_EXTENSIBLE {
+        return 0;
+    }
+
+    debug_assert_eq! {
+        (*format).cbSize as usize,
+        mem::size_of::<audio::WAVEFORMATEXTENSIBLE>() - mem::size_of::<audio::WAVEFORMATEX>()
+    };
+
+    (*(format as *const audio::WAVEFORMATEXTENSIBLE)).dwChannelMask
+}
@@ -0,0 +1,75 @@
+use audio_core::ChannelPosition::{self, *};
+use windows::Win32::Media::KernelStreaming as ks;
+
+use super::{default_mask, from_positions, to_positions};
+
+const STEREO: u32 = ks::SPEAKER_FRONT_LEFT | ks::SPEAKER_FRONT_RIGHT;
+
+const SURROUND_5_1: u32 = STEREO
+    | ks::SPEAKER_FRONT_CENTER
+    | ks::SPEAKER_LOW_FREQUENCY
+    | ks::SPEAKER_BACK_LEFT
+    | ks::SPEAKER_BACK_RIGHT;
+
+const SURROUND_7_1: u32 = SURROUND_5_1 | ks::SPEAKER_SIDE_LEFT | ks::SPEAKER_SIDE_RIGHT;
+
+#[test]
+fn test_to_positions() {
+    assert!(to_positions(0).is_empty());
+    assert_eq!(to_positions(ks::SPEAKER_FRONT_CENTER), [Mono]);
+    assert_eq!(to_positions(STEREO), [FrontLeft, FrontRight]);
+    assert_eq!(
+        to_positions(SURROUND_5_1),
+        [FrontLeft, FrontRight, FrontCenter, Lfe, RearLeft, RearRight]
+    );
+
+    // NB: channels are interleaved in the order of the bits, not the order
+    // in which the speakers are commonly listed.
+    assert_eq!(
+        to_positions(ks::SPEAKER_TOP_BACK_RIGHT | ks::SPEAKER_SIDE_LEFT | ks::SPEAKER_FRONT_LEFT),
+        [FrontLeft, SideLeft, TopRearRight]
+    );
+}
+
+#[test]
+fn test_to_positions_ignores_unknown_bits() {
+    assert_eq!(
+        to_positions(STEREO | 1 << 18 | 1 << 31),
+        [FrontLeft, FrontRight]
+    );
+}
+
+#[test]
+fn test_from_positions() {
+    assert_eq!(from_positions(&[]), 0);
+    assert_eq!(from_positions(&[Mono]), ks::SPEAKER_FRONT_CENTER);
+    assert_eq!(from_positions(&[FrontRight, FrontLeft]), STEREO);
+    assert_eq!(
+        from_positions(&[FrontLeft, FrontRight, FrontCenter, Lfe, RearLeft, RearRight]),
+        SURROUND_5_1
+    );
+}
+
+#[test]
+fn test_every_position_roundtrips() {
+    for bit in 0..18 {
+        let mask = 1 << bit;
+        let positions = to_positions(mask);
+        assert_eq!(positions.len(), 1);
+        assert_eq!(from_positions(&positions), mask);
+    }
+}
+
+#[test]
+fn test_default_mask() {
+    assert_eq!(default_mask(1), ks::SPEAKER_FRONT_CENTER);
+    assert_eq!(default_mask(2), STEREO);
+    assert_eq!(default_mask(6), SURROUND_5_1);
+    assert_eq!(default_mask(8), SURROUND_7_1);
+    assert_eq!(default_mask(7), 0);
+
+    for channels in [1, 2, 3, 4, 5, 6, 8] {
+        let layout = ChannelPosition::default_layout(channels).unwrap();
+        assert_eq!(to_positions(default_mask(channels as u16)), layout);
+    }
+}
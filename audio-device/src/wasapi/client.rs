@@ -1,19 +1,24 @@
 use std::marker;
-use std::mem;
 use std::ptr;
 
 use windows::Win32::System::Com as com;
 use windows::Win32::Media::Audio as audio;
-use windows::Win32::Media::Multimedia as mm;
-use windows::Win32::Media::KernelStreaming as ks;
+use windows::Win32::Media::Audio::Endpoints as endpoints;
+
+use audio_core::Endian;
 
 use crate::loom::sync::Arc;
-use crate::wasapi::{ClientConfig, Error, InitializedClient, Sample, SampleFormat};
+use crate::wasapi::channel_mask;
+use crate::wasapi::{
+    ClientConfig, EndpointVolume, Error, InitializedClient, Meter, PackedI24, Sample, SampleFormat,
+    SpatialClient,
+};
 use crate::windows::{AsyncEvent, Event, RawEvent};
 
 /// An audio client.
 pub struct Client {
     pub(super) tag: ste::Tag,
+    pub(super) device: audio::IMMDevice,
     pub(super) audio_client: audio::IAudioClient,
 }
 
@@ -28,49 +33,39 @@ impl Client {
             let mix_format = self.audio_client
                 .GetMixFormat()?;
 
-            let bits_per_sample = (*mix_format).wBitsPerSample;
-
-            let sample_format = match (*mix_format).wFormatTag as u32 {
-                ks::WAVE_FORMAT_EXTENSIBLE => {
-                    debug_assert_eq! {
-                        (*mix_format).cbSize as usize,
-                        mem::size_of::<audio::WAVEFORMATEXTENSIBLE>() - mem::size_of::<audio::WAVEFORMATEX>()
-                    };
-
-                    let mix_format = mix_format as *const audio::WAVEFORMATEXTENSIBLE;
-
-                    if bits_per_sample == 32
-                        && matches!((*mix_format).SubFormat, mm::KSDATAFORMAT_SUBTYPE_IEEE_FLOAT)
-                    {
-                        SampleFormat::F32
-                    } else {
-                        return Err(Error::UnsupportedMixFormat);
-                    }
-                }
-                audio::WAVE_FORMAT_PCM => {
-                    assert!((*mix_format).cbSize == 0);
-
-                    if bits_per_sample == 16 {
-                        SampleFormat::I16
-                    } else {
-                        return Err(Error::UnsupportedMixFormat);
-                    }
-                }
-                _ => {
-                    return Err(Error::UnsupportedMixFormat);
-                }
+            // NB: 24-bit samples in a 32-bit container are aligned to the most
+            // significant bits, so they're reported as 32-bit samples.
+            let sample_format = if f32::is_compatible_with(mix_format) {
+                SampleFormat::F32(Endian::Little)
+            } else if i16::is_compatible_with(mix_format) {
+                SampleFormat::I16(Endian::Little)
+            } else if i32::is_compatible_with(mix_format) {
+                SampleFormat::I32(Endian::Little)
+            } else if PackedI24::is_compatible_with(mix_format) {
+                SampleFormat::I24Packed(Endian::Little)
+            } else {
+                return Err(Error::UnsupportedMixFormat);
             };
 
             let channels = (*mix_format).nChannels;
             let sample_rate = (*mix_format).nSamplesPerSec;
-            
-            tracing::trace!(?tag, ?channels, ?sample_rate, ?sample_format, "got client config");
+            let channel_mask = channel_mask::of_format(mix_format);
+
+            tracing::trace!(
+                ?tag,
+                ?channels,
+                ?sample_rate,
+                ?sample_format,
+                ?channel_mask,
+                "got client config"
+            );
 
             Ok(ClientConfig {
                 _tag: tag,
                 channels,
                 sample_rate,
                 sample_format,
+                channel_mask,
             })
         }
     }
@@ -81,7 +76,7 @@ impl Client {
     where
         T: Sample,
     {
-        self.initialize_inner(config, || Event::new(false, false))
+        self.initialize_inner(config, || Ok(Event::new(false, false)?))
     }
 
     cfg_events_driver! {
@@ -100,20 +95,75 @@ impl Client {
         where
             T: Sample,
         {
-            self.initialize_inner(config, || AsyncEvent::new(false))
+            self.initialize_inner(config, || Ok(AsyncEvent::new(false)?))
+        }
+    }
+
+    /// Try to initialize the client in exclusive mode with the given
+    /// configuration.
+    ///
+    /// In exclusive mode the stream has sole access to the device, which
+    /// bypasses the system mixer and uses the minimum period supported by the
+    /// device for the lowest possible latency. The format described by the
+    /// configuration must be supported natively by the device or this will
+    /// error with [Error::UnsupportedMixFormat].
+    #[tracing::instrument(skip_all)]
+    pub fn initialize_exclusive<T>(
+        &mut self,
+        config: ClientConfig,
+    ) -> Result<InitializedClient<T, Event>, Error>
+    where
+        T: Sample,
+    {
+        self.initialize_exclusive_inner(config, || Ok(Event::new(false, false)?))
+    }
+
+    cfg_events_driver! {
+        /// Try to initialize the client in exclusive mode with the given
+        /// configuration.
+        ///
+        /// See [Client::initialize_exclusive].
+        ///
+        /// # Panics
+        ///
+        /// Panics if the audio runtime is not available.
+        ///
+        /// See [Runtime][crate::runtime::Runtime] for more.
+        #[tracing::instrument(skip_all)]
+        pub fn initialize_exclusive_async<T>(
+            &mut self,
+            config: ClientConfig,
+        ) -> Result<InitializedClient<T, AsyncEvent>, Error>
+        where
+            T: Sample,
+        {
+            self.initialize_exclusive_inner(config, || Ok(AsyncEvent::new(false)?))
         }
     }
 
     /// Try to initialize the client with the given configuration.
     fn initialize_inner<T, F, E>(
         &self,
-        mut config: ClientConfig,
+        config: ClientConfig,
         event: F,
     ) -> Result<InitializedClient<T, E>, Error>
     where
         T: Sample,
-        F: FnOnce() -> windows::core::Result<E>,
+        F: FnOnce() -> Result<E, Error>,
         E: RawEvent,
+    {
+        let config = self.initialize_shared::<T>(config)?;
+        self.finish_initialize(config, event)
+    }
+
+    /// Initialize the client in shared mode with the closest supported match
+    /// of the given configuration, returning the configuration in use.
+    pub(super) fn initialize_shared<T>(
+        &self,
+        mut config: ClientConfig,
+    ) -> Result<ClientConfig, Error>
+    where
+        T: Sample,
     {
         unsafe {
             let mut mix_format = T::mix_format(config);
@@ -140,6 +190,7 @@ impl Client {
                 mix_format = *(closest_match as *mut audio::WAVEFORMATEXTENSIBLE);
                 config.sample_rate = mix_format.Format.nSamplesPerSec;
                 config.channels = mix_format.Format.nChannels;
+                config.channel_mask = channel_mask::of_format(closest_match);
                 com::CoTaskMemFree(closest_match.cast());
             };
 
@@ -155,6 +206,91 @@ impl Client {
                     None,
                 )?;
 
+            Ok(config)
+        }
+    }
+
+    /// Try to initialize the client in exclusive mode with the given
+    /// configuration.
+    fn initialize_exclusive_inner<T, F, E>(
+        &mut self,
+        config: ClientConfig,
+        event: F,
+    ) -> Result<InitializedClient<T, E>, Error>
+    where
+        T: Sample,
+        F: FnOnce() -> Result<E, Error>,
+        E: RawEvent,
+    {
+        unsafe {
+            let mix_format = T::mix_format(config);
+
+            // NB: exclusive mode never provides a closest match, the format
+            // must be supported as-is.
+            let result = self.audio_client.IsFormatSupported(
+                audio::AUDCLNT_SHAREMODE_EXCLUSIVE,
+                &mix_format.Format,
+                None,
+            );
+
+            if result.is_err() {
+                return Err(Error::UnsupportedMixFormat);
+            }
+
+            let mut period = 0;
+            self.audio_client.GetDevicePeriod(None, Some(&mut period))?;
+
+            tracing::trace!(?period, "initializing exclusive audio client");
+
+            let result = self.audio_client.Initialize(
+                audio::AUDCLNT_SHAREMODE_EXCLUSIVE,
+                audio::AUDCLNT_STREAMFLAGS_EVENTCALLBACK,
+                period,
+                period,
+                &mix_format.Format,
+                None,
+            );
+
+            match result {
+                Err(e) if e.code() == audio::AUDCLNT_E_BUFFER_SIZE_NOT_ALIGNED => {
+                    // The device requires the period to be aligned to its
+                    // buffer size, so we align it to the buffer size which
+                    // was suggested and retry with a fresh audio client.
+                    let frames = self.audio_client.GetBufferSize()?;
+                    let period = aligned_period(frames, config.sample_rate);
+
+                    tracing::trace!(?frames, ?period, "retrying with aligned period");
+
+                    self.audio_client = self.device.Activate(com::CLSCTX_ALL, None)?;
+
+                    self.audio_client.Initialize(
+                        audio::AUDCLNT_SHAREMODE_EXCLUSIVE,
+                        audio::AUDCLNT_STREAMFLAGS_EVENTCALLBACK,
+                        period,
+                        period,
+                        &mix_format.Format,
+                        None,
+                    )?;
+                }
+                result => result?,
+            }
+
+            self.finish_initialize(config, event)
+        }
+    }
+
+    /// Finish initializing the client by setting up its event handle.
+    fn finish_initialize<T, F, E>(
+        &self,
+        config: ClientConfig,
+        event: F,
+    ) -> Result<InitializedClient<T, E>, Error>
+    where
+        T: Sample,
+        F: FnOnce() -> Result<E, Error>,
+        E: RawEvent,
+    {
+        unsafe {
             let event = Arc::new(event()?);
 
             tracing::trace!("set event handle");
@@ -177,6 +313,56 @@ impl Client {
         }
     }
 
+    /// Access the master volume of the endpoint the client belongs to.
+    pub fn endpoint_volume(&self) -> Result<EndpointVolume, Error> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            let volume: endpoints::IAudioEndpointVolume =
+                self.device.Activate(com::CLSCTX_ALL, None)?;
+
+            Ok(EndpointVolume {
+                tag: self.tag,
+                volume,
+            })
+        }
+    }
+
+    /// Access the peak meter of the endpoint the client belongs to.
+    ///
+    /// For an output device this meters everything which is being played
+    /// through it, which can be used for loopback monitoring.
+    pub fn endpoint_meter(&self) -> Result<Meter, Error> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            let meter: endpoints::IAudioMeterInformation =
+                self.device.Activate(com::CLSCTX_ALL, None)?;
+
+            Ok(Meter {
+                tag: self.tag,
+                meter,
+            })
+        }
+    }
+
+    /// Construct a spatial audio client for the endpoint the client belongs
+    /// to.
+    ///
+    /// This errors if spatial audio isn't enabled for the endpoint.
+    pub fn spatial_client(&self) -> Result<SpatialClient, Error> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            let client: audio::ISpatialAudioClient = self.device.Activate(com::CLSCTX_ALL, None)?;
+
+            Ok(SpatialClient {
+                tag: self.tag,
+                client,
+            })
+        }
+    }
+
     /// Start playback on device.
     pub fn start(&self) -> Result<(), Error> {
         unsafe {
@@ -196,5 +382,12 @@ impl Client {
     }
 }
 
+/// Calculate the period in 100-nanosecond units which corresponds to the
+/// given number of frames.
+fn aligned_period(frames: u32, sample_rate: u32) -> i64 {
+    const REFTIMES_PER_SEC: f64 = 10_000_000.0;
+    (REFTIMES_PER_SEC * frames as f64 / sample_rate as f64 + 0.5) as i64
+}
+
 // Safety: thread safety is ensured through tagging with ste::Tag.
 unsafe impl Send for Client {}
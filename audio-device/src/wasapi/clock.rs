@@ -0,0 +1,144 @@
+use std::time::Duration;
+
+use windows::core::Interface;
+use windows::Win32::Media::Audio as audio;
+
+use crate::wasapi::Error;
+
+/// A sampled position of a [Clock].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ClockPosition {
+    /// The position of the stream in clock units, see [Clock::frequency].
+    pub position: u64,
+    /// The frequency of the clock in units per second.
+    pub frequency: u64,
+    /// The value of the performance counter at the time the position was
+    /// sampled, in 100-nanosecond units.
+    pub qpc_position: u64,
+}
+
+impl ClockPosition {
+    /// Get the position of the stream as the amount of time which has been
+    /// played since the stream started.
+    pub fn as_duration(&self) -> Duration {
+        if self.frequency == 0 {
+            return Duration::ZERO;
+        }
+
+        let secs = self.position / self.frequency;
+        let rem = self.position % self.frequency;
+        let nanos = (rem as u128 * 1_000_000_000 / self.frequency as u128) as u32;
+        Duration::new(secs, nanos)
+    }
+
+    /// Get the position of the stream as the number of frames which have been
+    /// played at the given sample rate.
+    pub fn as_frames(&self, sample_rate: u32) -> u64 {
+        if self.frequency == 0 {
+            return 0;
+        }
+
+        (self.position as u128 * sample_rate as u128 / self.frequency as u128) as u64
+    }
+}
+
+/// A monotonic clock of the position of a stream as it's being played by the
+/// device.
+///
+/// This can be used to synchronize other media, like video or MIDI, with the
+/// audio being rendered.
+///
+/// See [InitializedClient::clock][crate::wasapi::InitializedClient::clock].
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::wasapi;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// wasapi::audio_prelude();
+///
+/// let client = match wasapi::default_output_client()? {
+///     Some(client) => client,
+///     None => return Ok(()),
+/// };
+///
+/// let config = client.default_client_config()?;
+/// let initialized = client.initialize::<f32>(config)?;
+/// let clock = initialized.clock()?;
+///
+/// client.start()?;
+///
+/// let position = clock.position()?;
+/// println!("played: {:?}", position.as_duration());
+/// # Ok(()) }
+/// ```
+pub struct Clock {
+    pub(super) tag: ste::Tag,
+    pub(super) clock: audio::IAudioClock,
+    pub(super) frequency: u64,
+}
+
+impl Clock {
+    /// Construct a new clock, caching its frequency which is fixed for the
+    /// lifetime of the stream.
+    pub(super) fn new(tag: ste::Tag, clock: audio::IAudioClock) -> Result<Self, Error> {
+        let frequency = unsafe { clock.GetFrequency()? };
+
+        Ok(Self {
+            tag,
+            clock,
+            frequency,
+        })
+    }
+
+    /// The frequency of the clock in units per second.
+    pub fn frequency(&self) -> u64 {
+        self.frequency
+    }
+
+    /// Sample the current position of the stream.
+    pub fn position(&self) -> Result<ClockPosition, Error> {
+        self.tag.ensure_on_thread();
+
+        let mut position = 0;
+        let mut qpc_position = 0;
+
+        unsafe {
+            self.clock
+                .GetPosition(&mut position, Some(&mut qpc_position))?;
+        }
+
+        Ok(ClockPosition {
+            position,
+            frequency: self.frequency,
+            qpc_position,
+        })
+    }
+
+    /// Sample the current position of the device in frames.
+    ///
+    /// Unlike [Clock::position] this is the raw position of the device, which
+    /// isn't adjusted for the latency of the stream. Returns `None` if the
+    /// clock doesn't support reporting the device position.
+    pub fn device_position(&self) -> Result<Option<u64>, Error> {
+        self.tag.ensure_on_thread();
+
+        let clock: audio::IAudioClock2 = match self.clock.cast() {
+            Ok(clock) => clock,
+            Err(..) => return Ok(None),
+        };
+
+        let mut position = 0;
+
+        unsafe {
+            clock.GetDevicePosition(&mut position, None)?;
+        }
+
+        Ok(Some(position))
+    }
+}
+
+// Safety: thread safety is ensured through tagging with ste::Tag.
+unsafe impl Send for Clock {}
@@ -0,0 +1,257 @@
+use windows::Win32::Devices::FunctionDiscovery as fd;
+use windows::Win32::Media::Audio as audio;
+use windows::Win32::Media::Audio::Endpoints as endpoints;
+use windows::Win32::System::Com as com;
+use windows::Win32::System::Com::StructuredStorage as ss;
+
+use crate::device::{DeviceInfo, Direction};
+use crate::wasapi::{from_wide, Client, EndpointVolume, Error, Meter};
+
+/// The direction of an audio endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Flow {
+    /// An endpoint which audio is rendered to, like speakers.
+    Render,
+    /// An endpoint which audio is captured from, like a microphone.
+    Capture,
+}
+
+impl Flow {
+    pub(super) fn data_flow(self) -> audio::EDataFlow {
+        match self {
+            Self::Render => audio::eRender,
+            Self::Capture => audio::eCapture,
+        }
+    }
+
+    pub(super) fn from_data_flow(flow: audio::EDataFlow) -> Option<Self> {
+        match flow {
+            audio::eRender => Some(Self::Render),
+            audio::eCapture => Some(Self::Capture),
+            _ => None,
+        }
+    }
+}
+
+/// The state of an audio endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DeviceState {
+    /// The device is active and can be used.
+    Active,
+    /// The device has been disabled by the user.
+    Disabled,
+    /// The device is not present.
+    NotPresent,
+    /// The device is present but has been unplugged.
+    Unplugged,
+}
+
+impl DeviceState {
+    pub(super) fn from_value(value: u32) -> Option<Self> {
+        Some(match value {
+            audio::DEVICE_STATE_ACTIVE => Self::Active,
+            audio::DEVICE_STATE_DISABLED => Self::Disabled,
+            audio::DEVICE_STATE_NOTPRESENT => Self::NotPresent,
+            audio::DEVICE_STATE_UNPLUGGED => Self::Unplugged,
+            _ => return None,
+        })
+    }
+}
+
+/// An audio endpoint.
+///
+/// See [devices].
+pub struct Device {
+    tag: ste::Tag,
+    device: audio::IMMDevice,
+}
+
+impl Device {
+    /// Get the unique identifier of the endpoint.
+    pub fn id(&self) -> Result<String, Error> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            let id = self.device.GetId()?;
+            let string = from_wide(id.0);
+            com::CoTaskMemFree(id.0.cast());
+            Ok(string)
+        }
+    }
+
+    /// Get the friendly name of the endpoint, like `Speakers (USB Audio)`.
+    pub fn friendly_name(&self) -> Result<String, Error> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            let store = self.device.OpenPropertyStore(com::STGM_READ)?;
+            let mut value = store.GetValue(&fd::PKEY_Device_FriendlyName)?;
+            let string = from_wide(value.Anonymous.Anonymous.Anonymous.pwszVal.0);
+            ss::PropVariantClear(&mut value)?;
+            Ok(string)
+        }
+    }
+
+    /// Get the state of the endpoint.
+    ///
+    /// Returns `None` if the state isn't recognized.
+    pub fn state(&self) -> Result<Option<DeviceState>, Error> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            let state = self.device.GetState()?;
+            Ok(DeviceState::from_value(state))
+        }
+    }
+
+    /// Access the master volume of the endpoint.
+    pub fn endpoint_volume(&self) -> Result<EndpointVolume, Error> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            let volume: endpoints::IAudioEndpointVolume =
+                self.device.Activate(com::CLSCTX_ALL, None)?;
+
+            Ok(EndpointVolume {
+                tag: self.tag,
+                volume,
+            })
+        }
+    }
+
+    /// Access the peak meter of the endpoint.
+    pub fn endpoint_meter(&self) -> Result<Meter, Error> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            let meter: endpoints::IAudioMeterInformation =
+                self.device.Activate(com::CLSCTX_ALL, None)?;
+
+            Ok(Meter {
+                tag: self.tag,
+                meter,
+            })
+        }
+    }
+
+    /// Construct an audio client for the endpoint.
+    #[tracing::instrument(skip_all)]
+    pub fn client(&self) -> Result<Client, Error> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            let audio_client: audio::IAudioClient = self.device.Activate(com::CLSCTX_ALL, None)?;
+            tracing::trace!("got audio client");
+
+            Ok(Client {
+                tag: self.tag,
+                device: self.device.clone(),
+                audio_client,
+            })
+        }
+    }
+}
+
+// Safety: thread safety is ensured through tagging with ste::Tag.
+unsafe impl Send for Device {}
+
+/// Enumerate all audio endpoints in the given direction, regardless of their
+/// state.
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::wasapi;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// wasapi::audio_prelude();
+///
+/// for device in wasapi::devices(wasapi::Flow::Render)? {
+///     println!("{}: {} ({:?})", device.id()?, device.friendly_name()?, device.state()?);
+/// }
+/// # Ok(()) }
+/// ```
+#[tracing::instrument(skip_all)]
+pub fn devices(flow: Flow) -> Result<Vec<Device>, Error> {
+    let tag = ste::Tag::current_thread();
+
+    unsafe {
+        let enumerator: audio::IMMDeviceEnumerator =
+            com::CoCreateInstance(&audio::MMDeviceEnumerator, None, com::CLSCTX_ALL)?;
+
+        let collection =
+            enumerator.EnumAudioEndpoints(flow.data_flow(), audio::DEVICE_STATEMASK_ALL)?;
+        let count = collection.GetCount()?;
+        let mut devices = Vec::with_capacity(count as usize);
+
+        for n in 0..count {
+            let device = collection.Item(n)?;
+            devices.push(Device { tag, device });
+        }
+
+        tracing::trace!(?count, "enumerated audio endpoints");
+        Ok(devices)
+    }
+}
+
+/// Describe all active audio endpoints in a backend-agnostic manner.
+///
+/// Nominal formats are based on the shared mode mix format of each endpoint.
+///
+/// See [crate::devices].
+pub(crate) fn device_infos() -> Result<Vec<DeviceInfo>, Error> {
+    let mut infos = Vec::new();
+
+    for flow in [Flow::Render, Flow::Capture] {
+        let default_id = match super::default_client(flow)? {
+            Some(client) => Some(
+                Device {
+                    tag: client.tag,
+                    device: client.device,
+                }
+                .id()?,
+            ),
+            None => None,
+        };
+
+        let direction = match flow {
+            Flow::Render => Direction::Output,
+            Flow::Capture => Direction::Input,
+        };
+
+        for device in devices(flow)? {
+            if device.state()? != Some(DeviceState::Active) {
+                continue;
+            }
+
+            let id = device.id()?;
+            let name = device.friendly_name()?;
+
+            let (formats, channels, sample_rates) =
+                match device.client().and_then(|c| c.default_client_config()) {
+                    Ok(config) => {
+                        let channels = config.channels as usize;
+                        (
+                            vec![config.sample_format],
+                            Some((channels, channels)),
+                            Some((config.sample_rate, config.sample_rate)),
+                        )
+                    }
+                    Err(..) => (Vec::new(), None, None),
+                };
+
+            infos.push(DeviceInfo {
+                is_default: default_id.as_deref() == Some(id.as_str()),
+                id,
+                name,
+                direction,
+                formats,
+                channels,
+                sample_rates,
+            });
+        }
+    }
+
+    Ok(infos)
+}
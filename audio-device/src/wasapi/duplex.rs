@@ -0,0 +1,122 @@
+use crate::stream::{DriftEstimator, StreamSpec};
+use crate::wasapi::stream::{configure, spec_of};
+use crate::wasapi::{Client, Clock, Error, Reader, RenderClient, Sample};
+use crate::windows::Event;
+
+/// A portable duplex stream using WASAPI.
+///
+/// The capture and render clients are initialized with matching
+/// configurations and started together. Drift is measured through the clocks
+/// of the two streams.
+///
+/// See [DuplexStream][crate::stream::DuplexStream].
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::stream::{DuplexStream, StreamSpec};
+/// use audio_device::wasapi;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// wasapi::audio_prelude();
+///
+/// let mut stream = wasapi::DuplexStream::<f32>::open_default(StreamSpec::new(2, 48000))?;
+/// let spec = stream.input_spec();
+/// let mut samples = vec![0.0f32; spec.channels * 256];
+///
+/// loop {
+///     stream.read(audio::wrap::interleaved(&mut samples[..], spec.channels))?;
+///     println!("resample ratio: {}", stream.resample_ratio()?);
+/// }
+/// # }
+/// ```
+pub struct DuplexStream<T> {
+    _capture: Client,
+    _render: Client,
+    reader: Reader<T>,
+    render_client: RenderClient<T, Event>,
+    capture_clock: Clock,
+    render_clock: Clock,
+    input_spec: StreamSpec,
+    output_spec: StreamSpec,
+    drift: DriftEstimator,
+}
+
+impl<T> crate::stream::DuplexStream for DuplexStream<T>
+where
+    T: Sample,
+{
+    type Sample = T;
+    type Error = Error;
+
+    fn open_default(spec: StreamSpec) -> Result<Self, Error> {
+        let capture = super::default_input_client()?.ok_or(Error::NoDefaultDevice)?;
+        let render = super::default_output_client()?.ok_or(Error::NoDefaultDevice)?;
+
+        let capture_initialized = capture.initialize::<T>(configure(&capture, spec)?)?;
+        let render_initialized = render.initialize::<T>(configure(&render, spec)?)?;
+
+        let reader = capture_initialized.reader()?;
+        let render_client = render_initialized.render_client()?;
+        let capture_clock = capture_initialized.clock()?;
+        let render_clock = render_initialized.clock()?;
+
+        let input_spec = spec_of(capture_initialized.config());
+        let output_spec = spec_of(render_initialized.config());
+
+        // NB: start the streams back to back so that their positions are
+        // measured from roughly the same point in time.
+        capture.start()?;
+        render.start()?;
+
+        Ok(Self {
+            _capture: capture,
+            _render: render,
+            reader,
+            render_client,
+            capture_clock,
+            render_clock,
+            input_spec,
+            output_spec,
+            drift: DriftEstimator::new(input_spec.sample_rate, output_spec.sample_rate),
+        })
+    }
+
+    fn input_spec(&self) -> StreamSpec {
+        self.input_spec
+    }
+
+    fn output_spec(&self) -> StreamSpec {
+        self.output_spec
+    }
+
+    fn read<B>(&mut self, buf: B) -> Result<(), Error>
+    where
+        B: audio_core::WriteBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBufMut<Sample = T>,
+    {
+        self.reader.read_interleaved(buf)
+    }
+
+    fn write<B>(&mut self, buf: B) -> Result<(), Error>
+    where
+        B: audio_core::ReadBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBuf<Sample = T>,
+    {
+        self.render_client.write_interleaved(buf)
+    }
+
+    fn resample_ratio(&mut self) -> Result<f64, Error> {
+        let input = self
+            .capture_clock
+            .position()?
+            .as_frames(self.input_spec.sample_rate);
+        let output = self
+            .render_clock
+            .position()?
+            .as_frames(self.output_spec.sample_rate);
+        Ok(self.drift.update(input, output))
+    }
+}
@@ -1,7 +1,13 @@
 use crate::loom::sync::Arc;
-use crate::wasapi::{ClientConfig, Error, RenderClient, Sample};
+use crate::wasapi::capture::Capture;
+use crate::wasapi::{
+    ClientConfig, Clock, Error, Meter, Reader, RenderClient, Sample, SessionEvents, SessionVolume,
+};
+use crate::windows::Event;
 use std::marker;
+use windows::core::Interface;
 use windows::Win32::Media::Audio as audio;
+use windows::Win32::Media::Audio::Endpoints as endpoints;
 
 /// A client that has been initialized with the given type `T`.
 ///
@@ -42,10 +48,115 @@ where
             render_client,
             buffer_size: self.buffer_size,
             channels: self.config.channels as usize,
+            config: self.config,
             event: self.event.clone(),
+            recover: false,
+            recoveries: 0,
+            recovered: None,
             _marker: marker::PhantomData,
         })
     }
+
+    /// Access the volume of the audio session the client belongs to.
+    pub fn session_volume(&self) -> Result<SessionVolume, Error> {
+        self.tag.ensure_on_thread();
+
+        let volume: audio::ISimpleAudioVolume = unsafe { self.audio_client.GetService()? };
+
+        Ok(SessionVolume {
+            tag: self.tag,
+            volume,
+        })
+    }
+
+    /// Access the peak meter of the audio session the client belongs to.
+    pub fn session_meter(&self) -> Result<Meter, Error> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            let control: audio::IAudioSessionControl = self.audio_client.GetService()?;
+            let meter: endpoints::IAudioMeterInformation = control.cast()?;
+
+            Ok(Meter {
+                tag: self.tag,
+                meter,
+            })
+        }
+    }
+
+    /// Access the clock of the stream, which reports the position of the
+    /// stream as it's being played by the device.
+    pub fn clock(&self) -> Result<Clock, Error> {
+        self.tag.ensure_on_thread();
+
+        let clock: audio::IAudioClock = unsafe { self.audio_client.GetService()? };
+
+        Clock::new(self.tag, clock)
+    }
+
+    fn capture(&self) -> Result<Capture<T>, Error> {
+        let capture_client: audio::IAudioCaptureClient = unsafe { self.audio_client.GetService()? };
+
+        Ok(Capture::new(
+            self.tag,
+            self.audio_client.clone(),
+            capture_client,
+            self.config,
+        ))
+    }
+
+    /// Subscribe to events for the audio session the client belongs to, such
+    /// as volume changes or the session being disconnected.
+    pub fn session_events(&self) -> Result<SessionEvents, Error> {
+        self.tag.ensure_on_thread();
+
+        let control: audio::IAudioSessionControl = unsafe { self.audio_client.GetService()? };
+
+        SessionEvents::new(control)
+    }
+}
+
+cfg_events_driver! {
+    use crate::wasapi::AsyncReader;
+    use crate::windows::AsyncEvent;
+
+    impl<T> InitializedClient<T, AsyncEvent>
+    where
+        T: Sample,
+    {
+        /// Construct an asynchronous reader of captured audio.
+        ///
+        /// The client must have been opened on a capture device, such as
+        /// through [default_input_client][crate::wasapi::default_input_client].
+        #[tracing::instrument(skip_all)]
+        pub fn async_reader(&self) -> Result<AsyncReader<T>, Error> {
+            tracing::trace!("initializing capture client");
+
+            self.tag.ensure_on_thread();
+
+            let capture = self.capture()?;
+            Ok(AsyncReader::new(capture, self.event.clone()))
+        }
+    }
+}
+
+impl<T> InitializedClient<T, Event>
+where
+    T: Sample,
+{
+    /// Construct a blocking reader of captured audio.
+    ///
+    /// The client must have been opened on a capture device, such as through
+    /// [default_input_client][crate::wasapi::default_input_client].
+    #[tracing::instrument(skip_all)]
+    pub fn reader(&self) -> Result<Reader<T>, Error> {
+        tracing::trace!("initializing capture client");
+
+        self.tag.ensure_on_thread();
+
+        let capture = self.capture()?;
+        Ok(Reader::new(capture, self.event.clone()))
+    }
 }
 
 // Safety: thread safety is ensured through tagging with ste::Tag.
@@ -0,0 +1,76 @@
+use windows::Win32::Media::Audio::Endpoints as endpoints;
+
+use crate::wasapi::Error;
+
+/// A peak meter of the audio passing through an endpoint or an audio session.
+///
+/// Peak levels are sample values in the range `0.0` to `1.0` which are
+/// measured over the most recent device period, which makes them suitable for
+/// drawing level meters.
+///
+/// See [InitializedClient::session_meter][crate::wasapi::InitializedClient::session_meter]
+/// and [Client::endpoint_meter][crate::wasapi::Client::endpoint_meter].
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::wasapi;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// wasapi::audio_prelude();
+///
+/// let client = match wasapi::default_output_client()? {
+///     Some(client) => client,
+///     None => return Ok(()),
+/// };
+///
+/// let meter = client.endpoint_meter()?;
+///
+/// loop {
+///     println!("peak: {:?}", meter.channel_peaks()?);
+///     std::thread::sleep(std::time::Duration::from_millis(50));
+/// }
+/// # }
+/// ```
+pub struct Meter {
+    pub(super) tag: ste::Tag,
+    pub(super) meter: endpoints::IAudioMeterInformation,
+}
+
+impl Meter {
+    /// Get the peak level across all channels.
+    pub fn peak(&self) -> Result<f32, Error> {
+        self.tag.ensure_on_thread();
+
+        unsafe { Ok(self.meter.GetPeakValue()?) }
+    }
+
+    /// Get the number of channels which are being metered.
+    pub fn channels(&self) -> Result<usize, Error> {
+        self.tag.ensure_on_thread();
+
+        unsafe { Ok(self.meter.GetMeteringChannelCount()? as usize) }
+    }
+
+    /// Get the peak level of each channel.
+    pub fn channel_peaks(&self) -> Result<Vec<f32>, Error> {
+        let mut peaks = vec![0.0; self.channels()?];
+        self.channel_peaks_into(&mut peaks)?;
+        Ok(peaks)
+    }
+
+    /// Write the peak level of each channel into `peaks`, which must hold
+    /// exactly [Meter::channels] elements.
+    pub fn channel_peaks_into(&self, peaks: &mut [f32]) -> Result<(), Error> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            self.meter.GetChannelsPeakValues(peaks)?;
+        }
+
+        Ok(())
+    }
+}
+
+// Safety: thread safety is ensured through tagging with ste::Tag.
+unsafe impl Send for Meter {}
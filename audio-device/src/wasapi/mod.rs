@@ -3,24 +3,72 @@
 use std::ptr;
 
 use thiserror::Error;
+use windows::core::{Interface, PCWSTR};
+use windows::Win32::Foundation as f;
 use windows::Win32::System::Com as com;
 use windows::Win32::Media::Audio as audio;
 
+mod channel_mask;
+
+mod clock;
+pub use self::clock::{Clock, ClockPosition};
+
 mod initialized_client;
 pub use self::initialized_client::InitializedClient;
 
 mod client;
 pub use self::client::Client;
 
+mod device;
+pub(crate) use self::device::device_infos;
+pub use self::device::{devices, Device, DeviceState, Flow};
+
+mod notifications;
+pub use self::notifications::{DeviceEvent, DeviceNotifications};
+
+mod session_events;
+pub use self::session_events::{DisconnectReason, SessionEvent, SessionEvents, SessionState};
+
+mod volume;
+pub use self::volume::{EndpointVolume, SessionVolume};
+
+mod meter;
+pub use self::meter::Meter;
+
+mod spatial;
+pub use self::spatial::{SpatialClient, SpatialObject, SpatialRenderStream, SpatialUpdate};
+
 mod render_client;
 pub use self::render_client::RenderClient;
 
+mod capture;
+
+mod recovery;
+pub use self::recovery::Recovered;
+
+mod reader;
+pub use self::reader::Reader;
+
+mod stream;
+pub use self::stream::{InputStream, OutputStream};
+
+mod duplex;
+pub use self::duplex::DuplexStream;
+
+cfg_events_driver! {
+    mod async_reader;
+    pub use self::async_reader::AsyncReader;
+}
+
 mod buffer_mut;
 pub use self::buffer_mut::BufferMut;
 
 mod sample;
 pub use self::sample::Sample;
 
+#[doc(inline)]
+pub use audio_core::{ChannelPosition, PackedI24, SampleFormat};
+
 /// WASAPI-specific errors.
 #[derive(Debug, Error)]
 pub enum Error {
@@ -31,9 +79,38 @@ pub enum Error {
         #[source]
         windows::core::Error,
     ),
+    /// An error raised by the audio runtime.
+    #[error("runtime error: {0}")]
+    Runtime(#[from] crate::runtime::Error),
+    /// There is no default device available.
+    #[error("no default device available")]
+    NoDefaultDevice,
     /// Trying to use a mix format which is not supported by the device.
     #[error("Device doesn't support a compatible mix format")]
     UnsupportedMixFormat,
+    /// Error raised when there's a channel count mismatch between a buffer and
+    /// the stream it's being written to.
+    #[error("mismatch in number of channels in buffer; actual = {actual}, expected = {expected}")]
+    ChannelsMismatch {
+        /// The actual number of channels.
+        actual: usize,
+        /// The expected number of channels.
+        expected: usize,
+    },
+}
+
+impl Error {
+    pub(crate) fn kind(&self) -> crate::ErrorKind {
+        use crate::ErrorKind;
+
+        match self {
+            Self::Sys(error) => crate::windows::error_kind(error),
+            Self::Runtime(error) => error.kind(),
+            Self::NoDefaultDevice => ErrorKind::NotFound,
+            Self::UnsupportedMixFormat => ErrorKind::UnsupportedFormat,
+            Self::ChannelsMismatch { .. } => ErrorKind::InvalidInput,
+        }
+    }
 }
 
 /// The audio prelude to use for wasapi.
@@ -45,15 +122,6 @@ pub fn audio_prelude() {
     }
 }
 
-/// The sample format detected for the device.
-#[derive(Debug, Clone, Copy)]
-pub enum SampleFormat {
-    /// A 16-bit sample format.
-    I16,
-    /// A 32-bit floating point sample format.
-    F32,
-}
-
 /// A client configuration.
 ///
 /// Constructed through [Client::default_client_config].
@@ -66,11 +134,99 @@ pub struct ClientConfig {
     pub sample_rate: u32,
     /// The sample format in use.
     pub sample_format: SampleFormat,
+    /// The speaker positions of the channels as a channel mask, where each
+    /// bit corresponds to one of the `SPEAKER_*` constants of the Windows API,
+    /// starting with `SPEAKER_FRONT_LEFT` in bit 0. Channels are interleaved
+    /// in the order of the bits which are set. A mask of `0` means that the
+    /// conventional layout for the number of channels is used.
+    ///
+    /// See [ClientConfig::channel_positions].
+    pub channel_mask: u32,
+}
+
+impl ClientConfig {
+    /// Get the speaker positions of the channels in the configuration.
+    ///
+    /// This has one position for each channel in use. Bits of the channel
+    /// mask beyond the number of channels are ignored, and a channel mask
+    /// which doesn't have a bit set for every channel is treated as if the
+    /// layout is unspecified.
+    ///
+    /// This is empty if the layout is unspecified and there is no
+    /// conventional layout for the number of channels in use.
+    pub fn channel_positions(&self) -> Vec<ChannelPosition> {
+        let channels = self.channels as usize;
+
+        if self.channel_mask != 0 {
+            let mut positions = channel_mask::to_positions(self.channel_mask);
+
+            if positions.len() >= channels {
+                positions.truncate(channels);
+                return positions;
+            }
+        }
+
+        ChannelPosition::default_layout(channels)
+            .map(<[_]>::to_vec)
+            .unwrap_or_default()
+    }
+
+    /// Configure the configuration to use channels with the given speaker
+    /// positions, like the ones provided by
+    /// [ChannelPosition::default_layout] for 5.1 or 7.1 surround.
+    ///
+    /// This sets both the number of channels and the channel mask. Positions
+    /// which can't be represented in a channel mask are ignored.
+    pub fn set_channel_positions(&mut self, positions: &[ChannelPosition]) {
+        self.channels = positions.len() as u16;
+        self.channel_mask = channel_mask::from_positions(positions);
+    }
+
+    /// Get the channel mask to use for the configuration, falling back to the
+    /// conventional one for the number of channels in use.
+    pub(super) fn effective_channel_mask(&self) -> u32 {
+        if self.channel_mask == 0 {
+            return channel_mask::default_mask(self.channels);
+        }
+
+        self.channel_mask
+    }
+}
+
+/// Convert a nul-terminated wide string into a [String].
+///
+/// # Safety
+///
+/// The caller must ensure that `ptr` points to a valid nul-terminated wide
+/// string.
+pub(super) unsafe fn from_wide(ptr: *const u16) -> String {
+    if ptr.is_null() {
+        return String::new();
+    }
+
+    let mut len = 0;
+
+    while *ptr.add(len) != 0 {
+        len += 1;
+    }
+
+    String::from_utf16_lossy(std::slice::from_raw_parts(ptr, len))
 }
 
 /// Open the default output device for WASAPI.
-#[tracing::instrument(skip_all)]
 pub fn default_output_client() -> Result<Option<Client>, Error> {
+    default_client(Flow::Render)
+}
+
+/// Open the default input device for WASAPI.
+///
+/// The returned client is associated with a capture endpoint.
+pub fn default_input_client() -> Result<Option<Client>, Error> {
+    default_client(Flow::Capture)
+}
+
+#[tracing::instrument(skip_all, fields(?flow))]
+fn default_client(flow: Flow) -> Result<Option<Client>, Error> {
     let tag = ste::Tag::current_thread();
 
     let enumerator: audio::IMMDeviceEnumerator = unsafe {
@@ -78,8 +234,7 @@ pub fn default_output_client() -> Result<Option<Client>, Error> {
     };
 
     unsafe {
-        let device = enumerator
-            .GetDefaultAudioEndpoint(audio::eRender, audio::eConsole);
+        let device = enumerator.GetDefaultAudioEndpoint(flow.data_flow(), audio::eConsole);
 
         let device = match device {
             Ok(device) => device,
@@ -89,6 +244,82 @@ pub fn default_output_client() -> Result<Option<Client>, Error> {
         tracing::trace!("got default audio endpoint");
         let audio_client: audio::IAudioClient = device.Activate(com::CLSCTX_ALL, None)?;
         tracing::trace!("got audio client");
-        Ok(Some(Client { tag, audio_client }))
+        Ok(Some(Client {
+            tag,
+            device,
+            audio_client,
+        }))
+    }
+}
+
+/// Open the output device with the given endpoint identifier for WASAPI.
+///
+/// Identifiers are obtained through [Device::id]. Returns `None` if there is
+/// no output device with the given identifier.
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::wasapi;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// wasapi::audio_prelude();
+///
+/// for device in wasapi::devices(wasapi::Flow::Render)? {
+///     let id = device.id()?;
+///
+///     if let Some(client) = wasapi::open_output_by_id(&id)? {
+///         println!("{}: {:?}", id, client.default_client_config()?);
+///     }
+/// }
+/// # Ok(()) }
+/// ```
+pub fn open_output_by_id(id: &str) -> Result<Option<Client>, Error> {
+    open_by_id(id, Flow::Render)
+}
+
+/// Open the input device with the given endpoint identifier for WASAPI.
+///
+/// Identifiers are obtained through [Device::id]. Returns `None` if there is
+/// no input device with the given identifier.
+pub fn open_input_by_id(id: &str) -> Result<Option<Client>, Error> {
+    open_by_id(id, Flow::Capture)
+}
+
+#[tracing::instrument(skip_all, fields(id = %id))]
+fn open_by_id(id: &str, flow: Flow) -> Result<Option<Client>, Error> {
+    let tag = ste::Tag::current_thread();
+
+    let enumerator: audio::IMMDeviceEnumerator =
+        unsafe { com::CoCreateInstance(&audio::MMDeviceEnumerator, None, com::CLSCTX_ALL)? };
+
+    let wide = id
+        .encode_utf16()
+        .chain(std::iter::once(0))
+        .collect::<Vec<u16>>();
+
+    unsafe {
+        let device = match enumerator.GetDevice(PCWSTR(wide.as_ptr())) {
+            Ok(device) => device,
+            Err(e) if e.code() == f::ERROR_NOT_FOUND.to_hresult() => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        // NB: the identifier might refer to an endpoint in the other
+        // direction.
+        let endpoint: audio::IMMEndpoint = device.cast()?;
+
+        if endpoint.GetDataFlow()? != flow.data_flow() {
+            return Ok(None);
+        }
+
+        tracing::trace!("got audio endpoint");
+        let audio_client: audio::IAudioClient = device.Activate(com::CLSCTX_ALL, None)?;
+        tracing::trace!("got audio client");
+        Ok(Some(Client {
+            tag,
+            device,
+            audio_client,
+        }))
     }
 }
@@ -0,0 +1,208 @@
+use std::task::{Context, Poll};
+
+use windows::core::PCWSTR;
+use windows::Win32::Media::Audio as audio;
+use windows::Win32::System::Com as com;
+use windows::Win32::UI::Shell::PropertiesSystem as ps;
+
+use crate::event_queue::EventQueue;
+use crate::loom::sync::Arc;
+use crate::wasapi::{from_wide, DeviceState, Error, Flow};
+
+/// An event about a change to the audio endpoints of the system.
+///
+/// See [DeviceNotifications].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DeviceEvent {
+    /// The default endpoint for the given flow has changed, like when the
+    /// user switches headsets. The identifier is `None` if there no longer is
+    /// a default endpoint.
+    DefaultChanged {
+        /// The flow of the endpoint which changed.
+        flow: Flow,
+        /// The identifier of the new default endpoint.
+        id: Option<String>,
+    },
+    /// An endpoint has been added.
+    Added {
+        /// The identifier of the endpoint.
+        id: String,
+    },
+    /// An endpoint has been removed.
+    Removed {
+        /// The identifier of the endpoint.
+        id: String,
+    },
+    /// The state of an endpoint has changed.
+    StateChanged {
+        /// The identifier of the endpoint.
+        id: String,
+        /// The new state of the endpoint, or `None` if it isn't recognized.
+        state: Option<DeviceState>,
+    },
+}
+
+#[windows::core::implement(audio::IMMNotificationClient)]
+struct NotificationClient {
+    queue: Arc<EventQueue<DeviceEvent>>,
+}
+
+#[allow(non_snake_case)]
+impl audio::IMMNotificationClient_Impl for NotificationClient {
+    fn OnDeviceStateChanged(&self, id: &PCWSTR, state: u32) -> windows::core::Result<()> {
+        let id = unsafe { from_wide(id.0) };
+        let state = DeviceState::from_value(state);
+        self.queue.push(DeviceEvent::StateChanged { id, state });
+        Ok(())
+    }
+
+    fn OnDeviceAdded(&self, id: &PCWSTR) -> windows::core::Result<()> {
+        let id = unsafe { from_wide(id.0) };
+        self.queue.push(DeviceEvent::Added { id });
+        Ok(())
+    }
+
+    fn OnDeviceRemoved(&self, id: &PCWSTR) -> windows::core::Result<()> {
+        let id = unsafe { from_wide(id.0) };
+        self.queue.push(DeviceEvent::Removed { id });
+        Ok(())
+    }
+
+    fn OnDefaultDeviceChanged(
+        &self,
+        flow: audio::EDataFlow,
+        role: audio::ERole,
+        id: &PCWSTR,
+    ) -> windows::core::Result<()> {
+        // NB: this is raised once for each role, but we only report the one
+        // which is used when opening default endpoints.
+        if role != audio::eConsole {
+            return Ok(());
+        }
+
+        let flow = match Flow::from_data_flow(flow) {
+            Some(flow) => flow,
+            None => return Ok(()),
+        };
+
+        let id = if id.0.is_null() {
+            None
+        } else {
+            Some(unsafe { from_wide(id.0) })
+        };
+
+        self.queue.push(DeviceEvent::DefaultChanged { flow, id });
+        Ok(())
+    }
+
+    fn OnPropertyValueChanged(&self, _: &PCWSTR, _: &ps::PROPERTYKEY) -> windows::core::Result<()> {
+        Ok(())
+    }
+}
+
+/// A subscription to changes to the audio endpoints of the system, such as
+/// the default endpoint changing.
+///
+/// Events can be received by blocking through [DeviceNotifications::wait] or
+/// asynchronously through [DeviceNotifications::next]. The subscription is
+/// cancelled when this is dropped.
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::wasapi;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// wasapi::audio_prelude();
+///
+/// let notifications = wasapi::DeviceNotifications::new()?;
+///
+/// loop {
+///     if let wasapi::DeviceEvent::DefaultChanged { flow: wasapi::Flow::Render, .. } = notifications.wait() {
+///         // migrate the output stream to the new default endpoint.
+///     }
+/// }
+/// # }
+/// ```
+pub struct DeviceNotifications {
+    enumerator: audio::IMMDeviceEnumerator,
+    client: audio::IMMNotificationClient,
+    queue: Arc<EventQueue<DeviceEvent>>,
+}
+
+impl DeviceNotifications {
+    /// Subscribe to endpoint changes.
+    #[tracing::instrument(skip_all)]
+    pub fn new() -> Result<Self, Error> {
+        let queue = Arc::new(EventQueue::new());
+
+        let client: audio::IMMNotificationClient = NotificationClient {
+            queue: queue.clone(),
+        }
+        .into();
+
+        unsafe {
+            let enumerator: audio::IMMDeviceEnumerator =
+                com::CoCreateInstance(&audio::MMDeviceEnumerator, None, com::CLSCTX_ALL)?;
+
+            enumerator.RegisterEndpointNotificationCallback(&client)?;
+            tracing::trace!("registered endpoint notifications");
+
+            Ok(Self {
+                enumerator,
+                client,
+                queue,
+            })
+        }
+    }
+
+    /// Try to receive the next event without blocking.
+    pub fn try_next(&self) -> Option<DeviceEvent> {
+        self.queue.try_pop()
+    }
+
+    /// Block until the next event is received.
+    pub fn wait(&self) -> DeviceEvent {
+        self.queue.pop()
+    }
+
+    /// Wait for the next event asynchronously.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::wasapi;
+    ///
+    /// # async fn run() -> anyhow::Result<()> {
+    /// let notifications = wasapi::DeviceNotifications::new()?;
+    ///
+    /// loop {
+    ///     let event = notifications.next().await;
+    ///     println!("{:?}", event);
+    /// }
+    /// # }
+    /// ```
+    pub async fn next(&self) -> DeviceEvent {
+        self.queue.pop_async().await
+    }
+
+    pub(crate) fn poll_next(&self, cx: &mut Context<'_>) -> Poll<DeviceEvent> {
+        self.queue.poll_pop(cx)
+    }
+}
+
+impl Drop for DeviceNotifications {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = self
+                .enumerator
+                .UnregisterEndpointNotificationCallback(&self.client);
+        }
+    }
+}
+
+// Safety: notifications are delivered from arbitrary threads and all shared
+// state is synchronized.
+unsafe impl Send for DeviceNotifications {}
+unsafe impl Sync for DeviceNotifications {}
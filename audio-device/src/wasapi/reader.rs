@@ -0,0 +1,116 @@
+use windows::Win32::Foundation as f;
+use windows::Win32::System::Threading as th;
+use windows::Win32::System::WindowsProgramming as wp;
+
+use crate::loom::sync::Arc;
+use crate::wasapi::capture::Capture;
+use crate::wasapi::{Error, Recovered, Sample};
+use crate::windows::{Event, RawEvent};
+
+/// An interleaved type-checked blocking reader of captured audio.
+///
+/// See [InitializedClient::reader][crate::wasapi::InitializedClient::reader].
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::wasapi;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// wasapi::audio_prelude();
+///
+/// let client = match wasapi::default_input_client()? {
+///     Some(client) => client,
+///     None => return Ok(()),
+/// };
+///
+/// let config = client.default_client_config()?;
+/// let initialized = client.initialize::<f32>(config)?;
+/// let mut reader = initialized.reader()?;
+/// client.start()?;
+///
+/// let mut samples = vec![0.0f32; config.channels as usize * 1024];
+/// let mut buf = audio::wrap::interleaved(&mut samples[..], config.channels as usize);
+/// reader.read_interleaved(&mut buf)?;
+/// # Ok(()) }
+/// ```
+pub struct Reader<T> {
+    capture: Capture<T>,
+    event: Arc<Event>,
+}
+
+impl<T> Reader<T>
+where
+    T: Sample,
+{
+    pub(super) fn new(capture: Capture<T>, event: Arc<Event>) -> Self {
+        Self { capture, event }
+    }
+
+    /// Configure if the reader should recover when the device it's capturing
+    /// from is invalidated, such as when a USB device is unplugged.
+    ///
+    /// When enabled, the stream is moved to the new default input device
+    /// with the same configuration and started, after which reading
+    /// continues. Recovery fails with the original error if there's no
+    /// default device or if it doesn't support the same number of channels
+    /// and sample rate. Each recovery is reported through
+    /// [Reader::take_recovered].
+    ///
+    /// Defaults to `false`.
+    pub fn set_recover(&mut self, recover: bool) {
+        self.capture.recover = recover;
+    }
+
+    /// The number of times the reader has recovered from the device being
+    /// invalidated.
+    ///
+    /// See [Reader::set_recover].
+    pub fn recoveries(&self) -> usize {
+        self.capture.recoveries
+    }
+
+    /// Take the most recent recovery from the device being invalidated which
+    /// hasn't been taken yet.
+    ///
+    /// See [Reader::set_recover].
+    pub fn take_recovered(&mut self) -> Option<Recovered> {
+        self.capture.recovered.take()
+    }
+
+    /// Read into an interleaved buffer.
+    ///
+    /// This will block until the remaining frames of the buffer have been
+    /// filled with captured audio. Frames which the device reports as silent
+    /// are filled with [Sample::MID].
+    pub fn read_interleaved<B>(&mut self, mut buf: B) -> Result<(), Error>
+    where
+        B: audio_core::Buf<Sample = T>
+            + audio_core::WriteBuf
+            + audio_core::InterleavedBufMut<Sample = T>,
+    {
+        self.capture.check_channels(&buf)?;
+
+        while buf.has_remaining_mut() {
+            let event = unsafe { self.event.raw_event() };
+
+            match self.capture.read_packet_or_recover(&mut buf, event)? {
+                Some(0) => break,
+                Some(_) => (),
+                None => unsafe {
+                    match th::WaitForSingleObject(self.event.raw_event(), wp::INFINITE) {
+                        f::WAIT_OBJECT_0 => (),
+                        _ => {
+                            return Err(Error::from(windows::core::Error::from_win32()));
+                        }
+                    }
+                },
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Safety: thread safety is ensured through tagging with ste::Tag.
+unsafe impl<T> Send for Reader<T> {}
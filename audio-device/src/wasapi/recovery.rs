@@ -0,0 +1,64 @@
+use windows::Win32::Foundation as f;
+use windows::Win32::Media::Audio as audio;
+
+use crate::wasapi::{ClientConfig, Error, Flow, Sample};
+
+/// Reported when a stream has recovered from the device it was using being
+/// invalidated, such as when a USB device is unplugged.
+///
+/// See [RenderClient::take_recovered][crate::wasapi::RenderClient::take_recovered]
+/// and [Reader::take_recovered][crate::wasapi::Reader::take_recovered].
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub struct Recovered {
+    /// The configuration the stream was reopened with on the new default
+    /// device.
+    pub config: ClientConfig,
+}
+
+/// Test if the error was raised because the device was invalidated.
+pub(super) fn is_invalidated(error: &Error) -> bool {
+    matches!(error, Error::Sys(e) if e.code() == audio::AUDCLNT_E_DEVICE_INVALIDATED)
+}
+
+/// Reopen a stream with the given configuration on the default device for the
+/// given flow, signalling the given event.
+///
+/// The new device has to support the same number of channels and sample rate
+/// as the stream was using, otherwise `error` is returned since the stream
+/// can't continue where it left off.
+#[tracing::instrument(skip_all, fields(?flow))]
+pub(super) fn reopen<T>(
+    flow: Flow,
+    config: ClientConfig,
+    event: f::HANDLE,
+    error: Error,
+) -> Result<(audio::IAudioClient, ClientConfig), Error>
+where
+    T: Sample,
+{
+    tracing::warn!("device invalidated, reopening default device");
+
+    let client = match super::default_client(flow)? {
+        Some(client) => client,
+        None => return Err(error),
+    };
+
+    let new = client.initialize_shared::<T>(config)?;
+
+    if new.channels != config.channels || new.sample_rate != config.sample_rate {
+        tracing::warn!(
+            channels = new.channels,
+            sample_rate = new.sample_rate,
+            "default device doesn't support the configuration of the stream"
+        );
+
+        return Err(error);
+    }
+
+    unsafe {
+        client.audio_client.SetEventHandle(event)?;
+    }
+
+    Ok((client.audio_client, new))
+}
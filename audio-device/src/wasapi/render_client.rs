@@ -1,7 +1,9 @@
 use crate::loom::sync::Arc;
-use crate::wasapi::{BufferMut, Error};
+use crate::wasapi::recovery;
+use crate::wasapi::{BufferMut, ClientConfig, Error, Flow, Recovered, Sample};
 use crate::windows::{Event, RawEvent};
 use std::marker;
+use std::time::Duration;
 use windows::Win32::Media::Audio as audio;
 use windows::Win32::System::Threading as th;
 use windows::Win32::System::WindowsProgramming as wp;
@@ -14,11 +16,133 @@ pub struct RenderClient<T, E> {
     pub(super) render_client: audio::IAudioRenderClient,
     pub(super) buffer_size: u32,
     pub(super) channels: usize,
+    pub(super) config: ClientConfig,
     pub(super) event: Arc<E>,
+    pub(super) recover: bool,
+    pub(super) recoveries: usize,
+    pub(super) recovered: Option<Recovered>,
     pub(super) _marker: marker::PhantomData<T>,
 }
 
 impl<T, E> RenderClient<T, E> {
+    /// The size of the buffer shared with the device in frames.
+    pub fn buffer_size(&self) -> u32 {
+        self.buffer_size
+    }
+
+    /// Get the number of frames which can currently be written without
+    /// blocking.
+    pub fn available_frames(&self) -> Result<u32, Error> {
+        self.tag.ensure_on_thread();
+        let padding = self.get_current_padding()?;
+        Ok(self.buffer_size.saturating_sub(padding))
+    }
+
+    /// Get the latency of the frames which have been written but not yet
+    /// played by the device.
+    pub fn latency(&self) -> Result<Duration, Error> {
+        self.tag.ensure_on_thread();
+        let padding = self.get_current_padding()?;
+
+        if self.config.sample_rate == 0 {
+            return Ok(Duration::ZERO);
+        }
+
+        let nanos = padding as u64 * 1_000_000_000 / self.config.sample_rate as u64;
+        Ok(Duration::from_nanos(nanos))
+    }
+
+    /// Configure if the client should recover when the device it's playing
+    /// through is invalidated, such as when a USB device is unplugged.
+    ///
+    /// When enabled, the stream is moved to the new default output device
+    /// with the same configuration and started, after which writing
+    /// continues. Recovery fails with the original error if there's no
+    /// default device or if it doesn't support the same number of channels
+    /// and sample rate. Each recovery is reported through
+    /// [RenderClient::take_recovered].
+    ///
+    /// Frames which were handed to the device when it was invalidated are
+    /// lost. A [BufferMut] which is released after the device has been
+    /// invalidated fails, and the stream is recovered the next time a buffer
+    /// is requested.
+    ///
+    /// Since the stream is restarted on a new device, it can't be stopped
+    /// through the [Client][crate::wasapi::Client] it was constructed from
+    /// after it has recovered.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::wasapi;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// wasapi::audio_prelude();
+    ///
+    /// let client = match wasapi::default_output_client()? {
+    ///     Some(client) => client,
+    ///     None => return Ok(()),
+    /// };
+    ///
+    /// let config = client.default_client_config()?;
+    /// let initialized = client.initialize::<f32>(config)?;
+    /// let mut render_client = initialized.render_client()?;
+    /// render_client.set_recover(true);
+    /// # Ok(()) }
+    /// ```
+    pub fn set_recover(&mut self, recover: bool) {
+        self.recover = recover;
+    }
+
+    /// The number of times the client has recovered from the device being
+    /// invalidated.
+    ///
+    /// See [RenderClient::set_recover].
+    pub fn recoveries(&self) -> usize {
+        self.recoveries
+    }
+
+    /// Take the most recent recovery from the device being invalidated which
+    /// hasn't been taken yet.
+    ///
+    /// See [RenderClient::set_recover].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use audio_device::wasapi;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// wasapi::audio_prelude();
+    ///
+    /// let client = match wasapi::default_output_client()? {
+    ///     Some(client) => client,
+    ///     None => return Ok(()),
+    /// };
+    ///
+    /// let config = client.default_client_config()?;
+    /// let initialized = client.initialize::<f32>(config)?;
+    /// let mut render_client = initialized.render_client()?;
+    /// render_client.set_recover(true);
+    /// client.start()?;
+    ///
+    /// let samples = vec![0.0f32; config.channels as usize * 1024];
+    ///
+    /// loop {
+    ///     render_client.write_interleaved(audio::wrap::interleaved(&samples[..], config.channels as usize))?;
+    ///
+    ///     if let Some(recovered) = render_client.take_recovered() {
+    ///         println!("moved to a new device: {:?}", recovered.config);
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn take_recovered(&mut self) -> Option<Recovered> {
+        self.recovered.take()
+    }
+
     fn get_current_padding(&self) -> Result<u32, Error> {
         unsafe {
             let padding = self.audio_client
@@ -36,9 +160,65 @@ impl<T, E> RenderClient<T, E> {
             Ok(data as *mut T)
         }
     }
+
+    /// Release the given number of frames of the buffer to the device.
+    fn release_buffer(&self, frames: u32) -> Result<(), Error> {
+        unsafe {
+            self.render_client.ReleaseBuffer(frames, 0)?;
+            Ok(())
+        }
+    }
 }
 
-impl<T> RenderClient<T, Event> {
+impl<T, E> RenderClient<T, E>
+where
+    T: Sample,
+    E: RawEvent,
+{
+    /// Get the number of available frames, recovering from the device being
+    /// invalidated if it's enabled.
+    fn available_frames_or_recover(&mut self) -> Result<u32, Error> {
+        loop {
+            match self.available_frames() {
+                Ok(frames) => return Ok(frames),
+                Err(e) => self.recover_or_fail(e)?,
+            }
+        }
+    }
+
+    /// Recover from the device being invalidated if the error was raised
+    /// because of it and recovery is enabled, otherwise return the error.
+    fn recover_or_fail(&mut self, error: Error) -> Result<(), Error> {
+        if !self.recover || !recovery::is_invalidated(&error) {
+            return Err(error);
+        }
+
+        let event = unsafe { self.event.raw_event() };
+        let (audio_client, config) =
+            recovery::reopen::<T>(Flow::Render, self.config, event, error)?;
+
+        unsafe {
+            let buffer_size = audio_client.GetBufferSize()?;
+            let render_client: audio::IAudioRenderClient = audio_client.GetService()?;
+            audio_client.Start()?;
+
+            self.audio_client = audio_client;
+            self.render_client = render_client;
+            self.buffer_size = buffer_size;
+        }
+
+        self.config = config;
+        self.recoveries += 1;
+        self.recovered = Some(Recovered { config });
+        tracing::trace!(recoveries = self.recoveries, "recovered stream");
+        Ok(())
+    }
+}
+
+impl<T> RenderClient<T, Event>
+where
+    T: Sample,
+{
     /// Get access to the raw mutable buffer.
     ///
     /// This will block until it is appropriate to submit a buffer.
@@ -54,20 +234,26 @@ impl<T> RenderClient<T, Event> {
                     }
                 }
 
-                let padding = self.get_current_padding()?;
-                let frames = self.buffer_size.saturating_sub(padding);
+                let frames = self.available_frames_or_recover()?;
 
                 if frames == 0 {
                     continue;
                 }
 
-                let data = self.get_buffer(frames)?;
+                let data = match self.get_buffer(frames) {
+                    Ok(data) => data,
+                    Err(e) => {
+                        self.recover_or_fail(e)?;
+                        continue;
+                    }
+                };
 
                 return Ok(BufferMut {
                     tag: self.tag,
                     render_client: &mut self.render_client,
                     data,
                     frames,
+                    channels: self.channels,
                     len: frames as usize * self.channels,
                     in_use: true,
                     _marker: marker::PhantomData,
@@ -75,34 +261,248 @@ impl<T> RenderClient<T, Event> {
             }
         }
     }
+
+    /// Write all frames in a uniform buffer.
+    ///
+    /// This accepts buffers of any layout, frames are written directly into
+    /// the buffer provided by the device as it becomes available. This will
+    /// block until all frames have been submitted.
+    pub fn write_frames<B>(&mut self, buf: B) -> Result<(), Error>
+    where
+        B: audio_core::UniformBuf<Sample = T>,
+    {
+        use audio_core::Frame as _;
+
+        if buf.channels() != self.channels {
+            return Err(Error::ChannelsMismatch {
+                actual: buf.channels(),
+                expected: self.channels,
+            });
+        }
+
+        self.tag.ensure_on_thread();
+
+        let mut frames = buf.iter_frames().peekable();
+
+        while frames.peek().is_some() {
+            unsafe {
+                match th::WaitForSingleObject(self.event.raw_event(), wp::INFINITE) {
+                    f::WAIT_OBJECT_0 => (),
+                    _ => {
+                        return Err(Error::from(windows::core::Error::from_win32()));
+                    }
+                }
+
+                let available = self.available_frames_or_recover()? as usize;
+
+                if available == 0 {
+                    continue;
+                }
+
+                let data = match self.get_buffer(available as u32) {
+                    Ok(data) => data,
+                    Err(e) => {
+                        self.recover_or_fail(e)?;
+                        continue;
+                    }
+                };
+
+                let mut written = 0;
+
+                for frame in (&mut frames).take(available) {
+                    let base = data.add(written * self.channels);
+
+                    for (n, sample) in frame.iter().enumerate() {
+                        base.add(n).write(sample);
+                    }
+
+                    written += 1;
+                }
+
+                if let Err(e) = self.release_buffer(written as u32) {
+                    self.recover_or_fail(e)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write the remaining frames of an interleaved buffer.
+    ///
+    /// Frames are copied into the buffer provided by the device as it becomes
+    /// available. This will block until all frames have been submitted.
+    pub fn write_interleaved<B>(&mut self, mut buf: B) -> Result<(), Error>
+    where
+        B: audio_core::ReadBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBuf<Sample = T>,
+    {
+        if buf.channels() != self.channels {
+            return Err(Error::ChannelsMismatch {
+                actual: buf.channels(),
+                expected: self.channels,
+            });
+        }
+
+        self.tag.ensure_on_thread();
+
+        while buf.has_remaining() {
+            unsafe {
+                match th::WaitForSingleObject(self.event.raw_event(), wp::INFINITE) {
+                    f::WAIT_OBJECT_0 => (),
+                    _ => {
+                        return Err(Error::from(windows::core::Error::from_win32()));
+                    }
+                }
+
+                let available = self.available_frames_or_recover()? as usize;
+                let frames = usize::min(available, buf.frames());
+
+                if frames == 0 {
+                    continue;
+                }
+
+                let data = match self.get_buffer(frames as u32) {
+                    Ok(data) => data,
+                    Err(e) => {
+                        self.recover_or_fail(e)?;
+                        continue;
+                    }
+                };
+
+                let from = &buf.as_interleaved()[..frames * self.channels];
+                std::ptr::copy_nonoverlapping(from.as_ptr(), data, from.len());
+
+                // NB: the frames are written again to the new device if it
+                // was invalidated before they were released.
+                if let Err(e) = self.release_buffer(frames as u32) {
+                    self.recover_or_fail(e)?;
+                    continue;
+                }
+
+                buf.advance(frames);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Render audio from the given block source and write it to the device
+    /// until the source is exhausted.
+    ///
+    /// Blocks are rendered into the provided interleaved buffer `buf` before
+    /// they are copied into the device buffer, so its size determines the
+    /// block size. This will block until all rendered frames have been
+    /// submitted.
+    pub fn write_source<S, B>(&mut self, mut source: S, mut buf: B) -> Result<(), Error>
+    where
+        S: audio_core::BlockSource<Sample = T>,
+        B: audio_core::BufMut<Sample = T> + audio_core::InterleavedBuf<Sample = T>,
+    {
+        if buf.channels() != self.channels {
+            return Err(Error::ChannelsMismatch {
+                actual: buf.channels(),
+                expected: self.channels,
+            });
+        }
+
+        self.tag.ensure_on_thread();
+
+        let frames = buf
+            .as_interleaved()
+            .len()
+            .checked_div(self.channels)
+            .unwrap_or_default();
+
+        loop {
+            // NB: clamp since the source is not trusted to stay in bounds.
+            let len = usize::min(source.render(&mut buf), frames);
+
+            if len == 0 {
+                return Ok(());
+            }
+
+            let mut offset = 0;
+
+            while offset < len {
+                unsafe {
+                    match th::WaitForSingleObject(self.event.raw_event(), wp::INFINITE) {
+                        f::WAIT_OBJECT_0 => (),
+                        _ => {
+                            return Err(Error::from(windows::core::Error::from_win32()));
+                        }
+                    }
+
+                    let available = self.available_frames_or_recover()? as usize;
+                    let available = usize::min(available, len - offset);
+
+                    if available == 0 {
+                        continue;
+                    }
+
+                    let data = match self.get_buffer(available as u32) {
+                        Ok(data) => data,
+                        Err(e) => {
+                            self.recover_or_fail(e)?;
+                            continue;
+                        }
+                    };
+
+                    let from = &buf.as_interleaved()[offset * self.channels..]
+                        [..available * self.channels];
+                    std::ptr::copy_nonoverlapping(from.as_ptr(), data, from.len());
+
+                    if let Err(e) = self.release_buffer(available as u32) {
+                        self.recover_or_fail(e)?;
+                        continue;
+                    }
+
+                    offset += available;
+                }
+            }
+        }
+    }
 }
 
 cfg_events_driver! {
     use crate::windows::AsyncEvent;
+    use std::pin::Pin;
+    use std::ptr;
+    use std::task::{Context, Poll};
 
-    impl<T> RenderClient<T, AsyncEvent> {
+    impl<T> RenderClient<T, AsyncEvent>
+    where
+        T: Sample,
+    {
         /// Get access to the raw mutable buffer.
         ///
         /// This will block until it is appropriate to submit a buffer.
         pub async fn buffer_mut_async(&mut self) -> Result<BufferMut<'_, T>, Error> {
             loop {
-                self.event.wait().await;
+                self.event.wait().await?;
                 self.tag.ensure_on_thread();
 
-                let padding = self.get_current_padding()?;
-                let frames = self.buffer_size.saturating_sub(padding);
+                let frames = self.available_frames_or_recover()?;
 
                 if frames == 0 {
                     continue;
                 }
 
-                let data = self.get_buffer(frames)?;
+                let data = match self.get_buffer(frames) {
+                    Ok(data) => data,
+                    Err(e) => {
+                        self.recover_or_fail(e)?;
+                        continue;
+                    }
+                };
 
                 return Ok(BufferMut {
                     tag: self.tag,
                     render_client: &mut self.render_client,
                     data,
                     frames,
+                    channels: self.channels,
                     len: frames as usize * self.channels,
                     in_use: true,
                     _marker: marker::PhantomData,
@@ -110,7 +510,79 @@ cfg_events_driver! {
             }
         }
     }
+
+    impl<T> audio_core::AsyncWriteBuf for RenderClient<T, AsyncEvent>
+    where
+        T: Sample,
+    {
+        type Sample = T;
+        type Error = Error;
+
+        fn poll_write_buf<B>(
+            self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+            mut buf: B,
+        ) -> Poll<Result<(), Error>>
+        where
+            B: audio_core::ReadBuf
+                + audio_core::ExactSizeBuf<Sample = T>
+                + audio_core::InterleavedBuf<Sample = T>,
+        {
+            let this = self.get_mut();
+
+            if buf.channels() != this.channels {
+                return Poll::Ready(Err(Error::ChannelsMismatch {
+                    actual: buf.channels(),
+                    expected: this.channels,
+                }));
+            }
+
+            while buf.has_remaining() {
+                // NB: only wait for the device when there's no room in its
+                // buffer, otherwise we write exactly as much as fits.
+                let available = this.available_frames_or_recover()? as usize;
+
+                if available == 0 {
+                    match this.event.poll_wait(cx) {
+                        Poll::Ready(result) => result?,
+                        Poll::Pending => return Poll::Pending,
+                    }
+
+                    continue;
+                }
+
+                let frames = usize::min(available, buf.frames());
+
+                let data = match this.get_buffer(frames as u32) {
+                    Ok(data) => data,
+                    Err(e) => {
+                        this.recover_or_fail(e)?;
+                        continue;
+                    }
+                };
+
+                let from = &buf.as_interleaved()[..frames * this.channels];
+
+                unsafe {
+                    ptr::copy_nonoverlapping(from.as_ptr(), data, from.len());
+                }
+
+                if let Err(e) = this.release_buffer(frames as u32) {
+                    this.recover_or_fail(e)?;
+                    continue;
+                }
+
+                buf.advance(frames);
+            }
+
+            Poll::Ready(Ok(()))
+        }
+    }
 }
 
+// Note: the sample and event types are only used as markers or through
+// shared references.
+impl<T, E> Unpin for RenderClient<T, E> {}
+
 // Safety: thread safety is ensured through tagging with ste::Tag.
 unsafe impl<T, E> Send for RenderClient<T, E> {}
@@ -1,4 +1,6 @@
+use audio_core::PackedI24;
 use std::mem;
+use windows::core::GUID;
 use windows::Win32::Media::Audio as audio;
 use windows::Win32::Media::KernelStreaming as ks;
 use windows::Win32::Media::Multimedia as mm;
@@ -22,6 +24,12 @@ unsafe impl Sample for i16 {
     const MID: Self = 0;
 
     fn mix_format(config: ClientConfig) -> audio::WAVEFORMATEXTENSIBLE {
+        // NB: plain PCM formats can't describe the speaker positions of more
+        // than two channels, so those need to use the extensible format.
+        if config.channels > 2 || config.channel_mask != 0 {
+            return extensible_format::<Self>(config, 16, ks::KSDATAFORMAT_SUBTYPE_PCM);
+        }
+
         let avg_bytes_per_sec =
             config.channels as u32 * config.sample_rate * mem::size_of::<Self>() as u32;
         let bits_per_sample = mem::size_of::<Self>() as u16 * 8;
@@ -41,20 +49,39 @@ unsafe impl Sample for i16 {
                 wValidBitsPerSample: 0,
             },
             dwChannelMask: 0,
-            SubFormat: windows::core::GUID::zeroed(),
+            SubFormat: GUID::zeroed(),
         }
     }
 
     unsafe fn is_compatible_with(mix_format: *const audio::WAVEFORMATEX) -> bool {
-        let bits_per_sample = (*mix_format).wBitsPerSample;
+        is_pcm_format(mix_format, 16, &[16])
+    }
+}
 
-        match (*mix_format).wFormatTag as u32 {
-            audio::WAVE_FORMAT_PCM => {
-                assert!((*mix_format).cbSize == 0);
-                bits_per_sample == 16
-            }
-            _ => false,
-        }
+unsafe impl Sample for i32 {
+    const MID: Self = 0;
+
+    fn mix_format(config: ClientConfig) -> audio::WAVEFORMATEXTENSIBLE {
+        extensible_format::<Self>(config, 32, ks::KSDATAFORMAT_SUBTYPE_PCM)
+    }
+
+    unsafe fn is_compatible_with(mix_format: *const audio::WAVEFORMATEX) -> bool {
+        // NB: 24-bit samples in a 32-bit container are aligned to the most
+        // significant bits, so they can be written as full scale 32-bit
+        // samples.
+        is_pcm_format(mix_format, 32, &[24, 32])
+    }
+}
+
+unsafe impl Sample for PackedI24 {
+    const MID: Self = PackedI24::from_le_bytes([0; 3]);
+
+    fn mix_format(config: ClientConfig) -> audio::WAVEFORMATEXTENSIBLE {
+        extensible_format::<Self>(config, 24, ks::KSDATAFORMAT_SUBTYPE_PCM)
+    }
+
+    unsafe fn is_compatible_with(mix_format: *const audio::WAVEFORMATEX) -> bool {
+        is_pcm_format(mix_format, 24, &[24])
     }
 }
 
@@ -62,29 +89,7 @@ unsafe impl Sample for f32 {
     const MID: Self = 0.0;
 
     fn mix_format(config: ClientConfig) -> audio::WAVEFORMATEXTENSIBLE {
-        let avg_bytes_per_sec =
-            config.channels as u32 * config.sample_rate * mem::size_of::<Self>() as u32;
-        let bits_per_sample = mem::size_of::<Self>() as u16 * 8;
-        let block_align = config.channels as u16 * mem::size_of::<Self>() as u16;
-        let cb_size = mem::size_of::<audio::WAVEFORMATEXTENSIBLE>() as u16
-            - mem::size_of::<audio::WAVEFORMATEX>() as u16;
-
-        audio::WAVEFORMATEXTENSIBLE {
-            Format: audio::WAVEFORMATEX {
-                wFormatTag: ks::WAVE_FORMAT_EXTENSIBLE as u16,
-                nChannels: config.channels,
-                nSamplesPerSec: config.sample_rate,
-                nAvgBytesPerSec: avg_bytes_per_sec,
-                nBlockAlign: block_align,
-                wBitsPerSample: bits_per_sample,
-                cbSize: cb_size,
-            },
-            Samples: audio::WAVEFORMATEXTENSIBLE_0 {
-                wValidBitsPerSample: bits_per_sample,
-            },
-            dwChannelMask: 0,
-            SubFormat: mm::KSDATAFORMAT_SUBTYPE_IEEE_FLOAT,
-        }
+        extensible_format::<Self>(config, 32, mm::KSDATAFORMAT_SUBTYPE_IEEE_FLOAT)
     }
 
     unsafe fn is_compatible_with(mix_format: *const audio::WAVEFORMATEX) -> bool {
@@ -105,3 +110,64 @@ unsafe impl Sample for f32 {
         }
     }
 }
+
+/// Construct an extensible wave format for samples of type `T` which have the
+/// given number of valid bits.
+fn extensible_format<T>(
+    config: ClientConfig,
+    valid_bits_per_sample: u16,
+    sub_format: GUID,
+) -> audio::WAVEFORMATEXTENSIBLE {
+    let avg_bytes_per_sec =
+        config.channels as u32 * config.sample_rate * mem::size_of::<T>() as u32;
+    let bits_per_sample = mem::size_of::<T>() as u16 * 8;
+    let block_align = config.channels as u16 * mem::size_of::<T>() as u16;
+    let cb_size = mem::size_of::<audio::WAVEFORMATEXTENSIBLE>() as u16
+        - mem::size_of::<audio::WAVEFORMATEX>() as u16;
+
+    audio::WAVEFORMATEXTENSIBLE {
+        Format: audio::WAVEFORMATEX {
+            wFormatTag: ks::WAVE_FORMAT_EXTENSIBLE as u16,
+            nChannels: config.channels,
+            nSamplesPerSec: config.sample_rate,
+            nAvgBytesPerSec: avg_bytes_per_sec,
+            nBlockAlign: block_align,
+            wBitsPerSample: bits_per_sample,
+            cbSize: cb_size,
+        },
+        Samples: audio::WAVEFORMATEXTENSIBLE_0 {
+            wValidBitsPerSample: valid_bits_per_sample,
+        },
+        dwChannelMask: config.effective_channel_mask(),
+        SubFormat: sub_format,
+    }
+}
+
+/// Test if the given format is an integer PCM format with the given container
+/// size and one of the given numbers of valid bits.
+unsafe fn is_pcm_format(
+    mix_format: *const audio::WAVEFORMATEX,
+    bits_per_sample: u16,
+    valid_bits_per_sample: &[u16],
+) -> bool {
+    if (*mix_format).wBitsPerSample != bits_per_sample {
+        return false;
+    }
+
+    match (*mix_format).wFormatTag as u32 {
+        ks::WAVE_FORMAT_EXTENSIBLE => {
+            debug_assert_eq! {
+                (*mix_format).cbSize as usize,
+                mem::size_of::<audio::WAVEFORMATEXTENSIBLE>() - mem::size_of::<audio::WAVEFORMATEX>()
+            };
+
+            let mix_format = mix_format as *const audio::WAVEFORMATEXTENSIBLE;
+            let valid_bits = (*mix_format).Samples.wValidBitsPerSample;
+
+            valid_bits_per_sample.contains(&valid_bits)
+                && matches!((*mix_format).SubFormat, ks::KSDATAFORMAT_SUBTYPE_PCM)
+        }
+        audio::WAVE_FORMAT_PCM => true,
+        _ => false,
+    }
+}
@@ -0,0 +1,236 @@
+use windows::core::{GUID, PCWSTR};
+use windows::Win32::Foundation as f;
+use windows::Win32::Media::Audio as audio;
+
+use crate::event_queue::EventQueue;
+use crate::loom::sync::Arc;
+use crate::wasapi::Error;
+
+/// The state of an audio session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SessionState {
+    /// The session has no active streams.
+    Inactive,
+    /// The session has at least one active stream.
+    Active,
+    /// The session has no remaining clients.
+    Expired,
+}
+
+impl SessionState {
+    pub(super) fn from_state(state: audio::AudioSessionState) -> Option<Self> {
+        Some(match state {
+            audio::AudioSessionStateInactive => Self::Inactive,
+            audio::AudioSessionStateActive => Self::Active,
+            audio::AudioSessionStateExpired => Self::Expired,
+            _ => return None,
+        })
+    }
+}
+
+/// The reason an audio session was disconnected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DisconnectReason {
+    /// The device the session plays through was removed.
+    DeviceRemoval,
+    /// The audio service was stopped.
+    ServerShutdown,
+    /// The shared mode format of the device changed.
+    FormatChanged,
+    /// The user logged off the session.
+    SessionLogoff,
+    /// The session was disconnected from a remote desktop.
+    SessionDisconnected,
+    /// An exclusive mode stream took over the device.
+    ExclusiveModeOverride,
+}
+
+impl DisconnectReason {
+    pub(super) fn from_reason(reason: audio::AudioSessionDisconnectReason) -> Option<Self> {
+        Some(match reason {
+            audio::DisconnectReasonDeviceRemoval => Self::DeviceRemoval,
+            audio::DisconnectReasonServerShutdown => Self::ServerShutdown,
+            audio::DisconnectReasonFormatChanged => Self::FormatChanged,
+            audio::DisconnectReasonSessionLogoff => Self::SessionLogoff,
+            audio::DisconnectReasonSessionDisconnected => Self::SessionDisconnected,
+            audio::DisconnectReasonExclusiveModeOverride => Self::ExclusiveModeOverride,
+            _ => return None,
+        })
+    }
+}
+
+/// An event about a change to the audio session a client belongs to.
+///
+/// See [SessionEvents].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum SessionEvent {
+    /// The volume or mute state of the session has changed.
+    VolumeChanged {
+        /// The new volume of the session in the range `0.0` to `1.0`.
+        volume: f32,
+        /// If the session is muted.
+        muted: bool,
+    },
+    /// The state of the session has changed. The state is `None` if it isn't
+    /// recognized.
+    StateChanged(Option<SessionState>),
+    /// The session has been disconnected and the client can no longer be
+    /// used. The reason is `None` if it isn't recognized.
+    Disconnected(Option<DisconnectReason>),
+}
+
+#[windows::core::implement(audio::IAudioSessionEvents)]
+struct SessionEventsClient {
+    queue: Arc<EventQueue<SessionEvent>>,
+}
+
+#[allow(non_snake_case)]
+impl audio::IAudioSessionEvents_Impl for SessionEventsClient {
+    fn OnDisplayNameChanged(&self, _: &PCWSTR, _: *const GUID) -> windows::core::Result<()> {
+        Ok(())
+    }
+
+    fn OnIconPathChanged(&self, _: &PCWSTR, _: *const GUID) -> windows::core::Result<()> {
+        Ok(())
+    }
+
+    fn OnSimpleVolumeChanged(
+        &self,
+        volume: f32,
+        muted: f::BOOL,
+        _: *const GUID,
+    ) -> windows::core::Result<()> {
+        self.queue.push(SessionEvent::VolumeChanged {
+            volume,
+            muted: muted.as_bool(),
+        });
+
+        Ok(())
+    }
+
+    fn OnChannelVolumeChanged(
+        &self,
+        _: u32,
+        _: *const f32,
+        _: u32,
+        _: *const GUID,
+    ) -> windows::core::Result<()> {
+        Ok(())
+    }
+
+    fn OnGroupingParamChanged(&self, _: *const GUID, _: *const GUID) -> windows::core::Result<()> {
+        Ok(())
+    }
+
+    fn OnStateChanged(&self, state: audio::AudioSessionState) -> windows::core::Result<()> {
+        let state = SessionState::from_state(state);
+        self.queue.push(SessionEvent::StateChanged(state));
+        Ok(())
+    }
+
+    fn OnSessionDisconnected(
+        &self,
+        reason: audio::AudioSessionDisconnectReason,
+    ) -> windows::core::Result<()> {
+        let reason = DisconnectReason::from_reason(reason);
+        self.queue.push(SessionEvent::Disconnected(reason));
+        Ok(())
+    }
+}
+
+/// A subscription to changes to the audio session a client belongs to, such
+/// as the session being muted or disconnected.
+///
+/// Events can be received by blocking through [SessionEvents::wait] or
+/// asynchronously through [SessionEvents::next]. The subscription is cancelled
+/// when this is dropped.
+///
+/// See [InitializedClient::session_events][crate::wasapi::InitializedClient::session_events].
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::wasapi;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// wasapi::audio_prelude();
+///
+/// let client = match wasapi::default_output_client()? {
+///     Some(client) => client,
+///     None => return Ok(()),
+/// };
+///
+/// let config = client.default_client_config()?;
+/// let client = client.initialize::<f32>(config)?;
+/// let events = client.session_events()?;
+///
+/// loop {
+///     if let wasapi::SessionEvent::Disconnected(reason) = events.wait() {
+///         println!("session disconnected: {:?}", reason);
+///         break;
+///     }
+/// }
+/// # Ok(()) }
+/// ```
+pub struct SessionEvents {
+    control: audio::IAudioSessionControl,
+    client: audio::IAudioSessionEvents,
+    queue: Arc<EventQueue<SessionEvent>>,
+}
+
+impl SessionEvents {
+    /// Subscribe to events for the given session.
+    pub(super) fn new(control: audio::IAudioSessionControl) -> Result<Self, Error> {
+        let queue = Arc::new(EventQueue::new());
+
+        let client: audio::IAudioSessionEvents = SessionEventsClient {
+            queue: queue.clone(),
+        }
+        .into();
+
+        unsafe {
+            control.RegisterAudioSessionNotification(&client)?;
+        }
+
+        tracing::trace!("registered session notifications");
+
+        Ok(Self {
+            control,
+            client,
+            queue,
+        })
+    }
+
+    /// Try to receive the next event without blocking.
+    pub fn try_next(&self) -> Option<SessionEvent> {
+        self.queue.try_pop()
+    }
+
+    /// Block until the next event is received.
+    pub fn wait(&self) -> SessionEvent {
+        self.queue.pop()
+    }
+
+    /// Wait for the next event asynchronously.
+    pub async fn next(&self) -> SessionEvent {
+        self.queue.pop_async().await
+    }
+}
+
+impl Drop for SessionEvents {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = self
+                .control
+                .UnregisterAudioSessionNotification(&self.client);
+        }
+    }
+}
+
+// Safety: notifications are delivered from arbitrary threads and all shared
+// state is synchronized.
+unsafe impl Send for SessionEvents {}
+unsafe impl Sync for SessionEvents {}
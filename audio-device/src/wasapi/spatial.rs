@@ -0,0 +1,361 @@
+use std::marker;
+use std::mem;
+use std::ptr;
+use std::slice;
+
+use windows::Win32::Foundation as f;
+use windows::Win32::Media::Audio as audio;
+use windows::Win32::System::Com as com;
+use windows::Win32::System::Com::StructuredStorage as ss;
+use windows::Win32::System::Threading as th;
+use windows::Win32::System::WindowsProgramming as wp;
+
+use crate::loom::sync::Arc;
+use crate::wasapi::{ClientConfig, Error, Sample};
+use crate::windows::{Event, RawEvent};
+
+/// A client for rendering spatial audio objects, which are positioned in 3D
+/// space around the listener by the spatial audio engine of the system.
+///
+/// Constructed through [Client::spatial_client][crate::wasapi::Client::spatial_client].
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::wasapi;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// wasapi::audio_prelude();
+///
+/// let client = match wasapi::default_output_client()? {
+///     Some(client) => client,
+///     None => return Ok(()),
+/// };
+///
+/// let config = client.default_client_config()?;
+/// let spatial = client.spatial_client()?;
+/// let mut stream = spatial.initialize::<f32>(config, 1)?;
+/// let mut object = stream.activate_dynamic_object()?;
+///
+/// stream.start()?;
+///
+/// loop {
+///     let update = stream.update()?;
+///     object.set_position(1.0, 0.0, -1.0)?;
+///
+///     for sample in object.buffer(&update)? {
+///         *sample = 0.0;
+///     }
+///
+///     update.end()?;
+/// }
+/// # }
+/// ```
+pub struct SpatialClient {
+    pub(super) tag: ste::Tag,
+    pub(super) client: audio::ISpatialAudioClient,
+}
+
+impl SpatialClient {
+    /// Get the maximum number of dynamic objects which can be rendered at the
+    /// same time by the endpoint.
+    pub fn max_dynamic_object_count(&self) -> Result<u32, Error> {
+        self.tag.ensure_on_thread();
+
+        unsafe { Ok(self.client.GetMaxDynamicObjectCount()?) }
+    }
+
+    /// Initialize a stream for rendering up to `max_dynamic_objects` dynamic
+    /// objects with the given configuration.
+    ///
+    /// Each object is a single channel of audio, so the number of channels and
+    /// the channel mask of the configuration are ignored. If the sample type
+    /// and rate isn't supported for objects this will error with
+    /// [Error::UnsupportedMixFormat].
+    #[tracing::instrument(skip_all)]
+    pub fn initialize<T>(
+        &self,
+        config: ClientConfig,
+        max_dynamic_objects: u32,
+    ) -> Result<SpatialRenderStream<T>, Error>
+    where
+        T: Sample,
+    {
+        self.tag.ensure_on_thread();
+
+        let config = ClientConfig {
+            channels: 1,
+            channel_mask: 0,
+            ..config
+        };
+
+        unsafe {
+            let mix_format = T::mix_format(config);
+
+            if self
+                .client
+                .IsAudioObjectFormatSupported(&mix_format.Format)
+                .is_err()
+            {
+                return Err(Error::UnsupportedMixFormat);
+            }
+
+            let event = Arc::new(Event::new(false, false)?);
+
+            let mut params = audio::SpatialAudioObjectRenderStreamActivationParams {
+                ObjectFormat: &mix_format.Format,
+                StaticObjectTypeMask: audio::AudioObjectType_None,
+                MinDynamicObjectCount: 0,
+                MaxDynamicObjectCount: max_dynamic_objects,
+                Category: audio::AudioCategory_GameEffects,
+                EventHandle: event.raw_event(),
+                NotifyObject: mem::ManuallyDrop::new(None),
+            };
+
+            // NB: the activation parameters are passed as a blob, which is
+            // only borrowed for the duration of the call.
+            let mut activation = ss::PROPVARIANT::default();
+            let inner = &mut *activation.Anonymous.Anonymous;
+            inner.vt = com::VT_BLOB;
+            inner.Anonymous.blob = com::BLOB {
+                cbSize: mem::size_of_val(&params) as u32,
+                pBlobData: ptr::addr_of_mut!(params).cast(),
+            };
+
+            let stream: audio::ISpatialAudioObjectRenderStream =
+                self.client.ActivateSpatialAudioStream(&activation)?;
+
+            tracing::trace!(?max_dynamic_objects, "initialized spatial stream");
+
+            Ok(SpatialRenderStream {
+                tag: self.tag,
+                stream,
+                event,
+                _marker: marker::PhantomData,
+            })
+        }
+    }
+}
+
+// Safety: thread safety is ensured through tagging with ste::Tag.
+unsafe impl Send for SpatialClient {}
+
+/// A stream rendering spatial audio objects.
+///
+/// See [SpatialClient::initialize].
+pub struct SpatialRenderStream<T> {
+    tag: ste::Tag,
+    stream: audio::ISpatialAudioObjectRenderStream,
+    event: Arc<Event>,
+    _marker: marker::PhantomData<T>,
+}
+
+impl<T> SpatialRenderStream<T> {
+    /// Activate a dynamic object, which can be freely positioned.
+    ///
+    /// This errors if the maximum number of dynamic objects specified when
+    /// the stream was initialized are already active.
+    pub fn activate_dynamic_object(&self) -> Result<SpatialObject<T>, Error> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            let object = self
+                .stream
+                .ActivateSpatialAudioObject(audio::AudioObjectType_Dynamic)?;
+
+            Ok(SpatialObject {
+                tag: self.tag,
+                object,
+                _marker: marker::PhantomData,
+            })
+        }
+    }
+
+    /// Start rendering objects.
+    pub fn start(&self) -> Result<(), Error> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            self.stream.Start()?;
+        }
+
+        Ok(())
+    }
+
+    /// Stop rendering objects.
+    pub fn stop(&self) -> Result<(), Error> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            self.stream.Stop()?;
+        }
+
+        Ok(())
+    }
+
+    /// Begin updating the objects of the stream.
+    ///
+    /// This will block until the stream is ready for the next buffer of
+    /// object data. Objects can then be positioned and their buffers filled
+    /// until the update is ended through [SpatialUpdate::end].
+    pub fn update(&mut self) -> Result<SpatialUpdate<'_, T>, Error> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            match th::WaitForSingleObject(self.event.raw_event(), wp::INFINITE) {
+                f::WAIT_OBJECT_0 => (),
+                _ => {
+                    return Err(Error::from(windows::core::Error::from_win32()));
+                }
+            }
+
+            let mut available = 0;
+            let mut frames = 0;
+            self.stream
+                .BeginUpdatingAudioObjects(&mut available, &mut frames)?;
+
+            Ok(SpatialUpdate {
+                tag: self.tag,
+                stream: &mut self.stream,
+                available,
+                frames,
+                in_use: true,
+                _marker: marker::PhantomData,
+            })
+        }
+    }
+}
+
+// Safety: thread safety is ensured through tagging with ste::Tag.
+unsafe impl<T> Send for SpatialRenderStream<T> {}
+
+/// An update of the objects in a [SpatialRenderStream] which is in progress.
+///
+/// The update is ended when this is dropped.
+pub struct SpatialUpdate<'a, T> {
+    tag: ste::Tag,
+    stream: &'a mut audio::ISpatialAudioObjectRenderStream,
+    available: u32,
+    frames: u32,
+    in_use: bool,
+    _marker: marker::PhantomData<T>,
+}
+
+impl<'a, T> SpatialUpdate<'a, T> {
+    /// The number of frames each object buffer holds during this update.
+    pub fn frames(&self) -> u32 {
+        self.frames
+    }
+
+    /// The number of dynamic objects which can still be activated.
+    pub fn available_dynamic_objects(&self) -> u32 {
+        self.available
+    }
+
+    /// End the update, allowing the system to render the objects.
+    pub fn end(mut self) -> Result<(), Error> {
+        self.tag.ensure_on_thread();
+
+        if mem::take(&mut self.in_use) {
+            unsafe {
+                self.stream.EndUpdatingAudioObjects()?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a, T> Drop for SpatialUpdate<'a, T> {
+    fn drop(&mut self) {
+        self.tag.ensure_on_thread();
+
+        if mem::take(&mut self.in_use) {
+            unsafe {
+                self.stream.EndUpdatingAudioObjects().ok().unwrap();
+            }
+        }
+    }
+}
+
+// Safety: thread safety is ensured through tagging with ste::Tag.
+unsafe impl<T> Send for SpatialUpdate<'_, T> {}
+
+/// A single channel audio object which is positioned in 3D space.
+///
+/// Positions are in meters relative to the listener, where positive `x` is
+/// to the right, positive `y` is up and negative `z` is in front.
+///
+/// See [SpatialRenderStream::activate_dynamic_object].
+pub struct SpatialObject<T> {
+    tag: ste::Tag,
+    object: audio::ISpatialAudioObject,
+    _marker: marker::PhantomData<T>,
+}
+
+impl<T> SpatialObject<T> {
+    /// Set the position of the object for the current update.
+    pub fn set_position(&mut self, x: f32, y: f32, z: f32) -> Result<(), Error> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            self.object.SetPosition(x, y, z)?;
+        }
+
+        Ok(())
+    }
+
+    /// Set the volume of the object in the range `0.0` to `1.0`.
+    pub fn set_volume(&mut self, volume: f32) -> Result<(), Error> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            self.object.SetVolume(volume)?;
+        }
+
+        Ok(())
+    }
+
+    /// Test if the object is still being rendered.
+    ///
+    /// Objects stop being rendered once the end of the stream has been
+    /// signalled or the system revokes them.
+    pub fn is_active(&self) -> Result<bool, Error> {
+        self.tag.ensure_on_thread();
+
+        unsafe { Ok(self.object.IsActive()?.as_bool()) }
+    }
+
+    /// Access the buffer of the object for the given update, which contains
+    /// [SpatialUpdate::frames] samples.
+    pub fn buffer<'b>(
+        &'b mut self,
+        update: &'b SpatialUpdate<'_, T>,
+    ) -> Result<&'b mut [T], Error> {
+        self.tag.ensure_on_thread();
+        debug_assert!(update.in_use);
+
+        unsafe {
+            let mut data = ptr::null_mut();
+            let mut len = 0;
+            self.object.GetBuffer(&mut data, &mut len)?;
+            let len = usize::min(len as usize / mem::size_of::<T>(), update.frames as usize);
+            Ok(slice::from_raw_parts_mut(data.cast(), len))
+        }
+    }
+
+    /// Signal that the object has no more data after `frames` frames of the
+    /// current update, after which it stops being rendered.
+    pub fn set_end_of_stream(&mut self, frames: u32) -> Result<(), Error> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            self.object.SetEndOfStream(frames)?;
+        }
+
+        Ok(())
+    }
+}
+
+// Safety: thread safety is ensured through tagging with ste::Tag.
+unsafe impl<T> Send for SpatialObject<T> {}
@@ -0,0 +1,164 @@
+use std::time::Duration;
+
+use crate::stream::StreamSpec;
+use crate::wasapi::{Client, ClientConfig, Error, Reader, RenderClient, Sample};
+use crate::windows::Event;
+
+/// A portable output stream using WASAPI.
+///
+/// The stream recovers from the device being invalidated by moving to the
+/// new default device, see [RenderClient::set_recover].
+///
+/// See [OutputStream][crate::stream::OutputStream].
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::stream::{OutputStream, StreamSpec};
+/// use audio_device::wasapi;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// wasapi::audio_prelude();
+///
+/// let mut stream = wasapi::OutputStream::<f32>::open_default(StreamSpec::new(2, 48000))?;
+/// let spec = stream.spec();
+///
+/// let samples = vec![0.0f32; spec.channels * 1024];
+/// stream.write(audio::wrap::interleaved(&samples[..], spec.channels))?;
+/// # Ok(()) }
+/// ```
+pub struct OutputStream<T> {
+    _client: Client,
+    render_client: RenderClient<T, Event>,
+    spec: StreamSpec,
+}
+
+impl<T> crate::stream::OutputStream for OutputStream<T>
+where
+    T: Sample,
+{
+    type Sample = T;
+    type Error = Error;
+
+    fn open_default(spec: StreamSpec) -> Result<Self, Error> {
+        let client = super::default_output_client()?.ok_or(Error::NoDefaultDevice)?;
+        let config = configure(&client, spec)?;
+
+        let initialized = client.initialize::<T>(config)?;
+        let mut render_client = initialized.render_client()?;
+        render_client.set_recover(true);
+        client.start()?;
+
+        Ok(Self {
+            _client: client,
+            render_client,
+            spec: spec_of(initialized.config()),
+        })
+    }
+
+    fn spec(&self) -> StreamSpec {
+        self.spec
+    }
+
+    fn latency(&mut self) -> Result<Duration, Error> {
+        self.render_client.latency()
+    }
+
+    fn write<B>(&mut self, buf: B) -> Result<(), Error>
+    where
+        B: audio_core::ReadBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBuf<Sample = T>,
+    {
+        self.render_client.write_interleaved(buf)
+    }
+}
+
+/// A portable input stream using WASAPI.
+///
+/// See [InputStream][crate::stream::InputStream].
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::stream::{InputStream, StreamSpec};
+/// use audio_device::wasapi;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// wasapi::audio_prelude();
+///
+/// let mut stream = wasapi::InputStream::<f32>::open_default(StreamSpec::new(2, 48000))?;
+/// let spec = stream.spec();
+///
+/// let mut samples = vec![0.0f32; spec.channels * 1024];
+/// stream.read(audio::wrap::interleaved(&mut samples[..], spec.channels))?;
+/// # Ok(()) }
+/// ```
+pub struct InputStream<T> {
+    client: Client,
+    reader: Reader<T>,
+    spec: StreamSpec,
+}
+
+impl<T> crate::stream::InputStream for InputStream<T>
+where
+    T: Sample,
+{
+    type Sample = T;
+    type Error = Error;
+
+    fn open_default(spec: StreamSpec) -> Result<Self, Error> {
+        let client = super::default_input_client()?.ok_or(Error::NoDefaultDevice)?;
+        let config = configure(&client, spec)?;
+
+        let initialized = client.initialize::<T>(config)?;
+        let reader = initialized.reader()?;
+        client.start()?;
+
+        Ok(Self {
+            client,
+            reader,
+            spec: spec_of(initialized.config()),
+        })
+    }
+
+    fn spec(&self) -> StreamSpec {
+        self.spec
+    }
+
+    fn latency(&mut self) -> Result<Duration, Error> {
+        self.client.tag.ensure_on_thread();
+
+        let padding = unsafe { self.client.audio_client.GetCurrentPadding()? };
+
+        if self.spec.sample_rate == 0 {
+            return Ok(Duration::ZERO);
+        }
+
+        let nanos = padding as u64 * 1_000_000_000 / self.spec.sample_rate as u64;
+        Ok(Duration::from_nanos(nanos))
+    }
+
+    fn read<B>(&mut self, buf: B) -> Result<(), Error>
+    where
+        B: audio_core::WriteBuf
+            + audio_core::ExactSizeBuf<Sample = T>
+            + audio_core::InterleavedBufMut<Sample = T>,
+    {
+        self.reader.read_interleaved(buf)
+    }
+}
+
+/// Construct a client configuration out of the desired specification.
+pub(super) fn configure(client: &Client, spec: StreamSpec) -> Result<ClientConfig, Error> {
+    let mut config = client.default_client_config()?;
+    config.channels = spec.channels as u16;
+    config.sample_rate = spec.sample_rate;
+    // NB: the default channel mask is for the channel count of the device.
+    config.channel_mask = 0;
+    Ok(config)
+}
+
+pub(super) fn spec_of(config: ClientConfig) -> StreamSpec {
+    StreamSpec::new(config.channels as usize, config.sample_rate)
+}
@@ -0,0 +1,114 @@
+use std::ptr;
+
+use windows::Win32::Foundation as f;
+use windows::Win32::Media::Audio as audio;
+use windows::Win32::Media::Audio::Endpoints as endpoints;
+
+use crate::wasapi::Error;
+
+/// The volume of the audio session which a client belongs to.
+///
+/// See [InitializedClient::session_volume][crate::wasapi::InitializedClient::session_volume].
+pub struct SessionVolume {
+    pub(super) tag: ste::Tag,
+    pub(super) volume: audio::ISimpleAudioVolume,
+}
+
+impl SessionVolume {
+    /// Get the volume of the session in the range `0.0` to `1.0`.
+    pub fn volume(&self) -> Result<f32, Error> {
+        self.tag.ensure_on_thread();
+
+        unsafe { Ok(self.volume.GetMasterVolume()?) }
+    }
+
+    /// Set the volume of the session in the range `0.0` to `1.0`.
+    pub fn set_volume(&self, volume: f32) -> Result<(), Error> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            self.volume.SetMasterVolume(volume, ptr::null())?;
+        }
+
+        Ok(())
+    }
+
+    /// Test if the session is muted.
+    pub fn is_muted(&self) -> Result<bool, Error> {
+        self.tag.ensure_on_thread();
+
+        unsafe { Ok(self.volume.GetMute()?.as_bool()) }
+    }
+
+    /// Mute or unmute the session.
+    pub fn set_muted(&self, muted: bool) -> Result<(), Error> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            self.volume.SetMute(f::BOOL::from(muted), ptr::null())?;
+        }
+
+        Ok(())
+    }
+}
+
+// Safety: thread safety is ensured through tagging with ste::Tag.
+unsafe impl Send for SessionVolume {}
+
+/// The master volume of an audio endpoint, which affects every session which
+/// plays through it.
+///
+/// See [Device::endpoint_volume][crate::wasapi::Device::endpoint_volume].
+pub struct EndpointVolume {
+    pub(super) tag: ste::Tag,
+    pub(super) volume: endpoints::IAudioEndpointVolume,
+}
+
+impl EndpointVolume {
+    /// Get the master volume of the endpoint in the range `0.0` to `1.0`.
+    pub fn volume(&self) -> Result<f32, Error> {
+        self.tag.ensure_on_thread();
+
+        unsafe { Ok(self.volume.GetMasterVolumeLevelScalar()?) }
+    }
+
+    /// Set the master volume of the endpoint in the range `0.0` to `1.0`.
+    pub fn set_volume(&self, volume: f32) -> Result<(), Error> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            self.volume
+                .SetMasterVolumeLevelScalar(volume, ptr::null())?;
+        }
+
+        Ok(())
+    }
+
+    /// Get the master volume of the endpoint in decibels.
+    pub fn volume_db(&self) -> Result<f32, Error> {
+        self.tag.ensure_on_thread();
+
+        unsafe { Ok(self.volume.GetMasterVolumeLevel()?) }
+    }
+
+    /// Test if the endpoint is muted.
+    pub fn is_muted(&self) -> Result<bool, Error> {
+        self.tag.ensure_on_thread();
+
+        unsafe { Ok(self.volume.GetMute()?.as_bool()) }
+    }
+
+    /// Mute or unmute the endpoint.
+    pub fn set_muted(&self, muted: bool) -> Result<(), Error> {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            self.volume.SetMute(f::BOOL::from(muted), ptr::null())?;
+        }
+
+        Ok(())
+    }
+}
+
+// Safety: thread safety is ensured through tagging with ste::Tag.
+unsafe impl Send for EndpointVolume {}
@@ -0,0 +1,75 @@
+//! An idiomatic Rust Web Audio interface, for use in the browser through
+//! `wasm32-unknown-unknown`.
+//!
+//! Audio is rendered through a `ScriptProcessorNode`, which calls into the
+//! WebAssembly module on the main thread of the page for every block of audio
+//! which is needed. The block is handed to the render callback as an
+//! [audio::buf::Dynamic] buffer, so code written against the buffer traits of
+//! this crate can be used to render audio on the web as well.
+//!
+//! An `AudioWorklet` would run on the audio thread of the browser instead, but
+//! can't call into a WebAssembly module which isn't shared with it.
+
+// Documentation: https://developer.mozilla.org/en-US/docs/Web/API/Web_Audio_API
+
+use thiserror::Error;
+use wasm_bindgen::JsValue;
+
+mod output;
+pub use self::output::{Output, State};
+
+/// Errors that can be raised by the Web Audio layer.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// An exception raised by the browser, like when an unsupported sample
+    /// rate or buffer size is requested.
+    #[error("browser error: {0}")]
+    Js(String),
+}
+
+impl From<JsValue> for Error {
+    fn from(value: JsValue) -> Self {
+        Self::Js(value.as_string().unwrap_or_else(|| format!("{:?}", value)))
+    }
+}
+
+/// Helper result wrapper.
+pub type Result<T, E = Error> = ::std::result::Result<T, E>;
+
+/// The configuration of an [Output].
+///
+/// # Examples
+///
+/// ```
+/// use audio_device::webaudio::OutputConfig;
+///
+/// let config = OutputConfig {
+///     channels: 1,
+///     ..OutputConfig::default()
+/// };
+///
+/// assert_eq!(config.channels, 1);
+/// assert_eq!(config.buffer_frames, 1024);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutputConfig {
+    /// The number of channels, at most 32.
+    pub channels: u32,
+    /// The sample rate in frames per second, or `None` to use the sample rate
+    /// of the output device.
+    pub sample_rate: Option<u32>,
+    /// The number of frames rendered at a time, which must be a power of two
+    /// between 256 and 16384. Each render quantum of the browser is 128
+    /// frames, so this determines how many of them are rendered at once.
+    pub buffer_frames: u32,
+}
+
+impl Default for OutputConfig {
+    fn default() -> Self {
+        Self {
+            channels: 2,
+            sample_rate: None,
+            buffer_frames: 1024,
+        }
+    }
+}
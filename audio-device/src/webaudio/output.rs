@@ -0,0 +1,191 @@
+use std::fmt;
+use std::time::Duration;
+
+use audio::buf::Dynamic;
+use audio::BufMut;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+use web_sys::{
+    AudioContext, AudioContextOptions, AudioContextState, AudioProcessingEvent, ScriptProcessorNode,
+};
+
+use crate::webaudio::{OutputConfig, Result};
+
+/// The state of an [Output].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum State {
+    /// The output is suspended, which is the state it starts out in unless
+    /// the page is allowed to play audio.
+    Suspended,
+    /// The output is rendering audio.
+    Running,
+    /// The output has been closed.
+    Closed,
+}
+
+impl fmt::Display for State {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Suspended => f.write_str("suspended"),
+            Self::Running => f.write_str("running"),
+            Self::Closed => f.write_str("closed"),
+        }
+    }
+}
+
+/// Audio output through the Web Audio API, which calls a render callback
+/// whenever the browser needs more audio.
+///
+/// Browsers only allow audio to start in response to a user gesture, like a
+/// click, so the output might start out [suspended][State::Suspended]. It is
+/// started by calling [Output::resume] from an event handler.
+///
+/// See [Output::open].
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio::Buf;
+/// use audio_device::webaudio;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let mut phase = 0.0f32;
+///
+/// let output = webaudio::Output::open(webaudio::OutputConfig::default(), move |buf| {
+///     for frame in 0..buf.frames() {
+///         let sample = (phase * std::f32::consts::TAU).sin() * 0.1;
+///         phase = (phase + 440.0 / 48000.0) % 1.0;
+///
+///         for mut channel in buf.iter_channels_mut() {
+///             channel[frame] = sample;
+///         }
+///     }
+/// })?;
+///
+/// output.resume()?;
+/// # Ok(()) }
+/// ```
+pub struct Output {
+    context: AudioContext,
+    node: ScriptProcessorNode,
+    // NB: invoked by the browser, so it must outlive the node.
+    _callback: Closure<dyn FnMut(AudioProcessingEvent)>,
+    config: OutputConfig,
+}
+
+impl Output {
+    /// Open an output with the given configuration, which renders audio
+    /// through the given callback.
+    ///
+    /// The callback is called with a silent buffer with one channel for each
+    /// configured channel, which it fills with the audio to play.
+    pub fn open<F>(config: OutputConfig, mut render: F) -> Result<Self>
+    where
+        F: 'static + FnMut(&mut Dynamic<f32>),
+    {
+        let options = AudioContextOptions::new();
+
+        if let Some(sample_rate) = config.sample_rate {
+            options.set_sample_rate(sample_rate as f32);
+        }
+
+        let context = AudioContext::new_with_context_options(&options)?;
+
+        let node = context
+            .create_script_processor_with_buffer_size_and_number_of_input_channels_and_number_of_output_channels(
+                config.buffer_frames,
+                0,
+                config.channels,
+            )?;
+
+        let mut buf =
+            Dynamic::with_topology(config.channels as usize, config.buffer_frames as usize);
+
+        let callback =
+            Closure::<dyn FnMut(AudioProcessingEvent)>::new(move |event: AudioProcessingEvent| {
+                let output = match event.output_buffer() {
+                    Ok(output) => output,
+                    Err(..) => return,
+                };
+
+                buf.resize_frames(output.length() as usize);
+
+                buf.fill(0.0);
+                render(&mut buf);
+
+                for (n, channel) in buf.iter_channels().enumerate() {
+                    // NB: can only fail if the channel doesn't exist, in which
+                    // case there's nothing to copy it to.
+                    let _ = output.copy_to_channel(channel.as_ref(), n as i32);
+                }
+            });
+
+        node.set_onaudioprocess(Some(callback.as_ref().unchecked_ref()));
+        node.connect_with_audio_node(&context.destination())?;
+
+        Ok(Self {
+            context,
+            node,
+            _callback: callback,
+            config,
+        })
+    }
+
+    /// The configuration of the output, with the sample rate which was picked
+    /// by the browser.
+    pub fn config(&self) -> OutputConfig {
+        OutputConfig {
+            sample_rate: Some(self.sample_rate()),
+            ..self.config
+        }
+    }
+
+    /// The sample rate in frames per second.
+    pub fn sample_rate(&self) -> u32 {
+        self.context.sample_rate() as u32
+    }
+
+    /// The current state of the output.
+    pub fn state(&self) -> State {
+        match self.context.state() {
+            AudioContextState::Running => State::Running,
+            AudioContextState::Closed => State::Closed,
+            _ => State::Suspended,
+        }
+    }
+
+    /// The time of the audio which is currently being played, counted from
+    /// when the output was opened and not advancing while it's suspended.
+    pub fn current_time(&self) -> Duration {
+        Duration::from_secs_f64(self.context.current_time().max(0.0))
+    }
+
+    /// Start or resume rendering audio.
+    ///
+    /// This completes in the background, and fails silently unless it's
+    /// called in response to a user gesture. Whether it has completed can be
+    /// seen through [Output::state].
+    pub fn resume(&self) -> Result<()> {
+        let _ = self.context.resume()?;
+        Ok(())
+    }
+
+    /// Suspend rendering audio.
+    ///
+    /// This completes in the background.
+    pub fn suspend(&self) -> Result<()> {
+        let _ = self.context.suspend()?;
+        Ok(())
+    }
+}
+
+impl Drop for Output {
+    fn drop(&mut self) {
+        // NB: the callback is freed once dropped, so it must be detached from
+        // the node before that happens.
+        self.node.set_onaudioprocess(None);
+        let _ = self.node.disconnect();
+        let _ = self.context.close();
+    }
+}
@@ -24,6 +24,17 @@ impl Event {
         Ok(Self { handle })
     }
 
+    /// Take ownership of a raw handle to an object which can be waited on,
+    /// like a waitable timer, so that it can be driven like an event.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the handle is valid and not owned by
+    /// anything else, since it's closed when this is dropped.
+    pub(crate) unsafe fn from_raw(handle: f::HANDLE) -> Self {
+        Self { handle }
+    }
+
     /// Set the event.
     pub fn set(&self) {
         unsafe {
@@ -0,0 +1,73 @@
+use windows::core::PCWSTR;
+use windows::Win32::Foundation as f;
+use windows::Win32::System::Threading as th;
+
+/// The MMCSS task used for audio rendering threads.
+const PRO_AUDIO: &str = "Pro Audio";
+
+/// A registration of the current thread with the Multimedia Class Scheduler
+/// Service (MMCSS).
+///
+/// The registration is reverted when this is dropped, which must happen on the
+/// thread it was registered on.
+///
+/// See [register_pro_audio_thread].
+pub struct MmcssRegistration {
+    tag: ste::Tag,
+    handle: f::HANDLE,
+}
+
+impl Drop for MmcssRegistration {
+    fn drop(&mut self) {
+        self.tag.ensure_on_thread();
+
+        unsafe {
+            // NB: We intentionally ignore errors here.
+            let _ = th::AvRevertMmThreadCharacteristics(self.handle);
+        }
+    }
+}
+
+/// Register the current thread with the "Pro Audio" task of the Multimedia
+/// Class Scheduler Service (MMCSS), which raises its scheduling priority to
+/// avoid glitches during playback.
+///
+/// This is done automatically for the thread driving asynchronous events, but
+/// threads which render audio through blocking APIs should call this before
+/// they start rendering.
+///
+/// # Examples
+///
+/// ```no_run
+/// use audio_device::windows;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let _registration = windows::register_pro_audio_thread()?;
+/// // render audio on the current thread.
+/// # Ok(()) }
+/// ```
+pub fn register_pro_audio_thread() -> windows::core::Result<MmcssRegistration> {
+    register_mmcss_thread(PRO_AUDIO)
+}
+
+/// Register the current thread with the given task of the Multimedia Class
+/// Scheduler Service (MMCSS), like `"Pro Audio"` or `"Audio"`.
+///
+/// The available tasks are listed in the registry under
+/// `HKEY_LOCAL_MACHINE\SOFTWARE\Microsoft\Windows NT\CurrentVersion\Multimedia\SystemProfile\Tasks`.
+///
+/// See [register_pro_audio_thread].
+pub fn register_mmcss_thread(task: &str) -> windows::core::Result<MmcssRegistration> {
+    let tag = ste::Tag::current_thread();
+    let task = task
+        .encode_utf16()
+        .chain(std::iter::once(0))
+        .collect::<Vec<u16>>();
+    let mut task_index = 0;
+
+    let handle =
+        unsafe { th::AvSetMmThreadCharacteristicsW(PCWSTR(task.as_ptr()), &mut task_index)? };
+
+    tracing::trace!(?task_index, "registered thread with mmcss");
+    Ok(MmcssRegistration { tag, handle })
+}
@@ -1,9 +1,15 @@
 //! Shared helpers for windows programming.
 
 use windows::Win32::Foundation as f;
+
+use crate::ErrorKind;
+
 mod event;
 pub use self::event::Event;
 
+mod mmcss;
+pub use self::mmcss::{register_mmcss_thread, register_pro_audio_thread, MmcssRegistration};
+
 cfg_events_driver! {
     pub use crate::runtime::events::AsyncEvent;
 }
@@ -18,3 +24,28 @@ pub trait RawEvent {
     /// whatever its being associated with.
     unsafe fn raw_event(&self) -> f::HANDLE;
 }
+
+/// Classify a windows error.
+pub(crate) fn error_kind(error: &windows::core::Error) -> ErrorKind {
+    let code = error.code();
+
+    #[cfg(feature = "wasapi")]
+    {
+        use windows::Win32::Media::Audio as audio;
+
+        match code {
+            audio::AUDCLNT_E_DEVICE_INVALIDATED | audio::AUDCLNT_E_SERVICE_NOT_RUNNING => {
+                return ErrorKind::Disconnected
+            }
+            audio::AUDCLNT_E_UNSUPPORTED_FORMAT => return ErrorKind::UnsupportedFormat,
+            audio::AUDCLNT_E_DEVICE_IN_USE => return ErrorKind::Busy,
+            _ => (),
+        }
+    }
+
+    match code {
+        f::E_ACCESSDENIED => ErrorKind::PermissionDenied,
+        f::E_INVALIDARG | f::E_POINTER => ErrorKind::InvalidInput,
+        _ => ErrorKind::Other,
+    }
+}
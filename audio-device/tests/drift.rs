@@ -0,0 +1,61 @@
+use audio_device::stream::DriftEstimator;
+
+#[test]
+fn test_nominal_ratio() {
+    assert_eq!(DriftEstimator::new(48000, 48000).ratio(), 1.0);
+    assert_eq!(DriftEstimator::new(96000, 48000).ratio(), 2.0);
+    // NB: an unknown rate can't be compensated for.
+    assert_eq!(DriftEstimator::new(0, 48000).ratio(), 1.0);
+    assert_eq!(DriftEstimator::new(48000, 0).ratio(), 1.0);
+}
+
+#[test]
+fn test_nominal_until_both_devices_move() {
+    let mut drift = DriftEstimator::new(44100, 48000);
+    let nominal = drift.ratio();
+
+    assert_eq!(drift.update(500, 700), nominal);
+    assert_eq!(drift.update(500, 700), nominal);
+    assert_eq!(drift.update(1000, 700), nominal);
+    assert_eq!(drift.update(500, 1000), nominal);
+}
+
+#[test]
+fn test_measures_from_origin() {
+    let mut drift = DriftEstimator::new(48000, 48000);
+    drift.update(1000, 2000);
+
+    assert_eq!(drift.update(1000 + 48048, 2000 + 48000), 1.001);
+    assert_eq!(drift.update(1000 + 47952, 2000 + 48000), 0.999);
+
+    // NB: the estimate covers everything since the origin, so it settles
+    // rather than following the jitter of each measurement.
+    drift.update(1000 + 96000 + 96, 2000 + 96000);
+    assert_eq!(drift.update(1000 + 144000 + 144, 2000 + 144000), 1.001);
+}
+
+#[test]
+fn test_positions_before_origin() {
+    let mut drift = DriftEstimator::new(48000, 48000);
+    drift.update(1000, 1000);
+    assert_eq!(drift.update(2100, 2000), 1.1);
+
+    // NB: a device which has been restarted behind our back reports an
+    // earlier position, which keeps the last estimate.
+    assert_eq!(drift.update(500, 3000), 1.1);
+    assert_eq!(drift.ratio(), 1.1);
+}
+
+#[test]
+fn test_reset() {
+    let mut drift = DriftEstimator::new(48000, 48000);
+    drift.update(0, 0);
+    assert_eq!(drift.update(1100, 1000), 1.1);
+
+    drift.reset();
+    assert_eq!(drift.ratio(), 1.0);
+
+    // NB: the first update after a reset establishes a new origin.
+    assert_eq!(drift.update(5000, 5000), 1.0);
+    assert_eq!(drift.update(5000 + 900, 5000 + 1000), 0.9);
+}
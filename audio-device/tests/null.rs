@@ -0,0 +1,243 @@
+use std::time::Duration;
+
+use audio::{ReadBuf, WriteBuf};
+use audio_device::null;
+use audio_device::stream::{DuplexStream, InputStream, OutputStream, StreamSpec};
+
+/// A configuration whose buffer holds 10ms of audio.
+const CONFIG: null::Config = null::Config {
+    channels: 2,
+    sample_rate: 48000,
+    buffer_frames: 480,
+};
+
+/// Long enough for a device using [CONFIG] to go through its whole buffer a
+/// few times over.
+const STALL: Duration = Duration::from_millis(50);
+
+/// The number of frames in [STALL].
+const STALL_FRAMES: u64 = 2400;
+
+fn clock() -> null::Clock {
+    null::Clock::manual(CONFIG.sample_rate)
+}
+
+#[test]
+fn test_output_buffers_frames() -> anyhow::Result<()> {
+    let mut output = null::Output::with_clock(CONFIG, clock());
+    assert_eq!(output.position(), 0);
+    assert_eq!(output.delay(), 0);
+
+    let samples = vec![0.0f32; CONFIG.channels * CONFIG.buffer_frames];
+    let mut buf = audio::wrap::interleaved(&samples[..], CONFIG.channels);
+    output.writer::<f32>().write_interleaved(&mut buf)?;
+    assert!(!buf.has_remaining());
+
+    // NB: a full buffer is accepted without waiting for anything to play.
+    assert_eq!(output.position(), 0);
+    assert_eq!(output.delay(), CONFIG.buffer_frames as u64);
+    assert_eq!(output.underruns(), 0);
+    Ok(())
+}
+
+#[test]
+fn test_output_blocks_on_full_buffer() -> anyhow::Result<()> {
+    let clock = clock();
+    let mut output = null::Output::with_clock(CONFIG, clock.clone());
+
+    let samples = vec![0i16; CONFIG.channels * CONFIG.buffer_frames * 3];
+    output
+        .writer::<i16>()
+        .write_interleaved(audio::wrap::interleaved(&samples[..], CONFIG.channels))?;
+
+    // NB: two buffers worth of frames must have been played to make room for
+    // the last one.
+    assert_eq!(clock.position(), CONFIG.buffer_frames as u64 * 2);
+    assert_eq!(output.position(), CONFIG.buffer_frames as u64 * 2);
+    assert_eq!(output.delay(), CONFIG.buffer_frames as u64);
+    assert_eq!(output.underruns(), 0);
+    Ok(())
+}
+
+#[test]
+fn test_output_underruns() -> anyhow::Result<()> {
+    let clock = clock();
+    let mut output = null::Output::with_clock(CONFIG, clock.clone());
+    let samples = vec![0.0f32; CONFIG.channels * 48];
+
+    output
+        .writer::<f32>()
+        .write_interleaved(audio::wrap::interleaved(&samples[..], CONFIG.channels))?;
+
+    clock.advance(STALL);
+
+    // NB: once drained, the device stops at the frames which were written.
+    assert_eq!(output.position(), 48);
+    assert_eq!(output.delay(), 0);
+    assert_eq!(output.underruns(), 0);
+
+    output
+        .writer::<f32>()
+        .write_interleaved(audio::wrap::interleaved(&samples[..], CONFIG.channels))?;
+
+    assert_eq!(output.underruns(), 1);
+    assert_eq!(output.position(), 48);
+    assert_eq!(output.delay(), 48);
+    Ok(())
+}
+
+#[test]
+fn test_output_channels_mismatch() {
+    let mut output = null::Output::with_clock(CONFIG, clock());
+    let samples = [0.0f32; 48];
+
+    let result = output
+        .writer::<f32>()
+        .write_interleaved(audio::wrap::interleaved(&samples[..], 1));
+
+    assert!(matches!(
+        result,
+        Err(null::Error::ChannelsMismatch {
+            actual: 1,
+            expected: 2
+        })
+    ));
+
+    assert_eq!(output.delay(), 0);
+}
+
+#[test]
+#[should_panic = "clock must run at the sample rate of the device"]
+fn test_output_clock_rate_mismatch() {
+    null::Output::with_clock(CONFIG, null::Clock::manual(44100));
+}
+
+#[test]
+fn test_input_reads_silence() -> anyhow::Result<()> {
+    let clock = clock();
+    let mut input = null::Input::with_clock(CONFIG, clock.clone());
+
+    let mut samples = vec![1i16; CONFIG.channels * 480];
+    let mut buf = audio::wrap::interleaved(&mut samples[..], CONFIG.channels);
+    input.reader::<i16>().read_interleaved(&mut buf)?;
+
+    assert!(!buf.has_remaining_mut());
+    assert!(samples.iter().all(|s| *s == 0));
+    // NB: reading waits until the frames have been captured.
+    assert_eq!(clock.position(), 480);
+    assert_eq!(input.position(), 480);
+    assert_eq!(input.delay(), 0);
+    assert_eq!(input.overruns(), 0);
+    Ok(())
+}
+
+#[test]
+fn test_input_overruns() -> anyhow::Result<()> {
+    let clock = clock();
+    let mut input = null::Input::with_clock(CONFIG, clock.clone());
+
+    clock.advance(STALL);
+
+    // NB: frames beyond the size of the buffer have been dropped.
+    assert_eq!(input.position(), STALL_FRAMES);
+    assert_eq!(input.delay(), CONFIG.buffer_frames as u64);
+
+    let mut samples = vec![0.0f32; CONFIG.channels * 48];
+    input
+        .reader::<f32>()
+        .read_interleaved(audio::wrap::interleaved(&mut samples[..], CONFIG.channels))?;
+
+    assert_eq!(input.overruns(), 1);
+    assert_eq!(input.delay(), CONFIG.buffer_frames as u64 - 48);
+    // NB: the frames were already captured, so reading didn't wait.
+    assert_eq!(clock.position(), STALL_FRAMES);
+    Ok(())
+}
+
+#[test]
+fn test_input_channels_mismatch() {
+    let mut input = null::Input::with_clock(CONFIG, clock());
+    let mut samples = [0.0f32; 48];
+
+    let result = input
+        .reader::<f32>()
+        .read_interleaved(audio::wrap::interleaved(&mut samples[..], 3));
+
+    assert!(matches!(
+        result,
+        Err(null::Error::ChannelsMismatch {
+            actual: 3,
+            expected: 2
+        })
+    ));
+
+    assert_eq!(input.overruns(), 0);
+}
+
+#[test]
+fn test_output_stream_latency() -> anyhow::Result<()> {
+    let clock = clock();
+    let spec = StreamSpec::new(1, 48000);
+    let mut stream = null::OutputStream::<f32>::with_clock(spec, clock.clone());
+    assert_eq!(stream.spec(), spec);
+    assert_eq!(stream.latency()?, Duration::ZERO);
+
+    let samples = vec![0.0f32; 480];
+    stream.write(audio::wrap::interleaved(&samples[..], 1))?;
+    assert_eq!(stream.latency()?, Duration::from_millis(10));
+
+    clock.advance(Duration::from_millis(4));
+    assert_eq!(stream.latency()?, Duration::from_millis(6));
+
+    clock.advance(STALL);
+    assert_eq!(stream.latency()?, Duration::ZERO);
+    Ok(())
+}
+
+#[test]
+fn test_input_stream_latency() -> anyhow::Result<()> {
+    let clock = clock();
+    let spec = StreamSpec::new(2, 48000);
+    let mut stream = null::InputStream::<i16>::with_clock(spec, clock.clone());
+    assert_eq!(stream.spec(), spec);
+
+    let mut samples = vec![1i16; spec.channels * 240];
+    stream.read(audio::wrap::interleaved(&mut samples[..], spec.channels))?;
+    assert!(samples.iter().all(|s| *s == 0));
+    assert_eq!(stream.latency()?, Duration::ZERO);
+
+    clock.advance(Duration::from_millis(10));
+    assert_eq!(stream.latency()?, Duration::from_millis(10));
+
+    // NB: the latency is bounded by the buffer of the device.
+    clock.advance(STALL);
+    let buffer_frames = null::Config::default().buffer_frames as u64;
+    assert_eq!(
+        stream.latency()?,
+        Duration::from_nanos(buffer_frames * 1_000_000_000 / 48000)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_duplex_stream() -> anyhow::Result<()> {
+    let spec = StreamSpec::new(2, 48000);
+    let mut stream = null::DuplexStream::<f32>::with_clock(spec, clock());
+    assert_eq!(stream.input_spec(), spec);
+    assert_eq!(stream.output_spec(), spec);
+
+    // NB: nothing to measure before the output has started.
+    assert_eq!(stream.resample_ratio()?, 1.0);
+
+    let mut samples = vec![0.0f32; spec.channels * 240];
+
+    for _ in 0..8 {
+        stream.read(audio::wrap::interleaved(&mut samples[..], spec.channels))?;
+        stream.write(audio::wrap::interleaved(&samples[..], spec.channels))?;
+        stream.resample_ratio()?;
+    }
+
+    // NB: both devices are driven by the same clock, so they don't drift.
+    assert_eq!(stream.resample_ratio()?, 1.0);
+    Ok(())
+}
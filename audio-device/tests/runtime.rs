@@ -0,0 +1,226 @@
+use std::future::{self, Future};
+use std::pin::Pin;
+use std::sync::mpsc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use audio_device::runtime::Runtime;
+
+/// How long to wait for a spawned task before giving up on it.
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A future which wakes itself up and yields the given number of times before
+/// completing.
+struct Yield(usize);
+
+impl Future for Yield {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.0 == 0 {
+            return Poll::Ready(());
+        }
+
+        self.0 -= 1;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+/// Sends a message when dropped.
+struct OnDrop(mpsc::Sender<()>);
+
+impl Drop for OnDrop {
+    fn drop(&mut self) {
+        let _ = self.0.send(());
+    }
+}
+
+#[test]
+fn test_spawn() -> anyhow::Result<()> {
+    let runtime = Runtime::new()?;
+    let (tx, rx) = mpsc::channel();
+
+    for n in 0..4 {
+        let tx = tx.clone();
+
+        runtime.spawn(async move {
+            Yield(n).await;
+            tx.send(n).unwrap();
+        });
+    }
+
+    let mut done = (0..4)
+        .map(|_| rx.recv_timeout(TIMEOUT))
+        .collect::<Result<Vec<_>, _>>()?;
+    done.sort();
+    assert_eq!(done, [0, 1, 2, 3]);
+
+    assert!(runtime.shutdown(TIMEOUT));
+    Ok(())
+}
+
+#[test]
+fn test_spawn_after_panic() -> anyhow::Result<()> {
+    let runtime = Runtime::new()?;
+    let (tx, rx) = mpsc::channel();
+
+    runtime.spawn(async {
+        panic!("task panicked");
+    });
+
+    runtime.spawn(async move {
+        tx.send(()).unwrap();
+    });
+
+    // NB: a panicking task doesn't take the executor down with it.
+    rx.recv_timeout(TIMEOUT)?;
+    assert!(runtime.shutdown(TIMEOUT));
+    Ok(())
+}
+
+#[test]
+fn test_shutdown_without_tasks() -> anyhow::Result<()> {
+    let runtime = Runtime::new()?;
+    assert!(runtime.shutdown(TIMEOUT));
+    Ok(())
+}
+
+#[test]
+fn test_shutdown_drops_pending_tasks() -> anyhow::Result<()> {
+    let runtime = Runtime::new()?;
+    let (started_tx, started_rx) = mpsc::channel();
+    let (dropped_tx, dropped_rx) = mpsc::channel();
+
+    runtime.spawn(async move {
+        let _guard = OnDrop(dropped_tx);
+        started_tx.send(()).unwrap();
+        future::pending::<()>().await;
+    });
+
+    started_rx.recv_timeout(TIMEOUT)?;
+    assert!(dropped_rx.try_recv().is_err());
+
+    assert!(runtime.shutdown(TIMEOUT));
+    dropped_rx.recv_timeout(TIMEOUT)?;
+    Ok(())
+}
+
+#[test]
+fn test_shutdown_timeout() -> anyhow::Result<()> {
+    let runtime = Runtime::new()?;
+    let (started_tx, started_rx) = mpsc::channel();
+    let (release_tx, release_rx) = mpsc::channel::<()>();
+
+    runtime.spawn(async move {
+        started_tx.send(()).unwrap();
+        // NB: blocks the executor thread, like a task stuck on a device.
+        let _ = release_rx.recv();
+    });
+
+    started_rx.recv_timeout(TIMEOUT)?;
+    assert!(!runtime.shutdown(Duration::from_millis(10)));
+
+    // NB: let the detached thread finish.
+    drop(release_tx);
+    Ok(())
+}
+
+#[cfg(all(feature = "poll-driver", target_os = "linux"))]
+mod timer {
+    use std::future;
+    use std::sync::mpsc;
+    use std::task::Poll;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use audio_device::runtime::{timeout, Runtime, Timer};
+
+    use super::TIMEOUT;
+
+    #[test]
+    fn test_timers_fire_in_order() -> anyhow::Result<()> {
+        let runtime = Runtime::new()?;
+        let (tx, rx) = mpsc::channel();
+
+        for (id, delay) in [(0, 60), (1, 20), (2, 40)] {
+            let tx = tx.clone();
+
+            runtime.spawn(async move {
+                let mut timer = Timer::after(Duration::from_millis(delay)).unwrap();
+                timer.tick().await.unwrap();
+                tx.send(id).unwrap();
+            });
+        }
+
+        let order = (0..3)
+            .map(|_| rx.recv_timeout(TIMEOUT))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(order, [1, 2, 0]);
+
+        assert!(runtime.shutdown(TIMEOUT));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_interval_counts_missed_ticks() -> anyhow::Result<()> {
+        let runtime = Runtime::new()?;
+
+        runtime
+            .wrap(async {
+                let start = Instant::now();
+                let mut timer = Timer::interval(Duration::from_millis(10))?;
+                assert_eq!(timer.tick().await?, 1);
+                assert!(start.elapsed() >= Duration::from_millis(10));
+
+                // NB: the ticks which fired while we weren't waiting are
+                // reported at once.
+                thread::sleep(Duration::from_millis(35));
+                assert!(timer.tick().await? >= 3);
+                Ok(())
+            })
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_timeout() -> anyhow::Result<()> {
+        let runtime = Runtime::new()?;
+
+        runtime
+            .wrap(async {
+                let start = Instant::now();
+                let output = timeout(Duration::from_millis(10), future::pending::<()>()).await?;
+                assert_eq!(output, None);
+                assert!(start.elapsed() >= Duration::from_millis(10));
+
+                let output = timeout(TIMEOUT, future::ready(42)).await?;
+                assert_eq!(output, Some(42));
+                Ok(())
+            })
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_timer() -> anyhow::Result<()> {
+        let runtime = Runtime::new()?;
+
+        runtime
+            .wrap(async {
+                let mut cancelled = Timer::after(Duration::from_millis(10))?;
+
+                // NB: register the timer with the runtime before it's dropped.
+                future::poll_fn(|cx| {
+                    assert!(cancelled.poll_tick(cx).is_pending());
+                    Poll::Ready(())
+                })
+                .await;
+
+                drop(cancelled);
+
+                let mut timer = Timer::after(Duration::from_millis(20))?;
+                assert_eq!(timer.tick().await?, 1);
+                Ok(())
+            })
+            .await
+    }
+}
@@ -1,6 +1,9 @@
 mod amplitude;
 pub use self::amplitude::Amplitude;
 
+mod block;
+pub use self::block::Block;
+
 mod iter;
 pub use self::iter::Iter;
 
@@ -60,4 +63,29 @@ pub trait Generator {
     {
         Amplitude::new(self, amplitude)
     }
+
+    /// Construct a [BlockSource][audio_core::BlockSource] from this generator.
+    ///
+    /// The block source renders the same generated sample into every channel
+    /// of a frame, and is never exhausted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio_core::BlockSource;
+    /// use audio_generator::{Generator, Sine};
+    ///
+    /// let mut source = Sine::new(440.0, 44100.0).block();
+    /// let mut buf = audio::sequential![[0.0f32; 16]; 2];
+    ///
+    /// assert_eq!(source.render(&mut buf), 16);
+    /// assert_eq!(buf.get_channel(0), buf.get_channel(1));
+    /// assert!(buf.get_channel(0).unwrap().iter().any(|s| s > 0.0));
+    /// ```
+    fn block(self) -> Block<Self>
+    where
+        Self: Sized,
+    {
+        Block::new(self)
+    }
 }
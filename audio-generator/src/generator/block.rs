@@ -0,0 +1,43 @@
+use crate::generator::Generator;
+use audio_core::{BlockSource, BufMut, ChannelMut};
+
+/// A block source constructed from a [Generator].
+///
+/// See [Generator::block].
+pub struct Block<G> {
+    generator: G,
+}
+
+impl<G> Block<G> {
+    pub(super) fn new(generator: G) -> Self {
+        Self { generator }
+    }
+}
+
+impl<G> BlockSource for Block<G>
+where
+    G: Generator,
+    G::Sample: Copy,
+{
+    type Sample = G::Sample;
+
+    fn render<B>(&mut self, out: &mut B) -> usize
+    where
+        B: BufMut<Sample = Self::Sample>,
+    {
+        let mut frames = 0;
+
+        if let Some(mut first) = out.get_channel_mut(0) {
+            for s in first.iter_mut() {
+                *s = self.generator.sample();
+                frames += 1;
+            }
+        }
+
+        for channel in 1..out.channels() {
+            out.copy_channel(0, channel);
+        }
+
+        frames
+    }
+}
@@ -1,6 +1,6 @@
 //! Utilities for working with audio buffers.
 
-use audio_core::{Buf, BufMut, Channel, ChannelMut, Translate};
+use audio_core::{Buf, BufMut, Translate};
 
 #[cfg(feature = "std")]
 pub mod dynamic;
@@ -39,9 +39,5 @@ where
     O::Sample: Translate<I::Sample>,
     I::Sample: Copy,
 {
-    for (mut to, from) in to.iter_channels_mut().zip(from.iter_channels()) {
-        for (t, f) in to.iter_mut().zip(from.iter()) {
-            *t = O::Sample::translate(f);
-        }
-    }
+    audio_core::copy(&mut to, &from);
 }
@@ -1,7 +1,7 @@
 //! A dynamically sized, multi-channel audio buffer.
 
 use crate::channel::{LinearChannel, LinearChannelMut};
-use audio_core::{Buf, BufMut, ExactSizeBuf, ResizableBuf, Sample};
+use audio_core::{Buf, BufMut, ExactSizeBuf, LayoutHint, ResizableBuf, Sample};
 use std::cmp;
 use std::fmt;
 use std::hash;
@@ -765,6 +765,11 @@ where
         (*self).channels()
     }
 
+    #[inline]
+    fn layout_hint(&self) -> LayoutHint {
+        LayoutHint::PerChannel
+    }
+
     #[inline]
     fn get_channel(&self, channel: usize) -> Option<Self::Channel<'_>> {
         (*self).get_channel(channel)
@@ -4,7 +4,8 @@ use std::hash;
 use std::ptr;
 
 use audio_core::{
-    Buf, BufMut, ExactSizeBuf, InterleavedBuf, InterleavedBufMut, ResizableBuf, Sample, UniformBuf,
+    Buf, BufMut, ExactSizeBuf, InterleavedBuf, InterleavedBufMut, LayoutHint, ResizableBuf,
+    ResizeError, Sample, UniformBuf,
 };
 
 use crate::buf::interleaved::{IterChannels, IterChannelsMut};
@@ -552,6 +553,44 @@ impl<T> Interleaved<T> {
         self.frames = frames;
     }
 
+    /// The internal fallible resize function for interleaved channel buffers.
+    ///
+    /// Memory is reserved up front, so that the buffer is left unchanged if
+    /// the reservation fails.
+    fn inner_try_resize(&mut self, channels: usize, frames: usize) -> Result<(), ResizeError>
+    where
+        T: Sample,
+    {
+        self.inner_try_reserve_cap(frames.saturating_mul(channels))?;
+        self.inner_resize(channels, frames);
+        Ok(())
+    }
+
+    /// Note: this is safe only because of the `T: Sample` bound. DO NOT REMOVE.
+    fn inner_try_reserve_cap(&mut self, new_cap: usize) -> Result<(), ResizeError>
+    where
+        T: Sample,
+    {
+        let old_cap = self.data.capacity();
+
+        if new_cap > old_cap {
+            let additional = new_cap - self.data.len();
+
+            if self.data.try_reserve(additional).is_err() {
+                return Err(ResizeError::new());
+            }
+
+            let new_cap = self.data.capacity();
+
+            // Safety: capacity is governed by the underlying vector.
+            unsafe {
+                ptr::write_bytes(self.data.as_mut_ptr().add(old_cap), 0, new_cap - old_cap);
+            }
+        }
+
+        Ok(())
+    }
+
     /// Note: this is safe only because of the `T: Sample` bound. DO NOT REMOVE.
     fn inner_reserve_cap(&mut self, new_cap: usize)
     where
@@ -757,6 +796,11 @@ where
         self.channels
     }
 
+    #[inline]
+    fn layout_hint(&self) -> LayoutHint {
+        LayoutHint::Interleaved
+    }
+
     #[inline]
     fn get_channel(&self, channel: usize) -> Option<Self::Channel<'_>> {
         InterleavedChannel::from_slice(&self.data, channel, self.channels)
@@ -816,6 +860,16 @@ where
     fn resize_topology(&mut self, channels: usize, frames: usize) {
         self.inner_resize(channels, frames);
     }
+
+    #[inline]
+    fn try_resize(&mut self, frames: usize) -> Result<(), ResizeError> {
+        self.inner_try_resize(self.channels, frames)
+    }
+
+    #[inline]
+    fn try_resize_topology(&mut self, channels: usize, frames: usize) -> Result<(), ResizeError> {
+        self.inner_try_resize(channels, frames)
+    }
 }
 
 impl<T> BufMut for Interleaved<T>
@@ -906,6 +960,14 @@ where
 
     #[inline]
     unsafe fn set_interleaved_topology(&mut self, channels: usize, frames: usize) {
+        debug_assert!(
+            channels.saturating_mul(frames) <= self.data.len(),
+            "topology {}x{} is larger than the initialized buffer {}",
+            channels,
+            frames,
+            self.data.len()
+        );
+
         self.channels = channels;
         self.frames = frames;
     }
@@ -4,7 +4,9 @@ use core::hash;
 use core::ops;
 use core::ptr;
 
-use audio_core::{Buf, BufMut, ExactSizeBuf, ResizableBuf, Sample, UniformBuf};
+use audio_core::{
+    Buf, BufMut, ExactSizeBuf, LayoutHint, ResizableBuf, ResizeError, Sample, UniformBuf,
+};
 
 use crate::buf::sequential::{IterChannels, IterChannelsMut};
 use crate::channel::{LinearChannel, LinearChannelMut};
@@ -583,6 +585,39 @@ impl<T> Sequential<T> {
         unsafe { RawSequential::new(&self.data, self.channels, self.frames) }
     }
 
+    /// Try to reserve and zero the capacity needed to resize the buffer to the
+    /// given topology, erroring if the reservation fails.
+    ///
+    /// Note: this is safe only because of the `T: Sample` bound. DO NOT REMOVE.
+    fn try_reserve_topology(&mut self, channels: usize, frames: usize) -> Result<(), ResizeError>
+    where
+        T: Sample,
+    {
+        // NB: resizing the topology is done in two steps, so reserve enough
+        // memory to cover the intermediate step as well.
+        let new_cap = usize::max(self.channels, channels).saturating_mul(frames);
+        let old_cap = self.data.capacity();
+
+        if new_cap > old_cap {
+            let additional = new_cap - self.data.len();
+
+            if self.data.try_reserve(additional).is_err() {
+                return Err(ResizeError::new());
+            }
+
+            // zero the additional capacity.
+            unsafe {
+                ptr::write_bytes(
+                    self.data.as_mut_ptr().add(old_cap),
+                    0,
+                    self.data.capacity() - old_cap,
+                );
+            }
+        }
+
+        Ok(())
+    }
+
     fn resize_inner(
         &mut self,
         from_channels: usize,
@@ -765,6 +800,11 @@ where
         (*self).channels()
     }
 
+    #[inline]
+    fn layout_hint(&self) -> LayoutHint {
+        LayoutHint::Sequential
+    }
+
     fn get_channel(&self, channel: usize) -> Option<Self::Channel<'_>> {
         (*self).get_channel(channel)
     }
@@ -820,6 +860,19 @@ where
         Self::resize_frames(self, frames);
         Self::resize_channels(self, channels);
     }
+
+    fn try_resize(&mut self, frames: usize) -> Result<(), ResizeError> {
+        self.try_reserve_topology(self.channels, frames)?;
+        Self::resize_frames(self, frames);
+        Ok(())
+    }
+
+    fn try_resize_topology(&mut self, channels: usize, frames: usize) -> Result<(), ResizeError> {
+        self.try_reserve_topology(channels, frames)?;
+        Self::resize_frames(self, frames);
+        Self::resize_channels(self, channels);
+        Ok(())
+    }
 }
 
 impl<T> BufMut for Sequential<T>
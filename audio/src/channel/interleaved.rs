@@ -5,6 +5,7 @@ use core::fmt;
 use core::marker;
 use core::mem;
 use core::ptr;
+use core::slice;
 
 use audio_core::{Channel, ChannelMut};
 
@@ -229,7 +230,13 @@ where
 
     #[inline]
     fn try_as_linear_mut(&mut self) -> Option<&mut [T]> {
-        None
+        if self.step != 1 {
+            return None;
+        }
+
+        // Safety: with a step of 1 the channel is a contiguous region of
+        // memory, and internal invariants ensure it's in bounds.
+        unsafe { Some(slice::from_raw_parts_mut(self.ptr.as_ptr(), len!(self))) }
     }
 }
 
@@ -364,7 +364,16 @@ macro_rules! interleaved_channel {
             }
 
             fn try_as_linear(&self) -> Option<&[T]> {
-                None
+                // NB: only a channel in a single-channel buffer is contiguous.
+                if self.step != 1 {
+                    return None;
+                }
+
+                // Safety: with a step of 1 the channel is a contiguous region of
+                // memory, and internal invariants ensure it's in bounds.
+                unsafe {
+                    Some(slice::from_raw_parts(self.ptr.as_ptr() as *const T, len!(self)))
+                }
             }
         }
     };
@@ -339,3 +339,26 @@ fn test_interleaved_channel_next_back() {
     assert_eq!(it.next_back(), Some(4));
     assert_eq!(it.next(), None);
 }
+
+#[test]
+fn test_interleaved_channel_try_as_linear() {
+    use crate::ChannelMut;
+
+    let buf: &[u32] = &[1, 2, 3, 4, 5, 6][..];
+
+    let c = InterleavedChannel::from_slice(buf, 0, 2).unwrap();
+    assert_eq!(c.try_as_linear(), None);
+
+    let c = InterleavedChannel::from_slice(buf, 0, 1).unwrap();
+    assert_eq!(c.try_as_linear(), Some(&[1, 2, 3, 4, 5, 6][..]));
+    assert_eq!(c.skip(1).limit(3).try_as_linear(), Some(&[2, 3, 4][..]));
+
+    let buf: &mut [u32] = &mut [1, 2, 3, 4][..];
+
+    let mut c = InterleavedChannelMut::from_slice(buf, 1, 2).unwrap();
+    assert_eq!(c.try_as_linear_mut(), None);
+
+    let mut c = InterleavedChannelMut::from_slice(buf, 0, 1).unwrap();
+    c.try_as_linear_mut().unwrap()[1] = 10;
+    assert_eq!(buf, &[1, 10, 3, 4]);
+}
@@ -12,9 +12,15 @@ use crate::slice::Slice;
 #[macro_use]
 mod macros;
 
+#[cfg(test)]
+mod tests;
+
 mod iter;
 pub use self::iter::{Iter, IterMut};
 
+mod chunks;
+pub use self::chunks::{ChunksExact, ChunksExactMut};
+
 slice_comparisons!({'a, T, const N: usize}, LinearChannel<'a, T>, [T; N]);
 slice_comparisons!({'a, T}, LinearChannel<'a, T>, [T]);
 slice_comparisons!({'a, T}, LinearChannel<'a, T>, &[T]);
@@ -80,6 +86,83 @@ impl<'a, T> LinearChannel<'a, T> {
     pub fn into_ref(self) -> &'a [T] {
         self.buf
     }
+
+    /// Divide the channel into two at the frame `mid`.
+    ///
+    /// The first channel contains the frames `[0, mid)` and the second the
+    /// frames `[mid, len)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid > len`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio::channel::LinearChannel;
+    ///
+    /// let channel = LinearChannel::new(&[1, 2, 3, 4]);
+    /// let (head, tail) = channel.split_at(1);
+    ///
+    /// assert_eq!(head, [1]);
+    /// assert_eq!(tail, [2, 3, 4]);
+    /// ```
+    #[inline]
+    pub fn split_at(self, mid: usize) -> (Self, Self) {
+        let (head, tail) = self.buf.split_at(mid);
+        (Self { buf: head }, Self { buf: tail })
+    }
+
+    /// Get a sub-channel covering the given range of frames, or `None` if the
+    /// range is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio::channel::LinearChannel;
+    ///
+    /// let channel = LinearChannel::new(&[1, 2, 3, 4]);
+    ///
+    /// assert_eq!(channel.slice(1..3).unwrap(), [2, 3]);
+    /// assert_eq!(channel.slice(2..).unwrap(), [3, 4]);
+    /// assert!(channel.slice(..5).is_none());
+    /// ```
+    #[inline]
+    pub fn slice<I>(self, range: I) -> Option<Self>
+    where
+        I: slice::SliceIndex<[T], Output = [T]>,
+    {
+        Some(Self {
+            buf: self.buf.get(range)?,
+        })
+    }
+
+    /// Construct an iterator over the channel in chunks of `size` frames.
+    ///
+    /// Frames which don't fit into a whole chunk are omitted, and can be
+    /// accessed through [ChunksExact::remainder].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio::channel::LinearChannel;
+    ///
+    /// let channel = LinearChannel::new(&[1, 2, 3, 4, 5]);
+    /// let mut it = channel.chunks_exact(2);
+    ///
+    /// assert_eq!(it.next().unwrap(), [1, 2]);
+    /// assert_eq!(it.next().unwrap(), [3, 4]);
+    /// assert!(it.next().is_none());
+    /// assert_eq!(it.remainder(), [5]);
+    /// ```
+    #[inline]
+    pub fn chunks_exact(self, size: usize) -> ChunksExact<'a, T> {
+        ChunksExact::new(self.buf, size)
+    }
 }
 
 impl<T> Channel for LinearChannel<'_, T>
@@ -233,6 +316,89 @@ impl<'a, T> LinearChannelMut<'a, T> {
     pub fn into_mut(self) -> &'a mut [T] {
         self.buf
     }
+
+    /// Divide the channel into two at the frame `mid`.
+    ///
+    /// The first channel contains the frames `[0, mid)` and the second the
+    /// frames `[mid, len)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid > len`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio::ChannelMut;
+    /// use audio::channel::LinearChannelMut;
+    ///
+    /// let mut buf = [0, 0, 0, 0];
+    /// let (mut head, mut tail) = LinearChannelMut::new(&mut buf).split_at(1);
+    ///
+    /// head.fill(1);
+    /// tail.fill(2);
+    ///
+    /// assert_eq!(buf, [1, 2, 2, 2]);
+    /// ```
+    #[inline]
+    pub fn split_at(self, mid: usize) -> (Self, Self) {
+        let (head, tail) = self.buf.split_at_mut(mid);
+        (Self { buf: head }, Self { buf: tail })
+    }
+
+    /// Get a mutable sub-channel covering the given range of frames, or
+    /// `None` if the range is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio::ChannelMut;
+    /// use audio::channel::LinearChannelMut;
+    ///
+    /// let mut buf = [0, 0, 0, 0];
+    /// LinearChannelMut::new(&mut buf).slice(1..3).unwrap().fill(1);
+    ///
+    /// assert_eq!(buf, [0, 1, 1, 0]);
+    /// assert!(LinearChannelMut::new(&mut buf).slice(..5).is_none());
+    /// ```
+    #[inline]
+    pub fn slice<I>(self, range: I) -> Option<Self>
+    where
+        I: slice::SliceIndex<[T], Output = [T]>,
+    {
+        Some(Self {
+            buf: self.buf.get_mut(range)?,
+        })
+    }
+
+    /// Construct a mutable iterator over the channel in chunks of `size`
+    /// frames.
+    ///
+    /// Frames which don't fit into a whole chunk are omitted, and can be
+    /// accessed through [ChunksExactMut::into_remainder].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio::ChannelMut;
+    /// use audio::channel::LinearChannelMut;
+    ///
+    /// let mut buf = [0, 0, 0, 0, 0];
+    ///
+    /// for (n, mut chunk) in LinearChannelMut::new(&mut buf).chunks_exact(2).enumerate() {
+    ///     chunk.fill(n + 1);
+    /// }
+    ///
+    /// assert_eq!(buf, [1, 1, 2, 2, 0]);
+    /// ```
+    #[inline]
+    pub fn chunks_exact(self, size: usize) -> ChunksExactMut<'a, T> {
+        ChunksExactMut::new(self.buf, size)
+    }
 }
 
 impl<T> audio_core::LinearChannel for LinearChannel<'_, T>
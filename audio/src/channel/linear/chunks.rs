@@ -0,0 +1,117 @@
+use core::slice;
+
+use super::{LinearChannel, LinearChannelMut};
+
+/// An iterator over a linear channel in non-overlapping chunks of a fixed
+/// number of frames.
+///
+/// Created with [LinearChannel::chunks_exact].
+pub struct ChunksExact<'a, T> {
+    iter: slice::ChunksExact<'a, T>,
+}
+
+impl<'a, T> ChunksExact<'a, T> {
+    #[inline]
+    pub(super) fn new(data: &'a [T], size: usize) -> Self {
+        Self {
+            iter: data.chunks_exact(size),
+        }
+    }
+
+    /// Get the frames which were left over since they didn't fit into a whole
+    /// chunk.
+    #[inline]
+    pub fn remainder(&self) -> LinearChannel<'a, T> {
+        LinearChannel::new(self.iter.remainder())
+    }
+}
+
+impl<'a, T> Iterator for ChunksExact<'a, T> {
+    type Item = LinearChannel<'a, T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(LinearChannel::new(self.iter.next()?))
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        Some(LinearChannel::new(self.iter.nth(n)?))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for ChunksExact<'_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        Some(LinearChannel::new(self.iter.next_back()?))
+    }
+}
+
+impl<T> ExactSizeIterator for ChunksExact<'_, T> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+/// A mutable iterator over a linear channel in non-overlapping chunks of a
+/// fixed number of frames.
+///
+/// Created with [LinearChannelMut::chunks_exact].
+pub struct ChunksExactMut<'a, T> {
+    iter: slice::ChunksExactMut<'a, T>,
+}
+
+impl<'a, T> ChunksExactMut<'a, T> {
+    #[inline]
+    pub(super) fn new(data: &'a mut [T], size: usize) -> Self {
+        Self {
+            iter: data.chunks_exact_mut(size),
+        }
+    }
+
+    /// Get the frames which were left over since they didn't fit into a whole
+    /// chunk.
+    #[inline]
+    pub fn into_remainder(self) -> LinearChannelMut<'a, T> {
+        LinearChannelMut::new(self.iter.into_remainder())
+    }
+}
+
+impl<'a, T> Iterator for ChunksExactMut<'a, T> {
+    type Item = LinearChannelMut<'a, T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(LinearChannelMut::new(self.iter.next()?))
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        Some(LinearChannelMut::new(self.iter.nth(n)?))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for ChunksExactMut<'_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        Some(LinearChannelMut::new(self.iter.next_back()?))
+    }
+}
+
+impl<T> ExactSizeIterator for ChunksExactMut<'_, T> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
@@ -0,0 +1,108 @@
+use super::{LinearChannel, LinearChannelMut};
+use crate::{Channel, ChannelMut};
+
+#[test]
+fn test_split_at() {
+    let channel = LinearChannel::new(&[1, 2, 3, 4]);
+
+    let (head, tail) = channel.split_at(0);
+    assert!(head.is_empty());
+    assert_eq!(tail, [1, 2, 3, 4]);
+
+    let (head, tail) = channel.split_at(4);
+    assert_eq!(head, [1, 2, 3, 4]);
+    assert!(tail.is_empty());
+
+    // NB: the halves are channels, so they compose with the rest of the
+    // channel API.
+    let (head, tail) = channel.split_at(2);
+    assert_eq!(head.skip(1), [2]);
+    assert_eq!(tail.limit(1), [3]);
+    assert_eq!(tail.iter().collect::<Vec<_>>(), [3, 4]);
+}
+
+#[test]
+#[should_panic]
+fn test_split_at_out_of_bounds() {
+    LinearChannel::new(&[1, 2, 3, 4]).split_at(5);
+}
+
+#[test]
+fn test_split_at_mut() {
+    let mut buf = [0; 6];
+    let (mut head, tail) = LinearChannelMut::new(&mut buf).split_at(2);
+    let (mut middle, mut tail) = tail.split_at(3);
+
+    head.fill(1);
+    middle.fill(2);
+    *tail.get_mut(0).unwrap() = 3;
+
+    assert_eq!(buf, [1, 1, 2, 2, 2, 3]);
+}
+
+#[test]
+fn test_slice() {
+    let channel = LinearChannel::new(&[1, 2, 3, 4]);
+
+    assert_eq!(channel.slice(..).unwrap(), [1, 2, 3, 4]);
+    assert_eq!(channel.slice(1..=2).unwrap(), [2, 3]);
+    assert!(channel.slice(4..).unwrap().is_empty());
+    assert!(channel.slice(5..).is_none());
+    assert!(channel.slice(..=4).is_none());
+
+    // NB: slicing a slice is relative to it.
+    assert_eq!(channel.slice(1..).unwrap().slice(1..2).unwrap(), [3]);
+}
+
+#[test]
+fn test_slice_mut() {
+    let mut buf = [0; 4];
+    LinearChannelMut::new(&mut buf).slice(2..).unwrap().fill(1);
+    assert_eq!(buf, [0, 0, 1, 1]);
+    assert!(LinearChannelMut::new(&mut buf).slice(..=4).is_none());
+}
+
+#[test]
+fn test_chunks_exact() {
+    let channel = LinearChannel::new(&[1, 2, 3, 4, 5, 6, 7]);
+
+    let mut it = channel.chunks_exact(3);
+    assert_eq!(it.len(), 2);
+    assert_eq!(it.next().unwrap(), [1, 2, 3]);
+    assert_eq!(it.len(), 1);
+    assert_eq!(it.next().unwrap(), [4, 5, 6]);
+    assert!(it.next().is_none());
+    assert_eq!(it.remainder(), [7]);
+
+    let mut it = channel.chunks_exact(2);
+    assert_eq!(it.next_back().unwrap(), [5, 6]);
+    assert_eq!(it.nth(1).unwrap(), [3, 4]);
+    assert!(it.next().is_none());
+    assert_eq!(it.remainder(), [7]);
+
+    let mut it = channel.chunks_exact(8);
+    assert!(it.next().is_none());
+    assert_eq!(it.remainder(), [1, 2, 3, 4, 5, 6, 7]);
+}
+
+#[test]
+#[should_panic]
+fn test_chunks_exact_zero() {
+    LinearChannel::new(&[1, 2, 3, 4]).chunks_exact(0);
+}
+
+#[test]
+fn test_chunks_exact_mut() {
+    let from = LinearChannel::new(&[1, 2, 3, 4, 5]);
+    let mut buf = [0; 5];
+
+    let mut it = LinearChannelMut::new(&mut buf).chunks_exact(2);
+
+    // NB: chunks are channels, so they can be copied between.
+    for (to, from) in (&mut it).zip(from.chunks_exact(2).rev()) {
+        crate::channel::copy(from, to);
+    }
+
+    it.into_remainder().fill(9);
+    assert_eq!(buf, [3, 4, 1, 2, 9]);
+}
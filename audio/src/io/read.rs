@@ -1,4 +1,4 @@
-use audio_core::{Buf, BufMut, Channel, ExactSizeBuf, ReadBuf};
+use audio_core::{Buf, BufMut, Channel, ExactSizeBuf, LayoutHint, ReadBuf};
 
 /// Make a buffer into a read adapter that implements [ReadBuf].
 ///
@@ -250,6 +250,11 @@ where
         self.buf.channels()
     }
 
+    #[inline]
+    fn layout_hint(&self) -> LayoutHint {
+        self.buf.layout_hint()
+    }
+
     #[inline]
     fn get_channel(&self, channel: usize) -> Option<Self::Channel<'_>> {
         Some(self.buf.get_channel(channel)?.tail(self.available))
@@ -1,4 +1,4 @@
-use audio_core::{Buf, BufMut, Channel, ExactSizeBuf, ReadBuf, WriteBuf};
+use audio_core::{Buf, BufMut, Channel, ExactSizeBuf, LayoutHint, ReadBuf, WriteBuf};
 
 /// Make any mutable buffer into a write adapter that implements
 /// [ReadBuf] and [WriteBuf].
@@ -324,6 +324,11 @@ where
         self.buf.channels()
     }
 
+    #[inline]
+    fn layout_hint(&self) -> LayoutHint {
+        self.buf.layout_hint()
+    }
+
     #[inline]
     fn get_channel(&self, channel: usize) -> Option<Self::Channel<'_>> {
         let channel = self.buf.get_channel(channel)?;
@@ -1,4 +1,4 @@
-use audio_core::{Buf, BufMut, Channel, ExactSizeBuf, WriteBuf};
+use audio_core::{Buf, BufMut, Channel, ExactSizeBuf, LayoutHint, WriteBuf};
 
 /// Make a mutable buffer into a write adapter that implements [WriteBuf].
 ///
@@ -257,6 +257,11 @@ where
         self.buf.channels()
     }
 
+    #[inline]
+    fn layout_hint(&self) -> LayoutHint {
+        self.buf.layout_hint()
+    }
+
     #[inline]
     fn get_channel(&self, channel: usize) -> Option<Self::Channel<'_>> {
         Some(self.buf.get_channel(channel)?.tail(self.available))
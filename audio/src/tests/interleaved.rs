@@ -175,3 +175,15 @@ fn test_as_interleaved_mut_ptr() {
         &[1, 1, 1, 1, 1, 1, 1, 1],
     };
 }
+
+#[test]
+fn test_try_resize_topology() {
+    let mut buf = crate::interleaved![[1.0f32; 4], [2.0f32; 4]];
+
+    assert!(buf.try_resize_topology(4, usize::MAX).is_err());
+    assert_eq!(buf, crate::interleaved![[1.0f32; 4], [2.0f32; 4]]);
+
+    assert!(buf.try_resize_topology(3, 8).is_ok());
+    assert_eq!(buf.channels(), 3);
+    assert_eq!(buf.frames(), 8);
+}
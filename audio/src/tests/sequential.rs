@@ -164,3 +164,17 @@ fn test_stale_allocation() {
 fn test_from_array() {
     let _ = crate::dynamic![[0.0; 128]; 2];
 }
+
+#[test]
+fn test_try_resize_topology() {
+    use audio_core::ResizableBuf;
+
+    let mut buf = crate::sequential![[1.0f32; 4], [2.0f32; 4]];
+
+    assert!(buf.try_resize_topology(4, usize::MAX).is_err());
+    assert_eq!(buf, crate::sequential![[1.0f32; 4], [2.0f32; 4]]);
+
+    assert!(buf.try_resize_topology(3, 8).is_ok());
+    assert_eq!(buf.channels(), 3);
+    assert_eq!(buf.frames(), 8);
+}
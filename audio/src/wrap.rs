@@ -4,6 +4,10 @@
 //! buffer. They may also be useful for incrementally introducing this crate
 //! into a codebase that uses a different buffer format.
 
+use core::mem;
+use core::ptr;
+use core::slice;
+
 use crate::slice::Slice;
 
 mod interleaved;
@@ -72,6 +76,104 @@ where
     Interleaved::new(value, channels)
 }
 
+/// Wrap raw parts as an interleaved buffer with the given number of channels.
+///
+/// This is intended for code which receives raw interleaved buffers, such as
+/// audio device backends. See [interleaved_from_raw_parts_mut] for a mutable
+/// variant.
+///
+/// # Panics
+///
+/// Panics if `len` is not a multiple of `channels`, or if `channels` is zero.
+///
+/// # Safety
+///
+/// The caller must ensure that `ptr` is valid for reads of `len` initialized
+/// samples for the lifetime `'a`, as with [slice::from_raw_parts].
+///
+/// [slice::from_raw_parts]: core::slice::from_raw_parts
+///
+/// # Examples
+///
+/// ```
+/// use std::ptr;
+///
+/// use audio::{Buf, Channel};
+///
+/// let data = [1, 5, 2, 6, 3, 7, 4, 8];
+/// let ptr = ptr::NonNull::from(&data).cast::<i32>();
+///
+/// let buf = unsafe { audio::wrap::interleaved_from_raw_parts(ptr, data.len(), 2) };
+///
+/// assert!(buf.get_channel(0).unwrap().iter().eq([1, 2, 3, 4]));
+/// assert!(buf.get_channel(1).unwrap().iter().eq([5, 6, 7, 8]));
+/// ```
+pub unsafe fn interleaved_from_raw_parts<'a, T>(
+    ptr: ptr::NonNull<T>,
+    len: usize,
+    channels: usize,
+) -> Interleaved<&'a [T]>
+where
+    T: Copy,
+{
+    debug_assert!(
+        ptr.as_ptr() as usize % mem::align_of::<T>() == 0,
+        "pointer is not aligned"
+    );
+
+    Interleaved::new(slice::from_raw_parts(ptr.as_ptr(), len), channels)
+}
+
+/// Wrap raw parts as a mutable interleaved buffer with the given number of
+/// channels.
+///
+/// The constructed buffer implements [WriteBuf][crate::WriteBuf], which makes
+/// it convenient for filling buffers provided by audio devices.
+///
+/// # Panics
+///
+/// Panics if `len` is not a multiple of `channels`, or if `channels` is zero.
+///
+/// # Safety
+///
+/// The caller must ensure that `ptr` is valid for reads and writes of `len`
+/// initialized samples for the lifetime `'a`, and that it is not accessed
+/// through any other pointer during that lifetime, as with
+/// [slice::from_raw_parts_mut].
+///
+/// [slice::from_raw_parts_mut]: core::slice::from_raw_parts_mut
+///
+/// # Examples
+///
+/// ```
+/// use std::ptr;
+///
+/// use audio::io;
+///
+/// let mut data = [0; 8];
+/// let ptr = ptr::NonNull::from(&mut data).cast::<i32>();
+///
+/// let buf = unsafe { audio::wrap::interleaved_from_raw_parts_mut(ptr, 8, 2) };
+/// io::copy_remaining(io::Read::new(audio::sequential![[1, 2, 3, 4], [5, 6, 7, 8]]), buf);
+///
+/// assert_eq!(data, [1, 5, 2, 6, 3, 7, 4, 8]);
+/// ```
+pub unsafe fn interleaved_from_raw_parts_mut<'a, T>(
+    ptr: ptr::NonNull<T>,
+    len: usize,
+    channels: usize,
+) -> Interleaved<&'a mut [T]>
+where
+    T: Copy,
+{
+    debug_assert!(
+        ptr.as_ptr() as usize % mem::align_of::<T>() == 0,
+        "pointer is not aligned"
+    );
+
+    Interleaved::new(slice::from_raw_parts_mut(ptr.as_ptr(), len), channels)
+}
+
 /// Wrap a slice as a sequential buffer with the given number of frames. The
 /// length of the buffer determines the number of channels it has.
 ///
@@ -1,5 +1,5 @@
 use crate::channel::{LinearChannel, LinearChannelMut};
-use audio_core::{Buf, BufMut, ResizableBuf, Sample};
+use audio_core::{Buf, BufMut, LayoutHint, ResizableBuf, ResizeError, Sample};
 
 /// A wrapper for an external dynamic audio buffer.
 ///
@@ -80,6 +80,11 @@ macro_rules! impl_buf {
                 self.value.len()
             }
 
+            #[inline]
+            fn layout_hint(&self) -> LayoutHint {
+                LayoutHint::PerChannel
+            }
+
             #[inline]
             fn get_channel(&self, channel: usize) -> Option<Self::Channel<'_>> {
                 Some(LinearChannel::new(self.value.get(channel)?))
@@ -191,6 +196,43 @@ where
             self.value.push(vec![T::ZERO; frames]);
         }
     }
+
+    fn try_resize(&mut self, frames: usize) -> Result<(), ResizeError> {
+        for buf in self.value.iter_mut() {
+            if let Some(additional) = frames.checked_sub(buf.len()) {
+                buf.try_reserve(additional)
+                    .map_err(|_| ResizeError::new())?;
+            }
+        }
+
+        self.resize_frames(frames);
+        Ok(())
+    }
+
+    fn try_resize_topology(&mut self, channels: usize, frames: usize) -> Result<(), ResizeError> {
+        let additional = channels.saturating_sub(self.value.len());
+
+        self.value
+            .try_reserve(additional)
+            .map_err(|_| ResizeError::new())?;
+
+        let mut added = Vec::new();
+        added
+            .try_reserve_exact(additional)
+            .map_err(|_| ResizeError::new())?;
+
+        for _ in 0..additional {
+            let mut buf = Vec::new();
+            buf.try_reserve_exact(frames)
+                .map_err(|_| ResizeError::new())?;
+            buf.resize(frames, T::ZERO);
+            added.push(buf);
+        }
+
+        self.try_resize(frames)?;
+        self.value.extend(added);
+        Ok(())
+    }
 }
 
 /// An iterator over a linear channel slice buffer.
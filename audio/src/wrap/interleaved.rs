@@ -2,8 +2,8 @@ use core::mem;
 use core::ptr;
 
 use audio_core::{
-    Buf, BufMut, ExactSizeBuf, InterleavedBuf, InterleavedBufMut, ReadBuf, ResizableBuf,
-    UniformBuf, WriteBuf,
+    Buf, BufMut, ExactSizeBuf, InterleavedBuf, InterleavedBufMut, LayoutHint, ReadBuf,
+    ResizableBuf, ResizeError, UniformBuf, WriteBuf,
 };
 
 use crate::buf::interleaved::{IterChannels, IterChannelsMut};
@@ -119,6 +119,11 @@ where
         self.channels
     }
 
+    #[inline]
+    fn layout_hint(&self) -> LayoutHint {
+        LayoutHint::Interleaved
+    }
+
     #[inline]
     fn get_channel(&self, channel: usize) -> Option<Self::Channel<'_>> {
         InterleavedChannel::from_slice(self.value.as_ref(), channel, self.channels)
@@ -196,6 +201,14 @@ where
 
     #[inline]
     unsafe fn set_interleaved_topology(&mut self, channels: usize, frames: usize) {
+        debug_assert!(
+            channels.saturating_mul(frames) <= self.value.as_ref().len(),
+            "topology {}x{} is larger than the wrapped buffer {}",
+            channels,
+            frames,
+            self.value.as_ref().len()
+        );
+
         self.channels = channels;
         self.frames = frames;
     }
@@ -321,4 +334,82 @@ where
         self.channels = channels;
         self.frames = frames;
     }
+
+    fn try_resize(&mut self, frames: usize) -> Result<(), ResizeError> {
+        self.try_resize_topology(self.channels, frames)
+    }
+
+    fn try_resize_topology(&mut self, channels: usize, frames: usize) -> Result<(), ResizeError> {
+        if channels.saturating_mul(frames) > self.value.len() {
+            return Err(ResizeError::new());
+        }
+
+        self.resize_topology(channels, frames);
+        Ok(())
+    }
+}
+
+impl<'a, T> IntoIterator for Interleaved<&'a [T]>
+where
+    T: Copy,
+{
+    type IntoIter = IterChannels<'a, T>;
+    type Item = <Self::IntoIter as Iterator>::Item;
+
+    /// Convert the wrapper into an iterator over its channels, which borrows
+    /// the wrapped slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let buf = audio::wrap::interleaved(&[1, 2, 3, 4][..], 2);
+    /// let mut it = buf.into_iter();
+    ///
+    /// assert_eq!(it.next().unwrap(), [1, 3]);
+    /// assert_eq!(it.next().unwrap(), [2, 4]);
+    /// ```
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        // SAFETY: construction of the wrapper ensures the slice is a valid
+        // interleaved region, which outlives the iterator.
+        unsafe {
+            IterChannels::new_unchecked(Slice::as_ptr(&self.value), self.value.len(), self.channels)
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for Interleaved<&'a mut [T]>
+where
+    T: Copy,
+{
+    type IntoIter = IterChannelsMut<'a, T>;
+    type Item = <Self::IntoIter as Iterator>::Item;
+
+    /// Convert the wrapper into a mutable iterator over its channels, which
+    /// borrows the wrapped slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audio::ChannelMut;
+    ///
+    /// let mut data = [1, 2, 3, 4];
+    ///
+    /// for mut channel in audio::wrap::interleaved(&mut data[..], 2) {
+    ///     for sample in channel.iter_mut() {
+    ///         *sample *= 10;
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(data, [10, 20, 30, 40]);
+    /// ```
+    #[inline]
+    fn into_iter(mut self) -> Self::IntoIter {
+        // SAFETY: construction of the wrapper ensures the slice is a valid
+        // interleaved region, which is exclusively borrowed by the iterator.
+        unsafe {
+            let ptr = SliceMut::as_mut_ptr(&mut self.value);
+            IterChannelsMut::new_unchecked(ptr, self.value.len(), self.channels)
+        }
+    }
 }
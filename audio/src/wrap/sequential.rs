@@ -1,4 +1,4 @@
-use audio_core::{Buf, BufMut, ExactSizeBuf, UniformBuf};
+use audio_core::{Buf, BufMut, ExactSizeBuf, LayoutHint, UniformBuf};
 
 use crate::buf::sequential::{IterChannels, IterChannelsMut};
 use crate::channel::{LinearChannel, LinearChannelMut};
@@ -112,6 +112,11 @@ where
         self.channels
     }
 
+    #[inline]
+    fn layout_hint(&self) -> LayoutHint {
+        LayoutHint::Sequential
+    }
+
     fn get_channel(&self, channel: usize) -> Option<Self::Channel<'_>> {
         let value = self
             .value
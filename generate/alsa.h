@@ -1 +1,2 @@
 #include <alsa/asoundlib.h>
+#include <alsa/use-case.h>
@@ -0,0 +1,3 @@
+#include <AudioToolbox/AudioQueue.h>
+#include <CoreAudio/AudioHardware.h>
+#include <CoreFoundation/CFString.h>
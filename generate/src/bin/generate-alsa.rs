@@ -38,6 +38,7 @@ fn generate_bindings(lib: &pkg_config::Library) -> anyhow::Result<()> {
         .allowlist_function("snd_.*")
         .allowlist_type("_?snd_.*")
         .allowlist_type(".*va_list.*")
+        .allowlist_var("SND_CHMAP_.*")
         .with_codegen_config(config)
         .clang_args(include_args)
         .header(root.join("alsa.h").display().to_string());
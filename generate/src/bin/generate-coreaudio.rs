@@ -0,0 +1,77 @@
+use anyhow::{anyhow, bail};
+use std::env;
+use std::path::PathBuf;
+use std::process::Command;
+
+fn main() -> anyhow::Result<()> {
+    let sdk = sdk_path()?;
+    generate_bindings(&sdk)?;
+    Ok(())
+}
+
+/// Find the path to the macOS SDK which contains the framework headers.
+fn sdk_path() -> anyhow::Result<String> {
+    let output = Command::new("xcrun")
+        .args(["--sdk", "macosx", "--show-sdk-path"])
+        .output()?;
+
+    if !output.status.success() {
+        bail!("xcrun failed: {}", output.status);
+    }
+
+    Ok(String::from_utf8(output.stdout)?.trim().to_owned())
+}
+
+fn generate_bindings(sdk: &str) -> anyhow::Result<()> {
+    let mut config = bindgen::CodegenConfig::empty();
+    config.insert(bindgen::CodegenConfig::FUNCTIONS);
+    config.insert(bindgen::CodegenConfig::TYPES);
+    config.insert(bindgen::CodegenConfig::VARS);
+
+    let root = PathBuf::from(env::var("CARGO_MANIFEST_DIR")?);
+    let output = root
+        .join("..")
+        .join("audio-device-coreaudio-sys")
+        .join("src")
+        .join("bindings.rs");
+
+    let builder = bindgen::Builder::default()
+        .size_t_is_usize(true)
+        .allowlist_recursively(false)
+        .prepend_enum_name(false)
+        .layout_tests(false)
+        .allowlist_function("AudioQueue.*")
+        .allowlist_type("AudioQueue.*")
+        .allowlist_type("AudioStream.*")
+        .allowlist_type("AudioTimeStamp")
+        .allowlist_type("AudioFormat(ID|Flags)")
+        .allowlist_type("SMPTETime")
+        .allowlist_type("OpaqueAudioQueue.*")
+        .allowlist_type("(OSStatus|Boolean|Float64)")
+        .allowlist_type("(__)?CF(RunLoop|String)(Ref)?")
+        .allowlist_type("CF(Index|TypeRef|StringEncoding)")
+        .allowlist_type("Audio(Object|Device)ID")
+        .allowlist_type("AudioObjectProperty(Address|Selector|Scope|Element)")
+        .allowlist_type("AudioValueRange|AudioBuffer(List)?")
+        .allowlist_function("AudioObjectGetPropertyData(Size)?")
+        .allowlist_function("CF(StringGetCString|Release)")
+        .allowlist_var("kAudio(Format|TimeStamp|QueueParam_|QueueErr_).*")
+        .allowlist_var(
+            "kAudioObject(SystemObject|PropertyName|PropertyScope.*|PropertyElementMain)",
+        )
+        .allowlist_var("kAudioHardware(PropertyDevices|PropertyDefault.*Device|Bad.*Error)")
+        .allowlist_var(
+            "kAudioDeviceProperty(DeviceUID|StreamConfiguration|AvailableNominalSampleRates)",
+        )
+        .allowlist_var("kCFStringEncodingUTF8")
+        .with_codegen_config(config)
+        .clang_arg(format!("-isysroot{}", sdk))
+        .header(root.join("coreaudio.h").display().to_string());
+
+    let bindings = builder
+        .generate()
+        .map_err(|()| anyhow!("Unable to generate bindings"))?;
+
+    bindings.write_to_file(output)?;
+    Ok(())
+}